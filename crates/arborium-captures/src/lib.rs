@@ -1,6 +1,6 @@
 //! Highlight category definitions - single source of truth.
 //!
-//! This module defines all highlight categories used for syntax highlighting.
+//! This crate defines all highlight categories used for syntax highlighting.
 //! It maps the large vocabulary of capture names from various sources (nvim-treesitter,
 //! helix, etc.) to a small set of theme slots.
 //!
@@ -21,6 +21,20 @@
 //! - `conditional`, `keyword.conditional`, `repeat` → all use the `keyword` slot
 //!
 //! Adjacent spans that map to the same slot are coalesced into a single HTML element.
+//!
+//! The crate is `no_std` and has no dependencies, so the wire protocol,
+//! plugins, themes and external hosts can all share this vocabulary without
+//! pulling in a highlighter. `arborium-theme` re-exports it as
+//! `arborium_theme::highlights`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
 
 /// The theme slots - the fixed set of color categories that themes define.
 /// This is the final destination for all capture names.
@@ -59,6 +73,24 @@ pub enum ThemeSlot {
     DiffAdd,
     /// Diff deletions
     DiffDelete,
+    /// Changed words inside an added line
+    DiffAddInline,
+    /// Changed words inside a removed line
+    DiffDeleteInline,
+    /// Values that look like credentials (tokens, passwords, keys)
+    Secret,
+    /// Tags in comments: TODO, FIXME, NOTE, SAFETY
+    CommentTag,
+    /// Visible whitespace markers
+    Whitespace,
+    /// Ranges overlaid on the syntax highlighting, such as search matches
+    Match,
+    /// Gutter annotations: who last changed a line
+    GutterAuthor,
+    /// Gutter annotations: when a line was last changed
+    GutterAge,
+    /// Gutter annotations: any other per-line label
+    GutterLabel,
     /// Embedded content
     Embedded,
     /// Errors
@@ -105,6 +137,21 @@ impl ThemeSlot {
             ThemeSlot::DiffAdd => Some("da"),
             // Diff deletions
             ThemeSlot::DiffDelete => Some("dd"),
+            // Changed words inside changed lines
+            ThemeSlot::DiffAddInline => Some("dai"),
+            ThemeSlot::DiffDeleteInline => Some("ddi"),
+            // Credentials
+            ThemeSlot::Secret => Some("sk"),
+            // Comment tags
+            ThemeSlot::CommentTag => Some("ct"),
+            // Whitespace markers
+            ThemeSlot::Whitespace => Some("ws"),
+            // Overlaid ranges
+            ThemeSlot::Match => Some("mh"),
+            // Gutter annotations
+            ThemeSlot::GutterAuthor => Some("ga"),
+            ThemeSlot::GutterAge => Some("gd"),
+            ThemeSlot::GutterLabel => Some("gl"),
             // Embedded content
             ThemeSlot::Embedded => Some("eb"),
             // Errors
@@ -143,6 +190,15 @@ impl ThemeSlot {
             ThemeSlot::Strikethrough => Some("strikethrough"),
             ThemeSlot::DiffAdd => Some("diff-add"),
             ThemeSlot::DiffDelete => Some("diff-delete"),
+            ThemeSlot::DiffAddInline => Some("diff-add-inline"),
+            ThemeSlot::DiffDeleteInline => Some("diff-delete-inline"),
+            ThemeSlot::Secret => Some("secret"),
+            ThemeSlot::CommentTag => Some("comment-tag"),
+            ThemeSlot::Whitespace => Some("whitespace"),
+            ThemeSlot::Match => Some("match"),
+            ThemeSlot::GutterAuthor => Some("gutter-author"),
+            ThemeSlot::GutterAge => Some("gutter-age"),
+            ThemeSlot::GutterLabel => Some("gutter-label"),
             ThemeSlot::Embedded => Some("embedded"),
             ThemeSlot::Error => Some("error"),
             ThemeSlot::None => None,
@@ -191,6 +247,21 @@ pub fn slot_to_highlight_index(slot: ThemeSlot) -> Option<usize> {
             .position(|h| h.name == "text.strikethrough"),
         ThemeSlot::DiffAdd => HIGHLIGHTS.iter().position(|h| h.name == "diff.addition"),
         ThemeSlot::DiffDelete => HIGHLIGHTS.iter().position(|h| h.name == "diff.deletion"),
+        ThemeSlot::DiffAddInline => HIGHLIGHTS
+            .iter()
+            .position(|h| h.name == "diff.addition.inline"),
+        ThemeSlot::DiffDeleteInline => HIGHLIGHTS
+            .iter()
+            .position(|h| h.name == "diff.deletion.inline"),
+        ThemeSlot::Secret => HIGHLIGHTS
+            .iter()
+            .position(|h| h.name == "string.special.secret"),
+        ThemeSlot::CommentTag => HIGHLIGHTS.iter().position(|h| h.name == "comment.todo"),
+        ThemeSlot::Whitespace => HIGHLIGHTS.iter().position(|h| h.name == "whitespace"),
+        ThemeSlot::Match => HIGHLIGHTS.iter().position(|h| h.name == "markup.highlight"),
+        ThemeSlot::GutterAuthor => HIGHLIGHTS.iter().position(|h| h.name == "gutter.author"),
+        ThemeSlot::GutterAge => HIGHLIGHTS.iter().position(|h| h.name == "gutter.age"),
+        ThemeSlot::GutterLabel => HIGHLIGHTS.iter().position(|h| h.name == "gutter.label"),
         ThemeSlot::Embedded => HIGHLIGHTS.iter().position(|h| h.name == "embedded"),
         ThemeSlot::Error => HIGHLIGHTS.iter().position(|h| h.name == "error"),
         ThemeSlot::None => None,
@@ -226,17 +297,35 @@ pub fn capture_to_slot(capture: &str) -> ThemeSlot {
         "function" | "function.builtin" | "function.method" | "function.definition"
         | "function.call" | "function.special" | "method" | "method.call" => ThemeSlot::Function,
 
+        // Credentials, before the other strings so themes can flag or blur them
+        "string.special.secret" => ThemeSlot::Secret,
+
         // Strings
         "string" | "string.special" | "string.special.symbol" | "string.special.path"
         | "string.special.url" | "string.escape" | "string.regexp" | "string.regex"
         | "character" | "character.special" | "escape" => ThemeSlot::String,
 
+        // Tags inside comments, before the other comments so they stand out
+        "comment.error" | "comment.warning" | "comment.note" | "comment.todo" => {
+            ThemeSlot::CommentTag
+        }
+
         // Comments
-        "comment" | "comment.documentation" | "comment.line" | "comment.block"
-        | "comment.error" | "comment.warning" | "comment.note" | "comment.todo" => {
+        "comment" | "comment.documentation" | "comment.line" | "comment.block" => {
             ThemeSlot::Comment
         }
 
+        // Visible whitespace
+        "whitespace" => ThemeSlot::Whitespace,
+
+        // Overlaid ranges, before the generic markup fallback
+        "markup.highlight" => ThemeSlot::Match,
+
+        // Gutter annotations
+        "gutter.author" => ThemeSlot::GutterAuthor,
+        "gutter.age" => ThemeSlot::GutterAge,
+        "gutter.label" => ThemeSlot::GutterLabel,
+
         // Types
         "type" | "type.builtin" | "type.qualifier" | "type.definition" | "type.enum"
         | "type.enum.variant" | "type.parameter" => ThemeSlot::Type,
@@ -316,6 +405,8 @@ pub fn capture_to_slot(capture: &str) -> ThemeSlot {
         // Diff
         "diff.addition" | "diff.plus" | "diff.delta" => ThemeSlot::DiffAdd,
         "diff.deletion" | "diff.minus" => ThemeSlot::DiffDelete,
+        "diff.addition.inline" => ThemeSlot::DiffAddInline,
+        "diff.deletion.inline" => ThemeSlot::DiffDeleteInline,
 
         // Embedded
         "embedded" => ThemeSlot::Embedded,
@@ -602,6 +693,42 @@ pub const HIGHLIGHTS: &[HighlightDef] = &[
         parent_tag: "c",
         aliases: &[],
     },
+    HighlightDef {
+        name: "comment.todo",
+        tag: "ct",
+        parent_tag: "c",
+        aliases: &["comment.error", "comment.warning", "comment.note"],
+    },
+    HighlightDef {
+        name: "whitespace",
+        tag: "ws",
+        parent_tag: "c",
+        aliases: &[],
+    },
+    HighlightDef {
+        name: "markup.highlight",
+        tag: "mh",
+        parent_tag: "",
+        aliases: &[],
+    },
+    HighlightDef {
+        name: "gutter.author",
+        tag: "ga",
+        parent_tag: "c",
+        aliases: &[],
+    },
+    HighlightDef {
+        name: "gutter.age",
+        tag: "gd",
+        parent_tag: "c",
+        aliases: &[],
+    },
+    HighlightDef {
+        name: "gutter.label",
+        tag: "gl",
+        parent_tag: "c",
+        aliases: &[],
+    },
     HighlightDef {
         name: "macro",
         tag: "m",
@@ -626,6 +753,18 @@ pub const HIGHLIGHTS: &[HighlightDef] = &[
         parent_tag: "",
         aliases: &["diff.minus"],
     },
+    HighlightDef {
+        name: "diff.addition.inline",
+        tag: "dai",
+        parent_tag: "",
+        aliases: &[],
+    },
+    HighlightDef {
+        name: "diff.deletion.inline",
+        tag: "ddi",
+        parent_tag: "",
+        aliases: &[],
+    },
     HighlightDef {
         name: "number",
         tag: "n",
@@ -795,6 +934,12 @@ pub const HIGHLIGHTS: &[HighlightDef] = &[
         parent_tag: "k",
         aliases: &[],
     },
+    HighlightDef {
+        name: "string.special.secret",
+        tag: "sk",
+        parent_tag: "s",
+        aliases: &[],
+    },
     HighlightDef {
         name: "string.regexp",
         tag: "rx",
@@ -863,11 +1008,10 @@ pub fn parent_tag(index: usize) -> Option<&'static str> {
 /// Generate CSS inheritance rules for sub-categories.
 /// Returns rules like "a-kc, a-kf, a-ki { color: inherit; }" grouped by parent.
 pub fn css_inheritance_rules() -> String {
-    use std::collections::HashMap;
-    use std::fmt::Write;
+    use core::fmt::Write;
 
     // Group children by parent
-    let mut parent_children: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut parent_children: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
     for def in HIGHLIGHTS {
         if !def.parent_tag.is_empty() && !def.tag.is_empty() {
             parent_children
@@ -898,7 +1042,7 @@ pub fn css_inheritance_rules() -> String {
 ///
 /// # Example
 /// ```
-/// use arborium_theme::highlights::tag_for_capture;
+/// use arborium_captures::tag_for_capture;
 ///
 /// // All these map to the keyword slot ("k")
 /// assert_eq!(tag_for_capture("keyword"), Some("k"));
@@ -921,7 +1065,7 @@ pub fn tag_for_capture(capture: &str) -> Option<&'static str> {
 ///
 /// # Example
 /// ```
-/// use arborium_theme::highlights::tag_to_name;
+/// use arborium_captures::tag_to_name;
 ///
 /// assert_eq!(tag_to_name("k"), Some("keyword"));
 /// assert_eq!(tag_to_name("f"), Some("function"));
@@ -954,6 +1098,15 @@ pub fn tag_to_name(tag: &str) -> Option<&'static str> {
         "tx" => Some("strikethrough"),
         "da" => Some("diff-add"),
         "dd" => Some("diff-delete"),
+        "dai" => Some("diff-add-inline"),
+        "ddi" => Some("diff-delete-inline"),
+        "sk" => Some("secret"),
+        "ct" => Some("comment-tag"),
+        "ws" => Some("whitespace"),
+        "mh" => Some("match"),
+        "ga" => Some("gutter-author"),
+        "gd" => Some("gutter-age"),
+        "gl" => Some("gutter-label"),
         "eb" => Some("embedded"),
         "er" => Some("error"),
         _ => None,
@@ -1012,6 +1165,7 @@ pub const CAPTURE_NAMES: &[&str] = &[
     "string.special.symbol",
     "string.special.path",
     "string.special.url",
+    "string.special.secret",
     "string.escape",
     "string.regexp",
     "string.regex",
@@ -1027,6 +1181,14 @@ pub const CAPTURE_NAMES: &[&str] = &[
     "comment.warning",
     "comment.note",
     "comment.todo",
+    // Whitespace markers
+    "whitespace",
+    // Overlaid ranges
+    "markup.highlight",
+    // Gutter annotations
+    "gutter.author",
+    "gutter.age",
+    "gutter.label",
     // Types
     "type",
     "type.builtin",
@@ -1129,6 +1291,8 @@ pub const CAPTURE_NAMES: &[&str] = &[
     "diff.delta",
     "diff.deletion",
     "diff.minus",
+    "diff.addition.inline",
+    "diff.deletion.inline",
     // Special
     "embedded",
     "error",
@@ -1140,6 +1304,7 @@ pub const CAPTURE_NAMES: &[&str] = &[
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::string::ToString;
 
     #[test]
     fn test_names_count() {
@@ -1226,6 +1391,71 @@ mod tests {
         assert_eq!(tag_for_capture("none"), None);
     }
 
+    #[test]
+    fn test_secret_slot() {
+        assert_eq!(capture_to_slot("string.special.secret"), ThemeSlot::Secret);
+        assert_eq!(tag_for_capture("string.special.secret"), Some("sk"));
+        assert_eq!(tag_to_name("sk"), Some("secret"));
+
+        // Themes without a secret style fall back to the string color
+        let idx = slot_to_highlight_index(ThemeSlot::Secret).unwrap();
+        assert_eq!(parent_tag(idx), Some("s"));
+    }
+
+    #[test]
+    fn test_comment_tag_slot() {
+        for capture in [
+            "comment.todo",
+            "comment.note",
+            "comment.warning",
+            "comment.error",
+        ] {
+            assert_eq!(capture_to_slot(capture), ThemeSlot::CommentTag);
+            assert_eq!(tag_for_capture(capture), Some("ct"));
+        }
+        assert_eq!(tag_to_name("ct"), Some("comment-tag"));
+
+        // Themes without a comment tag style fall back to the comment color
+        let idx = slot_to_highlight_index(ThemeSlot::CommentTag).unwrap();
+        assert_eq!(parent_tag(idx), Some("c"));
+    }
+
+    #[test]
+    fn test_inline_diff_slots() {
+        assert_eq!(
+            capture_to_slot("diff.addition.inline"),
+            ThemeSlot::DiffAddInline
+        );
+        assert_eq!(tag_for_capture("diff.deletion.inline"), Some("ddi"));
+        assert_eq!(tag_to_name("dai"), Some("diff-add-inline"));
+    }
+
+    #[test]
+    fn test_match_slot() {
+        assert_eq!(capture_to_slot("markup.highlight"), ThemeSlot::Match);
+        assert_eq!(tag_for_capture("markup.highlight"), Some("mh"));
+        assert_eq!(tag_to_name("mh"), Some("match"));
+        assert!(slot_to_highlight_index(ThemeSlot::Match).is_some());
+    }
+
+    #[test]
+    fn test_gutter_slots() {
+        assert_eq!(capture_to_slot("gutter.age"), ThemeSlot::GutterAge);
+        assert_eq!(tag_for_capture("gutter.author"), Some("ga"));
+        assert_eq!(tag_to_name("gl"), Some("gutter-label"));
+        let idx = slot_to_highlight_index(ThemeSlot::GutterLabel).unwrap();
+        assert_eq!(parent_tag(idx), Some("c"));
+    }
+
+    #[test]
+    fn test_whitespace_slot() {
+        assert_eq!(capture_to_slot("whitespace"), ThemeSlot::Whitespace);
+        assert_eq!(tag_for_capture("whitespace"), Some("ws"));
+        assert_eq!(tag_to_name("ws"), Some("whitespace"));
+        let idx = slot_to_highlight_index(ThemeSlot::Whitespace).unwrap();
+        assert_eq!(parent_tag(idx), Some("c"));
+    }
+
     #[test]
     fn test_theme_slot_tag() {
         assert_eq!(ThemeSlot::Keyword.tag(), Some("k"));
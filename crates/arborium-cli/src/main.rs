@@ -1,9 +1,15 @@
-use arborium::theme::builtin;
-use arborium::{AnsiHighlighter, Highlighter};
+use arborium::advanced::{ParseResult, QueryCache};
+use arborium::config_file::{self, FileConfig, OutputMode};
+use arborium::theme::{Theme, builtin};
+use arborium::{AnsiHighlighter, Config, Diagnostic, GrammarStore, Highlighter};
 use facet::Facet;
 use facet_args as args;
 use std::io::{self, Read};
 use std::path::Path;
+use std::sync::Arc;
+
+/// Guesses below this confidence are not trusted for input without a file name.
+const MIN_GUESS_CONFIDENCE: f32 = 0.5;
 
 /// Arborium syntax highlighter - terminal-friendly code highlighting
 #[derive(Debug, Facet)]
@@ -18,6 +24,10 @@ struct Args {
     #[facet(args::named, default)]
     html: bool,
 
+    /// Output ANSI escape sequences even if arborium.toml asks for HTML
+    #[facet(args::named, default)]
+    ansi: bool,
+
     /// Input: code string, filename, or '-' for stdin
     ///
     /// If a file path is provided, reads from that file.
@@ -29,6 +39,19 @@ struct Args {
     /// Theme for ANSI output (ignored with --html)
     #[facet(args::named, default)]
     theme: Option<String>,
+
+    /// Compile queries instead of loading them from the user's cache directory
+    #[facet(args::named, default)]
+    no_query_cache: bool,
+
+    /// Print warnings about what couldn't be fully highlighted
+    #[facet(args::named, args::short = 'v', default)]
+    verbose: bool,
+
+    /// Print the grammar's raw spans and injections as JSON instead of
+    /// highlighting, without resolving injections
+    #[facet(args::named, default)]
+    parse: bool,
 }
 
 fn main() {
@@ -48,6 +71,13 @@ fn main() {
 }
 
 fn run(args: Args) -> Result<(), String> {
+    // Settings from arborium.toml in the working directory; flags win
+    let file_config = FileConfig::discover(Path::new("."))
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+    let mut config = Config::default();
+    file_config.apply(&mut config);
+
     // Determine input source and read content
     let (content, filename) = match args.input.as_deref() {
         None | Some("-") => {
@@ -78,8 +108,13 @@ fn run(args: Args) -> Result<(), String> {
     } else if let Some(filename) = &filename {
         arborium::detect_language(filename)
     } else {
-        // Try to detect from content (shebang)
-        detect_from_content(&content)
+        // Try to detect from content (shebang, diff headers), then guess
+        arborium::detect_language_from_content(&content).or_else(|| {
+            arborium::guess_language(&content)
+                .first()
+                .filter(|(_, confidence)| *confidence >= MIN_GUESS_CONFIDENCE)
+                .map(|(lang, _)| *lang)
+        })
     };
 
     let lang = detected_lang.ok_or_else(|| {
@@ -94,72 +129,132 @@ fn run(args: Args) -> Result<(), String> {
             "Could not detect language. Use --lang to specify.".to_string()
         }
     })?;
+    let lang = file_config
+        .languages
+        .resolve(lang)
+        .ok_or_else(|| format!("Language {lang} is excluded by {}", config_file::FILE_NAME))?;
+
+    let html = if args.html || args.ansi {
+        args.html
+    } else {
+        file_config.output.mode == Some(OutputMode::Html)
+    };
+
+    // Compiled queries are cached across runs, since compiling them is most of the work
+    let query_cache = if args.no_query_cache {
+        None
+    } else {
+        QueryCache::user()
+    };
+    let store = Arc::new(GrammarStore::new().with_query_cache(query_cache));
+
+    if args.parse {
+        let grammar = store
+            .get(lang)
+            .ok_or_else(|| format!("No grammar for {lang}"))?;
+        let mut ctx = store
+            .parser_pool()
+            .acquire(grammar.language())
+            .map_err(|e| format!("Failed to create parser: {}", e))?;
+        println!("{}", parse_result_json(&grammar.parse(&mut ctx, &content)));
+        return Ok(());
+    }
 
     // Highlight based on output format
-    if args.html {
-        let mut highlighter = Highlighter::new();
+    if html {
+        let mut highlighter = Highlighter::with_store_and_config(store, config);
         let html = highlighter
             .highlight(lang, &content)
             .map_err(|e| format!("Highlighting failed: {}", e))?;
         println!("{}", html);
+        print_diagnostics(args.verbose, highlighter.take_diagnostics());
     } else {
         // Determine theme
         let theme = match args.theme.as_deref() {
-            Some("mocha") | Some("catppuccin-mocha") => builtin::catppuccin_mocha(),
-            Some("latte") | Some("catppuccin-latte") => builtin::catppuccin_latte(),
-            Some("macchiato") | Some("catppuccin-macchiato") => builtin::catppuccin_macchiato(),
-            Some("frappe") | Some("catppuccin-frappe") => builtin::catppuccin_frappe(),
-            Some("dracula") => builtin::dracula(),
-            Some("tokyo-night") => builtin::tokyo_night(),
-            Some("nord") => builtin::nord(),
-            Some("one-dark") => builtin::one_dark(),
-            Some("github-dark") => builtin::github_dark(),
-            Some("github-light") => builtin::github_light(),
-            Some("gruvbox-dark") => builtin::gruvbox_dark(),
-            Some("gruvbox-light") => builtin::gruvbox_light(),
-            Some(other) => {
-                return Err(format!("Unknown theme: {}", other));
-            }
-            None => builtin::catppuccin_mocha(), // Default theme
+            Some(name) => theme_by_name(name)?,
+            None => file_config
+                .theme()
+                .map_err(|e| e.to_string())?
+                .unwrap_or_else(builtin::catppuccin_mocha), // Default theme
         };
 
-        let mut highlighter = AnsiHighlighter::new(theme.clone());
+        let mut highlighter = AnsiHighlighter::with_store_and_config(store, theme, config);
         let ansi = highlighter
             .highlight(lang, &content)
             .map_err(|e| format!("Highlighting failed: {}", e))?;
         println!("{}", ansi);
+        print_diagnostics(args.verbose, highlighter.take_diagnostics());
     }
 
     Ok(())
 }
 
-/// Detect language from content (e.g., shebang lines)
-fn detect_from_content(content: &str) -> Option<&'static str> {
-    let first_line = content.lines().next()?;
-
-    // Check for shebang
-    if let Some(shebang) = first_line.strip_prefix("#!") {
-        let shebang = shebang.trim();
-
-        // Common interpreters
-        if shebang.contains("python") {
-            return Some("python");
-        } else if shebang.contains("node") || shebang.contains("nodejs") {
-            return Some("javascript");
-        } else if shebang.contains("ruby") {
-            return Some("ruby");
-        } else if shebang.contains("perl") {
-            return Some("perl");
-        } else if shebang.contains("bash") || shebang.contains("/sh") {
-            return Some("bash");
-        } else if shebang.contains("zsh") {
-            return Some("zsh");
-        } else if shebang.contains("fish") {
-            return Some("fish");
-        } else if shebang.contains("php") {
-            return Some("php");
+/// Print what couldn't be fully highlighted, with `--verbose`.
+fn print_diagnostics(verbose: bool, diagnostics: Vec<Diagnostic>) {
+    if verbose {
+        for diagnostic in diagnostics {
+            eprintln!("Warning: {diagnostic}");
+        }
+    }
+}
+
+/// `--parse` output: the parse result with UTF-8 byte offsets, in the shape
+/// grammar plugins return it, so the two can be compared.
+fn parse_result_json(result: &ParseResult) -> String {
+    let spans: Vec<String> = result
+        .spans
+        .iter()
+        .map(|span| {
+            format!(
+                r#"{{"start":{},"end":{},"capture":{}}}"#,
+                span.start,
+                span.end,
+                json_string(&span.capture)
+            )
+        })
+        .collect();
+    let injections: Vec<String> = result
+        .injections
+        .iter()
+        .map(|injection| {
+            format!(
+                r#"{{"start":{},"end":{},"language":{},"include_children":{}}}"#,
+                injection.start,
+                injection.end,
+                json_string(&injection.language),
+                injection.include_children
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"spans":[{}],"injections":[{}]}}"#,
+        spans.join(","),
+        injections.join(",")
+    )
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
         }
     }
+    out.push('"');
+    out
+}
 
-    None
+/// Resolve a `--theme` value, including the short Catppuccin names.
+fn theme_by_name(name: &str) -> Result<Theme, String> {
+    Ok(match name {
+        "mocha" => builtin::catppuccin_mocha(),
+        "latte" => builtin::catppuccin_latte(),
+        "macchiato" => builtin::catppuccin_macchiato(),
+        "frappe" => builtin::catppuccin_frappe(),
+        other => config_file::find_theme(other).map_err(|e| e.to_string())?,
+    })
 }
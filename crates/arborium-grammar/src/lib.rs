@@ -0,0 +1,231 @@
+//! The [`declare_grammar!`] macro behind arborium's grammar crates.
+//!
+//! Every `arborium-<lang>` crate exposes the same items: the tree-sitter
+//! [`LanguageFn`](https://docs.rs/tree-sitter-language), its queries, a bit of
+//! metadata and the test harness hookup. Their `src/lib.rs` is a single
+//! invocation of this macro, generated by `cargo xtask gen` from
+//! `arborium.yaml`:
+//!
+//! ```ignore
+//! arborium_grammar::declare_grammar! {
+//!     id: "cpp",
+//!     name: "C++",
+//!     c_symbol: tree_sitter_cpp,
+//!     highlights: "highlights.scm",
+//!     injections: "injections.scm",
+//!     locals: none,
+//!     textobjects: none,
+//!     line_comment: "//",
+//!     block_comment: ("/*", "*/"),
+//!     brackets: [("(", ")"), ("[", "]"), ("{", "}")],
+//!     auto_close: [("\"", "\""), ("'", "'")],
+//!     highlights_prepend: [arborium_c],
+//!     tests: true,
+//! }
+//! ```
+//!
+//! Queries are read from the crate's `queries/` directory at compile time;
+//! `none` stands for a query the grammar doesn't have. The invoking crate
+//! must depend on `tree-sitter-language`, on every crate in
+//! `highlights_prepend`, and (with `tests: true`) on `arborium-test-harness`
+//! as a dev-dependency.
+//!
+//! With the `build` feature, [`validate::validate_queries`] checks those
+//! queries from the grammar crate's `build.rs`. With `precompile`,
+//! [`precompile::precompile_queries`] also compiles them there, for grammar
+//! crates built with their `precompiled` feature.
+
+#![no_std]
+
+#[cfg(any(test, feature = "build"))]
+extern crate std;
+
+#[cfg(all(feature = "precompile", not(target_family = "wasm")))]
+pub mod precompile;
+#[cfg(any(test, feature = "build"))]
+pub mod validate;
+
+/// Declare a grammar crate's public items.
+///
+/// Expands to:
+///
+/// - the `extern "C"` declaration of the parser's `c_symbol`
+/// - `pub const fn language() -> LanguageFn`
+/// - `GRAMMAR_ID` and `GRAMMAR_NAME`
+/// - `HIGHLIGHTS_QUERY`, `INJECTIONS_QUERY`, `LOCALS_QUERY` and
+///   `TEXTOBJECTS_QUERY`, empty for
+///   queries given as `none`. With a non-empty `highlights_prepend`,
+///   `HIGHLIGHTS_QUERY` is a `LazyLock<String>` holding those crates'
+///   highlights followed by this grammar's own.
+/// - `LINE_COMMENT` and `BLOCK_COMMENT`, the language's comment delimiters,
+///   `None` for delimiters given as `none`
+/// - `BRACKETS` and `AUTO_CLOSE_PAIRS`, the pairs editors match and close
+///   automatically: the brackets, then the `auto_close` pairs
+/// - `PRECOMPILED_HIGHLIGHTS` and `PRECOMPILED_INJECTIONS`, the queries as
+///   compiled by [`precompile::precompile_queries`] with the crate's
+///   `precompiled` feature, and empty without it
+/// - with `tests: true`, the `test_grammar` and `test_corpus` tests
+///
+/// See the [crate docs](crate) for an example.
+#[macro_export]
+macro_rules! declare_grammar {
+    (@query none) => {
+        ""
+    };
+    (@query $file:literal) => {
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/queries/", $file))
+    };
+
+    (@line_comment none) => {
+        None
+    };
+    (@line_comment $token:literal) => {
+        Some($token)
+    };
+
+    (@block_comment none) => {
+        None
+    };
+    (@block_comment ($start:literal, $end:literal)) => {
+        Some(($start, $end))
+    };
+
+    (@precompiled $file:literal) => {{
+        #[cfg(feature = "precompiled")]
+        let bytes: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/", $file));
+        #[cfg(not(feature = "precompiled"))]
+        let bytes: &[u8] = &[];
+        bytes
+    }};
+
+    (@highlights $id:literal, $highlights:tt, []) => {
+        #[doc = concat!("The highlights query for ", $id, ".")]
+        pub const HIGHLIGHTS_QUERY: &str = $crate::declare_grammar!(@query $highlights);
+    };
+    (@highlights $id:literal, $highlights:tt, [$($prepend:ident),+]) => {
+        /// The highlights query for this grammar alone.
+        /// Use [`HIGHLIGHTS_QUERY`] for the full query including inherited queries.
+        const HIGHLIGHTS_QUERY_BASE: &str = $crate::declare_grammar!(@query $highlights);
+
+        #[doc = concat!("The highlights query for ", $id, ".")]
+        #[doc = ""]
+        #[doc = concat!(
+            "The highlights of ", $("`", stringify!($prepend), "`, ",)+ "then ", $id, "'s own."
+        )]
+        pub static HIGHLIGHTS_QUERY: ::std::sync::LazyLock<::std::string::String> =
+            ::std::sync::LazyLock::new(|| {
+                let mut query = ::std::string::String::new();
+                $(
+                    query.push_str(&$prepend::HIGHLIGHTS_QUERY);
+                    query.push('\n');
+                )+
+                query.push_str(HIGHLIGHTS_QUERY_BASE);
+                query
+            });
+    };
+
+    (@tests false, $id:literal) => {};
+    (@tests true, $id:literal) => {
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            #[test]
+            fn test_grammar() {
+                ::arborium_test_harness::test_grammar(
+                    language(),
+                    $id,
+                    &HIGHLIGHTS_QUERY,
+                    INJECTIONS_QUERY,
+                    LOCALS_QUERY,
+                    env!("CARGO_MANIFEST_DIR"),
+                );
+            }
+
+            #[test]
+            fn test_corpus() {
+                ::arborium_test_harness::test_corpus(language(), $id, env!("CARGO_MANIFEST_DIR"));
+            }
+        }
+    };
+
+    (
+        id: $id:literal,
+        name: $name:literal,
+        c_symbol: $symbol:ident,
+        highlights: $highlights:tt,
+        injections: $injections:tt,
+        locals: $locals:tt,
+        textobjects: $textobjects:tt,
+        line_comment: $line_comment:tt,
+        block_comment: $block_comment:tt,
+        brackets: [$(($bracket_open:literal, $bracket_close:literal)),* $(,)?],
+        auto_close: [$(($auto_open:literal, $auto_close:literal)),* $(,)?],
+        highlights_prepend: [$($prepend:ident),* $(,)?],
+        tests: $tests:tt $(,)?
+    ) => {
+        unsafe extern "C" {
+            fn $symbol() -> *const ();
+        }
+
+        #[doc = concat!(
+            "Returns the ", $id, " tree-sitter [`LanguageFn`](::tree_sitter_language::LanguageFn)."
+        )]
+        pub const fn language() -> ::tree_sitter_language::LanguageFn {
+            unsafe { ::tree_sitter_language::LanguageFn::from_raw($symbol) }
+        }
+
+        /// The grammar's id in arborium, as used in `arborium.yaml` and
+        /// returned by `arborium::detect_language`.
+        pub const GRAMMAR_ID: &str = $id;
+
+        /// The grammar's human-readable name.
+        pub const GRAMMAR_NAME: &str = $name;
+
+        $crate::declare_grammar!(@highlights $id, $highlights, [$($prepend),*]);
+
+        #[doc = concat!("The injections query for ", $id, ".")]
+        pub const INJECTIONS_QUERY: &str = $crate::declare_grammar!(@query $injections);
+
+        #[doc = concat!("The locals query for ", $id, ".")]
+        pub const LOCALS_QUERY: &str = $crate::declare_grammar!(@query $locals);
+
+        #[doc = concat!("The textobjects query for ", $id, ", for structural navigation.")]
+        pub const TEXTOBJECTS_QUERY: &str = $crate::declare_grammar!(@query $textobjects);
+
+        /// The token starting a comment that runs to the end of the line,
+        /// like `//`, if the language has such comments.
+        pub const LINE_COMMENT: Option<&str> =
+            $crate::declare_grammar!(@line_comment $line_comment);
+
+        /// The start and end of a block comment, like `/*` and `*/`, if the
+        /// language has block comments.
+        pub const BLOCK_COMMENT: Option<(&str, &str)> =
+            $crate::declare_grammar!(@block_comment $block_comment);
+
+        /// Brackets that editors match and highlight in pairs, like `(`
+        /// and `)`, as (open, close).
+        pub const BRACKETS: &[(&str, &str)] = &[$(($bracket_open, $bracket_close)),*];
+
+        /// Pairs that editors close automatically when the opening one is
+        /// typed: the [`BRACKETS`], then others like quotes.
+        pub const AUTO_CLOSE_PAIRS: &[(&str, &str)] = &[
+            $(($bracket_open, $bracket_close),)*
+            $(($auto_open, $auto_close),)*
+        ];
+
+        /// [`HIGHLIGHTS_QUERY`] as compiled at build time, for
+        /// `Query::from_bytes`. Empty without the `precompiled` feature, or
+        /// if the build couldn't compile it.
+        pub const PRECOMPILED_HIGHLIGHTS: &[u8] =
+            $crate::declare_grammar!(@precompiled "highlights.query");
+
+        /// [`INJECTIONS_QUERY`] as compiled at build time, for
+        /// `Query::from_bytes`. Empty without the `precompiled` feature, or
+        /// if the build couldn't compile it.
+        pub const PRECOMPILED_INJECTIONS: &[u8] =
+            $crate::declare_grammar!(@precompiled "injections.query");
+
+        $crate::declare_grammar!(@tests $tests, $id);
+    };
+}
@@ -0,0 +1,148 @@
+//! Build-time query compilation (the `precompile` feature).
+//!
+//! Compiling a grammar's queries is most of the cost of its first highlight.
+//! With their `precompiled` feature, grammar crates do it in `build.rs`
+//! instead: [`precompile_queries`] writes the compiled queries to `OUT_DIR`,
+//! and `declare_grammar!` embeds them as `PRECOMPILED_HIGHLIGHTS` and
+//! `PRECOMPILED_INJECTIONS`, which load in a fraction of the time.
+//!
+//! Build scripts run on the host, so the grammar is built a second time, as
+//! a shared library for the host, and loaded to compile the queries
+//! against. Compiled queries are only valid for the exact tree-sitter
+//! build that wrote them; when cross-compiling to a target that lays out
+//! tree-sitter's query structures differently, the embedded bytes are
+//! rejected at runtime and the queries are compiled from source as usual.
+
+use std::env;
+use std::format;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::println;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use arborium_tree_sitter::{Language, LanguageFn, Query};
+use libloading::{Library, Symbol};
+
+/// The files [`precompile_queries`] writes to `OUT_DIR`, for the
+/// highlights and injections queries.
+pub const PRECOMPILED_FILES: [&str; 2] = ["highlights.query", "injections.query"];
+
+/// Compile the calling crate's highlights and injections queries into
+/// `OUT_DIR`; meant to be called from `build.rs`.
+///
+/// `c_symbol` is the grammar's language function, and `sources` and
+/// `include_dirs` the C files and include directories it is built from.
+/// With `inherits_highlights`, the highlights query is left out: its full
+/// source includes other grammar crates' highlights, which the build
+/// script can't see.
+///
+/// Never fails the build. If the grammar can't be built for the host or a
+/// query doesn't compile, a warning is printed and the file is left empty,
+/// which the runtime treats as "not precompiled".
+pub fn precompile_queries(
+    crate_dir: &Path,
+    c_symbol: &str,
+    sources: &[PathBuf],
+    include_dirs: &[PathBuf],
+    inherits_highlights: bool,
+) {
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR is set for build scripts"));
+    let query_files = [
+        (!inherits_highlights).then_some("highlights.scm"),
+        Some("injections.scm"),
+    ];
+
+    let mut compiled: [Vec<u8>; 2] = Default::default();
+    match build_host_library(&out_dir, c_symbol, sources, include_dirs) {
+        Ok(path) => {
+            if let Err(e) = compile_queries(&path, c_symbol, crate_dir, query_files, &mut compiled)
+            {
+                println!("cargo:warning=not precompiling queries: {e}");
+            }
+        }
+        Err(e) => println!("cargo:warning=not precompiling queries: {e}"),
+    }
+
+    for (file, bytes) in PRECOMPILED_FILES.iter().zip(compiled) {
+        fs::write(out_dir.join(file), bytes).expect("failed to write to OUT_DIR");
+    }
+}
+
+/// Build the grammar as a shared library for the host.
+fn build_host_library(
+    out_dir: &Path,
+    c_symbol: &str,
+    sources: &[PathBuf],
+    include_dirs: &[PathBuf],
+) -> Result<PathBuf, String> {
+    let host = env::var("HOST").map_err(|_| "HOST is not set".to_string())?;
+    let mut build = cc::Build::new();
+    build
+        .host(&host)
+        .target(&host)
+        .opt_level(0)
+        .cargo_metadata(false)
+        .warnings(false)
+        .pic(true);
+    for dir in include_dirs {
+        build.include(dir);
+    }
+    let compiler = build.try_get_compiler().map_err(|e| e.to_string())?;
+
+    let path = out_dir.join(format!("{c_symbol}-host{}", env::consts::DLL_SUFFIX));
+    let mut command = compiler.to_command();
+    if compiler.is_like_msvc() {
+        command.arg("/LD").arg(format!("/Fe{}", path.display()));
+        command.arg(format!("/Fo{}\\", out_dir.display()));
+    } else {
+        command.arg("-shared").arg("-o").arg(&path);
+    }
+    command.args(sources);
+
+    let output = command
+        .output()
+        .map_err(|e| format!("failed to run the C compiler: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "building the grammar for the host failed: {}",
+            stderr.lines().next().unwrap_or_default()
+        ));
+    }
+    Ok(path)
+}
+
+/// Load the host library at `path` and compile the queries in `files`.
+fn compile_queries(
+    path: &Path,
+    c_symbol: &str,
+    crate_dir: &Path,
+    files: [Option<&str>; 2],
+    compiled: &mut [Vec<u8>; 2],
+) -> Result<(), String> {
+    // Safety: the library is the grammar we just built, whose only
+    // initialization is that of its static tables
+    let library = unsafe { Library::new(path) }.map_err(|e| e.to_string())?;
+    let language = {
+        let language_fn: Symbol<unsafe extern "C" fn() -> *const ()> =
+            unsafe { library.get(c_symbol.as_bytes()) }.map_err(|e| e.to_string())?;
+        // Safety: `c_symbol` is the grammar's language function. Queries and
+        // the language are dropped below, before the library is unloaded
+        Language::new(unsafe { LanguageFn::from_raw(*language_fn) })
+    };
+
+    for (file, compiled) in files.into_iter().zip(compiled.iter_mut()) {
+        let Some(file) = file else { continue };
+        let Ok(source) = fs::read_to_string(crate_dir.join("queries").join(file)) else {
+            continue;
+        };
+        match Query::new(&language, &source) {
+            Ok(query) => *compiled = query.to_bytes(),
+            Err(e) => println!("cargo:warning=not precompiling queries/{file}: {e}"),
+        }
+    }
+
+    drop(language);
+    library.close().map_err(|e| e.to_string())
+}
@@ -0,0 +1,429 @@
+//! Build-time query validation (the `build` feature).
+//!
+//! A grammar crate's `build.rs` calls [`validate_queries`], which checks its
+//! `queries/*.scm` against the grammar's `node-types.json` and fails the
+//! build with `file:line:column` diagnostics. Without it, a query naming a
+//! node type or field the grammar doesn't have compiles fine and only shows
+//! up as missing highlights at runtime.
+//!
+//! This is a static check, not a full `Query::new`: it catches syntax errors
+//! (unbalanced parentheses, unterminated strings, stray characters) and
+//! unknown node types, anonymous nodes and field names. The test harness
+//! still compiles every query against the real language.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::format;
+use std::fs;
+use std::path::Path;
+use std::string::{String, ToString};
+use std::vec::Vec;
+use std::{eprintln, println};
+
+/// The queries a grammar crate may have, in `queries/`.
+pub const QUERY_FILES: [&str; 4] = [
+    "highlights.scm",
+    "injections.scm",
+    "locals.scm",
+    "textobjects.scm",
+];
+
+/// Validate the calling crate's queries; meant to be called from `build.rs`.
+///
+/// Does nothing if `grammar/src/node-types.json` doesn't exist. On errors,
+/// prints one line per diagnostic and exits the build script with status 1.
+pub fn validate_queries(crate_dir: &Path) {
+    let node_types_path = crate_dir.join("grammar/src/node-types.json");
+    println!("cargo:rerun-if-changed={}", node_types_path.display());
+    for file in QUERY_FILES {
+        println!(
+            "cargo:rerun-if-changed={}",
+            crate_dir.join("queries").join(file).display()
+        );
+    }
+
+    let Ok(json) = fs::read_to_string(&node_types_path) else {
+        return;
+    };
+    let node_types = match NodeTypes::from_json(&json) {
+        Ok(node_types) => node_types,
+        Err(e) => {
+            eprintln!("error: {}: {e}", node_types_path.display());
+            std::process::exit(1);
+        }
+    };
+
+    let mut failed = false;
+    for file in QUERY_FILES {
+        let Ok(source) = fs::read_to_string(crate_dir.join("queries").join(file)) else {
+            continue;
+        };
+        for diagnostic in check_query(&source, &node_types) {
+            eprintln!("error: queries/{file}:{diagnostic}");
+            failed = true;
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// The node types, anonymous nodes and field names of a grammar.
+#[derive(Debug, Default)]
+pub struct NodeTypes {
+    named: BTreeSet<String>,
+    anonymous: BTreeSet<String>,
+    fields: BTreeSet<String>,
+}
+
+impl NodeTypes {
+    /// Read the node types from the contents of a `node-types.json`.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let mut node_types = Self::default();
+        node_types.collect(&value);
+        if node_types.named.is_empty() && node_types.anonymous.is_empty() {
+            return Err("no node types found".to_string());
+        }
+        Ok(node_types)
+    }
+
+    /// Collect every `{ "type", "named" }` object, wherever it appears:
+    /// supertypes' subtypes and fields' types are node types too.
+    fn collect(&mut self, value: &serde_json::Value) {
+        match value {
+            serde_json::Value::Array(items) => items.iter().for_each(|item| self.collect(item)),
+            serde_json::Value::Object(map) => {
+                if let (Some(kind), Some(named)) = (
+                    map.get("type").and_then(|v| v.as_str()),
+                    map.get("named").and_then(|v| v.as_bool()),
+                ) {
+                    if named {
+                        self.named.insert(kind.to_string());
+                    } else {
+                        self.anonymous.insert(kind.to_string());
+                    }
+                }
+                if let Some(serde_json::Value::Object(fields)) = map.get("fields") {
+                    self.fields.extend(fields.keys().cloned());
+                }
+                map.values().for_each(|v| self.collect(v));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A problem found in a query, with a 1-based position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Frame {
+    /// `(` before the node type
+    NodeStart,
+    /// `(MISSING` before the missing node's type
+    Missing,
+    /// `(` after the node type, with child patterns
+    Node,
+    /// `(#predicate? ...)`, whose arguments aren't patterns
+    Predicate,
+    /// `[ ... ]`
+    Alternation,
+}
+
+/// Check a query against a grammar's node types.
+pub fn check_query(source: &str, node_types: &NodeTypes) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut stack: Vec<(Frame, char, usize, usize)> = Vec::new();
+    let mut cursor = Cursor {
+        chars: source.chars().peekable(),
+        line: 1,
+        column: 1,
+    };
+
+    while let Some(c) = cursor.peek() {
+        let (line, column) = (cursor.line, cursor.column);
+        let mut error = |message: String| {
+            diagnostics.push(Diagnostic {
+                line,
+                column,
+                message,
+            })
+        };
+        let frame = stack.last().map(|&(frame, ..)| frame);
+        let in_predicate = frame == Some(Frame::Predicate);
+        // Whether this token is the type in `(type` or `(MISSING type`
+        let mut is_node_type = false;
+
+        match c {
+            _ if c.is_whitespace() => {
+                cursor.bump();
+            }
+            ';' => {
+                while cursor.peek().is_some_and(|c| c != '\n') {
+                    cursor.bump();
+                }
+            }
+            '(' | '[' => {
+                cursor.bump();
+                let new_frame = match (in_predicate, c) {
+                    (true, _) => Frame::Predicate,
+                    (false, '(') => Frame::NodeStart,
+                    (false, _) => Frame::Alternation,
+                };
+                stack.push((new_frame, c, line, column));
+            }
+            ')' | ']' => {
+                cursor.bump();
+                let expected = if c == ')' { '(' } else { '[' };
+                match stack.pop() {
+                    Some((_, open, ..)) if open == expected => {}
+                    Some((_, open, open_line, open_column)) => error(format!(
+                        "`{c}` doesn't match `{open}` at {open_line}:{open_column}"
+                    )),
+                    None => error(format!("unexpected `{c}`")),
+                }
+            }
+            '"' => {
+                cursor.bump();
+                is_node_type = true;
+                match cursor.read_string() {
+                    None => error("unterminated string".to_string()),
+                    Some(_) if in_predicate => {}
+                    Some(value) if !node_types.anonymous.contains(&value) => {
+                        error(format!("unknown anonymous node {value:?}"))
+                    }
+                    Some(_) => {}
+                }
+            }
+            '@' => {
+                cursor.bump();
+                cursor.read_identifier();
+            }
+            '#' if frame == Some(Frame::NodeStart) => {
+                if let Some((frame, ..)) = stack.last_mut() {
+                    *frame = Frame::Predicate;
+                }
+            }
+            '!' | '.' | '*' | '+' | '?' if !in_predicate => {
+                cursor.bump();
+                if c == '!' {
+                    let field = cursor.read_identifier();
+                    if !node_types.fields.contains(&field) {
+                        error(format!("unknown field `{field}`"));
+                    }
+                }
+            }
+            _ if is_identifier_char(c) => {
+                let identifier = cursor.read_identifier();
+                if in_predicate {
+                    continue;
+                }
+                if cursor.peek() == Some(':') {
+                    cursor.bump();
+                    if !node_types.fields.contains(&identifier) {
+                        error(format!("unknown field `{identifier}`"));
+                    }
+                    continue;
+                }
+                match frame {
+                    Some(Frame::NodeStart) if identifier == "MISSING" => {
+                        if let Some((frame, ..)) = stack.last_mut() {
+                            *frame = Frame::Missing;
+                        }
+                        continue;
+                    }
+                    Some(Frame::NodeStart | Frame::Missing) => {
+                        // `supertype/subtype` names two node types
+                        for kind in identifier.split('/') {
+                            if !matches!(kind, "_" | "ERROR") && !node_types.named.contains(kind) {
+                                error(format!("unknown node type `{kind}`"));
+                            }
+                        }
+                    }
+                    _ if identifier == "_" => {}
+                    _ => error(format!(
+                        "unexpected `{identifier}`; node patterns go in parentheses"
+                    )),
+                }
+                is_node_type = true;
+            }
+            _ if in_predicate => {
+                cursor.bump();
+            }
+            _ => {
+                cursor.bump();
+                error(format!("unexpected `{c}`"));
+            }
+        }
+
+        if is_node_type {
+            if let Some((frame @ (Frame::NodeStart | Frame::Missing), ..)) = stack.last_mut() {
+                *frame = Frame::Node;
+            }
+        }
+    }
+
+    for (_, open, line, column) in stack {
+        diagnostics.push(Diagnostic {
+            line,
+            column,
+            message: format!("unclosed `{open}`"),
+        });
+    }
+    diagnostics
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/')
+}
+
+/// The query source, keeping track of the position.
+struct Cursor<'a> {
+    chars: core::iter::Peekable<core::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl Cursor<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Read an identifier, including `supertype/subtype` paths.
+    fn read_identifier(&mut self) -> String {
+        let mut identifier = String::new();
+        while let Some(c) = self.peek().filter(|&c| is_identifier_char(c)) {
+            self.bump();
+            identifier.push(c);
+        }
+        identifier
+    }
+
+    /// Read the rest of a string after its opening quote, unescaped.
+    /// `None` if it isn't closed on the same line.
+    fn read_string(&mut self) -> Option<String> {
+        let mut value = String::new();
+        loop {
+            match self.peek()? {
+                '\n' => return None,
+                '"' => {
+                    self.bump();
+                    return Some(value);
+                }
+                '\\' => {
+                    self.bump();
+                    value.push(match self.bump()? {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        '0' => '\0',
+                        c => c,
+                    });
+                }
+                c => {
+                    self.bump();
+                    value.push(c);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec;
+
+    const NODE_TYPES: &str = r#"[
+        {
+            "type": "function_item",
+            "named": true,
+            "fields": {
+                "name": { "multiple": false, "required": true, "types": [{ "type": "identifier", "named": true }] }
+            },
+            "children": { "multiple": true, "required": false, "types": [{ "type": "block", "named": true }] }
+        },
+        { "type": "_expression", "named": true, "subtypes": [{ "type": "call_expression", "named": true }] },
+        { "type": "fn", "named": false },
+        { "type": "\"", "named": false }
+    ]"#;
+
+    fn check(query: &str) -> Vec<String> {
+        let node_types = NodeTypes::from_json(NODE_TYPES).unwrap();
+        check_query(query, &node_types)
+            .iter()
+            .map(|d| d.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_query() {
+        let query = r#"
+; comment with (unbalanced "stuff
+(function_item
+  name: (identifier) @function
+  !name
+  . (block)+ @body) @item
+["fn" "\""] @keyword
+(_expression/call_expression) @call
+((identifier) @constant
+  (#match? @constant "^[A-Z]")
+  (#set! priority 90))
+(MISSING identifier) @missing
+(ERROR) @error
+(function_item (_) @child)
+"#;
+        assert_eq!(check(query), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_unknown_names() {
+        assert_eq!(
+            check("(function_item\n  nme: (idnetifier) @x)\n\"def\" @keyword"),
+            vec![
+                "2:3: unknown field `nme`",
+                "2:9: unknown node type `idnetifier`",
+                "3:1: unknown anonymous node \"def\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_syntax_errors() {
+        assert_eq!(
+            check("(block]\n(identifier\n\"fn"),
+            vec![
+                "1:7: `]` doesn't match `(` at 1:1",
+                "3:1: unterminated string",
+                "2:1: unclosed `(`",
+            ]
+        );
+        assert_eq!(
+            check("identifier @x"),
+            vec!["1:1: unexpected `identifier`; node patterns go in parentheses"]
+        );
+    }
+}
@@ -0,0 +1,321 @@
+//! Handling input that already contains ANSI escape sequences.
+//!
+//! Captured terminal output (CI logs, `cargo` output, `ls --color`) comes with
+//! its own escape sequences. Highlighting it as-is makes the grammar see
+//! `\x1b[31m` as code, and the ANSI renderer interleaves its colors with the
+//! original ones.
+//!
+//! [`strip_ansi`] removes the escapes so the text can be highlighted cleanly.
+//! [`parse_ansi`] does the same but remembers which SGR (color and style)
+//! sequences applied where, so [`spans_to_ansi_merged`] can render syntax
+//! colors only where the input wasn't already colored.
+
+use std::borrow::Cow;
+use std::cmp::Reverse;
+
+use arborium_theme::Theme;
+
+use crate::Span;
+use crate::render::spans_to_themed;
+
+/// Text with its ANSI escape sequences removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnsiText {
+    /// The text without escape sequences.
+    pub text: String,
+    /// Regions of [`text`](Self::text) the input styled, in order and not
+    /// overlapping.
+    pub styles: Vec<AnsiStyleRun>,
+}
+
+/// A region of text the input styled with SGR sequences.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnsiStyleRun {
+    /// Byte offset where the region starts (inclusive).
+    pub start: u32,
+    /// Byte offset where the region ends (exclusive).
+    pub end: u32,
+    /// The SGR parameters in effect, e.g. `"1;31"` for bold red.
+    pub sgr: String,
+}
+
+/// An escape sequence found in the input.
+enum Escape<'a> {
+    /// `ESC [ params m`: Select Graphic Rendition.
+    Sgr(&'a str),
+    /// Anything else: cursor movement, erasing, OSC hyperlinks and titles.
+    Other,
+}
+
+/// Find the escape sequence at the start of `s` (which starts with ESC).
+///
+/// Returns the sequence and its length in bytes.
+fn escape_at(s: &str) -> (Escape<'_>, usize) {
+    let bytes = s.as_bytes();
+    match bytes.get(1) {
+        // CSI: parameters and intermediates, then a final byte in @..~
+        Some(b'[') => match bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b)) {
+            Some(i) if bytes[2 + i] == b'm' => (Escape::Sgr(&s[2..2 + i]), i + 3),
+            Some(i) => (Escape::Other, i + 3),
+            None => (Escape::Other, s.len()),
+        },
+        // OSC: terminated by BEL or ST (`ESC \`). Any other ESC ends it too,
+        // and starts the next sequence.
+        Some(b']') => {
+            let end = s[2..]
+                .find(['\x07', '\x1b'])
+                .map(|i| {
+                    let terminator = 2 + i;
+                    match &bytes[terminator..] {
+                        [b'\x07', ..] => terminator + 1,
+                        [b'\x1b', b'\\', ..] => terminator + 2,
+                        _ => terminator,
+                    }
+                })
+                .unwrap_or(s.len());
+            (Escape::Other, end)
+        }
+        // Two-byte escapes (`ESC c`, `ESC 7`, ...). A stray ESC before
+        // anything else is dropped on its own.
+        Some(b) if b.is_ascii() => (Escape::Other, 2),
+        _ => (Escape::Other, 1),
+    }
+}
+
+/// Remove all ANSI escape sequences from `input`.
+///
+/// Borrows the input when there's nothing to remove.
+pub fn strip_ansi(input: &str) -> Cow<'_, str> {
+    if !input.contains('\x1b') {
+        return Cow::Borrowed(input);
+    }
+
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(i) = rest.find('\x1b') {
+        out.push_str(&rest[..i]);
+        let (_, len) = escape_at(&rest[i..]);
+        rest = &rest[i + len..];
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+/// Remove all ANSI escape sequences from `input`, keeping track of the
+/// colors and styles they applied.
+pub fn parse_ansi(input: &str) -> AnsiText {
+    let mut result = AnsiText {
+        text: String::with_capacity(input.len()),
+        styles: Vec::new(),
+    };
+    // SGR parameters in effect, and where they started applying
+    let mut current = String::new();
+    let mut current_start = 0;
+
+    let mut rest = input;
+    loop {
+        let i = rest.find('\x1b').unwrap_or(rest.len());
+        result.text.push_str(&rest[..i]);
+        if i == rest.len() {
+            break;
+        }
+
+        let (escape, len) = escape_at(&rest[i..]);
+        rest = &rest[i + len..];
+        let Escape::Sgr(params) = escape else {
+            continue;
+        };
+
+        let pos = result.text.len() as u32;
+        if !current.is_empty() && pos > current_start {
+            result.styles.push(AnsiStyleRun {
+                start: current_start,
+                end: pos,
+                sgr: current.clone(),
+            });
+        }
+        current_start = pos;
+
+        // A reset drops everything before it
+        if params.is_empty() || params == "0" {
+            current.clear();
+        } else if let Some(after) = last_reset(params) {
+            current.clear();
+            current.push_str(after);
+        } else {
+            if !current.is_empty() {
+                current.push(';');
+            }
+            current.push_str(params);
+        }
+    }
+
+    let end = result.text.len() as u32;
+    if !current.is_empty() && end > current_start {
+        result.styles.push(AnsiStyleRun {
+            start: current_start,
+            end,
+            sgr: current,
+        });
+    }
+    result
+}
+
+/// The parameters after the last reset (`0`) in an SGR parameter list.
+///
+/// Extended colors (`38;5;0`, `48;2;0;0;0`) contain zeros that aren't resets.
+fn last_reset(params: &str) -> Option<&str> {
+    let mut after = None;
+    let mut offset = 0;
+    let mut parts = params.split(';');
+    while let Some(part) = parts.next() {
+        offset += part.len() + 1;
+        match part {
+            "38" | "48" | "58" => {
+                let mode = parts.next().unwrap_or_default();
+                offset += mode.len() + 1;
+                let skip = match mode {
+                    "5" => 1,
+                    "2" => 3,
+                    _ => 0,
+                };
+                for part in parts.by_ref().take(skip) {
+                    offset += part.len() + 1;
+                }
+            }
+            "0" | "" => after = Some(params.get(offset..).unwrap_or_default()),
+            _ => {}
+        }
+    }
+    after
+}
+
+/// Render highlighted text as ANSI, keeping the colors the input came with.
+///
+/// `text` is the result of [`parse_ansi`] and `spans` the syntax highlighting
+/// of `text.text`. Regions the input styled keep their original SGR sequences;
+/// syntax colors are applied everywhere else.
+pub fn spans_to_ansi_merged(text: &AnsiText, spans: Vec<Span>, theme: &Theme) -> String {
+    let source = text.text.as_str();
+    let themed = spans_to_themed(spans);
+
+    // Every position where the style may change
+    let mut bounds: Vec<u32> = themed
+        .iter()
+        .flat_map(|s| [s.start, s.end])
+        .chain(text.styles.iter().flat_map(|s| [s.start, s.end]))
+        .chain([0, source.len() as u32])
+        .filter(|&b| b as usize <= source.len())
+        .collect();
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    let mut out = String::with_capacity(source.len() * 2);
+    let mut runs = text.styles.iter().peekable();
+    let mut next_span = 0;
+    // Syntax spans covering the current position
+    let mut active: Vec<usize> = Vec::new();
+
+    for window in bounds.windows(2) {
+        let (start, end) = (window[0], window[1]);
+        while runs.next_if(|r| r.end <= start).is_some() {}
+        active.retain(|&i| themed[i].end > start);
+        while next_span < themed.len() && themed[next_span].start <= start {
+            if themed[next_span].end > start {
+                active.push(next_span);
+            }
+            next_span += 1;
+        }
+
+        let escape = match runs.peek() {
+            Some(run) if run.start <= start => format!("\x1b[{}m", run.sgr),
+            // The innermost syntax span wins, like in the other renderers
+            _ => active
+                .iter()
+                .max_by_key(|&&i| (themed[i].start, Reverse(themed[i].end)))
+                .map(|&i| theme.ansi_style(themed[i].theme_index))
+                .unwrap_or_default(),
+        };
+
+        let segment = &source[start as usize..end as usize];
+        if escape.is_empty() {
+            out.push_str(segment);
+            continue;
+        }
+        out.push_str(&escape);
+        out.push_str(segment);
+        out.push_str(Theme::ANSI_RESET);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arborium_theme::{capture_to_slot, slot_to_highlight_index};
+
+    #[test]
+    fn test_strip_ansi() {
+        assert!(matches!(strip_ansi("plain"), Cow::Borrowed("plain")));
+        assert_eq!(strip_ansi("\x1b[1;31merror\x1b[0m: oops"), "error: oops");
+        assert_eq!(strip_ansi("a\x1b[2Kb\x1b7c"), "abc");
+        assert_eq!(
+            strip_ansi("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x07!"),
+            "link!"
+        );
+        // Truncated sequences are dropped, not echoed
+        assert_eq!(strip_ansi("x\x1b[31"), "x");
+        // A stray ESC before a multibyte character only drops the ESC
+        assert_eq!(strip_ansi("\x1bé"), "é");
+        assert_eq!(strip_ansi("\x1b]0;title\x1bé"), "é");
+        assert_eq!(parse_ansi("\x1b]8;;\x1b\u{1F980}").text, "\u{1F980}");
+    }
+
+    #[test]
+    fn test_parse_ansi() {
+        let parsed = parse_ansi("\x1b[1m\x1b[31merror\x1b[0m: \x1b[38;5;0mdim\x1b[m");
+        assert_eq!(parsed.text, "error: dim");
+        assert_eq!(
+            parsed.styles,
+            [
+                AnsiStyleRun {
+                    start: 0,
+                    end: 5,
+                    sgr: "1;31".to_string(),
+                },
+                AnsiStyleRun {
+                    start: 7,
+                    end: 10,
+                    sgr: "38;5;0".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_last_reset() {
+        assert_eq!(last_reset("1;31"), None);
+        assert_eq!(last_reset("0;31"), Some("31"));
+        assert_eq!(last_reset("31;0"), Some(""));
+        assert_eq!(last_reset("38;5;0;1"), None);
+        assert_eq!(last_reset("48;2;0;0;0;0;4"), Some("4"));
+    }
+
+    #[test]
+    fn test_merged_keeps_input_colors() {
+        let theme = arborium_theme::theme::builtin::catppuccin_mocha();
+        let parsed = parse_ansi("\x1b[31mfn\x1b[0m fn");
+        let span = |start, end| Span {
+            start,
+            end,
+            capture: "keyword".into(),
+        };
+        let out = spans_to_ansi_merged(&parsed, vec![span(0, 2), span(3, 5)], &theme);
+
+        let keyword =
+            theme.ansi_style(slot_to_highlight_index(capture_to_slot("keyword")).unwrap());
+        assert!(out.starts_with("\x1b[31mfn\x1b[0m "), "{out:?}");
+        assert!(out.ends_with(&format!("{keyword}fn\x1b[0m")), "{out:?}");
+    }
+}
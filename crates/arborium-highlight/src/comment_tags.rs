@@ -0,0 +1,132 @@
+//! Highlighting for tags in comments: `TODO:`, `FIXME(alice):`, `SAFETY:`.
+//!
+//! Grammars capture whole comments, so markers that editors make stand out
+//! get the same muted color as the rest of the comment. [`comment_tag_spans`]
+//! finds them inside comment spans and returns nested spans for them, which
+//! the renderers draw on top of the comment.
+//!
+//! A tag is an uppercase keyword at a word boundary, optionally followed by a
+//! parenthesized owner or issue, then a colon. Requiring the colon keeps
+//! prose like "NOTE that..." and identifiers like `TODO_LIST` plain.
+
+use crate::types::Span;
+
+/// Recognized tags and the capture each one gets.
+///
+/// The captures follow nvim-treesitter's `comment` grammar: `comment.todo`
+/// for work to do, `comment.error` for known bugs, `comment.warning` for
+/// things to be careful with and `comment.note` for explanations.
+pub const COMMENT_TAGS: &[(&str, &str)] = &[
+    ("TODO", "comment.todo"),
+    ("WIP", "comment.todo"),
+    ("FIXME", "comment.error"),
+    ("BUG", "comment.error"),
+    ("HACK", "comment.warning"),
+    ("XXX", "comment.warning"),
+    ("WARNING", "comment.warning"),
+    ("NOTE", "comment.note"),
+    ("INFO", "comment.note"),
+    ("SAFETY", "comment.note"),
+];
+
+/// Find comment tags inside the comment spans in `spans`.
+///
+/// Returns one span per tag, covering the keyword, the optional `(...)` and
+/// the colon. Spans whose capture doesn't start with `comment` are ignored.
+pub fn comment_tag_spans(source: &str, spans: &[Span]) -> Vec<Span> {
+    let mut tags = Vec::new();
+    for span in spans {
+        if !span.capture.starts_with("comment") {
+            continue;
+        }
+        let (start, end) = (span.start as usize, span.end as usize);
+        let Some(text) = source.get(start..end) else {
+            continue;
+        };
+        tags.extend(tags_in(text).map(|(tag_start, tag_end, capture)| Span {
+            start: (start + tag_start) as u32,
+            end: (start + tag_end) as u32,
+            capture: capture.into(),
+        }));
+    }
+    tags
+}
+
+/// Tags in one comment, as `(start, end, capture)` relative to `text`.
+fn tags_in(text: &str) -> impl Iterator<Item = (usize, usize, &'static str)> + '_ {
+    let bytes = text.as_bytes();
+    (0..bytes.len()).filter_map(move |i| {
+        if !bytes[i].is_ascii_uppercase() || (i > 0 && is_word_byte(bytes[i - 1])) {
+            return None;
+        }
+        let word_end = i + bytes[i..].iter().take_while(|&&b| is_word_byte(b)).count();
+        let (_, capture) = COMMENT_TAGS
+            .iter()
+            .find(|(tag, _)| tag.as_bytes() == &bytes[i..word_end])?;
+
+        let mut end = word_end;
+        if bytes.get(end) == Some(&b'(') {
+            end += bytes[end..].iter().position(|&b| b == b')' || b == b'\n')?;
+            if bytes[end] != b')' {
+                return None;
+            }
+            end += 1;
+        }
+        (bytes.get(end) == Some(&b':')).then_some((i, end + 1, *capture))
+    })
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(source: &str) -> Vec<(&str, String)> {
+        let comment = Span {
+            start: 0,
+            end: source.len() as u32,
+            capture: "comment".into(),
+        };
+        comment_tag_spans(source, &[comment])
+            .into_iter()
+            .map(|s| {
+                (
+                    &source[s.start as usize..s.end as usize],
+                    s.capture.into_owned(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_comment_tags() {
+        assert_eq!(
+            tags("// TODO: one\n// FIXME(#12): two, SAFETY: three"),
+            vec![
+                ("TODO:", "comment.todo".to_string()),
+                ("FIXME(#12):", "comment.error".to_string()),
+                ("SAFETY:", "comment.note".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comment_tags_need_a_colon() {
+        assert!(tags("// NOTE that TODO_LIST and XTODO: are not tags").is_empty());
+        assert!(tags("// TODO(unclosed: nope").is_empty());
+    }
+
+    #[test]
+    fn test_comment_tags_only_in_comments() {
+        let source = "\"TODO: string\"";
+        let string = Span {
+            start: 0,
+            end: source.len() as u32,
+            capture: "string".into(),
+        };
+        assert!(comment_tag_spans(source, &[string]).is_empty());
+    }
+}
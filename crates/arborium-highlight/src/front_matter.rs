@@ -0,0 +1,158 @@
+//! Front matter detection for markup documents.
+//!
+//! Static site sources usually start with a metadata block in another
+//! language: YAML between `---` lines, or TOML between `+++` lines.
+//!
+//! ```text
+//! ---
+//! title: Hello
+//! ---
+//! # Markdown starts here
+//! ```
+//!
+//! Parsed as markdown, that block comes out as a thematic break and a setext
+//! heading. The highlighters split it off instead: the delimiters become
+//! punctuation, the content is injected as YAML or TOML, and only the rest of
+//! the document goes through the markdown grammar.
+
+use crate::types::{Injection, Span};
+
+/// A front matter block at the very start of a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontMatter<'a> {
+    /// Language of the content: `"yaml"` for `---`, `"toml"` for `+++`.
+    pub language: &'static str,
+    /// The text between the delimiter lines.
+    pub content: &'a str,
+    /// Byte offset of `content` in the source.
+    pub content_start: usize,
+    /// Everything after the closing delimiter line.
+    pub body: &'a str,
+    /// Byte offset of `body` in the source.
+    pub body_start: usize,
+    /// Byte range of the closing delimiter, without its line ending.
+    closing: (usize, usize),
+}
+
+impl FrontMatter<'_> {
+    /// Spans for the opening and closing delimiters.
+    pub fn delimiter_spans(&self) -> [Span; 2] {
+        let delimiter = |start: usize, end: usize| Span {
+            start: start as u32,
+            end: end as u32,
+            capture: "punctuation.delimiter".into(),
+        };
+        [delimiter(0, 3), delimiter(self.closing.0, self.closing.1)]
+    }
+
+    /// An injection covering the content, in source coordinates.
+    pub fn injection(&self) -> Injection {
+        Injection {
+            start: self.content_start as u32,
+            end: (self.content_start + self.content.len()) as u32,
+            language: self.language.to_string(),
+            include_children: false,
+        }
+    }
+}
+
+/// Whether documents in `language` may start with front matter.
+pub fn supports_front_matter(language: &str) -> bool {
+    matches!(language, "markdown" | "md" | "mdx")
+}
+
+/// Detect YAML (`---`) or TOML (`+++`) front matter at the start of `source`.
+///
+/// The opening delimiter must be the first line and the block must be closed
+/// by a line with the same delimiter (or `...` for YAML). Returns `None` if
+/// there is no complete front matter block.
+pub fn split_front_matter(source: &str) -> Option<FrontMatter<'_>> {
+    let (delimiter, language) = if source.starts_with("---") {
+        ("---", "yaml")
+    } else if source.starts_with("+++") {
+        ("+++", "toml")
+    } else {
+        return None;
+    };
+
+    let first_line_end = source.find('\n')?;
+    if source[..first_line_end].trim_end() != delimiter {
+        return None;
+    }
+
+    let content_start = first_line_end + 1;
+    let mut line_start = content_start;
+    while line_start < source.len() {
+        let line_end = source[line_start..]
+            .find('\n')
+            .map_or(source.len(), |i| line_start + i);
+        let line = source[line_start..line_end].trim_end();
+
+        if line == delimiter || (language == "yaml" && line == "...") {
+            let body_start = (line_end + 1).min(source.len());
+            return Some(FrontMatter {
+                language,
+                content: &source[content_start..line_start],
+                content_start,
+                body: &source[body_start..],
+                body_start,
+                closing: (line_start, line_start + line.len()),
+            });
+        }
+        line_start = line_end + 1;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaml_front_matter() {
+        let source = "---\ntitle: Hi\n---\n# Body\n";
+        let fm = split_front_matter(source).unwrap();
+        assert_eq!(fm.language, "yaml");
+        assert_eq!(fm.content, "title: Hi\n");
+        assert_eq!(fm.body, "# Body\n");
+        assert_eq!(&source[fm.body_start..], fm.body);
+
+        let [open, close] = fm.delimiter_spans();
+        assert_eq!((open.start, open.end), (0, 3));
+        assert_eq!(&source[close.start as usize..close.end as usize], "---");
+
+        let injection = fm.injection();
+        assert_eq!(injection.language, "yaml");
+        assert_eq!(
+            &source[injection.start as usize..injection.end as usize],
+            "title: Hi\n"
+        );
+    }
+
+    #[test]
+    fn test_toml_front_matter_crlf() {
+        let source = "+++\r\ntitle = \"Hi\"\r\n+++\r\nBody";
+        let fm = split_front_matter(source).unwrap();
+        assert_eq!(fm.language, "toml");
+        assert_eq!(fm.content, "title = \"Hi\"\r\n");
+        assert_eq!(fm.body, "Body");
+    }
+
+    #[test]
+    fn test_no_front_matter() {
+        // Thematic break, not front matter
+        assert_eq!(split_front_matter("---\n\nSome text\n"), None);
+        assert_eq!(split_front_matter("----\na: b\n----\n"), None);
+        assert_eq!(split_front_matter("# Title\n---\n"), None);
+        // Unclosed
+        assert_eq!(split_front_matter("+++\ntitle = 1\n"), None);
+    }
+
+    #[test]
+    fn test_front_matter_at_end_of_file() {
+        let fm = split_front_matter("---\na: b\n...").unwrap();
+        assert_eq!(fm.content, "a: b\n");
+        assert_eq!(fm.body, "");
+    }
+}
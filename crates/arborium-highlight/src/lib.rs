@@ -99,20 +99,57 @@
 //!
 //! See [`HtmlFormat`] for examples and use cases.
 
+mod ansi_input;
+mod comment_tags;
+mod front_matter;
+mod links;
+#[cfg(feature = "tree-sitter")]
+mod navigation;
+mod normalize;
+mod overlay;
+mod pairs;
+mod plain;
+#[cfg(feature = "tree-sitter")]
+mod query_cache;
 mod render;
 mod types;
+mod whitespace;
 
 #[cfg(feature = "tree-sitter")]
 pub mod tree_sitter;
 
+pub use ansi_input::{AnsiStyleRun, AnsiText, parse_ansi, spans_to_ansi_merged, strip_ansi};
+pub use arborium_syntax::{
+    CommentEdit, CommentSyntax, ProseClass, ProseRange, prose_ranges, toggle_comments,
+};
+pub use comment_tags::{COMMENT_TAGS, comment_tag_spans};
+pub use front_matter::{FrontMatter, split_front_matter, supports_front_matter};
+pub use links::{LINK_PATH_CAPTURE, LINK_URL_CAPTURE, is_linkable_url, link_spans};
+pub use normalize::{LineEndings, NormalizedInput};
+pub use overlay::{MATCH_CAPTURE, write_spans_as_html_with_overlay};
+pub use pairs::Pairs;
+pub use plain::{GENERIC_CAPTURES, generic_spans, generic_spans_into, is_generic, is_plain_text};
 pub use render::{
     AnsiOptions, ThemedSpan, html_escape, spans_to_ansi, spans_to_ansi_with_options, spans_to_html,
-    spans_to_themed, write_spans_as_ansi, write_spans_as_html,
+    spans_to_themed, write_spans_as_ansi, write_spans_as_html, write_spans_as_html_chunked,
+    write_spans_as_html_fmt, write_spans_as_html_with_links,
 };
 pub use types::{HighlightError, Injection, ParseResult, Span};
+pub use whitespace::{DEFAULT_TAB_WIDTH, RenderOptions, WHITESPACE_CAPTURE, apply_render_options};
+
+#[cfg(feature = "tree-sitter")]
+pub use tree_sitter::{
+    CaptureExplanation, CompiledGrammar, GrammarConfig, GrammarError, ParseContext, ParserPool,
+    PooledParseContext, QueryCache, QueryKind, RAINBOW_CAPTURES, SexpOptions, Token, check_abi,
+    tree_sexp,
+};
 
 #[cfg(feature = "tree-sitter")]
-pub use tree_sitter::{CompiledGrammar, GrammarConfig, GrammarError, ParseContext};
+pub use arborium_syntax::{
+    FoldingKind, FoldingRange, SYMBOL_KINDS, Symbol, expand_selection, folding_ranges, symbols,
+};
+#[cfg(feature = "tree-sitter")]
+pub use navigation::{Motion, navigate};
 
 // Backward compatibility aliases
 #[cfg(feature = "tree-sitter")]
@@ -248,6 +285,13 @@ pub struct HighlightConfig {
 
     /// HTML output format (custom elements vs class-based spans).
     pub html_format: HtmlFormat,
+
+    /// Maximum bytes of text in a single HTML element, if any.
+    ///
+    /// Longer runs are split into several elements, see
+    /// [`write_spans_as_html_chunked`]. Useful for minified sources with
+    /// very long lines.
+    pub max_chunk_len: Option<usize>,
 }
 
 impl Default for HighlightConfig {
@@ -255,6 +299,7 @@ impl Default for HighlightConfig {
         Self {
             max_injection_depth: 3,
             html_format: HtmlFormat::default(),
+            max_chunk_len: None,
         }
     }
 }
@@ -295,17 +340,39 @@ impl<P: GrammarProvider> HighlighterCore<P> {
             .await
             .ok_or_else(|| HighlightError::UnsupportedLanguage(language.into()))?;
 
-        // 2. Parse the primary language
-        let result = grammar.parse(source);
+        // 2. Parse the primary language, minus any front matter
+        let front_matter = if supports_front_matter(language) {
+            split_front_matter(source)
+        } else {
+            None
+        };
+        let (body, body_start) = match &front_matter {
+            Some(fm) => (fm.body, fm.body_start as u32),
+            None => (source, 0),
+        };
+        let result = grammar.parse(body);
 
         // 3. Collect all spans (including from injections)
         let mut all_spans = result.spans;
+        let mut injections = result.injections;
+        for span in &mut all_spans {
+            span.start += body_start;
+            span.end += body_start;
+        }
+        for injection in &mut injections {
+            injection.start += body_start;
+            injection.end += body_start;
+        }
+        if let Some(fm) = &front_matter {
+            all_spans.extend(fm.delimiter_spans());
+            injections.push(fm.injection());
+        }
 
         // 4. Process injections recursively
         if self.config.max_injection_depth > 0 {
             self.process_injections(
                 source,
-                result.injections,
+                injections,
                 0,
                 self.config.max_injection_depth,
                 &mut all_spans,
@@ -319,7 +386,16 @@ impl<P: GrammarProvider> HighlighterCore<P> {
     /// The main highlight function - written once, used by both wrappers.
     async fn highlight(&mut self, language: &str, source: &str) -> Result<String, HighlightError> {
         let spans = self.highlight_spans(language, source).await?;
-        Ok(spans_to_html(source, spans, &self.config.html_format))
+        let format = &self.config.html_format;
+        Ok(match self.config.max_chunk_len {
+            Some(max) => {
+                let mut html = String::with_capacity(source.len() * 2);
+                // Writing to a String never fails
+                let _ = write_spans_as_html_chunked(&mut html, source, spans, format, max);
+                html
+            }
+            None => spans_to_html(source, spans, format),
+        })
     }
 
     /// Process injections recursively.
@@ -638,6 +714,51 @@ mod tests {
         assert_eq!(html, "<a-s>hello</a-s>");
     }
 
+    #[test]
+    fn test_front_matter_injection() {
+        let provider = MockProvider {
+            grammars: [
+                (
+                    "markdown",
+                    MockGrammar {
+                        result: ParseResult {
+                            // "Body" is a heading, relative to the body text
+                            spans: vec![Span {
+                                start: 0,
+                                end: 4,
+                                capture: "text.title".into(),
+                            }],
+                            injections: vec![],
+                        },
+                    },
+                ),
+                (
+                    "yaml",
+                    MockGrammar {
+                        result: ParseResult {
+                            spans: vec![Span {
+                                start: 0,
+                                end: 1,
+                                capture: "property".into(),
+                            }],
+                            injections: vec![],
+                        },
+                    },
+                ),
+            ]
+            .into(),
+        };
+
+        let mut highlighter = SyncHighlighter::new(provider);
+        let html = highlighter
+            .highlight("markdown", "---\na: 1\n---\nBody")
+            .unwrap();
+        assert_eq!(
+            html,
+            "<a-p>---</a-p>\n<a-pr>a</a-pr>: 1\n<a-p>---</a-p>\n<a-tt>Body</a-tt>"
+        );
+    }
+
     #[test]
     fn test_unsupported_language() {
         let provider = MockProvider {
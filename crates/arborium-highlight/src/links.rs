@@ -0,0 +1,226 @@
+//! Links in comments and strings: URLs and file paths.
+//!
+//! Grammars see `// see https://example.com/issue/12` as one comment, so the
+//! URL can't be styled or clicked on its own. [`link_spans`] finds URLs and
+//! file paths inside comment and string spans and returns nested spans for
+//! them: [`LINK_URL_CAPTURE`] for URLs and [`LINK_PATH_CAPTURE`] for paths.
+//! The HTML renderers can also wrap URLs in `<a href>`; see
+//! [`write_spans_as_html_with_links`](crate::write_spans_as_html_with_links).
+
+use crate::types::Span;
+
+/// Capture for URLs found by [`link_spans`].
+pub const LINK_URL_CAPTURE: &str = "markup.link.url";
+
+/// Capture for file paths found by [`link_spans`].
+pub const LINK_PATH_CAPTURE: &str = "markup.link";
+
+/// URL schemes that are recognized in text and turned into links.
+const SCHEMES: &[&str] = &["https://", "http://", "ftp://", "file://", "mailto:"];
+
+/// Find URLs and file paths inside the comment and string spans in `spans`.
+///
+/// URLs start with one of the usual schemes (`https://`, `mailto:`, ...)
+/// and end at whitespace or a quote; trailing punctuation and unbalanced
+/// closing brackets are left out, so `(see https://x.org/a_(b))` and
+/// `https://x.org.` come out right. Paths are either rooted (`/etc/hosts`,
+/// `./run.sh`, `../lib`, `~/.config/app`) or relative with a file extension
+/// (`src/main.rs`), which keeps `and/or` and `1/2` plain.
+pub fn link_spans(source: &str, spans: &[Span]) -> Vec<Span> {
+    let mut links = Vec::new();
+    for span in spans {
+        let capture = &*span.capture;
+        let in_text = capture.starts_with("comment")
+            || (capture.starts_with("string") && !capture.starts_with("string.special"));
+        if !in_text {
+            continue;
+        }
+        let (start, end) = (span.start as usize, span.end as usize);
+        let Some(text) = source.get(start..end) else {
+            continue;
+        };
+        for (link_start, link_end, capture) in links_in(text) {
+            links.push(Span {
+                start: (start + link_start) as u32,
+                end: (start + link_end) as u32,
+                capture: capture.into(),
+            });
+        }
+    }
+    links
+}
+
+/// Whether `text` is a URL that can be used as a link target as-is.
+pub fn is_linkable_url(text: &str) -> bool {
+    SCHEMES
+        .iter()
+        .any(|scheme| text.len() > scheme.len() && text.starts_with(scheme))
+}
+
+/// Links in one comment or string, as `(start, end, capture)` relative to `text`.
+fn links_in(text: &str) -> Vec<(usize, usize, &'static str)> {
+    let bytes = text.as_bytes();
+    let mut links = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let at_boundary = i == 0 || is_boundary(bytes[i - 1]);
+        if !at_boundary {
+            i += 1;
+            continue;
+        }
+
+        let rest = &text[i..];
+        if let Some(scheme) = SCHEMES.iter().find(|s| rest.starts_with(*s)) {
+            let len = rest
+                .bytes()
+                .position(|b| b.is_ascii_whitespace() || b"\"'`<>".contains(&b))
+                .unwrap_or(rest.len());
+            let len = trim_url_end(&rest[..len]);
+            if len > scheme.len() {
+                links.push((i, i + len, LINK_URL_CAPTURE));
+                i += len;
+                continue;
+            }
+        } else if let Some(len) = path_len(rest) {
+            links.push((i, i + len, LINK_PATH_CAPTURE));
+            i += len;
+            continue;
+        }
+
+        i += 1;
+    }
+
+    links
+}
+
+/// Whether a link may start right after byte `b`.
+fn is_boundary(b: u8) -> bool {
+    b.is_ascii_whitespace() || b"\"'`([{<=,:;".contains(&b)
+}
+
+/// Length of `url` without trailing punctuation and unbalanced closing brackets.
+fn trim_url_end(url: &str) -> usize {
+    let mut end = url.len();
+    while let Some(&last) = url.as_bytes()[..end].last() {
+        let open = match last {
+            b')' => b'(',
+            b']' => b'[',
+            b'}' => b'{',
+            b'.' | b',' | b';' | b':' | b'!' | b'?' => {
+                end -= 1;
+                continue;
+            }
+            _ => break,
+        };
+        let body = &url.as_bytes()[..end];
+        let opened = body.iter().filter(|&&b| b == open).count();
+        let closed = body.iter().filter(|&&b| b == last).count();
+        if closed > opened {
+            end -= 1;
+        } else {
+            break;
+        }
+    }
+    end
+}
+
+/// Length of the file path at the start of `text`, if there is one.
+fn path_len(text: &str) -> Option<usize> {
+    let len = text
+        .bytes()
+        .position(|b| !(b.is_ascii_alphanumeric() || b"_.-~/+".contains(&b)))
+        .unwrap_or(text.len());
+    let path = text[..len].trim_end_matches(['.', ',']);
+    if path.contains("//") {
+        return None;
+    }
+
+    let rooted = ["./", "../", "~/", "/"]
+        .iter()
+        .find_map(|prefix| path.strip_prefix(prefix));
+    let valid = match rooted {
+        // `/usr/bin` but not `/2` or a lone `/x`
+        Some(rest) if path.starts_with('/') => {
+            rest.contains('/') && rest.bytes().any(|b| b.is_ascii_alphabetic())
+        }
+        Some(rest) => rest.bytes().any(|b| b.is_ascii_alphanumeric()),
+        // `src/main.rs` but not `and/or`
+        None => {
+            let (dir, file) = path.rsplit_once('/')?;
+            let (stem, ext) = file.rsplit_once('.')?;
+            !dir.is_empty()
+                && !stem.is_empty()
+                && (1..=5).contains(&ext.len())
+                && ext.as_bytes()[0].is_ascii_alphabetic()
+                && ext.bytes().all(|b| b.is_ascii_alphanumeric())
+        }
+    };
+    valid.then_some(path.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn links(source: &str) -> Vec<(&str, String)> {
+        let comment = Span {
+            start: 0,
+            end: source.len() as u32,
+            capture: "comment".into(),
+        };
+        link_spans(source, &[comment])
+            .into_iter()
+            .map(|s| {
+                (
+                    &source[s.start as usize..s.end as usize],
+                    s.capture.into_owned(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_urls() {
+        assert_eq!(
+            links("// see https://example.com/a?b=c#d. Or (mailto:me@example.com)"),
+            vec![
+                ("https://example.com/a?b=c#d", LINK_URL_CAPTURE.to_string()),
+                ("mailto:me@example.com", LINK_URL_CAPTURE.to_string()),
+            ]
+        );
+        assert_eq!(
+            links("# https://en.wikipedia.org/wiki/Rust_(language)"),
+            vec![(
+                "https://en.wikipedia.org/wiki/Rust_(language)",
+                LINK_URL_CAPTURE.to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_paths() {
+        assert_eq!(
+            links("// reads /etc/hosts, ./run.sh and src/main.rs"),
+            vec![
+                ("/etc/hosts", LINK_PATH_CAPTURE.to_string()),
+                ("./run.sh", LINK_PATH_CAPTURE.to_string()),
+                ("src/main.rs", LINK_PATH_CAPTURE.to_string()),
+            ]
+        );
+        assert!(links("// and/or 1/2 /x a/b.5").is_empty());
+    }
+
+    #[test]
+    fn test_only_comments_and_strings() {
+        let source = "https://example.com";
+        let keyword = Span {
+            start: 0,
+            end: source.len() as u32,
+            capture: "keyword".into(),
+        };
+        assert!(link_spans(source, &[keyword]).is_empty());
+        assert!(is_linkable_url(source));
+        assert!(!is_linkable_url("https://"));
+    }
+}
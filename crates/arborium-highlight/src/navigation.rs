@@ -0,0 +1,165 @@
+//! Moving through code by syntax unit, like editors' "next function" and
+//! "go to enclosing block" commands.
+//!
+//! Units come from the grammar's `textobjects.scm` where it has one, with
+//! the captures of nvim-treesitter-textobjects: `@function.outer` around a
+//! whole function, `@class.outer` and `@block.outer` (Helix's `.around`
+//! works too). Units the grammar has no captures for are read off the shape
+//! of the syntax tree, like [`symbols`](crate::symbols): functions and
+//! classes are definitions with a `name` field, blocks are nodes whose kind
+//! mentions a block or body.
+
+use std::cmp::Reverse;
+use std::ops::Range;
+
+use arborium_tree_sitter::{Node, Query, QueryCursor, Tree};
+use streaming_iterator::StreamingIterator;
+
+use arborium_syntax::symbol_kind;
+
+/// A way of moving through code, for [`navigate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Motion {
+    /// To the next function or method starting after the offset.
+    NextFunction,
+    /// To the last function or method starting before the offset.
+    PrevFunction,
+    /// To the next class, struct, interface or enum starting after the offset.
+    NextClass,
+    /// To the last class, struct, interface or enum starting before the offset.
+    PrevClass,
+    /// Out to the innermost function around the offset.
+    EnclosingFunction,
+    /// Out to the innermost class around the offset.
+    EnclosingClass,
+    /// Out to the innermost block around the offset, like a loop's body.
+    EnclosingBlock,
+}
+
+/// What a [`Motion`] moves between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Function,
+    Class,
+    Block,
+}
+
+impl Motion {
+    fn unit(self) -> Unit {
+        match self {
+            Motion::NextFunction | Motion::PrevFunction | Motion::EnclosingFunction => {
+                Unit::Function
+            }
+            Motion::NextClass | Motion::PrevClass | Motion::EnclosingClass => Unit::Class,
+            Motion::EnclosingBlock => Unit::Block,
+        }
+    }
+}
+
+impl Unit {
+    /// The textobjects captures around the whole unit.
+    fn captures(self) -> [&'static str; 2] {
+        match self {
+            Unit::Function => ["function.outer", "function.around"],
+            Unit::Class => ["class.outer", "class.around"],
+            Unit::Block => ["block.outer", "block.around"],
+        }
+    }
+
+    /// Whether `node` is the unit, for grammars without captures for it.
+    fn is(self, node: Node) -> bool {
+        match self {
+            Unit::Function => {
+                matches!(
+                    symbol_kind(node),
+                    Some("function" | "method" | "constructor")
+                )
+            }
+            Unit::Class => {
+                matches!(
+                    symbol_kind(node),
+                    Some("class" | "interface" | "struct" | "enum")
+                )
+            }
+            Unit::Block => {
+                node.is_named()
+                    && node
+                        .kind()
+                        .split('_')
+                        .any(|word| word == "block" || word == "body")
+            }
+        }
+    }
+}
+
+/// The byte range of the syntax unit `motion` moves to from `offset`, in
+/// `tree` parsed from `source`, or `None` if there is none.
+///
+/// `textobjects` is the grammar's compiled `textobjects.scm`, if any. Of
+/// units starting at the same place, like a decorated function and the
+/// function itself, the outermost is picked.
+pub fn navigate(
+    tree: &Tree,
+    source: &str,
+    textobjects: Option<&Query>,
+    offset: usize,
+    motion: Motion,
+) -> Option<Range<usize>> {
+    let units = units(tree, source, textobjects, motion.unit()).into_iter();
+    match motion {
+        Motion::NextFunction | Motion::NextClass => units
+            .filter(|range| range.start > offset)
+            .min_by_key(|range| (range.start, Reverse(range.end))),
+        Motion::PrevFunction | Motion::PrevClass => units
+            .filter(|range| range.start < offset)
+            .max_by_key(|range| (range.start, range.end)),
+        Motion::EnclosingFunction | Motion::EnclosingClass | Motion::EnclosingBlock => units
+            .filter(|range| range.start <= offset && offset < range.end)
+            .min_by_key(|range| range.len()),
+    }
+}
+
+/// The byte ranges of every `unit` in the tree.
+fn units(tree: &Tree, source: &str, textobjects: Option<&Query>, unit: Unit) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+
+    let query = textobjects.and_then(|query| {
+        let indices: Vec<u32> = unit
+            .captures()
+            .iter()
+            .filter_map(|name| query.capture_index_for_name(name))
+            .collect();
+        (!indices.is_empty()).then_some((query, indices))
+    });
+    if let Some((query, indices)) = query {
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(query, tree.root_node(), source.as_bytes());
+        while let Some(m) = matches.next() {
+            // A quantified capture, like comments before a function, is one unit
+            let range = m
+                .captures
+                .iter()
+                .filter(|capture| indices.contains(&capture.index))
+                .map(|capture| capture.node.byte_range())
+                .reduce(|a, b| a.start.min(b.start)..a.end.max(b.end));
+            ranges.extend(range);
+        }
+        return ranges;
+    }
+
+    let mut cursor = tree.walk();
+    loop {
+        let node = cursor.node();
+        if unit.is(node) {
+            ranges.push(node.byte_range());
+        }
+        if cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return ranges;
+            }
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Input normalization: byte order marks and line endings.
+//!
+//! Files written on Windows often start with a UTF-8 BOM and use `\r\n`
+//! line endings. Grammars disagree on where the `\r` belongs: some end a
+//! line comment before it, some after, so span boundaries around line ends
+//! are off by one depending on the language. [`NormalizedInput`] gives the
+//! highlighters one consistent view of the text and maps spans found in it
+//! back to the caller's offsets.
+
+use std::borrow::Cow;
+
+use crate::types::Span;
+
+/// The UTF-8 byte order mark.
+const BOM: &str = "\u{feff}";
+
+/// How to treat `\r\n` and lone `\r` line endings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEndings {
+    /// Keep line endings as they are.
+    ///
+    /// Spans never end with the `\r` of a `\r\n` pair, whatever the grammar
+    /// does, so renderers see the same boundaries as for `\n` input.
+    #[default]
+    Preserve,
+    /// Parse and render `\r\n` and lone `\r` as `\n`.
+    ///
+    /// Spans are still reported in offsets of the original text, where a
+    /// span ending after a line break covers the whole `\r\n`.
+    Lf,
+}
+
+/// Source text prepared for parsing, with a mapping back to the original.
+#[derive(Debug, Clone)]
+pub struct NormalizedInput<'a> {
+    text: Cow<'a, str>,
+    /// Bytes removed from the start of the original (the BOM, if stripped).
+    offset: u32,
+    /// Offsets in `text` of the `\n`s that had their `\r` removed.
+    removed_crs: Vec<u32>,
+    line_endings: LineEndings,
+}
+
+impl<'a> NormalizedInput<'a> {
+    /// Normalize `source`, optionally stripping a leading BOM.
+    ///
+    /// Borrows `source` when nothing needs to change.
+    pub fn new(source: &'a str, strip_bom: bool, line_endings: LineEndings) -> Self {
+        let (source, offset) = match source.strip_prefix(BOM) {
+            Some(rest) if strip_bom => (rest, BOM.len() as u32),
+            _ => (source, 0),
+        };
+
+        let mut removed_crs = Vec::new();
+        let text = if line_endings == LineEndings::Lf && source.contains('\r') {
+            let mut text = String::with_capacity(source.len());
+            let mut rest = source;
+            while let Some(cr) = rest.find('\r') {
+                text.push_str(&rest[..cr]);
+                if rest[cr + 1..].starts_with('\n') {
+                    removed_crs.push(text.len() as u32);
+                } else {
+                    text.push('\n');
+                }
+                rest = &rest[cr + 1..];
+            }
+            text.push_str(rest);
+            Cow::Owned(text)
+        } else {
+            Cow::Borrowed(source)
+        };
+
+        Self {
+            text,
+            offset,
+            removed_crs,
+            line_endings,
+        }
+    }
+
+    /// The text to parse and render.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Convert an offset in [`text`](Self::text) to one in the original source.
+    pub fn to_original(&self, offset: u32) -> u32 {
+        let removed = self.removed_crs.partition_point(|&n| n < offset);
+        offset + removed as u32 + self.offset
+    }
+
+    /// Convert an offset in the original source to one in [`text`](Self::text).
+    ///
+    /// Offsets inside a stripped BOM map to the start, and the `\n` of a
+    /// `\r\n` pair maps to the same place as its `\r`.
+    pub fn to_normalized(&self, offset: u32) -> u32 {
+        // The k-th removed `\r` was at `removed_crs[k] + k` in the source
+        // without its BOM
+        let offset = offset.saturating_sub(self.offset);
+        let removed = self
+            .removed_crs
+            .iter()
+            .enumerate()
+            .take_while(|&(k, &n)| n + (k as u32) < offset)
+            .count();
+        offset - removed as u32
+    }
+
+    /// Convert spans found in [`text`](Self::text) to original source offsets.
+    ///
+    /// With [`LineEndings::Preserve`], this is also where spans that end with
+    /// the `\r` of a `\r\n` pair are shortened to end before it.
+    pub fn spans_to_original(&self, spans: &mut [Span]) {
+        let bytes = self.text.as_bytes();
+        for span in spans {
+            if self.line_endings == LineEndings::Preserve {
+                let end = span.end as usize;
+                if end > span.start as usize
+                    && bytes.get(end - 1) == Some(&b'\r')
+                    && matches!(bytes.get(end), Some(b'\n') | None)
+                {
+                    span.end -= 1;
+                }
+            }
+            span.start = self.to_original(span.start);
+            span.end = self.to_original(span.end);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: u32, end: u32) -> Span {
+        Span {
+            start,
+            end,
+            capture: "comment".into(),
+        }
+    }
+
+    #[test]
+    fn test_unchanged_input_is_borrowed() {
+        let input = NormalizedInput::new("a\r\nb", true, LineEndings::Preserve);
+        assert!(matches!(input.text, Cow::Borrowed("a\r\nb")));
+        assert_eq!(input.to_original(3), 3);
+    }
+
+    #[test]
+    fn test_strip_bom() {
+        let source = "\u{feff}// hi";
+        let input = NormalizedInput::new(source, true, LineEndings::Preserve);
+        assert_eq!(input.text(), "// hi");
+        let mut spans = [span(0, 5)];
+        input.spans_to_original(&mut spans);
+        assert_eq!(
+            &source[spans[0].start as usize..spans[0].end as usize],
+            "// hi"
+        );
+
+        let input = NormalizedInput::new(source, false, LineEndings::Preserve);
+        assert_eq!(input.text(), source);
+    }
+
+    #[test]
+    fn test_preserve_trims_cr_from_span_ends() {
+        let source = "// a\r\nx // b\r";
+        let input = NormalizedInput::new(source, true, LineEndings::Preserve);
+        let mut spans = [span(0, 5), span(7, 13), span(0, 6)];
+        input.spans_to_original(&mut spans);
+        assert_eq!((spans[0].start, spans[0].end), (0, 4));
+        assert_eq!((spans[1].start, spans[1].end), (7, 12));
+        // Spans covering the whole line ending keep it.
+        assert_eq!((spans[2].start, spans[2].end), (0, 6));
+    }
+
+    #[test]
+    fn test_lf_maps_back_to_crlf() {
+        let source = "\u{feff}a\r\nb\rc\r\n";
+        let input = NormalizedInput::new(source, true, LineEndings::Lf);
+        assert_eq!(input.text(), "a\nb\nc\n");
+
+        let mut spans = [span(0, 1), span(1, 2), span(2, 3), span(4, 6)];
+        input.spans_to_original(&mut spans);
+        let texts: Vec<_> = spans
+            .iter()
+            .map(|s| &source[s.start as usize..s.end as usize])
+            .collect();
+        assert_eq!(texts, ["a", "\r\n", "b", "c\r\n"]);
+
+        let normalized: Vec<_> = (0..=source.len() as u32)
+            .map(|offset| input.to_normalized(offset))
+            .collect();
+        assert_eq!(normalized, [0, 0, 0, 0, 1, 1, 2, 3, 4, 5, 5, 6]);
+    }
+}
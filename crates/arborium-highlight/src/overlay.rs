@@ -0,0 +1,194 @@
+//! Ranges drawn on top of syntax highlighting, such as search matches.
+//!
+//! The HTML renderers give each piece of text a single element, so a search
+//! match added as one more span would replace the syntax colour of the text
+//! it covers. [`write_spans_as_html_with_overlay`] keeps the two apart: each
+//! overlay span becomes an element around the syntax elements inside it, and
+//! syntax spans that cross an overlay boundary are split in two.
+//!
+//! ```text
+//! <a-k>fn</a-k> <a-mh><a-f>ma</a-f></a-mh><a-f>in</a-f>() {}
+//! ```
+
+use std::fmt;
+
+use arborium_theme::tag_for_capture;
+
+use crate::HtmlFormat;
+use crate::render::{make_html_tags, write_html};
+use crate::types::Span;
+
+/// Capture for search matches and other highlighted ranges.
+pub const MATCH_CAPTURE: &str = "markup.highlight";
+
+/// Like [`write_spans_as_html_with_links`](crate::write_spans_as_html_with_links),
+/// with `overlay` spans, such as [`MATCH_CAPTURE`] spans for search matches,
+/// drawn on top of the syntax `spans`.
+///
+/// Overlay spans are moved out to `char` boundaries and clamped to `source`.
+/// Where they overlap, the shortest one wins, and adjacent overlay spans
+/// with the same theme slot are merged into one element. Overlay spans
+/// whose capture has no theme slot are ignored. URLs are only wrapped in
+/// `<a href>` if `links` is set.
+pub fn write_spans_as_html_with_overlay<W: fmt::Write>(
+    w: &mut W,
+    source: &str,
+    spans: Vec<Span>,
+    overlay: Vec<Span>,
+    format: &HtmlFormat,
+    max_chunk_len: Option<usize>,
+    links: bool,
+) -> fmt::Result {
+    for (start, end, tag) in overlay_pieces(source, overlay) {
+        let text = &source[start..end];
+        let spans = clip_spans(&spans, start as u32, end as u32);
+        match tag {
+            Some(tag) => {
+                let (open_tag, close_tag) = make_html_tags(tag, format);
+                w.write_str(&open_tag)?;
+                write_html(w, text, spans, format, max_chunk_len, links)?;
+                w.write_str(&close_tag)?;
+            }
+            None => write_html(w, text, spans, format, max_chunk_len, links)?,
+        }
+    }
+    Ok(())
+}
+
+/// Cut `source` into `(start, end, tag)` pieces at the overlay boundaries,
+/// where `tag` is the theme slot tag of the overlay span covering the piece.
+fn overlay_pieces(source: &str, overlay: Vec<Span>) -> Vec<(usize, usize, Option<&'static str>)> {
+    let overlay: Vec<(usize, usize, &'static str)> = overlay
+        .into_iter()
+        .filter_map(|span| {
+            let tag = tag_for_capture(&span.capture)?;
+            let start = floor_char_boundary(source, span.start as usize);
+            let end = ceil_char_boundary(source, span.end as usize);
+            (start < end).then_some((start, end, tag))
+        })
+        .collect();
+
+    let mut bounds: Vec<usize> = overlay
+        .iter()
+        .flat_map(|&(start, end, _)| [start, end])
+        .chain([0, source.len()])
+        .collect();
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    let mut pieces: Vec<(usize, usize, Option<&'static str>)> = Vec::new();
+    for pair in bounds.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        let tag = overlay
+            .iter()
+            .filter(|&&(s, e, _)| s <= start && end <= e)
+            .min_by_key(|&&(s, e, _)| e - s)
+            .map(|&(_, _, tag)| tag);
+        match pieces.last_mut() {
+            Some(last) if last.2 == tag => last.1 = end,
+            _ => pieces.push((start, end, tag)),
+        }
+    }
+    pieces
+}
+
+/// The parts of `spans` between `start` and `end`, relative to `start`.
+fn clip_spans(spans: &[Span], start: u32, end: u32) -> Vec<Span> {
+    spans
+        .iter()
+        .filter(|span| span.start < end && span.end > start)
+        .map(|span| Span {
+            start: span.start.max(start) - start,
+            end: span.end.min(end) - start,
+            capture: span.capture.clone(),
+        })
+        .collect()
+}
+
+fn floor_char_boundary(text: &str, mut offset: usize) -> usize {
+    offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+fn ceil_char_boundary(text: &str, mut offset: usize) -> usize {
+    offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset += 1;
+    }
+    offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: u32, end: u32, capture: &'static str) -> Span {
+        Span {
+            start,
+            end,
+            capture: capture.into(),
+        }
+    }
+
+    fn render(source: &str, spans: Vec<Span>, overlay: Vec<Span>) -> String {
+        let mut html = String::new();
+        write_spans_as_html_with_overlay(
+            &mut html,
+            source,
+            spans,
+            overlay,
+            &HtmlFormat::CustomElements,
+            None,
+            false,
+        )
+        .unwrap();
+        html
+    }
+
+    #[test]
+    fn test_overlay_splits_spans() {
+        let spans = vec![span(0, 2, "keyword"), span(3, 7, "function")];
+        assert_eq!(
+            render("fn main() {}", spans, vec![span(3, 5, MATCH_CAPTURE)]),
+            "<a-k>fn</a-k> <a-mh><a-f>ma</a-f></a-mh><a-f>in</a-f>() {}"
+        );
+    }
+
+    #[test]
+    fn test_overlay_across_spans() {
+        let spans = vec![span(0, 2, "keyword"), span(3, 7, "function")];
+        assert_eq!(
+            render("fn main", spans, vec![span(1, 4, MATCH_CAPTURE)]),
+            "<a-k>f</a-k><a-mh><a-k>n</a-k> <a-f>m</a-f></a-mh><a-f>ain</a-f>"
+        );
+    }
+
+    #[test]
+    fn test_overlapping_overlays() {
+        let overlay = vec![
+            span(0, 4, MATCH_CAPTURE),
+            span(2, 6, MATCH_CAPTURE),
+            span(8, 9, "spell"),
+        ];
+        assert_eq!(
+            render("abcdefghij", vec![], overlay),
+            "<a-mh>abcdef</a-mh>ghij"
+        );
+    }
+
+    #[test]
+    fn test_overlay_char_boundaries() {
+        // "é" is two bytes; a match on its second byte covers the whole char
+        assert_eq!(
+            render("aéb", vec![], vec![span(2, 3, MATCH_CAPTURE)]),
+            "a<a-mh>é</a-mh>b"
+        );
+        assert_eq!(
+            render("ab", vec![], vec![span(1, 10, MATCH_CAPTURE)]),
+            "a<a-mh>b</a-mh>"
+        );
+    }
+}
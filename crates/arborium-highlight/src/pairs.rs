@@ -0,0 +1,53 @@
+//! Bracket and auto-close pairs, for editors' bracket matching and
+//! auto-closing.
+
+/// A language's delimiter pairs, from its `arborium.yaml`, as (open, close).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pairs {
+    /// Brackets that editors match and highlight in pairs, like `(` and `)`.
+    pub brackets: &'static [(&'static str, &'static str)],
+    /// Pairs that editors close automatically when the opening one is typed:
+    /// the brackets, then others like quotes.
+    pub auto_close: &'static [(&'static str, &'static str)],
+}
+
+impl Pairs {
+    /// The delimiter that closes `open`, if it's automatically closed.
+    pub fn closing(&self, open: &str) -> Option<&'static str> {
+        self.auto_close
+            .iter()
+            .find(|(candidate, _)| *candidate == open)
+            .map(|&(_, close)| close)
+    }
+
+    /// The bracket matching `bracket`, opening or closing, if it is one.
+    pub fn matching_bracket(&self, bracket: &str) -> Option<&'static str> {
+        self.brackets.iter().find_map(|&(open, close)| {
+            if open == bracket {
+                Some(close)
+            } else if close == bracket {
+                Some(open)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookups() {
+        let pairs = Pairs {
+            brackets: &[("(", ")"), ("{", "}")],
+            auto_close: &[("(", ")"), ("{", "}"), ("\"", "\"")],
+        };
+        assert_eq!(pairs.closing("\""), Some("\""));
+        assert_eq!(pairs.closing(")"), None);
+        assert_eq!(pairs.matching_bracket("}"), Some("{"));
+        assert_eq!(pairs.matching_bracket("("), Some(")"));
+        assert_eq!(pairs.matching_bracket("\""), None);
+    }
+}
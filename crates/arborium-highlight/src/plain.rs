@@ -0,0 +1,187 @@
+//! Highlighting for languages without a grammar.
+//!
+//! Two built-in pseudo-languages don't go through tree-sitter:
+//!
+//! - `plaintext` (also `plain`, `text`, `txt`): no spans at all. The text is
+//!   still escaped and rendered like any other block, so every code block on
+//!   a page goes through the same markup and CSS.
+//! - `generic`: a small hand-written tokenizer that knows what most
+//!   programming languages have in common: numbers, quoted strings, and
+//!   comments with the usual delimiters (`//`, `/* */`, `#`, `--`, `<!-- -->`).
+//!
+//! Highlighters can also fall back to either mode for languages they don't
+//! support, instead of failing.
+
+use crate::types::Span;
+
+/// Capture names produced by [`generic_spans_into`].
+pub const GENERIC_CAPTURES: &[&str] = &["comment", "string", "number"];
+
+/// Whether `language` names the built-in plain text mode.
+pub fn is_plain_text(language: &str) -> bool {
+    matches!(language, "plaintext" | "plain" | "text" | "txt")
+}
+
+/// Whether `language` names the built-in generic tokenizer.
+pub fn is_generic(language: &str) -> bool {
+    language == "generic"
+}
+
+/// Tokenize `source` without a grammar and append the spans to `spans`.
+///
+/// Comments and strings end at the closing delimiter or, for line comments
+/// and single-quoted strings, at the end of the line. A `'` directly after a
+/// letter is an apostrophe (`don't`), and `#` or `--` only start a comment at
+/// the start of a line or after whitespace, so `a-- b` and `C#` stay plain.
+pub fn generic_spans_into(source: &str, spans: &mut Vec<Span>) {
+    let bytes = source.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let prev = if i == 0 { None } else { Some(bytes[i - 1]) };
+        let after_space = prev.is_none_or(|b| b.is_ascii_whitespace());
+        let rest = &bytes[i..];
+
+        let token = if rest.starts_with(b"//")
+            || (rest[0] == b'#' && after_space)
+            || (rest.starts_with(b"-- ") && after_space)
+        {
+            Some((line_end(bytes, i), "comment"))
+        } else if rest.starts_with(b"/*") {
+            Some((find_after(bytes, i + 2, b"*/"), "comment"))
+        } else if rest.starts_with(b"<!--") {
+            Some((find_after(bytes, i + 4, b"-->"), "comment"))
+        } else if rest[0] == b'"' || rest[0] == b'`' {
+            Some((string_end(bytes, i, rest[0], false), "string"))
+        } else if rest[0] == b'\'' && !prev.is_some_and(is_word_byte) {
+            Some((string_end(bytes, i, b'\'', true), "string"))
+        } else if rest[0].is_ascii_digit() && !prev.is_some_and(is_word_byte) {
+            Some((number_end(bytes, i), "number"))
+        } else {
+            None
+        };
+
+        match token {
+            Some((end, capture)) => {
+                spans.push(Span {
+                    start: i as u32,
+                    end: end as u32,
+                    capture: capture.into(),
+                });
+                i = end;
+            }
+            None => i += 1,
+        }
+    }
+}
+
+/// Tokenize `source` without a grammar.
+pub fn generic_spans(source: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    generic_spans_into(source, &mut spans);
+    spans
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80
+}
+
+/// End of the line starting at `from`, excluding the line break.
+fn line_end(bytes: &[u8], from: usize) -> usize {
+    bytes[from..]
+        .iter()
+        .position(|&b| b == b'\n' || b == b'\r')
+        .map_or(bytes.len(), |p| from + p)
+}
+
+/// Offset just past the first `needle` at or after `from`, or the end of input.
+fn find_after(bytes: &[u8], from: usize, needle: &[u8]) -> usize {
+    bytes[from..]
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map_or(bytes.len(), |p| from + p + needle.len())
+}
+
+/// Offset just past the closing `quote` of the string starting at `start`.
+fn string_end(bytes: &[u8], start: usize, quote: u8, single_line: bool) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'\n' if single_line => return i,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Offset just past the number starting at `start`: digits, letters for
+/// radix prefixes and suffixes (`0xff`, `1e9`, `10u8`), `_` separators and a
+/// decimal point followed by a digit, so `1..2` stays two numbers.
+fn number_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start;
+    while i < bytes.len() {
+        let b = bytes[i];
+        let decimal_point = b == b'.' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+        if b.is_ascii_alphanumeric() || b == b'_' || decimal_point {
+            i += 1;
+        } else {
+            break;
+        }
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(source: &str) -> Vec<(&str, String)> {
+        generic_spans(source)
+            .into_iter()
+            .map(|s| {
+                (
+                    &source[s.start as usize..s.end as usize],
+                    s.capture.into_owned(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_generic_tokens() {
+        let source = "let x = 0x1F + 2.5; // answer\nprint(\"a \\\" b\", 'c') # done";
+        assert_eq!(
+            tokens(source),
+            vec![
+                ("0x1F", "number".to_string()),
+                ("2.5", "number".to_string()),
+                ("// answer", "comment".to_string()),
+                ("\"a \\\" b\"", "string".to_string()),
+                ("'c'", "string".to_string()),
+                ("# done", "comment".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generic_prose_stays_plain() {
+        assert!(tokens("don't use C# or x2 for a-- b").is_empty());
+        assert_eq!(
+            tokens("1..2"),
+            vec![("1", "number".to_string()), ("2", "number".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_generic_block_comments() {
+        assert_eq!(
+            tokens("a /* one\ntwo */ b <!-- c"),
+            vec![
+                ("/* one\ntwo */", "comment".to_string()),
+                ("<!-- c", "comment".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! Compiled tree-sitter queries, cached on disk.
+//!
+//! Compiling a grammar's queries is most of the cost of its first highlight,
+//! and a CLI invocation or a freshly started server pays it again for every
+//! language. A [`QueryCache`] keeps the compiled form of each query in a
+//! directory and loads it from there on later runs, which takes a fraction
+//! of the time.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use arborium_tree_sitter::{Language, Query, QueryError};
+
+/// Marks cache files, and changes whenever their layout does.
+const MAGIC: &[u8; 8] = b"arbq\0\0\0\x01";
+
+/// A directory of compiled queries.
+///
+/// Entries are content-addressed: a file's name hashes the query source, the
+/// grammar's node kinds and fields, and the version of arborium, so edited
+/// queries, regenerated grammars and upgrades all miss the cache instead of
+/// loading something stale. Each file also holds the full query source,
+/// compared on load, and tree-sitter checks the compiled query's layout and
+/// checksum, so truncated or corrupted files are recompiled too.
+///
+/// Failing to read or write the cache is never an error; the query is
+/// compiled as if there were no cache. Nothing is ever evicted, but the
+/// directory can be deleted, or emptied with [`clear`](Self::clear), at any
+/// time. Several processes may share it.
+///
+/// The cache directory has to be as trusted as the program itself: its
+/// files are loaded without recompiling, and only checked against accidents.
+#[derive(Debug, Clone)]
+pub struct QueryCache {
+    dir: PathBuf,
+}
+
+impl QueryCache {
+    /// Cache queries in `dir`, which is created on first write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Cache queries in `arborium/queries` under the user's cache directory:
+    /// `$XDG_CACHE_HOME`, `~/.cache` or `%LOCALAPPDATA%`.
+    ///
+    /// Returns `None` if none of those are set.
+    pub fn user() -> Option<Self> {
+        let base = std::env::var_os("XDG_CACHE_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+            .or_else(|| std::env::var_os("LOCALAPPDATA").map(PathBuf::from))?;
+        Some(Self::new(base.join("arborium").join("queries")))
+    }
+
+    /// The directory holding the cached queries.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Compile `source` for `language`, or load it from the cache if it was
+    /// compiled before.
+    ///
+    /// Errors are those of [`Query::new`]; queries that fail to compile
+    /// aren't cached.
+    pub fn query(&self, language: &Language, source: &str) -> Result<Query, QueryError> {
+        let path = self.path_for(language, source);
+        if let Some(query) = Self::load(&path, language, source) {
+            return Ok(query);
+        }
+
+        let query = Query::new(language, source)?;
+        // A read-only or full disk only costs the next run the compile
+        let _ = Self::store(&path, source, &query);
+        Ok(query)
+    }
+
+    /// Delete all cached queries.
+    pub fn clear(&self) -> io::Result<()> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "query") {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn path_for(&self, language: &Language, source: &str) -> PathBuf {
+        let mut hash = Fnv1a::default();
+        hash.write(env!("CARGO_PKG_VERSION").as_bytes());
+        hash.write(&(language.abi_version() as u32).to_le_bytes());
+        for id in 0..language.node_kind_count() as u16 {
+            hash.write(language.node_kind_for_id(id).unwrap_or_default().as_bytes());
+            hash.write(&[language.node_kind_is_named(id) as u8]);
+        }
+        for id in 1..=language.field_count() as u16 {
+            hash.write(
+                language
+                    .field_name_for_id(id)
+                    .unwrap_or_default()
+                    .as_bytes(),
+            );
+            hash.write(&[0]);
+        }
+        hash.write(source.as_bytes());
+        self.dir.join(format!("{:016x}.query", hash.0))
+    }
+
+    fn load(path: &Path, language: &Language, source: &str) -> Option<Query> {
+        let bytes = fs::read(path).ok()?;
+        let rest = bytes.strip_prefix(MAGIC)?;
+        let (length, rest) = rest.split_first_chunk::<8>()?;
+        let length = usize::try_from(u64::from_le_bytes(*length)).ok()?;
+        if rest.get(..length)? != source.as_bytes() {
+            return None;
+        }
+        Query::from_bytes(language, &rest[length..], source)
+    }
+
+    fn store(path: &Path, source: &str, query: &Query) -> io::Result<()> {
+        let compiled = query.to_bytes();
+        let mut bytes = Vec::with_capacity(MAGIC.len() + 8 + source.len() + compiled.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&(source.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(source.as_bytes());
+        bytes.extend_from_slice(&compiled);
+
+        // Write under a name of our own and rename, so that other processes
+        // never see a partial file
+        fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))?;
+        let temp = path.with_extension(format!("{}.tmp", std::process::id()));
+        fs::write(&temp, bytes)?;
+        fs::rename(&temp, path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp);
+        })
+    }
+}
+
+/// 64-bit FNV-1a, stable across platforms and Rust versions.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
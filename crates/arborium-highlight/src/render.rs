@@ -12,11 +12,13 @@
 //!
 //! Both map to the "keyword" slot (`k` tag), so they become a single `<a-k>` element.
 
+use crate::links::{LINK_URL_CAPTURE, is_linkable_url};
 use crate::{HtmlFormat, Span};
 use arborium_theme::{
     Theme, capture_to_slot, slot_to_highlight_index, tag_for_capture, tag_to_name,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::io::{self, Write};
 
 /// A span with a theme style index for rendering.
@@ -89,7 +91,7 @@ use unicode_width::UnicodeWidthChar;
 /// Generate opening and closing HTML tags based on the configured format.
 ///
 /// Returns (opening_tag, closing_tag) for the given short tag and format.
-fn make_html_tags(short_tag: &str, format: &HtmlFormat) -> (String, String) {
+pub(crate) fn make_html_tags(short_tag: &str, format: &HtmlFormat) -> (String, String) {
     match format {
         HtmlFormat::CustomElements => {
             let open = format!("<a-{short_tag}>");
@@ -184,14 +186,91 @@ fn normalize_and_coalesce(spans: Vec<Span>) -> Vec<NormalizedSpan> {
 ///
 /// The `format` parameter controls the HTML output style.
 pub fn spans_to_html(source: &str, spans: Vec<Span>, format: &HtmlFormat) -> String {
+    let mut html = String::with_capacity(source.len() * 2);
+    // Writing to a String never fails
+    let _ = write_spans_as_html_fmt(&mut html, source, spans, format);
+    html
+}
+
+/// Write spans as HTML to a [`fmt::Write`] destination, e.g. a `String`.
+///
+/// Produces the same output as [`spans_to_html`], but appends to an existing
+/// buffer instead of allocating a new one per call.
+pub fn write_spans_as_html_fmt<W: fmt::Write>(
+    w: &mut W,
+    source: &str,
+    spans: Vec<Span>,
+    format: &HtmlFormat,
+) -> fmt::Result {
+    write_html(w, source, spans, format, None, false)
+}
+
+/// Like [`write_spans_as_html_fmt`], but no element holds more than
+/// `max_chunk_len` bytes of source text.
+///
+/// Longer runs are closed and re-opened, e.g. `<a-s>aaa</a-s><a-s>aa</a-s>`
+/// for a 5-byte string with a limit of 3. Minified JS or JSON with a single
+/// huge string or line otherwise ends up as one enormous element, which some
+/// HTML parsers and browsers handle poorly. Chunks are cut on char
+/// boundaries, so a chunk may exceed the limit by up to 3 bytes.
+pub fn write_spans_as_html_chunked<W: fmt::Write>(
+    w: &mut W,
+    source: &str,
+    spans: Vec<Span>,
+    format: &HtmlFormat,
+    max_chunk_len: usize,
+) -> fmt::Result {
+    write_html(w, source, spans, format, Some(max_chunk_len), false)
+}
+
+/// Like [`write_spans_as_html_fmt`], but URLs are also wrapped in `<a href>`.
+///
+/// Only [`LINK_URL_CAPTURE`] spans whose text is a complete URL with a known
+/// scheme become links, such as the ones [`link_spans`](crate::link_spans)
+/// finds in comments and strings. `max_chunk_len` works as in
+/// [`write_spans_as_html_chunked`].
+pub fn write_spans_as_html_with_links<W: fmt::Write>(
+    w: &mut W,
+    source: &str,
+    spans: Vec<Span>,
+    format: &HtmlFormat,
+    max_chunk_len: Option<usize>,
+) -> fmt::Result {
+    write_html(w, source, spans, format, max_chunk_len, true)
+}
+
+pub(crate) fn write_html<W: fmt::Write>(
+    w: &mut W,
+    source: &str,
+    spans: Vec<Span>,
+    format: &HtmlFormat,
+    max_chunk_len: Option<usize>,
+    links: bool,
+) -> fmt::Result {
     if spans.is_empty() {
-        return html_escape(source);
+        return write_html_escaped(w, source);
     }
 
     // Sort spans by (start, -end) so longer spans come first at same start
     let mut spans = spans;
     spans.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| b.end.cmp(&a.end)));
 
+    // Ranges to wrap in <a href>, decided before captures are mapped to tags
+    let anchors: HashSet<(u32, u32)> = if links {
+        spans
+            .iter()
+            .filter(|span| {
+                span.capture == LINK_URL_CAPTURE
+                    && source
+                        .get(span.start as usize..span.end as usize)
+                        .is_some_and(is_linkable_url)
+            })
+            .map(|span| (span.start, span.end))
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
     // Deduplicate: for spans with the exact same (start, end), prefer spans with styling.
     // This handles the case where @comment @spell produces two spans - we want @comment,
     // not @spell (which maps to ThemeSlot::None and produces no HTML).
@@ -218,7 +297,7 @@ pub fn spans_to_html(source: &str, spans: Vec<Span>, format: &HtmlFormat) -> Str
     let spans = normalize_and_coalesce(spans);
 
     if spans.is_empty() {
-        return html_escape(source);
+        return write_html_escaped(w, source);
     }
 
     // Re-sort after coalescing
@@ -238,7 +317,6 @@ pub fn spans_to_html(source: &str, spans: Vec<Span>, format: &HtmlFormat) -> Str
     });
 
     // Process events with a stack
-    let mut html = String::with_capacity(source.len() * 2);
     let mut last_pos: usize = 0;
     let mut stack: Vec<usize> = Vec::new(); // indices into spans
 
@@ -249,13 +327,18 @@ pub fn spans_to_html(source: &str, spans: Vec<Span>, format: &HtmlFormat) -> Str
         if pos > last_pos && pos <= source.len() {
             let text = &source[last_pos..pos];
             if let Some(&top_idx) = stack.last() {
-                let tag = spans[top_idx].tag;
-                let (open_tag, close_tag) = make_html_tags(tag, format);
-                html.push_str(&open_tag);
-                html.push_str(&html_escape(text));
-                html.push_str(&close_tag);
+                let span = &spans[top_idx];
+                let (open_tag, close_tag) = make_html_tags(span.tag, format);
+                let anchor = anchors.contains(&(span.start, span.end));
+                if anchor {
+                    write_anchor_open(w, &source[span.start as usize..span.end as usize])?;
+                }
+                write_element(w, text, &open_tag, &close_tag, max_chunk_len)?;
+                if anchor {
+                    w.write_str("</a>")?;
+                }
             } else {
-                html.push_str(&html_escape(text));
+                write_html_escaped(w, text)?;
             }
             last_pos = pos;
         }
@@ -275,48 +358,129 @@ pub fn spans_to_html(source: &str, spans: Vec<Span>, format: &HtmlFormat) -> Str
     if last_pos < source.len() {
         let text = &source[last_pos..];
         if let Some(&top_idx) = stack.last() {
-            let tag = spans[top_idx].tag;
-            let (open_tag, close_tag) = make_html_tags(tag, format);
-            html.push_str(&open_tag);
-            html.push_str(&html_escape(text));
-            html.push_str(&close_tag);
+            let span = &spans[top_idx];
+            let (open_tag, close_tag) = make_html_tags(span.tag, format);
+            let anchor = anchors.contains(&(span.start, span.end));
+            if anchor {
+                write_anchor_open(w, &source[span.start as usize..span.end as usize])?;
+            }
+            write_element(w, text, &open_tag, &close_tag, max_chunk_len)?;
+            if anchor {
+                w.write_str("</a>")?;
+            }
         } else {
-            html.push_str(&html_escape(text));
+            write_html_escaped(w, text)?;
         }
     }
 
-    html
+    Ok(())
+}
+
+/// Write the opening `<a>` tag for a link to `url`.
+fn write_anchor_open<W: fmt::Write>(w: &mut W, url: &str) -> fmt::Result {
+    w.write_str("<a href=\"")?;
+    write_html_escaped(w, url)?;
+    w.write_str("\">")
+}
+
+/// Write `text` wrapped in an element, split into several elements of at
+/// most `max_chunk_len` bytes (rounded up to a char boundary) if set.
+fn write_element<W: fmt::Write>(
+    w: &mut W,
+    text: &str,
+    open_tag: &str,
+    close_tag: &str,
+    max_chunk_len: Option<usize>,
+) -> fmt::Result {
+    let mut rest = text;
+    loop {
+        let mut split = match max_chunk_len {
+            Some(max) if max < rest.len() => max.max(1),
+            _ => rest.len(),
+        };
+        while !rest.is_char_boundary(split) {
+            split += 1;
+        }
+        let (chunk, tail) = rest.split_at(split);
+
+        w.write_str(open_tag)?;
+        write_html_escaped(w, chunk)?;
+        w.write_str(close_tag)?;
+
+        if tail.is_empty() {
+            return Ok(());
+        }
+        rest = tail;
+    }
 }
 
 /// Write spans as HTML to a writer.
 ///
-/// This is more efficient than `spans_to_html` for streaming output.
+/// This is more efficient than `spans_to_html` for streaming output: the
+/// HTML goes straight to the writer without being collected in a `String`
+/// first, so wrap unbuffered writers in a `BufWriter`.
 pub fn write_spans_as_html<W: Write>(
     w: &mut W,
     source: &str,
     spans: Vec<Span>,
     format: &HtmlFormat,
 ) -> io::Result<()> {
-    let html = spans_to_html(source, spans, format);
-    w.write_all(html.as_bytes())
+    let mut adapter = IoAdapter {
+        inner: w,
+        error: Ok(()),
+    };
+    match write_spans_as_html_fmt(&mut adapter, source, spans, format) {
+        Ok(()) => Ok(()),
+        Err(fmt::Error) => match adapter.error {
+            Err(e) => Err(e),
+            Ok(()) => Err(io::Error::other("formatter error")),
+        },
+    }
+}
+
+/// Forwards [`fmt::Write`] to [`io::Write`], keeping the I/O error around
+/// since `fmt::Error` can't carry it.
+struct IoAdapter<'a, W: Write> {
+    inner: &'a mut W,
+    error: io::Result<()>,
+}
+
+impl<W: Write> fmt::Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Err(e);
+            fmt::Error
+        })
+    }
 }
 
 /// Escape HTML special characters.
 pub fn html_escape(text: &str) -> String {
     let mut result = String::with_capacity(text.len());
-    for c in text.chars() {
-        match c {
-            '<' => result.push_str("&lt;"),
-            '>' => result.push_str("&gt;"),
-            '&' => result.push_str("&amp;"),
-            '"' => result.push_str("&quot;"),
-            '\'' => result.push_str("&#39;"),
-            _ => result.push(c),
-        }
-    }
+    // Writing to a String never fails
+    let _ = write_html_escaped(&mut result, text);
     result
 }
 
+/// Write `text` with HTML special characters escaped, copying unescaped runs in one go.
+fn write_html_escaped<W: fmt::Write>(w: &mut W, text: &str) -> fmt::Result {
+    let mut last = 0;
+    for (i, b) in text.bytes().enumerate() {
+        let escaped = match b {
+            b'<' => "&lt;",
+            b'>' => "&gt;",
+            b'&' => "&amp;",
+            b'"' => "&quot;",
+            b'\'' => "&#39;",
+            _ => continue,
+        };
+        w.write_str(&text[last..i])?;
+        w.write_str(escaped)?;
+        last = i + 1;
+    }
+    w.write_str(&text[last..])
+}
+
 /// Options controlling ANSI rendering behavior.
 #[derive(Debug, Clone)]
 pub struct AnsiOptions {
@@ -399,7 +563,7 @@ impl Default for AnsiOptions {
 }
 
 #[cfg(feature = "unicode-width")]
-fn char_display_width(c: char, col: usize, tab_width: usize) -> usize {
+pub(crate) fn char_display_width(c: char, col: usize, tab_width: usize) -> usize {
     if c == '\t' {
         let next_tab = ((col / tab_width) + 1) * tab_width;
         next_tab - col
@@ -409,7 +573,7 @@ fn char_display_width(c: char, col: usize, tab_width: usize) -> usize {
 }
 
 #[cfg(not(feature = "unicode-width"))]
-fn char_display_width(c: char, col: usize, tab_width: usize) -> usize {
+pub(crate) fn char_display_width(c: char, col: usize, tab_width: usize) -> usize {
     if c == '\t' {
         let next_tab = ((col / tab_width) + 1) * tab_width;
         next_tab - col
@@ -1245,6 +1409,112 @@ mod tests {
         assert_eq!(html, "&lt;script&gt;");
     }
 
+    #[test]
+    fn test_write_spans_as_html_appends() {
+        let source = "fn <T>";
+        let spans = || {
+            vec![Span {
+                start: 0,
+                end: 2,
+                capture: "keyword".into(),
+            }]
+        };
+        let expected = spans_to_html(source, spans(), &HtmlFormat::CustomElements);
+        assert_eq!(expected, "<a-k>fn</a-k> &lt;T&gt;");
+
+        let mut html = String::from("<pre>");
+        write_spans_as_html_fmt(&mut html, source, spans(), &HtmlFormat::CustomElements).unwrap();
+        assert_eq!(html, format!("<pre>{expected}"));
+
+        let mut bytes = Vec::new();
+        write_spans_as_html(&mut bytes, source, spans(), &HtmlFormat::CustomElements).unwrap();
+        assert_eq!(String::from_utf8(bytes).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_html_with_links() {
+        let source = "// https://x.org/?a&b see a/b.rs";
+        let spans = vec![
+            Span {
+                start: 0,
+                end: source.len() as u32,
+                capture: "comment".into(),
+            },
+            Span {
+                start: 3,
+                end: 21,
+                capture: "markup.link.url".into(),
+            },
+            Span {
+                start: 26,
+                end: 32,
+                capture: "markup.link".into(),
+            },
+        ];
+
+        let mut html = String::new();
+        write_spans_as_html_with_links(&mut html, source, spans, &HtmlFormat::CustomElements, None)
+            .unwrap();
+        assert_eq!(
+            html,
+            "<a-c>// </a-c><a href=\"https://x.org/?a&amp;b\"><a-tu>https://x.org/?a&amp;b</a-tu></a>\
+             <a-c> see </a-c><a-tu>a/b.rs</a-tu>"
+        );
+    }
+
+    #[test]
+    fn test_chunked_long_spans() {
+        // A minified-JSON-like line: one huge string, then a number
+        let source = format!("\"{}\":1", "é".repeat(200_000));
+        let string_end = source.len() as u32 - 2;
+        let spans = || {
+            vec![
+                Span {
+                    start: 0,
+                    end: string_end,
+                    capture: "string".into(),
+                },
+                Span {
+                    start: string_end + 1,
+                    end: string_end + 2,
+                    capture: "number".into(),
+                },
+            ]
+        };
+        let format = HtmlFormat::CustomElements;
+
+        let mut html = String::new();
+        write_spans_as_html_chunked(&mut html, &source, spans(), &format, 1000).unwrap();
+
+        let chunks: Vec<&str> = html
+            .split("<a-s>")
+            .skip(1)
+            .map(|c| c.split("</a-s>").next().unwrap())
+            .collect();
+        assert!(chunks.len() >= 400);
+        // One extra byte to reach a char boundary, plus the escaped quote
+        assert!(chunks.iter().all(|c| c.len() <= 1001 + "&quot;".len()));
+
+        // Removing the chunk boundaries gives back the unchunked output
+        let unchunked = spans_to_html(&source, spans(), &format);
+        assert_eq!(html.replace("</a-s><a-s>", ""), unchunked);
+    }
+
+    #[test]
+    fn test_chunked_zero_limit() {
+        let source = "\"ab\"";
+        let spans = vec![Span {
+            start: 0,
+            end: 4,
+            capture: "string".into(),
+        }];
+
+        let mut html = String::new();
+        write_spans_as_html_chunked(&mut html, source, spans, &HtmlFormat::CustomElements, 0)
+            .unwrap();
+        assert_eq!(html, "<a-s>&quot;</a-s><a-s>a</a-s><a-s>b</a-s><a-s>&quot;</a-s>");
+    }
+
     #[test]
     fn test_nospell_filtered() {
         // Captures like "spell" and "nospell" should produce no output
@@ -1510,7 +1780,7 @@ mod tests {
             spans.push(Span {
                 start: offset,
                 end: offset + len,
-                capture: capture_name.to_string(),
+                capture: (*capture_name).into(),
             });
             offset += len;
         }
@@ -1564,6 +1834,7 @@ mod html_tests {
             highlights_query: &arborium_cpp::HIGHLIGHTS_QUERY,
             injections_query: arborium_cpp::INJECTIONS_QUERY,
             locals_query: "",
+            textobjects_query: "",
         };
 
         let grammar = CompiledGrammar::new(config).expect("Failed to compile grammar");
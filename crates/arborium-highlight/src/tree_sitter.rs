@@ -30,9 +30,27 @@
 //!     grammar.parse(&mut ctx, code)
 //! }).collect();
 //! ```
+//!
+//! Long-running servers that highlight on many short-lived threads or tasks
+//! can keep contexts in a [`ParserPool`] instead of creating one per request.
+//!
+//! Programs that start often, like CLIs, can keep compiled queries in a
+//! [`QueryCache`] on disk instead of compiling them on every start.
+
+use std::borrow::Cow;
+use std::ops::{Deref, DerefMut, Range};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
+use crate::navigation::Motion;
+pub use crate::query_cache::QueryCache;
 use crate::types::{Injection, ParseResult, Span};
-use arborium_tree_sitter::{Language, Parser, Query, QueryCursor};
+pub use arborium_syntax::{SexpOptions, annotated_language, injection_range, tree_sexp};
+use arborium_theme::CAPTURE_NAMES;
+use arborium_tree_sitter::{
+    LANGUAGE_VERSION, Language, MIN_COMPATIBLE_LANGUAGE_VERSION, Node, ParseOptions, ParseState,
+    Parser, Query, QueryCursor, Tree,
+};
 use streaming_iterator::StreamingIterator;
 
 /// Configuration for creating a [`CompiledGrammar`].
@@ -45,27 +63,131 @@ pub struct GrammarConfig<'a> {
     pub injections_query: &'a str,
     /// The locals query (for local variable tracking, currently unused)
     pub locals_query: &'a str,
+    /// The textobjects query (for [`navigate`](crate::navigate), optional)
+    pub textobjects_query: &'a str,
 }
 
 /// Error when creating a grammar or parse context.
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum GrammarError {
     /// Failed to set the parser language
+    #[error("failed to set parser language")]
     LanguageError,
+    /// The grammar was generated for a tree-sitter ABI this build can't load
+    #[error(
+        "grammar uses tree-sitter ABI {found}, but only ABI {min} through {max} is supported; \
+         regenerate it with a tree-sitter CLI in that range"
+    )]
+    IncompatibleAbi {
+        /// The grammar's ABI version
+        found: usize,
+        /// The oldest ABI version this build supports
+        min: usize,
+        /// The newest ABI version this build supports
+        max: usize,
+    },
     /// Failed to compile a query
-    QueryError(String),
+    #[error("{query} query: {message}")]
+    QueryError {
+        /// Which of the grammar's queries failed to compile
+        query: QueryKind,
+        /// 0-based row of the error in the query source
+        row: usize,
+        /// 0-based column of the error in the query source
+        column: usize,
+        /// Byte offset of the error in the query source
+        offset: usize,
+        /// Tree-sitter's description of the error, including its position
+        message: String,
+    },
 }
 
-impl std::fmt::Display for GrammarError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            GrammarError::LanguageError => write!(f, "Failed to set parser language"),
-            GrammarError::QueryError(e) => write!(f, "Query compilation error: {}", e),
+impl GrammarError {
+    fn query(query: QueryKind, e: arborium_tree_sitter::QueryError) -> Self {
+        GrammarError::QueryError {
+            query,
+            row: e.row,
+            column: e.column,
+            offset: e.offset,
+            message: e.to_string(),
         }
     }
 }
 
-impl std::error::Error for GrammarError {}
+/// Check that `language` was generated for a tree-sitter ABI this build
+/// supports.
+///
+/// Compiled-in grammars always are, but grammars loaded at runtime, e.g.
+/// from a shared library, may have been generated by an older or newer
+/// tree-sitter CLI. [`CompiledGrammar::new`] and [`ParseContext`] run this
+/// check, so that such grammars fail with [`GrammarError::IncompatibleAbi`]
+/// rather than a query or parser error that doesn't say why.
+pub fn check_abi(language: &Language) -> Result<(), GrammarError> {
+    let found = language.abi_version();
+    if (MIN_COMPATIBLE_LANGUAGE_VERSION..=LANGUAGE_VERSION).contains(&found) {
+        Ok(())
+    } else {
+        Err(GrammarError::IncompatibleAbi {
+            found,
+            min: MIN_COMPATIBLE_LANGUAGE_VERSION,
+            max: LANGUAGE_VERSION,
+        })
+    }
+}
+
+/// One of a grammar's queries, as reported in [`GrammarError::QueryError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryKind {
+    Highlights,
+    Injections,
+    Locals,
+    Textobjects,
+}
+
+impl std::fmt::Display for QueryKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            QueryKind::Highlights => "highlights",
+            QueryKind::Injections => "injections",
+            QueryKind::Locals => "locals",
+            QueryKind::Textobjects => "textobjects",
+        })
+    }
+}
+
+/// A highlights query capture covering a given position, as returned by
+/// [`CompiledGrammar::explain`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureExplanation {
+    /// The kind of the captured node (e.g. `"string_literal"`).
+    pub node_kind: &'static str,
+    /// Byte offset where the captured node starts (inclusive).
+    pub start: u32,
+    /// Byte offset where the captured node ends (exclusive).
+    pub end: u32,
+    /// Index of the matched pattern in the highlights query.
+    pub pattern_index: usize,
+    /// 1-based line in the highlights query source where the pattern starts.
+    pub pattern_line: usize,
+    /// The capture name (e.g. `"string"`).
+    pub capture: String,
+}
+
+/// A leaf of the syntax tree with the capture that highlights it, as returned
+/// by [`CompiledGrammar::tokens`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Token {
+    /// Byte offset where the token starts (inclusive).
+    pub start: u32,
+    /// Byte offset where the token ends (exclusive).
+    pub end: u32,
+    /// The kind of the leaf node: a named kind like `"identifier"`, or the
+    /// text of an anonymous node like `"fn"` or `"("`.
+    pub node_kind: &'static str,
+    /// The innermost highlights query capture covering the token, if any.
+    pub capture: Option<Cow<'static, str>>,
+}
 
 /// Compiled grammar data that can be shared across threads.
 ///
@@ -81,9 +203,62 @@ pub struct CompiledGrammar {
     language: Language,
     highlights_query: Query,
     injections_query: Option<Query>,
+    textobjects_query: Option<Query>,
     // Cached capture indices for injection query
     injection_content_idx: Option<u32>,
     injection_language_idx: Option<u32>,
+    // 1-based starting line of each highlights pattern, for `explain`
+    pattern_lines: Vec<usize>,
+    // Span capture name for each highlights capture index, borrowed from
+    // `CAPTURE_NAMES` when possible so spans don't allocate
+    span_captures: Vec<Cow<'static, str>>,
+    // Index of the `@rainbow` capture, if the highlights query uses it
+    rainbow_idx: Option<u32>,
+}
+
+/// Captures a `@rainbow` capture cycles through, one per column.
+///
+/// Grammars for tabular data (CSV, TSV) capture their fields as `@rainbow`.
+/// Queries can't count, so the column is worked out from the tree instead: it's
+/// the number of anonymous siblings (the separators) before the node. Column 0
+/// gets the first capture here, column 1 the second, and so on, wrapping
+/// around after the last one.
+pub const RAINBOW_CAPTURES: [&str; 8] = [
+    "variable", "keyword", "string", "function", "type", "number", "property", "constant",
+];
+
+/// Columns of the children of the last parent a `@rainbow` node was in.
+///
+/// Captures come in document order, so each row is walked once.
+#[derive(Default)]
+struct RainbowColumns {
+    parent: Option<usize>,
+    /// Start byte and column of each named child.
+    columns: Vec<(usize, usize)>,
+}
+
+impl RainbowColumns {
+    fn column(&mut self, node: Node<'_>) -> usize {
+        let Some(parent) = node.parent() else {
+            return 0;
+        };
+        if self.parent != Some(parent.id()) {
+            self.parent = Some(parent.id());
+            self.columns.clear();
+            let mut column = 0;
+            let mut cursor = parent.walk();
+            for child in parent.children(&mut cursor) {
+                if child.is_named() {
+                    self.columns.push((child.start_byte(), column));
+                } else {
+                    column += 1;
+                }
+            }
+        }
+        self.columns
+            .binary_search_by_key(&node.start_byte(), |&(start, _)| start)
+            .map_or(0, |i| self.columns[i].1)
+    }
 }
 
 // Safety: CompiledGrammar only contains Language and Query types from tree-sitter.
@@ -105,15 +280,63 @@ impl CompiledGrammar {
     /// This compiles the highlight and injection queries, which can be expensive.
     /// The resulting `CompiledGrammar` can be wrapped in `Arc` and shared across threads.
     pub fn new(config: GrammarConfig<'_>) -> Result<Self, GrammarError> {
-        let highlights_query = Query::new(&config.language, config.highlights_query)
-            .map_err(|e| GrammarError::QueryError(e.to_string()))?;
+        Self::with_query_cache(config, None)
+    }
+
+    /// Like [`new`](Self::new), but loads the compiled queries from `cache`
+    /// when they were compiled before, and stores them there otherwise.
+    pub fn with_query_cache(
+        config: GrammarConfig<'_>,
+        cache: Option<&QueryCache>,
+    ) -> Result<Self, GrammarError> {
+        Self::with_precompiled(config, &[], &[], cache)
+    }
+
+    /// Like [`with_query_cache`](Self::with_query_cache), but first tries
+    /// to load the highlights and injections queries from `highlights` and
+    /// `injections`, as serialized by [`Query::to_bytes`] from the config's
+    /// sources.
+    ///
+    /// This is how grammar crates' `PRECOMPILED_HIGHLIGHTS` and
+    /// `PRECOMPILED_INJECTIONS` are used. Bytes that are empty or can't be
+    /// loaded, e.g. because they were compiled for a different target, are
+    /// ignored.
+    pub fn with_precompiled(
+        config: GrammarConfig<'_>,
+        highlights: &[u8],
+        injections: &[u8],
+        cache: Option<&QueryCache>,
+    ) -> Result<Self, GrammarError> {
+        check_abi(&config.language)?;
+
+        let compile = |source, precompiled: &[u8]| {
+            if let Some(query) = Query::from_bytes(&config.language, precompiled, source) {
+                return Ok(query);
+            }
+            match cache {
+                Some(cache) => cache.query(&config.language, source),
+                None => Query::new(&config.language, source),
+            }
+        };
+
+        let highlights_query = compile(config.highlights_query, highlights)
+            .map_err(|e| GrammarError::query(QueryKind::Highlights, e))?;
 
         let injections_query = if config.injections_query.is_empty() {
             None
         } else {
             Some(
-                Query::new(&config.language, config.injections_query)
-                    .map_err(|e| GrammarError::QueryError(e.to_string()))?,
+                compile(config.injections_query, injections)
+                    .map_err(|e| GrammarError::query(QueryKind::Injections, e))?,
+            )
+        };
+
+        let textobjects_query = if config.textobjects_query.is_empty() {
+            None
+        } else {
+            Some(
+                compile(config.textobjects_query, &[])
+                    .map_err(|e| GrammarError::query(QueryKind::Textobjects, e))?,
             )
         };
 
@@ -134,12 +357,42 @@ impl CompiledGrammar {
                 (None, None)
             };
 
+        // Pattern start offsets are increasing, so count newlines incrementally
+        let mut pattern_lines = Vec::with_capacity(highlights_query.pattern_count());
+        let (mut line, mut offset) = (1, 0);
+        for i in 0..highlights_query.pattern_count() {
+            let start = highlights_query.start_byte_for_pattern(i);
+            line += config.highlights_query.as_bytes()[offset..start]
+                .iter()
+                .filter(|&&b| b == b'\n')
+                .count();
+            offset = start;
+            pattern_lines.push(line);
+        }
+
+        let span_captures = highlights_query
+            .capture_names()
+            .iter()
+            .map(
+                |name| match CAPTURE_NAMES.iter().find(|known| *known == name) {
+                    Some(known) => Cow::Borrowed(*known),
+                    None => Cow::Owned(name.to_string()),
+                },
+            )
+            .collect();
+
+        let rainbow_idx = highlights_query.capture_index_for_name("rainbow");
+
         Ok(Self {
             language: config.language,
             highlights_query,
             injections_query,
+            textobjects_query,
             injection_content_idx,
             injection_language_idx,
+            pattern_lines,
+            span_captures,
+            rainbow_idx,
         })
     }
 
@@ -148,26 +401,88 @@ impl CompiledGrammar {
         &self.language
     }
 
+    /// Get the capture names that can appear in spans produced by this grammar.
+    ///
+    /// This lists the captures of the highlights query, in query order, minus
+    /// internal (`_`-prefixed) and `injection.*` captures which never produce spans.
+    /// A `@rainbow` capture is listed as the [`RAINBOW_CAPTURES`] it turns into.
+    pub fn capture_names(&self) -> Vec<&str> {
+        self.highlights_query
+            .capture_names()
+            .iter()
+            .flat_map(|name| match *name {
+                "rainbow" => &RAINBOW_CAPTURES[..],
+                _ => std::slice::from_ref(name),
+            })
+            .copied()
+            .filter(|name| !name.starts_with('_') && !name.starts_with("injection."))
+            .collect()
+    }
+
+    /// The [`capture_names`](Self::capture_names) that aren't in
+    /// `arborium_theme::CAPTURE_NAMES`.
+    ///
+    /// Themes map these by their longest known prefix, if any, so they are
+    /// often styled like a more general capture or not at all.
+    pub fn unknown_captures(&self) -> Vec<&str> {
+        self.capture_names()
+            .into_iter()
+            .filter(|name| !CAPTURE_NAMES.contains(name))
+            .collect()
+    }
+
+    /// The general predicates of the highlights query that arborium doesn't
+    /// evaluate, such as Neovim's `#lua-match?`, as the 1-based line of
+    /// their pattern and the predicate's name.
+    ///
+    /// Tree-sitter leaves general predicates to the caller, so patterns
+    /// using these match as if the predicate held.
+    pub fn ignored_predicates(&self) -> Vec<(usize, &str)> {
+        (0..self.highlights_query.pattern_count())
+            .flat_map(|i| {
+                self.highlights_query
+                    .general_predicates(i)
+                    .iter()
+                    .map(move |predicate| (self.pattern_lines[i], &*predicate.operator))
+            })
+            .collect()
+    }
+
     /// Parse text and return highlight spans and injection points.
     ///
     /// Requires a [`ParseContext`] which holds the mutable parser state.
     /// Each thread should have its own context.
     pub fn parse(&self, ctx: &mut ParseContext, text: &str) -> ParseResult {
+        let mut spans = Vec::new();
+        let injections = self.parse_into(ctx, text, &mut spans);
+        ParseResult { spans, injections }
+    }
+
+    /// Parse text, appending highlight spans to `spans` and returning injection points.
+    ///
+    /// Like [`parse`](Self::parse), but lets callers reuse one span buffer
+    /// across calls. Spans for captures listed in `arborium_theme::CAPTURE_NAMES`
+    /// don't allocate.
+    pub fn parse_into(
+        &self,
+        ctx: &mut ParseContext,
+        text: &str,
+        spans: &mut Vec<Span>,
+    ) -> Vec<Injection> {
         // Parse the text
-        let tree = match ctx.parser.parse(text, None) {
+        let tree = match ctx.parse_tree(text) {
             Some(tree) => tree,
-            None => return ParseResult::default(),
+            None => return Vec::new(),
         };
 
         let root_node = tree.root_node();
         let source = text.as_bytes();
 
         // Collect highlight spans
-        let mut spans = Vec::new();
-
         let mut matches = ctx
             .cursor
             .matches(&self.highlights_query, root_node, source);
+        let mut rainbow = RainbowColumns::default();
 
         while let Some(m) = matches.next() {
             for capture in m.captures {
@@ -184,10 +499,16 @@ impl CompiledGrammar {
                 }
 
                 let node = capture.node;
+                let capture = if Some(capture.index) == self.rainbow_idx {
+                    let column = rainbow.column(node);
+                    Cow::Borrowed(RAINBOW_CAPTURES[column % RAINBOW_CAPTURES.len()])
+                } else {
+                    self.span_captures[capture.index as usize].clone()
+                };
                 spans.push(Span {
                     start: node.start_byte() as u32,
                     end: node.end_byte() as u32,
-                    capture: capture_name.to_string(),
+                    capture,
                 });
             }
         }
@@ -200,6 +521,7 @@ impl CompiledGrammar {
 
             while let Some(m) = matches.next() {
                 let mut content_node = None;
+                let mut language_node = None;
                 let mut language_name = None;
                 let mut include_children = false;
 
@@ -223,6 +545,7 @@ impl CompiledGrammar {
                     if Some(capture.index) == self.injection_content_idx {
                         content_node = Some(capture.node);
                     } else if Some(capture.index) == self.injection_language_idx {
+                        language_node = Some(capture.node);
                         // Language can come from captured text
                         if language_name.is_none() {
                             if let Ok(lang) = capture.node.utf8_text(source) {
@@ -232,10 +555,35 @@ impl CompiledGrammar {
                     }
                 }
 
+                // `(#downcase! @injection.language)`, for languages named by
+                // uppercase tags like Terraform's `<<JSON` heredocs
+                let downcase = injections_query
+                    .general_predicates(m.pattern_index)
+                    .iter()
+                    .any(|predicate| &*predicate.operator == "downcase!");
+                if downcase {
+                    language_name = language_name.map(|lang| lang.to_lowercase());
+                }
+
+                // `(#strip-comment! @injection.language)`, for strings annotated
+                // with their language like `/* wgsl */ r#"..."#`
+                let strip_comment = injections_query
+                    .general_predicates(m.pattern_index)
+                    .iter()
+                    .any(|predicate| &*predicate.operator == "strip-comment!");
+                if strip_comment {
+                    language_name = language_name
+                        .as_deref()
+                        .and_then(annotated_language)
+                        .map(str::to_ascii_lowercase);
+                }
+
                 if let (Some(node), Some(lang)) = (content_node, language_name) {
+                    let (start, end) =
+                        injection_range(injections_query, m.pattern_index, node, language_node);
                     injections.push(Injection {
-                        start: node.start_byte() as u32,
-                        end: node.end_byte() as u32,
+                        start: start as u32,
+                        end: end as u32,
                         language: lang,
                         include_children,
                     });
@@ -243,7 +591,173 @@ impl CompiledGrammar {
             }
         }
 
-        ParseResult { spans, injections }
+        injections
+    }
+
+    /// Explain which highlights query captures cover a byte offset.
+    ///
+    /// Returns every capture whose node contains `byte_offset`, outermost node
+    /// first; among captures of the same node, in match order. The last entry
+    /// is therefore the one the renderer ends up using. Internal (`_`-prefixed)
+    /// and `injection.*` captures are skipped like in [`parse`](Self::parse).
+    pub fn explain(
+        &self,
+        ctx: &mut ParseContext,
+        text: &str,
+        byte_offset: u32,
+    ) -> Vec<CaptureExplanation> {
+        let Some(tree) = ctx.parse_tree(text) else {
+            return Vec::new();
+        };
+
+        let offset = byte_offset as usize;
+        let mut explanations = Vec::new();
+        let mut matches =
+            ctx.cursor
+                .matches(&self.highlights_query, tree.root_node(), text.as_bytes());
+
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let capture_name = self.highlights_query.capture_names()[capture.index as usize];
+                if capture_name.starts_with('_') || capture_name.starts_with("injection.") {
+                    continue;
+                }
+
+                let node = capture.node;
+                if node.start_byte() > offset || offset >= node.end_byte() {
+                    continue;
+                }
+
+                explanations.push(CaptureExplanation {
+                    node_kind: node.kind(),
+                    start: node.start_byte() as u32,
+                    end: node.end_byte() as u32,
+                    pattern_index: m.pattern_index,
+                    pattern_line: self.pattern_lines[m.pattern_index],
+                    capture: capture_name.to_string(),
+                });
+            }
+        }
+
+        // Stable sort keeps match order for captures of the same node
+        explanations.sort_by_key(|e| std::cmp::Reverse(e.end - e.start));
+        explanations
+    }
+
+    /// Parse text into its tokens: every non-empty leaf of the syntax tree,
+    /// named or anonymous, in document order.
+    ///
+    /// Each token gets the capture of the innermost node covering it, with
+    /// later matches winning among nodes of the same size, like in the
+    /// rendered output. Internal (`_`-prefixed) and `injection.*` captures are
+    /// skipped like in [`parse`](Self::parse). Returns an empty vector if
+    /// parsing fails.
+    pub fn tokens(&self, ctx: &mut ParseContext, text: &str) -> Vec<Token> {
+        let Some(tree) = ctx.parse_tree(text) else {
+            return Vec::new();
+        };
+
+        let mut tokens = Vec::new();
+        let mut cursor = tree.walk();
+        'walk: loop {
+            let node = cursor.node();
+            if node.child_count() == 0 {
+                if node.start_byte() < node.end_byte() {
+                    tokens.push(Token {
+                        start: node.start_byte() as u32,
+                        end: node.end_byte() as u32,
+                        node_kind: node.kind(),
+                        capture: None,
+                    });
+                }
+            } else if cursor.goto_first_child() {
+                continue;
+            }
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    break 'walk;
+                }
+            }
+        }
+
+        // Length of the node each token got its capture from
+        let mut covered_by: Vec<Option<u32>> = vec![None; tokens.len()];
+        let mut matches =
+            ctx.cursor
+                .matches(&self.highlights_query, tree.root_node(), text.as_bytes());
+        let mut rainbow = RainbowColumns::default();
+
+        while let Some(m) = matches.next() {
+            for capture in m.captures {
+                let capture_name = self.highlights_query.capture_names()[capture.index as usize];
+                if capture_name.starts_with('_') || capture_name.starts_with("injection.") {
+                    continue;
+                }
+
+                let node = capture.node;
+                let (start, end) = (node.start_byte() as u32, node.end_byte() as u32);
+                let name = if Some(capture.index) == self.rainbow_idx {
+                    let column = rainbow.column(node);
+                    Cow::Borrowed(RAINBOW_CAPTURES[column % RAINBOW_CAPTURES.len()])
+                } else {
+                    self.span_captures[capture.index as usize].clone()
+                };
+
+                let first = tokens.partition_point(|token| token.start < start);
+                for (token, len) in tokens[first..].iter_mut().zip(&mut covered_by[first..]) {
+                    if token.end > end {
+                        break;
+                    }
+                    if len.is_none_or(|covered| end - start <= covered) {
+                        *len = Some(end - start);
+                        token.capture = Some(name.clone());
+                    }
+                }
+            }
+        }
+
+        tokens
+    }
+
+    /// Parse text and pretty-print its syntax tree, see [`tree_sexp`].
+    ///
+    /// Returns an empty string if parsing fails.
+    pub fn tree_sexp(&self, ctx: &mut ParseContext, text: &str, options: SexpOptions) -> String {
+        ctx.parser
+            .parse(text, None)
+            .map(|tree| tree_sexp(&tree, options))
+            .unwrap_or_default()
+    }
+
+    /// Parse text and grow `range` to the next larger syntax node, see
+    /// [`expand_selection`](crate::expand_selection).
+    ///
+    /// Returns `range` if parsing fails.
+    pub fn expand_selection(
+        &self,
+        ctx: &mut ParseContext,
+        text: &str,
+        range: Range<usize>,
+    ) -> Range<usize> {
+        match ctx.parse_tree(text) {
+            Some(tree) => crate::expand_selection(&tree, range),
+            None => range,
+        }
+    }
+
+    /// Parse text and find the syntax unit `motion` moves to from `offset`,
+    /// see [`navigate`](crate::navigate).
+    ///
+    /// Returns `None` if parsing fails.
+    pub fn navigate(
+        &self,
+        ctx: &mut ParseContext,
+        text: &str,
+        offset: usize,
+        motion: Motion,
+    ) -> Option<Range<usize>> {
+        let tree = ctx.parse_tree(text)?;
+        crate::navigate(&tree, text, self.textobjects_query.as_ref(), offset, motion)
     }
 }
 
@@ -267,6 +781,7 @@ impl CompiledGrammar {
 pub struct ParseContext {
     parser: Parser,
     cursor: QueryCursor,
+    cancellation_flag: Option<Arc<AtomicBool>>,
 }
 
 impl ParseContext {
@@ -274,6 +789,7 @@ impl ParseContext {
     ///
     /// The parser is configured for the grammar's language.
     pub fn for_grammar(grammar: &CompiledGrammar) -> Result<Self, GrammarError> {
+        check_abi(&grammar.language)?;
         let mut parser = Parser::new();
         parser
             .set_language(&grammar.language)
@@ -282,6 +798,7 @@ impl ParseContext {
         Ok(Self {
             parser,
             cursor: QueryCursor::new(),
+            cancellation_flag: None,
         })
     }
 
@@ -290,6 +807,7 @@ impl ParseContext {
     /// Use this when you need a context before having a grammar,
     /// or when switching between grammars with the same language.
     pub fn for_language(language: &Language) -> Result<Self, GrammarError> {
+        check_abi(language)?;
         let mut parser = Parser::new();
         parser
             .set_language(language)
@@ -298,6 +816,7 @@ impl ParseContext {
         Ok(Self {
             parser,
             cursor: QueryCursor::new(),
+            cancellation_flag: None,
         })
     }
 
@@ -305,10 +824,151 @@ impl ParseContext {
     ///
     /// Call this when switching to a grammar with a different language.
     pub fn set_language(&mut self, language: &Language) -> Result<(), GrammarError> {
+        check_abi(language)?;
         self.parser
             .set_language(language)
             .map_err(|_| GrammarError::LanguageError)
     }
+
+    /// Abandon parses once `flag` is set.
+    ///
+    /// A cancelled parse yields no spans and no injections, as if parsing had
+    /// failed. Pass `None` to parse to completion again.
+    pub fn set_cancellation_flag(&mut self, flag: Option<Arc<AtomicBool>>) {
+        self.cancellation_flag = flag;
+    }
+
+    /// Whether the cancellation flag is set.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation_flag
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+
+    /// Parse `text`, checking the cancellation flag as the parser goes.
+    fn parse_tree(&mut self, text: &str) -> Option<Tree> {
+        let Some(flag) = &self.cancellation_flag else {
+            return self.parser.parse(text, None);
+        };
+        let bytes = text.as_bytes();
+        let mut progress = |_: &ParseState| flag.load(Ordering::Relaxed);
+        self.parser.parse_with_options(
+            &mut |i, _| bytes.get(i..).unwrap_or_default(),
+            None,
+            Some(ParseOptions::new().progress_callback(&mut progress)),
+        )
+    }
+}
+
+/// A pool of idle [`ParseContext`]s shared between threads.
+///
+/// Parsers and query cursors own sizeable native allocations. Servers that
+/// fork a highlighter per request can return contexts to a pool when done
+/// and pick them up again on the next request, instead of reallocating them.
+///
+/// At most `max_idle` contexts are kept; contexts returned to a full pool are
+/// dropped. A pool with `max_idle == 0` never reuses anything.
+///
+/// ```rust,ignore
+/// let pool = ParserPool::new(8);
+///
+/// let mut ctx = pool.acquire(grammar.language())?;
+/// let result = grammar.parse(&mut ctx, "fn main() {}");
+/// // `ctx` goes back to the pool when dropped
+/// ```
+pub struct ParserPool {
+    idle: Mutex<Vec<ParseContext>>,
+    max_idle: usize,
+}
+
+impl Default for ParserPool {
+    /// A pool keeping one idle context per available CPU.
+    fn default() -> Self {
+        Self::new(std::thread::available_parallelism().map_or(4, |n| n.get()))
+    }
+}
+
+impl ParserPool {
+    /// Create a pool that keeps at most `max_idle` idle contexts.
+    pub fn new(max_idle: usize) -> Self {
+        Self {
+            idle: Mutex::new(Vec::new()),
+            max_idle,
+        }
+    }
+
+    /// Maximum number of idle contexts kept.
+    pub fn max_idle(&self) -> usize {
+        self.max_idle
+    }
+
+    /// Number of contexts currently idle in the pool.
+    pub fn idle_count(&self) -> usize {
+        self.idle.lock().unwrap().len()
+    }
+
+    /// Take a context out of the pool, or create one if the pool is empty.
+    ///
+    /// The context is set up for `language`. Hand it back with [`put`](Self::put)
+    /// when done, or use [`acquire`](Self::acquire) to have that done on drop.
+    pub fn take(&self, language: &Language) -> Result<ParseContext, GrammarError> {
+        let pooled = self.idle.lock().unwrap().pop();
+        match pooled {
+            Some(mut ctx) => {
+                ctx.set_language(language)?;
+                Ok(ctx)
+            }
+            None => ParseContext::for_language(language),
+        }
+    }
+
+    /// Return a context to the pool, dropping it if the pool is full.
+    pub fn put(&self, mut ctx: ParseContext) {
+        // Drop any state left over from a cancelled or interrupted parse
+        ctx.parser.reset();
+        ctx.cancellation_flag = None;
+
+        let mut idle = self.idle.lock().unwrap();
+        if idle.len() < self.max_idle {
+            idle.push(ctx);
+        }
+    }
+
+    /// Take a context that is returned to the pool when the guard is dropped.
+    pub fn acquire(&self, language: &Language) -> Result<PooledParseContext<'_>, GrammarError> {
+        Ok(PooledParseContext {
+            pool: self,
+            ctx: Some(self.take(language)?),
+        })
+    }
+}
+
+/// A [`ParseContext`] borrowed from a [`ParserPool`], returned on drop.
+pub struct PooledParseContext<'a> {
+    pool: &'a ParserPool,
+    ctx: Option<ParseContext>,
+}
+
+impl Deref for PooledParseContext<'_> {
+    type Target = ParseContext;
+
+    fn deref(&self) -> &ParseContext {
+        self.ctx.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledParseContext<'_> {
+    fn deref_mut(&mut self) -> &mut ParseContext {
+        self.ctx.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledParseContext<'_> {
+    fn drop(&mut self) {
+        if let Some(ctx) = self.ctx.take() {
+            self.pool.put(ctx);
+        }
+    }
 }
 
 // Backward compatibility aliases
@@ -1,6 +1,6 @@
 //! Core types for highlighting.
 
-use std::fmt;
+use std::borrow::Cow;
 
 /// A span of highlighted text.
 ///
@@ -19,7 +19,11 @@ pub struct Span {
     ///
     /// Examples: "keyword", "function.builtin", "include", "storageclass"
     /// All are mapped to theme slots via `arborium_theme::tag_for_capture()`.
-    pub capture: String,
+    ///
+    /// Native grammars borrow the names arborium knows about from
+    /// `arborium_theme::CAPTURE_NAMES`, so producing a span doesn't allocate.
+    /// Only unrecognized names and spans coming from plugins are owned.
+    pub capture: Cow<'static, str>,
 }
 
 /// An injection point for embedded languages.
@@ -52,26 +56,13 @@ pub struct ParseResult {
 }
 
 /// Errors that can occur during highlighting.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 pub enum HighlightError {
     /// The requested language is not supported.
+    #[error("unsupported language: {0}")]
     UnsupportedLanguage(String),
 
     /// An error occurred during parsing.
+    #[error("parse error: {0}")]
     ParseError(String),
 }
-
-impl fmt::Display for HighlightError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            HighlightError::UnsupportedLanguage(lang) => {
-                write!(f, "unsupported language: {}", lang)
-            }
-            HighlightError::ParseError(msg) => {
-                write!(f, "parse error: {}", msg)
-            }
-        }
-    }
-}
-
-impl std::error::Error for HighlightError {}
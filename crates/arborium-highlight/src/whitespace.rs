@@ -0,0 +1,177 @@
+//! Tab expansion and visible whitespace.
+//!
+//! Tabs render at whatever width the page or terminal uses, which breaks the
+//! alignment of code written with another width in mind, and in Makefiles or
+//! Python the difference between a tab and spaces matters but can't be seen.
+//! [`apply_render_options`] rewrites the text before rendering: tabs become
+//! spaces up to the next tab stop, and with
+//! [`show_whitespace`](RenderOptions::show_whitespace) spaces and tabs are
+//! drawn as `·` and `→` in their own low-contrast spans.
+
+use std::borrow::Cow;
+
+use crate::render::char_display_width;
+use crate::types::Span;
+
+/// Capture for the markers drawn by [`RenderOptions::show_whitespace`].
+pub const WHITESPACE_CAPTURE: &str = "whitespace";
+
+/// Tab width used for visible tabs when [`RenderOptions::tab_width`] is not set.
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
+/// Options for how whitespace in the source is rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Expand tabs to spaces, up to the next multiple of this many columns.
+    ///
+    /// `None` (the default) keeps tabs as they are.
+    pub tab_width: Option<usize>,
+    /// Draw spaces as `·` and tabs as `→`, in [`WHITESPACE_CAPTURE`] spans.
+    ///
+    /// The arrow is padded to the next tab stop, which is
+    /// [`DEFAULT_TAB_WIDTH`] columns when `tab_width` is not set.
+    pub show_whitespace: bool,
+}
+
+/// Apply `options` to `source` and move `spans` along with the text.
+///
+/// Returns the text to render and the spans for it, including the
+/// whitespace markers. Borrows `source` and returns `spans` untouched when
+/// there is nothing to change.
+pub fn apply_render_options<'a>(
+    source: &'a str,
+    mut spans: Vec<Span>,
+    options: &RenderOptions,
+) -> (Cow<'a, str>, Vec<Span>) {
+    let show = options.show_whitespace;
+    let tab_width = match options.tab_width {
+        Some(width) => Some(width.max(1)),
+        None if show => Some(DEFAULT_TAB_WIDTH),
+        None => None,
+    };
+    if !show && (tab_width.is_none() || !source.contains('\t')) {
+        return (Cow::Borrowed(source), spans);
+    }
+
+    let mut text = String::with_capacity(source.len() + source.len() / 2);
+    // (end of a replaced character in `source`, bytes added up to there)
+    let mut shifts: Vec<(u32, u32)> = Vec::new();
+    let mut markers = Vec::new();
+    let mut col = 0;
+
+    for (i, c) in source.char_indices() {
+        let start = text.len();
+        match (c, tab_width) {
+            ('\n', _) => {
+                col = 0;
+                text.push('\n');
+                continue;
+            }
+            ('\t', Some(width)) => {
+                let w = char_display_width('\t', col, width);
+                if show {
+                    text.push('→');
+                    text.extend(std::iter::repeat_n(' ', w - 1));
+                } else {
+                    text.extend(std::iter::repeat_n(' ', w));
+                }
+                col += w;
+            }
+            (' ', _) if show => {
+                text.push('·');
+                col += 1;
+            }
+            _ => {
+                text.push(c);
+                col += char_display_width(c, col, 1);
+                continue;
+            }
+        }
+
+        let end = i + c.len_utf8();
+        shifts.push((end as u32, (text.len() - end) as u32));
+        if show {
+            markers.push(Span {
+                start: start as u32,
+                end: text.len() as u32,
+                capture: WHITESPACE_CAPTURE.into(),
+            });
+        }
+    }
+
+    let map = |offset: u32| {
+        let idx = shifts.partition_point(|&(end, _)| end <= offset);
+        offset + idx.checked_sub(1).map_or(0, |prev| shifts[prev].1)
+    };
+    for span in &mut spans {
+        span.start = map(span.start);
+        span.end = map(span.end);
+    }
+    spans.extend(markers);
+
+    (Cow::Owned(text), spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyword(start: u32, end: u32) -> Span {
+        Span {
+            start,
+            end,
+            capture: "keyword".into(),
+        }
+    }
+
+    fn texts(text: &str, spans: Vec<Span>) -> Vec<(&str, String)> {
+        spans
+            .into_iter()
+            .map(|s| {
+                (
+                    &text[s.start as usize..s.end as usize],
+                    s.capture.into_owned(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_default_is_a_no_op() {
+        let (text, spans) =
+            apply_render_options("\tif x", vec![keyword(1, 3)], &RenderOptions::default());
+        assert!(matches!(text, Cow::Borrowed("\tif x")));
+        assert_eq!((spans[0].start, spans[0].end), (1, 3));
+    }
+
+    #[test]
+    fn test_expand_tabs() {
+        let options = RenderOptions {
+            tab_width: Some(4),
+            show_whitespace: false,
+        };
+        let source = "a\tif\n\t\tx";
+        let (text, spans) = apply_render_options(source, vec![keyword(2, 4)], &options);
+        assert_eq!(text, "a   if\n        x");
+        assert_eq!(texts(&text, spans), [("if", "keyword".to_string())]);
+    }
+
+    #[test]
+    fn test_show_whitespace() {
+        let options = RenderOptions {
+            tab_width: None,
+            show_whitespace: true,
+        };
+        let source = "\tif x";
+        let (text, spans) = apply_render_options(source, vec![keyword(1, 3)], &options);
+        assert_eq!(text, "→   if·x");
+        assert_eq!(
+            texts(&text, spans),
+            [
+                ("if", "keyword".to_string()),
+                ("→   ", WHITESPACE_CAPTURE.to_string()),
+                ("·", WHITESPACE_CAPTURE.to_string()),
+            ]
+        );
+    }
+}
@@ -90,7 +90,7 @@ fn parse_js_result(value: JsValue) -> ParseResult {
         spans.push(Span {
             start,
             end,
-            capture,
+            capture: capture.into(),
         });
     }
 
@@ -225,6 +225,7 @@ impl GrammarProvider for JsGrammarProvider {
 pub struct HighlightConfig {
     max_injection_depth: u32,
     html_format: CoreHtmlFormat,
+    max_chunk_len: Option<usize>,
 }
 
 #[wasm_bindgen]
@@ -234,6 +235,7 @@ impl HighlightConfig {
         Self {
             max_injection_depth: 3,
             html_format: CoreHtmlFormat::default(),
+            max_chunk_len: None,
         }
     }
 
@@ -265,6 +267,14 @@ impl HighlightConfig {
     pub fn set_html_format_class_names_with_prefix(&mut self, prefix: String) {
         self.html_format = CoreHtmlFormat::ClassNamesWithPrefix(prefix);
     }
+
+    /// Split highlighted elements holding more than `max` bytes of text.
+    ///
+    /// Keeps minified sources with very long lines from producing huge elements.
+    #[wasm_bindgen(js_name = setMaxChunkLen)]
+    pub fn set_max_chunk_len(&mut self, max: usize) {
+        self.max_chunk_len = Some(max);
+    }
 }
 
 impl Default for HighlightConfig {
@@ -292,6 +302,7 @@ pub async fn highlight_with_config(
     let core_config = CoreConfig {
         max_injection_depth: config.max_injection_depth,
         html_format: config.html_format.clone(),
+        max_chunk_len: config.max_chunk_len,
     };
 
     let provider = JsGrammarProvider::new();
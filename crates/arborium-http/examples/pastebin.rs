@@ -0,0 +1,36 @@
+//! Pastebin - highlight posted snippets and the files in a directory
+//!
+//! Run with: cargo run -p arborium-http --example pastebin -- [DIR]
+//!
+//! Then:
+//!
+//! - `curl --data-binary @src/main.rs 'http://127.0.0.1:8000/highlight?lang=rust'`
+//! - `curl http://127.0.0.1:8000/files/README.md` for a file from `DIR`
+//!   (default: the current directory), its language picked by extension
+//! - `curl 'http://127.0.0.1:8000/files/Makefile?lang=make'` for the rest
+//!
+//! Repeat a request with `-H 'If-None-Match: "<etag>"'` to get a 304.
+
+use std::sync::Arc;
+
+use arborium::Highlighter;
+use arborium_http::{highlight_files, router};
+use axum::Router;
+use tower_http::services::ServeDir;
+
+#[tokio::main]
+async fn main() {
+    let dir = std::env::args().nth(1).unwrap_or_else(|| ".".to_string());
+
+    let highlighter = Arc::new(Highlighter::new());
+    let files = Router::new().fallback_service(ServeDir::new(&dir)).layer(
+        axum::middleware::from_fn_with_state(highlighter.clone(), highlight_files),
+    );
+    let app = router(highlighter).nest("/files", files);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:8000")
+        .await
+        .expect("failed to bind");
+    println!("Serving on http://127.0.0.1:8000, files from {dir}");
+    axum::serve(listener, app).await.expect("server failed");
+}
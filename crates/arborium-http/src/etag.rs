@@ -0,0 +1,93 @@
+//! Content-addressed ETags for highlighted output.
+
+use axum::http::HeaderMap;
+use axum::http::header::IF_NONE_MATCH;
+
+/// A strong ETag for the highlighted HTML of `source` in `language`.
+///
+/// The tag is a hash of the inputs rather than of the output, so a handler
+/// can answer a matching `If-None-Match` before highlighting anything. The
+/// arborium version is part of the hash: upgrading changes the output, and
+/// with it every tag. Nothing else is, so services that change a
+/// highlighter's configuration at runtime should add to the tag themselves.
+///
+/// The hash is 64-bit FNV-1a, which is stable across platforms and
+/// processes, so tags survive restarts and load balancers.
+pub fn content_etag(language: &str, source: &str) -> String {
+    let mut hash = Fnv1a::default();
+    hash.write(env!("CARGO_PKG_VERSION").as_bytes());
+    hash.write(&[0]);
+    hash.write(language.as_bytes());
+    hash.write(&[0]);
+    hash.write(source.as_bytes());
+    format!("\"{:016x}\"", hash.0)
+}
+
+/// Whether the request's `If-None-Match` header matches `etag`, meaning the
+/// client's cached copy is current and a `304 Not Modified` will do.
+///
+/// Uses the weak comparison that RFC 9110 prescribes for `If-None-Match`:
+/// `W/` prefixes are ignored, and `*` matches anything.
+pub fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_content_etag() {
+        let tag = content_etag("rust", "fn main() {}");
+        assert_eq!(tag, content_etag("rust", "fn main() {}"));
+        assert_ne!(tag, content_etag("rust", "fn main() { }"));
+        assert_ne!(content_etag("rust", "ab"), content_etag("rusta", "b"));
+        assert!(tag.starts_with('"') && tag.ends_with('"'));
+        assert_eq!(tag.len(), 18);
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        let tag = content_etag("json", "{}");
+        let mut headers = HeaderMap::new();
+        assert!(!if_none_match(&headers, &tag));
+
+        headers.insert(
+            IF_NONE_MATCH,
+            HeaderValue::from_static("\"0000000000000000\""),
+        );
+        assert!(!if_none_match(&headers, &tag));
+
+        let list = format!("\"0000000000000000\", W/{tag}");
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_str(&list).unwrap());
+        assert!(if_none_match(&headers, &tag));
+
+        headers.insert(IF_NONE_MATCH, HeaderValue::from_static("*"));
+        assert!(if_none_match(&headers, &tag));
+    }
+}
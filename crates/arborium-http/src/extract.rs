@@ -0,0 +1,70 @@
+//! The [`Highlight`] extractor.
+
+use axum::extract::{FromRequest, Query, Request};
+use axum::http::{StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
+
+/// Source code to highlight: the request body, in the language named by the
+/// `?lang=` query parameter.
+///
+/// Consumes the body, so it has to be the handler's last argument.
+/// Requests without a `lang` parameter are rejected with `400 Bad Request`.
+///
+/// ```rust,ignore
+/// async fn handler(Highlight { language, source }: Highlight) -> String {
+///     format!("{} bytes of {language}", source.len())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Highlight {
+    /// The `lang` query parameter, a language name or alias.
+    pub language: String,
+    /// The request body.
+    pub source: String,
+}
+
+impl<S: Send + Sync> FromRequest<S> for Highlight {
+    type Rejection = Response;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Some(language) = lang_param(request.uri()) else {
+            return Err((StatusCode::BAD_REQUEST, "missing `lang` query parameter").into_response());
+        };
+        let source = String::from_request(request, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        Ok(Self { language, source })
+    }
+}
+
+#[derive(Deserialize)]
+struct Params {
+    lang: Option<String>,
+}
+
+/// The non-empty `lang` query parameter of `uri`.
+pub(crate) fn lang_param(uri: &Uri) -> Option<String> {
+    let Query(params) = Query::<Params>::try_from_uri(uri).ok()?;
+    params.lang.filter(|lang| !lang.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lang_param() {
+        let uri = |s: &str| s.parse::<Uri>().unwrap();
+        assert_eq!(
+            lang_param(&uri("/highlight?lang=rust")).as_deref(),
+            Some("rust")
+        );
+        assert_eq!(
+            lang_param(&uri("/a.txt?x=1&lang=c%2B%2B")).as_deref(),
+            Some("c++")
+        );
+        assert_eq!(lang_param(&uri("/highlight?lang=")), None);
+        assert_eq!(lang_param(&uri("/highlight")), None);
+    }
+}
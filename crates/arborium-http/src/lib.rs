@@ -0,0 +1,115 @@
+//! Serve arborium-highlighted HTML over HTTP.
+//!
+//! Pastebins, doc sites and code review tools all need the same layer
+//! between an HTTP request and a [`Highlighter`]: pick the language, run the
+//! highlighter without blocking the executor, and send the output with
+//! caching headers. This crate provides it for [axum](https://docs.rs/axum)
+//! and other [tower](https://docs.rs/tower) based servers:
+//!
+//! - [`router`]: `POST /highlight?lang=<language>` with the source as body
+//! - [`Highlight`]: the extractor behind it, for handlers of your own
+//! - [`highlight_files`]: middleware that highlights the files served by an
+//!   inner service, like tower-http's `ServeDir`
+//!
+//! Responses carry a [`content_etag`] derived from the language and source,
+//! so a matching `If-None-Match` gets a `304 Not Modified` without any
+//! highlighting. Highlighting runs on tokio's blocking pool through
+//! [`Highlighter::highlight_to_html_async`], and stops when the client goes
+//! away.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//!
+//! use arborium::Highlighter;
+//! use arborium_http::{highlight_files, router};
+//! use axum::Router;
+//! use tower_http::services::ServeDir;
+//!
+//! let highlighter = Arc::new(Highlighter::new());
+//! let files = Router::new()
+//!     .fallback_service(ServeDir::new("pastes"))
+//!     .layer(axum::middleware::from_fn_with_state(highlighter.clone(), highlight_files));
+//! let app = router(highlighter).nest("/files", files);
+//! ```
+//!
+//! # Languages
+//!
+//! The default `all-languages` feature builds every grammar arborium has.
+//! To build only some, turn default features off and enable the `lang-*`
+//! features of `arborium` itself:
+//!
+//! ```toml
+//! arborium = { version = "2", features = ["lang-rust", "lang-markdown"] }
+//! arborium-http = { version = "2", default-features = false }
+//! ```
+//!
+//! # Other frameworks
+//!
+//! [`content_etag`] doesn't depend on a server, and
+//! `highlight_to_html_async` only needs a tokio runtime, so the same layer
+//! takes a few lines in actix-web:
+//!
+//! ```rust,ignore
+//! async fn highlight(
+//!     hl: web::Data<Highlighter>,
+//!     query: web::Query<Params>,
+//!     request: HttpRequest,
+//!     body: String,
+//! ) -> HttpResponse {
+//!     let etag = arborium_http::content_etag(&query.lang, &body);
+//!     // compare with `request.headers().get("if-none-match")`, then
+//!     let html = hl.highlight_to_html_async(&query.lang, body).await;
+//!     // ...
+//! }
+//! ```
+
+mod etag;
+mod extract;
+mod middleware;
+mod response;
+
+use std::sync::Arc;
+
+use arborium::Highlighter;
+use axum::Router;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+
+pub use etag::{content_etag, if_none_match};
+pub use extract::Highlight;
+pub use middleware::highlight_files;
+pub use response::{DEFAULT_CACHE_CONTROL, HighlightedHtml, error_response, not_modified};
+
+/// A router serving `POST /highlight?lang=<language>`, which answers with
+/// the request body highlighted as HTML.
+///
+/// Merge or nest it into an application's own router; forks of
+/// `highlighter` do the work, so its configuration and span filters apply.
+pub fn router(highlighter: Arc<Highlighter>) -> Router {
+    Router::new()
+        .route("/highlight", post(highlight))
+        .with_state(highlighter)
+}
+
+/// The handler behind [`router`], for mounting under a route of your own.
+///
+/// Needs the highlighter as state.
+pub async fn highlight(
+    State(highlighter): State<Arc<Highlighter>>,
+    headers: HeaderMap,
+    Highlight { language, source }: Highlight,
+) -> Response {
+    let etag = content_etag(&language, &source);
+    if if_none_match(&headers, &etag) {
+        return not_modified(etag);
+    }
+
+    match highlighter.highlight_to_html_async(&language, source).await {
+        Ok(html) => HighlightedHtml::new(html, etag).into_response(),
+        Err(e) => error_response(e),
+    }
+}
@@ -0,0 +1,96 @@
+//! Middleware that highlights the files an inner service responds with.
+
+use std::sync::Arc;
+
+use arborium::{Highlighter, detect_language};
+use axum::body::to_bytes;
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::http::header::{IF_MODIFIED_SINCE, IF_NONE_MATCH};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http_body_util::LengthLimitError;
+
+use crate::etag::{content_etag, if_none_match};
+use crate::extract::lang_param;
+use crate::response::{HighlightedHtml, error_response, not_modified};
+
+/// Files larger than this are answered with `413 Payload Too Large`.
+const MAX_FILE_SIZE: usize = 16 * 1024 * 1024;
+
+/// The status for a file whose body couldn't be read: `413 Payload Too
+/// Large` past [`MAX_FILE_SIZE`], `400 Bad Request` otherwise.
+fn read_error_status(error: &axum::Error) -> StatusCode {
+    match std::error::Error::source(error) {
+        Some(source) if source.is::<LengthLimitError>() => StatusCode::PAYLOAD_TOO_LARGE,
+        _ => StatusCode::BAD_REQUEST,
+    }
+}
+
+/// Highlight the successful responses of the wrapped service, e.g. a
+/// static file server, as HTML.
+///
+/// The language comes from the `?lang=` query parameter, or else from the
+/// request path's extension, so `/docs/intro.md` comes back as highlighted
+/// Markdown, code blocks included. Requests for neither are passed through
+/// untouched, as are responses other than `200 OK`.
+///
+/// The inner service's own validators describe the raw file, so
+/// conditional request headers are withheld from it and answered here from
+/// the highlighted output's [`content_etag`].
+///
+/// ```rust,ignore
+/// let files = Router::new()
+///     .fallback_service(ServeDir::new("pastes"))
+///     .layer(axum::middleware::from_fn_with_state(highlighter, highlight_files));
+/// ```
+pub async fn highlight_files(
+    State(highlighter): State<Arc<Highlighter>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let language = lang_param(request.uri())
+        .or_else(|| detect_language(request.uri().path()).map(str::to_string));
+    let Some(language) = language else {
+        return next.run(request).await;
+    };
+
+    let headers = request.headers().clone();
+    request.headers_mut().remove(IF_NONE_MATCH);
+    request.headers_mut().remove(IF_MODIFIED_SINCE);
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let bytes = match to_bytes(response.into_body(), MAX_FILE_SIZE).await {
+        Ok(bytes) => bytes,
+        Err(e) => return read_error_status(&e).into_response(),
+    };
+    let source = String::from_utf8_lossy(&bytes).into_owned();
+
+    let etag = content_etag(&language, &source);
+    if if_none_match(&headers, &etag) {
+        return not_modified(etag);
+    }
+
+    match highlighter.highlight_to_html_async(&language, source).await {
+        Ok(html) => HighlightedHtml::new(html, etag).into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+
+    #[tokio::test]
+    async fn test_read_error_status() {
+        let too_large = to_bytes(Body::from("0123456789"), 4).await.unwrap_err();
+        assert_eq!(read_error_status(&too_large), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let reset = axum::Error::new(std::io::Error::other("connection reset"));
+        assert_eq!(read_error_status(&reset), StatusCode::BAD_REQUEST);
+    }
+}
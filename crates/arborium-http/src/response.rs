@@ -0,0 +1,78 @@
+//! Responses carrying highlighted HTML, and the errors that replace them.
+
+use arborium::Error;
+use axum::http::StatusCode;
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE, ETAG};
+use axum::response::{IntoResponse, Response};
+
+/// The default `Cache-Control`: caches may store the output, but have to
+/// revalidate it with the [`ETag`](crate::content_etag) on every use.
+pub const DEFAULT_CACHE_CONTROL: &str = "public, no-cache";
+
+/// Highlighted HTML, sent as `text/html` with its ETag and a `Cache-Control`
+/// header.
+///
+/// The HTML is a fragment of arborium's custom elements; pages showing it
+/// need a theme's CSS.
+#[derive(Debug, Clone)]
+pub struct HighlightedHtml {
+    html: String,
+    etag: String,
+    cache_control: &'static str,
+}
+
+impl HighlightedHtml {
+    /// Wrap `html` with its `etag`, usually from [`content_etag`](crate::content_etag).
+    pub fn new(html: String, etag: String) -> Self {
+        Self {
+            html,
+            etag,
+            cache_control: DEFAULT_CACHE_CONTROL,
+        }
+    }
+
+    /// Send `value` as the `Cache-Control` header instead of
+    /// [`DEFAULT_CACHE_CONTROL`], e.g. `"public, max-age=31536000, immutable"`
+    /// for URLs that embed the content hash.
+    pub fn with_cache_control(mut self, value: &'static str) -> Self {
+        self.cache_control = value;
+        self
+    }
+}
+
+impl IntoResponse for HighlightedHtml {
+    fn into_response(self) -> Response {
+        (
+            [
+                (CONTENT_TYPE, "text/html; charset=utf-8"),
+                (CACHE_CONTROL, self.cache_control),
+            ],
+            [(ETAG, self.etag)],
+            self.html,
+        )
+            .into_response()
+    }
+}
+
+/// A `304 Not Modified` response for a request whose `If-None-Match`
+/// matched `etag`.
+pub fn not_modified(etag: String) -> Response {
+    (
+        StatusCode::NOT_MODIFIED,
+        [(CACHE_CONTROL, DEFAULT_CACHE_CONTROL)],
+        [(ETAG, etag)],
+    )
+        .into_response()
+}
+
+/// The response for a failed highlight: `400 Bad Request` for languages
+/// that aren't supported, `503 Service Unavailable` for cancelled
+/// highlights, and `500 Internal Server Error` for the rest.
+pub fn error_response(error: Error) -> Response {
+    let status = match error {
+        Error::UnsupportedLanguage { .. } => StatusCode::BAD_REQUEST,
+        Error::Cancelled => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, error.to_string()).into_response()
+}
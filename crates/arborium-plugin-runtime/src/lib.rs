@@ -6,8 +6,14 @@
 //! - Session management (create/free)
 //! - Parser state and tree storage
 //! - Query execution to produce Span and Injection records
-//! - Incremental parsing via edit application
+//! - Incremental parsing via edit application or text splicing
 //! - Cancellation support
+//! - Replacing the queries of live sessions
+//! - Syntax tree dumps for debugging grammars
+//! - Ad-hoc queries over the syntax tree, for structural search
+//! - Folding ranges, document symbols and selection expansion, for editors
+//! - Comment toggling
+//! - Prose ranges, for spell checkers
 //!
 //! # Example
 //!
@@ -37,11 +43,57 @@ use alloc::string::String;
 use alloc::vec::Vec;
 use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 
+use arborium_syntax::{annotated_language, injection_range, tree_sexp};
 use arborium_tree_sitter::{
-    InputEdit, Language, LanguageFn, Parser, Point, Query, QueryCursor, QueryError,
-    StreamingIterator, Tree,
+    InputEdit, LANGUAGE_VERSION, Language, LanguageFn, MIN_COMPATIBLE_LANGUAGE_VERSION, Parser,
+    Point, Query, QueryCursor, QueryError, StreamingIterator, Tree,
 };
-use arborium_wire::{Edit, Injection, ParseError, ParseResult, Span};
+use arborium_wire::{
+    CommentEdit, Edit, FoldingRange, Injection, ParseError, ParseErrorKind, ParseResult,
+    ProseRange, QueryCapture, QueryMatch, Range, Span, Symbol, Utf16Offsets,
+};
+
+pub use arborium_syntax::{CommentSyntax, ProseClass, SexpOptions};
+
+/// Check that `language` was generated for a tree-sitter ABI this runtime
+/// can load.
+///
+/// Plugins call this in their handshake with the host, so that an
+/// incompatible grammar is reported as a [`ParseErrorKind::IncompatibleGrammar`]
+/// error naming `language_id` and the ABI versions, rather than failing on
+/// first use.
+pub fn check_language(language: LanguageFn, language_id: &str) -> Result<(), ParseError> {
+    let found = Language::from(language).abi_version();
+    if (MIN_COMPATIBLE_LANGUAGE_VERSION..=LANGUAGE_VERSION).contains(&found) {
+        return Ok(());
+    }
+    Err(ParseError::with_kind(
+        ParseErrorKind::IncompatibleGrammar,
+        format!(
+            "{language_id} grammar uses tree-sitter ABI {found}, but only ABI \
+             {MIN_COMPATIBLE_LANGUAGE_VERSION} through {LANGUAGE_VERSION} is supported; \
+             regenerate it with a tree-sitter CLI in that range"
+        ),
+    ))
+}
+
+/// The edits that toggle comments on the lines `start..end` touches in
+/// `text`, for a language whose comment delimiters are `syntax`.
+///
+/// Offsets are UTF-16 code units, in and out, like those of the plugins'
+/// newer exports. See [`arborium_syntax::toggle_comments`].
+pub fn comment_ranges(syntax: CommentSyntax, text: &str, start: u32, end: u32) -> Vec<CommentEdit> {
+    let offsets = Utf16Offsets::new(text);
+    let selection = offsets.to_byte(start) as usize..offsets.to_byte(end) as usize;
+    arborium_syntax::toggle_comments(syntax, text, selection)
+        .into_iter()
+        .map(|edit| CommentEdit {
+            start: offsets.to_utf16(edit.range.start as u32),
+            end: offsets.to_utf16(edit.range.end as u32),
+            replacement: edit.replacement,
+        })
+        .collect()
+}
 
 /// Configuration for syntax highlighting.
 ///
@@ -69,7 +121,20 @@ impl HighlightConfig {
         injections_query: &str,
         locals_query: &str,
     ) -> Result<Self, QueryError> {
-        let language: Language = language.into();
+        Self::for_language(
+            language.into(),
+            highlights_query,
+            injections_query,
+            locals_query,
+        )
+    }
+
+    fn for_language(
+        language: Language,
+        highlights_query: &str,
+        injections_query: &str,
+        locals_query: &str,
+    ) -> Result<Self, QueryError> {
         // Concatenate queries: injections, then locals, then highlights
         // Add newline separators to ensure queries don't merge incorrectly
         // if they don't end with newlines
@@ -232,6 +297,57 @@ impl PluginRuntime {
         }
     }
 
+    /// Replace the text between UTF-16 offsets `start` and `end` with
+    /// `replacement`, and reparse incrementally.
+    ///
+    /// Unlike [`set_text`](Self::set_text), hosts only send the change, and
+    /// unlike [`apply_edit`](Self::apply_edit), they don't track byte offsets
+    /// and rows: the edit is computed from the session's copy of the text.
+    /// Offsets are clamped to the text and rounded down to a character start,
+    /// like [`Utf16Offsets::to_byte`].
+    pub fn splice_text(
+        &mut self,
+        session_id: u32,
+        start: u32,
+        end: u32,
+        replacement: &str,
+    ) -> Result<(), ParseError> {
+        let session = self
+            .sessions
+            .get_mut(&session_id)
+            .ok_or_else(ParseError::invalid_session)?;
+        if start > end {
+            return Err(ParseError::new(format!(
+                "splice start {start} is after its end {end}"
+            )));
+        }
+
+        let offsets = Utf16Offsets::new(&session.text);
+        let start_byte = offsets.to_byte(start) as usize;
+        let old_end_byte = offsets.to_byte(end) as usize;
+        let new_end_byte = start_byte + replacement.len();
+
+        let start_position = point_at(&session.text, start_byte);
+        let input_edit = InputEdit {
+            start_byte,
+            old_end_byte,
+            new_end_byte,
+            start_position,
+            old_end_position: point_at(&session.text, old_end_byte),
+            new_end_position: advance(start_position, replacement),
+        };
+
+        session
+            .text
+            .replace_range(start_byte..old_end_byte, replacement);
+        if let Some(tree) = &mut session.tree {
+            tree.edit(&input_edit);
+        }
+        session.tree = session.parser.parse(&session.text, session.tree.as_ref());
+        session.cancelled.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
     /// Request cancellation of an in-progress parse.
     pub fn cancel(&mut self, session_id: u32) {
         if let Some(session) = self.sessions.get(&session_id) {
@@ -246,17 +362,14 @@ impl PluginRuntime {
         let session = self
             .sessions
             .get_mut(&session_id)
-            .ok_or_else(|| ParseError::new("invalid session id"))?;
+            .ok_or_else(ParseError::invalid_session)?;
 
         // Check for cancellation
         if session.cancelled.load(Ordering::Relaxed) {
             return Ok(ParseResult::empty());
         }
 
-        let tree = session
-            .tree
-            .as_ref()
-            .ok_or_else(|| ParseError::new("no text set for session"))?;
+        let tree = session.tree.as_ref().ok_or_else(ParseError::no_text)?;
 
         // Temporary structs to hold byte offsets before conversion
         struct RawSpan {
@@ -297,11 +410,13 @@ impl PluginRuntime {
             // Process injections (patterns before locals_pattern_index)
             if m.pattern_index < self.config.locals_pattern_index {
                 let mut language_name: Option<&str> = None;
+                let mut language_node = None;
                 let mut content_node = None;
                 let mut include_children = false;
 
                 for capture in m.captures {
                     if Some(capture.index) == self.config.injection_language_capture_index {
+                        language_node = Some(capture.node);
                         if let Ok(name) = capture.node.utf8_text(source) {
                             language_name = Some(name);
                         }
@@ -325,11 +440,36 @@ impl PluginRuntime {
                     }
                 }
 
+                // `(#strip-comment! @injection.language)`, for strings annotated
+                // with their language like `/* wgsl */ r#"..."#`
+                let strip_comment = self
+                    .config
+                    .query
+                    .general_predicates(m.pattern_index)
+                    .iter()
+                    .any(|predicate| &*predicate.operator == "strip-comment!");
+                if strip_comment {
+                    language_name = language_name.and_then(annotated_language);
+                }
+
                 if let (Some(lang), Some(node)) = (language_name, content_node) {
+                    let (start, end) =
+                        injection_range(&self.config.query, m.pattern_index, node, language_node);
+                    // `(#downcase! @injection.language)`, for `<<SQL` heredocs
+                    let downcase = self
+                        .config
+                        .query
+                        .general_predicates(m.pattern_index)
+                        .iter()
+                        .any(|predicate| &*predicate.operator == "downcase!");
                     raw_injections.push(RawInjection {
-                        start: node.start_byte(),
-                        end: node.end_byte(),
-                        language: String::from(lang),
+                        start,
+                        end,
+                        language: if downcase || strip_comment {
+                            lang.to_lowercase()
+                        } else {
+                            String::from(lang)
+                        },
                         include_children,
                     });
                 }
@@ -371,8 +511,8 @@ impl PluginRuntime {
         }
 
         // Output raw UTF-8 byte offsets (native to tree-sitter).
-        // The Rust host needs UTF-8 byte offsets for string slicing.
-        // The JS fallback path will convert to UTF-16 indices if needed.
+        // The Rust host needs UTF-8 byte offsets for string slicing;
+        // `parse_utf16` converts them for hosts that index by UTF-16.
         let mut spans: Vec<Span> = raw_spans
             .into_iter()
             .map(|s| Span {
@@ -399,15 +539,285 @@ impl PluginRuntime {
         Ok(ParseResult { spans, injections })
     }
 
+    /// Like [`parse`](Self::parse), but with UTF-16 code unit offsets.
+    ///
+    /// For hosts that index text the way JavaScript strings do. Offsets are
+    /// converted with [`Utf16Offsets`], so they never split a surrogate pair.
+    pub fn parse_utf16(&mut self, session_id: u32) -> Result<ParseResult, ParseError> {
+        let mut result = self.parse(session_id)?;
+        if let Some(session) = self.sessions.get(&session_id) {
+            Utf16Offsets::new(&session.text).convert(&mut result);
+        }
+        Ok(result)
+    }
+
+    /// Run an ad-hoc tree-sitter query over the session's current syntax tree.
+    ///
+    /// For structural search and lint rules in web tools, which would
+    /// otherwise need a tree-sitter build of their own. Text predicates like
+    /// `#eq?` and `#match?` are applied; captures whose name starts with `_`
+    /// are left out, as in highlights. Offsets are byte offsets, like
+    /// [`parse`](Self::parse)'s.
+    pub fn query(
+        &mut self,
+        session_id: u32,
+        query_source: &str,
+    ) -> Result<Vec<QueryMatch>, ParseError> {
+        let session = self
+            .sessions
+            .get_mut(&session_id)
+            .ok_or_else(ParseError::invalid_session)?;
+        let tree = session.tree.as_ref().ok_or_else(ParseError::no_text)?;
+        let query = Query::new(&self.config.language, query_source)
+            .map_err(|e| ParseError::new(format!("query error: {e}")))?;
+
+        let mut result = Vec::new();
+        let source = session.text.as_bytes();
+        let mut matches = session.cursor.matches(&query, tree.root_node(), source);
+        while let Some(m) = matches.next() {
+            let captures = m
+                .captures
+                .iter()
+                .map(|capture| (query.capture_names()[capture.index as usize], capture.node))
+                .filter(|(name, _)| !name.starts_with('_'))
+                .map(|(name, node)| QueryCapture {
+                    name: String::from(name),
+                    kind: String::from(node.kind()),
+                    start: node.start_byte() as u32,
+                    end: node.end_byte() as u32,
+                })
+                .collect();
+            result.push(QueryMatch {
+                pattern: m.pattern_index as u32,
+                captures,
+            });
+        }
+        Ok(result)
+    }
+
+    /// Like [`query`](Self::query), but with UTF-16 code unit offsets.
+    pub fn query_utf16(
+        &mut self,
+        session_id: u32,
+        query_source: &str,
+    ) -> Result<Vec<QueryMatch>, ParseError> {
+        let mut matches = self.query(session_id, query_source)?;
+        if let Some(session) = self.sessions.get(&session_id) {
+            Utf16Offsets::new(&session.text).convert_matches(&mut matches);
+        }
+        Ok(matches)
+    }
+
+    /// The lines of the session's text that can be folded, ordered by start
+    /// line. See [`arborium_syntax::folding_ranges`].
+    pub fn folding_ranges(&self, session_id: u32) -> Result<Vec<FoldingRange>, ParseError> {
+        let tree = self.tree(session_id)?;
+        Ok(arborium_syntax::folding_ranges(tree)
+            .into_iter()
+            .map(|range| FoldingRange {
+                start_row: range.start_row,
+                end_row: range.end_row,
+                kind: String::from(range.kind.as_str()),
+            })
+            .collect())
+    }
+
+    /// The definitions in the session's text, for outline panes. See
+    /// [`arborium_syntax::symbols`].
+    pub fn symbols(&self, session_id: u32) -> Result<Vec<Symbol>, ParseError> {
+        fn convert(symbol: arborium_syntax::Symbol) -> Symbol {
+            Symbol {
+                name: symbol.name,
+                kind: String::from(symbol.kind),
+                start: symbol.start,
+                end: symbol.end,
+                name_start: symbol.name_start,
+                name_end: symbol.name_end,
+                children: symbol.children.into_iter().map(convert).collect(),
+            }
+        }
+
+        let tree = self.tree(session_id)?;
+        let text = &self.sessions[&session_id].text;
+        Ok(arborium_syntax::symbols(tree, text)
+            .into_iter()
+            .map(convert)
+            .collect())
+    }
+
+    /// Like [`symbols`](Self::symbols), but with UTF-16 code unit offsets.
+    pub fn symbols_utf16(&self, session_id: u32) -> Result<Vec<Symbol>, ParseError> {
+        let mut symbols = self.symbols(session_id)?;
+        Utf16Offsets::new(&self.sessions[&session_id].text).convert_symbols(&mut symbols);
+        Ok(symbols)
+    }
+
+    /// Grow the selection `start..end`, byte offsets into the session's
+    /// text, to the next larger syntax node. See
+    /// [`arborium_syntax::expand_selection`].
+    pub fn expand_selection(
+        &self,
+        session_id: u32,
+        start: u32,
+        end: u32,
+    ) -> Result<Range, ParseError> {
+        let tree = self.tree(session_id)?;
+        let range = arborium_syntax::expand_selection(tree, start as usize..end as usize);
+        Ok(Range {
+            start: range.start as u32,
+            end: range.end as u32,
+        })
+    }
+
+    /// Like [`expand_selection`](Self::expand_selection), but with UTF-16
+    /// code unit offsets, in and out.
+    pub fn expand_selection_utf16(
+        &self,
+        session_id: u32,
+        start: u32,
+        end: u32,
+    ) -> Result<Range, ParseError> {
+        let session = self
+            .sessions
+            .get(&session_id)
+            .ok_or_else(ParseError::invalid_session)?;
+        let offsets = Utf16Offsets::new(&session.text);
+        let range =
+            self.expand_selection(session_id, offsets.to_byte(start), offsets.to_byte(end))?;
+        Ok(Range {
+            start: offsets.to_utf16(range.start),
+            end: offsets.to_utf16(range.end),
+        })
+    }
+
+    /// The ranges of the session's text holding prose of the given
+    /// `classes`, for spell checkers, read off its highlights. See
+    /// [`arborium_syntax::prose_ranges`].
+    ///
+    /// Only this plugin's language is looked at: text in injected languages
+    /// is whatever the injection's host node is.
+    pub fn prose_ranges(
+        &mut self,
+        session_id: u32,
+        classes: &[ProseClass],
+    ) -> Result<Vec<ProseRange>, ParseError> {
+        let spans = self.parse(session_id)?.spans;
+        let spans = spans
+            .iter()
+            .map(|span| (span.start..span.end, span.capture.as_str()));
+        Ok(arborium_syntax::prose_ranges(spans, classes)
+            .into_iter()
+            .map(|prose| ProseRange {
+                start: prose.range.start as u32,
+                end: prose.range.end as u32,
+                class: String::from(prose.class.as_str()),
+            })
+            .collect())
+    }
+
+    /// Like [`prose_ranges`](Self::prose_ranges), but with UTF-16 code unit
+    /// offsets.
+    pub fn prose_ranges_utf16(
+        &mut self,
+        session_id: u32,
+        classes: &[ProseClass],
+    ) -> Result<Vec<ProseRange>, ParseError> {
+        let mut ranges = self.prose_ranges(session_id, classes)?;
+        let offsets = Utf16Offsets::new(&self.sessions[&session_id].text);
+        for range in &mut ranges {
+            range.start = offsets.to_utf16(range.start);
+            range.end = offsets.to_utf16(range.end);
+        }
+        Ok(ranges)
+    }
+
+    /// The session's current syntax tree.
+    fn tree(&self, session_id: u32) -> Result<&Tree, ParseError> {
+        let session = self
+            .sessions
+            .get(&session_id)
+            .ok_or_else(ParseError::invalid_session)?;
+        session.tree.as_ref().ok_or_else(ParseError::no_text)
+    }
+
+    /// Pretty-print the session's current syntax tree, like `tree-sitter parse`.
+    pub fn tree_sexp(&self, session_id: u32, options: SexpOptions) -> Result<String, ParseError> {
+        let session = self
+            .sessions
+            .get(&session_id)
+            .ok_or_else(ParseError::invalid_session)?;
+        let tree = session.tree.as_ref().ok_or_else(ParseError::no_text)?;
+
+        Ok(tree_sexp(tree, options))
+    }
+
+    /// Replace the highlights, injections and locals queries.
+    ///
+    /// Sessions and their syntax trees are kept: their next [`parse`](Self::parse)
+    /// runs the new queries over the current tree, without reparsing. Used by
+    /// the playground to edit a grammar's queries live. If the queries don't
+    /// compile, the error is returned and the old ones stay in use.
+    pub fn update_queries(
+        &mut self,
+        highlights_query: &str,
+        injections_query: &str,
+        locals_query: &str,
+    ) -> Result<(), QueryError> {
+        self.config = HighlightConfig::for_language(
+            self.config.language.clone(),
+            highlights_query,
+            injections_query,
+            locals_query,
+        )?;
+        Ok(())
+    }
+
     /// Get the language provided by this plugin.
     pub fn language(&self) -> &Language {
         &self.config.language
     }
 }
 
+/// The row and byte column of `byte` in `text`, as tree-sitter counts them.
+fn point_at(text: &str, byte: usize) -> Point {
+    advance(Point::new(0, 0), &text[..byte])
+}
+
+/// The position after `text`, when it starts at `point`.
+fn advance(point: Point, text: &str) -> Point {
+    match text.rfind('\n') {
+        Some(last) => Point::new(
+            point.row + text.matches('\n').count(),
+            text.len() - last - 1,
+        ),
+        None => Point::new(point.row, point.column + text.len()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    // Integration tests that require a grammar - only available after grammar generation
+    use super::*;
+
+    #[test]
+    fn test_comment_ranges_utf16() {
+        let syntax = CommentSyntax {
+            line: Some("//"),
+            block: None,
+        };
+        // 变 is 3 bytes but 1 code unit
+        let edits = comment_ranges(syntax, "变\nx", 2, 3);
+        assert_eq!(
+            edits,
+            [CommentEdit {
+                start: 2,
+                end: 2,
+                replacement: "// ".into(),
+            }]
+        );
+    }
+
+    // Integration tests that require a grammar - only available after grammar generation,
+    // with `--features integration-tests`
     #[cfg(feature = "integration-tests")]
     mod integration {
         use super::super::*;
@@ -442,6 +852,34 @@ mod tests {
             runtime.free_session(session);
         }
 
+        #[test]
+        fn test_parse_utf16() {
+            let config = HighlightConfig::new(
+                arborium_rust::language(),
+                arborium_rust::HIGHLIGHTS_QUERY,
+                arborium_rust::INJECTIONS_QUERY,
+                arborium_rust::LOCALS_QUERY,
+            )
+            .expect("failed to create config");
+
+            let mut runtime = PluginRuntime::new(config);
+            let session = runtime.create_session();
+
+            let text = "// 🎉 变量\nfn main() {}";
+            runtime.set_text(session, text);
+            let bytes = runtime.parse(session).expect("parse failed");
+            let units = runtime.parse_utf16(session).expect("parse failed");
+
+            let utf16: Vec<u16> = text.encode_utf16().collect();
+            for (b, u) in bytes.spans.iter().zip(&units.spans) {
+                let expected = &text[b.start as usize..b.end as usize];
+                let actual = &utf16[u.start as usize..u.end as usize];
+                assert_eq!(String::from_utf16(actual).unwrap(), expected);
+            }
+
+            runtime.free_session(session);
+        }
+
         #[test]
         fn test_incremental_edit() {
             let config = HighlightConfig::new(
@@ -482,6 +920,225 @@ mod tests {
             runtime.free_session(session);
         }
 
+        #[test]
+        fn test_update_queries() {
+            let config = HighlightConfig::new(
+                arborium_rust::language(),
+                arborium_rust::HIGHLIGHTS_QUERY,
+                arborium_rust::INJECTIONS_QUERY,
+                arborium_rust::LOCALS_QUERY,
+            )
+            .expect("failed to create config");
+
+            let mut runtime = PluginRuntime::new(config);
+            let session = runtime.create_session();
+            runtime.set_text(session, "fn main() {}");
+
+            runtime
+                .update_queries("\"fn\" @keyword.function", "", "")
+                .expect("valid query");
+            let result = runtime.parse(session).expect("parse failed");
+            assert_eq!(result.spans.len(), 1);
+            assert_eq!(result.spans[0].capture, "keyword.function");
+
+            // Invalid queries keep the old ones
+            assert!(runtime.update_queries("(no_such_node) @x", "", "").is_err());
+            let result = runtime.parse(session).expect("parse failed");
+            assert_eq!(result.spans.len(), 1);
+
+            runtime.free_session(session);
+        }
+
+        #[test]
+        fn test_splice_text() {
+            let config = HighlightConfig::new(
+                arborium_rust::language(),
+                arborium_rust::HIGHLIGHTS_QUERY,
+                arborium_rust::INJECTIONS_QUERY,
+                arborium_rust::LOCALS_QUERY,
+            )
+            .expect("failed to create config");
+
+            let mut runtime = PluginRuntime::new(config);
+            let session = runtime.create_session();
+            let fresh = runtime.create_session();
+
+            // UTF-16 offsets: the emoji is 2 code units but 4 bytes
+            runtime.set_text(session, "// 🎉\nfn main() {}");
+            runtime
+                .splice_text(session, 17, 17, "\n    let x = 1;\n")
+                .expect("splice failed");
+            runtime
+                .splice_text(session, 9, 13, "start")
+                .expect("splice failed");
+
+            let text = "// 🎉\nfn start() {\n    let x = 1;\n}";
+            runtime.set_text(fresh, text);
+            let spliced = runtime.parse(session).expect("parse failed");
+            let expected = runtime.parse(fresh).expect("parse failed");
+            assert_eq!(spliced, expected);
+            assert_eq!(
+                runtime.tree_sexp(session, SexpOptions::default()),
+                runtime.tree_sexp(fresh, SexpOptions::default())
+            );
+
+            assert!(runtime.splice_text(session, 3, 2, "").is_err());
+            assert!(runtime.splice_text(999, 0, 0, "").is_err());
+        }
+
+        #[test]
+        fn test_query() {
+            let config = HighlightConfig::new(
+                arborium_rust::language(),
+                arborium_rust::HIGHLIGHTS_QUERY,
+                arborium_rust::INJECTIONS_QUERY,
+                arborium_rust::LOCALS_QUERY,
+            )
+            .expect("failed to create config");
+
+            let mut runtime = PluginRuntime::new(config);
+            let session = runtime.create_session();
+            let text = "fn 变() {}\nfn main() { 变(); }";
+            runtime.set_text(session, text);
+
+            let query = "(function_item name: (identifier) @name (#not-eq? @name \"main\")) @fn";
+            let matches = runtime.query(session, query).expect("query failed");
+            assert_eq!(matches.len(), 1);
+            assert_eq!(matches[0].pattern, 0);
+            let name = matches[0]
+                .captures
+                .iter()
+                .find(|c| c.name == "name")
+                .unwrap();
+            assert_eq!(name.kind, "identifier");
+            assert_eq!(&text[name.start as usize..name.end as usize], "变");
+
+            let matches = runtime.query_utf16(session, query).expect("query failed");
+            let name = matches[0]
+                .captures
+                .iter()
+                .find(|c| c.name == "name")
+                .unwrap();
+            assert_eq!((name.start, name.end), (3, 4));
+
+            let error = runtime.query(session, "(no_such_node) @x").unwrap_err();
+            assert!(error.message.starts_with("query error"));
+
+            runtime.free_session(session);
+        }
+
+        #[test]
+        fn test_folding_ranges_and_symbols() {
+            let config = HighlightConfig::new(
+                arborium_rust::language(),
+                arborium_rust::HIGHLIGHTS_QUERY,
+                arborium_rust::INJECTIONS_QUERY,
+                arborium_rust::LOCALS_QUERY,
+            )
+            .expect("failed to create config");
+
+            let mut runtime = PluginRuntime::new(config);
+            let session = runtime.create_session();
+            let text = "// 变\n// b\nmod m {\n    fn f() {\n        g();\n    }\n}\n";
+            runtime.set_text(session, text);
+
+            let ranges = runtime.folding_ranges(session).expect("no tree");
+            let ranges: Vec<_> = ranges
+                .iter()
+                .map(|r| (r.start_row, r.end_row, r.kind.as_str()))
+                .collect();
+            assert_eq!(
+                ranges,
+                [(0, 1, "comment"), (2, 5, "region"), (3, 4, "region")]
+            );
+
+            let symbols = runtime.symbols(session).expect("no tree");
+            assert_eq!(symbols.len(), 1);
+            assert_eq!(
+                (symbols[0].name.as_str(), symbols[0].kind.as_str()),
+                ("m", "module")
+            );
+            let f = &symbols[0].children[0];
+            assert_eq!((f.name.as_str(), f.kind.as_str()), ("f", "function"));
+            assert_eq!(&text[f.name_start as usize..f.name_end as usize], "f");
+
+            // The comment's 变 is 3 bytes but 1 code unit
+            let symbols = runtime.symbols_utf16(session).expect("no tree");
+            assert_eq!(symbols[0].children[0].name_start, f.name_start - 2);
+
+            runtime.free_session(session);
+        }
+
+        #[test]
+        fn test_expand_selection() {
+            let config = HighlightConfig::new(
+                arborium_rust::language(),
+                arborium_rust::HIGHLIGHTS_QUERY,
+                arborium_rust::INJECTIONS_QUERY,
+                arborium_rust::LOCALS_QUERY,
+            )
+            .expect("failed to create config");
+
+            let mut runtime = PluginRuntime::new(config);
+            let session = runtime.create_session();
+            let text = "// 变
+fn f() { g(1, 2); }";
+            runtime.set_text(session, text);
+
+            let mut range = Range { start: 18, end: 18 };
+            let mut selected = Vec::new();
+            for _ in 0..3 {
+                range = runtime
+                    .expand_selection(session, range.start, range.end)
+                    .expect("no tree");
+                selected.push(&text[range.start as usize..range.end as usize]);
+            }
+            assert_eq!(selected, ["1", "(1, 2)", "g(1, 2)"]);
+
+            // The comment's 变 is 3 bytes but 1 code unit
+            let range = runtime
+                .expand_selection_utf16(session, 16, 16)
+                .expect("no tree");
+            assert_eq!(range, Range { start: 16, end: 17 });
+
+            runtime.free_session(session);
+        }
+
+        #[test]
+        fn test_prose_ranges() {
+            let config = HighlightConfig::new(
+                arborium_rust::language(),
+                arborium_rust::HIGHLIGHTS_QUERY,
+                arborium_rust::INJECTIONS_QUERY,
+                arborium_rust::LOCALS_QUERY,
+            )
+            .expect("failed to create config");
+
+            let mut runtime = PluginRuntime::new(config);
+            let session = runtime.create_session();
+            runtime.set_text(session, "// 变\nfn f() { g(\"a\\tb\"); }");
+
+            let all = runtime
+                .prose_ranges(session, ProseClass::ALL)
+                .expect("parse failed");
+            assert_eq!((all[0].start, all[0].class.as_str()), (0, "comment"));
+
+            // The escape is left out of the string
+            let bounds = |ranges: Vec<ProseRange>| -> Vec<(u32, u32)> {
+                ranges.iter().map(|r| (r.start, r.end)).collect()
+            };
+            let strings = runtime
+                .prose_ranges(session, &[ProseClass::String])
+                .expect("parse failed");
+            assert_eq!(bounds(strings), [(18, 20), (22, 24)]);
+            let strings = runtime
+                .prose_ranges_utf16(session, &[ProseClass::String])
+                .expect("parse failed");
+            assert_eq!(bounds(strings), [(16, 18), (20, 22)]);
+
+            runtime.free_session(session);
+        }
+
         #[test]
         fn test_cancellation() {
             let config = HighlightConfig::new(
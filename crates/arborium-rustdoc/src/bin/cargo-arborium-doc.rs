@@ -0,0 +1,172 @@
+//! `cargo arborium-doc`: run `cargo doc`, then highlight the result in place.
+//!
+//! All arguments are forwarded to `cargo doc`. The doc directory is found
+//! with `cargo metadata`, honouring `--manifest-path`, `--target-dir` and
+//! `--target`. Highlighting options come from the documented package's
+//! `[package.metadata.arborium]` table, or else an `arborium.toml` in the
+//! working directory.
+
+use anyhow::{Context, Result, bail};
+use arborium::config_file::FileConfig;
+use arborium_rustdoc::{PackageMetadata, Processor};
+use owo_colors::OwoColorize;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::{Command, exit};
+
+fn main() -> Result<()> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    // Cargo runs `cargo arborium-doc ...` as `cargo-arborium-doc arborium-doc ...`
+    if args.first().is_some_and(|arg| arg == "arborium-doc") {
+        args.remove(0);
+    }
+
+    let help = args.iter().any(|arg| arg == "-h" || arg == "--help");
+    if help {
+        eprintln!("Usage: cargo arborium-doc [CARGO DOC OPTIONS]\n");
+        eprintln!("Runs `cargo doc` with the given options, then highlights non-Rust code");
+        eprintln!("blocks in the generated docs. `cargo doc`'s options follow.\n");
+    }
+
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let status = Command::new(&cargo)
+        .arg("doc")
+        .args(&args)
+        .status()
+        .context("failed to run cargo doc")?;
+    if !status.success() {
+        exit(status.code().unwrap_or(1));
+    }
+    if help {
+        return Ok(());
+    }
+
+    let metadata = cargo_metadata(&cargo, &args)?;
+    let doc_dir = doc_dir(&metadata, &args)?;
+    if !doc_dir.is_dir() {
+        bail!("cargo doc didn't write to {}", doc_dir.display());
+    }
+
+    let manifest_path = match flag_value(&args, "--manifest-path") {
+        Some(path) => PathBuf::from(path),
+        None => find_manifest()?,
+    };
+    let manifest_path = manifest_path.canonicalize().unwrap_or(manifest_path);
+    let package = flag_value(&args, "-p").or_else(|| flag_value(&args, "--package"));
+    let package_metadata =
+        match PackageMetadata::from_cargo_metadata(&metadata, package, &manifest_path)? {
+            Some(package_metadata) => package_metadata,
+            None => PackageMetadata {
+                config: FileConfig::discover(Path::new("."))?.unwrap_or_default(),
+                ..Default::default()
+            },
+        };
+    let options = package_metadata.process_options(doc_dir.clone())?;
+
+    eprintln!(
+        "{} Highlighting {}",
+        "arborium-rustdoc".green().bold(),
+        doc_dir.display()
+    );
+    let stats = Processor::new(options).process()?;
+    eprintln!(
+        "  {} code blocks highlighted in {} HTML files",
+        stats.blocks_highlighted.to_string().green(),
+        stats.files_processed.to_string().cyan()
+    );
+    if !stats.unsupported_languages.is_empty() {
+        eprintln!(
+            "  {} Unsupported languages: {}",
+            "Note:".yellow(),
+            stats.unsupported_languages.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Run `cargo metadata` for the workspace `cargo doc` documented.
+fn cargo_metadata(cargo: &OsStr, args: &[String]) -> Result<serde_json::Value> {
+    let mut command = Command::new(cargo);
+    command.args(["metadata", "--format-version", "1", "--no-deps"]);
+    if let Some(manifest_path) = flag_value(args, "--manifest-path") {
+        command.args(["--manifest-path", manifest_path]);
+    }
+    let output = command.output().context("failed to run cargo metadata")?;
+    if !output.status.success() {
+        bail!(
+            "cargo metadata failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    serde_json::from_slice(&output.stdout).context("failed to parse cargo metadata output")
+}
+
+/// The directory `cargo doc` wrote to.
+fn doc_dir(metadata: &serde_json::Value, args: &[String]) -> Result<PathBuf> {
+    let target_dir = match flag_value(args, "--target-dir") {
+        Some(dir) => PathBuf::from(dir),
+        None => metadata["target_directory"]
+            .as_str()
+            .map(PathBuf::from)
+            .context("cargo metadata didn't report a target directory")?,
+    };
+
+    // With `--target`, docs go to `target/<triple>/doc`
+    Ok(match flag_value(args, "--target") {
+        Some(triple) => target_dir.join(triple).join("doc"),
+        None => target_dir.join("doc"),
+    })
+}
+
+/// The `Cargo.toml` cargo uses without `--manifest-path`: the nearest one in
+/// the working directory or its parents.
+fn find_manifest() -> Result<PathBuf> {
+    let cwd = std::env::current_dir().context("failed to get the working directory")?;
+    cwd.ancestors()
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|path| path.is_file())
+        .context("could not find Cargo.toml in the working directory or its parents")
+}
+
+/// The value of `--flag value` or `--flag=value`, before any `--`.
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let mut args = args.iter().take_while(|arg| *arg != "--");
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next().map(String::as_str);
+        }
+        if let Some(value) = arg
+            .strip_prefix(flag)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(value);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_value() {
+        let args: Vec<String> = [
+            "--target-dir=out",
+            "--target",
+            "wasm32-unknown-unknown",
+            "--",
+        ]
+        .into_iter()
+        .chain(["--target", "ignored"])
+        .map(String::from)
+        .collect();
+        assert_eq!(flag_value(&args, "--target-dir"), Some("out"));
+        assert_eq!(
+            flag_value(&args, "--target"),
+            Some("wasm32-unknown-unknown")
+        );
+        assert_eq!(flag_value(&args, "--manifest-path"), None);
+    }
+}
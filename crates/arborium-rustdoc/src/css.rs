@@ -4,18 +4,30 @@
 //! theme system. The generated CSS uses `[data-theme="..."]` selectors to match
 //! rustdoc's built-in themes.
 
-use arborium_theme::builtin;
+use arborium_theme::{Theme, builtin};
 use std::fmt::Write;
 
-/// Theme provider function type.
-type ThemeProvider = fn() -> arborium_theme::Theme;
+/// The arborium theme used for each of rustdoc's built-in themes.
+#[derive(Debug, Clone)]
+pub struct RustdocThemes {
+    /// Used for rustdoc's `light` theme, and when no theme is selected.
+    pub light: Theme,
+    /// Used for rustdoc's `dark` theme.
+    pub dark: Theme,
+    /// Used for rustdoc's `ayu` theme.
+    pub ayu: Theme,
+}
 
-/// Rustdoc's built-in themes and their corresponding arborium theme.
-const RUSTDOC_THEMES: &[(&str, ThemeProvider)] = &[
-    ("light", builtin::rustdoc_light),
-    ("dark", builtin::rustdoc_dark),
-    ("ayu", builtin::rustdoc_ayu),
-];
+impl Default for RustdocThemes {
+    /// arborium's ports of rustdoc's own themes.
+    fn default() -> Self {
+        Self {
+            light: builtin::rustdoc_light(),
+            dark: builtin::rustdoc_dark(),
+            ayu: builtin::rustdoc_ayu(),
+        }
+    }
+}
 
 /// Generate CSS for all rustdoc themes.
 ///
@@ -23,6 +35,24 @@ const RUSTDOC_THEMES: &[(&str, ThemeProvider)] = &[
 /// rules are scoped to `[data-theme="..."]` selectors and target code blocks
 /// with `language-*` classes.
 pub fn generate_rustdoc_theme_css() -> String {
+    generate_css(&RustdocThemes::default(), None)
+}
+
+/// Generate CSS for all rustdoc themes, restricted to the given element tags.
+///
+/// Use [`arborium::Highlighter::take_used_tags`] to collect the tags of the
+/// code blocks once they're highlighted, injected languages included, so the
+/// patched stylesheet doesn't carry rules no code block uses.
+pub fn generate_rustdoc_theme_css_for_tags(tags: &[&str]) -> String {
+    generate_css(&RustdocThemes::default(), Some(tags))
+}
+
+/// Like [`generate_rustdoc_theme_css_for_tags`], with other arborium themes.
+pub fn generate_rustdoc_theme_css_with(themes: &RustdocThemes, tags: &[&str]) -> String {
+    generate_css(themes, Some(tags))
+}
+
+fn generate_css(themes: &RustdocThemes, tags: Option<&[&str]>) -> String {
     let mut css = String::new();
 
     // Header comment
@@ -32,13 +62,16 @@ pub fn generate_rustdoc_theme_css() -> String {
     )
     .unwrap();
 
-    for (theme_name, theme_fn) in RUSTDOC_THEMES {
-        let theme = theme_fn();
-
+    let rustdoc_themes = [
+        ("light", &themes.light),
+        ("dark", &themes.dark),
+        ("ayu", &themes.ayu),
+    ];
+    for (theme_name, theme) in rustdoc_themes {
         // Generate CSS for this theme
         // We need to target: pre.language-* code a-*
         // The selector prefix scopes it to the specific rustdoc theme
-        let selector = if *theme_name == "light" {
+        let selector = if theme_name == "light" {
             // Light is the default, so we need both :root (no theme) and explicit light
             ":root:not([data-theme]), :root[data-theme=\"light\"]".to_string()
         } else {
@@ -47,7 +80,7 @@ pub fn generate_rustdoc_theme_css() -> String {
 
         // Use the theme's to_css method but we need to adjust the selector
         // to target our code blocks specifically
-        let theme_css = generate_theme_css_for_rustdoc(&theme, &selector);
+        let theme_css = generate_theme_css_for_rustdoc(theme, &selector, tags);
         css.push_str(&theme_css);
     }
 
@@ -55,7 +88,11 @@ pub fn generate_rustdoc_theme_css() -> String {
 }
 
 /// Generate CSS rules for a single theme, targeting rustdoc's code block structure.
-fn generate_theme_css_for_rustdoc(theme: &arborium_theme::Theme, selector_prefix: &str) -> String {
+fn generate_theme_css_for_rustdoc(
+    theme: &arborium_theme::Theme,
+    selector_prefix: &str,
+    tags: Option<&[&str]>,
+) -> String {
     use arborium_theme::HIGHLIGHTS;
     use std::collections::HashMap;
 
@@ -86,6 +123,9 @@ fn generate_theme_css_for_rustdoc(theme: &arborium_theme::Theme, selector_prefix
         if def.tag.is_empty() {
             continue;
         }
+        if tags.is_some_and(|tags| !tags.contains(&def.tag)) {
+            continue;
+        }
 
         // Get style (own or parent)
         let style = theme.style(i).filter(|s| !s.is_empty()).or_else(|| {
@@ -157,4 +197,26 @@ mod tests {
         assert!(css.contains("a-s"));
         assert!(css.contains("a-c"));
     }
+
+    #[test]
+    fn test_generate_theme_css_for_tags() {
+        let css = generate_rustdoc_theme_css_for_tags(&["k"]);
+
+        assert!(css.contains("data-theme=\"dark\""));
+        assert!(css.contains("a-k {"));
+        assert!(!css.contains("a-s {"));
+        assert!(css.len() < generate_rustdoc_theme_css().len());
+    }
+
+    #[test]
+    fn test_generate_theme_css_with_themes() {
+        let themes = RustdocThemes {
+            dark: builtin::tokyo_night(),
+            ..Default::default()
+        };
+        let css = generate_rustdoc_theme_css_with(&themes, &["k"]);
+
+        assert_ne!(css, generate_rustdoc_theme_css_for_tags(&["k"]));
+        assert!(css.contains("data-theme=\"ayu\""));
+    }
 }
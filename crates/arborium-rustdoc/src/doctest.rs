@@ -0,0 +1,100 @@
+//! Rustdoc conventions for Rust code blocks: hidden lines and block attributes.
+//!
+//! These only matter when re-highlighting Rust, which rustdoc normally does
+//! itself (see [`TransformOptions::highlight_rust`](crate::TransformOptions)).
+
+/// Block attributes from a code block's info string that themes can badge.
+///
+/// `edition2015` through `edition2024` are also recognized, by prefix.
+pub const BLOCK_ATTRIBUTES: &[&str] = &[
+    "ignore",
+    "no_run",
+    "compile_fail",
+    "should_panic",
+    "test_harness",
+];
+
+/// Remove hidden lines from a Rust doc example, the way rustdoc does.
+///
+/// A line is hidden if, after indentation, it is `#` alone or starts with
+/// `# `. A leading `##` escapes this and is shown as a single `#`, so
+/// `## [derive(Debug)]` renders as `# [derive(Debug)]`. Attributes like
+/// `#[derive(Debug)]` and `#![allow(unused)]` are not hidden.
+pub fn strip_hidden_lines(code: &str) -> String {
+    let mut out = String::with_capacity(code.len());
+    for line in code.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if trimmed == "#" || trimmed.starts_with("# ") || trimmed.starts_with("#\t") {
+            continue;
+        }
+        if trimmed.starts_with("##") {
+            out.push_str(&line.replacen("##", "#", 1));
+        } else {
+            out.push_str(line);
+        }
+    }
+    out
+}
+
+/// Split an info string like `rust,no_run` or `ignore edition2021` into its
+/// language (if any) and the [block attributes](BLOCK_ATTRIBUTES) it sets.
+///
+/// As in rustdoc, a block without a language is Rust when it has any of
+/// these attributes, and attributes can be separated by commas or spaces.
+pub fn parse_info_string(info: &str) -> (Option<&str>, Vec<&str>) {
+    let mut lang = None;
+    let mut attributes = Vec::new();
+    for token in info
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+    {
+        if is_block_attribute(token) {
+            attributes.push(token);
+        } else if lang.is_none() {
+            lang = Some(token);
+        }
+    }
+    if lang.is_none() && !attributes.is_empty() {
+        lang = Some("rust");
+    }
+    (lang, attributes)
+}
+
+/// Whether `token` is one of the [`BLOCK_ATTRIBUTES`] or an edition marker.
+pub fn is_block_attribute(token: &str) -> bool {
+    BLOCK_ATTRIBUTES.contains(&token)
+        || token
+            .strip_prefix("edition")
+            .is_some_and(|year| year.len() == 4 && year.bytes().all(|b| b.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_hidden_lines() {
+        let code = "# use std::fmt;\n#\n#[derive(Debug)]\nstruct S;\n    # fn hidden() {}\n## not hidden\n#![allow(unused)]\n";
+        assert_eq!(
+            strip_hidden_lines(code),
+            "#[derive(Debug)]\nstruct S;\n# not hidden\n#![allow(unused)]\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_info_string() {
+        assert_eq!(
+            parse_info_string("rust,no_run"),
+            (Some("rust"), vec!["no_run"])
+        );
+        assert_eq!(
+            parse_info_string("ignore edition2021"),
+            (Some("rust"), vec!["ignore", "edition2021"])
+        );
+        assert_eq!(parse_info_string("toml"), (Some("toml"), vec![]));
+        assert_eq!(
+            parse_info_string("edition20xx"),
+            (Some("edition20xx"), vec![])
+        );
+    }
+}
@@ -1,10 +1,22 @@
 //! HTML transformation using lol_html.
 //!
 //! Transforms rustdoc HTML to add syntax highlighting for non-Rust code blocks.
+//!
+//! The language of a block comes from its `language-*` class. When the
+//! markdown that generated the docs can't be fixed, it can be overridden (or
+//! supplied for an untagged block) with a `data-arborium-lang="toml"` attribute
+//! on the `<pre>`, or an `<!-- arborium: lang=toml -->` comment anywhere before
+//! the block.
+//!
+//! Blocks rustdoc already highlighted as Rust are left alone unless
+//! [`TransformOptions::highlight_rust`] is set.
 
-use arborium::{Error as ArboriumError, Highlighter};
+use crate::doctest::{is_block_attribute, parse_info_string, strip_hidden_lines};
+use arborium::advanced::is_plain_text;
+use arborium::config_file::LanguagesConfig;
+use arborium::{Error as ArboriumError, Highlighter, guess_language};
 use lol_html::html_content::ContentType;
-use lol_html::{ElementContentHandlers, HtmlRewriter, Selector, Settings};
+use lol_html::{DocumentContentHandlers, ElementContentHandlers, HtmlRewriter, Selector, Settings};
 use std::borrow::Cow;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -18,13 +30,56 @@ pub struct TransformResult {
     pub blocks_skipped: usize,
     /// Languages that were encountered but not supported.
     pub unsupported_languages: Vec<String>,
+    /// Languages of the code blocks that were highlighted.
+    pub highlighted_languages: Vec<String>,
+    /// Element tags of the highlighted code, injected languages included,
+    /// from [`Highlighter::take_used_tags`].
+    pub used_tags: Vec<&'static str>,
+}
+
+/// Options controlling which code blocks get highlighted.
+#[derive(Debug, Default, Clone)]
+pub struct TransformOptions {
+    /// Leave diagram sources (mermaid, plantuml) untouched so a client-side
+    /// renderer such as mermaid.js can still find their original text.
+    pub skip_diagrams: bool,
+    /// Guess the language of blocks without one, or tagged `text`, from their
+    /// contents. Untagged blocks that can't be guessed are left alone; `text`
+    /// blocks stay plain text.
+    pub guess_languages: bool,
+    /// Re-highlight Rust examples with arborium instead of keeping rustdoc's
+    /// highlighting, so they match the other blocks.
+    ///
+    /// Hidden lines (`# `) are stripped from Rust blocks rustdoc didn't render
+    /// itself, and block attributes such as `ignore`, `no_run`, `compile_fail`
+    /// and `edition2021` are added as classes on the `<pre>` for themes to badge.
+    pub highlight_rust: bool,
+    /// Language aliases, and which languages to highlight at all, from
+    /// `arborium.toml`. Blocks in excluded languages are left alone.
+    pub languages: LanguagesConfig,
 }
 
+/// Languages whose code blocks are usually rendered as diagrams in the browser.
+const DIAGRAM_LANGUAGES: &[&str] = &["mermaid", "mmd", "plantuml", "puml"];
+
+/// Guesses below this confidence are not trusted.
+const MIN_GUESS_CONFIDENCE: f32 = 0.5;
+
 /// State shared between lol_html handlers.
 #[derive(Default)]
 struct TransformState {
     /// The language of the current code block (if any).
+    ///
+    /// Empty for an untagged block whose language is to be guessed.
     current_lang: Option<String>,
+    /// Whether to guess the language of the current code block from its contents.
+    guess_lang: bool,
+    /// Language from an `<!-- arborium: lang=... -->` comment, for the next block.
+    pending_lang: Option<String>,
+    /// Block attributes from rustdoc's `<div class="example-wrap">`, for the next block.
+    pending_attributes: Vec<String>,
+    /// Whether to strip rustdoc hidden lines from the current code block.
+    strip_hidden: bool,
     /// Accumulated text content from the current code block.
     collected_text: String,
     /// Whether we successfully registered an end tag handler for the current block.
@@ -34,6 +89,10 @@ struct TransformState {
     result: TransformResult,
     /// The highlighter (wrapped for sharing).
     highlighter: Option<Highlighter>,
+    /// HTML output buffer, reused across code blocks.
+    html_buffer: String,
+    /// Language aliases and include/exclude lists.
+    languages: LanguagesConfig,
 }
 
 /// Transform rustdoc HTML, adding syntax highlighting to non-Rust code blocks.
@@ -43,6 +102,15 @@ struct TransformState {
 pub fn transform_html(
     html: &str,
     highlighter: &mut Highlighter,
+) -> Result<(String, TransformResult), TransformError> {
+    transform_html_with_options(html, highlighter, &TransformOptions::default())
+}
+
+/// Like [`transform_html`], with control over which blocks are highlighted.
+pub fn transform_html_with_options(
+    html: &str,
+    highlighter: &mut Highlighter,
+    options: &TransformOptions,
 ) -> Result<(String, TransformResult), TransformError> {
     // Fork the highlighter - shares the grammar store but has its own parse context
     // This is needed because lol_html requires 'static closures
@@ -51,19 +119,45 @@ pub fn transform_html(
     // Shared state wrapped in Rc<RefCell<>> for the closure dance
     let state = Rc::new(RefCell::new(TransformState {
         highlighter: Some(forked),
+        languages: options.languages.clone(),
         ..Default::default()
     }));
 
     let mut output = Vec::new();
 
+    let skip_diagrams = options.skip_diagrams;
+    let guess_languages = options.guess_languages;
+    let highlight_rust = options.highlight_rust;
+    let state_for_example_wrap = state.clone();
     let state_for_pre = state.clone();
+    let state_for_untagged_pre = state.clone();
     let state_for_code_el = state.clone();
     let state_for_code_text = state.clone();
+    let state_for_comments = state.clone();
 
     {
         let mut rewriter = HtmlRewriter::new(
             Settings {
                 element_content_handlers: vec![
+                    // Handler for rustdoc's example wrapper - remember its block attributes
+                    (
+                        Cow::<Selector>::Owned("div.example-wrap".parse().unwrap()),
+                        ElementContentHandlers::default().element(
+                            move |el: &mut lol_html::html_content::Element| {
+                                let class = el.get_attribute("class").unwrap_or_default();
+                                let mut attributes: Vec<String> = class
+                                    .split_whitespace()
+                                    .filter(|c| is_block_attribute(c))
+                                    .map(str::to_string)
+                                    .collect();
+                                if let Some(edition) = el.get_attribute("data-edition") {
+                                    attributes.push(format!("edition{edition}"));
+                                }
+                                state_for_example_wrap.borrow_mut().pending_attributes = attributes;
+                                Ok(())
+                            },
+                        ),
+                    ),
                     // Handler for <pre class="language-*"> - extract language
                     (
                         Cow::<Selector>::Owned("pre[class*='language-']".parse().unwrap()),
@@ -72,6 +166,8 @@ pub fn transform_html(
                                 let mut state = state_for_pre.borrow_mut();
 
                                 let class = el.get_attribute("class").unwrap_or_default();
+                                let lang_override = take_language_override(el, &mut state);
+                                state.strip_hidden = false;
 
                                 // Skip if it has "rust" class (already highlighted by rustdoc)
                                 // Use word boundary check to avoid false positives like "language-rustscript"
@@ -81,16 +177,98 @@ pub fn transform_html(
                                     return Ok(());
                                 }
 
-                                // Extract language from class
-                                state.current_lang = extract_language_from_class(&class);
+                                // Rust from a markdown renderer (`language-rust,no_run`)
+                                // still has its hidden lines and attributes
+                                if highlight_rust && lang_override.is_none() {
+                                    let info = class
+                                        .split_whitespace()
+                                        .find_map(|c| c.strip_prefix("language-"))
+                                        .unwrap_or_default();
+                                    if let (Some("rust"), attributes) = parse_info_string(info) {
+                                        add_classes(el, &attributes)?;
+                                        state.guess_lang = false;
+                                        state.strip_hidden = true;
+                                        state.current_lang = Some("rust".to_string());
+                                        return Ok(());
+                                    }
+                                }
+
+                                // Extract language from the override or the class
+                                let lang =
+                                    lang_override.or_else(|| extract_language_from_class(&class));
+
+                                // Skip diagrams that a client-side renderer will replace
+                                if skip_diagrams
+                                    && lang
+                                        .as_deref()
+                                        .is_some_and(|l| DIAGRAM_LANGUAGES.contains(&l))
+                                {
+                                    state.result.blocks_skipped += 1;
+                                    state.current_lang = None;
+                                    return Ok(());
+                                }
+
+                                state.guess_lang =
+                                    guess_languages && lang.as_deref().is_some_and(is_plain_text);
+                                state.current_lang = lang;
+
+                                Ok(())
+                            },
+                        ),
+                    ),
+                    // Handler for other <pre> - guess the language of untagged blocks if enabled
+                    (
+                        Cow::<Selector>::Owned("pre:not([class*='language-'])".parse().unwrap()),
+                        ElementContentHandlers::default().element(
+                            move |el: &mut lol_html::html_content::Element| {
+                                let mut state = state_for_untagged_pre.borrow_mut();
+
+                                let class = el.get_attribute("class").unwrap_or_default();
+                                let lang_override = take_language_override(el, &mut state);
+                                let attributes = std::mem::take(&mut state.pending_attributes);
+                                state.strip_hidden = false;
+
+                                // Rust blocks and rustdoc's own <pre> (signatures, sources)
+                                // have classes; only bare blocks are untagged code.
+                                // Examples can be re-highlighted; rustdoc already removed
+                                // their hidden lines.
+                                if class.split_whitespace().any(|c| c == "rust") {
+                                    let example = highlight_rust
+                                        && class
+                                            .split_whitespace()
+                                            .any(|c| c == "rust-example-rendered");
+                                    if example {
+                                        add_classes(el, &attributes)?;
+                                    }
+                                    state.guess_lang = false;
+                                    state.current_lang = example.then(|| "rust".to_string());
+                                } else if let Some(lang) = lang_override {
+                                    state.guess_lang = false;
+                                    state.current_lang = Some(lang);
+                                } else {
+                                    state.guess_lang = guess_languages && class.trim().is_empty();
+                                    state.current_lang = state.guess_lang.then(String::new);
+                                }
 
                                 Ok(())
                             },
                         ),
                     ),
-                    // Handler for <code> inside language pre - collect text and replace
+                    // Handler for rustdoc's highlighting markup - unwrap it when re-highlighting
                     (
-                        Cow::<Selector>::Owned("pre[class*='language-'] code".parse().unwrap()),
+                        Cow::<Selector>::Owned("pre.rust-example-rendered code *".parse().unwrap()),
+                        ElementContentHandlers::default().element(
+                            move |el: &mut lol_html::html_content::Element| {
+                                if highlight_rust {
+                                    el.remove_and_keep_content();
+                                }
+                                Ok(())
+                            },
+                        ),
+                    ),
+                    // Handler for <code> inside pre - collect text and replace
+                    (
+                        Cow::<Selector>::Owned("pre code".parse().unwrap()),
                         ElementContentHandlers::default()
                             .element({
                                 let state_ref = state_for_code_el.clone();
@@ -114,22 +292,75 @@ pub fn transform_html(
                                         handlers.push(Box::new(move |end| {
                                             let mut state = state_for_end.borrow_mut();
 
-                                            let lang = match &state.current_lang {
+                                            let mut lang = match &state.current_lang {
                                                 Some(l) => l.clone(),
                                                 None => return Ok(()),
                                             };
 
                                             // Decode HTML entities
-                                            let decoded =
+                                            let mut decoded =
                                                 decode_html_entities(&state.collected_text);
+                                            if state.strip_hidden {
+                                                decoded = strip_hidden_lines(&decoded);
+                                            }
+
+                                            if state.guess_lang
+                                                && let Some((guess, _)) = guess_language(&decoded)
+                                                    .into_iter()
+                                                    .next()
+                                                    .filter(|(_, confidence)| {
+                                                        *confidence >= MIN_GUESS_CONFIDENCE
+                                                    })
+                                            {
+                                                lang = guess.to_string();
+                                            }
+
+                                            // Apply aliases; excluded languages are skipped
+                                            lang = state
+                                                .languages
+                                                .resolve(&lang)
+                                                .map(str::to_string)
+                                                .unwrap_or_default();
+
+                                            // Untagged, not guessed or excluded - keep original
+                                            if lang.is_empty() {
+                                                end.before(
+                                                    &state.collected_text,
+                                                    ContentType::Html,
+                                                );
+                                                state.result.blocks_skipped += 1;
+                                                state.current_lang = None;
+                                                state.collected_text.clear();
+                                                state.can_process = false;
+                                                return Ok(());
+                                            }
 
                                             // Highlight the code
+                                            let state = &mut *state;
                                             let highlighter = state.highlighter.as_mut().unwrap();
-                                            match highlighter.highlight(&lang, &decoded) {
-                                                Ok(highlighted) => {
+                                            state.html_buffer.clear();
+                                            match highlighter.highlight_to_fmt(
+                                                &mut state.html_buffer,
+                                                &lang,
+                                                &decoded,
+                                            ) {
+                                                Ok(()) => {
                                                     // Insert highlighted content before </code>
-                                                    end.before(&highlighted, ContentType::Html);
+                                                    end.before(
+                                                        &state.html_buffer,
+                                                        ContentType::Html,
+                                                    );
                                                     state.result.blocks_highlighted += 1;
+                                                    if !state
+                                                        .result
+                                                        .highlighted_languages
+                                                        .contains(&lang)
+                                                    {
+                                                        state
+                                                            .result
+                                                            .highlighted_languages
+                                                            .push(lang.clone());
+                                                    }
                                                 }
                                                 Err(ArboriumError::UnsupportedLanguage {
                                                     ..
@@ -164,6 +395,7 @@ pub fn transform_html(
 
                                             // Reset for next block
                                             state.current_lang = None;
+                                            state.strip_hidden = false;
                                             state.collected_text.clear();
                                             state.can_process = false;
 
@@ -193,6 +425,15 @@ pub fn transform_html(
                             }),
                     ),
                 ],
+                // Remember `<!-- arborium: lang=... -->` for the next block
+                document_content_handlers: vec![DocumentContentHandlers::default().comments(
+                    move |comment: &mut lol_html::html_content::Comment| {
+                        if let Some(lang) = parse_language_comment(&comment.text()) {
+                            state_for_comments.borrow_mut().pending_lang = Some(lang);
+                        }
+                        Ok(())
+                    },
+                )],
                 ..Settings::new()
             },
             |c: &[u8]| output.extend_from_slice(c),
@@ -205,7 +446,11 @@ pub fn transform_html(
     }
 
     // Extract final result
-    let result = state.borrow().result.clone();
+    let mut state = state.borrow_mut();
+    let mut result = state.result.clone();
+    if let Some(highlighter) = &mut state.highlighter {
+        result.used_tags = highlighter.take_used_tags();
+    }
     let output_str = String::from_utf8(output)
         .map_err(|e| TransformError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
 
@@ -226,6 +471,51 @@ fn extract_language_from_class(class: &str) -> Option<String> {
     None
 }
 
+/// Take the language override for a `<pre>`: its `data-arborium-lang`
+/// attribute, or else a pending `<!-- arborium: lang=... -->` comment.
+///
+/// The pending comment is consumed either way, so it never leaks past the
+/// block it precedes.
+fn take_language_override(
+    el: &lol_html::html_content::Element,
+    state: &mut TransformState,
+) -> Option<String> {
+    let pending = state.pending_lang.take();
+    el.get_attribute("data-arborium-lang")
+        .map(|lang| lang.trim().to_lowercase())
+        .filter(|lang| !lang.is_empty())
+        .or(pending)
+}
+
+/// Append `classes` to an element's `class` attribute.
+fn add_classes(
+    el: &mut lol_html::html_content::Element,
+    classes: &[impl AsRef<str>],
+) -> Result<(), lol_html::errors::AttributeNameError> {
+    if classes.is_empty() {
+        return Ok(());
+    }
+    let mut class = el.get_attribute("class").unwrap_or_default();
+    for c in classes {
+        if !class.is_empty() {
+            class.push(' ');
+        }
+        class.push_str(c.as_ref());
+    }
+    el.set_attribute("class", &class)
+}
+
+/// Parse an `arborium: lang=toml` comment into its (lowercased) language.
+fn parse_language_comment(text: &str) -> Option<String> {
+    let lang = text
+        .trim()
+        .strip_prefix("arborium:")?
+        .trim_start()
+        .strip_prefix("lang=")?
+        .trim();
+    (!lang.is_empty() && !lang.contains(char::is_whitespace)).then(|| lang.to_lowercase())
+}
+
 fn decode_html_entities(s: &str) -> String {
     // Note: &amp; must be decoded LAST to avoid double-decoding
     // e.g., "&lt;" should become "<", not "&<"
@@ -238,25 +528,16 @@ fn decode_html_entities(s: &str) -> String {
 }
 
 /// Errors that can occur during HTML transformation.
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum TransformError {
     /// Error from lol_html rewriter.
-    Rewrite(lol_html::errors::RewritingError),
+    #[error("HTML rewrite error: {0}")]
+    Rewrite(#[source] lol_html::errors::RewritingError),
     /// IO error.
-    Io(std::io::Error),
-}
-
-impl std::fmt::Display for TransformError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            TransformError::Rewrite(e) => write!(f, "HTML rewrite error: {}", e),
-            TransformError::Io(e) => write!(f, "IO error: {}", e),
-        }
-    }
+    #[error("IO error: {0}")]
+    Io(#[source] std::io::Error),
 }
 
-impl std::error::Error for TransformError {}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -281,6 +562,20 @@ mod tests {
         assert_eq!(extract_language_from_class("foo bar"), None);
     }
 
+    #[test]
+    fn test_parse_language_comment() {
+        assert_eq!(
+            parse_language_comment(" arborium: lang=TOML "),
+            Some("toml".to_string())
+        );
+        assert_eq!(
+            parse_language_comment("arborium:lang=json"),
+            Some("json".to_string())
+        );
+        assert_eq!(parse_language_comment(" arborium: lang= "), None);
+        assert_eq!(parse_language_comment(" TODO: lang=toml "), None);
+    }
+
     #[test]
     fn test_decode_html_entities() {
         assert_eq!(decode_html_entities("&lt;div&gt;"), "<div>");
@@ -297,10 +592,24 @@ name = "test"</code></pre>"#;
 
         assert_eq!(result.blocks_highlighted, 1);
         assert_eq!(result.blocks_skipped, 0);
+        assert_eq!(result.highlighted_languages, vec!["toml".to_string()]);
         // Output should contain arborium's custom elements
         assert!(output.contains("<a-"));
     }
 
+    #[test]
+    fn test_transform_html_used_tags_include_injections() {
+        let html = r#"<pre class="language-markdown"><code>```rust
+println!("hi");
+```</code></pre>"#;
+
+        let mut highlighter = Highlighter::new();
+        let (_, result) = transform_html(html, &mut highlighter).unwrap();
+
+        assert_eq!(result.highlighted_languages, vec!["markdown".to_string()]);
+        assert!(result.used_tags.contains(&"m"), "{:?}", result.used_tags);
+    }
+
     #[test]
     fn test_transform_html_skips_rust() {
         let html = r#"<pre class="language-rust rust"><code>fn main() {}</code></pre>"#;
@@ -333,6 +642,22 @@ name = "test"</code></pre>"#;
         assert!(output.contains("some code"));
     }
 
+    #[test]
+    fn test_transform_html_generic_fallback() {
+        let html = r#"<pre class="language-nosuchlang"><code>x = 42 # answer</code></pre>"#;
+
+        let mut highlighter = Highlighter::with_config(arborium::Config {
+            fallback: arborium::Fallback::Generic,
+            ..Default::default()
+        });
+        let (output, result) = transform_html(html, &mut highlighter).unwrap();
+
+        assert_eq!(result.blocks_highlighted, 1);
+        assert!(result.unsupported_languages.is_empty());
+        assert!(output.contains("<a-n>42</a-n>"));
+        assert!(output.contains("<a-c># answer</a-c>"));
+    }
+
     #[test]
     fn test_transform_html_decodes_entities() {
         // TOML with HTML entities that need decoding
@@ -348,6 +673,125 @@ foo = &quot;bar&quot;</code></pre>"#;
         assert!(output.contains("<a-"));
     }
 
+    #[test]
+    fn test_transform_html_skips_diagrams() {
+        let html = r#"<pre class="language-mermaid"><code>graph LR
+    a --&gt; b</code></pre>"#;
+
+        let mut highlighter = Highlighter::new();
+        let options = TransformOptions {
+            skip_diagrams: true,
+            ..Default::default()
+        };
+        let (output, result) =
+            transform_html_with_options(html, &mut highlighter, &options).unwrap();
+
+        assert_eq!(result.blocks_highlighted, 0);
+        assert_eq!(result.blocks_skipped, 1);
+        // The renderer needs the original text, entities and all
+        assert_eq!(output, html);
+    }
+
+    #[test]
+    fn test_transform_html_language_config() {
+        let html = r#"<pre class="language-cfg"><code>a = 1</code></pre><pre class="language-json"><code>{}</code></pre>"#;
+
+        let mut highlighter = Highlighter::new();
+        let options = TransformOptions {
+            languages: LanguagesConfig {
+                exclude: vec!["json".to_string()],
+                aliases: [("cfg".to_string(), "toml".to_string())].into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (output, result) =
+            transform_html_with_options(html, &mut highlighter, &options).unwrap();
+
+        assert_eq!(result.blocks_highlighted, 1);
+        assert_eq!(result.blocks_skipped, 1);
+        assert_eq!(result.highlighted_languages, vec!["toml".to_string()]);
+        assert!(output.contains(r#"<pre class="language-json"><code>{}</code></pre>"#));
+    }
+
+    #[test]
+    fn test_transform_html_guesses_untagged_blocks() {
+        let html = r#"<pre><code>[package]
+name = &quot;test&quot;
+
+[dependencies]
+serde = &quot;1&quot;</code></pre><pre><code>hello world</code></pre>"#;
+
+        let mut highlighter = Highlighter::new();
+        let (output, result) = transform_html(html, &mut highlighter).unwrap();
+        assert_eq!(result.blocks_highlighted, 0);
+        assert_eq!(output, html);
+
+        let options = TransformOptions {
+            guess_languages: true,
+            ..Default::default()
+        };
+        let (output, result) =
+            transform_html_with_options(html, &mut highlighter, &options).unwrap();
+
+        assert_eq!(result.blocks_highlighted, 1);
+        assert_eq!(result.blocks_skipped, 1);
+        assert_eq!(result.highlighted_languages, vec!["toml".to_string()]);
+        assert!(output.contains("<pre><code>hello world</code></pre>"));
+    }
+
+    #[test]
+    fn test_transform_html_language_overrides() {
+        let html = r#"<pre class="language-text" data-arborium-lang="toml"><code>a = 1</code></pre>
+<!-- arborium: lang=json -->
+<pre><code>{"a": 1}</code></pre>
+<pre><code>plain</code></pre>
+<!-- arborium: lang=toml -->
+<pre class="rust"><code>fn main() {}</code></pre>"#;
+
+        let mut highlighter = Highlighter::new();
+        let (output, result) = transform_html(html, &mut highlighter).unwrap();
+
+        assert_eq!(result.blocks_highlighted, 2);
+        assert_eq!(
+            result.highlighted_languages,
+            vec!["toml".to_string(), "json".to_string()]
+        );
+        // The comment only applies to the block right after it
+        assert!(output.contains("<pre><code>plain</code></pre>"));
+        // Rust blocks are left to rustdoc
+        assert!(output.contains(r#"<pre class="rust"><code>fn main() {}</code></pre>"#));
+    }
+
+    #[test]
+    fn test_transform_html_rehighlights_rust() {
+        let html = r#"<div class="example-wrap ignore" data-edition="2021"><pre class="rust rust-example-rendered"><code><span class="kw">fn </span>main() {}</code></pre></div>
+<pre class="language-rust,no_run"><code># use std::io;
+fn run() {}</code></pre>
+<pre class="rust item-decl"><code>pub fn run()</code></pre>"#;
+
+        let mut highlighter = Highlighter::new();
+        let (output, result) = transform_html(html, &mut highlighter).unwrap();
+        assert_eq!(result.blocks_highlighted, 0);
+        assert_eq!(output, html);
+
+        let options = TransformOptions {
+            highlight_rust: true,
+            ..Default::default()
+        };
+        let (output, result) =
+            transform_html_with_options(html, &mut highlighter, &options).unwrap();
+
+        assert_eq!(result.blocks_highlighted, 2);
+        assert_eq!(result.highlighted_languages, vec!["rust".to_string()]);
+        assert!(!output.contains(r#"<span class="kw">"#));
+        assert!(output.contains(r#"class="rust rust-example-rendered ignore edition2021""#));
+        assert!(output.contains(r#"class="language-rust,no_run no_run""#));
+        assert!(!output.contains("std::io"));
+        // Signatures are not examples
+        assert!(output.contains(r#"<pre class="rust item-decl"><code>pub fn run()</code></pre>"#));
+    }
+
     #[test]
     fn test_transform_html_preserves_non_code_content() {
         let html = r#"<html><body><h1>Title</h1><pre class="language-json"><code>{"key": "value"}</code></pre><p>Footer</p></body></html>"#;
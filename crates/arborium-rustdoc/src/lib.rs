@@ -8,26 +8,42 @@
 //!
 //! ```bash
 //! arborium-rustdoc ./target/doc ./target/doc-highlighted
+//!
+//! # Or: run `cargo doc` and highlight its output in place
+//! cargo arborium-doc --no-deps
 //! ```
 //!
 //! # How it works
 //!
-//! 1. **CSS Generation**: Generates theme CSS rules for arborium's custom elements
-//!    and appends them to rustdoc's CSS file (`static.files/rustdoc-*.css`)
-//!
-//! 2. **HTML Transformation**: Uses lol_html to stream through each HTML file,
+//! 1. **HTML Transformation**: Uses lol_html to stream through each HTML file,
 //!    finding `<pre class="language-*">` elements and replacing their content
 //!    with syntax-highlighted HTML.
 //!
+//! 2. **CSS Generation**: Generates theme CSS rules for arborium's custom elements
+//!    and appends them to rustdoc's CSS file (`static.files/rustdoc-*.css`).
+//!    Only the rules needed by the languages found in step 1 are emitted.
+//!
+//! Rust examples keep rustdoc's own highlighting by default. With
+//! `--highlight-rust` they are re-highlighted too, and their block attributes
+//! (`ignore`, `no_run`, `compile_fail`, `edition2021`, ...) become classes on
+//! the `<pre>` so themes can badge them.
+//!
 //! # Theme Support
 //!
 //! Integrates with rustdoc's built-in theme system (light, dark, ayu) by generating
 //! CSS rules scoped to `[data-theme="..."]` selectors.
 
 mod css;
+mod doctest;
 mod html;
+mod metadata;
 mod processor;
 
-pub use css::generate_rustdoc_theme_css;
-pub use html::transform_html;
+pub use css::{
+    RustdocThemes, generate_rustdoc_theme_css, generate_rustdoc_theme_css_for_tags,
+    generate_rustdoc_theme_css_with,
+};
+pub use doctest::{BLOCK_ATTRIBUTES, is_block_attribute, parse_info_string, strip_hidden_lines};
+pub use html::{TransformOptions, transform_html, transform_html_with_options};
+pub use metadata::{PackageMetadata, RustdocMetadata, ThemeNames};
 pub use processor::{ProcessError, ProcessOptions, Processor, ProcessorStats};
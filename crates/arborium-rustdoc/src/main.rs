@@ -1,11 +1,13 @@
 //! arborium-rustdoc CLI - Post-process rustdoc output with syntax highlighting.
 
 use anyhow::{Result, bail};
+use arborium::Fallback;
+use arborium::config_file::FileConfig;
 use arborium_rustdoc::{ProcessOptions, Processor};
 use facet::Facet;
 use facet_args as args;
 use owo_colors::OwoColorize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 /// Post-process rustdoc HTML output to add syntax highlighting for non-Rust code blocks.
@@ -25,6 +27,27 @@ struct Args {
     /// Show verbose output
     #[facet(args::named, args::short = 'v', default)]
     verbose: bool,
+
+    /// Leave mermaid and plantuml blocks untouched for a client-side diagram renderer
+    #[facet(args::named, default)]
+    skip_diagrams: bool,
+
+    /// Guess the language of code blocks without one (or tagged `text`) from their contents
+    #[facet(args::named, default)]
+    guess_languages: bool,
+
+    /// Re-highlight Rust examples too, badging `ignore`, `no_run`, `compile_fail` and editions
+    #[facet(args::named, default)]
+    highlight_rust: bool,
+
+    /// Highlight TODO, FIXME, SAFETY and similar tags in comments
+    #[facet(args::named, default)]
+    comment_tags: bool,
+
+    /// How to render blocks in unsupported languages: `skip` (default), `plain`, or
+    /// `generic` (numbers, strings and comments only)
+    #[facet(args::named, default)]
+    fallback: Option<String>,
 }
 
 /// Format a size difference as a human-readable string with appropriate unit.
@@ -59,11 +82,31 @@ fn main() -> Result<()> {
         bail!("Input path is not a directory: {}", args.input.display());
     }
 
+    // Settings from arborium.toml in the working directory; flags win. The
+    // theme and output mode don't apply: rustdoc's own themes are patched.
+    let file_config = FileConfig::discover(Path::new("."))?.unwrap_or_default();
+
+    let fallback = match args.fallback.as_deref() {
+        None => file_config.fallback.unwrap_or_default(),
+        Some("skip") => Fallback::Error,
+        Some("plain") => Fallback::Plain,
+        Some("generic") => Fallback::Generic,
+        Some(other) => bail!("Unknown fallback mode: {other} (expected skip, plain or generic)"),
+    };
+
     // Create processor
     let options = ProcessOptions {
         input_dir: args.input.clone(),
         output_dir: args.output.clone(),
         verbose: args.verbose,
+        skip_diagrams: args.skip_diagrams,
+        guess_languages: args.guess_languages,
+        highlight_rust: args.highlight_rust,
+        comment_tags: args.comment_tags || file_config.comment_tags.unwrap_or(false),
+        fallback,
+        languages: file_config.languages,
+        max_injection_depth: file_config.injections.max_depth,
+        themes: Default::default(),
     };
 
     let mut processor = Processor::new(options);
@@ -103,6 +146,14 @@ fn main() -> Result<()> {
         stats.blocks_skipped.to_string().yellow()
     );
 
+    if stats.injection_cache_hits + stats.injection_cache_misses > 0 {
+        eprintln!(
+            "  {} injected snippets reused ({:.1}% of injections)",
+            stats.injection_cache_hits.to_string().cyan(),
+            stats.injection_cache_hit_rate() * 100.0
+        );
+    }
+
     if let Some(ref css_path) = stats.css_file_modified {
         eprintln!("  {} CSS patched: {}", "✓".green(), css_path.display());
     }
@@ -141,6 +192,18 @@ fn main() -> Result<()> {
         );
     }
 
+    if stats.unknown_injections > 0 {
+        eprintln!(
+            "\n  {} {} injected snippets in languages without a grammar",
+            "Note:".yellow(),
+            stats.unknown_injections
+        );
+    }
+
+    for error in &stats.init_errors {
+        eprintln!("\n  {} {error}", "Warning:".yellow());
+    }
+
     eprintln!(
         "\n  Completed in {:.2}s (processing: {:.2}s @ {:.1} MB/s)",
         elapsed.as_secs_f64(),
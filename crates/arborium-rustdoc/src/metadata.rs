@@ -0,0 +1,222 @@
+//! Per-crate settings from `[package.metadata.arborium]`.
+//!
+//! `cargo arborium-doc` reads this table from the manifest of the package it
+//! documents (the one selected with `-p`/`--package` or `--manifest-path`, or
+//! found from the working directory), or from `[workspace.metadata.arborium]`
+//! for a virtual workspace:
+//!
+//! ```toml
+//! [package.metadata.arborium]
+//! fallback = "generic"
+//! comment-tags = true
+//! languages = { exclude = ["text"], aliases = { cfg = "toml" } }
+//!
+//! [package.metadata.arborium.rustdoc]
+//! guess-languages = true
+//! highlight-rust = true
+//! themes = { light = "github-light", dark = "github-dark", ayu = "tokyo-night" }
+//! ```
+//!
+//! Everything outside the `rustdoc` table has the same keys as an
+//! `arborium.toml` (see [`arborium::config_file`]) and, when present, is used
+//! instead of the `arborium.toml` in the working directory. The `theme` and
+//! `output` keys don't apply to rustdoc output.
+
+use crate::css::RustdocThemes;
+use crate::processor::ProcessOptions;
+use arborium::Error;
+use arborium::config_file::{FileConfig, find_theme};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// The `[package.metadata.arborium]` table.
+#[derive(Debug, Clone, Default)]
+pub struct PackageMetadata {
+    /// The `arborium.toml` settings.
+    pub config: FileConfig,
+    /// The `rustdoc` table.
+    pub rustdoc: RustdocMetadata,
+}
+
+/// The `[package.metadata.arborium.rustdoc]` table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RustdocMetadata {
+    /// Leave mermaid and plantuml blocks alone for a client-side renderer.
+    pub skip_diagrams: bool,
+    /// Guess the language of untagged and `text` blocks from their contents.
+    pub guess_languages: bool,
+    /// Re-highlight Rust examples instead of keeping rustdoc's highlighting.
+    pub highlight_rust: bool,
+    /// arborium themes to use instead of the ports of rustdoc's own.
+    pub themes: ThemeNames,
+}
+
+/// Theme names (or `.toml` theme paths) for rustdoc's built-in themes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeNames {
+    /// For rustdoc's `light` theme.
+    pub light: Option<String>,
+    /// For rustdoc's `dark` theme.
+    pub dark: Option<String>,
+    /// For rustdoc's `ayu` theme.
+    pub ayu: Option<String>,
+}
+
+impl PackageMetadata {
+    /// Parse the `arborium` table of a package's metadata, as JSON from
+    /// `cargo metadata`. `manifest_path` is only used in error messages.
+    pub fn from_value(value: &serde_json::Value, manifest_path: &Path) -> Result<Self, Error> {
+        let invalid = |e: serde_json::Error| Error::InvalidConfig {
+            path: manifest_path.to_path_buf(),
+            message: format!("[package.metadata.arborium]: {e}"),
+        };
+        let mut table = value.clone();
+        let rustdoc = table
+            .as_object_mut()
+            .and_then(|table| table.remove("rustdoc"));
+        Ok(Self {
+            config: serde_json::from_value(table).map_err(invalid)?,
+            rustdoc: rustdoc
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(invalid)?
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Find the table in the output of `cargo metadata --format-version 1`.
+    ///
+    /// Looks at the package named `package` if given, else the one whose
+    /// manifest is `manifest_path`, else the workspace metadata. `None` if
+    /// there's no `arborium` table there.
+    pub fn from_cargo_metadata(
+        metadata: &serde_json::Value,
+        package: Option<&str>,
+        manifest_path: &Path,
+    ) -> Result<Option<Self>, Error> {
+        let packages = metadata["packages"]
+            .as_array()
+            .map_or(&[][..], Vec::as_slice);
+        // `-p name@1.0.0` names a package and a version
+        let package = match package.map(|spec| spec.split('@').next().unwrap_or(spec)) {
+            Some(name) => packages.iter().find(|p| p["name"] == name),
+            None => packages.iter().find(|p| {
+                p["manifest_path"]
+                    .as_str()
+                    .is_some_and(|path| Path::new(path) == manifest_path)
+            }),
+        };
+
+        let (table, manifest_path) = match package {
+            Some(package) => (
+                &package["metadata"]["arborium"],
+                package["manifest_path"].as_str().map(PathBuf::from),
+            ),
+            None => (
+                &metadata["workspace_metadata"]["arborium"],
+                metadata["workspace_root"]
+                    .as_str()
+                    .map(|root| Path::new(root).join("Cargo.toml")),
+            ),
+        };
+        if table.is_null() {
+            return Ok(None);
+        }
+        let manifest_path = manifest_path.unwrap_or_else(|| PathBuf::from("Cargo.toml"));
+        Self::from_value(table, &manifest_path).map(Some)
+    }
+
+    /// Processor options for highlighting the docs in `input_dir` in place.
+    pub fn process_options(&self, input_dir: PathBuf) -> Result<ProcessOptions, Error> {
+        let defaults = RustdocThemes::default();
+        let theme = |name: &Option<String>, default: arborium_theme::Theme| {
+            name.as_deref().map_or(Ok(default), find_theme)
+        };
+        let themes = &self.rustdoc.themes;
+
+        Ok(ProcessOptions {
+            input_dir,
+            output_dir: None,
+            verbose: false,
+            skip_diagrams: self.rustdoc.skip_diagrams,
+            guess_languages: self.rustdoc.guess_languages,
+            highlight_rust: self.rustdoc.highlight_rust,
+            comment_tags: self.config.comment_tags.unwrap_or(false),
+            fallback: self.config.fallback.unwrap_or_default(),
+            languages: self.config.languages.clone(),
+            max_injection_depth: self.config.injections.max_depth,
+            themes: RustdocThemes {
+                light: theme(&themes.light, defaults.light)?,
+                dark: theme(&themes.dark, defaults.dark)?,
+                ayu: theme(&themes.ayu, defaults.ayu)?,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arborium::Fallback;
+
+    const METADATA: &str = r#"{
+        "packages": [
+            {"name": "plain", "manifest_path": "/ws/plain/Cargo.toml", "metadata": null},
+            {
+                "name": "fancy",
+                "manifest_path": "/ws/fancy/Cargo.toml",
+                "metadata": {"arborium": {
+                    "fallback": "plain",
+                    "languages": {"aliases": {"cfg": "toml"}},
+                    "rustdoc": {"highlight-rust": true, "themes": {"dark": "tokyo-night"}}
+                }}
+            }
+        ],
+        "workspace_root": "/ws",
+        "workspace_metadata": {"arborium": {"comment-tags": true}}
+    }"#;
+
+    #[test]
+    fn test_package_metadata() {
+        let metadata: serde_json::Value = serde_json::from_str(METADATA).unwrap();
+        let manifest = Path::new("/ws/fancy/Cargo.toml");
+
+        let fancy = PackageMetadata::from_cargo_metadata(&metadata, None, manifest)
+            .unwrap()
+            .unwrap();
+        assert!(fancy.rustdoc.highlight_rust);
+        assert_eq!(fancy.config.languages.resolve("cfg"), Some("toml"));
+
+        let options = fancy.process_options(PathBuf::from("doc")).unwrap();
+        assert_eq!(options.fallback, Fallback::Plain);
+        assert_eq!(options.themes.dark.name, "Tokyo Night");
+        assert_eq!(
+            options.themes.light.name,
+            RustdocThemes::default().light.name
+        );
+
+        let plain = Path::new("/ws/plain/Cargo.toml");
+        assert!(
+            PackageMetadata::from_cargo_metadata(&metadata, Some("plain@0.1.0"), plain)
+                .unwrap()
+                .is_none()
+        );
+
+        let workspace = Path::new("/ws/Cargo.toml");
+        let workspace = PackageMetadata::from_cargo_metadata(&metadata, None, workspace)
+            .unwrap()
+            .unwrap();
+        assert_eq!(workspace.config.comment_tags, Some(true));
+    }
+
+    #[test]
+    fn test_invalid_metadata() {
+        let value = serde_json::json!({"rustdoc": {"highlight-rusty": true}});
+        assert!(matches!(
+            PackageMetadata::from_value(&value, Path::new("Cargo.toml")),
+            Err(Error::InvalidConfig { .. })
+        ));
+    }
+}
@@ -1,13 +1,15 @@
 //! Main processor that transforms rustdoc output directories.
 
-use crate::css::generate_rustdoc_theme_css;
-use crate::html::{TransformError, TransformResult, transform_html};
-use arborium::{GrammarStore, Highlighter};
+use crate::css::{RustdocThemes, generate_rustdoc_theme_css_with};
+use crate::html::{TransformError, TransformOptions, TransformResult, transform_html_with_options};
+use arborium::config_file::LanguagesConfig;
+use arborium::{Config, Diagnostic, Fallback, GrammarStore, Highlighter, UnknownInjections};
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use walkdir::WalkDir;
@@ -21,6 +23,24 @@ pub struct ProcessOptions {
     pub output_dir: Option<PathBuf>,
     /// Whether to show verbose output.
     pub verbose: bool,
+    /// Leave mermaid and plantuml blocks alone for a client-side diagram renderer.
+    pub skip_diagrams: bool,
+    /// Guess the language of untagged and `text` blocks from their contents.
+    pub guess_languages: bool,
+    /// Re-highlight Rust examples instead of keeping rustdoc's highlighting.
+    pub highlight_rust: bool,
+    /// Make `TODO:`, `FIXME:`, `SAFETY:` and similar tags in comments stand out.
+    pub comment_tags: bool,
+    /// How to handle blocks in languages without a grammar.
+    ///
+    /// With [`Fallback::Error`], they are skipped and left as rustdoc rendered them.
+    pub fallback: Fallback,
+    /// Language aliases, and which languages to highlight at all.
+    pub languages: LanguagesConfig,
+    /// Maximum depth of language injections, or `None` for arborium's default.
+    pub max_injection_depth: Option<u32>,
+    /// The arborium theme for each of rustdoc's themes.
+    pub themes: RustdocThemes,
 }
 
 /// Statistics from processing.
@@ -36,6 +56,16 @@ pub struct ProcessorStats {
     pub css_file_modified: Option<PathBuf>,
     /// Languages that were not supported.
     pub unsupported_languages: Vec<String>,
+    /// Why grammars that failed to compile did, one message per language.
+    ///
+    /// Blocks in those languages are left as rustdoc rendered them.
+    pub init_errors: Vec<String>,
+    /// Injected snippets in languages without a grammar, left unhighlighted.
+    pub unknown_injections: usize,
+    /// Injected snippets whose spans were reused from an earlier block.
+    pub injection_cache_hits: u64,
+    /// Injected snippets that were parsed.
+    pub injection_cache_misses: u64,
     /// Total bytes read from input HTML files.
     pub bytes_input: u64,
     /// Total bytes written to output HTML files.
@@ -63,6 +93,14 @@ impl ProcessorStats {
         }
     }
 
+    /// The share of injected snippets that were reused, between 0 and 1.
+    pub fn injection_cache_hit_rate(&self) -> f64 {
+        match self.injection_cache_hits + self.injection_cache_misses {
+            0 => 0.0,
+            total => self.injection_cache_hits as f64 / total as f64,
+        }
+    }
+
     /// Calculate processing throughput in MB/s (excludes clone time).
     pub fn throughput_mb_s(&self) -> f64 {
         let secs = self.process_duration.as_secs_f64();
@@ -102,7 +140,7 @@ impl Processor {
         {
             // Remove output directory if it exists (clean slate)
             if out.exists() {
-                fs::remove_dir_all(out)?;
+                fs::remove_dir_all(out).map_err(io_error(out))?;
             }
 
             // Show spinner while cloning
@@ -117,13 +155,17 @@ impl Processor {
 
             // Use clonetree for fast copy-on-write cloning (instant on APFS)
             clonetree::clone_tree(&self.options.input_dir, out, &clonetree::Options::new())
-                .map_err(|e| ProcessError::Io(std::io::Error::other(e.to_string())))?;
+                .map_err(|e| ProcessError::Clone {
+                    from: self.options.input_dir.clone(),
+                    to: out.clone(),
+                    message: e.to_string(),
+                })?;
 
             spinner.finish_with_message("Clone complete");
         }
 
-        // Step 1: Find and patch the rustdoc CSS file
-        let css_file_modified = self.find_and_patch_css(output_dir)?;
+        // Step 1: Find the rustdoc CSS file (patched once we know which languages are used)
+        let css_path = self.find_css(output_dir)?;
 
         // Step 2: Collect all HTML files to process
         let html_files: Vec<PathBuf> = WalkDir::new(output_dir)
@@ -135,6 +177,15 @@ impl Processor {
 
         // Create a shared grammar store for all highlighters
         let store = Arc::new(GrammarStore::new());
+        let mut config = Config {
+            fallback: self.options.fallback,
+            comment_tags: self.options.comment_tags,
+            unknown_injections: UnknownInjections::Warn,
+            ..Config::default()
+        };
+        if let Some(max_injection_depth) = self.options.max_injection_depth {
+            config.max_injection_depth = max_injection_depth;
+        }
 
         // Create progress bar for file processing
         let progress = ProgressBar::new(html_files.len() as u64);
@@ -153,20 +204,48 @@ impl Processor {
         let blocks_skipped = AtomicUsize::new(0);
         let bytes_input = AtomicUsize::new(0);
         let bytes_output = AtomicUsize::new(0);
+        let injection_cache_hits = AtomicU64::new(0);
+        let injection_cache_misses = AtomicU64::new(0);
+        let unknown_injections = AtomicUsize::new(0);
         let unsupported_languages = Mutex::new(Vec::<String>::new());
+        let used_tags = Mutex::new(BTreeSet::new());
 
         let verbose = self.options.verbose;
+        let transform_options = TransformOptions {
+            skip_diagrams: self.options.skip_diagrams,
+            guess_languages: self.options.guess_languages,
+            highlight_rust: self.options.highlight_rust,
+            languages: self.options.languages.clone(),
+        };
 
         // Process files in parallel using rayon
         // for_each_init creates one Highlighter per thread (not per file!)
         html_files.par_iter().for_each_init(
-            || Highlighter::with_store(store.clone()),
+            || Highlighter::with_store_and_config(store.clone(), config.clone()),
             |highlighter, path| {
                 if verbose {
                     eprintln!("Processing: {}", path.display());
                 }
 
-                match Self::process_html_file_with_highlighter(path, highlighter) {
+                let before = highlighter.injection_cache_stats();
+                let processed =
+                    Self::process_html_file_with_highlighter(path, highlighter, &transform_options);
+                let after = highlighter.injection_cache_stats();
+                injection_cache_hits.fetch_add(after.hits - before.hits, Ordering::Relaxed);
+                injection_cache_misses.fetch_add(after.misses - before.misses, Ordering::Relaxed);
+                let diagnostics = highlighter.take_diagnostics();
+                let unknown = diagnostics
+                    .iter()
+                    .filter(|d| matches!(d, Diagnostic::UnknownInjection { .. }))
+                    .count();
+                unknown_injections.fetch_add(unknown, Ordering::Relaxed);
+                if verbose {
+                    for diagnostic in diagnostics {
+                        progress.println(format!("Warning: {}: {diagnostic}", path.display()));
+                    }
+                }
+
+                match processed {
                     Ok((result, input_size, output_size)) => {
                         files_processed.fetch_add(1, Ordering::Relaxed);
                         blocks_highlighted.fetch_add(result.blocks_highlighted, Ordering::Relaxed);
@@ -182,13 +261,11 @@ impl Processor {
                                 }
                             }
                         }
+
+                        used_tags.lock().unwrap().extend(result.used_tags);
                     }
                     Err(e) => {
-                        progress.println(format!(
-                            "Warning: Failed to process {}: {}",
-                            path.display(),
-                            e
-                        ));
+                        progress.println(format!("Warning: Failed to process {e}"));
                     }
                 }
                 progress.inc(1);
@@ -198,31 +275,45 @@ impl Processor {
         let process_duration = process_start.elapsed();
         progress.finish_and_clear();
 
+        // Step 3: Patch the CSS with rules for the tags we actually emitted
+        let init_errors = store
+            .init_errors()
+            .into_iter()
+            .map(|(_, error)| error.to_string())
+            .collect();
+        let tags: Vec<&str> = used_tags.into_inner().unwrap().into_iter().collect();
+        let css_file_modified = Self::patch_css(css_path, &self.options.themes, &tags)?;
+
         Ok(ProcessorStats {
             files_processed: files_processed.load(Ordering::Relaxed),
             blocks_highlighted: blocks_highlighted.load(Ordering::Relaxed),
             blocks_skipped: blocks_skipped.load(Ordering::Relaxed),
             css_file_modified,
             unsupported_languages: unsupported_languages.into_inner().unwrap(),
+            init_errors,
+            unknown_injections: unknown_injections.into_inner(),
+            injection_cache_hits: injection_cache_hits.into_inner(),
+            injection_cache_misses: injection_cache_misses.into_inner(),
             bytes_input: bytes_input.load(Ordering::Relaxed) as u64,
             bytes_output: bytes_output.load(Ordering::Relaxed) as u64,
             process_duration,
         })
     }
 
-    /// Find the rustdoc CSS file and append arborium theme CSS.
-    fn find_and_patch_css(&self, output_dir: &Path) -> Result<Option<PathBuf>, ProcessError> {
+    /// Find the rustdoc CSS file.
+    fn find_css(&self, output_dir: &Path) -> Result<PathBuf, ProcessError> {
         let static_files = output_dir.join("static.files");
 
         if !static_files.exists() {
-            return Err(ProcessError::CssPatch(format!(
-                "static.files directory not found at {}. Is this a rustdoc output directory?",
-                static_files.display()
-            )));
+            return Err(ProcessError::CssPatch {
+                path: static_files,
+                message: "directory not found. Is this a rustdoc output directory?".to_string(),
+            });
         }
 
         // Find rustdoc-*.css file
-        let css_file = fs::read_dir(&static_files)?
+        let css_file = fs::read_dir(&static_files)
+            .map_err(io_error(&static_files))?
             .filter_map(|e| e.ok())
             .find(|e| {
                 e.file_name()
@@ -231,15 +322,20 @@ impl Processor {
             })
             .map(|e| e.path());
 
-        let Some(css_path) = css_file else {
-            return Err(ProcessError::CssPatch(format!(
-                "No rustdoc-*.css file found in {}",
-                static_files.display()
-            )));
-        };
+        css_file.ok_or_else(|| ProcessError::CssPatch {
+            path: static_files,
+            message: "no rustdoc-*.css file found".to_string(),
+        })
+    }
 
+    /// Append arborium theme CSS for the given element tags to the rustdoc CSS file.
+    fn patch_css(
+        css_path: PathBuf,
+        themes: &RustdocThemes,
+        tags: &[&str],
+    ) -> Result<Option<PathBuf>, ProcessError> {
         // Read existing CSS
-        let mut css_content = fs::read_to_string(&css_path)?;
+        let mut css_content = fs::read_to_string(&css_path).map_err(io_error(&css_path))?;
 
         // Check if we've already patched it
         if css_content.contains("/* arborium syntax highlighting") {
@@ -247,11 +343,11 @@ impl Processor {
         }
 
         // Generate and append arborium theme CSS
-        let arborium_css = generate_rustdoc_theme_css();
+        let arborium_css = generate_rustdoc_theme_css_with(themes, tags);
         css_content.push_str(&arborium_css);
 
         // Write back
-        fs::write(&css_path, css_content)?;
+        fs::write(&css_path, css_content).map_err(io_error(&css_path))?;
 
         Ok(Some(css_path))
     }
@@ -260,8 +356,9 @@ impl Processor {
     fn process_html_file_with_highlighter(
         path: &Path,
         highlighter: &mut Highlighter,
+        options: &TransformOptions,
     ) -> Result<(TransformResult, usize, usize), ProcessError> {
-        let html = fs::read_to_string(path)?;
+        let html = fs::read_to_string(path).map_err(io_error(path))?;
         let input_size = html.len();
 
         // Quick check: skip lol_html parsing if there's no language- class at all
@@ -270,12 +367,16 @@ impl Processor {
             return Ok((TransformResult::default(), input_size, input_size));
         }
 
-        let (transformed, result) = transform_html(&html, highlighter)?;
+        let (transformed, result) = transform_html_with_options(&html, highlighter, options)
+            .map_err(|source| ProcessError::Transform {
+                path: path.to_path_buf(),
+                source,
+            })?;
         let output_size = transformed.len();
 
         // Only write if we actually changed something
         if result.blocks_highlighted > 0 {
-            fs::write(path, &transformed)?;
+            fs::write(path, &transformed).map_err(io_error(path))?;
         }
 
         Ok((result, input_size, output_size))
@@ -283,36 +384,54 @@ impl Processor {
 }
 
 /// Errors that can occur during processing.
-#[derive(Debug)]
+///
+/// Every variant carries the path it's about, so callers can report or skip
+/// individual files.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum ProcessError {
-    /// IO error.
-    Io(std::io::Error),
+    /// Reading or writing a file or directory failed.
+    #[error("{}: {source}", .path.display())]
+    Io {
+        /// The file or directory being accessed.
+        path: PathBuf,
+        /// The underlying IO error.
+        #[source]
+        source: std::io::Error,
+    },
+    /// Copying the input directory to the output directory failed.
+    #[error("failed to copy {} to {}: {message}", .from.display(), .to.display())]
+    Clone {
+        /// The input directory.
+        from: PathBuf,
+        /// The output directory.
+        to: PathBuf,
+        /// What went wrong.
+        message: String,
+    },
     /// HTML transformation error.
-    Transform(TransformError),
+    #[error("{}: {source}", .path.display())]
+    Transform {
+        /// The HTML file being transformed.
+        path: PathBuf,
+        /// The underlying transformation error.
+        #[source]
+        source: TransformError,
+    },
     /// CSS patching error.
-    CssPatch(String),
-}
-
-impl From<std::io::Error> for ProcessError {
-    fn from(e: std::io::Error) -> Self {
-        ProcessError::Io(e)
-    }
-}
-
-impl From<TransformError> for ProcessError {
-    fn from(e: TransformError) -> Self {
-        ProcessError::Transform(e)
-    }
+    #[error("{}: {message}", .path.display())]
+    CssPatch {
+        /// The CSS file or `static.files` directory.
+        path: PathBuf,
+        /// What went wrong.
+        message: String,
+    },
 }
 
-impl std::fmt::Display for ProcessError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ProcessError::Io(e) => write!(f, "IO error: {}", e),
-            ProcessError::Transform(e) => write!(f, "Transform error: {}", e),
-            ProcessError::CssPatch(msg) => write!(f, "CSS patch error: {}", msg),
-        }
+/// Attach a path to an IO error, for `map_err`.
+fn io_error(path: &Path) -> impl FnOnce(std::io::Error) -> ProcessError {
+    move |source| ProcessError::Io {
+        path: path.to_path_buf(),
+        source,
     }
 }
-
-impl std::error::Error for ProcessError {}
@@ -0,0 +1,228 @@
+//! Toggling comments, like editors' "toggle comment" command.
+//!
+//! [`toggle_comments`] works on whole lines: the lines a selection touches
+//! get commented out, or uncommented if they all are already. Languages with
+//! line comments get one per line, inserted at the lines' common indentation
+//! so the code stays aligned. Languages with only block comments, like CSS
+//! and HTML, get one block comment around the lines' text.
+
+use std::ops::Range;
+
+/// A language's comment delimiters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CommentSyntax {
+    /// Starts a comment running to the end of the line, like `//`.
+    pub line: Option<&'static str>,
+    /// Start and end of a block comment, like `/*` and `*/`.
+    pub block: Option<(&'static str, &'static str)>,
+}
+
+/// A change [`toggle_comments`] makes: replace `range` of the source with
+/// `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentEdit {
+    /// Byte range of the source to replace, empty for an insertion.
+    pub range: Range<usize>,
+    /// The text to put there, empty for a deletion.
+    pub replacement: String,
+}
+
+/// The edits that comment out the lines `selection` touches in `source`, or
+/// uncomment them if they are all commented out already.
+///
+/// A selection ending at the start of a line, as selecting whole lines does,
+/// doesn't touch that line. Blank lines are left alone. The edits are in
+/// source order and don't overlap, so apply them from last to first to keep
+/// their ranges valid. Returns no edits if the language has no comments or
+/// the lines are blank.
+pub fn toggle_comments(
+    syntax: CommentSyntax,
+    source: &str,
+    selection: Range<usize>,
+) -> Vec<CommentEdit> {
+    let lines: Vec<Range<usize>> = lines(source, selection)
+        .into_iter()
+        .filter(|line| !source[line.clone()].trim().is_empty())
+        .collect();
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    match syntax {
+        CommentSyntax {
+            line: Some(token), ..
+        } => toggle_line_comments(token, source, &lines),
+        CommentSyntax {
+            block: Some((start, end)),
+            ..
+        } => {
+            let first = &lines[0];
+            let last = &lines[lines.len() - 1];
+            let text = &source[first.clone()];
+            let region = first.start + (text.len() - text.trim_start().len())
+                ..last.start + source[last.clone()].trim_end().len();
+            toggle_block_comment(start, end, source, region)
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// The lines `selection` touches, without their line breaks.
+fn lines(source: &str, selection: Range<usize>) -> Vec<Range<usize>> {
+    let end = floor_char_boundary(source, selection.end.min(source.len()));
+    let start = floor_char_boundary(source, selection.start.min(end));
+    let last = if end > start && source[..end].ends_with('\n') {
+        end - 1
+    } else {
+        end
+    };
+
+    let mut lines = Vec::new();
+    let mut pos = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    loop {
+        let line_end = source[pos..].find('\n').map_or(source.len(), |i| pos + i);
+        let text = source[pos..line_end]
+            .strip_suffix('\r')
+            .unwrap_or(&source[pos..line_end]);
+        lines.push(pos..pos + text.len());
+        if line_end >= last || line_end == source.len() {
+            return lines;
+        }
+        pos = line_end + 1;
+    }
+}
+
+fn toggle_line_comments(token: &str, source: &str, lines: &[Range<usize>]) -> Vec<CommentEdit> {
+    let indent = |line: &Range<usize>| {
+        let text = &source[line.clone()];
+        text.len() - text.trim_start().len()
+    };
+
+    let commented = lines
+        .iter()
+        .all(|line| source[line.start + indent(line)..line.end].starts_with(token));
+    if commented {
+        return lines
+            .iter()
+            .map(|line| {
+                let start = line.start + indent(line);
+                let after = &source[start + token.len()..line.end];
+                let len = token.len() + usize::from(after.starts_with(' '));
+                CommentEdit {
+                    range: start..start + len,
+                    replacement: String::new(),
+                }
+            })
+            .collect();
+    }
+
+    let common = lines.iter().map(indent).min().unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| CommentEdit {
+            range: line.start + common..line.start + common,
+            replacement: format!("{token} "),
+        })
+        .collect()
+}
+
+fn toggle_block_comment(
+    start: &str,
+    end: &str,
+    source: &str,
+    region: Range<usize>,
+) -> Vec<CommentEdit> {
+    let text = &source[region.clone()];
+    if text.len() >= start.len() + end.len() && text.starts_with(start) && text.ends_with(end) {
+        let inner = &text[start.len()..text.len() - end.len()];
+        let open = start.len() + usize::from(inner.starts_with(' '));
+        let close = (end.len() + usize::from(inner.ends_with(' '))).min(text.len() - open);
+        return vec![
+            CommentEdit {
+                range: region.start..region.start + open,
+                replacement: String::new(),
+            },
+            CommentEdit {
+                range: region.end - close..region.end,
+                replacement: String::new(),
+            },
+        ];
+    }
+
+    vec![
+        CommentEdit {
+            range: region.start..region.start,
+            replacement: format!("{start} "),
+        },
+        CommentEdit {
+            range: region.end..region.end,
+            replacement: format!(" {end}"),
+        },
+    ]
+}
+
+fn floor_char_boundary(source: &str, mut index: usize) -> usize {
+    while !source.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUST: CommentSyntax = CommentSyntax {
+        line: Some("//"),
+        block: Some(("/*", "*/")),
+    };
+    const CSS: CommentSyntax = CommentSyntax {
+        line: None,
+        block: Some(("/*", "*/")),
+    };
+
+    fn toggle(syntax: CommentSyntax, source: &str, selection: Range<usize>) -> String {
+        let mut text = source.to_string();
+        for edit in toggle_comments(syntax, source, selection).into_iter().rev() {
+            text.replace_range(edit.range, &edit.replacement);
+        }
+        text
+    }
+
+    #[test]
+    fn test_line_comments() {
+        let source = "fn f() {\n    if x {\n        y();\n    }\n}\n";
+        // From `if` to the start of the last line
+        let body = |s: &str| s.find("if").unwrap()..s.find("}\n}").unwrap() + 2;
+        let commented = toggle(RUST, source, body(source));
+        assert_eq!(
+            commented,
+            "fn f() {\n    // if x {\n    //     y();\n    // }\n}\n"
+        );
+        assert_eq!(toggle(RUST, &commented, body(&commented)), source);
+
+        // Whole lines end at the start of the next line, which stays as it is
+        assert_eq!(toggle(RUST, "a\nb\n", 0..2), "// a\nb\n");
+    }
+
+    #[test]
+    fn test_mixed_lines_get_commented() {
+        assert_eq!(toggle(RUST, "// a\nb\n\nc", 0..9), "// // a\n// b\n\n// c");
+        assert_eq!(toggle(RUST, "//a\r\n  //b", 0..10), "a\r\n  b");
+    }
+
+    #[test]
+    fn test_block_comments() {
+        let source = "  a { color: red; }\n  b { }\n";
+        let commented = toggle(CSS, source, 3..22);
+        assert_eq!(commented, "  /* a { color: red; }\n  b { } */\n");
+        assert_eq!(toggle(CSS, &commented, 0..commented.len()), source);
+        assert_eq!(toggle(CSS, "/**/", 0..0), "");
+    }
+
+    #[test]
+    fn test_nothing_to_toggle() {
+        assert!(toggle_comments(RUST, "a\n\n  \nb", 2..5).is_empty());
+        assert!(toggle_comments(CommentSyntax::default(), "a", 0..1).is_empty());
+    }
+}
@@ -0,0 +1,37 @@
+//! Syntax tree utilities shared by arborium's highlighter and its grammar
+//! plugins.
+//!
+//! `arborium-highlight` re-exports everything here, and WASM grammar plugins
+//! use it through `arborium-plugin-runtime`, so both answer editors the same
+//! way without plugins pulling in the highlighter, its themes and renderers.
+//!
+//! - Comment toggling, from a language's comment delimiters
+//! - Prose ranges, for spell checkers, read off highlighted spans
+//!
+//! With the `tree-sitter` feature, also:
+//!
+//! - Syntax tree dumps, like `tree-sitter parse` prints them
+//! - Injection ranges and language annotations in comments
+//! - Folding ranges and document symbols, for editors
+//! - Selection expansion by syntax node
+
+mod comment_toggle;
+#[cfg(feature = "tree-sitter")]
+mod outline;
+mod prose;
+#[cfg(feature = "tree-sitter")]
+mod selection;
+#[cfg(feature = "tree-sitter")]
+mod tree;
+
+pub use comment_toggle::{CommentEdit, CommentSyntax, toggle_comments};
+pub use prose::{ProseClass, ProseRange, prose_ranges};
+
+#[cfg(feature = "tree-sitter")]
+pub use outline::{
+    FoldingKind, FoldingRange, SYMBOL_KINDS, Symbol, folding_ranges, symbol_kind, symbols,
+};
+#[cfg(feature = "tree-sitter")]
+pub use selection::expand_selection;
+#[cfg(feature = "tree-sitter")]
+pub use tree::{SexpOptions, annotated_language, injection_range, tree_sexp};
@@ -0,0 +1,273 @@
+//! Folding ranges and document symbols, for editors' code folding and
+//! outline panes.
+//!
+//! Few grammars ship `folds.scm` or `tags.scm` queries, so both are read off
+//! the shape of the syntax tree, which works the same for every grammar:
+//!
+//! - A named node spanning several lines folds, from its first line to the
+//!   line before its closing delimiter, so `}` stays visible. Runs of line
+//!   comments fold together.
+//! - A named node with a `name` field whose kind mentions a kind of
+//!   definition, like `function_item` or `class_declaration`, is a symbol.
+//!   Symbols nest the way their nodes do.
+
+use std::collections::BTreeMap;
+
+use arborium_tree_sitter::{Node, Tree, TreeCursor};
+
+/// What a [`FoldingRange`] folds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoldingKind {
+    /// A block of code, like a function body or an array literal.
+    Region,
+    /// A block comment or a run of line comments.
+    Comment,
+}
+
+impl FoldingKind {
+    /// The name of the kind, as LSP's `FoldingRangeKind` spells it.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FoldingKind::Region => "region",
+            FoldingKind::Comment => "comment",
+        }
+    }
+}
+
+/// Lines that can be folded, 0-based and inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoldingRange {
+    /// The line the fold starts on, which stays visible when folded.
+    pub start_row: u32,
+    /// The last line hidden by the fold.
+    pub end_row: u32,
+    /// What the range folds.
+    pub kind: FoldingKind,
+}
+
+/// Definition kinds, by a word that appears in the kinds of their nodes.
+///
+/// The first match wins, so `method_definition` is a method, not a
+/// definition of some other kind. The kinds follow LSP's `SymbolKind`.
+pub const SYMBOL_KINDS: &[(&str, &str)] = &[
+    ("method", "method"),
+    ("constructor", "constructor"),
+    ("function", "function"),
+    ("class", "class"),
+    ("interface", "interface"),
+    ("trait", "interface"),
+    ("protocol", "interface"),
+    ("variant", "enum_member"),
+    ("enum", "enum"),
+    ("struct", "struct"),
+    ("union", "struct"),
+    ("record", "struct"),
+    ("module", "module"),
+    ("mod", "module"),
+    ("namespace", "namespace"),
+    ("package", "package"),
+    ("macro", "macro"),
+    ("type", "type"),
+    ("const", "constant"),
+    ("constant", "constant"),
+];
+
+/// A definition in the document outline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// The defined name.
+    pub name: String,
+    /// The kind of definition, from [`SYMBOL_KINDS`].
+    pub kind: &'static str,
+    /// Byte offset where the definition starts.
+    pub start: u32,
+    /// Byte offset where the definition ends (exclusive).
+    pub end: u32,
+    /// Byte offset where the name starts.
+    pub name_start: u32,
+    /// Byte offset where the name ends (exclusive).
+    pub name_end: u32,
+    /// Definitions nested in this one, like a class's methods.
+    pub children: Vec<Symbol>,
+}
+
+/// The ranges of `tree` that can be folded, ordered by start line.
+///
+/// A line starts at most one fold, the outermost.
+pub fn folding_ranges(tree: &Tree) -> Vec<FoldingRange> {
+    // Nodes are visited outermost first, so the first range on a line wins
+    let mut ranges = BTreeMap::new();
+    let mut push = |range: FoldingRange| {
+        if range.end_row > range.start_row {
+            ranges.entry(range.start_row).or_insert(range);
+        }
+    };
+
+    // The line comments of the current run, as (start_row, end_row)
+    let mut comments: Option<(u32, u32)> = None;
+    let mut cursor = tree.walk();
+    // Skip the root: folding the whole document is not useful
+    let mut more = cursor.goto_first_child();
+    while more {
+        let node = cursor.node();
+        let (start_row, end_row) = (
+            node.start_position().row as u32,
+            node.end_position().row as u32,
+        );
+
+        if is_comment(node) {
+            if start_row == end_row || ends_with_newline(node) {
+                // A line comment: extend the run if it's on the next line
+                match &mut comments {
+                    Some((_, run_end)) if *run_end + 1 == start_row => *run_end = start_row,
+                    _ => {
+                        if let Some((start_row, end_row)) = comments.take() {
+                            push(FoldingRange {
+                                start_row,
+                                end_row,
+                                kind: FoldingKind::Comment,
+                            });
+                        }
+                        comments = Some((start_row, start_row));
+                    }
+                }
+            } else {
+                push(FoldingRange {
+                    start_row,
+                    end_row,
+                    kind: FoldingKind::Comment,
+                });
+            }
+            more = next_node(&mut cursor, false);
+            continue;
+        }
+
+        if node.is_named() && end_row > start_row {
+            push(FoldingRange {
+                start_row,
+                end_row: fold_end(node, end_row),
+                kind: FoldingKind::Region,
+            });
+        }
+        more = next_node(&mut cursor, true);
+    }
+    if let Some((start_row, end_row)) = comments {
+        push(FoldingRange {
+            start_row,
+            end_row,
+            kind: FoldingKind::Comment,
+        });
+    }
+
+    ranges.into_values().collect()
+}
+
+/// The definitions in `tree`, parsed from `source`, nested as in the tree.
+pub fn symbols(tree: &Tree, source: &str) -> Vec<Symbol> {
+    let mut cursor = tree.walk();
+    symbols_in(&mut cursor, source)
+}
+
+/// The symbols among the descendants of the cursor's node, which the cursor
+/// ends on again.
+fn symbols_in(cursor: &mut TreeCursor, source: &str) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+    if !cursor.goto_first_child() {
+        return symbols;
+    }
+    loop {
+        let node = cursor.node();
+        let mut children = symbols_in(cursor, source);
+        match symbol(node, source) {
+            Some(mut symbol) => {
+                symbol.children = children;
+                symbols.push(symbol);
+            }
+            None => symbols.append(&mut children),
+        }
+        if !cursor.goto_next_sibling() {
+            break;
+        }
+    }
+    cursor.goto_parent();
+    symbols
+}
+
+/// The symbol `node` defines, without its children.
+fn symbol(node: Node, source: &str) -> Option<Symbol> {
+    let kind = symbol_kind(node)?;
+    let name = node.child_by_field_name("name")?;
+    let text = source.get(name.byte_range())?.trim();
+    if text.is_empty() || text.contains('\n') {
+        return None;
+    }
+    Some(Symbol {
+        name: text.to_string(),
+        kind,
+        start: node.start_byte() as u32,
+        end: node.end_byte() as u32,
+        name_start: name.start_byte() as u32,
+        name_end: name.end_byte() as u32,
+        children: Vec::new(),
+    })
+}
+
+/// The kind of definition `node` is, from [`SYMBOL_KINDS`], if it's one.
+pub fn symbol_kind(node: Node) -> Option<&'static str> {
+    if !node.is_named() || node.child_by_field_name("name").is_none() {
+        return None;
+    }
+    let node_kind = node.kind();
+    SYMBOL_KINDS
+        .iter()
+        .find(|(word, _)| node_kind.split('_').any(|part| part == *word))
+        .map(|&(_, kind)| kind)
+}
+
+fn is_comment(node: Node) -> bool {
+    node.kind().contains("comment")
+}
+
+/// Whether a comment node includes its line break, as line comments do in
+/// some grammars.
+fn ends_with_newline(node: Node) -> bool {
+    node.end_position().column == 0
+}
+
+/// The last line to hide when folding `node`: the line before its closing
+/// delimiter if that starts its last line, so `}` stays visible.
+fn fold_end(node: Node, end_row: u32) -> u32 {
+    // The delimiter may belong to a body node, like a function's block
+    let mut last = node;
+    while let Some(child) = last
+        .child_count()
+        .checked_sub(1)
+        .and_then(|i| last.child(i))
+    {
+        last = child;
+    }
+    let starts_line = last
+        .prev_sibling()
+        .is_some_and(|prev| (prev.end_position().row as u32) < end_row);
+    if !last.is_named() && last.start_position().row as u32 == end_row && starts_line {
+        end_row - 1
+    } else {
+        end_row
+    }
+}
+
+/// Move to the next node in pre-order, skipping the current node's children
+/// unless `into_children`. Returns false at the end of the tree.
+fn next_node(cursor: &mut TreeCursor, into_children: bool) -> bool {
+    if into_children && cursor.goto_first_child() {
+        return true;
+    }
+    loop {
+        if cursor.goto_next_sibling() {
+            return true;
+        }
+        if !cursor.goto_parent() {
+            return false;
+        }
+    }
+}
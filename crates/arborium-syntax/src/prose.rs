@@ -0,0 +1,215 @@
+//! Text to spell check: comments, string literals and markup text, for spell
+//! checkers and documentation linters.
+//!
+//! Highlighting already tells these regions apart, so [`prose_ranges`] reads
+//! them off the spans. Where spans nest, the innermost one decides: escape
+//! sequences and interpolations in a string, inline code in a paragraph and
+//! URLs in a comment are left out, while a comment in a code block of a
+//! Markdown document is picked up. Regions a grammar captures as `@spell`
+//! count as markup text, and `@nospell` ones are left out.
+
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+/// A kind of text [`prose_ranges`] picks out, by the capture of its span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProseClass {
+    /// Comments, including doc comments, but not commented-out code like
+    /// Clojure's `#_` forms.
+    Comment,
+    /// String literals, but not regexes, symbols, paths or URLs.
+    String,
+    /// Text of markup languages: headings, emphasis, link text, and whatever
+    /// the grammar captures as `@spell`.
+    Markup,
+}
+
+impl ProseClass {
+    /// Every class, for [`prose_ranges`] to pick out all prose.
+    pub const ALL: &[ProseClass] = &[ProseClass::Comment, ProseClass::String, ProseClass::Markup];
+
+    /// The class of text a span with `capture` holds, if it's prose.
+    pub fn of(capture: &str) -> Option<ProseClass> {
+        let mut parts = capture.split('.');
+        match (parts.next()?, parts.next()) {
+            ("comment", Some("unused" | "discard")) => None,
+            ("comment", _) => Some(ProseClass::Comment),
+            ("string", Some("special" | "escape" | "regex" | "regexp")) => None,
+            ("string", _) => Some(ProseClass::String),
+            ("spell" | "text", None) => Some(ProseClass::Markup),
+            ("text", Some("title" | "strong" | "emphasis" | "underline" | "strikethrough")) => {
+                Some(ProseClass::Markup)
+            }
+            ("markup", Some("heading")) if parts.next() != Some("marker") => {
+                Some(ProseClass::Markup)
+            }
+            ("markup", Some("bold" | "italic" | "underline" | "strikethrough" | "quote")) => {
+                Some(ProseClass::Markup)
+            }
+            ("markup", Some("link")) if parts.next() == Some("text") => Some(ProseClass::Markup),
+            _ => None,
+        }
+    }
+
+    /// The name of the class: `comment`, `string` or `markup`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProseClass::Comment => "comment",
+            ProseClass::String => "string",
+            ProseClass::Markup => "markup",
+        }
+    }
+
+    /// The class named `name`, as [`as_str`](Self::as_str) spells it.
+    pub fn from_name(name: &str) -> Option<ProseClass> {
+        ProseClass::ALL
+            .iter()
+            .copied()
+            .find(|class| class.as_str() == name)
+    }
+}
+
+/// A run of prose found by [`prose_ranges`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProseRange {
+    /// Byte range of the text, in the same offsets as the spans.
+    pub range: Range<usize>,
+    /// What kind of text it is.
+    pub class: ProseClass,
+}
+
+/// The ranges of prose of the given `classes` in highlighted text, from its
+/// `spans`, ordered by offset.
+///
+/// Each span is its byte range and capture name, so this works on the spans
+/// of the highlighter and of plugins alike. Adjacent ranges of the same class
+/// are merged, so a run of line comments can come back as one range.
+pub fn prose_ranges<'a>(
+    spans: impl IntoIterator<Item = (Range<u32>, &'a str)>,
+    classes: &[ProseClass],
+) -> Vec<ProseRange> {
+    let spans: Vec<(Range<u32>, &str)> = spans.into_iter().collect();
+    let mut bounds: Vec<u32> = spans
+        .iter()
+        .flat_map(|(range, _)| [range.start, range.end])
+        .collect();
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    let mut order: Vec<usize> = (0..spans.len())
+        .filter(|&i| !spans[i].0.is_empty())
+        .collect();
+    order.sort_by_key(|&i| spans[i].0.start);
+    let mut order = order.into_iter().peekable();
+
+    // The spans around the current position, innermost last. Of spans with
+    // the same range, `@spell` is the outer one, so `@comment @spell` is a
+    // comment. Spans that ended are only dropped once they're innermost.
+    let mut active = BTreeSet::new();
+    let mut ranges: Vec<ProseRange> = Vec::new();
+    for window in bounds.windows(2) {
+        let (from, to) = (window[0], window[1]);
+        while let Some(i) = order.next_if(|&i| spans[i].0.start <= from) {
+            let (range, capture) = &spans[i];
+            active.insert((range.start, Reverse(range.end), *capture != "spell", i));
+        }
+        while active
+            .last()
+            .is_some_and(|&(_, Reverse(end), _, _)| end <= from)
+        {
+            active.pop_last();
+        }
+
+        let Some(&(_, _, _, i)) = active.last() else {
+            continue;
+        };
+        let Some(class) = ProseClass::of(spans[i].1).filter(|c| classes.contains(c)) else {
+            continue;
+        };
+        let range = from as usize..to as usize;
+        match ranges.last_mut() {
+            Some(last) if last.class == class && last.range.end == range.start => {
+                last.range.end = range.end;
+            }
+            _ => ranges.push(ProseRange { range, class }),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: u32, end: u32, capture: &'static str) -> (Range<u32>, &'static str) {
+        (start..end, capture)
+    }
+
+    fn prose<'a>(
+        source: &'a str,
+        spans: &[(Range<u32>, &str)],
+        classes: &[ProseClass],
+    ) -> Vec<&'a str> {
+        prose_ranges(spans.iter().cloned(), classes)
+            .into_iter()
+            .map(|prose| &source[prose.range])
+            .collect()
+    }
+
+    #[test]
+    fn test_nested_spans() {
+        // let s = "a\tb {x}"; // see https://example.com
+        let source = "let s = \"a\\tb {x}\"; // see https://example.com";
+        let spans = [
+            span(0, 3, "keyword"),
+            span(8, 18, "string"),
+            span(10, 12, "string.escape"),
+            span(14, 17, "embedded"),
+            span(20, 46, "comment"),
+            span(27, 46, "markup.link.url"),
+        ];
+        assert_eq!(
+            prose(source, &spans, ProseClass::ALL),
+            ["\"a", "b ", "\"", "// see "]
+        );
+        assert_eq!(prose(source, &spans, &[ProseClass::Comment]), ["// see "]);
+    }
+
+    #[test]
+    fn test_spell_and_nospell() {
+        let source = "Some *text* and `code`.";
+        let spans = [
+            span(0, 23, "spell"),
+            span(5, 11, "markup.italic"),
+            span(16, 22, "nospell"),
+        ];
+        let ranges = prose_ranges(spans, ProseClass::ALL);
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(&source[ranges[0].range.clone()], "Some *text* and ");
+        assert_eq!(ranges[0].class, ProseClass::Markup);
+        assert_eq!(&source[ranges[1].range.clone()], ".");
+
+        // `@comment @spell` on the same node is a comment
+        let spans = [span(0, 4, "comment"), span(0, 4, "spell")];
+        assert_eq!(
+            prose_ranges(spans, ProseClass::ALL)[0].class,
+            ProseClass::Comment
+        );
+    }
+
+    #[test]
+    fn test_classes() {
+        assert_eq!(
+            ProseClass::of("comment.documentation"),
+            Some(ProseClass::Comment)
+        );
+        assert_eq!(ProseClass::of("comment.discard"), None);
+        assert_eq!(ProseClass::of("string.special.symbol"), None);
+        assert_eq!(ProseClass::of("markup.heading.2"), Some(ProseClass::Markup));
+        assert_eq!(ProseClass::of("markup.heading.marker"), None);
+        assert_eq!(ProseClass::of("markup.link.url"), None);
+        assert_eq!(ProseClass::of("keyword"), None);
+        assert_eq!(ProseClass::from_name("string"), Some(ProseClass::String));
+    }
+}
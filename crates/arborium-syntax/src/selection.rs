@@ -0,0 +1,33 @@
+//! Growing a selection to the syntax node around it, like editors'
+//! "expand selection" command.
+
+use std::ops::Range;
+
+use arborium_tree_sitter::Tree;
+
+/// The byte range of the smallest node of `tree` that contains `range` and
+/// is larger than it.
+///
+/// Calling it again on the result selects the next larger node, so an empty
+/// range (a cursor) grows to the token under it, then to the expression
+/// around that, and so on up to the whole document. Returns `range` as it
+/// is once it covers the whole tree; offsets past the end are clamped to it.
+pub fn expand_selection(tree: &Tree, range: Range<usize>) -> Range<usize> {
+    let root = tree.root_node();
+    let end = range.end.min(root.end_byte());
+    let start = range.start.min(end);
+
+    let Some(mut node) = root.descendant_for_byte_range(start, end) else {
+        return range;
+    };
+    loop {
+        let found = node.byte_range();
+        if found.start <= start && found.end >= end && found != (start..end) {
+            return found;
+        }
+        match node.parent() {
+            Some(parent) => node = parent,
+            None => return range,
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Dumping syntax trees and locating injections in them.
+
+use arborium_tree_sitter::{Node, Query, QueryPredicateArg, Tree};
+
+/// Options for [`tree_sexp`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SexpOptions {
+    /// Append each node's `[row, column] - [row, column]` range (0-based).
+    pub ranges: bool,
+    /// Include anonymous nodes (keywords, punctuation) as quoted strings.
+    pub anonymous: bool,
+}
+
+/// Pretty-print a syntax tree as an S-expression, one node per line.
+///
+/// The output follows `tree-sitter parse`: children are indented by two spaces,
+/// field names prefix the nodes they label, and missing nodes are shown as
+/// `(MISSING kind)`.
+pub fn tree_sexp(tree: &Tree, options: SexpOptions) -> String {
+    use std::fmt::Write;
+
+    let mut out = String::new();
+    let mut cursor = tree.walk();
+    let mut depth = 0usize;
+
+    loop {
+        let node = cursor.node();
+        if node.is_named() || options.anonymous {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            for _ in 0..depth {
+                out.push_str("  ");
+            }
+            if let Some(field) = cursor.field_name() {
+                let _ = write!(out, "{field}: ");
+            }
+            out.push('(');
+            if node.is_missing() {
+                out.push_str("MISSING ");
+            }
+            if node.is_named() {
+                out.push_str(node.kind());
+            } else {
+                let _ = write!(out, "{:?}", node.kind());
+            }
+            if options.ranges {
+                let (start, end) = (node.start_position(), node.end_position());
+                let _ = write!(
+                    out,
+                    " [{}, {}] - [{}, {}]",
+                    start.row, start.column, end.row, end.column
+                );
+            }
+            depth += 1;
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+
+        // Close finished nodes until one has a next sibling
+        loop {
+            let node = cursor.node();
+            if node.is_named() || options.anonymous {
+                out.push(')');
+                depth -= 1;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+            if !cursor.goto_parent() {
+                return out;
+            }
+        }
+    }
+}
+
+/// Byte range to inject for a match's `@injection.content` node.
+///
+/// Applies `(#offset! @injection.content 0 1 0 -1)`, which trims delimiters
+/// such as the quotes around a string. Only the column deltas are used, as
+/// byte deltas from the node's start and end; row deltas are ignored.
+///
+/// An `@injection.language` node that closes the content, like the `SQL`
+/// ending a `<<SQL` heredoc, is left out of the range.
+pub fn injection_range(
+    query: &Query,
+    pattern_index: usize,
+    node: Node,
+    language_node: Option<Node>,
+) -> (usize, usize) {
+    let content_idx = query.capture_index_for_name("injection.content");
+    let (mut start, mut end) = (node.start_byte(), node.end_byte());
+    if let Some(language) = language_node {
+        if language.start_byte() > start && language.end_byte() == end {
+            end = language.start_byte();
+        }
+    }
+    for predicate in query.general_predicates(pattern_index) {
+        if &*predicate.operator != "offset!" {
+            continue;
+        }
+        let [
+            QueryPredicateArg::Capture(idx),
+            QueryPredicateArg::String(_),
+            QueryPredicateArg::String(start_col),
+            QueryPredicateArg::String(_),
+            QueryPredicateArg::String(end_col),
+        ] = &*predicate.args
+        else {
+            continue;
+        };
+        if Some(*idx) == content_idx {
+            let delta = |col: &str| col.parse::<isize>().unwrap_or(0);
+            start = start.saturating_add_signed(delta(start_col));
+            end = end
+                .saturating_add_signed(delta(end_col))
+                .min(node.end_byte());
+        }
+    }
+    (start.min(end), end)
+}
+
+/// The language named by a comment annotating an injection.
+///
+/// Used for `(#strip-comment! @injection.language)`, so a string can be
+/// marked with the language it contains: `/* wgsl */`, `// glsl` and the
+/// JetBrains-style `// language=wgsl` all work, as do `#` comments. Comments
+/// that are not a single language name, like `// the blur shader`, give
+/// `None`.
+pub fn annotated_language(comment: &str) -> Option<&str> {
+    let comment = comment.trim();
+    let text = match comment.strip_prefix("/*") {
+        Some(block) => block.strip_suffix("*/")?,
+        None => comment
+            .strip_prefix("//")
+            .or_else(|| comment.strip_prefix('#'))?,
+    };
+    let text = text.trim();
+    let text = text.strip_prefix("language=").unwrap_or(text);
+    let is_name = !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '+'));
+    is_name.then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotated_language() {
+        assert_eq!(annotated_language("/* wgsl */"), Some("wgsl"));
+        assert_eq!(annotated_language("// language=GLSL"), Some("GLSL"));
+        assert_eq!(annotated_language("# sql"), Some("sql"));
+        assert_eq!(annotated_language("// the blur shader"), None);
+        assert_eq!(annotated_language("/**/"), None);
+    }
+}
@@ -37,8 +37,8 @@ use arborium_tree_sitter::Language;
 use arborium_tree_sitter::{Node, Parser, Tree};
 use tree_sitter_language::LanguageFn;
 
-// Re-export CAPTURE_NAMES from arborium-theme as HIGHLIGHT_NAMES for convenience
-pub use arborium_theme::CAPTURE_NAMES as HIGHLIGHT_NAMES_FULL;
+// Re-export CAPTURE_NAMES from arborium-captures as HIGHLIGHT_NAMES for convenience
+pub use arborium_captures::CAPTURE_NAMES as HIGHLIGHT_NAMES_FULL;
 
 #[derive(Debug, Default)]
 struct CorpusTest {
@@ -114,6 +114,7 @@ pub fn test_grammar(
         highlights_query,
         injections_query,
         locals_query: "", // Not used by arborium-highlight yet
+        textobjects_query: "",
     };
 
     // Validate queries compile by creating the grammar
@@ -560,8 +561,8 @@ fn parse_samples_from_kdl(path: &Path) -> Vec<String> {
 
 /// Standard highlight names used by arborium.
 ///
-/// **Deprecated**: Use [`arborium_theme::CAPTURE_NAMES`] instead, which is the
+/// **Deprecated**: Use [`arborium_captures::CAPTURE_NAMES`] instead, which is the
 /// canonical source of truth for all capture names.
 ///
 /// This constant is kept for backwards compatibility.
-pub const HIGHLIGHT_NAMES: &[&str] = arborium_theme::CAPTURE_NAMES;
+pub const HIGHLIGHT_NAMES: &[&str] = arborium_captures::CAPTURE_NAMES;
@@ -0,0 +1,292 @@
+//! Helpers for testing arborium grammars and highlight queries.
+//!
+//! [`record_events`] highlights source code and flattens the spans into a
+//! stream of [`Event`]s: highlights starting and ending around the text they
+//! cover, properly nested. Tests then check the stream with
+//! [`assert_has_highlights`] and [`assert_text_highlighted`], or compare
+//! [`format_events`] with a snapshot.
+//!
+//! ```rust,ignore
+//! use arborium::Highlighter;
+//! use arborium_test_support::{assert_text_highlighted, record_events};
+//!
+//! let mut highlighter = Highlighter::new();
+//! let events = record_events(&mut highlighter, "html", "<script>let x = 1;</script>");
+//! assert_text_highlighted(&events, "let", "keyword", "script injection");
+//! ```
+//!
+//! Grammars that aren't compiled into arborium, like a plugin's grammar
+//! under development, are tested the same way after registering them with
+//! [`GrammarStore::register_language`](arborium::GrammarStore::register_language)
+//! and highlighting with [`Highlighter::with_store`].
+//!
+//! Highlight names are the captures of the grammar's `highlights.scm`. A
+//! name matches its own capture and the captures it is a prefix of, so
+//! `"keyword"` matches `@keyword.return` the way themes fall back to it.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use arborium::Highlighter;
+use arborium::advanced::Span;
+
+/// A recorded highlight event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// Source text, highlighted by all highlights started and not ended yet.
+    Source {
+        /// The text.
+        text: String,
+    },
+    /// A highlight starts.
+    Start {
+        /// The capture name, without the `@`.
+        name: String,
+    },
+    /// The most recently started highlight ends.
+    End,
+}
+
+/// Highlight `source` as `language`, injections included, and record the
+/// result as events.
+///
+/// # Panics
+///
+/// Panics if `source` can't be highlighted as `language`, as tests want.
+pub fn record_events(highlighter: &mut Highlighter, language: &str, source: &str) -> Vec<Event> {
+    let spans = highlighter
+        .highlight_spans(language, source)
+        .unwrap_or_else(|e| panic!("failed to highlight {language}: {e}"));
+    spans_to_events(source, spans)
+}
+
+/// Turn spans into events, for spans that come from elsewhere, such as a
+/// plugin's parse results.
+///
+/// Spans that overlap without nesting are cut off where the enclosing span
+/// ends, so every [`Event::Start`] has a matching [`Event::End`].
+pub fn spans_to_events(source: &str, mut spans: Vec<Span>) -> Vec<Event> {
+    // Outer spans first, so inner ones nest inside them
+    spans.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+
+    let mut events = Vec::new();
+    // The ends of the spans started and not ended yet, innermost last
+    let mut open: Vec<usize> = Vec::new();
+    let mut pos = 0;
+    let text = |events: &mut Vec<Event>, pos: &mut usize, to: usize| {
+        if *pos < to {
+            events.push(Event::Source {
+                text: source[*pos..to].to_string(),
+            });
+            *pos = to;
+        }
+    };
+
+    for span in &spans {
+        let start = span.start as usize;
+        while let Some(&end) = open.last() {
+            if end > start {
+                break;
+            }
+            text(&mut events, &mut pos, end);
+            events.push(Event::End);
+            open.pop();
+        }
+        let end = (span.end as usize).min(open.last().copied().unwrap_or(source.len()));
+        if end <= start {
+            continue;
+        }
+        text(&mut events, &mut pos, start);
+        events.push(Event::Start {
+            name: span.capture.to_string(),
+        });
+        open.push(end);
+    }
+    while let Some(end) = open.pop() {
+        text(&mut events, &mut pos, end);
+        events.push(Event::End);
+    }
+    text(&mut events, &mut pos, source.len());
+    events
+}
+
+/// Whether the highlight name `expected` matches `capture`: it is the
+/// capture or a prefix of it ending at a `.`.
+pub fn highlight_matches(capture: &str, expected: &str) -> bool {
+    capture
+        .strip_prefix(expected)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+/// Assert that each of `expected` highlights something in `events`.
+///
+/// `context` starts the panic message, to tell assertions apart.
+#[track_caller]
+pub fn assert_has_highlights(events: &[Event], expected: &[&str], context: &str) {
+    let found: BTreeSet<&str> = events
+        .iter()
+        .filter_map(|e| match e {
+            Event::Start { name } => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    for expected in expected {
+        assert!(
+            found.iter().any(|name| highlight_matches(name, expected)),
+            "{context}: Expected highlight '{expected}' not found. Found: {found:?}"
+        );
+    }
+}
+
+/// Assert that source text containing `text` is highlighted as `highlight`,
+/// possibly among other highlights around it.
+///
+/// `context` starts the panic message, to tell assertions apart.
+#[track_caller]
+pub fn assert_text_highlighted(events: &[Event], text: &str, highlight: &str, context: &str) {
+    let mut open: Vec<&str> = Vec::new();
+    let mut found = false;
+
+    for event in events {
+        match event {
+            Event::Start { name } => open.push(name),
+            Event::End => {
+                open.pop();
+            }
+            Event::Source { text: source } => {
+                if source.contains(text)
+                    && open.iter().any(|name| highlight_matches(name, highlight))
+                {
+                    found = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    assert!(
+        found,
+        "{context}: Text '{text}' should be highlighted as '{highlight}'.\n{}",
+        format_events(events)
+    );
+}
+
+/// Format events for snapshot tests and failure messages: one line per
+/// piece of source text, followed by the highlights on it, outermost first.
+///
+/// ```text
+/// "<"          punctuation.bracket
+/// "script"     tag
+/// ">"          punctuation.bracket
+/// "let"        keyword
+/// " x = "
+/// "1"          number
+/// ```
+pub fn format_events(events: &[Event]) -> String {
+    let mut out = String::new();
+    let mut open: Vec<&str> = Vec::new();
+    for event in events {
+        match event {
+            Event::Start { name } => open.push(name),
+            Event::End => {
+                open.pop();
+            }
+            Event::Source { text } => {
+                let text = format!("{text:?}");
+                if open.is_empty() {
+                    let _ = writeln!(out, "{text}");
+                } else {
+                    let _ = writeln!(out, "{text:<12} {}", open.join(" > "));
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: u32, end: u32, capture: &'static str) -> Span {
+        Span {
+            start,
+            end,
+            capture: capture.into(),
+        }
+    }
+
+    fn source(text: &str) -> Event {
+        Event::Source {
+            text: text.to_string(),
+        }
+    }
+
+    fn start(name: &str) -> Event {
+        Event::Start {
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_spans_to_events_nests_spans() {
+        let events = spans_to_events(
+            "let s = \"a\\n\";",
+            vec![
+                span(13, 14, "punctuation"),
+                span(0, 3, "keyword"),
+                span(10, 12, "string.escape"),
+                span(8, 13, "string"),
+            ],
+        );
+        assert_eq!(
+            events,
+            vec![
+                start("keyword"),
+                source("let"),
+                Event::End,
+                source(" s = "),
+                start("string"),
+                source("\"a"),
+                start("string.escape"),
+                source("\\n"),
+                Event::End,
+                source("\""),
+                Event::End,
+                start("punctuation"),
+                source(";"),
+                Event::End,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spans_to_events_cuts_crossing_spans() {
+        let events = spans_to_events("abcdef", vec![span(0, 4, "outer"), span(2, 6, "inner")]);
+        assert_eq!(
+            events,
+            vec![
+                start("outer"),
+                source("ab"),
+                start("inner"),
+                source("cd"),
+                Event::End,
+                Event::End,
+                source("ef"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assertions_match_capture_prefixes() {
+        let events = spans_to_events("return x", vec![span(0, 6, "keyword.return")]);
+        assert_has_highlights(&events, &["keyword"], "prefix");
+        assert_text_highlighted(&events, "return", "keyword", "prefix");
+        assert!(!highlight_matches("keywords", "keyword"));
+        assert_eq!(
+            format_events(&events),
+            "\"return\"     keyword.return\n\" x\"\n"
+        );
+    }
+}
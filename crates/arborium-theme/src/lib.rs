@@ -12,8 +12,11 @@
 //! The crate provides a unified system for mapping the many capture names from
 //! various sources (nvim-treesitter, helix, etc.) to a small set of theme slots.
 //! See [`highlights::capture_to_slot`] and [`highlights::tag_for_capture`] for details.
+//!
+//! The capture vocabulary itself lives in the dependency-free
+//! `arborium-captures` crate, re-exported here as [`highlights`].
 
-pub mod highlights;
+pub use arborium_captures as highlights;
 pub mod theme;
 
 pub use highlights::{
@@ -316,6 +316,20 @@ impl Theme {
     /// Uses CSS nesting for compact output. The selector_prefix is prepended
     /// to scope the rules (e.g., `[data-theme="mocha"]`).
     pub fn to_css(&self, selector_prefix: &str) -> String {
+        self.write_css(selector_prefix, |_| true)
+    }
+
+    /// Generate CSS for this theme, restricted to the given element tags.
+    ///
+    /// Only `a-{tag}` rules whose tag is in `tags` are emitted; the base
+    /// background/foreground variables are always included. Pair this with
+    /// [`tag_for_capture`](crate::highlights::tag_for_capture) to ship only
+    /// the rules a page's languages can produce.
+    pub fn to_css_for_tags(&self, selector_prefix: &str, tags: &[&str]) -> String {
+        self.write_css(selector_prefix, |tag| tags.contains(&tag))
+    }
+
+    fn write_css(&self, selector_prefix: &str, keep_tag: impl Fn(&str) -> bool) -> String {
         use crate::highlights::HIGHLIGHTS;
         use std::collections::HashMap;
 
@@ -382,6 +396,9 @@ impl Theme {
             if def.tag.is_empty() || emitted_tags.contains(def.tag) {
                 continue; // Skip categories like "none" that have no tag, or already emitted tags
             }
+            if !keep_tag(def.tag) {
+                continue;
+            }
 
             // Use own style, or fall back to parent style
             let style = if !self.styles[i].is_empty() {
@@ -665,4 +682,14 @@ mod tests {
         assert_eq!(Color::new(255, 0, 0).to_hex(), "#ff0000");
         assert_eq!(Color::new(0, 255, 0).to_hex(), "#00ff00");
     }
+
+    #[test]
+    fn test_to_css_for_tags() {
+        let theme = builtin::catppuccin_mocha();
+        let css = theme.to_css_for_tags("pre", &["k"]);
+
+        assert!(css.contains("a-k {"));
+        assert!(!css.contains("a-s {"));
+        assert!(css.contains("--bg:"));
+    }
 }
@@ -603,6 +603,22 @@ extern "C" {
     #[doc = " Delete a query, freeing all of the memory that it used."]
     pub fn ts_query_delete(self_: *mut TSQuery);
 }
+extern "C" {
+    #[doc = " Write a compiled query to `buffer` as a flat sequence of bytes, which\n [`ts_query_deserialize`] turns back into a query without recompiling it.\n\n Returns the number of bytes the query takes, which may be more than\n `capacity`: in that case nothing useful was written, and the call has to\n be repeated with a buffer that large. Pass a `NULL` buffer to get the size\n alone. Returns zero if the query is too large to serialize.\n\n Serialized queries can only be read back by the same version of this\n library, for the same language."]
+    pub fn ts_query_serialize(
+        self_: *const TSQuery,
+        buffer: *mut ::core::ffi::c_char,
+        capacity: u32,
+    ) -> u32;
+}
+extern "C" {
+    #[doc = " Read a query written by [`ts_query_serialize`].\n\n Returns `NULL` if the buffer was written by a different version of this\n library or for a language with different symbols or fields, or if it is\n truncated or corrupted. The buffer can be freed once this returns."]
+    pub fn ts_query_deserialize(
+        language: *const TSLanguage,
+        buffer: *const ::core::ffi::c_char,
+        length: u32,
+    ) -> *mut TSQuery;
+}
 extern "C" {
     #[doc = " Get the number of patterns, captures, or string literals in the query."]
     pub fn ts_query_pattern_count(self_: *const TSQuery) -> u32;
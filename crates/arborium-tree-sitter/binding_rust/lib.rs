@@ -2803,6 +2803,73 @@ impl Query {
         Ok(result)
     }
 
+    /// Serialize the compiled query, so that [`Query::from_bytes`] can load it
+    /// again without recompiling the source.
+    ///
+    /// The bytes can only be loaded by the same version of this crate, for the
+    /// same language.
+    #[doc(alias = "ts_query_serialize")]
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let length = unsafe { ffi::ts_query_serialize(self.ptr.as_ptr(), ptr::null_mut(), 0) };
+        let mut bytes = Vec::new();
+        bytes.resize(length as usize, 0);
+        unsafe {
+            ffi::ts_query_serialize(
+                self.ptr.as_ptr(),
+                bytes.as_mut_ptr().cast::<c_char>(),
+                length,
+            )
+        };
+        bytes
+    }
+
+    /// Load a query serialized with [`Query::to_bytes`].
+    ///
+    /// `source` has to be the source the query was compiled from; it is only
+    /// used for error positions of predicates. Returns `None` if the bytes
+    /// were written by another version of this crate or for a different
+    /// language, or if they are truncated or corrupted, in which case the
+    /// caller should fall back to [`Query::new`].
+    #[doc(alias = "ts_query_deserialize")]
+    #[must_use]
+    pub fn from_bytes(language: &Language, bytes: &[u8], source: &str) -> Option<Self> {
+        let length = u32::try_from(bytes.len()).ok()?;
+        let ptr = unsafe {
+            ffi::ts_query_deserialize(language.0, bytes.as_ptr().cast::<c_char>(), length)
+        };
+        if ptr.is_null() {
+            return None;
+        }
+
+        // `from_raw_parts` takes capture names and strings to be UTF-8, as
+        // they are when compiled from a `&str`
+        let names_are_utf8 = unsafe {
+            let capture_count = ffi::ts_query_capture_count(ptr);
+            let string_count = ffi::ts_query_string_count(ptr);
+            let is_utf8 = |name: *const c_char, length: u32| {
+                str::from_utf8(slice::from_raw_parts(name.cast::<u8>(), length as usize)).is_ok()
+            };
+            (0..capture_count).all(|i| {
+                let mut length = 0u32;
+                let name =
+                    ffi::ts_query_capture_name_for_id(ptr, i, core::ptr::addr_of_mut!(length));
+                is_utf8(name, length)
+            }) && (0..string_count).all(|i| {
+                let mut length = 0u32;
+                let value =
+                    ffi::ts_query_string_value_for_id(ptr, i, core::ptr::addr_of_mut!(length));
+                is_utf8(value, length)
+            })
+        };
+        if !names_are_utf8 {
+            unsafe { ffi::ts_query_delete(ptr) };
+            return None;
+        }
+
+        unsafe { Self::from_raw_parts(ptr, source) }.ok()
+    }
+
     /// Get the byte offset where the given pattern starts in the query's
     /// source.
     #[doc(alias = "ts_query_start_byte_for_pattern")]
@@ -922,6 +922,29 @@ TSQuery *ts_query_new(
  */
 void ts_query_delete(TSQuery *self);
 
+/**
+ * Write a compiled query to `buffer` as a flat sequence of bytes, which
+ * [`ts_query_deserialize`] turns back into a query without recompiling it.
+ *
+ * Returns the number of bytes the query takes, which may be more than
+ * `capacity`: in that case nothing useful was written, and the call has to
+ * be repeated with a buffer that large. Pass a `NULL` buffer to get the size
+ * alone. Returns zero if the query is too large to serialize.
+ *
+ * Serialized queries can only be read back by the same version of this
+ * library, for the same language.
+ */
+uint32_t ts_query_serialize(const TSQuery *self, char *buffer, uint32_t capacity);
+
+/**
+ * Read a query written by [`ts_query_serialize`].
+ *
+ * Returns `NULL` if the buffer was written by a different version of this
+ * library or for a language with different symbols or fields, or if it is
+ * truncated or corrupted. The buffer can be freed once this returns.
+ */
+TSQuery *ts_query_deserialize(const TSLanguage *language, const char *buffer, uint32_t length);
+
 /**
  * Get the number of patterns, captures, or string literals in the query.
  */
@@ -2931,6 +2931,289 @@ void ts_query_delete(TSQuery *self) {
   }
 }
 
+/*
+ * Serialization - A compiled query can be written out as a flat buffer and
+ * read back in with `ts_query_deserialize`, which is much faster than parsing
+ * and analyzing the query source again. Buffers are only meant to be read by
+ * the same build of the library that wrote them: they hold the query's
+ * internal arrays as they are laid out in memory, and the symbol and field
+ * ids of the language they were compiled for.
+ */
+
+static const uint32_t QUERY_SERIALIZATION_MAGIC = 0x51535354; // "TSSQ"
+static const uint32_t QUERY_SERIALIZATION_VERSION = 1;
+
+typedef struct {
+  char *buffer;
+  uint64_t capacity;
+  uint64_t size;
+  uint32_t checksum;
+} QueryWriter;
+
+typedef struct {
+  const char *data;
+  uint64_t length;
+  uint64_t offset;
+  uint32_t checksum;
+} QueryReader;
+
+// FNV-1a, to tell truncated or corrupted buffers apart from valid ones.
+static uint32_t ts_query__checksum(uint32_t hash, const char *data, uint64_t length) {
+  for (uint64_t i = 0; i < length; i++) {
+    hash ^= (uint8_t)data[i];
+    hash *= 16777619;
+  }
+  return hash;
+}
+
+static void ts_query__write(QueryWriter *self, const void *data, uint64_t length) {
+  if (length == 0) return;
+  self->checksum = ts_query__checksum(self->checksum, data, length);
+  if (self->size + length <= self->capacity) {
+    memcpy(self->buffer + self->size, data, length);
+  }
+  self->size += length;
+}
+
+static void ts_query__write_u32(QueryWriter *self, uint32_t value) {
+  ts_query__write(self, &value, sizeof(value));
+}
+
+static void ts_query__write_elements(QueryWriter *self, const Array *array, size_t element_size) {
+  ts_query__write_u32(self, array->size);
+  ts_query__write(self, array->contents, (uint64_t)array->size * element_size);
+}
+
+#define ts_query__write_array(self, array) \
+  ts_query__write_elements(self, (const Array *)(array), array_elem_size(array))
+
+static bool ts_query__read(QueryReader *self, void *out, uint64_t length) {
+  if (length > self->length - self->offset) return false;
+  if (length == 0) return true;
+  memcpy(out, self->data + self->offset, length);
+  self->checksum = ts_query__checksum(self->checksum, self->data + self->offset, length);
+  self->offset += length;
+  return true;
+}
+
+static bool ts_query__read_u32(QueryReader *self, uint32_t *value) {
+  return ts_query__read(self, value, sizeof(*value));
+}
+
+static bool ts_query__read_elements(QueryReader *self, Array *array, size_t element_size) {
+  uint32_t size;
+  if (!ts_query__read_u32(self, &size)) return false;
+  if ((uint64_t)size * element_size > self->length - self->offset) return false;
+  _array__reserve(array, element_size, size);
+  if (!ts_query__read(self, array->contents, (uint64_t)size * element_size)) return false;
+  array->size = size;
+  return true;
+}
+
+#define ts_query__read_array(self, array) \
+  ts_query__read_elements(self, (Array *)(array), array_elem_size(array))
+
+static void ts_query__write_header(QueryWriter *self, const TSLanguage *language) {
+  ts_query__write_u32(self, QUERY_SERIALIZATION_MAGIC);
+  ts_query__write_u32(self, QUERY_SERIALIZATION_VERSION);
+  ts_query__write_u32(self, sizeof(QueryStep));
+  ts_query__write_u32(self, sizeof(PatternEntry));
+  ts_query__write_u32(self, sizeof(QueryPattern));
+  ts_query__write_u32(self, sizeof(StepOffset));
+  ts_query__write_u32(self, sizeof(TSQueryPredicateStep));
+  ts_query__write_u32(self, ts_language_abi_version(language));
+  ts_query__write_u32(self, ts_language_symbol_count(language));
+  ts_query__write_u32(self, ts_language_field_count(language));
+}
+
+uint32_t ts_query_serialize(const TSQuery *self, char *buffer, uint32_t capacity) {
+  QueryWriter writer = {
+    .buffer = buffer,
+    .capacity = buffer ? capacity : 0,
+    .size = 0,
+    .checksum = 2166136261u,
+  };
+  ts_query__write_header(&writer, self->language);
+  ts_query__write_u32(&writer, self->wildcard_root_pattern_count);
+  ts_query__write_array(&writer, &self->captures.characters);
+  ts_query__write_array(&writer, &self->captures.slices);
+  ts_query__write_array(&writer, &self->predicate_values.characters);
+  ts_query__write_array(&writer, &self->predicate_values.slices);
+  ts_query__write_u32(&writer, self->capture_quantifiers.size);
+  for (uint32_t i = 0; i < self->capture_quantifiers.size; i++) {
+    ts_query__write_array(&writer, &self->capture_quantifiers.contents[i]);
+  }
+  ts_query__write_array(&writer, &self->steps);
+  ts_query__write_array(&writer, &self->pattern_map);
+  ts_query__write_array(&writer, &self->predicate_steps);
+  ts_query__write_array(&writer, &self->patterns);
+  ts_query__write_array(&writer, &self->step_offsets);
+  ts_query__write_array(&writer, &self->negated_fields);
+  ts_query__write_array(&writer, &self->repeat_symbols_with_rootless_patterns);
+
+  uint32_t checksum = writer.checksum;
+  ts_query__write_u32(&writer, checksum);
+  return writer.size > UINT32_MAX ? 0 : (uint32_t)writer.size;
+}
+
+static bool ts_query__slice_is_valid(Slice slice, uint32_t size) {
+  return slice.offset <= size && slice.length <= size - slice.offset;
+}
+
+static bool ts_query__symbol_table_is_valid(const SymbolTable *self) {
+  for (uint32_t i = 0; i < self->slices.size; i++) {
+    if (!ts_query__slice_is_valid(self->slices.contents[i], self->characters.size)) return false;
+  }
+  return true;
+}
+
+// Check that the indices between the query's arrays stay in bounds, so
+// that a buffer that passes its checksum by accident can't cause reads
+// outside of them.
+static bool ts_query__is_valid(const TSQuery *self) {
+  uint32_t capture_count = self->captures.slices.size;
+  uint32_t step_count = self->steps.size;
+  if (
+    !ts_query__symbol_table_is_valid(&self->captures) ||
+    !ts_query__symbol_table_is_valid(&self->predicate_values) ||
+    self->capture_quantifiers.size != self->patterns.size ||
+    self->negated_fields.size == 0 ||
+    self->negated_fields.contents[self->negated_fields.size - 1] != 0
+  ) return false;
+
+  for (uint32_t i = 0; i < self->capture_quantifiers.size; i++) {
+    if (self->capture_quantifiers.contents[i].size > capture_count) return false;
+  }
+  for (uint32_t i = 0; i < step_count; i++) {
+    const QueryStep *step = &self->steps.contents[i];
+    for (unsigned j = 0; j < MAX_STEP_CAPTURE_COUNT; j++) {
+      if (step->capture_ids[j] != NONE && step->capture_ids[j] >= capture_count) return false;
+    }
+    if (step->alternative_index != NONE && step->alternative_index >= step_count) return false;
+    if (step->negated_field_list_id >= self->negated_fields.size) return false;
+  }
+  for (uint32_t i = 0; i < self->patterns.size; i++) {
+    const QueryPattern *pattern = &self->patterns.contents[i];
+    if (
+      pattern->steps.length == 0 ||
+      !ts_query__slice_is_valid(pattern->steps, step_count) ||
+      !ts_query__slice_is_valid(pattern->predicate_steps, self->predicate_steps.size)
+    ) return false;
+    uint32_t last_step = pattern->steps.offset + pattern->steps.length - 1;
+    if (self->steps.contents[last_step].depth != PATTERN_DONE_MARKER) return false;
+  }
+  for (uint32_t i = 0; i < self->predicate_steps.size; i++) {
+    const TSQueryPredicateStep *step = &self->predicate_steps.contents[i];
+    if (
+      (step->type == TSQueryPredicateStepTypeCapture && step->value_id >= capture_count) ||
+      (step->type == TSQueryPredicateStepTypeString &&
+        step->value_id >= self->predicate_values.slices.size) ||
+      step->type > TSQueryPredicateStepTypeString
+    ) return false;
+  }
+  for (uint32_t i = 0; i < self->pattern_map.size; i++) {
+    const PatternEntry *entry = &self->pattern_map.contents[i];
+    if (entry->step_index >= step_count || entry->pattern_index >= self->patterns.size) {
+      return false;
+    }
+  }
+  for (uint32_t i = 0; i < self->step_offsets.size; i++) {
+    if (self->step_offsets.contents[i].step_index >= step_count) return false;
+  }
+  return step_count > 0 && self->steps.contents[step_count - 1].depth == PATTERN_DONE_MARKER;
+}
+
+TSQuery *ts_query_deserialize(const TSLanguage *language, const char *buffer, uint32_t length) {
+  if (
+    !language ||
+    !buffer ||
+    language->abi_version > TREE_SITTER_LANGUAGE_VERSION ||
+    language->abi_version < TREE_SITTER_MIN_COMPATIBLE_LANGUAGE_VERSION
+  ) {
+    return NULL;
+  }
+
+  // The header has to match the one this build writes for `language`.
+  char expected_header[10 * sizeof(uint32_t)];
+  QueryWriter header_writer = {
+    .buffer = expected_header,
+    .capacity = sizeof(expected_header),
+    .size = 0,
+    .checksum = 2166136261u,
+  };
+  ts_query__write_header(&header_writer, language);
+  QueryReader reader = {
+    .data = buffer,
+    .length = length,
+    .offset = 0,
+    .checksum = 2166136261u,
+  };
+  char header[sizeof(expected_header)];
+  if (
+    !ts_query__read(&reader, header, sizeof(header)) ||
+    memcmp(header, expected_header, sizeof(header)) != 0
+  ) {
+    return NULL;
+  }
+
+  TSQuery *self = ts_malloc(sizeof(TSQuery));
+  *self = (TSQuery) {
+    .steps = array_new(),
+    .pattern_map = array_new(),
+    .captures = symbol_table_new(),
+    .capture_quantifiers = array_new(),
+    .predicate_values = symbol_table_new(),
+    .predicate_steps = array_new(),
+    .patterns = array_new(),
+    .step_offsets = array_new(),
+    .string_buffer = array_new(),
+    .negated_fields = array_new(),
+    .repeat_symbols_with_rootless_patterns = array_new(),
+    .wildcard_root_pattern_count = 0,
+    .language = ts_language_copy(language),
+  };
+
+  uint32_t wildcard_root_pattern_count = 0;
+  uint32_t pattern_count = 0;
+  bool ok =
+    ts_query__read_u32(&reader, &wildcard_root_pattern_count) &&
+    wildcard_root_pattern_count <= UINT16_MAX &&
+    ts_query__read_array(&reader, &self->captures.characters) &&
+    ts_query__read_array(&reader, &self->captures.slices) &&
+    ts_query__read_array(&reader, &self->predicate_values.characters) &&
+    ts_query__read_array(&reader, &self->predicate_values.slices) &&
+    ts_query__read_u32(&reader, &pattern_count) &&
+    (uint64_t)pattern_count * sizeof(uint32_t) <= reader.length - reader.offset;
+  for (uint32_t i = 0; ok && i < pattern_count; i++) {
+    CaptureQuantifiers capture_quantifiers = capture_quantifiers_new();
+    ok = ts_query__read_array(&reader, &capture_quantifiers);
+    array_push(&self->capture_quantifiers, capture_quantifiers);
+  }
+  ok = ok &&
+    ts_query__read_array(&reader, &self->steps) &&
+    ts_query__read_array(&reader, &self->pattern_map) &&
+    ts_query__read_array(&reader, &self->predicate_steps) &&
+    ts_query__read_array(&reader, &self->patterns) &&
+    ts_query__read_array(&reader, &self->step_offsets) &&
+    ts_query__read_array(&reader, &self->negated_fields) &&
+    ts_query__read_array(&reader, &self->repeat_symbols_with_rootless_patterns);
+
+  uint32_t expected_checksum = reader.checksum;
+  uint32_t checksum = 0;
+  ok = ok &&
+    ts_query__read_u32(&reader, &checksum) &&
+    checksum == expected_checksum &&
+    reader.offset == reader.length &&
+    ts_query__is_valid(self);
+
+  if (!ok) {
+    ts_query_delete(self);
+    return NULL;
+  }
+  self->wildcard_root_pattern_count = (uint16_t)wildcard_root_pattern_count;
+  return self;
+}
+
 uint32_t ts_query_pattern_count(const TSQuery *self) {
   return self->patterns.size;
 }
@@ -0,0 +1,139 @@
+//! JSON encoding of parse results, for hosts without JavaScript.
+//!
+//! Browser hosts get [`ParseResult`]s from plugins as JavaScript objects.
+//! Hosts that run plugins in a plain WebAssembly runtime, like
+//! `cargo xtask difftest` does in wasmtime, get the same shape as JSON text
+//! instead, which plugins can produce without a JSON library.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{ParseError, ParseErrorKind, ParseResult};
+
+impl ParseResult {
+    /// The result as JSON, in the shape serde gives it:
+    /// `{"spans":[{"start":0,"end":2,"capture":"keyword"}],"injections":[]}`.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"spans\":[");
+        for (i, span) in self.spans.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write!(
+                out,
+                "{{\"start\":{},\"end\":{},\"capture\":",
+                span.start, span.end
+            );
+            push_string(&mut out, &span.capture);
+            out.push('}');
+        }
+        out.push_str("],\"injections\":[");
+        for (i, injection) in self.injections.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write!(
+                out,
+                "{{\"start\":{},\"end\":{},\"language\":",
+                injection.start, injection.end
+            );
+            push_string(&mut out, &injection.language);
+            let _ = write!(
+                out,
+                ",\"include_children\":{}}}",
+                injection.include_children
+            );
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+impl ParseError {
+    /// The error as JSON, in the shape serde gives it:
+    /// `{"kind":"NoText","message":"no text set for session"}`.
+    pub fn to_json(&self) -> String {
+        let kind = match self.kind {
+            ParseErrorKind::InvalidSession => "InvalidSession",
+            ParseErrorKind::NoText => "NoText",
+            ParseErrorKind::IncompatibleGrammar => "IncompatibleGrammar",
+            ParseErrorKind::Other => "Other",
+        };
+        let mut out = String::new();
+        let _ = write!(out, "{{\"kind\":\"{kind}\",\"message\":");
+        push_string(&mut out, &self.message);
+        out.push('}');
+        out
+    }
+}
+
+/// Push `s` as a JSON string literal.
+fn push_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Injection, Span};
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn test_parse_result_to_json() {
+        let result = ParseResult {
+            spans: vec![
+                Span {
+                    start: 0,
+                    end: 2,
+                    capture: "keyword".to_string(),
+                },
+                Span {
+                    start: 3,
+                    end: 9,
+                    capture: "string \"quoted\"\n".to_string(),
+                },
+            ],
+            injections: vec![Injection {
+                start: 10,
+                end: 20,
+                language: "css".to_string(),
+                include_children: true,
+            }],
+        };
+        assert_eq!(
+            result.to_json(),
+            r#"{"spans":[{"start":0,"end":2,"capture":"keyword"},{"start":3,"end":9,"capture":"string \"quoted\"\n"}],"injections":[{"start":10,"end":20,"language":"css","include_children":true}]}"#
+        );
+        assert_eq!(
+            ParseResult::empty().to_json(),
+            r#"{"spans":[],"injections":[]}"#
+        );
+    }
+
+    #[test]
+    fn test_parse_error_to_json() {
+        assert_eq!(
+            ParseError::no_text().to_json(),
+            r#"{"kind":"NoText","message":"no text set for session"}"#
+        );
+        assert_eq!(
+            ParseError::new("a\u{1}b").to_json(),
+            r#"{"kind":"Other","message":"a\u0001b"}"#
+        );
+    }
+}
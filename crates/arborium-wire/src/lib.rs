@@ -9,6 +9,23 @@
 //! The `WIRE_VERSION` constant should be checked by both host and plugins
 //! to ensure compatibility. If versions don't match, the host should
 //! reject the plugin with a clear error message.
+//!
+//! # Offsets
+//!
+//! Plugins report span, injection, query capture and symbol offsets as UTF-8
+//! byte offsets, which is what tree-sitter and Rust string slicing use. Hosts that
+//! index text by UTF-16 code units, like JavaScript, convert them with
+//! [`Utf16Offsets`].
+//!
+//! # JSON
+//!
+//! Hosts that run plugins without JavaScript get results as JSON text, with
+//! [`ParseResult::to_json`] and [`ParseError::to_json`].
+//!
+//! # Plugin Index
+//!
+//! A directory of built plugins comes with a [`PluginIndex`] listing them,
+//! so hosts can discover and verify plugins at runtime.
 
 #![no_std]
 
@@ -18,6 +35,13 @@ use alloc::string::String;
 use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 
+mod json;
+mod plugin_index;
+mod utf16;
+
+pub use plugin_index::{PLUGIN_INDEX_FILE, PluginFile, PluginIndex, PluginIndexEntry};
+pub use utf16::Utf16Offsets;
+
 /// Wire protocol version.
 ///
 /// Bump this when making breaking changes to the protocol.
@@ -27,13 +51,13 @@ pub const WIRE_VERSION: u32 = 1;
 /// A span of highlighted text with a capture name.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Span {
-    /// UTF-16 code unit offset where the span starts.
+    /// Offset where the span starts.
     ///
-    /// This is compatible with JavaScript string APIs like `slice()` and `Range`.
+    /// A UTF-8 byte offset as produced by plugins; after
+    /// [`Utf16Offsets::convert`], a UTF-16 code unit offset compatible with
+    /// JavaScript string APIs like `slice()` and `Range`.
     pub start: u32,
-    /// UTF-16 code unit offset where the span ends (exclusive).
-    ///
-    /// This is compatible with JavaScript string APIs like `slice()` and `Range`.
+    /// Offset where the span ends (exclusive), in the same unit as `start`.
     pub end: u32,
     /// The capture name (e.g., "keyword", "function", "string").
     pub capture: String,
@@ -42,9 +66,9 @@ pub struct Span {
 /// An injection point where another language should be parsed.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Injection {
-    /// UTF-16 code unit offset where the injection starts.
+    /// Offset where the injection starts, in the same unit as [`Span::start`].
     pub start: u32,
-    /// UTF-16 code unit offset where the injection ends (exclusive).
+    /// Offset where the injection ends (exclusive).
     pub end: u32,
     /// The language ID to inject (e.g., "javascript", "css").
     pub language: String,
@@ -94,22 +118,192 @@ pub struct Edit {
     pub new_end_col: u32,
 }
 
+/// A match of an ad-hoc tree-sitter query against a session's syntax tree,
+/// as used for structural search and lint rules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryMatch {
+    /// Index of the matching pattern, counting the query's patterns from 0.
+    pub pattern: u32,
+    /// The nodes the pattern captured.
+    pub captures: Vec<QueryCapture>,
+}
+
+/// A node captured by a [`QueryMatch`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueryCapture {
+    /// The capture name, without the `@`.
+    pub name: String,
+    /// The node's kind, like `function_item`.
+    pub kind: String,
+    /// Offset where the node starts, in the same unit as [`Span::start`].
+    pub start: u32,
+    /// Offset where the node ends (exclusive).
+    pub end: u32,
+}
+
+/// A range of text, like a selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Range {
+    /// Offset where the range starts, in the same unit as [`Span::start`].
+    pub start: u32,
+    /// Offset where the range ends (exclusive).
+    pub end: u32,
+}
+
+/// A language's comment delimiters, for toggling comments in editors.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentSyntax {
+    /// Starts a comment running to the end of the line, like `//`.
+    pub line: Option<String>,
+    /// Starts a block comment, like `/*`.
+    pub block_start: Option<String>,
+    /// Ends a block comment, like `*/`.
+    pub block_end: Option<String>,
+}
+
+/// A change that toggles comments: replace `start..end` with `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentEdit {
+    /// Offset where the replaced text starts, in the same unit as [`Span::start`].
+    pub start: u32,
+    /// Offset where the replaced text ends (exclusive).
+    pub end: u32,
+    /// The text to put there, empty for a deletion.
+    pub replacement: String,
+}
+
+/// An opening delimiter and the closing one that goes with it, like `(` and
+/// `)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pair {
+    /// The opening delimiter.
+    pub open: String,
+    /// The closing delimiter.
+    pub close: String,
+}
+
+/// What a plugin's language is and how editors should treat it, from the
+/// language's `arborium.yaml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginInfo {
+    /// The language's id, like `rust`.
+    pub language_id: String,
+    /// The [`WIRE_VERSION`] the plugin was built with.
+    pub wire_version: u32,
+    /// Comment delimiters, for toggling comments.
+    pub comments: CommentSyntax,
+    /// Brackets that editors match and highlight in pairs.
+    pub brackets: Vec<Pair>,
+    /// Pairs that editors close automatically when the opening one is typed:
+    /// the brackets, then others like quotes.
+    pub auto_close_pairs: Vec<Pair>,
+}
+
+/// A run of prose, for spell checkers: a comment, string literal or markup
+/// text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProseRange {
+    /// Offset where the text starts, in the same unit as [`Span::start`].
+    pub start: u32,
+    /// Offset where the text ends (exclusive).
+    pub end: u32,
+    /// `"comment"`, `"string"` or `"markup"`.
+    pub class: String,
+}
+
+/// Lines that can be folded, for code folding in editors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FoldingRange {
+    /// The line the fold starts on (0-based), which stays visible when folded.
+    pub start_row: u32,
+    /// The last line hidden by the fold (inclusive).
+    pub end_row: u32,
+    /// `"region"` for code or `"comment"` for comments, as in LSP.
+    pub kind: String,
+}
+
+/// A definition in the document outline, like a function or a class.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Symbol {
+    /// The defined name.
+    pub name: String,
+    /// The kind of definition, like `"function"` or `"class"`, named after
+    /// LSP's symbol kinds.
+    pub kind: String,
+    /// Offset where the definition starts, in the same unit as [`Span::start`].
+    pub start: u32,
+    /// Offset where the definition ends (exclusive).
+    pub end: u32,
+    /// Offset where the name starts.
+    pub name_start: u32,
+    /// Offset where the name ends (exclusive).
+    pub name_end: u32,
+    /// Definitions nested in this one, like a class's methods.
+    pub children: Vec<Symbol>,
+}
+
 /// Error that can occur during parsing.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ParseError {
+    /// What kind of failure this is, for hosts that handle them differently.
+    #[serde(default)]
+    pub kind: ParseErrorKind,
     /// Error message.
     pub message: String,
 }
 
+/// The kinds of [`ParseError`].
+///
+/// Plugins built before this field existed don't send it; those errors
+/// deserialize as [`ParseErrorKind::Other`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The session id doesn't belong to a live session.
+    InvalidSession,
+    /// The session has no text to parse yet.
+    NoText,
+    /// The plugin's grammar was generated for a tree-sitter ABI that its
+    /// runtime can't load.
+    IncompatibleGrammar,
+    /// Anything else.
+    #[default]
+    Other,
+}
+
 impl ParseError {
-    /// Create a new parse error.
+    /// Create a new parse error of kind [`ParseErrorKind::Other`].
     pub fn new(message: impl Into<String>) -> Self {
+        Self::with_kind(ParseErrorKind::Other, message)
+    }
+
+    /// Create a new parse error of the given kind.
+    pub fn with_kind(kind: ParseErrorKind, message: impl Into<String>) -> Self {
         Self {
+            kind,
             message: message.into(),
         }
     }
+
+    /// The session id doesn't belong to a live session.
+    pub fn invalid_session() -> Self {
+        Self::with_kind(ParseErrorKind::InvalidSession, "invalid session id")
+    }
+
+    /// The session has no text to parse yet.
+    pub fn no_text() -> Self {
+        Self::with_kind(ParseErrorKind::NoText, "no text set for session")
+    }
 }
 
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl core::error::Error for ParseError {}
+
 /// Check if a wire version is compatible with the current version.
 ///
 /// Currently requires exact match. In the future, we might allow
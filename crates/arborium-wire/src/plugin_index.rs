@@ -0,0 +1,137 @@
+//! The index of a directory of built plugins.
+//!
+//! `cargo xtask build --output <dir>` writes each plugin to
+//! `<dir>/<language>/` and lists them in `<dir>/plugins.json`, a
+//! [`PluginIndex`]. Web hosts fetch the index to find out which languages
+//! are available, under which names, and what the files they load should
+//! hash to, instead of hard-coding the list.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::WIRE_VERSION;
+
+/// The name of the index file in a plugin directory.
+pub const PLUGIN_INDEX_FILE: &str = "plugins.json";
+
+/// The plugins in a plugin directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginIndex {
+    /// The [`WIRE_VERSION`] the plugins were built with.
+    pub wire_version: u32,
+    /// The arborium version the plugins were built from.
+    pub version: String,
+    /// The plugins, sorted by language.
+    pub plugins: Vec<PluginIndexEntry>,
+}
+
+/// One plugin in a [`PluginIndex`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginIndexEntry {
+    /// The language id, such as `javascript`.
+    pub language: String,
+    /// Other names the language goes by, file extensions included (`js`,
+    /// `mjs`).
+    pub aliases: Vec<String>,
+    /// The plugin's JavaScript glue, which loads [`wasm`](Self::wasm).
+    pub js: PluginFile,
+    /// The plugin's WebAssembly module.
+    pub wasm: PluginFile,
+    /// The plugin's queries, for plugins that don't embed them in
+    /// [`wasm`](Self::wasm) to keep it small: a JSON object with
+    /// `highlights`, `injections` and `locals` strings, which hosts pass to
+    /// the plugin's `set_queries` before its first parse.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queries: Option<PluginFile>,
+}
+
+/// A file of a plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginFile {
+    /// The path of the file relative to the index, with `/` separators.
+    ///
+    /// Paths don't change between releases (`javascript/grammar.js`), so
+    /// they can be cached by version and checked with [`sha256`](Self::sha256).
+    pub path: String,
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// The SHA-256 of the file, in lowercase hex.
+    pub sha256: String,
+}
+
+impl PluginIndex {
+    /// Whether the plugins speak this crate's wire protocol; see
+    /// [`is_version_compatible`](crate::is_version_compatible).
+    pub fn is_compatible(&self) -> bool {
+        crate::is_version_compatible(self.wire_version)
+    }
+
+    /// The plugin for a language id, alias or file extension, compared
+    /// ignoring ASCII case. A leading `.` on extensions is ignored.
+    pub fn find(&self, name: &str) -> Option<&PluginIndexEntry> {
+        let name = name.strip_prefix('.').unwrap_or(name);
+        self.plugins
+            .iter()
+            .find(|entry| entry.language.eq_ignore_ascii_case(name))
+            .or_else(|| {
+                self.plugins.iter().find(|entry| {
+                    entry
+                        .aliases
+                        .iter()
+                        .any(|alias| alias.eq_ignore_ascii_case(name))
+                })
+            })
+    }
+}
+
+impl Default for PluginIndex {
+    fn default() -> Self {
+        Self {
+            wire_version: WIRE_VERSION,
+            version: String::new(),
+            plugins: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    fn entry(language: &str, aliases: &[&str]) -> PluginIndexEntry {
+        let file = |name: &str| PluginFile {
+            path: alloc::format!("{language}/{name}"),
+            size: 0,
+            sha256: String::new(),
+        };
+        PluginIndexEntry {
+            language: language.to_string(),
+            aliases: aliases.iter().map(|a| a.to_string()).collect(),
+            js: file("grammar.js"),
+            wasm: file("grammar_bg.wasm"),
+            queries: None,
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        let index = PluginIndex {
+            plugins: vec![
+                entry("javascript", &["js", "mjs"]),
+                entry("json", &["jsonc"]),
+            ],
+            ..Default::default()
+        };
+        assert!(index.is_compatible());
+        assert_eq!(index.find("JavaScript").unwrap().language, "javascript");
+        assert_eq!(index.find(".mjs").unwrap().language, "javascript");
+        assert_eq!(
+            index.find("jsonc").unwrap().wasm.path,
+            "json/grammar_bg.wasm"
+        );
+        assert!(index.find("python").is_none());
+    }
+}
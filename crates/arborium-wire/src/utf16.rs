@@ -0,0 +1,268 @@
+//! Converting tree-sitter's UTF-8 byte offsets to UTF-16 code unit offsets.
+//!
+//! Plugins produce byte offsets, which Rust hosts can slice with directly.
+//! JavaScript hosts need UTF-16 offsets instead, and the two differ as soon
+//! as the text contains anything but ASCII: `é` is 2 bytes but 1 code unit,
+//! `变` is 3 bytes but 1 code unit, and `🎉` is 4 bytes but 2 code units (a
+//! surrogate pair). [`Utf16Offsets`] does this conversion for many offsets
+//! into the same text, and the reverse for offsets hosts send to plugins.
+
+use alloc::vec::Vec;
+
+use crate::{ParseResult, QueryMatch, Symbol};
+
+/// Maps UTF-8 byte offsets in a text to UTF-16 code unit offsets.
+///
+/// Building the map is linear in the text; each lookup is a binary search
+/// over the text's non-ASCII characters, so ASCII-only text costs nothing.
+///
+/// Offsets that fall inside a multi-byte character are rounded down to the
+/// start of that character, so a span never ends in the middle of a surrogate
+/// pair. Offsets past the end of the text are clamped to its end.
+#[derive(Debug, Clone)]
+pub struct Utf16Offsets {
+    /// Non-ASCII sequences, in order.
+    sequences: Vec<Sequence>,
+    /// Length of the text in bytes.
+    len: u32,
+}
+
+/// A run of bytes that isn't one byte per code unit.
+#[derive(Debug, Clone, Copy)]
+struct Sequence {
+    /// Byte offset where the sequence starts.
+    start: u32,
+    /// Byte offset where the sequence ends (exclusive).
+    end: u32,
+    /// UTF-16 offset where the sequence ends.
+    utf16_end: u32,
+}
+
+impl Utf16Offsets {
+    /// Build the map for `text`.
+    pub fn new(text: &str) -> Self {
+        Self::from_bytes(text.as_bytes())
+    }
+
+    /// Build the map for `bytes`, which may contain invalid UTF-8.
+    ///
+    /// Invalid sequences count as one U+FFFD replacement character each,
+    /// the way [`String::from_utf8_lossy`](alloc::string::String::from_utf8_lossy)
+    /// and JavaScript's `TextDecoder` decode them.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut sequences = Vec::new();
+        let mut offset = 0usize;
+        let mut shrink = 0usize;
+        let mut push = |start: usize, len: usize, units: usize, shrink: &mut usize| {
+            *shrink += len - units;
+            sequences.push(Sequence {
+                start: start as u32,
+                end: (start + len) as u32,
+                utf16_end: (start + len - *shrink) as u32,
+            });
+        };
+
+        for chunk in bytes.utf8_chunks() {
+            for (i, c) in chunk.valid().char_indices() {
+                if !c.is_ascii() {
+                    push(offset + i, c.len_utf8(), c.len_utf16(), &mut shrink);
+                }
+            }
+            offset += chunk.valid().len();
+            if !chunk.invalid().is_empty() {
+                push(offset, chunk.invalid().len(), 1, &mut shrink);
+                offset += chunk.invalid().len();
+            }
+        }
+
+        Self {
+            sequences,
+            len: bytes.len() as u32,
+        }
+    }
+
+    /// Length of the text in UTF-16 code units.
+    pub fn utf16_len(&self) -> u32 {
+        self.to_utf16(self.len)
+    }
+
+    /// Convert a UTF-8 byte offset to a UTF-16 code unit offset.
+    pub fn to_utf16(&self, byte: u32) -> u32 {
+        let byte = byte.min(self.len);
+        // Sequences ending at or before `byte` are entirely before it.
+        let idx = self.sequences.partition_point(|s| s.end <= byte);
+        let (base_byte, base_utf16) = match idx.checked_sub(1) {
+            Some(prev) => (self.sequences[prev].end, self.sequences[prev].utf16_end),
+            None => (0, 0),
+        };
+        let byte = match self.sequences.get(idx) {
+            // Inside a character: round down to its start.
+            Some(s) if s.start < byte => s.start,
+            _ => byte,
+        };
+        base_utf16 + (byte - base_byte)
+    }
+
+    /// Convert a UTF-16 code unit offset to a UTF-8 byte offset.
+    ///
+    /// The inverse of [`to_utf16`](Self::to_utf16): offsets inside a
+    /// surrogate pair are rounded down to the start of its character, and
+    /// offsets past the end of the text are clamped to its end.
+    pub fn to_byte(&self, utf16: u32) -> u32 {
+        let utf16 = utf16.min(self.utf16_len());
+        // Sequences ending at or before `utf16` are entirely before it.
+        let idx = self.sequences.partition_point(|s| s.utf16_end <= utf16);
+        let (base_byte, base_utf16) = match idx.checked_sub(1) {
+            Some(prev) => (self.sequences[prev].end, self.sequences[prev].utf16_end),
+            None => (0, 0),
+        };
+        let byte = base_byte + (utf16 - base_utf16);
+        match self.sequences.get(idx) {
+            // Inside a character: round down to its start.
+            Some(s) if s.start < byte => s.start,
+            _ => byte,
+        }
+    }
+
+    /// Convert all span and injection offsets in `result` to UTF-16.
+    pub fn convert(&self, result: &mut ParseResult) {
+        for span in &mut result.spans {
+            span.start = self.to_utf16(span.start);
+            span.end = self.to_utf16(span.end);
+        }
+        for injection in &mut result.injections {
+            injection.start = self.to_utf16(injection.start);
+            injection.end = self.to_utf16(injection.end);
+        }
+    }
+
+    /// Convert all offsets in `symbols` and their children to UTF-16.
+    pub fn convert_symbols(&self, symbols: &mut [Symbol]) {
+        for symbol in symbols {
+            symbol.start = self.to_utf16(symbol.start);
+            symbol.end = self.to_utf16(symbol.end);
+            symbol.name_start = self.to_utf16(symbol.name_start);
+            symbol.name_end = self.to_utf16(symbol.name_end);
+            self.convert_symbols(&mut symbol.children);
+        }
+    }
+
+    /// Convert all capture offsets in `matches` to UTF-16.
+    pub fn convert_matches(&self, matches: &mut [QueryMatch]) {
+        for capture in matches.iter_mut().flat_map(|m| &mut m.captures) {
+            capture.start = self.to_utf16(capture.start);
+            capture.end = self.to_utf16(capture.end);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+
+    /// Reference implementation: count code units of every character before `byte`.
+    fn naive(text: &str, byte: u32) -> u32 {
+        text.char_indices()
+            .take_while(|&(i, c)| i + c.len_utf8() <= byte as usize)
+            .map(|(_, c)| c.len_utf16() as u32)
+            .sum()
+    }
+
+    #[test]
+    fn test_ascii() {
+        let map = Utf16Offsets::new("fn main() {}");
+        assert_eq!(map.to_utf16(0), 0);
+        assert_eq!(map.to_utf16(5), 5);
+        assert_eq!(map.to_utf16(12), 12);
+        assert_eq!(map.to_utf16(100), 12);
+    }
+
+    #[test]
+    fn test_surrogate_pairs() {
+        // a(1 byte, 1 unit) 🎉(4, 2) 🎊(4, 2) b(1, 1)
+        let map = Utf16Offsets::new("a🎉🎊b");
+        assert_eq!(map.to_utf16(1), 1);
+        assert_eq!(map.to_utf16(5), 3);
+        assert_eq!(map.to_utf16(9), 5);
+        assert_eq!(map.to_utf16(10), 6);
+        // Never split a surrogate pair.
+        assert_eq!(map.to_utf16(2), 1);
+        assert_eq!(map.to_utf16(4), 1);
+        assert_eq!(map.utf16_len(), 6);
+
+        assert_eq!(map.to_byte(1), 1);
+        assert_eq!(map.to_byte(3), 5);
+        assert_eq!(map.to_byte(6), 10);
+        assert_eq!(map.to_byte(2), 1);
+        assert_eq!(map.to_byte(100), 10);
+    }
+
+    #[test]
+    fn test_cjk_and_bom() {
+        // BOM(3, 1) let(3) space(1) 变(3, 1) 量(3, 1)
+        let map = Utf16Offsets::new("\u{feff}let 变量");
+        assert_eq!(map.to_utf16(3), 1);
+        assert_eq!(map.to_utf16(7), 5);
+        assert_eq!(map.to_utf16(10), 6);
+        assert_eq!(map.to_utf16(13), 7);
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        // a, an invalid byte, a truncated 3-byte sequence, then b.
+        let bytes = b"a\xff\xe5\x8f b";
+        let map = Utf16Offsets::from_bytes(bytes);
+        let lossy = String::from_utf8_lossy(bytes);
+        assert_eq!(map.utf16_len() as usize, lossy.encode_utf16().count());
+        assert_eq!(map.to_utf16(1), 1);
+        assert_eq!(map.to_utf16(2), 2);
+        assert_eq!(map.to_utf16(4), 3);
+        assert_eq!(map.to_utf16(6), 5);
+    }
+
+    #[test]
+    fn test_matches_naive_on_mixed_text() {
+        const PIECES: &[&str] = &[
+            "a",
+            " ",
+            "\n",
+            "é",
+            "变",
+            "🎉",
+            "\u{feff}",
+            "e\u{301}",
+            "👩‍👩‍👧",
+            "𝕏",
+            "\r\n",
+        ];
+
+        // xorshift, so the test is deterministic without extra dependencies
+        let mut state = 0x2545_f491_u32;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state
+        };
+
+        for _ in 0..200 {
+            let mut text = String::new();
+            for _ in 0..next() % 40 {
+                text.push_str(PIECES[next() as usize % PIECES.len()]);
+            }
+            let map = Utf16Offsets::new(&text);
+            assert_eq!(map.utf16_len() as usize, text.encode_utf16().count());
+            for byte in 0..=text.len() as u32 + 2 {
+                assert_eq!(map.to_utf16(byte), naive(&text, byte), "{text:?} at {byte}");
+            }
+            for utf16 in 0..=map.utf16_len() {
+                let byte = map.to_byte(utf16);
+                assert!(text.is_char_boundary(byte as usize), "{text:?} at {utf16}");
+                // Exact, or rounded down from the middle of a surrogate pair
+                let back = naive(&text, byte);
+                assert!(back == utf16 || back + 1 == utf16, "{text:?} at {utf16}");
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Measure parse throughput per language over a corpus of files.
+//!
+//! Run by `cargo xtask perf`, which passes the grammars' samples as the
+//! corpus and compares the results with a committed baseline. By hand:
+//!
+//! ```text
+//! printf 'rust\tlangs/group-birch/rust/def/sample.rs\n' > corpus.tsv
+//! ARBORIUM_BENCH_CORPUS=corpus.tsv cargo bench -p arborium --bench corpus --features lang-rust
+//! ```
+//!
+//! The corpus file has one `<language>\t<path>` line per file. For each
+//! language, this prints `<language>\t<bytes>\t<nanoseconds>`: the size of
+//! its files and the fastest of several passes parsing all of them, which is
+//! less noisy than the mean.
+
+use std::collections::BTreeMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use arborium::GrammarStore;
+use arborium::advanced::ParseContext;
+
+/// Keep measuring a language until this much time has passed...
+const MIN_TIME: Duration = Duration::from_millis(500);
+/// ...and at least this many passes were made.
+const MIN_PASSES: u32 = 5;
+
+fn main() {
+    let corpus_path = std::env::var("ARBORIUM_BENCH_CORPUS")
+        .expect("ARBORIUM_BENCH_CORPUS must name a file of <language>\\t<path> lines");
+    let corpus = std::fs::read_to_string(&corpus_path).expect("failed to read the corpus");
+
+    let mut files: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for line in corpus.lines().filter(|line| !line.trim().is_empty()) {
+        let (language, path) = line
+            .split_once('\t')
+            .unwrap_or_else(|| panic!("corpus line is not <language>\\t<path>: {line}"));
+        let text =
+            std::fs::read_to_string(path).unwrap_or_else(|e| panic!("failed to read {path}: {e}"));
+        files.entry(language).or_default().push(text);
+    }
+
+    let store = GrammarStore::new();
+    for (language, texts) in &files {
+        let Some(grammar) = store.get(language) else {
+            eprintln!("{language}: not compiled in, skipped");
+            continue;
+        };
+        let mut ctx = ParseContext::for_grammar(&grammar).expect("failed to create a parser");
+        let bytes: usize = texts.iter().map(String::len).sum();
+
+        // Warm up caches and the allocator outside the measurement
+        for text in texts {
+            black_box(grammar.parse(&mut ctx, text));
+        }
+
+        let mut fastest = Duration::MAX;
+        let mut passes = 0;
+        let started = Instant::now();
+        while passes < MIN_PASSES || started.elapsed() < MIN_TIME {
+            let pass = Instant::now();
+            for text in texts {
+                black_box(grammar.parse(&mut ctx, text));
+            }
+            fastest = fastest.min(pass.elapsed());
+            passes += 1;
+        }
+
+        println!("{language}\t{bytes}\t{}", fastest.as_nanos());
+    }
+}
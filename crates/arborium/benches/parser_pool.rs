@@ -0,0 +1,59 @@
+//! Compare forking a highlighter per request with and without parser reuse.
+//!
+//! Run with: cargo bench -p arborium --bench parser_pool --features lang-rust
+//!
+//! Every iteration forks a fresh highlighter, highlights a small snippet and
+//! drops it, which is what a server handling one request per task does. With
+//! the default pool, parse contexts are picked up again by the next fork;
+//! with `ParserPool::new(0)`, each fork allocates its own.
+
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use arborium::advanced::ParserPool;
+use arborium::{GrammarStore, Highlighter};
+
+const SOURCE: &str = r#"
+fn main() {
+    let greeting = "Hello, world!";
+    println!("{greeting}");
+}
+"#;
+
+const ITERATIONS: u32 = 10_000;
+const THREADS: usize = 4;
+
+fn main() {
+    let pooled = Arc::new(GrammarStore::new());
+    let unpooled = Arc::new(GrammarStore::with_parser_pool(ParserPool::new(0)));
+
+    for (name, store) in [("pooled", pooled), ("unpooled", unpooled)] {
+        // Compile the grammar outside the measurement
+        store.get("rust").expect("lang-rust feature is required");
+
+        let single = run(&store, 1);
+        let multi = run(&store, THREADS);
+        println!(
+            "{name:>8}: {:>8.2?}/iter on 1 thread, {:>8.2?}/iter on {THREADS} threads",
+            single / ITERATIONS,
+            multi / ITERATIONS,
+        );
+    }
+}
+
+/// Fork, highlight and drop `ITERATIONS` times on each of `threads` threads.
+fn run(store: &Arc<GrammarStore>, threads: usize) -> Duration {
+    let start = Instant::now();
+    std::thread::scope(|s| {
+        for _ in 0..threads {
+            s.spawn(|| {
+                for _ in 0..ITERATIONS {
+                    let mut hl = Highlighter::with_store(store.clone());
+                    black_box(hl.highlight("rust", SOURCE).unwrap());
+                }
+            });
+        }
+    });
+    start.elapsed() / threads as u32
+}
@@ -0,0 +1,297 @@
+//! Playground - a tiny HTTP server for previewing highlighting in the browser
+//!
+//! Run with: cd crates/arborium && cargo run --example serve --features lang-rust,lang-json
+//!
+//! Then open http://127.0.0.1:8000, paste some code and pick a language and theme.
+//! The output re-renders as you type, and once per second while idle.
+//!
+//! Query files are hot-reloaded: whenever a language's `highlights.scm`,
+//! `injections.scm` or `locals.scm` under `langs/group-*/<lang>/def/queries/`
+//! changes on disk, the grammar is recompiled with the new queries on the next
+//! render. No rebuild of the grammar crate needed.
+//!
+//! Options:
+//!
+//! - `--port <PORT>`: port to listen on (default: 8000)
+//! - `--langs <DIR>`: directory holding the `group-*` grammar definitions
+//!   (default: the repository's `langs/` directory)
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use arborium::theme::{Theme, builtin};
+use arborium::{GrammarStore, Highlighter, advanced::html_escape};
+
+const QUERY_FILES: [&str; 3] = ["highlights.scm", "injections.scm", "locals.scm"];
+
+/// Largest request body accepted, so a bogus `Content-Length` can't make us
+/// allocate arbitrary amounts of memory.
+const MAX_BODY_LEN: usize = 1 << 20;
+
+fn main() {
+    let mut port = 8000u16;
+    let mut langs_dir = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../../langs"));
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--port" => {
+                port = args
+                    .next()
+                    .and_then(|p| p.parse().ok())
+                    .expect("--port <PORT>")
+            }
+            "--langs" => langs_dir = args.next().map(PathBuf::from).expect("--langs <DIR>"),
+            other => {
+                eprintln!("Unknown argument: {other}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let listener = TcpListener::bind(("127.0.0.1", port)).expect("failed to bind");
+    println!("Serving on http://127.0.0.1:{port}");
+    println!("Watching queries in {}", langs_dir.display());
+
+    let store = Arc::new(GrammarStore::new());
+    let mut highlighter = Highlighter::with_store(store.clone());
+    let mut watcher = QueryWatcher::new(langs_dir);
+    let themes = builtin::all();
+
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        if let Err(e) = handle(stream, &store, &mut highlighter, &mut watcher, &themes) {
+            eprintln!("Request failed: {e}");
+        }
+    }
+}
+
+fn handle(
+    mut stream: TcpStream,
+    store: &GrammarStore,
+    highlighter: &mut Highlighter,
+    watcher: &mut QueryWatcher,
+    themes: &[Theme],
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    // Request line and headers
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+
+    let mut body = Vec::new();
+    if content_length <= MAX_BODY_LEN {
+        body.resize(content_length, 0);
+        reader.read_exact(&mut body)?;
+    }
+
+    let (status, content_type, response) = match request_line.split_whitespace().next() {
+        _ if content_length > MAX_BODY_LEN => {
+            ("413 Content Too Large", "text/plain", String::new())
+        }
+        Some("GET") => ("200 OK", "text/html", index_page(themes)),
+        Some("POST") => {
+            let form = parse_form(&String::from_utf8_lossy(&body));
+            let field = |name: &str| form.get(name).map(String::as_str).unwrap_or_default();
+            let lang = field("lang");
+            let theme = themes
+                .iter()
+                .find(|t| t.name == field("theme"))
+                .unwrap_or(&themes[0]);
+
+            (
+                "200 OK",
+                "text/html",
+                render(store, highlighter, watcher, theme, lang, field("code")),
+            )
+        }
+        _ => ("405 Method Not Allowed", "text/plain", String::new()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        response.len()
+    )?;
+    stream.flush()
+}
+
+/// Render the output fragment: theme CSS, status line, and highlighted code.
+fn render(
+    store: &GrammarStore,
+    highlighter: &mut Highlighter,
+    watcher: &mut QueryWatcher,
+    theme: &Theme,
+    lang: &str,
+    code: &str,
+) -> String {
+    let status = match watcher.refresh(store, lang) {
+        Ok(Some(path)) => format!("Reloaded queries from {}", path.display()),
+        Ok(None) => String::new(),
+        Err(e) => format!("Query reload failed: {e}"),
+    };
+
+    let body = match highlighter.highlight(lang, code) {
+        Ok(html) => html,
+        Err(e) => format!("<a-er>{}</a-er>", html_escape(&e.to_string())),
+    };
+
+    format!(
+        "<style>{}</style><p class=\"status\">{}</p><pre class=\"code\">{body}</pre>",
+        theme.to_css("#output .code"),
+        html_escape(&status),
+    )
+}
+
+fn index_page(themes: &[Theme]) -> String {
+    let theme_options: String = themes
+        .iter()
+        .map(|t| format!("<option>{}</option>", html_escape(&t.name)))
+        .collect();
+
+    format!(
+        r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>arborium playground</title>
+<style>
+  body {{ font-family: sans-serif; margin: 1rem; display: grid; grid-template-columns: 1fr 1fr; gap: 1rem; }}
+  textarea {{ width: 100%; height: 80vh; font-family: monospace; }}
+  .code {{ padding: 1rem; min-height: 80vh; margin: 0; overflow: auto; }}
+  .status {{ color: #888; min-height: 1.2em; margin: 0 0 0.5rem; }}
+</style>
+</head>
+<body>
+<form id="form">
+  <input name="lang" value="rust" placeholder="language">
+  <select name="theme">{theme_options}</select>
+  <textarea name="code">fn main() {{
+    println!("Hello, world!");
+}}</textarea>
+</form>
+<div id="output"></div>
+<script>
+  const form = document.getElementById("form");
+  const output = document.getElementById("output");
+  let timer;
+  async function render() {{
+    const res = await fetch("/", {{ method: "POST", body: new URLSearchParams(new FormData(form)) }});
+    output.innerHTML = await res.text();
+  }}
+  form.addEventListener("input", () => {{ clearTimeout(timer); timer = setTimeout(render, 150); }});
+  // Poll so query file edits show up without touching the page
+  setInterval(() => {{ if (!document.hidden) render(); }}, 1000);
+  render();
+</script>
+</body>
+</html>
+"#
+    )
+}
+
+/// Decode an `application/x-www-form-urlencoded` body.
+fn parse_form(body: &str) -> HashMap<String, String> {
+    body.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (percent_decode(k), percent_decode(v)))
+        .collect()
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+                match u8::from_str_radix(hex, 16) {
+                    Ok(b) => {
+                        out.push(b);
+                        i += 2;
+                    }
+                    Err(_) => out.push(b'%'),
+                }
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Tracks query files on disk and recompiles grammars when they change.
+struct QueryWatcher {
+    langs_dir: PathBuf,
+    /// Last seen modification time of each language's query files.
+    loaded: HashMap<String, SystemTime>,
+}
+
+impl QueryWatcher {
+    fn new(langs_dir: PathBuf) -> Self {
+        Self {
+            langs_dir,
+            loaded: HashMap::new(),
+        }
+    }
+
+    /// Find `group-*/<lang>/def/queries` for a language.
+    fn queries_dir(&self, lang: &str) -> Option<PathBuf> {
+        std::fs::read_dir(&self.langs_dir)
+            .ok()?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with("group-"))
+            .map(|e| e.path().join(lang).join("def/queries"))
+            .find(|dir| dir.join("highlights.scm").is_file())
+    }
+
+    /// Reload the language's queries if they changed since the last call.
+    ///
+    /// Returns the queries directory if a reload happened.
+    fn refresh(&mut self, store: &GrammarStore, lang: &str) -> Result<Option<PathBuf>, String> {
+        let Some(dir) = self.queries_dir(lang) else {
+            return Ok(None);
+        };
+
+        let modified = QUERY_FILES
+            .iter()
+            .filter_map(|f| std::fs::metadata(dir.join(f)).ok()?.modified().ok())
+            .max()
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+
+        // The compiled-in queries match what's on disk at startup
+        let last = *self.loaded.entry(lang.to_string()).or_insert(modified);
+        if last == modified {
+            return Ok(None);
+        }
+        self.loaded.insert(lang.to_string(), modified);
+
+        let [highlights, injections, locals] = QUERY_FILES.map(|f| read_or_empty(&dir.join(f)));
+        store
+            .set_queries(lang, &highlights, &injections, &locals)
+            .map_err(|e| e.to_string())?;
+        Ok(Some(dir))
+    }
+}
+
+fn read_or_empty(path: &Path) -> String {
+    std::fs::read_to_string(path).unwrap_or_default()
+}
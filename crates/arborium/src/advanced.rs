@@ -12,6 +12,8 @@
 //!
 //! - [`CompiledGrammar`]: Thread-safe compiled queries (share via `Arc`)
 //! - [`ParseContext`]: Per-thread parser state (cheap to create)
+//! - [`ParserPool`]: Idle parse contexts, reused across threads
+//! - [`QueryCache`]: Compiled queries on disk, reused across runs
 //!
 //! # Example: Direct Grammar Usage
 //!
@@ -25,6 +27,7 @@
 //!     highlights_query: &arborium::lang_rust::HIGHLIGHTS_QUERY,
 //!     injections_query: arborium::lang_rust::INJECTIONS_QUERY,
 //!     locals_query: arborium::lang_rust::LOCALS_QUERY,
+//!     textobjects_query: arborium::lang_rust::TEXTOBJECTS_QUERY,
 //! };
 //! let grammar = Arc::new(CompiledGrammar::new(config)?);
 //!
@@ -38,7 +41,8 @@
 
 // Core tree-sitter types
 pub use arborium_highlight::tree_sitter::{
-    CompiledGrammar, GrammarConfig, GrammarError, ParseContext,
+    CaptureExplanation, CompiledGrammar, GrammarConfig, GrammarError, ParseContext, ParserPool,
+    PooledParseContext, QueryCache, QueryKind, RAINBOW_CAPTURES, SexpOptions, check_abi, tree_sexp,
 };
 
 // Data types
@@ -47,7 +51,16 @@ pub use arborium_highlight::{Injection, ParseResult, Span};
 // Low-level rendering utilities
 pub use arborium_highlight::{
     html_escape, spans_to_ansi, spans_to_ansi_with_options, spans_to_html, write_spans_as_html,
+    write_spans_as_html_chunked, write_spans_as_html_fmt,
 };
 
 // ANSI rendering options
 pub use arborium_highlight::AnsiOptions;
+
+// Input that already contains ANSI escapes
+pub use arborium_highlight::{
+    AnsiStyleRun, AnsiText, parse_ansi, spans_to_ansi_merged, strip_ansi,
+};
+
+// Highlighting without a grammar
+pub use arborium_highlight::{generic_spans, is_generic, is_plain_text};
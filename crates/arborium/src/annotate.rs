@@ -0,0 +1,209 @@
+//! Per-line annotations in a gutter column.
+//!
+//! [`Highlighter::highlight_annotated`](crate::Highlighter::highlight_annotated)
+//! renders every line after a gutter holding its [`LineAnnotation`]: who
+//! last changed the line, how long ago, or any other label such as a hit
+//! count or a timing. Each field has its own capture, so pages can style
+//! them apart (`<a-ga>`, `<a-gd>`, `<a-gl>`); themes without styles for them
+//! use the comment colour.
+//!
+//! Lines can also be shaded by a weight, for coverage reports and profiler
+//! heatmaps; see [`LineAnnotation::heat`] and [`HeatShading`].
+
+use std::ops::Range;
+
+use arborium_highlight::{HtmlFormat, Span};
+
+/// Capture for [`LineAnnotation::author`].
+pub(crate) const AUTHOR_CAPTURE: &str = "gutter.author";
+
+/// Capture for [`LineAnnotation::age`].
+pub(crate) const AGE_CAPTURE: &str = "gutter.age";
+
+/// Capture for [`LineAnnotation::label`].
+pub(crate) const LABEL_CAPTURE: &str = "gutter.label";
+
+/// Metadata shown in the gutter next to one line.
+///
+/// Every field is a column of its own, padded to the widest value in the
+/// snippet; a column no line uses is left out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LineAnnotation {
+    /// Who last changed the line, e.g. from `git blame`.
+    pub author: Option<String>,
+    /// When the line was last changed, e.g. `3 months ago`.
+    pub age: Option<String>,
+    /// Any other label, such as a coverage count or a profiling timing.
+    pub label: Option<String>,
+    /// Shade the line's code by a weight from `0.0` to `1.0`, such as the
+    /// share of samples or the hit rate, as set by
+    /// [`Config::heat_shading`](crate::Config::heat_shading).
+    ///
+    /// Values outside that range are clamped; `NaN` leaves the line unshaded.
+    pub heat: Option<f32>,
+}
+
+/// How [`LineAnnotation::heat`] is rendered.
+///
+/// Either way the line's code is wrapped in a `<span>` on top of the syntax
+/// highlighting. Give it `display: inline-block; width: 100%` to shade the
+/// whole line rather than just its text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HeatShading {
+    /// A class from `heat-0` to `heat-10`, the weight in tenths, for the
+    /// page's stylesheet to colour. A prefix from the [`HtmlFormat`] is
+    /// prepended, as in `arb-heat-7`.
+    #[default]
+    Classes,
+    /// An inline `background` mixing the `--heat` CSS variable (red if
+    /// unset) with transparency, by the weight. Needs no stylesheet.
+    InlineStyles,
+}
+
+impl HeatShading {
+    /// The opening tag of the element shading a line by `weight`, or `None`
+    /// for `NaN`. The closing tag is `</span>`.
+    pub(crate) fn open_tag(self, weight: f32, format: &HtmlFormat) -> Option<String> {
+        if weight.is_nan() {
+            return None;
+        }
+        let weight = weight.clamp(0.0, 1.0);
+        Some(match self {
+            HeatShading::Classes => {
+                let level = (weight * 10.0).round() as u8;
+                match format {
+                    HtmlFormat::CustomElementsWithPrefix(prefix)
+                    | HtmlFormat::ClassNamesWithPrefix(prefix) => {
+                        format!("<span class=\"{prefix}-heat-{level}\">")
+                    }
+                    _ => format!("<span class=\"heat-{level}\">"),
+                }
+            }
+            HeatShading::InlineStyles => {
+                let percent = (weight * 100.0).round() as u8;
+                format!(
+                    "<span style=\"background: color-mix(in srgb, var(--heat, #f85149) {percent}%, transparent)\">"
+                )
+            }
+        })
+    }
+}
+
+/// The captures of the gutter columns, in order.
+const COLUMNS: [&str; 3] = [AUTHOR_CAPTURE, AGE_CAPTURE, LABEL_CAPTURE];
+
+impl LineAnnotation {
+    /// The value of the field with the given capture.
+    pub(crate) fn field(&self, capture: &str) -> Option<&str> {
+        match capture {
+            AUTHOR_CAPTURE => self.author.as_deref(),
+            AGE_CAPTURE => self.age.as_deref(),
+            LABEL_CAPTURE => self.label.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// The gutter columns used by `annotations`, as `(capture, width in chars)`.
+pub(crate) fn gutter_columns(annotations: &[LineAnnotation]) -> Vec<(&'static str, usize)> {
+    COLUMNS
+        .into_iter()
+        .filter_map(|capture| {
+            let width = annotations
+                .iter()
+                .filter_map(|annotation| annotation.field(capture))
+                .map(|value| value.chars().count())
+                .max()?;
+            Some((capture, width))
+        })
+        .collect()
+}
+
+/// Split `spans` at the boundaries of `lines` and sort the pieces by line,
+/// relative to the start of their line.
+///
+/// `lines` are the line ranges without line endings, in order.
+pub(crate) fn spans_by_line(spans: Vec<Span>, lines: &[Range<usize>]) -> Vec<Vec<Span>> {
+    let mut by_line = vec![Vec::new(); lines.len()];
+    for span in spans {
+        let (start, end) = (span.start as usize, span.end as usize);
+        let first = lines.partition_point(|line| line.end <= start);
+        for (i, line) in lines.iter().enumerate().skip(first) {
+            if line.start >= end {
+                break;
+            }
+            let (from, to) = (start.max(line.start), end.min(line.end));
+            if from < to {
+                by_line[i].push(Span {
+                    start: (from - line.start) as u32,
+                    end: (to - line.start) as u32,
+                    capture: span.capture.clone(),
+                });
+            }
+        }
+    }
+    by_line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spans_by_line() {
+        // "/* a\n b */\nx"
+        let lines = [0..4, 5..10, 11..12];
+        let spans = vec![Span {
+            start: 0,
+            end: 10,
+            capture: "comment".into(),
+        }];
+        let by_line = spans_by_line(spans, &lines);
+        let ranges: Vec<Vec<(u32, u32)>> = by_line
+            .iter()
+            .map(|spans| spans.iter().map(|s| (s.start, s.end)).collect())
+            .collect();
+        assert_eq!(ranges, vec![vec![(0, 4)], vec![(0, 5)], vec![]]);
+    }
+
+    #[test]
+    fn test_heat_open_tag() {
+        let format = HtmlFormat::ClassNamesWithPrefix("arb".to_string());
+        assert_eq!(
+            HeatShading::Classes.open_tag(0.66, &format).as_deref(),
+            Some("<span class=\"arb-heat-7\">")
+        );
+        assert_eq!(
+            HeatShading::Classes
+                .open_tag(3.0, &HtmlFormat::CustomElements)
+                .as_deref(),
+            Some("<span class=\"heat-10\">")
+        );
+        assert!(
+            HeatShading::InlineStyles
+                .open_tag(0.25, &format)
+                .unwrap()
+                .contains(" 25%, transparent)")
+        );
+        assert_eq!(HeatShading::Classes.open_tag(f32::NAN, &format), None);
+    }
+
+    #[test]
+    fn test_gutter_columns() {
+        let annotations = [
+            LineAnnotation {
+                author: Some("ann".to_string()),
+                ..Default::default()
+            },
+            LineAnnotation {
+                author: Some("bé".to_string()),
+                label: Some("12".to_string()),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(
+            gutter_columns(&annotations),
+            vec![(AUTHOR_CAPTURE, 3), (LABEL_CAPTURE, 2)]
+        );
+    }
+}
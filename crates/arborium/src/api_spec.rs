@@ -0,0 +1,258 @@
+//! OpenAPI and JSON Schema keywords in YAML and JSON documents.
+//!
+//! To the YAML and JSON grammars, `paths:` and `$ref:` are keys like any
+//! other. [`api_spec_keywords`] is a [span filter](crate::Highlighter::add_span_filter)
+//! that recognizes OpenAPI and Swagger documents by their `openapi` or
+//! `swagger` key, and JSON Schemas by their `$schema` key, and turns the
+//! keys the spec defines into `keyword` spans. Keys that are names chosen by
+//! the document's author, like the entries of `properties` or `paths`, are
+//! left alone even if they happen to be spelled like a keyword.
+
+use std::collections::HashSet;
+
+use arborium_highlight::Span;
+
+use crate::mapping_keys::{mapping_keys, set_captures};
+
+/// Keys defined by JSON Schema, including the ones OpenAPI adds to schemas.
+const SCHEMA_KEYWORDS: &[&str] = &[
+    "$anchor",
+    "$comment",
+    "$defs",
+    "$dynamicAnchor",
+    "$dynamicRef",
+    "$id",
+    "$ref",
+    "$schema",
+    "$vocabulary",
+    "additionalItems",
+    "additionalProperties",
+    "allOf",
+    "anyOf",
+    "const",
+    "contains",
+    "default",
+    "definitions",
+    "dependentRequired",
+    "dependentSchemas",
+    "deprecated",
+    "description",
+    "discriminator",
+    "else",
+    "enum",
+    "example",
+    "examples",
+    "exclusiveMaximum",
+    "exclusiveMinimum",
+    "externalDocs",
+    "format",
+    "if",
+    "items",
+    "maxItems",
+    "maxLength",
+    "maxProperties",
+    "maximum",
+    "minItems",
+    "minLength",
+    "minProperties",
+    "minimum",
+    "multipleOf",
+    "not",
+    "nullable",
+    "oneOf",
+    "pattern",
+    "patternProperties",
+    "prefixItems",
+    "properties",
+    "propertyNames",
+    "readOnly",
+    "required",
+    "then",
+    "title",
+    "type",
+    "unevaluatedItems",
+    "unevaluatedProperties",
+    "uniqueItems",
+    "writeOnly",
+    "xml",
+];
+
+/// Keys defined by OpenAPI 3 and Swagger 2, outside of schemas.
+const OPENAPI_KEYWORDS: &[&str] = &[
+    "allowEmptyValue",
+    "allowReserved",
+    "authorizationUrl",
+    "basePath",
+    "bearerFormat",
+    "callbacks",
+    "components",
+    "consumes",
+    "contact",
+    "content",
+    "delete",
+    "email",
+    "encoding",
+    "explode",
+    "externalValue",
+    "flows",
+    "get",
+    "head",
+    "headers",
+    "host",
+    "in",
+    "info",
+    "jsonSchemaDialect",
+    "license",
+    "links",
+    "name",
+    "openIdConnectUrl",
+    "openapi",
+    "operationId",
+    "operationRef",
+    "options",
+    "parameters",
+    "patch",
+    "pathItems",
+    "paths",
+    "post",
+    "produces",
+    "put",
+    "refreshUrl",
+    "requestBodies",
+    "requestBody",
+    "responses",
+    "schema",
+    "schemas",
+    "scheme",
+    "schemes",
+    "scopes",
+    "security",
+    "securityDefinitions",
+    "securitySchemes",
+    "servers",
+    "style",
+    "summary",
+    "swagger",
+    "tags",
+    "termsOfService",
+    "tokenUrl",
+    "trace",
+    "url",
+    "value",
+    "variables",
+    "version",
+    "webhooks",
+];
+
+/// Keys whose values map names of the author's choosing to objects.
+const NAME_MAPS: &[&str] = &[
+    "$defs",
+    "callbacks",
+    "content",
+    "definitions",
+    "dependentSchemas",
+    "encoding",
+    "examples",
+    "headers",
+    "links",
+    "mapping",
+    "parameters",
+    "pathItems",
+    "paths",
+    "patternProperties",
+    "properties",
+    "requestBodies",
+    "responses",
+    "schemas",
+    "scopes",
+    "securityDefinitions",
+    "securitySchemes",
+    "variables",
+    "webhooks",
+];
+
+/// Give OpenAPI and JSON Schema keys `keyword` spans, for YAML and JSON
+/// documents that are one. Other languages and documents are left alone.
+///
+/// Meant for [`Highlighter::add_span_filter`](crate::Highlighter::add_span_filter):
+///
+/// ```rust,ignore
+/// let mut hl = Highlighter::new();
+/// hl.add_span_filter(|spans, language, source| {
+///     arborium::api_spec_keywords(spans, language, source)
+/// });
+/// ```
+pub fn api_spec_keywords(spans: &mut [Span], language: &str, source: &str) {
+    let Some(keys) = mapping_keys(spans, language, source) else {
+        return;
+    };
+
+    let top_level = |name: &str| keys.iter().any(|key| key.top_level && key.text == name);
+    let openapi = top_level("openapi") || top_level("swagger");
+    if !openapi && !top_level("$schema") {
+        return;
+    }
+
+    let mut ranges = HashSet::new();
+    for key in &keys {
+        let defined = SCHEMA_KEYWORDS.contains(&key.text)
+            || (openapi && OPENAPI_KEYWORDS.contains(&key.text));
+        let named = key
+            .parent
+            .is_some_and(|parent| NAME_MAPS.contains(&keys[parent].text));
+        if defined && !named {
+            ranges.insert((spans[key.span].start, spans[key.span].end));
+        }
+    }
+
+    set_captures(spans, &ranges, "keyword");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapping_keys::tests::{captured, keys};
+
+    #[test]
+    fn test_yaml_openapi() {
+        let source = "openapi: 3.1.0\npaths:\n  /pets:\n    get:\n      parameters:\n        \
+                      - name: limit\n          in: query\ncomponents:\n  schemas:\n    \
+                      type:\n      properties:\n        type:\n          type: string\n";
+        let mut spans = keys(source, "property");
+        api_spec_keywords(&mut spans, "yml", source);
+        assert_eq!(
+            captured(&spans, source, "keyword"),
+            [
+                "openapi",
+                "paths",
+                "get",
+                "parameters",
+                "name",
+                "in",
+                "components",
+                "schemas",
+                "properties",
+                "type",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let source = r#"{"$schema": "x", "properties": {"type": {"type": "string"}}, "items": [{"enum": [1]}]}"#;
+        let mut spans = keys(source, "string.special.key");
+        api_spec_keywords(&mut spans, "json", source);
+        assert_eq!(
+            captured(&spans, source, "keyword"),
+            ["$schema", "properties", "type", "items", "enum"]
+        );
+    }
+
+    #[test]
+    fn test_other_documents() {
+        let source = "paths:\n  - a\n";
+        let mut spans = keys(source, "property");
+        api_spec_keywords(&mut spans, "yaml", source);
+        assert!(captured(&spans, source, "keyword").is_empty());
+    }
+}
@@ -0,0 +1,352 @@
+//! `arborium.toml` project configuration.
+//!
+//! The arborium CLI and `arborium-rustdoc` look for an `arborium.toml` in the
+//! working directory, so a project can pin its theme, restrict and alias
+//! languages, and pick an output format in one place. Command-line flags
+//! take precedence over the file.
+//!
+//! ```toml
+//! theme = "tokyo-night"        # a built-in theme, or a path to a Helix-style .toml
+//! fallback = "generic"         # error, plain or generic
+//! comment-tags = true
+//!
+//! [languages]
+//! include = ["rust", "toml", "bash"]  # empty (the default) allows every language
+//! exclude = ["text"]
+//! aliases = { sh = "bash", cargo = "toml" }
+//!
+//! [injections]
+//! max-depth = 1
+//! unknown = "warn"             # skip, embedded, generic or warn
+//!
+//! [output]
+//! mode = "html"                # html or ansi
+//! html-format = "class-names"  # custom-elements or class-names
+//! prefix = "hl"
+//! ```
+//!
+//! The types are plain data, so build tools can also construct them directly:
+//!
+//! ```rust
+//! use arborium::Config;
+//! use arborium::config_file::{FileConfig, InjectionsConfig, LanguagesConfig};
+//!
+//! let file = FileConfig {
+//!     languages: LanguagesConfig {
+//!         aliases: [("sh".to_string(), "bash".to_string())].into(),
+//!         ..Default::default()
+//!     },
+//!     injections: InjectionsConfig {
+//!         max_depth: Some(0),
+//!         ..Default::default()
+//!     },
+//!     ..Default::default()
+//! };
+//! assert_eq!(file.languages.resolve("sh"), Some("bash"));
+//!
+//! let mut config = Config::default();
+//! file.apply(&mut config);
+//! assert_eq!(config.max_injection_depth, 0);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::theme::{Theme, builtin};
+use crate::{Config, Fallback, HtmlFormat, UnknownInjections};
+
+/// The name of the configuration file.
+pub const FILE_NAME: &str = "arborium.toml";
+
+/// The contents of an `arborium.toml`.
+///
+/// Every setting is optional; unset ones leave the tool's defaults alone.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct FileConfig {
+    /// Theme for ANSI output: a built-in theme name such as `tokyo-night`,
+    /// or the path of a Helix-style theme file ending in `.toml`.
+    pub theme: Option<String>,
+    /// What to do with languages arborium has no grammar for.
+    pub fallback: Option<Fallback>,
+    /// Highlight `TODO:`, `FIXME:` and similar tags in comments.
+    pub comment_tags: Option<bool>,
+    /// Which languages to highlight, and what to call them.
+    pub languages: LanguagesConfig,
+    /// Language injection settings.
+    pub injections: InjectionsConfig,
+    /// Output mode and HTML format.
+    pub output: OutputConfig,
+}
+
+/// The `[languages]` table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct LanguagesConfig {
+    /// Only highlight these languages. Empty means every language.
+    pub include: Vec<String>,
+    /// Never highlight these languages.
+    pub exclude: Vec<String>,
+    /// Extra language names, mapped to the language they stand for.
+    pub aliases: BTreeMap<String, String>,
+}
+
+impl LanguagesConfig {
+    /// Apply the aliases to a language name, then the include and exclude
+    /// lists to the result.
+    ///
+    /// Returns `None` if the language shouldn't be highlighted.
+    pub fn resolve<'a>(&'a self, language: &'a str) -> Option<&'a str> {
+        let language = self.aliases.get(language).map_or(language, String::as_str);
+        let listed = |list: &[String]| list.iter().any(|l| l.eq_ignore_ascii_case(language));
+        if listed(&self.exclude) || (!self.include.is_empty() && !listed(&self.include)) {
+            return None;
+        }
+        Some(language)
+    }
+}
+
+/// The `[injections]` table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct InjectionsConfig {
+    /// See [`Config::max_injection_depth`]; `0` disables injections.
+    pub max_depth: Option<u32>,
+    /// What to do with injected code in languages without a grammar.
+    pub unknown: Option<UnknownInjections>,
+}
+
+/// The `[output]` table.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct OutputConfig {
+    /// Whether the CLI writes HTML or ANSI escape sequences.
+    ///
+    /// `arborium-rustdoc` always writes HTML and ignores this.
+    pub mode: Option<OutputMode>,
+    /// Custom elements (`<a-k>`) or class names (`<span class="keyword">`).
+    pub html_format: Option<HtmlFormatKind>,
+    /// Prefix for the custom elements or class names.
+    pub prefix: Option<String>,
+}
+
+impl OutputConfig {
+    /// The [`HtmlFormat`] described by `html-format` and `prefix`, if either
+    /// is set.
+    pub fn to_html_format(&self) -> Option<HtmlFormat> {
+        if self.html_format.is_none() && self.prefix.is_none() {
+            return None;
+        }
+        Some(
+            match (self.html_format.unwrap_or_default(), self.prefix.clone()) {
+                (HtmlFormatKind::CustomElements, None) => HtmlFormat::CustomElements,
+                (HtmlFormatKind::CustomElements, Some(prefix)) => {
+                    HtmlFormat::CustomElementsWithPrefix(prefix)
+                }
+                (HtmlFormatKind::ClassNames, None) => HtmlFormat::ClassNames,
+                (HtmlFormatKind::ClassNames, Some(prefix)) => {
+                    HtmlFormat::ClassNamesWithPrefix(prefix)
+                }
+            },
+        )
+    }
+}
+
+/// What the CLI writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputMode {
+    /// Highlighted HTML.
+    Html,
+    /// ANSI escape sequences for terminals.
+    Ansi,
+}
+
+/// The kind of [`HtmlFormat`], without its prefix.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HtmlFormatKind {
+    /// `<a-k>fn</a-k>`
+    #[default]
+    CustomElements,
+    /// `<span class="keyword">fn</span>`
+    ClassNames,
+}
+
+impl FileConfig {
+    /// Load the `arborium.toml` in `dir`, if there is one.
+    ///
+    /// Only `dir` itself is searched, not its parents.
+    pub fn discover(dir: &Path) -> Result<Option<Self>, Error> {
+        let path = dir.join(FILE_NAME);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        Self::load(&path).map(Some)
+    }
+
+    /// Load a configuration file.
+    ///
+    /// A relative theme path is taken relative to the file's directory.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = fs::read_to_string(path).map_err(|e| invalid(path, e))?;
+        let mut config: Self = toml::from_str(&text).map_err(|e| invalid(path, e))?;
+        if let (Some(theme), Some(dir)) = (&mut config.theme, path.parent())
+            && is_theme_path(theme)
+        {
+            *theme = dir.join(&*theme).to_string_lossy().into_owned();
+        }
+        Ok(config)
+    }
+
+    /// Parse the contents of a configuration file.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        toml::from_str(text).map_err(|e| invalid(Path::new(FILE_NAME), e))
+    }
+
+    /// Apply the settings that map onto a highlighter [`Config`].
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(fallback) = self.fallback {
+            config.fallback = fallback;
+        }
+        if let Some(comment_tags) = self.comment_tags {
+            config.comment_tags = comment_tags;
+        }
+        if let Some(max_depth) = self.injections.max_depth {
+            config.max_injection_depth = max_depth;
+        }
+        if let Some(unknown) = self.injections.unknown {
+            config.unknown_injections = unknown;
+        }
+        if let Some(html_format) = self.output.to_html_format() {
+            config.html_format = html_format;
+        }
+    }
+
+    /// Load the configured theme, if any. See [`find_theme`].
+    pub fn theme(&self) -> Result<Option<Theme>, Error> {
+        self.theme.as_deref().map(find_theme).transpose()
+    }
+}
+
+/// Find a built-in theme by name, or load a theme file if `name` ends in
+/// `.toml`.
+///
+/// Names are matched loosely against the themes' display names:
+/// `tokyo-night`, `tokyo_night` and `Tokyo Night` all work.
+pub fn find_theme(name: &str) -> Result<Theme, Error> {
+    if is_theme_path(name) {
+        let path = Path::new(name);
+        let text = fs::read_to_string(path).map_err(|e| invalid(path, e))?;
+        return Theme::from_toml(&text).map_err(|e| invalid(path, e));
+    }
+    let wanted = normalize_theme_name(name);
+    builtin::all()
+        .into_iter()
+        .find(|theme| normalize_theme_name(&theme.name) == wanted)
+        .ok_or_else(|| Error::UnknownTheme {
+            theme: name.to_string(),
+        })
+}
+
+fn is_theme_path(theme: &str) -> bool {
+    theme.ends_with(".toml")
+}
+
+/// Lowercase, with dashes for spaces and underscores, and without the
+/// accents in "Frappé" and "Rosé Pine".
+fn normalize_theme_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| match c {
+            ' ' | '_' => '-',
+            'é' | 'É' => 'e',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+fn invalid(path: &Path, e: impl std::fmt::Display) -> Error {
+    Error::InvalidConfig {
+        path: PathBuf::from(path),
+        message: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = FileConfig::parse(
+            r#"
+theme = "tokyo-night"
+fallback = "plain"
+
+[languages]
+exclude = ["text"]
+aliases = { sh = "bash" }
+
+[injections]
+max-depth = 1
+unknown = "generic"
+
+[output]
+mode = "ansi"
+html-format = "class-names"
+prefix = "hl"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.output.mode, Some(OutputMode::Ansi));
+        assert_eq!(config.languages.resolve("sh"), Some("bash"));
+        assert_eq!(config.languages.resolve("text"), None);
+        assert_eq!(config.theme().unwrap().unwrap().name, "Tokyo Night");
+
+        let mut highlighter_config = Config::default();
+        config.apply(&mut highlighter_config);
+        assert_eq!(highlighter_config.fallback, Fallback::Plain);
+        assert_eq!(highlighter_config.max_injection_depth, 1);
+        assert_eq!(
+            highlighter_config.unknown_injections,
+            UnknownInjections::Generic
+        );
+        assert_eq!(
+            highlighter_config.html_format,
+            HtmlFormat::ClassNamesWithPrefix("hl".to_string())
+        );
+    }
+
+    #[test]
+    fn test_include_list() {
+        let languages = LanguagesConfig {
+            include: vec!["bash".to_string()],
+            aliases: [("zsh".to_string(), "bash".to_string())].into(),
+            ..Default::default()
+        };
+        assert_eq!(languages.resolve("zsh"), Some("bash"));
+        assert_eq!(languages.resolve("Bash"), Some("Bash"));
+        assert_eq!(languages.resolve("python"), None);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        assert!(matches!(
+            FileConfig::parse("colour = \"red\""),
+            Err(Error::InvalidConfig { .. })
+        ));
+        assert!(matches!(
+            find_theme("no-such-theme"),
+            Err(Error::UnknownTheme { .. })
+        ));
+        assert_eq!(
+            find_theme("catppuccin_frappe").unwrap().name,
+            "Catppuccin Frappé"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Non-fatal problems noticed while highlighting.
+//!
+//! Highlighting degrades gracefully: code in a language without a grammar
+//! is left unhighlighted rather than failing the document, captures a theme
+//! doesn't know go unstyled, and injections nested too deep stay with their
+//! host. Diagnostics say where that happened, for tools that want to report
+//! it; see [`Highlighter::take_diagnostics`](crate::Highlighter::take_diagnostics).
+
+use std::fmt;
+
+/// How many diagnostics a highlighter keeps until they are taken; later
+/// ones are dropped.
+pub(crate) const MAX_DIAGNOSTICS: usize = 256;
+
+/// A non-fatal problem noticed while highlighting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Diagnostic {
+    /// There is no grammar for the language of a highlight call, and
+    /// [`Config::fallback`](crate::Config::fallback) was used instead.
+    MissingLanguage {
+        /// The language, as passed in.
+        language: String,
+    },
+    /// Code injected into `host` is in a language without a grammar, and
+    /// was handled according to [`Config::unknown_injections`](crate::Config::unknown_injections).
+    UnknownInjection {
+        /// The language the code is injected into.
+        host: String,
+        /// The injected language, after the injection language map.
+        language: String,
+    },
+    /// A language's highlights query uses a capture that isn't in arborium's
+    /// table, so themes style it by its longest known prefix, if any.
+    ///
+    /// Reported once per highlighter and language.
+    UnknownCapture {
+        /// The grammar's language.
+        language: String,
+        /// The capture, without the `@`.
+        capture: String,
+    },
+    /// A pattern in a language's highlights query uses a predicate arborium
+    /// doesn't evaluate, so it matches as if the predicate held.
+    ///
+    /// Reported once per highlighter and language.
+    IgnoredPredicate {
+        /// The grammar's language.
+        language: String,
+        /// The 1-based line of the pattern in `highlights.scm`.
+        line: usize,
+        /// The predicate, such as `lua-match?`.
+        predicate: String,
+    },
+    /// Code in `language` had injections left when
+    /// [`Config::max_injection_depth`](crate::Config::max_injection_depth)
+    /// ran out, so they were left to `language`.
+    InjectionDepthExceeded {
+        /// The language the injections are in.
+        language: String,
+        /// How many injections were dropped.
+        injections: usize,
+    },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingLanguage { language } => write!(f, "no grammar for {language}"),
+            Self::UnknownInjection { host, language } => {
+                write!(f, "no grammar for {language} code injected into {host}")
+            }
+            Self::UnknownCapture { language, capture } => {
+                write!(f, "{language}: capture @{capture} is not a known highlight")
+            }
+            Self::IgnoredPredicate {
+                language,
+                line,
+                predicate,
+            } => write!(
+                f,
+                "{language}: highlights.scm:{line}: predicate #{predicate} is not supported"
+            ),
+            Self::InjectionDepthExceeded {
+                language,
+                injections,
+            } => write!(
+                f,
+                "{injections} injections in {language} skipped: maximum injection depth reached"
+            ),
+        }
+    }
+}
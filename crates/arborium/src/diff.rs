@@ -0,0 +1,259 @@
+//! Second highlighting layer for unified diffs.
+//!
+//! The diff grammar only knows about diff structure: file headers, hunk
+//! headers, added and removed lines. To also color the code inside hunks,
+//! every hunk is rebuilt into its old side (context and removed lines) and its
+//! new side (context and added lines), with the one-character line prefixes
+//! stripped. Each side is highlighted as standalone source in the language
+//! detected from the file header, and the resulting spans are mapped back onto
+//! the diff.
+//!
+//! Context lines are part of both sides so each parses with the surrounding
+//! code, but their spans are only taken from the new side.
+
+use arborium_highlight::Span;
+
+use crate::detect_language;
+
+/// One side of a hunk as standalone source.
+pub(crate) struct HunkSide {
+    /// Language detected from the file header.
+    pub(crate) language: &'static str,
+    /// The hunk's lines with their `+`/`-`/` ` prefix stripped.
+    pub(crate) text: String,
+    /// `(offset in text, offset in the diff, length)` for every line whose
+    /// spans map back onto the diff, in order.
+    lines: Vec<(u32, u32, u32)>,
+}
+
+impl HunkSide {
+    fn new(language: &'static str) -> Self {
+        Self {
+            language,
+            text: String::new(),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Append a line's content, which starts at `diff_offset` in the diff.
+    fn push(&mut self, content: &str, diff_offset: usize, mapped: bool) {
+        if mapped {
+            self.lines.push((
+                self.text.len() as u32,
+                diff_offset as u32,
+                content.len() as u32,
+            ));
+        }
+        self.text.push_str(content);
+    }
+
+    /// Map spans over [`text`](Self::text) onto the diff and append them to `out`.
+    ///
+    /// Spans crossing line boundaries (block comments, multi-line strings) are
+    /// split per line, since the lines aren't contiguous in the diff.
+    pub(crate) fn map_spans(&self, spans: Vec<Span>, out: &mut Vec<Span>) {
+        for span in spans {
+            let first = self
+                .lines
+                .partition_point(|&(start, _, len)| start + len <= span.start);
+            for &(start, diff_start, len) in &self.lines[first..] {
+                if start >= span.end {
+                    break;
+                }
+                let from = span.start.max(start);
+                let to = span.end.min(start + len);
+                if from < to {
+                    out.push(Span {
+                        start: diff_start + (from - start),
+                        end: diff_start + (to - start),
+                        capture: span.capture.clone(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Split every hunk of a unified diff into its old and new side.
+///
+/// Hunks of files whose language can't be detected from their path are
+/// skipped, as are hunks with a malformed `@@` header.
+pub(crate) fn hunk_sides(diff: &str) -> Vec<HunkSide> {
+    let mut sides = Vec::new();
+    let mut language = None;
+    // Old and new side of the current hunk, with their remaining line counts
+    let mut hunk: Option<(HunkSide, HunkSide, u32, u32)> = None;
+    let mut offset = 0;
+
+    for line in diff.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        if let Some((old, new, old_left, new_left)) = &mut hunk {
+            // `get`: a line ending the hunk may start with a multi-byte char
+            let (content, content_start) = (line.get(1..).unwrap_or_default(), line_start + 1);
+            let consumed = match line.as_bytes()[0] {
+                b' ' => {
+                    old.push(content, content_start, false);
+                    new.push(content, content_start, true);
+                    *old_left = old_left.saturating_sub(1);
+                    *new_left = new_left.saturating_sub(1);
+                    true
+                }
+                // Some tools strip the space from empty context lines
+                b'\n' | b'\r' => {
+                    old.push(line, line_start, false);
+                    new.push(line, line_start, false);
+                    *old_left = old_left.saturating_sub(1);
+                    *new_left = new_left.saturating_sub(1);
+                    true
+                }
+                b'-' => {
+                    old.push(content, content_start, true);
+                    *old_left = old_left.saturating_sub(1);
+                    true
+                }
+                b'+' => {
+                    new.push(content, content_start, true);
+                    *new_left = new_left.saturating_sub(1);
+                    true
+                }
+                // "\ No newline at end of file"
+                b'\\' => true,
+                _ => false,
+            };
+            if !consumed || (*old_left == 0 && *new_left == 0) {
+                let (old, new, ..) = hunk.take().unwrap();
+                sides.extend([old, new]);
+            }
+            if consumed {
+                continue;
+            }
+        }
+
+        if line.starts_with("@@ ") {
+            hunk = language
+                .zip(hunk_line_counts(line))
+                .map(|(lang, (old_left, new_left))| {
+                    (HunkSide::new(lang), HunkSide::new(lang), old_left, new_left)
+                });
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            language = path_language(path);
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            // A deleted file's new path is /dev/null: keep the old one
+            language = path_language(path).or(language);
+        } else if let Some(paths) = line.strip_prefix("diff ") {
+            language = paths.split_whitespace().last().and_then(detect_language);
+        }
+    }
+
+    if let Some((old, new, ..)) = hunk {
+        sides.extend([old, new]);
+    }
+    sides
+}
+
+/// Parse the old and new line counts out of `@@ -l,s +l,s @@`.
+fn hunk_line_counts(header: &str) -> Option<(u32, u32)> {
+    let mut ranges = header.strip_prefix("@@ ")?.split(' ');
+    let count = |range: Option<&str>, sign: char| -> Option<u32> {
+        let range = range?.strip_prefix(sign)?;
+        match range.split_once(',') {
+            Some((_, count)) => count.parse().ok(),
+            None => range.parse::<u32>().ok().map(|_| 1),
+        }
+    };
+    Some((count(ranges.next(), '-')?, count(ranges.next(), '+')?))
+}
+
+/// Detect the language of a `---`/`+++` header path.
+fn path_language(path: &str) -> Option<&'static str> {
+    // Drop the timestamp `diff -u` appends after a tab
+    let path = path.split('\t').next()?.trim_end();
+    if path == "/dev/null" {
+        return None;
+    }
+    detect_language(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+-    old();
++    new();
+ }
+";
+
+    #[test]
+    fn test_hunk_sides() {
+        let sides = hunk_sides(DIFF);
+        assert_eq!(sides.len(), 2);
+        assert_eq!(sides[0].language, "rust");
+        assert_eq!(sides[0].text, "fn main() {\n    old();\n}\n");
+        assert_eq!(sides[1].text, "fn main() {\n    new();\n}\n");
+    }
+
+    #[test]
+    fn test_map_spans() {
+        let sides = hunk_sides(DIFF);
+        let new = &sides[1];
+        let span = |start, end| Span {
+            start,
+            end,
+            capture: "function".into(),
+        };
+        // `new` on the added line, and a span crossing into the context line
+        let mut out = Vec::new();
+        new.map_spans(vec![span(16, 19), span(21, 24)], &mut out);
+
+        let text = |s: &Span| &DIFF[s.start as usize..s.end as usize];
+        assert_eq!(
+            out.iter().map(text).collect::<Vec<_>>(),
+            ["new", ";\n", "}"]
+        );
+    }
+
+    #[test]
+    fn test_old_side_context_not_mapped() {
+        let sides = hunk_sides(DIFF);
+        let mut out = Vec::new();
+        let all = Span {
+            start: 0,
+            end: sides[0].text.len() as u32,
+            capture: "comment".into(),
+        };
+        sides[0].map_spans(vec![all], &mut out);
+        assert_eq!(out.len(), 1);
+        assert_eq!(
+            &DIFF[out[0].start as usize..out[0].end as usize],
+            "    old();\n"
+        );
+    }
+
+    #[test]
+    fn test_hunk_line_counts() {
+        assert_eq!(hunk_line_counts("@@ -1,3 +1,4 @@ fn main()"), Some((3, 4)));
+        assert_eq!(hunk_line_counts("@@ -1 +1 @@"), Some((1, 1)));
+        assert_eq!(hunk_line_counts("@@ -0,0 +1 @@"), Some((0, 1)));
+        assert_eq!(hunk_line_counts("@@@ -1,2 -1,2 +1,3 @@@"), None);
+    }
+
+    #[test]
+    fn test_unknown_and_deleted_files() {
+        let diff = "--- a/notes.unknownext\n+++ b/notes.unknownext\n@@ -1 +1 @@\n-a\n+b\n\
+                    --- a/gone.py\n+++ /dev/null\n@@ -1 +0,0 @@\n-x = 1\n";
+        let sides = hunk_sides(diff);
+        assert_eq!(sides.len(), 2);
+        assert_eq!(sides[0].language, "python");
+        assert_eq!(sides[0].text, "x = 1\n");
+        assert_eq!(sides[1].text, "");
+    }
+}
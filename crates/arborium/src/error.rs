@@ -2,18 +2,22 @@
 
 use std::fmt;
 use std::io;
+use std::path::PathBuf;
+
+use arborium_highlight::tree_sitter::{GrammarError, QueryKind};
 
 /// Error type for highlighting operations.
 ///
 /// This is marked `#[non_exhaustive]` to allow adding new variants
 /// in future versions without breaking changes.
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
 pub enum Error {
     /// The requested language is not supported.
     ///
     /// This occurs when no grammar is available for the given language name.
     /// Language availability depends on which `lang-*` features are enabled.
+    #[error("unsupported language: {language}")]
     UnsupportedLanguage {
         /// The language that was requested.
         language: String,
@@ -23,6 +27,7 @@ pub enum Error {
     ///
     /// This typically indicates a problem with the grammar or an internal
     /// tree-sitter error.
+    #[error("parse error for {language}: {message}")]
     ParseError {
         /// The language being parsed.
         language: String,
@@ -32,52 +37,112 @@ pub enum Error {
 
     /// An error occurred while compiling a tree-sitter query.
     ///
-    /// This indicates a problem with the grammar's highlight or injection queries.
+    /// This indicates a problem with the grammar's highlight or injection
+    /// queries, for instance ones passed to `GrammarStore::set_queries`.
+    #[error("{query} query error for {language}: {message}")]
     QueryError {
         /// The language whose query failed.
         language: String,
+        /// Which of the language's queries failed.
+        query: QueryKind,
+        /// 0-based row of the error in the query source.
+        row: usize,
+        /// 0-based column of the error in the query source.
+        column: usize,
+        /// Byte offset of the error in the query source.
+        offset: usize,
         /// A description of the query error.
         message: String,
     },
 
+    /// A grammar was generated for a tree-sitter ABI that this build of
+    /// arborium can't load.
+    ///
+    /// Only grammars that aren't compiled in, like those passed to
+    /// `GrammarStore::register_language`, can fail this way. Regenerating
+    /// the grammar with a tree-sitter CLI whose ABI is in `min..=max` fixes
+    /// it.
+    #[error(
+        "{language} grammar uses tree-sitter ABI {found}, but arborium supports ABI {min} \
+         through {max}; regenerate it with a tree-sitter CLI in that range"
+    )]
+    IncompatibleGrammar {
+        /// The language whose grammar was rejected.
+        language: String,
+        /// The grammar's ABI version.
+        found: usize,
+        /// The oldest ABI version arborium supports.
+        min: usize,
+        /// The newest ABI version arborium supports.
+        max: usize,
+    },
+
+    /// The highlight was cancelled before it finished.
+    ///
+    /// Returned by the async highlighting methods when their future is
+    /// dropped or the tokio runtime shuts down mid-highlight.
+    #[error("highlighting was cancelled")]
+    Cancelled,
+
+    /// A theme name didn't match any built-in theme.
+    #[error("unknown theme: {theme}")]
+    UnknownTheme {
+        /// The theme that was requested.
+        theme: String,
+    },
+
+    /// An `arborium.toml` or theme file couldn't be read or parsed.
+    #[error("{}: {message}", .path.display())]
+    InvalidConfig {
+        /// The file at fault.
+        path: PathBuf,
+        /// A description of what went wrong.
+        message: String,
+    },
+
     /// An I/O error occurred during highlighting.
     ///
     /// This typically happens when writing to a `Write` destination fails.
-    Io(io::Error),
-}
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Error::UnsupportedLanguage { language } => {
-                write!(f, "unsupported language: {}", language)
-            }
-            Error::ParseError { language, message } => {
-                write!(f, "parse error for {}: {}", language, message)
-            }
-            Error::QueryError { language, message } => {
-                write!(f, "query error for {}: {}", language, message)
-            }
-            Error::Io(e) => write!(f, "I/O error: {}", e),
-        }
-    }
+    /// Writing to a [`fmt::Write`] destination failed.
+    #[error("formatting error: {0}")]
+    Fmt(#[from] fmt::Error),
 }
 
-impl std::error::Error for Error {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        match self {
-            Error::Io(e) => Some(e),
-            _ => None,
+impl Error {
+    /// Attach the language to an error from compiling or setting up a grammar.
+    pub(crate) fn grammar(language: &str, e: GrammarError) -> Self {
+        match e {
+            GrammarError::LanguageError => Error::ParseError {
+                language: language.to_string(),
+                message: e.to_string(),
+            },
+            GrammarError::IncompatibleAbi { found, min, max } => Error::IncompatibleGrammar {
+                language: language.to_string(),
+                found,
+                min,
+                max,
+            },
+            GrammarError::QueryError {
+                query,
+                row,
+                column,
+                offset,
+                message,
+            } => Error::QueryError {
+                language: language.to_string(),
+                query,
+                row,
+                column,
+                offset,
+                message,
+            },
         }
     }
 }
 
-impl From<io::Error> for Error {
-    fn from(e: io::Error) -> Self {
-        Error::Io(e)
-    }
-}
-
 /// Convert from the internal arborium-highlight error type.
 impl From<arborium_highlight::HighlightError> for Error {
     fn from(e: arborium_highlight::HighlightError) -> Self {
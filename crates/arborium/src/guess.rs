@@ -0,0 +1,375 @@
+//! Content-based language guessing for code without a file name or info string.
+//!
+//! Each language has a list of weighted signals: keywords at the start of a
+//! line, telltale substrings, and how lines tend to end (`;`, `:`, `{`). The
+//! weights of the signals a snippet matches are added up and turned into a
+//! confidence between 0 and 1. Shebangs and diff headers are recognized by
+//! [`detect_language_from_content`] and always win.
+//!
+//! This is deliberately cheap and shallow. It gets typical snippets right,
+//! but short or ambiguous input (`x = 1`) is better left unhighlighted, so
+//! callers should ignore guesses below a threshold.
+
+use crate::GrammarStore;
+use crate::detect_language_from_content;
+
+/// A piece of evidence for a language.
+enum Signal {
+    /// The text starts with this, after leading whitespace.
+    Starts(&'static str),
+    /// Some line starts with this, after indentation.
+    LineStart(&'static str),
+    /// At least a quarter of the non-blank lines end with this.
+    LineEnd(&'static str),
+    /// The text contains this anywhere.
+    Contains(&'static str),
+}
+
+use Signal::*;
+
+/// Signals and their weights, per language.
+const RULES: &[(&str, &[(Signal, f32)])] = &[
+    (
+        "rust",
+        &[
+            (LineStart("fn "), 1.5),
+            (LineStart("pub fn "), 2.0),
+            (LineStart("impl "), 1.5),
+            (LineStart("#["), 1.5),
+            (LineStart("use "), 0.5),
+            (LineStart("mod "), 1.0),
+            (LineStart("struct "), 0.5),
+            (Contains("let mut "), 2.0),
+            (Contains("println!("), 2.0),
+            (Contains("&str"), 1.5),
+            (Contains("Option<"), 1.0),
+            (Contains("Vec<"), 1.0),
+            (Contains("::"), 0.5),
+            (Contains(") -> "), 0.5),
+            (LineEnd(";"), 0.3),
+        ],
+    ),
+    (
+        "python",
+        &[
+            (LineStart("def "), 1.5),
+            (LineStart("import "), 0.7),
+            (LineStart("from "), 0.5),
+            (Contains(" import "), 0.7),
+            (LineStart("elif "), 2.0),
+            (Contains("__init__"), 2.0),
+            (Contains("self."), 0.7),
+            (Contains("print("), 0.5),
+            (Contains("None"), 0.5),
+            (LineStart("@"), 0.3),
+            (LineEnd(":"), 1.0),
+        ],
+    ),
+    (
+        "javascript",
+        &[
+            (Contains("function "), 1.0),
+            (Contains("console.log"), 2.0),
+            (Contains("require("), 1.5),
+            (Contains("document."), 1.5),
+            (Contains("==="), 1.5),
+            (Contains("const "), 0.7),
+            (Contains(" => "), 0.7),
+            (LineStart("export "), 0.7),
+            (LineEnd(";"), 0.5),
+        ],
+    ),
+    (
+        "typescript",
+        &[
+            (Contains(": string"), 2.0),
+            (Contains(": number"), 2.0),
+            (Contains(": boolean"), 2.0),
+            (LineStart("interface "), 1.5),
+            (LineStart("type "), 1.0),
+            (LineStart("export "), 0.5),
+            (Contains("const "), 0.3),
+            (Contains(" => "), 0.3),
+        ],
+    ),
+    (
+        "json",
+        &[
+            (Starts("{"), 1.0),
+            (Starts("["), 0.5),
+            (Contains("\": "), 1.5),
+            (LineEnd(","), 0.5),
+        ],
+    ),
+    (
+        "toml",
+        &[
+            (LineStart("[package]"), 2.5),
+            (LineStart("[dependencies]"), 2.5),
+            (LineStart("[["), 1.0),
+            (Contains(" = \""), 1.0),
+            (Contains(" = { "), 1.0),
+        ],
+    ),
+    (
+        "yaml",
+        &[
+            (Starts("---"), 0.7),
+            (LineStart("- "), 0.7),
+            (LineStart("apiVersion: "), 2.5),
+            (Contains(": "), 0.5),
+            (LineEnd(":"), 0.5),
+        ],
+    ),
+    (
+        "bash",
+        &[
+            (LineStart("echo "), 1.5),
+            (LineStart("export "), 0.5),
+            (LineStart("if ["), 2.0),
+            (LineStart("fi"), 1.5),
+            (LineStart("cd "), 1.0),
+            (LineStart("sudo "), 1.5),
+            (LineStart("cargo "), 1.5),
+            (LineStart("npm "), 1.5),
+            (LineStart("git "), 1.5),
+            (Contains("$("), 0.7),
+            (Contains(" && "), 0.5),
+            (Contains(" | "), 0.5),
+        ],
+    ),
+    (
+        "c",
+        &[
+            (LineStart("#include <"), 2.0),
+            (LineStart("typedef "), 1.0),
+            (Contains("printf("), 1.0),
+            (Contains("int main("), 1.5),
+            (Contains("malloc("), 1.5),
+            (Contains("NULL"), 1.0),
+            (Contains("->"), 0.3),
+            (LineEnd(";"), 0.5),
+        ],
+    ),
+    (
+        "cpp",
+        &[
+            (LineStart("#include <"), 1.5),
+            (LineStart("template <"), 2.0),
+            (LineStart("template<"), 2.0),
+            (LineStart("namespace "), 1.0),
+            (Contains("std::"), 2.0),
+            (Contains("nullptr"), 2.0),
+            (Contains("cout"), 1.5),
+            (Contains("::"), 0.5),
+            (LineEnd(";"), 0.5),
+        ],
+    ),
+    (
+        "go",
+        &[
+            (LineStart("package "), 1.5),
+            (LineStart("func "), 2.0),
+            (LineStart("import ("), 2.0),
+            (Contains(" := "), 1.5),
+            (Contains("fmt."), 2.0),
+            (Contains("err != nil"), 2.5),
+        ],
+    ),
+    (
+        "java",
+        &[
+            (LineStart("public class "), 2.0),
+            (LineStart("import java"), 2.5),
+            (LineStart("@Override"), 1.5),
+            (LineStart("package "), 0.5),
+            (Contains("System.out"), 2.5),
+            (Contains("public static void"), 2.5),
+            (Contains("private "), 0.5),
+            (LineEnd(";"), 0.5),
+        ],
+    ),
+    (
+        "html",
+        &[
+            (Starts("<!DOCTYPE"), 3.0),
+            (Starts("<!doctype"), 3.0),
+            (Contains("<html"), 2.0),
+            (Contains("<div"), 1.5),
+            (Contains("<span"), 1.0),
+            (Contains("<p>"), 1.0),
+            (Contains("</"), 0.7),
+        ],
+    ),
+    (
+        "xml",
+        &[
+            (Starts("<?xml"), 3.0),
+            (Contains("xmlns"), 1.5),
+            (Contains("</"), 0.5),
+        ],
+    ),
+    (
+        "css",
+        &[
+            (LineStart("@media"), 2.0),
+            (Contains("px;"), 2.0),
+            (Contains("color:"), 1.5),
+            (Contains("display:"), 1.5),
+            (Contains("margin"), 1.0),
+            (LineEnd("{"), 0.3),
+        ],
+    ),
+    (
+        "sql",
+        &[
+            (LineStart("SELECT "), 2.0),
+            (LineStart("select "), 1.5),
+            (LineStart("CREATE TABLE"), 2.5),
+            (LineStart("INSERT INTO"), 2.5),
+            (LineStart("UPDATE "), 1.0),
+            (Contains(" FROM "), 1.5),
+            (Contains(" WHERE "), 1.5),
+            (Contains(" from "), 0.5),
+        ],
+    ),
+    (
+        "ruby",
+        &[
+            (LineStart("require '"), 1.5),
+            (LineStart("def "), 0.7),
+            (LineStart("end"), 0.7),
+            (Contains("puts "), 2.0),
+            (Contains("attr_accessor"), 2.5),
+            (Contains(".each do"), 2.0),
+            (Contains("do |"), 2.0),
+        ],
+    ),
+    (
+        "php",
+        &[
+            (Starts("<?php"), 4.0),
+            (Contains("$this->"), 2.0),
+            (Contains("echo "), 0.5),
+        ],
+    ),
+    (
+        "lua",
+        &[
+            (LineStart("local "), 1.5),
+            (LineStart("function "), 0.5),
+            (LineStart("end"), 0.3),
+            (Contains(" then"), 1.0),
+            (Contains("~="), 1.5),
+        ],
+    ),
+    (
+        "markdown",
+        &[
+            (LineStart("```"), 2.0),
+            (LineStart("## "), 1.5),
+            (LineStart("# "), 0.7),
+            (LineStart("- ["), 0.5),
+            (Contains("]("), 1.5),
+            (Contains("**"), 0.5),
+        ],
+    ),
+    (
+        "dockerfile",
+        &[
+            (LineStart("FROM "), 2.5),
+            (LineStart("RUN "), 2.0),
+            (LineStart("WORKDIR "), 2.0),
+            (LineStart("ENTRYPOINT"), 2.0),
+            (LineStart("COPY "), 1.5),
+            (LineStart("EXPOSE "), 1.5),
+            (LineStart("CMD "), 1.0),
+        ],
+    ),
+];
+
+/// Guess the language of `content`, most likely first.
+///
+/// Returns `(language, confidence)` pairs with a confidence between 0 and 1,
+/// for languages whose grammar is enabled and that match at least one
+/// signal. A shebang or diff header gives its language a confidence of 1.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use arborium::guess_language;
+///
+/// let guesses = guess_language("def greet(name):\n    print(f\"hi {name}\")\n");
+/// assert_eq!(guesses[0].0, "python");
+/// ```
+pub fn guess_language(content: &str) -> Vec<(&'static str, f32)> {
+    if let Some(language) = detect_language_from_content(content)
+        && GrammarStore::supports(language)
+    {
+        return vec![(language, 1.0)];
+    }
+
+    let text = content.trim_start();
+    let lines: Vec<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    let matches = |signal: &Signal| match *signal {
+        Starts(prefix) => text.starts_with(prefix),
+        LineStart(prefix) => lines.iter().any(|line| line.starts_with(prefix)),
+        LineEnd(suffix) => {
+            lines.iter().filter(|line| line.ends_with(suffix)).count() * 4 >= lines.len()
+        }
+        Contains(needle) => content.contains(needle),
+    };
+
+    let mut guesses: Vec<(&'static str, f32)> = RULES
+        .iter()
+        .filter(|(language, _)| GrammarStore::supports(language))
+        .filter_map(|(language, signals)| {
+            let score: f32 = signals
+                .iter()
+                .filter(|(signal, _)| matches(signal))
+                .map(|(_, weight)| weight)
+                .sum();
+            (score > 0.0).then(|| (*language, 1.0 - (-score / 2.0).exp()))
+        })
+        .collect();
+
+    guesses.sort_by(|a, b| b.1.total_cmp(&a.1));
+    guesses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "lang-rust", feature = "lang-python"))]
+    fn test_guess_language() {
+        let rust =
+            "fn main() {\n    let mut v: Vec<u8> = Vec::new();\n    println!(\"{v:?}\");\n}\n";
+        assert_eq!(guess_language(rust)[0].0, "rust");
+        assert!(guess_language(rust)[0].1 > 0.9);
+
+        let python = "class Greeter:\n    def __init__(self, name):\n        self.name = name\n";
+        assert_eq!(guess_language(python)[0].0, "python");
+    }
+
+    #[test]
+    #[cfg(feature = "lang-bash")]
+    fn test_guess_language_shebang() {
+        assert_eq!(guess_language("#!/bin/sh\nls\n"), vec![("bash", 1.0)]);
+    }
+
+    #[test]
+    fn test_guess_language_nothing_to_go_on() {
+        assert!(guess_language("").is_empty());
+        assert!(guess_language("hello world").is_empty());
+    }
+}
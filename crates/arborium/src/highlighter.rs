@@ -11,6 +11,13 @@
 //! has its own parse context (cheap to create). Use [`Highlighter::fork`] to create
 //! a new highlighter that shares the grammar store.
 //!
+//! [`Highlighter`], [`AnsiHighlighter`] and [`GrammarStore`] are `Send` and
+//! `Sync`, and a compile-time check keeps them that way.
+//!
+//! Async servers can enable the `tokio` feature and use
+//! [`Highlighter::highlight_to_html_async`], which parses on tokio's blocking
+//! thread pool instead of the executor.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -27,16 +34,71 @@
 //! }).collect();
 //! ```
 
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
 use std::io::Write;
+use std::ops::Range;
 use std::sync::Arc;
-
-use arborium_highlight::tree_sitter::{CompiledGrammar, ParseContext};
-use arborium_highlight::{AnsiOptions, Span, spans_to_ansi_with_options, spans_to_html};
-use arborium_theme::Theme;
-
-use crate::Config;
+#[cfg(debug_assertions)]
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+
+use arborium_highlight::tree_sitter::{
+    CaptureExplanation, CompiledGrammar, ParseContext, SexpOptions, Token,
+};
+use arborium_highlight::{
+    AnsiOptions, CommentEdit, GENERIC_CAPTURES, LINK_URL_CAPTURE, MATCH_CAPTURE, Motion,
+    NormalizedInput, ProseClass, ProseRange, Span, WHITESPACE_CAPTURE, apply_render_options,
+    comment_tag_spans, generic_spans_into, is_generic, is_plain_text, link_spans, parse_ansi,
+    spans_to_ansi_merged, spans_to_ansi_with_options, split_front_matter, supports_front_matter,
+    write_spans_as_html, write_spans_as_html_chunked, write_spans_as_html_fmt,
+    write_spans_as_html_with_links, write_spans_as_html_with_overlay,
+};
+use arborium_theme::{Theme, tag_for_capture};
+
+use crate::annotate::{self, LineAnnotation};
+use crate::diagnostics::{Diagnostic, MAX_DIAGNOSTICS};
+use crate::diff;
 use crate::error::Error;
+use crate::injection_cache::{InjectionCache, InjectionCacheStats};
+#[cfg(debug_assertions)]
+use crate::query_dir::QueryDir;
 use crate::store::GrammarStore;
+use crate::word_diff::{self, Block};
+use crate::{Config, DiffLayout, Fallback, Links, UnknownInjections};
+
+/// The highlights query captures covering a position in one language layer.
+///
+/// Returned by [`Highlighter::explain`]: one entry for the requested language,
+/// plus one per injected language the position falls into.
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    /// The language of this layer.
+    pub language: String,
+    /// Captures covering the position, outermost node first.
+    ///
+    /// Node offsets are relative to the whole document. The last capture is the
+    /// one that determines the rendered highlight for this layer.
+    pub captures: Vec<CaptureExplanation>,
+}
+
+/// A span post-processing hook; see [`Highlighter::add_span_filter`].
+type SpanFilter = Arc<dyn Fn(&mut Vec<Span>, &str, &str) + Send + Sync>;
+
+/// An injected language hook; see [`Highlighter::set_injection_language_map`].
+type InjectionLanguageMap = Arc<dyn Fn(&str, &str) -> Option<String> + Send + Sync>;
+
+/// A hook for injected languages without a grammar; see
+/// [`Highlighter::set_unknown_injection_handler`].
+type UnknownInjectionHandler = Arc<dyn Fn(&GrammarStore, &str, &str) -> bool + Send + Sync>;
+
+// Servers keep highlighters in shared state; don't let a field take that away
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Highlighter>();
+    assert_send_sync::<AnsiHighlighter>();
+    assert_send_sync::<GrammarStore>();
+};
 
 /// High-level syntax highlighter for HTML output.
 ///
@@ -48,6 +110,9 @@ use crate::store::GrammarStore;
 ///
 /// The highlighter can be forked to create copies that share the grammar store
 /// but have independent parse contexts. This enables efficient parallel highlighting.
+/// Parse contexts come from the store's [`ParserPool`](crate::advanced::ParserPool)
+/// and go back to it when the highlighter is dropped, so forking per request
+/// doesn't reallocate parsers.
 ///
 /// ```rust,ignore
 /// let hl = Highlighter::new();
@@ -59,10 +124,42 @@ use crate::store::GrammarStore;
 ///     hl.highlight("rust", code)
 /// });
 /// ```
+///
+/// `Highlighter` is `Send` and `Sync`, so a configured one can live in a web
+/// server's shared state behind an [`Arc`]. Highlighting takes `&mut self`
+/// for the parse context, so request handlers fork the shared highlighter
+/// rather than lock it. Forks keep the configuration, span filters and
+/// injection language map, and need no grammar compilation.
+///
+/// ```rust,ignore
+/// let shared = Arc::new(Highlighter::with_config(config));
+///
+/// // In each request handler
+/// let html = shared.fork().highlight("rust", code)?;
+/// ```
 pub struct Highlighter {
     store: Arc<GrammarStore>,
     ctx: Option<ParseContext>,
     config: Config,
+    /// Span post-processing hooks, run in order, shared between forks.
+    span_filters: Vec<SpanFilter>,
+    /// Renames injected languages, shared between forks.
+    injection_language_map: Option<InjectionLanguageMap>,
+    /// Called for injected languages without a grammar, shared between forks.
+    unknown_injection_handler: Option<UnknownInjectionHandler>,
+    /// Collected until [`take_diagnostics`](Self::take_diagnostics).
+    diagnostics: Vec<Diagnostic>,
+    /// Languages whose grammar's own diagnostics were already collected.
+    diagnosed_grammars: HashSet<String>,
+    /// Collected until [`take_used_tags`](Self::take_used_tags).
+    used_tags: BTreeSet<&'static str>,
+    /// Set when the future of an async highlight is dropped.
+    cancellation_flag: Option<Arc<AtomicBool>>,
+    /// Spans of injected snippets seen before; not shared between forks.
+    injection_cache: InjectionCache,
+    /// Queries loaded from `ARBORIUM_QUERY_DIR`, shared between forks.
+    #[cfg(debug_assertions)]
+    query_dir: Option<Arc<Mutex<QueryDir>>>,
 }
 
 impl Default for Highlighter {
@@ -71,16 +168,21 @@ impl Default for Highlighter {
     }
 }
 
+impl Drop for Highlighter {
+    /// Return the parse context to the store's pool for the next highlighter.
+    fn drop(&mut self) {
+        if let Some(ctx) = self.ctx.take() {
+            self.store.parser_pool().put(ctx);
+        }
+    }
+}
+
 impl Clone for Highlighter {
     /// Clone creates a new highlighter sharing the grammar store.
     ///
     /// This is equivalent to [`fork`](Self::fork).
     fn clone(&self) -> Self {
-        Self {
-            store: self.store.clone(),
-            ctx: None, // New context will be created on first use
-            config: self.config.clone(),
-        }
+        self.fork()
     }
 }
 
@@ -89,39 +191,41 @@ impl Highlighter {
     ///
     /// Uses custom elements (`<a-k>`, `<a-f>`, etc.) for HTML output.
     pub fn new() -> Self {
-        Self {
-            store: Arc::new(GrammarStore::new()),
-            ctx: None,
-            config: Config::default(),
-        }
+        Self::with_store_and_config(Arc::new(GrammarStore::new()), Config::default())
     }
 
     /// Create a new highlighter with custom configuration.
     pub fn with_config(config: Config) -> Self {
-        Self {
-            store: Arc::new(GrammarStore::new()),
-            ctx: None,
-            config,
-        }
+        Self::with_store_and_config(Arc::new(GrammarStore::new()), config)
     }
 
     /// Create a new highlighter with a shared grammar store.
     ///
     /// Use this when you want multiple highlighters to share compiled grammars.
     pub fn with_store(store: Arc<GrammarStore>) -> Self {
-        Self {
-            store,
-            ctx: None,
-            config: Config::default(),
-        }
+        Self::with_store_and_config(store, Config::default())
     }
 
     /// Create a new highlighter with a shared store and custom configuration.
+    ///
+    /// In debug builds, if `ARBORIUM_QUERY_DIR` is set, queries are read from
+    /// `$ARBORIUM_QUERY_DIR/<lang>/*.scm` (or the repository's `langs/` layout)
+    /// instead of the compiled-in constants, and re-read whenever they change.
     pub fn with_store_and_config(store: Arc<GrammarStore>, config: Config) -> Self {
         Self {
             store,
             ctx: None,
+            injection_cache: InjectionCache::new(config.injection_cache_size),
             config,
+            span_filters: Vec::new(),
+            injection_language_map: None,
+            unknown_injection_handler: None,
+            diagnostics: Vec::new(),
+            diagnosed_grammars: HashSet::new(),
+            used_tags: BTreeSet::new(),
+            cancellation_flag: None,
+            #[cfg(debug_assertions)]
+            query_dir: QueryDir::from_env().map(|dir| Arc::new(Mutex::new(dir))),
         }
     }
 
@@ -132,8 +236,18 @@ impl Highlighter {
     pub fn fork(&self) -> Self {
         Self {
             store: self.store.clone(),
-            ctx: None,
+            ctx: None, // Taken from the store's parser pool on first use
             config: self.config.clone(),
+            span_filters: self.span_filters.clone(),
+            injection_language_map: self.injection_language_map.clone(),
+            unknown_injection_handler: self.unknown_injection_handler.clone(),
+            diagnostics: Vec::new(),
+            diagnosed_grammars: HashSet::new(),
+            used_tags: BTreeSet::new(),
+            cancellation_flag: None,
+            injection_cache: InjectionCache::new(self.config.injection_cache_size),
+            #[cfg(debug_assertions)]
+            query_dir: self.query_dir.clone(),
         }
     }
 
@@ -144,13 +258,282 @@ impl Highlighter {
         &self.store
     }
 
+    /// The languages whose grammar failed to compile so far, with why.
+    ///
+    /// A broken grammar only makes its own language unsupported, so the
+    /// highlighter keeps working for the rest; this is where the cause ends
+    /// up. Grammars are compiled on first use, so the list grows as
+    /// languages are highlighted. See [`GrammarStore::init_errors`].
+    pub fn init_errors(&self) -> Vec<(String, Arc<Error>)> {
+        self.store.init_errors()
+    }
+
+    /// Add a hook that post-processes spans before they are rendered.
+    ///
+    /// The filter gets the spans of one highlight call (injections included),
+    /// the language it was called with and the source text, and can merge,
+    /// re-label or drop spans: demote comments, promote `TODO` markers, hide
+    /// captures a theme has no use for. Filters run in the order they were
+    /// added, for every output format and for [`highlight_spans`](Self::highlight_spans),
+    /// and are shared with forks.
+    ///
+    /// ```rust,ignore
+    /// let mut hl = Highlighter::new();
+    /// hl.add_span_filter(|spans, _language, _source| {
+    ///     spans.retain(|span| span.capture != "comment");
+    /// });
+    /// ```
+    pub fn add_span_filter<F>(&mut self, filter: F)
+    where
+        F: Fn(&mut Vec<Span>, &str, &str) + Send + Sync + 'static,
+    {
+        self.span_filters.push(Arc::new(filter));
+    }
+
+    /// Set a hook that renames injected languages before they are looked up,
+    /// such as the info strings of Markdown code fences.
+    ///
+    /// The hook gets the canonical name of the host language and the injected
+    /// language as the host grammar names it, and returns the language to use instead, or
+    /// `None` to keep it. Injections in languages without a grammar are
+    /// dropped and left to the host language; map them to `plaintext` to
+    /// keep them unhighlighted, or to `generic` for the grammar-free
+    /// tokenizer. Replaces any previous hook; shared with forks.
+    ///
+    /// ```rust,ignore
+    /// let mut hl = Highlighter::new();
+    /// hl.set_injection_language_map(|host, language| match (host, language) {
+    ///     ("markdown", "terminal") => Some("bash".to_string()),
+    ///     ("markdown", "pseudocode") => Some("generic".to_string()),
+    ///     ("markdown", _) if !GrammarStore::supports(language) => {
+    ///         Some("plaintext".to_string())
+    ///     }
+    ///     _ => None,
+    /// });
+    /// ```
+    pub fn set_injection_language_map<F>(&mut self, map: F)
+    where
+        F: Fn(&str, &str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.injection_language_map = Some(Arc::new(map));
+        self.injection_cache.clear();
+    }
+
+    /// Set a hook for injected languages that have no grammar, which can
+    /// add one to the store.
+    ///
+    /// The hook gets the store, the canonical name of the host language and
+    /// the injected language, after the [injection language
+    /// map](Self::set_injection_language_map). If it returns `true`, the
+    /// grammar is looked up again, so a hook that calls
+    /// [`GrammarStore::register_language`] loads languages on first use.
+    /// Otherwise, or if there's still no grammar, [`Config::unknown_injections`]
+    /// applies. Replaces any previous hook; shared with forks.
+    ///
+    /// ```rust,ignore
+    /// let mut hl = Highlighter::new();
+    /// hl.set_unknown_injection_handler(|store, _host, language| {
+    ///     match load_grammar_config(language) {
+    ///         Some(config) => store.register_language(language, config).is_ok(),
+    ///         None => false,
+    ///     }
+    /// });
+    /// ```
+    pub fn set_unknown_injection_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(&GrammarStore, &str, &str) -> bool + Send + Sync + 'static,
+    {
+        self.unknown_injection_handler = Some(Arc::new(handler));
+        self.injection_cache.clear();
+    }
+
+    /// Take the diagnostics collected since the last call.
+    ///
+    /// Highlighting never fails over what a [`Diagnostic`] describes: a
+    /// missing language, a capture no theme knows, a query predicate that
+    /// isn't evaluated, injections nested too deep. Call this after
+    /// highlighting to find out what was degraded. Up to 256 are kept;
+    /// later ones are dropped until they are taken. Forks start with none.
+    ///
+    /// ```rust,ignore
+    /// let html = hl.highlight("markdown", source)?;
+    /// for diagnostic in hl.take_diagnostics() {
+    ///     eprintln!("warning: {diagnostic}");
+    /// }
+    /// ```
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// Take the element tags (`k`, `f`, `s`, ...) of the HTML rendered since
+    /// the last call, sorted.
+    ///
+    /// Unlike [`tags_for`](Self::tags_for), these come from the spans that
+    /// were actually written, so they include the tags of injected languages,
+    /// like the CSS and JavaScript in an HTML block. Pass them to
+    /// [`Theme::to_css_for_tags`] to generate CSS for a page once its code
+    /// blocks are highlighted. Forks start with none.
+    pub fn take_used_tags(&mut self) -> Vec<&'static str> {
+        std::mem::take(&mut self.used_tags).into_iter().collect()
+    }
+
+    /// Record the tags of `spans` for [`take_used_tags`](Self::take_used_tags).
+    fn use_tags(&mut self, spans: &[Span]) {
+        self.used_tags.extend(
+            spans
+                .iter()
+                .filter_map(|span| tag_for_capture(&span.capture)),
+        );
+    }
+
+    /// Record a diagnostic for [`take_diagnostics`](Self::take_diagnostics).
+    fn diagnose(&mut self, diagnostic: Diagnostic) {
+        if self.diagnostics.len() < MAX_DIAGNOSTICS {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Record the diagnostics about `grammar` itself, the first time this
+    /// highlighter uses it.
+    fn diagnose_grammar(&mut self, language: &str, grammar: &CompiledGrammar) {
+        let language = GrammarStore::normalize_language(language);
+        if self.diagnosed_grammars.contains(&*language) {
+            return;
+        }
+        for capture in grammar.unknown_captures() {
+            self.diagnose(Diagnostic::UnknownCapture {
+                language: language.to_string(),
+                capture: capture.to_string(),
+            });
+        }
+        for (line, predicate) in grammar.ignored_predicates() {
+            self.diagnose(Diagnostic::IgnoredPredicate {
+                language: language.to_string(),
+                line,
+                predicate: predicate.to_string(),
+            });
+        }
+        self.diagnosed_grammars.insert(language.into_owned());
+    }
+
+    /// The grammar for `language` injected into `host`, after asking the
+    /// [unknown injection handler](Self::set_unknown_injection_handler).
+    fn injected_grammar(&self, host: &str, language: &str) -> Option<Arc<CompiledGrammar>> {
+        if let Some(grammar) = self.store.get(language) {
+            return Some(grammar);
+        }
+        let handler = self.unknown_injection_handler.as_ref()?;
+        if handler(
+            &self.store,
+            &GrammarStore::normalize_language(host),
+            language,
+        ) {
+            self.store.get(language)
+        } else {
+            None
+        }
+    }
+
+    /// How often injected snippets were reused instead of parsed again.
+    ///
+    /// Each highlighter remembers the spans of up to
+    /// [`Config::injection_cache_size`] injected snippets, by language and
+    /// content, so a code block repeated across documents is parsed once.
+    /// Forks start with an empty cache.
+    pub fn injection_cache_stats(&self) -> InjectionCacheStats {
+        self.injection_cache.stats()
+    }
+
+    /// The language to highlight code injected into `host` with, after the
+    /// [injection language map](Self::set_injection_language_map).
+    fn injection_language(&self, host: &str, language: String) -> String {
+        match &self.injection_language_map {
+            Some(map) => {
+                map(&GrammarStore::normalize_language(host), &language).unwrap_or(language)
+            }
+            None => language,
+        }
+    }
+
+    /// List the capture names used by a language's highlights query.
+    ///
+    /// Useful for theme authors, or for emitting only the CSS rules a page
+    /// actually needs. Language aliases are resolved like in [`highlight`](Self::highlight).
+    pub fn captures_for(&self, language: &str) -> Result<Vec<String>, Error> {
+        let grammar = self
+            .store
+            .get(language)
+            .ok_or_else(|| Error::UnsupportedLanguage {
+                language: language.to_string(),
+            })?;
+
+        Ok(grammar
+            .capture_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Collect the element tags (`k`, `f`, `s`, ...) that highlighting the
+    /// given languages can produce, sorted and deduplicated.
+    ///
+    /// Unsupported languages are skipped, unless the configured [`Fallback`]
+    /// highlights them generically. Injected languages are not followed, as
+    /// which ones a document injects depends on its text; to cover them, take
+    /// the tags of the highlighted code with
+    /// [`take_used_tags`](Self::take_used_tags) instead.
+    pub fn tags_for(&self, languages: &[&str]) -> Vec<&'static str> {
+        let mut tags = BTreeSet::new();
+        for language in languages {
+            let Some(grammar) = self.store.get(language) else {
+                if is_generic(language)
+                    || (self.config.fallback == Fallback::Generic && !is_plain_text(language))
+                {
+                    tags.extend(GENERIC_CAPTURES.iter().copied().filter_map(tag_for_capture));
+                }
+                continue;
+            };
+            tags.extend(
+                grammar
+                    .capture_names()
+                    .into_iter()
+                    .filter_map(tag_for_capture),
+            );
+        }
+        if self.config.comment_tags && tags.contains("c") {
+            tags.extend(tag_for_capture("comment.todo"));
+        }
+        if self.config.links != Links::Off && (tags.contains("c") || tags.contains("s")) {
+            tags.extend(tag_for_capture(LINK_URL_CAPTURE));
+        }
+        if self.config.render_options.show_whitespace {
+            tags.extend(tag_for_capture(WHITESPACE_CAPTURE));
+        }
+        tags.into_iter().collect()
+    }
+
+    /// Generate theme CSS with only the rules needed by the given languages.
+    ///
+    /// This is [`Theme::to_css`] restricted to the tags returned by
+    /// [`tags_for`](Self::tags_for), so a page with one or two languages
+    /// doesn't carry rules for every capture arborium knows about.
+    pub fn theme_css_for(
+        &self,
+        languages: &[&str],
+        theme: &Theme,
+        selector_prefix: &str,
+    ) -> String {
+        theme.to_css_for_tags(selector_prefix, &self.tags_for(languages))
+    }
+
     /// Highlight source code and return HTML string.
     ///
     /// This automatically handles language injections (e.g., CSS/JS in HTML,
     /// SQL in Python strings, etc.).
     pub fn highlight(&mut self, language: &str, source: &str) -> Result<String, Error> {
-        let spans = self.highlight_spans(language, source)?;
-        Ok(spans_to_html(source, spans, &self.config.html_format))
+        let mut html = String::with_capacity(source.len() * 2);
+        self.highlight_to_fmt(&mut html, language, source)?;
+        Ok(html)
     }
 
     /// Highlight source code and write HTML directly to a writer.
@@ -163,125 +546,1077 @@ impl Highlighter {
         language: &str,
         source: &str,
     ) -> Result<(), Error> {
-        let html = self.highlight(language, source)?;
-        writer.write_all(html.as_bytes())?;
+        if self.config.max_chunk_len.is_some() || self.config.links == Links::Anchors {
+            let html = self.highlight(language, source)?;
+            writer.write_all(html.as_bytes())?;
+            return Ok(());
+        }
+
+        let input = self.normalize(source);
+        let spans = self.highlight_spans(language, input.text())?;
+        let (text, spans) = apply_render_options(input.text(), spans, &self.config.render_options);
+        self.use_tags(&spans);
+        write_spans_as_html(writer, &text, spans, &self.config.html_format)?;
         Ok(())
     }
 
-    /// Highlight and return raw spans (for custom rendering).
-    pub fn highlight_spans(&mut self, language: &str, source: &str) -> Result<Vec<Span>, Error> {
-        // Get the primary grammar
-        let grammar = self
-            .store
-            .get(language)
-            .ok_or_else(|| Error::UnsupportedLanguage {
-                language: language.to_string(),
-            })?;
+    /// Highlight source code and append HTML to a [`fmt::Write`] destination.
+    ///
+    /// Use this to render into a preallocated or reused `String` (e.g. a
+    /// response buffer) without allocating a new string per code block.
+    pub fn highlight_to_fmt<W: fmt::Write>(
+        &mut self,
+        writer: &mut W,
+        language: &str,
+        source: &str,
+    ) -> Result<(), Error> {
+        let input = self.normalize(source);
+        let spans = self.highlight_spans(language, input.text())?;
+        self.render_html(writer, input.text(), spans, Vec::new())
+    }
 
-        // Ensure we have a parse context
-        self.ensure_context(&grammar)?;
-        let ctx = self.ctx.as_mut().unwrap();
+    /// Highlight a whole document but render only the bytes in `range`.
+    ///
+    /// The document is parsed in full, so the snippet is highlighted exactly
+    /// as it would be in context: a range starting inside a block comment or
+    /// a multi-line string renders as comment or string. Elements cut by the
+    /// range are closed at its end and opened at its start. Meant for search
+    /// result snippets and "lines around the match" views.
+    ///
+    /// `range` is in bytes of `source`, like [`Span`] offsets.
+    ///
+    /// # Panics
+    ///
+    /// If `range` is out of bounds or doesn't start and end on `char`
+    /// boundaries, like slicing `source` would.
+    pub fn highlight_range(
+        &mut self,
+        language: &str,
+        source: &str,
+        range: Range<usize>,
+    ) -> Result<String, Error> {
+        assert!(
+            range.start <= range.end
+                && source.is_char_boundary(range.start)
+                && source.is_char_boundary(range.end),
+            "byte range {range:?} is not valid in a source of {} bytes",
+            source.len()
+        );
 
-        // Set the language for this grammar
-        ctx.set_language(grammar.language())
-            .map_err(|_| Error::ParseError {
-                language: language.to_string(),
-                message: "Failed to set parser language".to_string(),
-            })?;
+        let input = self.normalize(source);
+        let start = input.to_normalized(range.start as u32);
+        let end = input.to_normalized(range.end as u32);
+        let spans = self.highlight_spans(language, input.text())?;
+        let spans = clip_spans(&spans, start, end);
+
+        let mut html = String::with_capacity((end - start) as usize * 2);
+        let text = &input.text()[start as usize..end as usize];
+        self.render_html(&mut html, text, spans, Vec::new())?;
+        Ok(html)
+    }
 
-        // Parse the primary language
-        let result = grammar.parse(ctx, source);
+    /// Highlight source code with extra ranges, such as search matches,
+    /// marked on top of the syntax highlighting.
+    ///
+    /// Each range becomes a [`MATCH_CAPTURE`] element (`<a-mh>`) around the
+    /// syntax elements inside it. Syntax elements cut by a range are split,
+    /// so a match on half an identifier keeps the identifier's colour on
+    /// both halves. Overlapping and adjacent ranges are merged, and ranges
+    /// are widened to `char` boundaries and clamped to `source`.
+    ///
+    /// `overlay` is in bytes of `source`, like [`Span`] offsets. The match
+    /// style isn't in [`theme_css_for`](Self::theme_css_for); add the
+    /// `mh` tag when generating CSS for a subset of tags.
+    pub fn highlight_with_overlay(
+        &mut self,
+        language: &str,
+        source: &str,
+        overlay: &[Range<usize>],
+    ) -> Result<String, Error> {
+        let input = self.normalize(source);
+        let overlay = overlay
+            .iter()
+            .map(|range| Span {
+                start: input.to_normalized(range.start.min(source.len()) as u32),
+                end: input.to_normalized(range.end.min(source.len()) as u32),
+                capture: MATCH_CAPTURE.into(),
+            })
+            .collect();
+        let spans = self.highlight_spans(language, input.text())?;
 
-        // Collect all spans (including from injections)
-        let mut all_spans = result.spans;
+        let mut html = String::with_capacity(source.len() * 2);
+        self.render_html(&mut html, input.text(), spans, overlay)?;
+        Ok(html)
+    }
 
-        // Process injections recursively
-        if self.config.max_injection_depth > 0 {
-            self.process_injections(
-                source,
-                result.injections,
-                0,
-                self.config.max_injection_depth,
-                &mut all_spans,
-            )?;
+    /// Highlight two versions of a snippet as a diff between them.
+    ///
+    /// Both versions are highlighted in full, then laid out line by line with
+    /// `-`, `+` or ` ` markers, either in one column like `diff -u` output or
+    /// as a two-column `<table>`; see [`DiffLayout`]. Within changed lines,
+    /// the words that differ are marked with `diff.addition.inline` and
+    /// `diff.deletion.inline` elements (`<a-dai>`, `<a-ddi>`) on top of the
+    /// syntax highlighting, so the code keeps its colours and the theme's
+    /// background shows what changed. Meant for changelogs and migration
+    /// guides that show code before and after a change.
+    pub fn highlight_diff(
+        &mut self,
+        language: &str,
+        old: &str,
+        new: &str,
+        layout: DiffLayout,
+    ) -> Result<String, Error> {
+        let (old, new) = (self.normalize(old), self.normalize(new));
+        let (old, new) = (old.text(), new.text());
+        let old_spans = self.highlight_spans(language, old)?;
+        let new_spans = self.highlight_spans(language, new)?;
+        let (old_lines, new_lines) = (line_ranges(old), line_ranges(new));
+        let old_side = (old, &old_spans[..], &old_lines[..]);
+        let new_side = (new, &new_spans[..], &new_lines[..]);
+
+        let old_texts: Vec<&str> = old_lines.iter().map(|line| &old[line.clone()]).collect();
+        let new_texts: Vec<&str> = new_lines.iter().map(|line| &new[line.clone()]).collect();
+        let blocks = word_diff::diff_lines(&old_texts, &new_texts);
+
+        let mut html = String::with_capacity((old.len() + new.len()) * 2);
+        if layout == DiffLayout::SideBySide {
+            html.push_str("<table>\n");
         }
+        for block in blocks {
+            let (removed, added) = match block {
+                Block::Same { old: i, new: j } => {
+                    match layout {
+                        DiffLayout::Unified => {
+                            self.write_diff_line(&mut html, ' ', new_side, j, Vec::new())?;
+                            html.push('\n');
+                        }
+                        DiffLayout::SideBySide => {
+                            html.push_str("<tr><td>");
+                            self.write_diff_line(&mut html, ' ', old_side, i, Vec::new())?;
+                            html.push_str("</td><td>");
+                            self.write_diff_line(&mut html, ' ', new_side, j, Vec::new())?;
+                            html.push_str("</td></tr>\n");
+                        }
+                    }
+                    continue;
+                }
+                Block::Changed { old, new } => (old, new),
+            };
+
+            // Removed and added lines are paired up in order for the word diff
+            let mut removed_overlay = vec![Vec::new(); removed.len()];
+            let mut added_overlay = vec![Vec::new(); added.len()];
+            for (k, (i, j)) in removed.clone().zip(added.clone()).enumerate() {
+                let (old_words, new_words) = word_diff::diff_words(old_texts[i], new_texts[j]);
+                removed_overlay[k] = overlay_spans(old_words, word_diff::REMOVED_CAPTURE);
+                added_overlay[k] = overlay_spans(new_words, word_diff::ADDED_CAPTURE);
+            }
 
-        Ok(all_spans)
+            match layout {
+                DiffLayout::Unified => {
+                    for (i, overlay) in removed.zip(removed_overlay) {
+                        self.write_diff_line(&mut html, '-', old_side, i, overlay)?;
+                        html.push('\n');
+                    }
+                    for (j, overlay) in added.zip(added_overlay) {
+                        self.write_diff_line(&mut html, '+', new_side, j, overlay)?;
+                        html.push('\n');
+                    }
+                }
+                DiffLayout::SideBySide => {
+                    let mut removed = removed.zip(removed_overlay);
+                    let mut added = added.zip(added_overlay);
+                    loop {
+                        let (left, right) = (removed.next(), added.next());
+                        if left.is_none() && right.is_none() {
+                            break;
+                        }
+                        html.push_str("<tr><td>");
+                        if let Some((i, overlay)) = left {
+                            self.write_diff_line(&mut html, '-', old_side, i, overlay)?;
+                        }
+                        html.push_str("</td><td>");
+                        if let Some((j, overlay)) = right {
+                            self.write_diff_line(&mut html, '+', new_side, j, overlay)?;
+                        }
+                        html.push_str("</td></tr>\n");
+                    }
+                }
+            }
+        }
+        if layout == DiffLayout::SideBySide {
+            html.push_str("</table>\n");
+        }
+        Ok(html)
     }
 
-    /// Ensure we have a parse context, creating one if needed.
-    fn ensure_context(&mut self, grammar: &CompiledGrammar) -> Result<(), Error> {
-        if self.ctx.is_none() {
-            self.ctx = Some(
-                ParseContext::for_grammar(grammar).map_err(|e| Error::ParseError {
-                    language: String::new(),
-                    message: e.to_string(),
-                })?,
-            );
+    /// Write line `index` of a diff side, `(text, spans, line ranges)`,
+    /// after its `-`, `+` or ` ` marker.
+    fn write_diff_line(
+        &mut self,
+        html: &mut String,
+        marker: char,
+        (text, spans, lines): (&str, &[Span], &[Range<usize>]),
+        index: usize,
+        overlay: Vec<Span>,
+    ) -> Result<(), Error> {
+        let capture = match marker {
+            '-' => Some("diff.minus"),
+            '+' => Some("diff.plus"),
+            _ => None,
+        };
+        match capture {
+            Some(capture) => {
+                let marker_span = Span {
+                    start: 0,
+                    end: 1,
+                    capture: capture.into(),
+                };
+                self.render_html(html, &marker.to_string(), vec![marker_span], Vec::new())?;
+            }
+            None => html.push(marker),
         }
-        Ok(())
+
+        let line = lines[index].clone();
+        let spans = clip_spans(spans, line.start as u32, line.end as u32);
+        self.render_html(html, &text[line], spans, overlay)
     }
 
-    /// Process injections recursively.
-    fn process_injections(
+    /// Highlight source code line by line, with a gutter of per-line
+    /// annotations such as blame or coverage data.
+    ///
+    /// `annotations[i]` goes next to line `i`; lines past the end of the
+    /// slice get an empty gutter. Each [`LineAnnotation`] field is a column
+    /// of its own, padded with spaces so the code lines up, and rendered as
+    /// a `gutter.author`, `gutter.age` or `gutter.label` element (`<a-ga>`,
+    /// `<a-gd>`, `<a-gl>`). The code is highlighted as a whole, so comments
+    /// and strings spanning several lines keep their colour on every line.
+    /// Lines with a [`heat`](LineAnnotation::heat) weight are shaded as set
+    /// by [`Config::heat_shading`].
+    ///
+    /// The gutter styles aren't in [`theme_css_for`](Self::theme_css_for);
+    /// add the `ga`, `gd` and `gl` tags when generating CSS for a subset of
+    /// tags, or use [`take_used_tags`](Self::take_used_tags), which has them.
+    pub fn highlight_annotated(
         &mut self,
+        language: &str,
         source: &str,
-        injections: Vec<arborium_highlight::Injection>,
-        base_offset: u32,
-        remaining_depth: u32,
-        all_spans: &mut Vec<Span>,
+        annotations: &[LineAnnotation],
+    ) -> Result<String, Error> {
+        let input = self.normalize(source);
+        let text = input.text();
+        let spans = self.highlight_spans(language, text)?;
+        let lines = line_ranges(text);
+        let columns = annotate::gutter_columns(annotations);
+
+        let mut html = String::with_capacity(text.len() * 2);
+        let by_line = annotate::spans_by_line(spans, &lines);
+        for (i, (line, spans)) in lines.iter().zip(by_line).enumerate() {
+            let annotation = annotations.get(i);
+            for &(capture, width) in &columns {
+                let value = annotation
+                    .and_then(|annotation| annotation.field(capture))
+                    .unwrap_or_default();
+                if !value.is_empty() {
+                    let span = Span {
+                        start: 0,
+                        end: value.len() as u32,
+                        capture: capture.into(),
+                    };
+                    self.used_tags.extend(tag_for_capture(capture));
+                    let format = &self.config.html_format;
+                    write_spans_as_html_fmt(&mut html, value, vec![span], format)?;
+                }
+                // Pad to the column width, plus one space between columns
+                let padding = width - value.chars().count() + 1;
+                html.extend(std::iter::repeat_n(' ', padding));
+            }
+            let heat = annotation
+                .and_then(|annotation| annotation.heat)
+                .and_then(|heat| {
+                    let format = &self.config.html_format;
+                    self.config.heat_shading.open_tag(heat, format)
+                });
+            html.push_str(heat.as_deref().unwrap_or_default());
+            self.render_html(&mut html, &text[line.clone()], spans, Vec::new())?;
+            if heat.is_some() {
+                html.push_str("</span>");
+            }
+            if line.end < text.len() {
+                html.push('\n');
+            }
+        }
+        Ok(html)
+    }
+
+    /// Render spans over `text` as HTML according to the config, with the
+    /// `overlay` spans drawn on top.
+    fn render_html<W: fmt::Write>(
+        &mut self,
+        writer: &mut W,
+        text: &str,
+        spans: Vec<Span>,
+        overlay: Vec<Span>,
     ) -> Result<(), Error> {
-        if remaining_depth == 0 {
-            return Ok(());
+        // Move the overlay along with the text, then take it back off the front
+        let overlay_len = overlay.len();
+        let mut all = overlay;
+        all.extend(spans);
+        let (text, mut spans) = apply_render_options(text, all, &self.config.render_options);
+        let overlay: Vec<Span> = spans.drain(..overlay_len).collect();
+        self.use_tags(&spans);
+        self.use_tags(&overlay);
+
+        let source = &*text;
+        let format = &self.config.html_format;
+        let max_chunk_len = self.config.max_chunk_len;
+        let links = self.config.links == Links::Anchors;
+        match max_chunk_len {
+            _ if !overlay.is_empty() => write_spans_as_html_with_overlay(
+                writer,
+                source,
+                spans,
+                overlay,
+                format,
+                max_chunk_len,
+                links,
+            )?,
+            _ if links => {
+                write_spans_as_html_with_links(writer, source, spans, format, max_chunk_len)?
+            }
+            Some(max) => write_spans_as_html_chunked(writer, source, spans, format, max)?,
+            None => write_spans_as_html_fmt(writer, source, spans, format)?,
         }
+        Ok(())
+    }
 
-        for injection in injections {
-            let start = injection.start as usize;
-            let end = injection.end as usize;
+    /// Highlight a batch of `(language, source)` snippets.
+    ///
+    /// Calls [`highlight`](Self::highlight) on each item in turn, so the
+    /// snippets share this highlighter's parser and query cursor like any
+    /// series of calls would. Meant for static site generators and doc tools
+    /// that render hundreds of small code blocks. Results are returned in
+    /// input order; a failing snippet doesn't stop the others.
+    pub fn highlight_many<'a, I>(&mut self, items: I) -> Vec<Result<String, Error>>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        items
+            .into_iter()
+            .map(|(language, source)| self.highlight(language, source))
+            .collect()
+    }
 
-            if start >= source.len() || end > source.len() || start >= end {
-                continue;
-            }
+    /// Like [`highlight_many`](Self::highlight_many), but spread over the
+    /// rayon thread pool, with one forked highlighter per worker thread.
+    #[cfg(feature = "rayon")]
+    pub fn par_highlight_many(&self, items: &[(&str, &str)]) -> Vec<Result<String, Error>> {
+        use rayon::prelude::*;
+
+        items
+            .par_iter()
+            .map_init(
+                || self.fork(),
+                |hl, (language, source)| hl.highlight(language, source),
+            )
+            .collect()
+    }
 
-            let injected_source = &source[start..end];
+    /// Like [`highlight`](Self::highlight), but for async code: parsing runs
+    /// on tokio's blocking thread pool, in a fork of this highlighter, so it
+    /// doesn't stall the executor.
+    ///
+    /// Dropping the returned future, e.g. when a client disconnects or a
+    /// timeout fires, cancels the highlight: the parser gives up at its next
+    /// progress check and remaining injections are skipped.
+    ///
+    /// ```rust,ignore
+    /// async fn render(State(hl): State<Arc<Highlighter>>, code: String) -> Html<String> {
+    ///     Html(hl.highlight_to_html_async("rust", code).await.unwrap_or_default())
+    /// }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn highlight_to_html_async(
+        &self,
+        language: &str,
+        source: impl Into<String>,
+    ) -> Result<String, Error> {
+        let cancel = CancelOnDrop::default();
+        let mut hl = self.fork();
+        hl.cancellation_flag = Some(cancel.0.clone());
+        let language = language.to_string();
+        let source = source.into();
+
+        let task = tokio::task::spawn_blocking(move || hl.highlight(&language, &source));
+        match task.await {
+            Ok(result) => result,
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(_) => Err(Error::Cancelled),
+        }
+    }
 
-            // Try to get grammar for injected language
-            let Some(grammar) = self.store.get(&injection.language) else {
+    /// Like [`highlight_many`](Self::highlight_many), but for async code,
+    /// with at most `max_concurrent` snippets on tokio's blocking thread pool
+    /// at a time.
+    ///
+    /// `items` is only pulled from as running highlights finish, so a lazy
+    /// iterator over a large batch doesn't buffer up, and a busy server
+    /// doesn't fill the blocking pool with one request's code blocks.
+    /// Results are returned in input order. Dropping the future cancels the
+    /// snippets still running.
+    #[cfg(feature = "tokio")]
+    pub async fn highlight_many_async<I, L, S>(
+        &self,
+        items: I,
+        max_concurrent: usize,
+    ) -> Vec<Result<String, Error>>
+    where
+        I: IntoIterator<Item = (L, S)>,
+        L: Into<String>,
+        S: Into<String>,
+    {
+        let cancel = CancelOnDrop::default();
+        let mut tasks = tokio::task::JoinSet::new();
+        let mut results = Vec::new();
+
+        let mut items = items.into_iter().enumerate().fuse();
+        loop {
+            if tasks.len() < max_concurrent.max(1)
+                && let Some((index, (language, source))) = items.next()
+            {
+                let mut hl = self.fork();
+                hl.cancellation_flag = Some(cancel.0.clone());
+                let (language, source): (String, String) = (language.into(), source.into());
+                results.push(None);
+                tasks.spawn_blocking(move || (index, hl.highlight(&language, &source)));
                 continue;
-            };
+            }
 
-            // Set language for this grammar
-            let ctx = self.ctx.as_mut().unwrap();
-            if ctx.set_language(grammar.language()).is_err() {
-                continue;
+            let Some(joined) = tasks.join_next().await else {
+                break;
+            };
+            match joined {
+                Ok((index, result)) => results[index] = Some(result),
+                Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+                Err(_) => {}
             }
+        }
 
-            // Parse injected content
-            let result = grammar.parse(ctx, injected_source);
+        results
+            .into_iter()
+            .map(|result| result.unwrap_or(Err(Error::Cancelled)))
+            .collect()
+    }
 
-            // Offset spans to document coordinates
-            let offset = base_offset + injection.start;
-            for mut span in result.spans {
-                span.start += offset;
-                span.end += offset;
-                all_spans.push(span);
-            }
+    /// Highlight and return raw spans (for custom rendering).
+    pub fn highlight_spans(&mut self, language: &str, source: &str) -> Result<Vec<Span>, Error> {
+        let mut spans = Vec::new();
+        self.highlight_spans_into(language, source, &mut spans)?;
+        Ok(spans)
+    }
 
-            // Recurse into nested injections
-            self.process_injections(
-                injected_source,
-                result.injections,
-                offset,
-                remaining_depth - 1,
-                all_spans,
-            )?;
+    /// Highlight and append raw spans to `spans`.
+    ///
+    /// Clear and reuse the same buffer across calls to highlight in a tight
+    /// loop (LSP semantic tokens, plugin runtimes) without reallocating it.
+    /// Capture names are borrowed from `arborium_theme::CAPTURE_NAMES`, so
+    /// spans for the captures arborium knows about don't allocate either.
+    ///
+    /// For `diff`, the code in every hunk is also highlighted in the language
+    /// detected from the file's `+++`/`---` header, so `git show` output gets
+    /// both diff markers and syntax colors. Like injections, this is off when
+    /// `max_injection_depth` is 0.
+    ///
+    /// `plaintext` produces no spans and `generic` uses a grammar-free
+    /// tokenizer; other unsupported languages are handled according to
+    /// [`Config::fallback`].
+    ///
+    /// With [`Config::comment_tags`] and [`Config::links`], tags like `TODO:`
+    /// and URLs in comments get their own spans. [Span filters](Self::add_span_filter)
+    /// run last and only see the spans appended by this call.
+    ///
+    /// The source is parsed after [`Config::strip_bom`] and
+    /// [`Config::line_endings`] are applied, but span offsets always refer to
+    /// `source` as passed in.
+    pub fn highlight_spans_into(
+        &mut self,
+        language: &str,
+        source: &str,
+        spans: &mut Vec<Span>,
+    ) -> Result<(), Error> {
+        if self.span_filters.is_empty() {
+            return self.collect_normalized_into(language, source, spans);
         }
 
-        Ok(())
+        // Reuse the caller's buffer when there's nothing in it to protect
+        let mut collected = if spans.is_empty() {
+            std::mem::take(spans)
+        } else {
+            Vec::new()
+        };
+        let result = self.collect_normalized_into(language, source, &mut collected);
+        if result.is_ok() {
+            for filter in &self.span_filters {
+                filter(&mut collected, language, source);
+            }
+        }
+        if spans.is_empty() {
+            *spans = collected;
+        } else {
+            spans.append(&mut collected);
+        }
+        result
     }
-}
 
-/// High-level syntax highlighter for ANSI terminal output.
+    /// Normalize `source` according to the config.
+    fn normalize<'a>(&self, source: &'a str) -> NormalizedInput<'a> {
+        NormalizedInput::new(source, self.config.strip_bom, self.config.line_endings)
+    }
+
+    /// Collect spans and nested spans for the normalized `source`, in
+    /// offsets of `source` itself.
+    fn collect_normalized_into(
+        &mut self,
+        language: &str,
+        source: &str,
+        spans: &mut Vec<Span>,
+    ) -> Result<(), Error> {
+        let input = self.normalize(source);
+        let first = spans.len();
+        let result = self.collect_spans_into(language, input.text(), spans);
+        if result.is_ok() {
+            self.add_nested_spans(input.text(), spans, first);
+        }
+        input.spans_to_original(&mut spans[first..]);
+        result
+    }
+
+    /// Add the optional spans nested in comments and strings (comment tags,
+    /// links) for `spans[first..]`.
+    fn add_nested_spans(&self, source: &str, spans: &mut Vec<Span>, first: usize) {
+        let mut nested = Vec::new();
+        if self.config.comment_tags {
+            nested.extend(comment_tag_spans(source, &spans[first..]));
+        }
+        if self.config.links != Links::Off {
+            nested.extend(link_spans(source, &spans[first..]));
+        }
+        spans.extend(nested);
+    }
+
+    /// Collect spans for [`highlight_spans_into`](Self::highlight_spans_into),
+    /// before span filters.
+    fn collect_spans_into(
+        &mut self,
+        language: &str,
+        source: &str,
+        spans: &mut Vec<Span>,
+    ) -> Result<(), Error> {
+        if is_plain_text(language) {
+            return Ok(());
+        }
+        if is_generic(language) {
+            generic_spans_into(source, spans);
+            return Ok(());
+        }
+
+        #[cfg(debug_assertions)]
+        self.refresh_queries(language)?;
+
+        // Get the primary grammar
+        let Some(grammar) = self.store.get(language) else {
+            if self.config.fallback != Fallback::Error {
+                self.diagnose(Diagnostic::MissingLanguage {
+                    language: language.to_string(),
+                });
+            }
+            return match self.config.fallback {
+                Fallback::Error => Err(Error::UnsupportedLanguage {
+                    language: language.to_string(),
+                }),
+                Fallback::Plain => Ok(()),
+                Fallback::Generic => {
+                    generic_spans_into(source, spans);
+                    Ok(())
+                }
+            };
+        };
+        self.diagnose_grammar(language, &grammar);
+
+        // Ensure we have a parse context
+        self.ensure_context(language, &grammar)?;
+        let ctx = self.ctx.as_mut().unwrap();
+
+        // Set the language for this grammar
+        ctx.set_language(grammar.language())
+            .map_err(|e| Error::grammar(language, e))?;
+
+        // Parse the primary language, minus any YAML/TOML front matter
+        let front_matter = if supports_front_matter(language) {
+            split_front_matter(source)
+        } else {
+            None
+        };
+        let (body, body_start) = match &front_matter {
+            Some(fm) => (fm.body, fm.body_start as u32),
+            None => (source, 0),
+        };
+
+        let first = spans.len();
+        let mut injections = grammar.parse_into(ctx, body, spans);
+        if ctx.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+        for span in &mut spans[first..] {
+            span.start += body_start;
+            span.end += body_start;
+        }
+        for injection in &mut injections {
+            injection.start += body_start;
+            injection.end += body_start;
+        }
+        if let Some(fm) = &front_matter {
+            spans.extend(fm.delimiter_spans());
+            injections.push(fm.injection());
+        }
+
+        // Process injections recursively
+        if self.config.max_injection_depth > 0 {
+            self.process_injections(
+                language,
+                source,
+                injections,
+                0,
+                self.config.max_injection_depth,
+                spans,
+            )?;
+
+            // Diffs get a second layer: the code in each hunk, in the file's language
+            if GrammarStore::normalize_language(language) == "diff" {
+                for side in diff::hunk_sides(source) {
+                    if side.language == "diff" {
+                        continue;
+                    }
+                    let mut side_spans = Vec::new();
+                    if self
+                        .collect_spans_into(side.language, &side.text, &mut side_spans)
+                        .is_ok()
+                    {
+                        side.map_spans(side_spans, spans);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reload the language's queries from `ARBORIUM_QUERY_DIR` if they changed.
+    #[cfg(debug_assertions)]
+    fn refresh_queries(&self, language: &str) -> Result<(), Error> {
+        match &self.query_dir {
+            Some(dir) => dir.lock().unwrap().refresh(&self.store, language),
+            None => Ok(()),
+        }
+    }
+
+    /// Explain how the character at `byte_offset` gets its highlight.
+    ///
+    /// For each language layer covering the position (the requested language,
+    /// then any injections it falls into), lists the node kind, matched
+    /// highlights pattern and capture name. Handy for answering "why is this
+    /// token colored as a string" without bisecting `highlights.scm` by hand.
+    pub fn explain(
+        &mut self,
+        language: &str,
+        source: &str,
+        byte_offset: u32,
+    ) -> Result<Vec<Explanation>, Error> {
+        #[cfg(debug_assertions)]
+        self.refresh_queries(language)?;
+
+        let mut grammar = self
+            .store
+            .get(language)
+            .ok_or_else(|| Error::UnsupportedLanguage {
+                language: language.to_string(),
+            })?;
+        self.ensure_context(language, &grammar)?;
+
+        let mut layers = Vec::new();
+        let mut language = language.to_string();
+        let mut text = source;
+        let mut base = 0;
+
+        for depth in 0..=self.config.max_injection_depth {
+            let ctx = self.ctx.as_mut().unwrap();
+            ctx.set_language(grammar.language())
+                .map_err(|e| Error::grammar(&language, e))?;
+
+            let relative = byte_offset - base;
+            let mut captures = grammar.explain(ctx, text, relative);
+            for capture in &mut captures {
+                capture.start += base;
+                capture.end += base;
+            }
+            layers.push(Explanation {
+                language: language.clone(),
+                captures,
+            });
+
+            if depth == self.config.max_injection_depth {
+                break;
+            }
+
+            // Descend into the injection containing the position, if any
+            let Some(injection) =
+                grammar.parse(ctx, text).injections.into_iter().find(|i| {
+                    i.start <= relative && relative < i.end && i.end as usize <= text.len()
+                })
+            else {
+                break;
+            };
+            let injected_language = self.injection_language(&language, injection.language);
+            let Some(injected) = self.store.get(&injected_language) else {
+                break;
+            };
+
+            text = &text[injection.start as usize..injection.end as usize];
+            base += injection.start;
+            language = injected_language;
+            grammar = injected;
+        }
+
+        Ok(layers)
+    }
+
+    /// Pretty-print the syntax tree of `source`, like `tree-sitter parse` does.
+    ///
+    /// Only the requested language is parsed; injections show up as the
+    /// host language's nodes that contain them.
+    pub fn tree_sexp(
+        &mut self,
+        language: &str,
+        source: &str,
+        options: SexpOptions,
+    ) -> Result<String, Error> {
+        let grammar = self
+            .store
+            .get(language)
+            .ok_or_else(|| Error::UnsupportedLanguage {
+                language: language.to_string(),
+            })?;
+        self.ensure_context(language, &grammar)?;
+
+        let ctx = self.ctx.as_mut().unwrap();
+        ctx.set_language(grammar.language())
+            .map_err(|e| Error::grammar(language, e))?;
+
+        Ok(grammar.tree_sexp(ctx, source, options))
+    }
+
+    /// Grow the selection `range`, byte offsets into `source`, to the next
+    /// larger syntax node, like editors' "expand selection" command.
+    ///
+    /// Call it again on the result to keep growing; once the selection
+    /// covers the whole document, it comes back unchanged. Only the
+    /// requested language is parsed, so injected code grows by the host
+    /// language's nodes.
+    pub fn expand_selection(
+        &mut self,
+        language: &str,
+        source: &str,
+        range: Range<usize>,
+    ) -> Result<Range<usize>, Error> {
+        let grammar = self
+            .store
+            .get(language)
+            .ok_or_else(|| Error::UnsupportedLanguage {
+                language: language.to_string(),
+            })?;
+        self.ensure_context(language, &grammar)?;
+
+        let ctx = self.ctx.as_mut().unwrap();
+        ctx.set_language(grammar.language())
+            .map_err(|e| Error::grammar(language, e))?;
+
+        Ok(grammar.expand_selection(ctx, source, range))
+    }
+
+    /// Find the syntax unit `motion` moves to from `offset`, a byte offset
+    /// into `source`, for keyboard navigation in code readers: the next
+    /// function, the enclosing block, and so on. See [`Motion`].
+    ///
+    /// Returns the unit's byte range, or `None` if there is no such unit,
+    /// e.g. no function after the last one. Units come from the grammar's
+    /// `textobjects.scm` where it has one, and from the shape of the syntax
+    /// tree otherwise.
+    pub fn navigate(
+        &mut self,
+        language: &str,
+        source: &str,
+        offset: usize,
+        motion: Motion,
+    ) -> Result<Option<Range<usize>>, Error> {
+        let grammar = self
+            .store
+            .get(language)
+            .ok_or_else(|| Error::UnsupportedLanguage {
+                language: language.to_string(),
+            })?;
+        self.ensure_context(language, &grammar)?;
+
+        let ctx = self.ctx.as_mut().unwrap();
+        ctx.set_language(grammar.language())
+            .map_err(|e| Error::grammar(language, e))?;
+
+        Ok(grammar.navigate(ctx, source, offset, motion))
+    }
+
+    /// The edits that toggle comments on the lines `selection` touches, a
+    /// byte range of `source`, using the language's comment delimiters from
+    /// [`GrammarStore::comment_syntax`].
+    ///
+    /// Apply the edits from last to first. See
+    /// [`toggle_comments`](arborium_highlight::toggle_comments) for how
+    /// lines are commented.
+    pub fn comment_ranges(
+        &self,
+        language: &str,
+        source: &str,
+        selection: Range<usize>,
+    ) -> Result<Vec<CommentEdit>, Error> {
+        let syntax =
+            GrammarStore::comment_syntax(language).ok_or_else(|| Error::UnsupportedLanguage {
+                language: language.to_string(),
+            })?;
+        Ok(arborium_highlight::toggle_comments(
+            syntax, source, selection,
+        ))
+    }
+
+    /// The ranges of `source` holding prose of the given `classes`, for
+    /// spell checkers and documentation linters: comments, string literals
+    /// and markup text, as the language's highlights classify them.
+    ///
+    /// Injected languages count, so comments in a Markdown code block are
+    /// found; escapes, interpolations, inline code and URLs are left out.
+    /// Pass [`ProseClass::ALL`] for all of them. See
+    /// [`prose_ranges`](arborium_highlight::prose_ranges).
+    pub fn prose_ranges(
+        &mut self,
+        language: &str,
+        source: &str,
+        classes: &[ProseClass],
+    ) -> Result<Vec<ProseRange>, Error> {
+        let spans = self.highlight_spans(language, source)?;
+        Ok(arborium_highlight::prose_ranges(
+            spans
+                .iter()
+                .map(|span| (span.start..span.end, &*span.capture)),
+            classes,
+        ))
+    }
+
+    /// Split `source` into tokens, the leaves of its syntax tree, each with
+    /// its node kind and highlight capture.
+    ///
+    /// Unlike the spans behind the HTML output, tokens cover unhighlighted
+    /// text too (punctuation, plain identifiers), and say which node kind
+    /// each one is, which makes them handy for corpus analysis and dataset
+    /// generation. With the `serde` feature, [`Token`] serializes to one
+    /// JSON object per token. Offsets are into `source` as given.
+    ///
+    /// Only the requested language is parsed; injected code shows up as the
+    /// host language's tokens that contain it.
+    pub fn highlight_tokens(&mut self, language: &str, source: &str) -> Result<Vec<Token>, Error> {
+        let grammar = self
+            .store
+            .get(language)
+            .ok_or_else(|| Error::UnsupportedLanguage {
+                language: language.to_string(),
+            })?;
+        self.ensure_context(language, &grammar)?;
+
+        let ctx = self.ctx.as_mut().unwrap();
+        ctx.set_language(grammar.language())
+            .map_err(|e| Error::grammar(language, e))?;
+
+        Ok(grammar.tokens(ctx, source))
+    }
+
+    /// Ensure we have a parse context, creating one if needed.
+    fn ensure_context(&mut self, language: &str, grammar: &CompiledGrammar) -> Result<(), Error> {
+        if self.ctx.is_none() {
+            let mut ctx = self
+                .store
+                .parser_pool()
+                .take(grammar.language())
+                .map_err(|e| Error::grammar(language, e))?;
+            ctx.set_cancellation_flag(self.cancellation_flag.clone());
+            self.ctx = Some(ctx);
+        }
+        Ok(())
+    }
+
+    /// Process injections recursively.
+    fn process_injections(
+        &mut self,
+        host: &str,
+        source: &str,
+        injections: Vec<arborium_highlight::Injection>,
+        base_offset: u32,
+        remaining_depth: u32,
+        all_spans: &mut Vec<Span>,
+    ) -> Result<(), Error> {
+        if remaining_depth == 0 {
+            if !injections.is_empty() {
+                self.diagnose(Diagnostic::InjectionDepthExceeded {
+                    language: GrammarStore::normalize_language(host).into_owned(),
+                    injections: injections.len(),
+                });
+            }
+            return Ok(());
+        }
+
+        for injection in injections {
+            if self.ctx.as_ref().is_some_and(ParseContext::is_cancelled) {
+                return Err(Error::Cancelled);
+            }
+
+            let start = injection.start as usize;
+            let end = injection.end as usize;
+
+            if start >= source.len() || end > source.len() || start >= end {
+                continue;
+            }
+
+            let injected_source = &source[start..end];
+            let offset = base_offset + injection.start;
+            let language = self.injection_language(host, injection.language);
+
+            if is_plain_text(&language) {
+                continue;
+            }
+            if is_generic(&language) {
+                let first = all_spans.len();
+                generic_spans_into(injected_source, all_spans);
+                for span in &mut all_spans[first..] {
+                    span.start += offset;
+                    span.end += offset;
+                }
+                continue;
+            }
+
+            // A broken on-disk query for an injected language shouldn't fail the whole document
+            #[cfg(debug_assertions)]
+            let _ = self.refresh_queries(&language);
+
+            // The same snippet on another page highlights the same way
+            self.injection_cache.sync(self.store.generation());
+            let cached = self
+                .injection_cache
+                .get(&language, remaining_depth, injected_source);
+            if let Some(entry) = cached {
+                all_spans.extend(entry.spans.iter().map(|span| Span {
+                    start: span.start + offset,
+                    end: span.end + offset,
+                    capture: span.capture.clone(),
+                }));
+                for diagnostic in entry.diagnostics.clone() {
+                    self.diagnose(diagnostic);
+                }
+                continue;
+            }
+
+            // Try to get grammar for injected language
+            let Some(grammar) = self.injected_grammar(host, &language) else {
+                let first = all_spans.len();
+                match self.config.unknown_injections {
+                    UnknownInjections::Skip => {}
+                    UnknownInjections::Embedded => all_spans.push(Span {
+                        start: 0,
+                        end: injected_source.len() as u32,
+                        capture: "embedded".into(),
+                    }),
+                    UnknownInjections::Generic => generic_spans_into(injected_source, all_spans),
+                    UnknownInjections::Warn => self.diagnose(Diagnostic::UnknownInjection {
+                        host: GrammarStore::normalize_language(host).into_owned(),
+                        language: language.clone(),
+                    }),
+                }
+                for span in &mut all_spans[first..] {
+                    span.start += offset;
+                    span.end += offset;
+                }
+                continue;
+            };
+            self.diagnose_grammar(&language, &grammar);
+
+            // Set language for this grammar
+            let ctx = self.ctx.as_mut().unwrap();
+            if ctx.set_language(grammar.language()).is_err() {
+                continue;
+            }
+
+            // Parse injected content
+            let first = all_spans.len();
+            let first_diagnostic = self.diagnostics.len();
+            let nested = grammar.parse_into(ctx, injected_source, all_spans);
+            if ctx.is_cancelled() {
+                return Err(Error::Cancelled);
+            }
+
+            // Offset spans to document coordinates
+            for span in &mut all_spans[first..] {
+                span.start += offset;
+                span.end += offset;
+            }
+
+            // Recurse into nested injections
+            self.process_injections(
+                &language,
+                injected_source,
+                nested,
+                offset,
+                remaining_depth - 1,
+                all_spans,
+            )?;
+
+            let spans = all_spans[first..]
+                .iter()
+                .map(|span| Span {
+                    start: span.start - offset,
+                    end: span.end - offset,
+                    capture: span.capture.clone(),
+                })
+                .collect();
+            // The grammars' own diagnostics are reported once per highlighter,
+            // and were when the snippet was first seen
+            let diagnostics = self.diagnostics[first_diagnostic..]
+                .iter()
+                .filter(|diagnostic| {
+                    !matches!(
+                        diagnostic,
+                        Diagnostic::UnknownCapture { .. } | Diagnostic::IgnoredPredicate { .. }
+                    )
+                })
+                .cloned()
+                .collect();
+            self.injection_cache.insert(
+                &language,
+                remaining_depth,
+                injected_source,
+                spans,
+                diagnostics,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// High-level syntax highlighter for ANSI terminal output.
 ///
 /// This highlighter produces ANSI escape sequences for colored terminal output.
 /// It owns a [`Theme`] which determines the colors used for each syntax element.
@@ -295,130 +1630,1013 @@ pub struct AnsiHighlighter {
     options: AnsiOptions,
 }
 
-impl Clone for AnsiHighlighter {
-    fn clone(&self) -> Self {
-        Self {
-            inner: self.inner.clone(),
-            theme: self.theme.clone(),
-            options: self.options.clone(),
+impl Clone for AnsiHighlighter {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            theme: self.theme.clone(),
+            options: self.options.clone(),
+        }
+    }
+}
+
+impl AnsiHighlighter {
+    /// Create a new ANSI highlighter with the given theme.
+    pub fn new(theme: Theme) -> Self {
+        Self {
+            inner: Highlighter::new(),
+            theme,
+            options: AnsiOptions::default(),
+        }
+    }
+
+    /// Create a new ANSI highlighter with custom configuration.
+    pub fn with_config(theme: Theme, config: Config) -> Self {
+        Self {
+            inner: Highlighter::with_config(config),
+            theme,
+            options: AnsiOptions::default(),
+        }
+    }
+
+    /// Create a new ANSI highlighter with custom configuration and rendering options.
+    pub fn with_options(theme: Theme, config: Config, options: AnsiOptions) -> Self {
+        Self {
+            inner: Highlighter::with_config(config),
+            theme,
+            options,
+        }
+    }
+
+    /// Create a new ANSI highlighter with a shared grammar store.
+    pub fn with_store(store: Arc<GrammarStore>, theme: Theme) -> Self {
+        Self {
+            inner: Highlighter::with_store(store),
+            theme,
+            options: AnsiOptions::default(),
+        }
+    }
+
+    /// Create a new ANSI highlighter with a shared store and custom configuration.
+    pub fn with_store_and_config(store: Arc<GrammarStore>, theme: Theme, config: Config) -> Self {
+        Self {
+            inner: Highlighter::with_store_and_config(store, config),
+            theme,
+            options: AnsiOptions::default(),
+        }
+    }
+
+    /// Fork this highlighter, creating a new one that shares the grammar store.
+    pub fn fork(&self) -> Self {
+        Self {
+            inner: self.inner.fork(),
+            theme: self.theme.clone(),
+            options: self.options.clone(),
+        }
+    }
+
+    /// Get the grammar store.
+    pub fn store(&self) -> &Arc<GrammarStore> {
+        self.inner.store()
+    }
+
+    /// How often injected snippets were reused instead of parsed again.
+    ///
+    /// See [`Highlighter::injection_cache_stats`].
+    pub fn injection_cache_stats(&self) -> InjectionCacheStats {
+        self.inner.injection_cache_stats()
+    }
+
+    /// Take the diagnostics collected since the last call.
+    ///
+    /// See [`Highlighter::take_diagnostics`].
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        self.inner.take_diagnostics()
+    }
+
+    /// Add a hook that post-processes spans before they are rendered.
+    ///
+    /// See [`Highlighter::add_span_filter`].
+    pub fn add_span_filter<F>(&mut self, filter: F)
+    where
+        F: Fn(&mut Vec<Span>, &str, &str) + Send + Sync + 'static,
+    {
+        self.inner.add_span_filter(filter);
+    }
+
+    /// Get a reference to the current theme.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Set a new theme.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// Get a reference to the current ANSI rendering options.
+    pub fn options(&self) -> &AnsiOptions {
+        &self.options
+    }
+
+    /// Get a mutable reference to the ANSI rendering options.
+    pub fn options_mut(&mut self) -> &mut AnsiOptions {
+        &mut self.options
+    }
+
+    /// Highlight source code and return ANSI-colored string.
+    ///
+    /// This automatically handles language injections.
+    pub fn highlight(&mut self, language: &str, source: &str) -> Result<String, Error> {
+        let spans = self.inner.highlight_spans(language, source)?;
+        let render_options = &self.inner.config.render_options;
+        let (text, spans) = apply_render_options(source, spans, render_options);
+        Ok(spans_to_ansi_with_options(
+            &text,
+            spans,
+            &self.theme,
+            &self.options,
+        ))
+    }
+
+    /// Highlight source code that already contains ANSI escape sequences.
+    ///
+    /// For captured terminal output: the escapes are removed before parsing,
+    /// and text the input colored keeps its original colors while the rest
+    /// gets syntax colors. Rendering options like width and borders don't
+    /// apply to this mode. To drop the input's colors instead, highlight
+    /// [`strip_ansi`](crate::advanced::strip_ansi)'s output.
+    pub fn highlight_colored(&mut self, language: &str, source: &str) -> Result<String, Error> {
+        let text = parse_ansi(source);
+        let spans = self.inner.highlight_spans(language, &text.text)?;
+        Ok(spans_to_ansi_merged(&text, spans, &self.theme))
+    }
+
+    /// Highlight source code and write ANSI output directly to a writer.
+    pub fn highlight_to_writer<W: Write>(
+        &mut self,
+        writer: &mut W,
+        language: &str,
+        source: &str,
+    ) -> Result<(), Error> {
+        let ansi = self.highlight(language, source)?;
+        writer.write_all(ansi.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Cancels the highlights holding its flag when dropped.
+#[cfg(feature = "tokio")]
+#[derive(Default)]
+struct CancelOnDrop(Arc<AtomicBool>);
+
+#[cfg(feature = "tokio")]
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+/// Clip spans to `start..end` and make them relative to `start`.
+fn clip_spans(spans: &[Span], start: u32, end: u32) -> Vec<Span> {
+    spans
+        .iter()
+        .filter(|span| span.start < end && span.end > start)
+        .map(|span| Span {
+            start: span.start.max(start) - start,
+            end: span.end.min(end) - start,
+            capture: span.capture.clone(),
+        })
+        .collect()
+}
+
+/// Turn byte ranges into overlay spans with the given capture.
+fn overlay_spans(ranges: Vec<Range<usize>>, capture: &'static str) -> Vec<Span> {
+    ranges
+        .into_iter()
+        .map(|range| Span {
+            start: range.start as u32,
+            end: range.end as u32,
+            capture: capture.into(),
+        })
+        .collect()
+}
+
+/// Byte ranges of the lines in `text`, without their line endings.
+fn line_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut offset = 0;
+    text.split_inclusive('\n')
+        .map(|line| {
+            let start = offset;
+            offset += line.len();
+            start..start + line.trim_end_matches(['\r', '\n']).len()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "lang-rust")]
+    use arborium_theme::builtin;
+
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_highlighter_fork() {
+        let hl = Highlighter::new();
+
+        // Fork creates independent highlighters sharing the store
+        let mut hl1 = hl.fork();
+        let mut hl2 = hl.fork();
+
+        // Both can highlight independently
+        let html1 = hl1.highlight("rust", "fn main() {}").unwrap();
+        let html2 = hl2.highlight("rust", "let x = 1;").unwrap();
+
+        assert!(html1.contains("<a-"));
+        assert!(html2.contains("<a-"));
+    }
+
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_shared_between_threads() {
+        use std::sync::Arc;
+
+        let shared = Arc::new(Highlighter::new());
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|i| {
+                    let shared = &shared;
+                    scope.spawn(move || shared.fork().highlight("rust", &format!("let x = {i};")))
+                })
+                .collect();
+            for handle in handles {
+                assert!(handle.join().unwrap().unwrap().contains("<a-k>let</a-k>"));
+            }
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_captures_for() {
+        let hl = Highlighter::new();
+
+        let captures = hl.captures_for("rust").unwrap();
+        assert!(captures.iter().any(|c| c == "keyword"));
+        assert!(!captures.iter().any(|c| c.starts_with('_')));
+        assert!(!captures.iter().any(|c| c.starts_with("injection.")));
+
+        assert!(matches!(
+            hl.captures_for("not-a-language"),
+            Err(Error::UnsupportedLanguage { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "lang-json")]
+    fn test_query_error_details() {
+        let hl = Highlighter::new();
+
+        let err = hl
+            .store()
+            .set_queries("json", "(string) @string\n(no_such_node) @x", "", "")
+            .unwrap_err();
+        match err {
+            Error::QueryError {
+                language,
+                query,
+                row,
+                column,
+                ..
+            } => {
+                assert_eq!(language, "json");
+                assert_eq!(query, crate::advanced::QueryKind::Highlights);
+                assert_eq!((row, column), (1, 1));
+            }
+            other => panic!("expected a query error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "lang-json", feature = "lang-rust"))]
+    fn test_theme_css_for() {
+        let hl = Highlighter::new();
+        let theme = builtin::catppuccin_mocha().clone();
+
+        let json_tags = hl.tags_for(&["json"]);
+        let both_tags = hl.tags_for(&["json", "rust", "not-a-language"]);
+        assert!(json_tags.len() < both_tags.len());
+        assert!(!json_tags.contains(&"m"), "JSON has no macros");
+
+        let json_css = hl.theme_css_for(&["json"], &theme, "pre");
+        assert!(json_css.len() < theme.to_css("pre").len());
+    }
+
+    #[test]
+    #[cfg(all(feature = "lang-markdown", feature = "lang-rust"))]
+    fn test_take_used_tags() {
+        let mut hl = Highlighter::new();
+        hl.highlight("markdown", "# Title\n\n```rust\nprintln!(\"hi\");\n```\n")
+            .unwrap();
+
+        let tags = hl.take_used_tags();
+        assert!(!hl.tags_for(&["markdown"]).contains(&"m"));
+        assert!(
+            tags.contains(&"m"),
+            "macro from the injected Rust: {tags:?}"
+        );
+        assert!(
+            tags.contains(&"s"),
+            "string from the injected Rust: {tags:?}"
+        );
+        assert!(hl.take_used_tags().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_explain() {
+        let mut hl = Highlighter::new();
+        let source = r#"let s = "hi";"#;
+
+        let layers = hl.explain("rust", source, 9).unwrap();
+        assert_eq!(layers.len(), 1);
+        assert_eq!(layers[0].language, "rust");
+
+        let last = layers[0]
+            .captures
+            .last()
+            .expect("string should be captured");
+        assert_eq!(last.capture, "string");
+        assert_eq!(&source[last.start as usize..last.end as usize], "\"hi\"");
+        assert!(last.pattern_line > 0);
+    }
+
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_tree_sexp() {
+        let mut hl = Highlighter::new();
+
+        let sexp = hl
+            .tree_sexp("rust", "fn f() {}", SexpOptions::default())
+            .unwrap();
+        assert!(sexp.starts_with("(source_file\n  (function_item"));
+        assert!(sexp.contains("name: (identifier)"));
+        assert!(!sexp.contains("\"fn\""));
+
+        let options = SexpOptions {
+            ranges: true,
+            anonymous: true,
+        };
+        let sexp = hl.tree_sexp("rust", "fn f() {}", options).unwrap();
+        assert!(sexp.contains("(\"fn\" [0, 0] - [0, 2])"));
+    }
+
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_expand_selection() {
+        let mut hl = Highlighter::new();
+        let source = "fn f() { g(1, 2); }";
+
+        let mut range = 11..11;
+        let mut selections = Vec::new();
+        loop {
+            let next = hl.expand_selection("rust", source, range.clone()).unwrap();
+            if next == range {
+                break;
+            }
+            selections.push(&source[next.clone()]);
+            range = next;
+        }
+        assert_eq!(
+            selections,
+            ["1", "(1, 2)", "g(1, 2)", "g(1, 2);", "{ g(1, 2); }", source]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_navigate() {
+        let mut hl = Highlighter::new();
+        let source = "fn a() {}\nimpl S {\n    fn b(&self) {\n        if x { y(); }\n    }\n}\n";
+        let y = source.find("y()").unwrap();
+        let mut go = |offset, motion| {
+            hl.navigate("rust", source, offset, motion)
+                .unwrap()
+                .map(|range| &source[range])
+        };
+
+        assert_eq!(
+            go(0, Motion::NextFunction),
+            Some("fn b(&self) {\n        if x { y(); }\n    }")
+        );
+        assert_eq!(
+            go(y, Motion::PrevFunction),
+            go(y, Motion::EnclosingFunction)
+        );
+        assert_eq!(go(y, Motion::EnclosingBlock), Some("{ y(); }"));
+        assert_eq!(go(y, Motion::NextFunction), None);
+    }
+
+    #[test]
+    #[cfg(feature = "lang-python")]
+    fn test_comment_ranges() {
+        let hl = Highlighter::new();
+        let source = "if x:\n    y()\n";
+        let edits = hl.comment_ranges("py", source, 0..source.len()).unwrap();
+        let mut text = source.to_string();
+        for edit in edits.into_iter().rev() {
+            text.replace_range(edit.range, &edit.replacement);
+        }
+        assert_eq!(text, "# if x:\n#     y()\n");
+
+        assert!(matches!(
+            hl.comment_ranges("no-such-language", source, 0..0),
+            Err(Error::UnsupportedLanguage { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "lang-python")]
+    fn test_prose_ranges() {
+        let mut hl = Highlighter::new();
+        let source = "x = 'some text'  # a comment\n";
+        let prose: Vec<_> = hl
+            .prose_ranges("python", source, ProseClass::ALL)
+            .unwrap()
+            .into_iter()
+            .map(|prose| (&source[prose.range], prose.class))
+            .collect();
+        assert_eq!(
+            prose,
+            [
+                ("'some text'", ProseClass::String),
+                ("# a comment", ProseClass::Comment),
+            ]
+        );
+
+        let comments = hl
+            .prose_ranges("python", source, &[ProseClass::Comment])
+            .unwrap();
+        assert_eq!(comments.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "lang-python")]
+    fn test_pairs() {
+        let pairs = GrammarStore::pairs("py").unwrap();
+        assert_eq!(pairs.matching_bracket("]"), Some("["));
+        assert_eq!(pairs.closing("'"), Some("'"));
+        assert!(GrammarStore::pairs("no-such-language").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_highlight_tokens() {
+        let mut hl = Highlighter::new();
+        let source = "fn f() {}";
+
+        let tokens = hl.highlight_tokens("rust", source).unwrap();
+        let texts: Vec<&str> = tokens
+            .iter()
+            .map(|t| &source[t.start as usize..t.end as usize])
+            .collect();
+        assert_eq!(texts, ["fn", "f", "(", ")", "{", "}"]);
+
+        assert_eq!(tokens[0].node_kind, "fn");
+        assert_eq!(tokens[0].capture.as_deref(), Some("keyword"));
+        assert_eq!(tokens[1].node_kind, "identifier");
+        assert!(tokens[1].capture.is_some());
+    }
+
+    #[test]
+    #[cfg(all(feature = "lang-json", feature = "lang-rust"))]
+    fn test_highlight_many() {
+        let mut hl = Highlighter::new();
+        let items = [("rust", "fn main() {}"), ("nope", "x"), ("json", "{}")];
+
+        let results = hl.highlight_many(items);
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[0].as_ref().unwrap(),
+            &hl.highlight("rust", "fn main() {}").unwrap()
+        );
+        assert!(matches!(results[1], Err(Error::UnsupportedLanguage { .. })));
+        assert!(results[2].is_ok());
+
+        #[cfg(feature = "rayon")]
+        {
+            let parallel = hl.par_highlight_many(&items);
+            assert_eq!(parallel[0].as_ref().unwrap(), results[0].as_ref().unwrap());
+            assert!(parallel[1].is_err());
         }
     }
-}
 
-impl AnsiHighlighter {
-    /// Create a new ANSI highlighter with the given theme.
-    pub fn new(theme: Theme) -> Self {
-        Self {
-            inner: Highlighter::new(),
-            theme,
-            options: AnsiOptions::default(),
-        }
+    #[test]
+    #[cfg(all(feature = "tokio", feature = "lang-json", feature = "lang-rust"))]
+    fn test_highlight_async() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let hl = Highlighter::new();
+        let expected = hl.fork().highlight("rust", "fn main() {}").unwrap();
+
+        let html = runtime.block_on(hl.highlight_to_html_async("rust", "fn main() {}"));
+        assert_eq!(html.unwrap(), expected);
+
+        let items = [("rust", "fn main() {}"), ("nope", "x"), ("json", "{}")];
+        let results = runtime.block_on(hl.highlight_many_async(items, 2));
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap(), &expected);
+        assert!(matches!(results[1], Err(Error::UnsupportedLanguage { .. })));
+        assert!(results[2].is_ok());
     }
 
-    /// Create a new ANSI highlighter with custom configuration.
-    pub fn with_config(theme: Theme, config: Config) -> Self {
-        Self {
-            inner: Highlighter::with_config(config),
-            theme,
-            options: AnsiOptions::default(),
-        }
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_parser_pool_reuse() {
+        let store = Arc::new(GrammarStore::new());
+        assert_eq!(store.parser_pool().idle_count(), 0);
+
+        let mut hl = Highlighter::with_store(store.clone());
+        hl.highlight("rust", "fn main() {}").unwrap();
+        drop(hl);
+        assert_eq!(store.parser_pool().idle_count(), 1);
+
+        // The next highlighter picks the context back up
+        let mut hl = Highlighter::with_store(store.clone());
+        hl.highlight("rust", "fn main() {}").unwrap();
+        assert_eq!(store.parser_pool().idle_count(), 0);
     }
 
-    /// Create a new ANSI highlighter with custom configuration and rendering options.
-    pub fn with_options(theme: Theme, config: Config, options: AnsiOptions) -> Self {
-        Self {
-            inner: Highlighter::with_config(config),
-            theme,
-            options,
-        }
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_highlight_spans_into() {
+        let mut hl = Highlighter::new();
+        let mut spans = Vec::new();
+
+        hl.highlight_spans_into("rust", "fn main() {}", &mut spans)
+            .unwrap();
+        let first = spans.len();
+        assert!(first > 0);
+        assert!(
+            spans
+                .iter()
+                .any(|s| matches!(s.capture, std::borrow::Cow::Borrowed(_)))
+        );
+
+        // Appends rather than replacing
+        hl.highlight_spans_into("rust", "fn main() {}", &mut spans)
+            .unwrap();
+        assert_eq!(spans.len(), 2 * first);
+        assert_eq!(
+            spans[..first],
+            hl.highlight_spans("rust", "fn main() {}").unwrap()[..]
+        );
     }
 
-    /// Create a new ANSI highlighter with a shared grammar store.
-    pub fn with_store(store: Arc<GrammarStore>, theme: Theme) -> Self {
-        Self {
-            inner: Highlighter::with_store(store),
-            theme,
-            options: AnsiOptions::default(),
-        }
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_span_filter() {
+        let source = "// TODO: fix\nfn main() {}";
+        let mut hl = Highlighter::new();
+        hl.add_span_filter(|spans, language, source| {
+            assert_eq!(language, "rust");
+            for span in spans.iter_mut() {
+                if source[span.start as usize..span.end as usize].contains("TODO") {
+                    span.capture = "comment.todo".into();
+                }
+            }
+        });
+        hl.add_span_filter(|spans, _, _| spans.retain(|span| span.capture != "keyword"));
+
+        let spans = hl.fork().highlight_spans("rust", source).unwrap();
+        assert!(spans.iter().any(|s| s.capture == "comment.todo"));
+        assert!(!spans.iter().any(|s| s.capture == "keyword"));
+
+        // Spans already in the buffer are left alone
+        let mut buf = vec![Span {
+            start: 0,
+            end: 2,
+            capture: "keyword".into(),
+        }];
+        hl.highlight_spans_into("rust", source, &mut buf).unwrap();
+        assert_eq!(buf[0].capture, "keyword");
+        assert_eq!(buf.len(), spans.len() + 1);
     }
 
-    /// Fork this highlighter, creating a new one that shares the grammar store.
-    pub fn fork(&self) -> Self {
-        Self {
-            inner: self.inner.fork(),
-            theme: self.theme.clone(),
-            options: self.options.clone(),
-        }
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_comment_tags() {
+        let source = "// SAFETY: checked above\nfn main() {} // TODO(alice): more";
+
+        let mut hl = Highlighter::new();
+        let html = hl.highlight("rust", source).unwrap();
+        assert!(!html.contains("<a-ct>"), "{html}");
+
+        let mut hl = Highlighter::with_config(Config {
+            comment_tags: true,
+            ..Default::default()
+        });
+        let html = hl.highlight("rust", source).unwrap();
+        assert!(html.contains("<a-ct>SAFETY:</a-ct>"), "{html}");
+        assert!(html.contains("<a-ct>TODO(alice):</a-ct>"), "{html}");
+        assert!(hl.tags_for(&["rust"]).contains(&"ct"));
     }
 
-    /// Get the grammar store.
-    pub fn store(&self) -> &Arc<GrammarStore> {
-        self.inner.store()
+    #[test]
+    #[cfg(feature = "lang-python")]
+    fn test_links() {
+        let source = "# docs: https://docs.python.org/3/\nopen(\"/etc/hosts\")\n";
+
+        let mut hl = Highlighter::with_config(Config {
+            links: Links::Highlight,
+            ..Default::default()
+        });
+        let html = hl.highlight("python", source).unwrap();
+        assert!(
+            html.contains("<a-tu>https://docs.python.org/3/</a-tu>"),
+            "{html}"
+        );
+        assert!(html.contains("<a-tu>/etc/hosts</a-tu>"), "{html}");
+        assert!(!html.contains("<a href"), "{html}");
+
+        let mut hl = Highlighter::with_config(Config {
+            links: Links::Anchors,
+            ..Default::default()
+        });
+        let html = hl.highlight("python", source).unwrap();
+        assert!(
+            html.contains(r#"<a href="https://docs.python.org/3/"><a-tu>"#),
+            "{html}"
+        );
+        assert!(!html.contains(r#"<a href="/etc/hosts">"#), "{html}");
     }
 
-    /// Get a reference to the current theme.
-    pub fn theme(&self) -> &Theme {
-        &self.theme
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_line_endings() {
+        use crate::LineEndings;
+
+        let crlf = "\u{feff}// one\r\nfn main() {}\r\n";
+        let lf = "// one\nfn main() {}\n";
+
+        // BOM stripped, `\r` kept but outside the comment
+        let mut hl = Highlighter::new();
+        let html = hl.highlight("rust", crlf).unwrap();
+        assert!(html.starts_with("<a-c>// one"), "{html}");
+        for span in hl.highlight_spans("rust", crlf).unwrap() {
+            let text = &crlf[span.start as usize..span.end as usize];
+            assert!(!text.ends_with('\r'), "{text:?}");
+        }
+
+        // Same output as LF input, spans still in CRLF offsets
+        let mut hl = Highlighter::with_config(Config {
+            line_endings: LineEndings::Lf,
+            ..Default::default()
+        });
+        assert_eq!(
+            hl.highlight("rust", crlf).unwrap(),
+            hl.highlight("rust", lf).unwrap()
+        );
+        let spans = hl.highlight_spans("rust", crlf).unwrap();
+        assert!(
+            spans
+                .iter()
+                .any(|s| &crlf[s.start as usize..s.end as usize] == "main")
+        );
     }
 
-    /// Set a new theme.
-    pub fn set_theme(&mut self, theme: Theme) {
-        self.theme = theme;
+    #[test]
+    #[cfg(feature = "lang-python")]
+    fn test_render_options() {
+        use crate::RenderOptions;
+
+        let source = "if x:\n\treturn 1\n";
+        let mut hl = Highlighter::with_config(Config {
+            render_options: RenderOptions {
+                tab_width: Some(4),
+                show_whitespace: true,
+            },
+            ..Default::default()
+        });
+        let html = hl.highlight("python", source).unwrap();
+        assert!(html.contains("<a-ws>→   </a-ws>"), "{html}");
+        assert!(html.contains("<a-ws>·</a-ws>"), "{html}");
+        assert!(hl.tags_for(&["python"]).contains(&"ws"));
+
+        // Spans still refer to the original source
+        let spans = hl.highlight_spans("python", source).unwrap();
+        assert!(spans.iter().all(|s| s.capture != "whitespace"));
     }
 
-    /// Get a reference to the current ANSI rendering options.
-    pub fn options(&self) -> &AnsiOptions {
-        &self.options
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_highlight_to_fmt() {
+        let mut hl = Highlighter::new();
+        let html = hl.highlight("rust", "fn main() {}").unwrap();
+
+        let mut buf = String::from("<pre>");
+        hl.highlight_to_fmt(&mut buf, "rust", "fn main() {}")
+            .unwrap();
+        assert_eq!(buf, format!("<pre>{html}"));
+
+        let mut bytes = Vec::new();
+        hl.highlight_to_writer(&mut bytes, "rust", "fn main() {}")
+            .unwrap();
+        assert_eq!(String::from_utf8(bytes).unwrap(), html);
     }
 
-    /// Get a mutable reference to the ANSI rendering options.
-    pub fn options_mut(&mut self) -> &mut AnsiOptions {
-        &mut self.options
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_highlight_range() {
+        let source = "/* a\nlong comment */\nfn main() {}\n";
+        let mut hl = Highlighter::new();
+
+        // Starts inside the comment: still a comment, closed at the cut
+        let start = source.find("long").unwrap();
+        let html = hl
+            .highlight_range("rust", source, start..start + 4)
+            .unwrap();
+        assert_eq!(html, "<a-c>long</a-c>");
+
+        // A whole line renders like the same line highlighted in context
+        let start = source.find("fn").unwrap();
+        let html = hl
+            .highlight_range("rust", source, start..source.len())
+            .unwrap();
+        assert_eq!(html, hl.highlight("rust", "fn main() {}\n").unwrap());
     }
 
-    /// Highlight source code and return ANSI-colored string.
-    ///
-    /// This automatically handles language injections.
-    pub fn highlight(&mut self, language: &str, source: &str) -> Result<String, Error> {
-        let spans = self.inner.highlight_spans(language, source)?;
-        Ok(spans_to_ansi_with_options(
-            source,
-            spans,
-            &self.theme,
-            &self.options,
-        ))
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_highlight_with_overlay() {
+        let mut hl = Highlighter::new();
+
+        // The match cuts the keyword in two; both halves keep its colour
+        let hit = 1..5;
+        let html = hl
+            .highlight_with_overlay("rust", "fn main() {}", &[hit])
+            .unwrap();
+        assert!(
+            html.starts_with("<a-k>f</a-k><a-mh><a-k>n</a-k> "),
+            "{html}"
+        );
+        assert_eq!(html.matches("<a-mh>").count(), 1, "{html}");
+
+        // Without ranges it's the plain highlighting
+        assert_eq!(
+            hl.highlight_with_overlay("rust", "fn main() {}", &[])
+                .unwrap(),
+            hl.highlight("rust", "fn main() {}").unwrap()
+        );
     }
 
-    /// Highlight source code and write ANSI output directly to a writer.
-    pub fn highlight_to_writer<W: Write>(
-        &mut self,
-        writer: &mut W,
-        language: &str,
-        source: &str,
-    ) -> Result<(), Error> {
-        let ansi = self.highlight(language, source)?;
-        writer.write_all(ansi.as_bytes())?;
-        Ok(())
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_highlight_diff() {
+        use crate::DiffLayout;
+
+        let old = "fn main() {\n    foo(1);\n}\n";
+        let new = "fn main() {\n    bar(1);\n}\n";
+        let mut hl = Highlighter::new();
+
+        let html = hl
+            .highlight_diff("rust", old, new, DiffLayout::Unified)
+            .unwrap();
+        let lines: Vec<&str> = html.lines().collect();
+        assert_eq!(lines.len(), 4, "{html}");
+        assert!(lines[0].starts_with(" <a-k>fn</a-k>"), "{html}");
+        assert!(lines[1].starts_with("<a-dd>-</a-dd>"), "{html}");
+        assert!(lines[1].contains("<a-ddi>"), "{html}");
+        assert!(lines[2].starts_with("<a-da>+</a-da>"), "{html}");
+        assert!(lines[2].contains("<a-dai>"), "{html}");
+        // Only the changed word is marked, not the whole line
+        assert_eq!(html.matches("<a-dai>").count(), 1, "{html}");
+
+        let html = hl
+            .highlight_diff("rust", old, new, DiffLayout::SideBySide)
+            .unwrap();
+        assert!(
+            html.starts_with("<table>\n<tr><td> <a-k>fn</a-k>"),
+            "{html}"
+        );
+        assert_eq!(html.matches("<tr>").count(), 3, "{html}");
     }
-}
 
-#[cfg(test)]
-mod tests {
     #[test]
     #[cfg(feature = "lang-rust")]
-    fn test_highlighter_fork() {
-        let hl = Highlighter::new();
+    fn test_highlight_annotated() {
+        use crate::LineAnnotation;
+
+        let annotations = [
+            LineAnnotation {
+                author: Some("ann".to_string()),
+                age: Some("2d".to_string()),
+                ..Default::default()
+            },
+            LineAnnotation {
+                author: Some("bo".to_string()),
+                ..Default::default()
+            },
+        ];
+        let mut hl = Highlighter::new();
+        let html = hl
+            .highlight_annotated("rust", "fn a() {}\nfn b() {}\n", &annotations)
+            .unwrap();
+        let lines: Vec<&str> = html.lines().collect();
+        assert!(
+            lines[0].starts_with("<a-ga>ann</a-ga> <a-gd>2d</a-gd> <a-k>fn</a-k>"),
+            "{html}"
+        );
+        // Short values and missing fields are padded so the code lines up
+        assert!(
+            lines[1].starts_with("<a-ga>bo</a-ga>     <a-k>fn</a-k>"),
+            "{html}"
+        );
+        assert!(html.ends_with("}\n"), "{html}");
+    }
 
-        // Fork creates independent highlighters sharing the store
-        let mut hl1 = hl.fork();
-        let mut hl2 = hl.fork();
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_heat_shading() {
+        use crate::LineAnnotation;
+
+        let annotations = [
+            LineAnnotation {
+                heat: Some(0.3),
+                ..Default::default()
+            },
+            LineAnnotation::default(),
+        ];
+        let mut hl = Highlighter::new();
+        let html = hl
+            .highlight_annotated("rust", "fn a() {}\nfn b() {}", &annotations)
+            .unwrap();
+        let lines: Vec<&str> = html.lines().collect();
+        assert!(
+            lines[0].starts_with("<span class=\"heat-3\"><a-k>fn</a-k>"),
+            "{html}"
+        );
+        assert!(lines[0].ends_with("</span>"), "{html}");
+        assert!(lines[1].starts_with("<a-k>fn</a-k>"), "{html}");
+    }
 
-        // Both can highlight independently
-        let html1 = hl1.highlight("rust", "fn main() {}").unwrap();
-        let html2 = hl2.highlight("rust", "let x = 1;").unwrap();
+    #[test]
+    #[cfg(feature = "lang-json")]
+    fn test_max_chunk_len() {
+        let source = format!(r#"{{"k":"{}"}}"#, "x".repeat(100_000));
+        let mut hl = Highlighter::with_config(Config {
+            max_chunk_len: Some(4096),
+            ..Config::default()
+        });
+
+        let html = hl.highlight("json", &source).unwrap();
+        // The limit is on source text, before quotes are escaped
+        let longest = html
+            .split(['<', '>'])
+            .map(|text| text.replace("&quot;", "\"").len())
+            .max()
+            .unwrap();
+        assert!(longest <= 4096, "element text of {longest} bytes");
+        assert!(html.len() > source.len());
+    }
 
-        assert!(html1.contains("<a-"));
-        assert!(html2.contains("<a-"));
+    #[test]
+    #[cfg(all(
+        feature = "lang-markdown",
+        feature = "lang-yaml",
+        feature = "lang-toml"
+    ))]
+    fn test_front_matter() {
+        let mut hl = Highlighter::new();
+
+        let html = hl
+            .highlight("markdown", "---\ntitle: Hello\n---\n# Heading\n")
+            .unwrap();
+        assert!(html.starts_with("<a-p>---</a-p>"), "{html}");
+        assert!(html.contains("<a-pr>title</a-pr>"), "YAML injected: {html}");
+
+        let html = hl
+            .highlight("md", "+++\ntitle = \"Hello\"\n+++\nText\n")
+            .unwrap();
+        assert!(
+            html.contains("<a-s>&quot;Hello&quot;</a-s>"),
+            "TOML injected: {html}"
+        );
+        assert!(html.ends_with("Text\n"), "{html}");
+    }
+
+    #[test]
+    #[cfg(all(feature = "lang-diff", feature = "lang-rust"))]
+    fn test_diff_layers() {
+        let diff = "--- a/lib.rs\n+++ b/lib.rs\n@@ -1 +1 @@\n-struct Old;\n+fn new() {}\n";
+        let mut hl = Highlighter::new();
+
+        let html = hl.highlight("diff", diff).unwrap();
+        assert!(html.contains("<a-da>+</a-da><a-k>fn</a-k>"), "{html}");
+        assert!(html.contains("<a-dd>-</a-dd><a-k>struct</a-k>"), "{html}");
+
+        // No second layer without injections
+        let mut hl = Highlighter::with_config(Config {
+            max_injection_depth: 0,
+            ..Default::default()
+        });
+        let html = hl.highlight("patch", diff).unwrap();
+        assert!(!html.contains("<a-k>"), "{html}");
+    }
+
+    #[test]
+    #[cfg(feature = "lang-json")]
+    fn test_api_spec_keywords() {
+        let mut hl = Highlighter::new();
+        hl.add_span_filter(|spans, language, source| {
+            crate::api_spec_keywords(spans, language, source)
+        });
+        let html = hl
+            .highlight("json", r#"{"openapi": "3.1.0", "paths": {"/info": {}}}"#)
+            .unwrap();
+        assert!(html.contains("<a-k>&quot;paths&quot;</a-k>"), "{html}");
+        assert!(html.contains("<a-s>&quot;/info&quot;</a-s>"), "{html}");
+    }
+
+    #[test]
+    #[cfg(all(feature = "lang-yaml", feature = "lang-bash"))]
+    fn test_kubernetes_manifest() {
+        let mut hl = Highlighter::new();
+        hl.add_span_filter(|spans, language, source| {
+            crate::kubernetes_keywords(spans, language, source)
+        });
+        let manifest = "apiVersion: v1\nkind: Pod\nspec:\n  containers:\n    - name: web\n      \
+                        command: [\"sh\", \"-c\", \"echo hi && sleep 10\"]\n";
+        let html = hl.highlight("yaml", manifest).unwrap();
+        assert!(html.contains("<a-k>apiVersion</a-k>"), "{html}");
+        assert!(html.contains("<a-t>Pod</a-t>"), "{html}");
+        assert!(html.contains("<a-pr>containers</a-pr>"), "{html}");
+        assert!(html.contains("<a-f>sleep</a-f>"), "{html}");
+    }
+
+    #[test]
+    #[cfg(all(feature = "lang-bash", feature = "lang-json"))]
+    fn test_curl_highlighting() {
+        let mut hl = Highlighter::new();
+        let command = "curl -X POST https://api.example.com/users \\\n  \
+                       -H 'Accept: application/json' -d '{\"id\": 1}'";
+        let html = hl.highlight("bash", command).unwrap();
+        assert!(html.contains("<a-v>-X</a-v> <a-k>POST</a-k>"), "{html}");
+        assert!(
+            html.contains("<a-tu>https://api.example.com/users</a-tu>"),
+            "{html}"
+        );
+        assert!(
+            html.contains("<a-s>&#39;Accept: application/json&#39;</a-s>"),
+            "{html}"
+        );
+        assert!(html.contains("<a-n>1</a-n>"), "{html}");
+    }
+
+    #[test]
+    fn test_plain_and_generic_fallback() {
+        let mut hl = Highlighter::new();
+        assert_eq!(hl.highlight("plaintext", "x < 1").unwrap(), "x &lt; 1");
+        assert_eq!(
+            hl.highlight("generic", "x = 1 # one").unwrap(),
+            "x = <a-n>1</a-n> <a-c># one</a-c>"
+        );
+        assert!(matches!(
+            hl.highlight("not-a-language", "x = 1"),
+            Err(Error::UnsupportedLanguage { .. })
+        ));
+
+        let mut hl = Highlighter::with_config(Config {
+            fallback: Fallback::Generic,
+            ..Default::default()
+        });
+        assert_eq!(
+            hl.highlight("not-a-language", "x = \"a\"").unwrap(),
+            "x = <a-s>&quot;a&quot;</a-s>"
+        );
+        assert_eq!(hl.tags_for(&["not-a-language"]), vec!["c", "n", "s"]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "lang-markdown", feature = "lang-rust"))]
+    fn test_injection_language_map() {
+        let source = "```console\nfn f() {}\n```\n\n```pseudo\nx = 1\n```\n";
+
+        let mut hl = Highlighter::new();
+        let html = hl.highlight("md", source).unwrap();
+        assert!(!html.contains("<a-k>fn</a-k>"));
+        assert!(!html.contains("<a-n>1</a-n>"));
+
+        hl.set_injection_language_map(|host, language| match (host, language) {
+            ("markdown", "console") => Some("rust".to_string()),
+            ("markdown", "pseudo") => Some("generic".to_string()),
+            _ => None,
+        });
+        let html = hl.fork().highlight("md", source).unwrap();
+        assert!(html.contains("<a-k>fn</a-k>"));
+        assert!(html.contains("<a-n>1</a-n>"));
     }
 
     #[test]
@@ -484,6 +2702,24 @@ fn main() {
         assert!(ansi_output.contains("\x1b["));
     }
 
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_ansi_highlight_colored() {
+        let mut highlighter = AnsiHighlighter::new(builtin::catppuccin_mocha());
+        let source = "\x1b[31mlet\x1b[0m x = \"s\";";
+
+        let ansi_output = highlighter.highlight_colored("rust", source).unwrap();
+        assert!(
+            ansi_output.starts_with("\x1b[31mlet\x1b[0m"),
+            "{ansi_output:?}"
+        );
+        assert!(!ansi_output.contains("[31m x"), "{ansi_output:?}");
+        assert!(
+            ansi_output.contains("\"s\"\x1b[0m"),
+            "string highlighted: {ansi_output:?}"
+        );
+    }
+
     #[test]
     #[cfg(feature = "lang-rust")]
     fn test_theme_switching() {
@@ -519,6 +2755,209 @@ fn main() {
         assert!(store.get("rust").is_some());
     }
 
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_query_cache() {
+        let dir = std::env::temp_dir().join(format!("arborium-query-cache-{}", std::process::id()));
+        let cache = crate::advanced::QueryCache::new(&dir);
+        let code = "fn main() { let x = 42; }";
+
+        // The first store compiles and fills the cache, the second loads from it
+        let cold = Arc::new(GrammarStore::new().with_query_cache(Some(cache.clone())));
+        let expected = Highlighter::with_store(cold)
+            .highlight("rust", code)
+            .unwrap();
+        assert!(std::fs::read_dir(&dir).unwrap().count() > 0);
+
+        let warm = Arc::new(GrammarStore::new().with_query_cache(Some(cache.clone())));
+        let html = Highlighter::with_store(warm)
+            .highlight("rust", code)
+            .unwrap();
+        assert_eq!(html, expected);
+
+        cache.clear().unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_init_errors() {
+        let mut hl = Highlighter::new();
+        hl.highlight("rust", "fn main() {}").unwrap();
+        assert!(hl.highlight("no-such-language", "x").is_err());
+        // Unsupported languages aren't init errors, only broken grammars are
+        assert!(hl.init_errors().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "lang-rust")]
+    fn test_register_language() {
+        use crate::advanced::GrammarConfig;
+
+        let store = Arc::new(GrammarStore::new());
+        store
+            .register_language(
+                "ferris",
+                GrammarConfig {
+                    language: crate::lang_rust::language().into(),
+                    highlights_query: crate::lang_rust::HIGHLIGHTS_QUERY,
+                    injections_query: "",
+                    locals_query: "",
+                    textobjects_query: "",
+                },
+            )
+            .unwrap();
+
+        let mut hl = Highlighter::with_store(store);
+        let html = hl.highlight("ferris", "fn main() {}").unwrap();
+        assert!(html.contains("<a-k>fn</a-k>"), "got: {html}");
+    }
+
+    #[test]
+    #[cfg(all(feature = "lang-markdown", feature = "lang-rust"))]
+    fn test_injection_cache() {
+        let page = "# Page\n\n```rust\nfn main() { let x = 1; }\n```\n";
+        let doc = format!("{page}\nText.\n\n```rust\nfn main() {{ let x = 1; }}\n```\n");
+
+        let mut uncached = Highlighter::with_config(Config {
+            injection_cache_size: 0,
+            ..Default::default()
+        });
+        let expected = uncached.highlight_spans("markdown", &doc).unwrap();
+        assert_eq!(uncached.injection_cache_stats().hits, 0);
+
+        let mut hl = Highlighter::new();
+        let spans = hl.highlight_spans("markdown", &doc).unwrap();
+        assert_eq!(spans, expected);
+        let stats = hl.injection_cache_stats();
+        assert!(stats.hits >= 1, "{stats:?}");
+
+        // Later documents reuse the snippet too
+        hl.highlight_spans("markdown", page).unwrap();
+        assert!(hl.injection_cache_stats().hits > stats.hits);
+        assert_eq!(hl.fork().injection_cache_stats(), Default::default());
+    }
+
+    #[test]
+    #[cfg(all(feature = "lang-markdown", feature = "lang-rust"))]
+    fn test_unknown_injections() {
+        use crate::advanced::GrammarConfig;
+
+        let doc = "```ferris\nfn main() {}\n```\n";
+        let code = doc.find("fn").unwrap() as u32;
+        let highlight = |unknown_injections| {
+            let mut hl = Highlighter::with_config(Config {
+                unknown_injections,
+                ..Default::default()
+            });
+            let spans = hl.highlight_spans("markdown", doc).unwrap();
+            (spans, hl.take_diagnostics())
+        };
+
+        let (spans, diagnostics) = highlight(UnknownInjections::Skip);
+        assert!(
+            !spans.iter().any(|s| s.start == code && s.capture != "none"),
+            "{spans:?}"
+        );
+        assert!(diagnostics.is_empty());
+
+        let (spans, _) = highlight(UnknownInjections::Embedded);
+        assert!(
+            spans
+                .iter()
+                .any(|s| s.start == code && s.capture == "embedded"),
+            "{spans:?}"
+        );
+
+        let (_, diagnostics) = highlight(UnknownInjections::Warn);
+        assert_eq!(
+            diagnostics,
+            [Diagnostic::UnknownInjection {
+                host: "markdown".to_string(),
+                language: "ferris".to_string(),
+            }]
+        );
+
+        let mut hl = Highlighter::new();
+        hl.set_unknown_injection_handler(|store, host, language| {
+            assert_eq!((host, language), ("markdown", "ferris"));
+            let config = GrammarConfig {
+                language: crate::lang_rust::language().into(),
+                highlights_query: crate::lang_rust::HIGHLIGHTS_QUERY,
+                injections_query: "",
+                locals_query: "",
+                textobjects_query: "",
+            };
+            store.register_language(language, config).is_ok()
+        });
+        let html = hl.highlight("markdown", doc).unwrap();
+        assert!(html.contains("<a-k>fn</a-k>"), "got: {html}");
+    }
+
+    #[test]
+    #[cfg(all(feature = "lang-markdown", feature = "lang-rust"))]
+    fn test_unknown_injection_handler_after_highlighting() {
+        use crate::advanced::GrammarConfig;
+
+        // The unknown language is nested in a markdown snippet, which is cached
+        let doc = "````markdown\n```ferris\nfn main() {}\n```\n````\n";
+        let mut hl = Highlighter::new();
+        let html = hl.highlight("markdown", doc).unwrap();
+        assert!(!html.contains("<a-k>fn</a-k>"), "got: {html}");
+
+        hl.set_unknown_injection_handler(|store, _host, language| {
+            let config = GrammarConfig {
+                language: crate::lang_rust::language().into(),
+                highlights_query: crate::lang_rust::HIGHLIGHTS_QUERY,
+                injections_query: "",
+                locals_query: "",
+                textobjects_query: "",
+            };
+            language == "ferris" && store.register_language(language, config).is_ok()
+        });
+        let html = hl.highlight("markdown", doc).unwrap();
+        assert!(html.contains("<a-k>fn</a-k>"), "got: {html}");
+    }
+
+    #[test]
+    #[cfg(all(feature = "lang-markdown", feature = "lang-html"))]
+    fn test_diagnostics() {
+        let mut hl = Highlighter::with_config(Config {
+            fallback: Fallback::Plain,
+            max_injection_depth: 1,
+            ..Default::default()
+        });
+        hl.highlight("no-such-language", "x").unwrap();
+        assert_eq!(
+            hl.take_diagnostics(),
+            [Diagnostic::MissingLanguage {
+                language: "no-such-language".to_string(),
+            }]
+        );
+        assert!(hl.take_diagnostics().is_empty());
+
+        // The HTML block is injected, but the CSS in it would be one level too deep
+        let doc = "```html\n<style>a {}</style>\n```\n";
+        let depth_exceeded = |diagnostics: &[Diagnostic]| {
+            diagnostics.iter().any(|d| {
+                matches!(
+                    d,
+                    Diagnostic::InjectionDepthExceeded { language, .. } if language == "html"
+                )
+            })
+        };
+        hl.highlight("markdown", doc).unwrap();
+        let diagnostics = hl.take_diagnostics();
+        assert!(depth_exceeded(&diagnostics), "{diagnostics:?}");
+
+        // Reported again when the HTML block comes from the injection cache
+        let hits = hl.injection_cache_stats().hits;
+        hl.highlight("markdown", doc).unwrap();
+        assert!(hl.injection_cache_stats().hits > hits);
+        let diagnostics = hl.take_diagnostics();
+        assert!(depth_exceeded(&diagnostics), "{diagnostics:?}");
+    }
+
     #[test]
     #[cfg(feature = "lang-rust")]
     fn test_multithreaded_highlighting() {
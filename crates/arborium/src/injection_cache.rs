@@ -0,0 +1,170 @@
+//! Spans of injected code, remembered across highlight calls.
+//!
+//! Generated docs and static sites repeat the same snippets on every page:
+//! a `<style>` reset, a `use` block, a shell one-liner. The highlighter
+//! remembers the spans of each injected snippet, keyed by language and
+//! content, and reuses them instead of parsing the snippet again.
+//! Diagnostics noticed in the snippet are remembered with them, so they are
+//! reported whether or not the snippet was parsed.
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use arborium_highlight::Span;
+
+use crate::diagnostics::Diagnostic;
+
+/// How often a highlighter's injection cache was used; see
+/// [`Highlighter::injection_cache_stats`](crate::Highlighter::injection_cache_stats).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InjectionCacheStats {
+    /// Injections whose spans were reused.
+    pub hits: u64,
+    /// Injections that were parsed.
+    pub misses: u64,
+    /// Distinct snippets currently remembered.
+    pub entries: usize,
+}
+
+impl InjectionCacheStats {
+    /// The share of injections that were reused, between 0 and 1.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl std::ops::AddAssign for InjectionCacheStats {
+    /// Add up the statistics of several highlighters.
+    fn add_assign(&mut self, other: Self) {
+        self.hits += other.hits;
+        self.misses += other.misses;
+        self.entries += other.entries;
+    }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct Key {
+    language: String,
+    /// Injections nest differently with less depth left.
+    remaining_depth: u32,
+    hash: u64,
+}
+
+pub(crate) struct Entry {
+    /// Compared on lookup, so a hash collision is only a miss.
+    source: Box<str>,
+    /// Relative to the start of the snippet, nested injections included.
+    pub(crate) spans: Vec<Span>,
+    /// Recorded while highlighting the snippet, nested injections included.
+    pub(crate) diagnostics: Vec<Diagnostic>,
+}
+
+/// Injected snippets' spans, by language, depth and content.
+///
+/// Holds up to `capacity` snippets; once full, new snippets aren't added,
+/// since the ones that repeat are usually seen early.
+pub(crate) struct InjectionCache {
+    entries: HashMap<Key, Entry>,
+    capacity: usize,
+    /// The store generation the entries were computed with.
+    generation: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl InjectionCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity,
+            generation: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Drop every entry if the grammars changed since they were computed.
+    pub(crate) fn sync(&mut self, generation: u64) {
+        if generation != self.generation {
+            self.entries.clear();
+            self.generation = generation;
+        }
+    }
+
+    /// Forget every snippet, keeping the statistics.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// The spans and diagnostics of `source` injected as `language`.
+    pub(crate) fn get(
+        &mut self,
+        language: &str,
+        remaining_depth: u32,
+        source: &str,
+    ) -> Option<&Entry> {
+        if self.capacity == 0 {
+            return None;
+        }
+        let key = Key {
+            language: language.to_string(),
+            remaining_depth,
+            hash: hash(source),
+        };
+        match self.entries.get(&key) {
+            Some(entry) if *entry.source == *source => {
+                self.hits += 1;
+                Some(entry)
+            }
+            _ => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Remember the spans of `source`, relative to its start, and the
+    /// diagnostics recorded while highlighting it.
+    pub(crate) fn insert(
+        &mut self,
+        language: &str,
+        remaining_depth: u32,
+        source: &str,
+        spans: Vec<Span>,
+        diagnostics: Vec<Diagnostic>,
+    ) {
+        if self.entries.len() >= self.capacity {
+            return;
+        }
+        let key = Key {
+            language: language.to_string(),
+            remaining_depth,
+            hash: hash(source),
+        };
+        self.entries.insert(
+            key,
+            Entry {
+                source: source.into(),
+                spans,
+                diagnostics,
+            },
+        );
+    }
+
+    pub(crate) fn stats(&self) -> InjectionCacheStats {
+        InjectionCacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.entries.len(),
+        }
+    }
+}
+
+fn hash(source: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
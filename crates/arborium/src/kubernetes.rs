@@ -0,0 +1,98 @@
+//! Kubernetes manifests in YAML and JSON.
+//!
+//! [`kubernetes_keywords`] is a [span filter](crate::Highlighter::add_span_filter)
+//! like [`api_spec_keywords`](crate::api_spec_keywords): it recognizes
+//! manifests by their top-level `apiVersion` and `kind` keys, and makes the
+//! top-level fields (`apiVersion`, `kind`, `metadata`, `spec`, ...) stand out
+//! as keywords and the kind itself as a type. Span filters can't parse, so
+//! the shell scripts in container `command` and `args` lists are injected as
+//! Bash by the YAML grammar's injections query instead.
+
+use std::collections::HashSet;
+
+use arborium_highlight::Span;
+
+use crate::mapping_keys::{mapping_keys, set_captures};
+
+/// Give the top-level keys of Kubernetes manifests `keyword` spans and
+/// their `kind` a `type` span. Other languages and documents are left alone.
+///
+/// Every document of a multi-document YAML stream counts.
+///
+/// ```rust,ignore
+/// let mut hl = Highlighter::new();
+/// hl.add_span_filter(|spans, language, source| {
+///     arborium::kubernetes_keywords(spans, language, source)
+/// });
+/// ```
+pub fn kubernetes_keywords(spans: &mut [Span], language: &str, source: &str) {
+    let Some(keys) = mapping_keys(spans, language, source) else {
+        return;
+    };
+    let top_level: Vec<_> = keys.iter().filter(|key| key.top_level).collect();
+    if !["apiVersion", "kind"]
+        .iter()
+        .all(|name| top_level.iter().any(|key| key.text == *name))
+    {
+        return;
+    }
+
+    let mut keywords = HashSet::new();
+    let mut kinds = HashSet::new();
+    for key in top_level {
+        let span = &spans[key.span];
+        keywords.insert((span.start, span.end));
+        if key.text == "kind"
+            && let Some(value) = value_span(spans, span.end as usize, source)
+        {
+            kinds.insert((value.start, value.end));
+        }
+    }
+    set_captures(spans, &keywords, "keyword");
+    set_captures(spans, &kinds, "type");
+}
+
+/// The string span on the same line after a key ending at `key_end`.
+fn value_span<'a>(spans: &'a [Span], key_end: usize, source: &str) -> Option<&'a Span> {
+    let line_end = source[key_end..]
+        .find('\n')
+        .map_or(source.len(), |i| key_end + i);
+    spans
+        .iter()
+        .filter(|span| span.capture == "string")
+        .filter(|span| (key_end..line_end).contains(&(span.start as usize)))
+        .min_by_key(|span| span.start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mapping_keys::tests::{captured, keys};
+
+    #[test]
+    fn test_manifest() {
+        let source = "apiVersion: apps/v1\nkind: Deployment\nmetadata:\n  name: web\n";
+        let mut spans = keys(source, "property");
+        // The value of `kind`
+        spans.push(Span {
+            start: 26,
+            end: 36,
+            capture: "string".into(),
+        });
+        kubernetes_keywords(&mut spans, "yaml", source);
+        assert_eq!(
+            captured(&spans, source, "keyword"),
+            ["apiVersion", "kind", "metadata"]
+        );
+        assert_eq!(captured(&spans, source, "type"), ["Deployment"]);
+        assert_eq!(captured(&spans, source, "property"), ["name"]);
+    }
+
+    #[test]
+    fn test_not_a_manifest() {
+        let source = "kind: cat\nname: Tom\n";
+        let mut spans = keys(source, "property");
+        kubernetes_keywords(&mut spans, "yaml", source);
+        assert!(captured(&spans, source, "keyword").is_empty());
+    }
+}
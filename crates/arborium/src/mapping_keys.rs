@@ -0,0 +1,226 @@
+//! Mapping keys in YAML and JSON documents, for span filters that give
+//! the keys of a known document type their own captures.
+//!
+//! The keys are found from the grammars' key spans, `property` in YAML and
+//! `string.special.key` in JSON, and put in their place in the document's
+//! structure by indentation or brackets.
+
+use std::collections::HashSet;
+
+use arborium_highlight::Span;
+
+use crate::store::GrammarStore;
+
+/// A mapping key found in the spans.
+pub(crate) struct Key<'a> {
+    /// Index of the key's span.
+    pub(crate) span: usize,
+    /// The key without quotes.
+    pub(crate) text: &'a str,
+    /// The key of the mapping this key's mapping is the value of. `None` at
+    /// the top level and in sequence items.
+    pub(crate) parent: Option<usize>,
+    /// Whether the key is in the top-level mapping.
+    pub(crate) top_level: bool,
+}
+
+/// The mapping keys of a YAML or JSON document, in document order, or
+/// `None` for other languages.
+pub(crate) fn mapping_keys<'a>(
+    spans: &[Span],
+    language: &str,
+    source: &'a str,
+) -> Option<Vec<Key<'a>>> {
+    match &*GrammarStore::normalize_language(language) {
+        "yaml" => Some(yaml_keys(spans, source)),
+        "json" => Some(json_keys(spans, source)),
+        _ => None,
+    }
+}
+
+/// Give every span covering one of `ranges` exactly the given capture.
+///
+/// All of them, since JSON keys also have a `string` span for the same
+/// node, which would otherwise win.
+pub(crate) fn set_captures(
+    spans: &mut [Span],
+    ranges: &HashSet<(u32, u32)>,
+    capture: &'static str,
+) {
+    for span in spans {
+        if ranges.contains(&(span.start, span.end)) {
+            span.capture = capture.into();
+        }
+    }
+}
+
+/// The spans of mapping keys captured with `capture`, in document order.
+fn key_spans(spans: &[Span], capture: &str) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..spans.len())
+        .filter(|&i| spans[i].capture == capture)
+        .collect();
+    indices.sort_by_key(|&i| spans[i].start);
+    indices.dedup_by_key(|i| spans[*i].start);
+    indices
+}
+
+fn unquote(text: &str) -> &str {
+    text.trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// Find YAML block mapping keys and their parents by indentation.
+fn yaml_keys<'a>(spans: &[Span], source: &'a str) -> Vec<Key<'a>> {
+    // (column, key index, whether the key is in a sequence item)
+    let mut stack: Vec<(usize, usize, bool)> = Vec::new();
+    let mut keys = Vec::new();
+
+    for span in key_spans(spans, "property") {
+        let start = spans[span].start as usize;
+        let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let indent = &source[line_start..start];
+        if !indent.chars().all(|c| c == ' ' || c == '-') {
+            continue; // a key in a flow mapping
+        }
+        let column = indent.len();
+
+        // Keys at the same column as a key in a sequence item are in it too
+        let mut in_item = indent.contains('-');
+        while let Some(&(top_column, _, top_in_item)) = stack.last() {
+            if top_column < column {
+                break;
+            }
+            if top_column == column {
+                in_item |= top_in_item;
+            }
+            stack.pop();
+        }
+
+        let index = keys.len();
+        keys.push(Key {
+            span,
+            text: unquote(&source[start..spans[span].end as usize]),
+            parent: if in_item {
+                None
+            } else {
+                stack.last().map(|&(_, key, _)| key)
+            },
+            top_level: stack.is_empty() && !in_item,
+        });
+        stack.push((column, index, in_item));
+    }
+    keys
+}
+
+/// Find JSON object keys and their parents by following brackets.
+fn json_keys<'a>(spans: &[Span], source: &'a str) -> Vec<Key<'a>> {
+    let key_spans = key_spans(spans, "string.special.key");
+    let bytes = source.as_bytes();
+    // Open brackets, with the key whose value they start
+    let mut stack: Vec<(u8, Option<usize>)> = Vec::new();
+    let mut pending = None;
+    let mut next_key = 0;
+    let mut keys = Vec::new();
+
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                pending = None;
+                while next_key < key_spans.len()
+                    && (spans[key_spans[next_key]].start as usize) < start
+                {
+                    next_key += 1;
+                }
+                if let Some(&span) = key_spans.get(next_key)
+                    && spans[span].start as usize == start
+                {
+                    let parent = match stack.last() {
+                        Some(&(b'{', key)) => key,
+                        _ => None,
+                    };
+                    pending = Some(keys.len());
+                    keys.push(Key {
+                        span,
+                        text: unquote(&source[start..spans[span].end as usize]),
+                        parent,
+                        top_level: stack.len() == 1,
+                    });
+                }
+            }
+            open @ (b'{' | b'[') => stack.push((open, pending.take())),
+            b'}' | b']' => {
+                stack.pop();
+            }
+            b',' => pending = None,
+            _ => {}
+        }
+        i += 1;
+    }
+    keys
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Key spans for every `"name":` or `name:` in `source`.
+    pub(crate) fn keys(source: &str, capture: &'static str) -> Vec<Span> {
+        let mut spans = Vec::new();
+        for (i, _) in source.match_indices(':') {
+            let before = &source[..i];
+            let start = before
+                .strip_suffix('"')
+                .and_then(|quoted| quoted.rfind('"'))
+                .unwrap_or_else(|| before.rfind([' ', '\n', '-']).map_or(0, |j| j + 1));
+            spans.push(Span {
+                start: start as u32,
+                end: i as u32,
+                capture: capture.into(),
+            });
+        }
+        spans
+    }
+
+    /// The unquoted text of the spans with the given capture.
+    pub(crate) fn captured(spans: &[Span], source: &str, capture: &str) -> Vec<String> {
+        spans
+            .iter()
+            .filter(|span| span.capture == capture)
+            .map(|span| unquote(&source[span.start as usize..span.end as usize]).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_keys_lone_quote() {
+        let source = "a\": 1\n\"b\": 2\n";
+        let spans = keys(source, "property");
+        assert_eq!(captured(&spans, source, "property"), ["a", "b"]);
+    }
+
+    #[test]
+    fn test_yaml_parents() {
+        let source = "a:\n  b:\n    - c: 1\n      d: 2\n  e: 3\nf: 4\n";
+        let spans = keys(source, "property");
+        let keys = mapping_keys(&spans, "yaml", source).unwrap();
+        let parents: Vec<(&str, Option<&str>, bool)> = keys
+            .iter()
+            .map(|key| (key.text, key.parent.map(|p| keys[p].text), key.top_level))
+            .collect();
+        assert_eq!(
+            parents,
+            [
+                ("a", None, true),
+                ("b", Some("a"), false),
+                ("c", None, false),
+                ("d", None, false),
+                ("e", Some("a"), false),
+                ("f", None, true),
+            ]
+        );
+    }
+}
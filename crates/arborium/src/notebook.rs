@@ -0,0 +1,216 @@
+//! Jupyter notebook (`.ipynb`) rendering.
+//!
+//! Highlights every cell of a notebook and emits a single HTML fragment:
+//! code cells in the kernel's language, markdown cells through the markdown
+//! grammar (including its fenced code block injections), and text outputs as
+//! plain preformatted text.
+//!
+//! ```rust,ignore
+//! use arborium::notebook::highlight_ipynb;
+//! use arborium::theme::builtin;
+//!
+//! let json = std::fs::read_to_string("analysis.ipynb")?;
+//! let html = highlight_ipynb(&json, &builtin::github_light())?;
+//! ```
+//!
+//! The output looks like this, with the theme's CSS scoped to the wrapper:
+//!
+//! ```html
+//! <style>.arborium-notebook a-k { ... }</style>
+//! <div class="arborium-notebook">
+//!   <div class="cell code" data-language="python"><pre><code>...</code></pre>
+//!     <pre class="output">...</pre></div>
+//!   <div class="cell markdown"><pre><code>...</code></pre></div>
+//! </div>
+//! ```
+
+use std::fmt::Write;
+
+use serde_json::Value;
+
+use crate::Highlighter;
+use crate::advanced::html_escape;
+use crate::error::Error;
+use crate::theme::Theme;
+
+/// Kernel language assumed when the notebook metadata doesn't name one.
+const DEFAULT_LANGUAGE: &str = "python";
+
+/// Highlight a notebook with a fresh [`Highlighter`].
+pub fn highlight_ipynb(json: &str, theme: &Theme) -> Result<String, Error> {
+    highlight_ipynb_with(&mut Highlighter::new(), json, theme)
+}
+
+/// Highlight a notebook, reusing an existing highlighter and its grammars.
+///
+/// Code in a language arborium doesn't support is emitted as escaped plain
+/// text rather than failing the whole notebook. Only malformed notebook JSON
+/// is an error.
+///
+/// The CSS covers the tags the highlighter rendered, from
+/// [`Highlighter::take_used_tags`], so languages injected into markdown cells
+/// are styled too.
+pub fn highlight_ipynb_with(
+    highlighter: &mut Highlighter,
+    json: &str,
+    theme: &Theme,
+) -> Result<String, Error> {
+    let notebook: Value = serde_json::from_str(json).map_err(|e| invalid(e.to_string()))?;
+    let cells = notebook["cells"]
+        .as_array()
+        .ok_or_else(|| invalid("missing `cells` array".to_string()))?;
+    let language = kernel_language(&notebook);
+    let language = language.as_str();
+
+    let mut html = String::new();
+    html.push_str("<div class=\"arborium-notebook\">\n");
+
+    for cell in cells {
+        let source = cell_text(&cell["source"]);
+        match cell["cell_type"].as_str() {
+            Some("code") => {
+                let _ = write!(
+                    html,
+                    "<div class=\"cell code\" data-language=\"{}\">",
+                    html_escape(language)
+                );
+                write_block(&mut html, highlighter, language, &source);
+                for output in cell["outputs"].as_array().into_iter().flatten() {
+                    write_output(&mut html, output);
+                }
+            }
+            Some("markdown") => {
+                html.push_str("<div class=\"cell markdown\">");
+                write_block(&mut html, highlighter, "markdown", &source);
+            }
+            _ => {
+                html.push_str("<div class=\"cell raw\">");
+                let _ = write!(html, "<pre><code>{}</code></pre>", html_escape(&source));
+            }
+        }
+        html.push_str("</div>\n");
+    }
+
+    html.push_str("</div>\n");
+
+    let css = theme.to_css_for_tags(".arborium-notebook", &highlighter.take_used_tags());
+    Ok(format!("<style>{css}</style>\n{html}"))
+}
+
+/// The notebook's kernel language, lowercased (kernels say `"Python"` too).
+fn kernel_language(notebook: &Value) -> String {
+    let metadata = &notebook["metadata"];
+    metadata["kernelspec"]["language"]
+        .as_str()
+        .or_else(|| metadata["language_info"]["name"].as_str())
+        .unwrap_or(DEFAULT_LANGUAGE)
+        .to_lowercase()
+}
+
+/// Notebook text fields are either a string or an array of lines.
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(lines) => lines.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+fn write_block(html: &mut String, highlighter: &mut Highlighter, language: &str, source: &str) {
+    html.push_str("<pre><code>");
+    let start = html.len();
+    if highlighter
+        .highlight_to_fmt(html, language, source)
+        .is_err()
+    {
+        html.truncate(start);
+        html.push_str(&html_escape(source));
+    }
+    html.push_str("</code></pre>");
+}
+
+/// Write the text form of an output: streams, results, and error tracebacks.
+fn write_output(html: &mut String, output: &Value) {
+    let text = match output["output_type"].as_str() {
+        Some("stream") => cell_text(&output["text"]),
+        Some("execute_result" | "display_data") => cell_text(&output["data"]["text/plain"]),
+        Some("error") => cell_text(&output["traceback"]),
+        _ => return,
+    };
+    if !text.is_empty() {
+        let _ = write!(html, "<pre class=\"output\">{}</pre>", html_escape(&text));
+    }
+}
+
+fn invalid(message: String) -> Error {
+    Error::ParseError {
+        language: "ipynb".to_string(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::builtin;
+
+    const NOTEBOOK: &str = r##"{
+        "metadata": {"kernelspec": {"language": "python", "name": "python3"}},
+        "nbformat": 4,
+        "cells": [
+            {"cell_type": "markdown", "source": ["# Title\n", "Some *text*"]},
+            {"cell_type": "code", "source": "print('hi')", "outputs": [
+                {"output_type": "stream", "name": "stdout", "text": ["hi\n"]}
+            ]},
+            {"cell_type": "raw", "source": "<raw>"}
+        ]
+    }"##;
+
+    #[test]
+    fn test_notebook_structure() {
+        let html = highlight_ipynb(NOTEBOOK, &builtin::github_light()).unwrap();
+        assert!(html.starts_with("<style>"));
+        assert!(html.contains("<div class=\"cell code\" data-language=\"python\">"));
+        assert!(html.contains("<pre class=\"output\">hi\n</pre>"));
+        assert!(html.contains("<div class=\"cell raw\"><pre><code>&lt;raw&gt;</code></pre>"));
+    }
+
+    #[test]
+    #[cfg(all(feature = "lang-python", feature = "lang-markdown"))]
+    fn test_notebook_highlighting() {
+        let html = highlight_ipynb(NOTEBOOK, &builtin::github_light()).unwrap();
+        assert!(html.contains("<a-s>"), "python string highlighted: {html}");
+        assert!(
+            html.contains("<a-tt>"),
+            "markdown heading highlighted: {html}"
+        );
+    }
+
+    #[test]
+    #[cfg(all(
+        feature = "lang-python",
+        feature = "lang-markdown",
+        feature = "lang-rust"
+    ))]
+    fn test_notebook_css_covers_injections() {
+        let notebook = r##"{
+            "metadata": {"kernelspec": {"language": "python"}},
+            "cells": [
+                {"cell_type": "markdown", "source": "```rust\nprintln!(\"hi\");\n```\n"}
+            ]
+        }"##;
+        let html = highlight_ipynb(notebook, &builtin::github_light()).unwrap();
+        assert!(html.contains("<a-m>"), "rust macro highlighted: {html}");
+        assert!(html.contains("a-m {"), "rust macro styled: {html}");
+    }
+
+    #[test]
+    fn test_invalid_notebook() {
+        let theme = &builtin::github_light();
+        assert!(matches!(
+            highlight_ipynb("not json", theme),
+            Err(Error::ParseError { .. })
+        ));
+        assert!(highlight_ipynb("{}", theme).is_err());
+    }
+}
@@ -0,0 +1,111 @@
+//! Runtime query loading for grammar development (debug builds only).
+//!
+//! When `ARBORIUM_QUERY_DIR` is set, highlighters read each language's queries
+//! from that directory instead of the compiled-in constants, and re-read them
+//! whenever they change on disk. Two layouts are supported:
+//!
+//! - `$ARBORIUM_QUERY_DIR/<lang>/highlights.scm`
+//! - `$ARBORIUM_QUERY_DIR/group-*/<lang>/def/queries/highlights.scm`, so the
+//!   repository's `langs/` directory can be used as-is
+//!
+//! Languages without a `highlights.scm` there keep their compiled-in queries.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::error::Error;
+use crate::store::GrammarStore;
+
+/// Environment variable pointing at the query directory.
+pub(crate) const QUERY_DIR_ENV: &str = "ARBORIUM_QUERY_DIR";
+
+const QUERY_FILES: [&str; 3] = ["highlights.scm", "injections.scm", "locals.scm"];
+
+/// Tracks on-disk queries and swaps them into a [`GrammarStore`] when they change.
+pub(crate) struct QueryDir {
+    root: PathBuf,
+    /// Modification time of the queries last loaded for each language.
+    loaded: HashMap<String, SystemTime>,
+}
+
+impl QueryDir {
+    /// Create a query dir from `ARBORIUM_QUERY_DIR`, if set.
+    pub(crate) fn from_env() -> Option<Self> {
+        let root = std::env::var_os(QUERY_DIR_ENV)?;
+        Some(Self {
+            root: root.into(),
+            loaded: HashMap::new(),
+        })
+    }
+
+    /// Load the language's queries into the store if they are new or changed.
+    ///
+    /// A failed reload is retried on the next call, so the query error keeps
+    /// being reported until the file is fixed.
+    pub(crate) fn refresh(&mut self, store: &GrammarStore, language: &str) -> Result<(), Error> {
+        let language = GrammarStore::normalize_language(language);
+        let Some(dir) = self.find(&language) else {
+            return Ok(());
+        };
+
+        let modified = QUERY_FILES
+            .iter()
+            .filter_map(|f| std::fs::metadata(dir.join(f)).ok()?.modified().ok())
+            .max()
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        if self.loaded.get(&*language) == Some(&modified) {
+            return Ok(());
+        }
+
+        let [highlights, injections, locals] = QUERY_FILES.map(|f| read_or_empty(&dir.join(f)));
+        store.set_queries(&language, &highlights, &injections, &locals)?;
+        self.loaded.insert(language.into_owned(), modified);
+        Ok(())
+    }
+
+    /// Find the directory holding a language's queries.
+    fn find(&self, language: &str) -> Option<PathBuf> {
+        let flat = self.root.join(language);
+        if flat.join("highlights.scm").is_file() {
+            return Some(flat);
+        }
+
+        std::fs::read_dir(&self.root)
+            .ok()?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with("group-"))
+            .map(|e| e.path().join(language).join("def/queries"))
+            .find(|dir| dir.join("highlights.scm").is_file())
+    }
+}
+
+fn read_or_empty(path: &Path) -> String {
+    std::fs::read_to_string(path).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_layouts() {
+        let root = std::env::temp_dir().join(format!("arborium-query-dir-{}", std::process::id()));
+        let flat = root.join("foo");
+        let grouped = root.join("group-acorn/bar/def/queries");
+        std::fs::create_dir_all(&flat).unwrap();
+        std::fs::create_dir_all(&grouped).unwrap();
+        std::fs::write(flat.join("highlights.scm"), "").unwrap();
+        std::fs::write(grouped.join("highlights.scm"), "").unwrap();
+
+        let dir = QueryDir {
+            root: root.clone(),
+            loaded: HashMap::new(),
+        };
+        assert_eq!(dir.find("foo"), Some(flat));
+        assert_eq!(dir.find("bar"), Some(grouped));
+        assert_eq!(dir.find("baz"), None);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,197 @@
+//! Line and word diffs between two versions of a snippet.
+//!
+//! Used by [`Highlighter::highlight_diff`](crate::Highlighter::highlight_diff).
+//! Lines are matched by their longest common subsequence. Within each block
+//! of changed lines, removed and added lines are paired up in order, and
+//! every pair is diffed again word by word. The changed words become overlay
+//! spans, so they're marked on top of the syntax highlighting.
+
+use std::ops::Range;
+
+/// Capture for changed words in an added line.
+pub(crate) const ADDED_CAPTURE: &str = "diff.addition.inline";
+
+/// Capture for changed words in a removed line.
+pub(crate) const REMOVED_CAPTURE: &str = "diff.deletion.inline";
+
+/// A run of lines in a line diff, by line index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Block {
+    /// A line in both versions.
+    Same { old: usize, new: usize },
+    /// Lines of the old version replaced by lines of the new one. Either
+    /// range may be empty.
+    Changed {
+        old: Range<usize>,
+        new: Range<usize>,
+    },
+}
+
+/// Diff two versions line by line.
+pub(crate) fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for (old_idx, new_idx) in common_subsequence(old, new)
+        .into_iter()
+        .chain([(old.len(), new.len())])
+    {
+        if i < old_idx || j < new_idx {
+            blocks.push(Block::Changed {
+                old: i..old_idx,
+                new: j..new_idx,
+            });
+        }
+        if old_idx < old.len() {
+            blocks.push(Block::Same {
+                old: old_idx,
+                new: new_idx,
+            });
+        }
+        (i, j) = (old_idx + 1, new_idx + 1);
+    }
+    blocks
+}
+
+/// Diff two lines word by word.
+///
+/// Returns the byte ranges of the words only in `old` and of the words only
+/// in `new`. Adjacent changed words are merged.
+pub(crate) fn diff_words(old: &str, new: &str) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let old_words = words(old);
+    let new_words = words(new);
+    let old_texts: Vec<&str> = old_words.iter().map(|range| &old[range.clone()]).collect();
+    let new_texts: Vec<&str> = new_words.iter().map(|range| &new[range.clone()]).collect();
+    let common = common_subsequence(&old_texts, &new_texts);
+
+    let changed = |words: &[Range<usize>], kept: &mut dyn Iterator<Item = usize>| {
+        let mut kept = kept.peekable();
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for (i, word) in words.iter().enumerate() {
+            if kept.next_if_eq(&i).is_some() {
+                continue;
+            }
+            match ranges.last_mut() {
+                Some(last) if last.end == word.start => last.end = word.end,
+                _ => ranges.push(word.clone()),
+            }
+        }
+        ranges
+    };
+    (
+        changed(&old_words, &mut common.iter().map(|&(i, _)| i)),
+        changed(&new_words, &mut common.iter().map(|&(_, j)| j)),
+    )
+}
+
+/// Split a line into words: runs of letters, digits and underscores, runs
+/// of whitespace, and single other characters.
+fn words(line: &str) -> Vec<Range<usize>> {
+    #[derive(PartialEq)]
+    enum Kind {
+        Word,
+        Space,
+        Other,
+    }
+    let kind_of = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            Kind::Word
+        } else if c.is_whitespace() {
+            Kind::Space
+        } else {
+            Kind::Other
+        }
+    };
+
+    let mut words: Vec<Range<usize>> = Vec::new();
+    let mut last_kind = None;
+    for (i, c) in line.char_indices() {
+        let end = i + c.len_utf8();
+        let kind = kind_of(c);
+        match words.last_mut() {
+            Some(last) if kind != Kind::Other && last_kind.as_ref() == Some(&kind) => {
+                last.end = end
+            }
+            _ => words.push(i..end),
+        }
+        last_kind = Some(kind);
+    }
+    words
+}
+
+/// Index pairs of a longest common subsequence of `a` and `b`, in order.
+///
+/// The common prefix and suffix are matched directly, and only the middle
+/// goes through the quadratic table.
+fn common_subsequence<T: PartialEq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a_mid, b_mid) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    // lengths[i][j]: length of the LCS of a_mid[i..] and b_mid[j..]
+    let width = b_mid.len() + 1;
+    let mut lengths = vec![0u32; (a_mid.len() + 1) * width];
+    for i in (0..a_mid.len()).rev() {
+        for j in (0..b_mid.len()).rev() {
+            lengths[i * width + j] = if a_mid[i] == b_mid[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut pairs: Vec<(usize, usize)> = (0..prefix).map(|i| (i, i)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < a_mid.len() && j < b_mid.len() {
+        if a_mid[i] == b_mid[j] {
+            pairs.push((prefix + i, prefix + j));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs.extend((0..suffix).map(|k| (a.len() - suffix + k, b.len() - suffix + k)));
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let old = ["a", "b", "c", "d"];
+        let new = ["a", "x", "c", "d", "e"];
+        assert_eq!(
+            diff_lines(&old, &new),
+            vec![
+                Block::Same { old: 0, new: 0 },
+                Block::Changed {
+                    old: 1..2,
+                    new: 1..2
+                },
+                Block::Same { old: 2, new: 2 },
+                Block::Same { old: 3, new: 3 },
+                Block::Changed {
+                    old: 4..4,
+                    new: 4..5
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_words() {
+        let (old, new) = diff_words("let x = foo(1);", "let x = bar(1, 2);");
+        assert_eq!(old, vec![8..11]);
+        assert_eq!(new, vec![8..11, 13..16]);
+    }
+}
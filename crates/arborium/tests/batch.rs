@@ -0,0 +1,17 @@
+//! Batch tests.
+//!
+//! Tests that verify batch scripts are highlighted under the `cmd` alias.
+
+#![cfg(feature = "lang-batch")]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_script() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight("cmd", "@echo off\r\nset /A count=1\r\necho %count%\r\n")
+        .unwrap();
+    assert!(html.contains("<a-k>@echo off</a-k>"), "{html}");
+    assert!(html.contains("<a-v>%count%</a-v>"), "{html}");
+}
@@ -0,0 +1,16 @@
+//! CMake injection tests.
+//!
+//! Tests that verify `sh -c` commands are injected as shell.
+
+#![cfg(all(feature = "lang-cmake", feature = "lang-bash"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_shell_injection() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight("cmake", "execute_process(COMMAND sh -c \"echo hi\")\n")
+        .unwrap();
+    assert!(html.contains("<a-f>echo</a-f>"), "{html}");
+}
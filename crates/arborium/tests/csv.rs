@@ -0,0 +1,44 @@
+//! CSV rainbow column tests.
+//!
+//! Tests that verify fields are highlighted by column.
+
+#![cfg(feature = "lang-csv")]
+
+use arborium::Highlighter;
+use arborium_highlight::tree_sitter::RAINBOW_CAPTURES;
+
+#[test]
+fn test_rainbow_columns() {
+    let mut hl = Highlighter::new();
+    let spans = hl
+        .highlight_spans("csv", "ab,\"c,d\",,ef\ngh,ij\n")
+        .unwrap();
+    let capture_at = |offset: u32| {
+        spans
+            .iter()
+            .find(|s| s.start == offset)
+            .map(|s| s.capture.as_ref())
+    };
+
+    // Columns 0, 1 and 3: the empty field still counts
+    assert_eq!(capture_at(0), Some(RAINBOW_CAPTURES[0]));
+    assert_eq!(capture_at(3), Some(RAINBOW_CAPTURES[1]));
+    assert_eq!(capture_at(10), Some(RAINBOW_CAPTURES[3]));
+    // Each row starts over
+    assert_eq!(capture_at(13), Some(RAINBOW_CAPTURES[0]));
+    assert_eq!(capture_at(16), Some(RAINBOW_CAPTURES[1]));
+}
+
+#[test]
+fn test_rainbow_capture_names() {
+    // `@rainbow` is listed as the captures it turns into
+    let hl = Highlighter::new();
+    let captures = hl.captures_for("csv").unwrap();
+    assert!(!captures.iter().any(|c| c == "rainbow"));
+    assert!(
+        RAINBOW_CAPTURES
+            .iter()
+            .all(|r| captures.iter().any(|c| c == r))
+    );
+    assert!(captures.iter().any(|c| c == "punctuation.delimiter"));
+}
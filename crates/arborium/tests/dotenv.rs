@@ -0,0 +1,17 @@
+//! dotenv tests.
+//!
+//! Tests that verify values of secret-looking variables are told apart.
+
+#![cfg(feature = "lang-dotenv")]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_secrets() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight("env", "GITHUB_TOKEN=ghp_123\nKEYBOARD=us\n")
+        .unwrap();
+    assert!(html.contains("<a-sk>ghp_123</a-sk>"), "{html}");
+    assert!(html.contains("<a-s>us</a-s>"), "{html}");
+}
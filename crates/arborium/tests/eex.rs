@@ -0,0 +1,14 @@
+//! EEx injection tests.
+//!
+//! Tests that verify the code in EEx tags is injected as Elixir.
+
+#![cfg(all(feature = "lang-elixir", feature = "lang-eex"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_elixir_injection() {
+    let mut hl = Highlighter::new();
+    let html = hl.highlight("eex", "<%= if @ok do %>yes<% end %>").unwrap();
+    assert!(html.contains("<a-k>do</a-k>"), "{html}");
+}
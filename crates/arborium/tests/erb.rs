@@ -0,0 +1,18 @@
+//! ERB injection tests.
+//!
+//! Tests that verify ERB templates inject HTML around their tags and Ruby
+//! inside them.
+
+#![cfg(all(feature = "lang-erb", feature = "lang-html", feature = "lang-ruby"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_html_and_ruby_injections() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight("erb", "<ul>\n<% xs.each do |x| %>\n  <li><%= x %></li>\n")
+        .unwrap();
+    assert!(html.contains("li</a-tg>"), "{html}");
+    assert!(html.contains("<a-k>do</a-k>"), "{html}");
+}
@@ -0,0 +1,25 @@
+//! Git commit message tests.
+//!
+//! Tests that verify commit messages are highlighted, with the diff below
+//! the scissors line injected.
+
+#![cfg(all(feature = "lang-git-commit", feature = "lang-diff"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_commit_message() {
+    let mut hl = Highlighter::new();
+    let message = "feat: add cache\n\nSigned-off-by: A U Thor <a@example.com>\n\
+                   # ------------------------ >8 ------------------------\n\
+                   diff --git a/x b/x\n+added\n";
+    let html = hl.highlight("gitcommit", message).unwrap();
+    assert!(html.contains("<a-k>feat</a-k>"), "{html}");
+    assert!(html.contains("<a-pr>Signed-off-by: </a-pr>"), "{html}");
+    assert!(html.contains("<a-da>+added"), "{html}");
+    assert_eq!(
+        arborium::detect_language(".git/COMMIT_EDITMSG"),
+        Some("git-commit")
+    );
+    assert_eq!(arborium::detect_language(".gitignore"), Some("gitignore"));
+}
@@ -0,0 +1,24 @@
+//! HCL injection tests.
+//!
+//! Tests that verify heredocs and decoded strings are injected in their
+//! language.
+
+#![cfg(all(feature = "lang-hcl", feature = "lang-json"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_heredoc_and_decode_injections() {
+    let mut hl = Highlighter::new();
+
+    // Heredoc tagged with a language, matched case-insensitively
+    let html = hl
+        .highlight("terraform", "policy = <<JSON\n[42]\nJSON\n")
+        .unwrap();
+    assert!(html.contains("<a-n>42</a-n>"), "{html}");
+
+    let html = hl
+        .highlight("hcl", "x = jsondecode(\"[42]\")\ny = <<EOT\n[42]\nEOT\n")
+        .unwrap();
+    assert_eq!(html.matches("<a-n>42</a-n>").count(), 1, "{html}");
+}
@@ -0,0 +1,36 @@
+//! HEEx injection tests.
+//!
+//! Tests that verify HEEx templates inject Elixir, and Elixir `~H` sigils
+//! inject HEEx.
+
+#![cfg(all(feature = "lang-elixir", feature = "lang-heex"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_elixir_injection() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight(
+            "heex",
+            "<.link :if={@ok} navigate={fn -> 1 end}>Hi</.link>\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-f>link</a-f>"), "{html}");
+    assert!(html.contains("<a-k>:if</a-k>"), "{html}");
+    assert!(html.contains("<a-k>fn</a-k>"), "Elixir injected: {html}");
+}
+
+#[test]
+fn test_sigil_injection() {
+    let mut hl = Highlighter::new();
+
+    // ~H sigils in Elixir inject HEEx, which injects Elixir again
+    let html = hl
+        .highlight(
+            "elixir",
+            "def hi(assigns), do: ~H\"<p>{fn -> 1 end}</p>\"\n",
+        )
+        .unwrap();
+    assert!(html.contains("p</a-tg>"), "{html}");
+}
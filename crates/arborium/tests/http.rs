@@ -0,0 +1,59 @@
+//! HTTP message tests.
+//!
+//! Tests that verify messages are highlighted, with bodies injected in the
+//! language of their Content-Type.
+
+#![cfg(all(feature = "lang-http", feature = "lang-json"))]
+
+use arborium::{Config, Diagnostic, Highlighter, UnknownInjections};
+use arborium_test_support::{Event, assert_text_highlighted, record_events};
+use indoc::indoc;
+
+#[test]
+fn test_content_type_injection() {
+    let mut hl = Highlighter::new();
+    let exchange = "GET /users/1 HTTP/1.1\nAccept: application/json\n\n\
+                    HTTP/1.1 200 OK\nContent-Type: application/json\n\n{\"id\": 1}\n";
+    let html = hl.highlight("http", exchange).unwrap();
+    assert!(html.contains("<a-k>GET</a-k>"), "{html}");
+    assert!(html.contains("<a-n>200</a-n>"), "{html}");
+    assert!(html.contains("<a-pr>Content-Type</a-pr>"), "{html}");
+    assert!(html.contains("<a-n>1</a-n>}"), "{html}");
+    assert_eq!(arborium::detect_language("api.http"), Some("http"));
+}
+
+#[test]
+fn test_structured_syntax_suffix() {
+    let mut highlighter = Highlighter::with_config(Config {
+        unknown_injections: UnknownInjections::Warn,
+        ..Default::default()
+    });
+    let source = indoc! {r#"
+        HTTP/1.1 404 Not Found
+        Content-Type: application/problem+json
+
+        {"title": "Not Found"}
+    "#};
+    let events = record_events(&mut highlighter, "http", source);
+
+    assert_text_highlighted(
+        &events,
+        "\"title\"",
+        "string.special.key",
+        "HTTP suffix injection",
+    );
+    let keys = events
+        .iter()
+        .filter(|e| matches!(e, Event::Start { name } if name == "string.special.key"))
+        .count();
+    assert_eq!(keys, 1, "the body is injected once");
+
+    // Not also injected as the subtype, which has no grammar
+    let diagnostics = highlighter.take_diagnostics();
+    assert!(
+        !diagnostics
+            .iter()
+            .any(|d| matches!(d, Diagnostic::UnknownInjection { .. })),
+        "{diagnostics:?}"
+    );
+}
@@ -0,0 +1,20 @@
+//! LLVM IR tests.
+//!
+//! Tests that verify functions and instructions are highlighted.
+
+#![cfg(feature = "lang-llvm")]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_function() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight(
+            "ll",
+            "define i32 @inc(i32 %x) {\n  %y = add i32 %x, 1\n  ret i32 %y\n}\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-f>@inc</a-f>"), "{html}");
+    assert!(html.contains("<a-k>add</a-k>"), "{html}");
+}
@@ -0,0 +1,20 @@
+//! Make injection tests.
+//!
+//! Tests that verify recipes are injected as shell.
+
+#![cfg(all(feature = "lang-make", feature = "lang-bash"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_recipe_injection() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight("make", "all: main.o\n\t@echo \"linking $@\"\n")
+        .unwrap();
+    assert!(
+        html.contains("<a-f>echo</a-f>"),
+        "recipe highlighted as bash: {html}"
+    );
+    assert!(html.contains("<a-f>all</a-f>"), "{html}");
+}
@@ -0,0 +1,21 @@
+//! Meson injection tests.
+//!
+//! Tests that verify `sh -c` commands are injected as shell.
+
+#![cfg(all(feature = "lang-meson", feature = "lang-bash"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_shell_injection() {
+    let mut hl = Highlighter::new();
+
+    // Only `sh -c` arguments are shell; the quotes stay meson strings.
+    let html = hl
+        .highlight(
+            "meson",
+            "run_command('sh', '-c', 'echo hi')\nrun_command('echo')\n",
+        )
+        .unwrap();
+    assert_eq!(html.matches("<a-f>echo</a-f>").count(), 1, "{html}");
+}
@@ -0,0 +1,16 @@
+//! MLIR tests.
+//!
+//! Tests that verify functions and operations are highlighted.
+
+#![cfg(feature = "lang-mlir")]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_function() {
+    let mut hl = Highlighter::new();
+    let func = "func.func @inc(%x: i32) -> i32 {\n  %y = arith.addi %x, %x : i32\n}\n";
+    let html = hl.highlight("mlir", func).unwrap();
+    assert!(html.contains("<a-s>@inc</a-s>"), "{html}");
+    assert!(html.contains("<a-f>arith.addi</a-f>"), "{html}");
+}
@@ -0,0 +1,18 @@
+//! Move tests.
+//!
+//! Tests that verify functions, parameters and modules are highlighted.
+
+#![cfg(feature = "lang-move")]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_function() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight("move", "public fun value(c: &Coin): u64 { coin::value(c) }")
+        .unwrap();
+    assert!(html.contains("<a-f>value</a-f>"), "{html}");
+    assert!(html.contains("<a-v>c</a-v>"), "{html}");
+    assert!(html.contains("<a-ns>coin</a-ns>"), "{html}");
+}
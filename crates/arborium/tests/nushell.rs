@@ -0,0 +1,21 @@
+//! Nushell tests.
+//!
+//! Tests that verify pipelines are highlighted and `nu` scripts detected.
+
+#![cfg(feature = "lang-nushell")]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_pipeline() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight("nu", "ls | where size > 10kb | get name | str join ', '")
+        .unwrap();
+    assert!(html.contains("<a-f>where</a-f>"), "{html}");
+    assert!(html.contains("<a-f>join</a-f>"), "{html}");
+    assert_eq!(
+        arborium::detect_language_from_content("#!/usr/bin/env nu\nls"),
+        Some("nushell")
+    );
+}
@@ -0,0 +1,20 @@
+//! Perl injection tests.
+//!
+//! Tests that verify heredocs are injected in the language they are named
+//! after.
+
+#![cfg(all(feature = "lang-perl", feature = "lang-sql"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_heredoc_injection() {
+    let mut hl = Highlighter::new();
+
+    // The closing delimiter is not part of the injected SQL
+    let html = hl
+        .highlight("perl", "my $q = <<~SQL;\n  SELECT 1\n  SQL\n")
+        .unwrap();
+    assert!(html.contains("<a-k>SELECT</a-k>"), "{html}");
+    assert_eq!(html.matches("SQL").count(), 2, "{html}");
+}
@@ -0,0 +1,19 @@
+//! PHP injection tests.
+//!
+//! Tests that verify the text around PHP tags is injected as HTML.
+
+#![cfg(all(feature = "lang-php", feature = "lang-html"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_html_injection() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight(
+            "php",
+            "<ul>\n<?php foreach ($xs as $x): ?>\n  <li><?= $x ?></li>\n",
+        )
+        .unwrap();
+    assert!(html.contains("li</a-tg>"), "{html}");
+}
@@ -0,0 +1,20 @@
+//! PowerShell tests.
+//!
+//! Tests that verify commands and their parameters are highlighted.
+
+#![cfg(feature = "lang-powershell")]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_parameters() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight(
+            "pwsh",
+            "Invoke-WebRequest -Uri $url -OutFile $env:TEMP\\setup.exe",
+        )
+        .unwrap();
+    assert!(html.contains("<a-f>Invoke-WebRequest</a-f>"), "{html}");
+    assert!(html.contains("-Uri</a-"), "{html}");
+}
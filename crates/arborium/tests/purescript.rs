@@ -0,0 +1,17 @@
+//! PureScript tests.
+//!
+//! Tests that verify signatures and record fields are highlighted.
+
+#![cfg(feature = "lang-purescript")]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_signatures() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight("purs", "run :: { step :: Int } -> Int\nrun c = c.step\n")
+        .unwrap();
+    assert!(html.contains("<a-f>run</a-f>"), "{html}");
+    assert!(html.contains("<a-pr>step</a-pr>"), "{html}");
+}
@@ -0,0 +1,15 @@
+//! R alias tests.
+//!
+//! Tests that verify R is found by its uppercase alias and extension.
+
+#![cfg(feature = "lang-r")]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_aliases() {
+    let mut hl = Highlighter::new();
+    let lower = hl.highlight("r", "x <- c(1, 2) # two\n").unwrap();
+    assert_eq!(hl.highlight("R", "x <- c(1, 2) # two\n").unwrap(), lower);
+    assert_eq!(arborium::detect_language("analysis.R"), Some("r"));
+}
@@ -0,0 +1,17 @@
+//! Ruby injection tests.
+//!
+//! Tests that verify heredocs are injected in the language they are named
+//! after.
+
+#![cfg(all(feature = "lang-ruby", feature = "lang-sql"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_heredoc_injection() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight("ruby", "q = <<~SQL\n  SELECT 1\nSQL\n")
+        .unwrap();
+    assert!(html.contains("<a-k>SELECT</a-k>"), "{html}");
+}
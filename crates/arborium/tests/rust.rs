@@ -0,0 +1,19 @@
+//! Rust injection tests.
+//!
+//! Tests that verify inline assembly templates are injected as assembly.
+
+#![cfg(all(feature = "lang-rust", feature = "lang-asm"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_asm_injection() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight(
+            "rust",
+            "unsafe { core::arch::asm!(\"mov eax, 5\", out(\"eax\") x) }",
+        )
+        .unwrap();
+    assert!(html.contains("mov</a-"), "asm template highlighted: {html}");
+}
@@ -0,0 +1,20 @@
+//! Shell session tests.
+//!
+//! Tests that verify session transcripts are highlighted as shell code.
+
+#![cfg(feature = "lang-bash")]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_console_is_bash() {
+    let mut hl = Highlighter::new();
+    let source = "cat Cargo.toml | grep name\n";
+    let html = hl.highlight("console", source).unwrap();
+    assert!(html.contains("<a-f>cat</a-f>"), "{html}");
+    assert_eq!(html, hl.highlight("bash", source).unwrap());
+    assert_eq!(
+        arborium::detect_language("install.sh-session"),
+        Some("bash")
+    );
+}
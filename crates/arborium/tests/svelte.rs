@@ -5,6 +5,7 @@
 #![cfg(feature = "lang-svelte")]
 
 use arborium::Highlighter;
+use arborium_test_support::{assert_has_highlights, assert_text_highlighted, record_events};
 use indoc::indoc;
 
 /// Check that HTML contains specific highlight tags
@@ -89,7 +90,7 @@ fn test_isolated_style() {
             }
         </style>
     "#};
-    let events = record_events(&mut highlighter, source);
+    let events = record_events(&mut highlighter, "svelte", source);
 
     assert_has_highlights(&events, &["property"], "Svelte style injection");
 }
@@ -108,7 +109,7 @@ fn test_style_with_multiple_selectors() {
             }
         </style>
     "#};
-    let events = record_events(&mut highlighter, source);
+    let events = record_events(&mut highlighter, "svelte", source);
 
     assert_has_highlights(&events, &["property"], "Svelte multiple selectors");
 }
@@ -124,7 +125,7 @@ fn test_template_expressions() {
         <h1>Hello {name}!</h1>
         <p>Count: {count + 1}</p>
     "#};
-    let events = record_events(&mut highlighter, source);
+    let events = record_events(&mut highlighter, "svelte", source);
 
     // Template expressions should produce events
     assert!(!events.is_empty(), "Svelte template should produce events");
@@ -139,7 +140,7 @@ fn test_only_template() {
             <p>No script or style tags here</p>
         </div>
     "#};
-    let events = record_events(&mut highlighter, source);
+    let events = record_events(&mut highlighter, "svelte", source);
     assert!(!events.is_empty());
 }
 
@@ -183,7 +184,7 @@ fn test_full_component() {
             }
         </style>
     "#};
-    let events = record_events(&mut highlighter, source);
+    let events = record_events(&mut highlighter, "svelte", source);
 
     // JS keywords
     assert_has_highlights(&events, &["keyword"], "Svelte full component - JS");
@@ -211,7 +212,7 @@ fn test_typescript_script() {
             let user: User = { name: "Alice", age: 30 };
         </script>
     "#};
-    let events = record_events(&mut highlighter, source);
+    let events = record_events(&mut highlighter, "svelte", source);
 
     assert_has_highlights(&events, &["keyword"], "Svelte TypeScript");
 }
@@ -232,7 +233,7 @@ fn test_highlighter_api() {
         </style>
     "#};
 
-    let html = highlighter.highlight_to_html("svelte", source).unwrap();
+    let html = highlighter.highlight("svelte", source).unwrap();
 
     // JS should be highlighted
     assert!(
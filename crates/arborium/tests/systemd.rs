@@ -0,0 +1,25 @@
+//! systemd unit tests.
+//!
+//! Tests that verify sections, directives and commands are highlighted.
+
+#![cfg(all(feature = "lang-systemd", feature = "lang-bash"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_service_unit() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight(
+            "service",
+            "[Service]\nExecStart=/usr/bin/app --port %i\nRestart=on-failure\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-t>Service</a-t>"), "{html}");
+    assert!(html.contains("<a-pr>Restart</a-pr>"), "{html}");
+    assert!(html.contains("<a-f>/usr/bin/app</a-f>"), "{html}");
+    assert_eq!(
+        arborium::detect_language("/etc/systemd/system/app.timer"),
+        Some("systemd")
+    );
+}
@@ -0,0 +1,20 @@
+//! Verilog tests.
+//!
+//! Tests that verify module definitions and types are highlighted.
+
+#![cfg(feature = "lang-verilog")]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_module() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight(
+            "sv",
+            "module top(input logic clk);\n  int count;\nendmodule\n",
+        )
+        .unwrap();
+    assert!(html.contains("<a-t>top</a-t>"), "{html}");
+    assert!(html.contains("<a-k>int</a-k>"), "{html}");
+}
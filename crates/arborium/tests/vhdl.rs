@@ -0,0 +1,17 @@
+//! VHDL tests.
+//!
+//! Tests that verify libraries and entity definitions are highlighted.
+
+#![cfg(feature = "lang-vhdl")]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_entity() {
+    let mut hl = Highlighter::new();
+    let html = hl
+        .highlight("vhd", "library ieee;\nentity counter is\nend entity;\n")
+        .unwrap();
+    assert!(html.contains("<a-ns>ieee</a-ns>"), "{html}");
+    assert!(html.contains("<a-t>counter</a-t>"), "{html}");
+}
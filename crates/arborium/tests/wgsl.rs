@@ -0,0 +1,24 @@
+//! WGSL injection tests.
+//!
+//! Tests that verify WGSL shaders in Rust raw strings are injected.
+
+#![cfg(all(feature = "lang-rust", feature = "lang-wgsl"))]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_rust_raw_strings() {
+    let mut hl = Highlighter::new();
+    let source = r##"const BLUR: &str = /* wgsl */ r#"fn blur() -> f32 { return 1.0; }"#;"##;
+    let html = hl.highlight("rust", source).unwrap();
+    assert!(html.contains("<a-f>blur</a-f>"), "{html}");
+
+    let source = r##"let source = ShaderSource::Wgsl(r#"@vertex fn vs() {}"#.into());"##;
+    let html = hl.highlight("rust", source).unwrap();
+    assert!(html.contains("<a-f>vs</a-f>"), "{html}");
+
+    // Only comments that are just a language name count as annotations
+    let source = r##"let s = /* not a language */ r#"fn blur() {}"#;"##;
+    let html = hl.highlight("rust", source).unwrap();
+    assert!(!html.contains("<a-f>blur</a-f>"), "{html}");
+}
@@ -0,0 +1,18 @@
+//! Maven POM tests.
+//!
+//! Tests that verify Maven coordinates and properties stand out in XML.
+
+#![cfg(feature = "lang-xml")]
+
+use arborium::Highlighter;
+
+#[test]
+fn test_maven_pom() {
+    let mut hl = Highlighter::new();
+    let pom = "<dependency>\n  <artifactId>jna</artifactId>\n  \
+               <version>${jna.version}</version>\n  <name>JNA</name>\n</dependency>\n";
+    let html = hl.highlight("pom", pom).unwrap();
+    assert!(html.contains("<a-s>jna</a-s>"), "{html}");
+    assert!(html.contains("<a-v>${jna.version}</a-v>"), "{html}");
+    assert!(!html.contains("<a-s>JNA</a-s>"), "{html}");
+}
@@ -0,0 +1,53 @@
+; ModuleID = 'fib.c'
+source_filename = "fib.c"
+target datalayout = "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-i128:128-f80:128-n8:16:32:64-S128"
+target triple = "x86_64-unknown-linux-gnu"
+
+%struct.Pair = type { i64, i64 }
+
+@.str = private unnamed_addr constant [8 x i8] c"%llu\0A\00\00", align 1
+@counter = internal global i32 0, align 4
+
+; Iterative Fibonacci, with the loop in SSA form
+define dso_local i64 @fib(i32 noundef %n) local_unnamed_addr #0 !dbg !10 {
+entry:
+  %cmp = icmp slt i32 %n, 2
+  br i1 %cmp, label %return, label %loop
+
+loop:                                             ; preds = %entry, %loop
+  %i = phi i32 [ 2, %entry ], [ %i.next, %loop ]
+  %a = phi i64 [ 0, %entry ], [ %b, %loop ]
+  %b = phi i64 [ 1, %entry ], [ %sum, %loop ]
+  %sum = add nuw nsw i64 %a, %b
+  %i.next = add nuw nsw i32 %i, 1
+  %done = icmp sgt i32 %i.next, %n
+  br i1 %done, label %return, label %loop, !llvm.loop !14
+
+return:                                           ; preds = %loop, %entry
+  %result = phi i64 [ 1, %entry ], [ %sum, %loop ]
+  %old = atomicrmw add ptr @counter, i32 1 monotonic, align 4
+  ret i64 %result
+}
+
+define dso_local i32 @main() #1 {
+  %pair = alloca %struct.Pair, align 8
+  %second = getelementptr inbounds %struct.Pair, ptr %pair, i32 0, i32 1
+  %f = call i64 @fib(i32 noundef 50)
+  store i64 %f, ptr %second, align 8
+  %vec = insertelement <4 x i32> zeroinitializer, i32 7, i64 0
+  %r = call i32 (ptr, ...) @printf(ptr noundef @.str, i64 noundef %f)
+  ret i32 0
+}
+
+declare i32 @printf(ptr noundef, ...) #2
+
+attributes #0 = { nofree norecurse nosync nounwind memory(readwrite, argmem: none) uwtable }
+attributes #1 = { nounwind uwtable "frame-pointer"="all" }
+attributes #2 = { "no-trapping-math"="true" }
+
+!llvm.module.flags = !{!0, !1}
+!0 = !{i32 7, !"Dwarf Version", i32 5}
+!1 = !{i32 2, !"Debug Info Version", i32 3}
+!10 = distinct !DISubprogram(name: "fib", scope: !11, file: !11, line: 3, type: !12, unit: !13)
+!14 = distinct !{!14, !15}
+!15 = !{!"llvm.loop.mustprogress"}
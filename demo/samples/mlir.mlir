@@ -0,0 +1,38 @@
+// Tiled matmul lowered to loops, the way `mlir-opt` prints it mid-pipeline
+#map = affine_map<(d0) -> (d0 * 4)>
+
+module attributes {gpu.container_module} {
+  func.func private @print_memref_f32(memref<*xf32>)
+
+  func.func @matmul(%A: memref<128x64xf32>, %B: memref<64x32xf32>,
+                    %C: memref<128x32xf32>) {
+    %c0 = arith.constant 0 : index
+    %c1 = arith.constant 1 : index
+    %c32 = arith.constant 32 : index
+    %c64 = arith.constant 64 : index
+    %c128 = arith.constant 128 : index
+    %zero = arith.constant 0.000000e+00 : f32
+    scf.for %i = %c0 to %c128 step %c1 {
+      scf.for %j = %c0 to %c32 step %c1 {
+        %acc = scf.for %k = %c0 to %c64 step %c1
+            iter_args(%sum = %zero) -> (f32) {
+          %a = memref.load %A[%i, %k] : memref<128x64xf32>
+          %b = memref.load %B[%k, %j] : memref<64x32xf32>
+          %prod = arith.mulf %a, %b : f32
+          %next = arith.addf %sum, %prod : f32
+          scf.yield %next : f32
+        }
+        memref.store %acc, %C[%i, %j] : memref<128x32xf32>
+      }
+    }
+    return
+  }
+
+  func.func @select(%cond: i1, %x: tensor<4x?xf32>) -> tensor<4x?xf32> {
+    %0 = "tosa.abs"(%x) : (tensor<4x?xf32>) -> tensor<4x?xf32>
+    cf.cond_br %cond, ^bb1(%0 : tensor<4x?xf32>), ^bb1(%x : tensor<4x?xf32>)
+  ^bb1(%r: tensor<4x?xf32>):
+    %cst = arith.constant dense<[1.0, 2.0]> : tensor<2xf32> loc("matmul.py":12:3)
+    return %r : tensor<4x?xf32>
+  }
+}
@@ -0,0 +1,58 @@
+/// A vault that holds coins for its owner and releases them after a deadline.
+module vault::vault {
+    use sui::balance::{Self, Balance};
+    use sui::clock::Clock;
+    use sui::coin::{Self, Coin};
+    use sui::event;
+
+    const ENotOwner: u64 = 0;
+    const ETooEarly: u64 = 1;
+
+    public struct Vault<phantom T> has key {
+        id: UID,
+        owner: address,
+        unlock_at_ms: u64,
+        funds: Balance<T>,
+    }
+
+    public struct Withdrawn has copy, drop {
+        amount: u64,
+    }
+
+    /* Anyone can open a vault; only the owner can empty it. */
+    public fun open<T>(coin: Coin<T>, unlock_at_ms: u64, ctx: &mut TxContext) {
+        let vault = Vault {
+            id: object::new(ctx),
+            owner: ctx.sender(),
+            unlock_at_ms,
+            funds: coin.into_balance(),
+        };
+        transfer::share_object(vault);
+    }
+
+    public fun withdraw<T>(
+        vault: &mut Vault<T>,
+        clock: &Clock,
+        ctx: &mut TxContext,
+    ): Coin<T> {
+        assert!(vault.owner == ctx.sender(), ENotOwner);
+        assert!(clock.timestamp_ms() >= vault.unlock_at_ms, ETooEarly);
+
+        let amount = balance::value(&vault.funds);
+        event::emit(Withdrawn { amount });
+        coin::from_balance(vault.funds.withdraw_all(), ctx)
+    }
+
+    #[test_only]
+    public fun checksum(bytes: vector<u8>): u64 {
+        let mut sum = 0u64;
+        let mut i = 0;
+        'bytes: while (i < bytes.length()) {
+            if (bytes[i] == 0) break 'bytes;
+            sum = sum + (bytes[i] as u64);
+            i = i + 1;
+        };
+        assert!(x"cafe" != b"cafe", 2);
+        sum
+    }
+}
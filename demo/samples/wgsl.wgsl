@@ -0,0 +1,71 @@
+// Separable Gaussian blur as a compute shader, one pass per direction
+enable f16;
+
+struct Params {
+    @align(16) direction: vec2<i32>,
+    radius: u32,
+    sigma: f32,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+alias Weights = array<f32, 32>;
+
+const WORKGROUP_SIZE: u32 = 64u;
+override gamma: f32 = 2.2;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var input: texture_2d<f32>;
+@group(0) @binding(2) var output: texture_storage_2d<rgba16float, write>;
+@group(0) @binding(3) var<storage, read_write> stats: array<atomic<u32>>;
+
+var<workgroup> weights: Weights;
+
+fn gaussian(x: f32, sigma: f32) -> f32 {
+    let s2 = 2.0 * sigma * sigma;
+    return exp(-(x * x) / s2) / sqrt(3.14159265 * s2);
+}
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn blur(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(local_invocation_index) local: u32,
+) {
+    let size = vec2<i32>(textureDimensions(input));
+    if (local < params.radius + 1u) {
+        weights[local] = gaussian(f32(local), params.sigma);
+    }
+    workgroupBarrier();
+
+    let center = vec2<i32>(id.xy);
+    if (any(center >= size)) {
+        return;
+    }
+
+    var sum = textureLoad(input, center, 0) * weights[0];
+    var total = weights[0];
+    for (var i = 1i; i <= i32(params.radius); i++) {
+        let offset = params.direction * i;
+        let w = weights[u32(i)];
+        let a = textureLoad(input, clamp(center + offset, vec2(0), size - 1), 0);
+        let b = textureLoad(input, clamp(center - offset, vec2(0), size - 1), 0);
+        sum += (a + b) * w;
+        total += 2.0 * w;
+    }
+
+    /* Store in linear space; the presentation pass applies gamma */
+    textureStore(output, center, vec4(pow(sum.rgb / total, vec3(gamma)), 1.0));
+    atomicAdd(&stats[0], 1u);
+}
+
+@vertex
+fn fullscreen(@builtin(vertex_index) index: u32) -> VertexOutput {
+    var out: VertexOutput;
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    out.position = vec4(uv * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = vec2(uv.x, 1.0 - uv.y);
+    return out;
+}
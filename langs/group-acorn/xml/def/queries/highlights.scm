@@ -161,6 +161,17 @@
   (CData) @markup.raw
   "]]>" @markup.heading)
 
+;; Maven POMs
+
+(element
+  (STag (Name) @_tag)
+  (content . (CharData) @string.special .)
+  (#any-of? @_tag
+    "groupId" "artifactId" "version" "classifier" "scope" "type" "packaging"))
+
+((CharData) @variable
+ (#match? @variable "^\\$\\{[^}]+\\}$"))
+
 ;; Misc
 
 (Comment) @comment
@@ -0,0 +1,23 @@
+// Sum an array of 64-bit integers.
+// x0: pointer, x1: length; returns the sum in x0
+    .text
+    .globl  sum_u64
+    .p2align 2
+    .type   sum_u64,@function
+sum_u64:
+    mov     x2, xzr             // accumulator
+    cbz     x1, .Ldone
+.Lloop:
+    ldr     x3, [x0], #8        // post-increment load
+    add     x2, x2, x3
+    subs    x1, x1, #1
+    b.ne    .Lloop
+.Ldone:
+    mov     x0, x2
+    ret
+    .size   sum_u64, .-sum_u64
+
+    .data
+table:
+    .quad   1, 2, 3, 0x10
+    .asciz  "sum"
@@ -0,0 +1,19 @@
+# size_t strlen(const char *s)
+    .section .text
+    .globl strlen
+strlen:
+    mv      a1, a0              # remember the start
+1:
+    lbu     t0, 0(a0)
+    beqz    t0, 2f
+    addi    a0, a0, 1
+    j       1b
+2:
+    sub     a0, a0, a1
+    ret
+
+    .section .rodata
+msg:
+    .string "hello"
+    lui     a2, %hi(msg)
+    addi    a2, a2, %lo(msg)
@@ -0,0 +1,591 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// Nim, following https://nim-lang.org/docs/manual.html
+//
+// Nim is indentation-sensitive: the external scanner tracks indentation and
+// emits `_indent`, `_dedent` and `_newline` tokens, like tree-sitter-python's,
+// so bodies nest under the statements that introduce them.
+
+const PREC = {
+  lambda: -1,
+  command: 1,
+  assignment: 2,
+  or: 3,
+  and: 4,
+  comparison: 5,
+  range: 6,
+  additive: 7,
+  multiplicative: 8,
+  unary: 9,
+  postfix: 10,
+};
+
+const commaSep1 = rule => seq(rule, repeat(seq(",", rule)));
+const commaSep = rule => optional(seq(commaSep1(rule), optional(",")));
+
+module.exports = grammar({
+  name: "nim",
+
+  extras: $ => [/\s/, $.comment, $.block_comment, $.documentation_comment],
+
+  externals: $ => [
+    $._newline,
+    $._indent,
+    $._dedent,
+    $.block_comment,
+    $.documentation_comment,
+  ],
+
+  word: $ => $.identifier,
+
+  conflicts: $ => [
+    [$.command_call, $._expression],
+    [$.tuple_constructor, $.parenthesized_expression],
+  ],
+
+  rules: {
+    source_file: $ => repeat($._statement),
+
+    _statement: $ => choice($._simple_statements, $._compound_statement),
+
+    // a = 1; echo a
+    _simple_statements: $ => seq(
+      $._simple_statement,
+      repeat(seq(";", $._simple_statement)),
+      optional(";"),
+      $._newline,
+    ),
+
+    _simple_statement: $ => choice(
+      $.import_statement,
+      $.from_import_statement,
+      $.include_statement,
+      $.return_statement,
+      $.yield_statement,
+      $.raise_statement,
+      $.discard_statement,
+      $.break_statement,
+      $.continue_statement,
+      $.assignment,
+      $.command_call,
+      $._expression,
+      $.pragma,
+    ),
+
+    _compound_statement: $ => choice(
+      $.routine_declaration,
+      $.type_section,
+      $.variable_section,
+      $.if_statement,
+      $.when_statement,
+      $.while_statement,
+      $.for_statement,
+      $.case_statement,
+      $.try_statement,
+      $.block_statement,
+      $.defer_statement,
+      $.block_call,
+    ),
+
+    // The body of `if x:`, `proc f() =` and the like: the rest of the line,
+    // or an indented block
+    _suite: $ => choice(
+      alias($._simple_statements, $.block),
+      seq($._indent, $.block),
+    ),
+
+    block: $ => seq(repeat($._statement), $._dedent),
+
+    // Modules
+
+    // import std/[os, strutils], ./utils as u
+    import_statement: $ => seq(
+      "import",
+      commaSep1($._module_path),
+      optional(seq("except", commaSep1($.identifier))),
+    ),
+
+    from_import_statement: $ => seq(
+      "from",
+      $._module_path,
+      "import",
+      commaSep1($.identifier),
+    ),
+
+    include_statement: $ => seq("include", commaSep1($._module_path)),
+
+    _module_path: $ => seq(
+      field("module", choice($.module_path, $.string)),
+      optional(seq("as", field("alias", $.identifier))),
+    ),
+
+    module_path: $ => prec.right(seq(
+      optional(choice("./", "../")),
+      $.identifier,
+      repeat(seq("/", choice($.identifier, seq("[", commaSep1($.identifier), "]")))),
+    )),
+
+    // Routines
+
+    // proc name*[T](params): Result {.pragmas.} = body
+    routine_declaration: $ => seq(
+      field("kind", choice("proc", "func", "method", "iterator", "converter", "template", "macro")),
+      field("name", $._symbol),
+      optional($.export_marker),
+      optional(field("generic_parameters", $.generic_parameters)),
+      optional(field("parameters", $.parameters)),
+      optional(seq(":", field("result", $._type))),
+      optional($.pragma),
+      choice(
+        // A forward declaration
+        $._newline,
+        seq("=", field("body", $._suite)),
+      ),
+    ),
+
+    // [T, U: SomeInteger; V]
+    generic_parameters: $ => seq(
+      "[",
+      $._generic_parameter,
+      repeat(seq(choice(",", ";"), $._generic_parameter)),
+      "]",
+    ),
+
+    _generic_parameter: $ => seq(field("name", $.identifier), optional(seq(":", $._type))),
+
+    parameters: $ => seq("(", optional(seq(
+      $.parameter,
+      repeat(seq(choice(",", ";"), $.parameter)),
+      optional(choice(",", ";")),
+    )), ")"),
+
+    parameter: $ => seq(
+      commaSep1(field("name", $.identifier)),
+      choice(
+        seq(":", field("type", $._type), optional(seq("=", field("default", $._expression)))),
+        seq("=", field("default", $._expression)),
+      ),
+    ),
+
+    export_marker: $ => token.immediate("*"),
+
+    _symbol: $ => choice($.identifier, $.accent_quoted),
+
+    // `+`, `[]=`
+    accent_quoted: $ => token(seq("`", /[^`\n]+/, "`")),
+
+    // Types
+
+    // type Id = int, or an indented list of definitions
+    type_section: $ => seq(
+      "type",
+      choice(
+        $.type_definition,
+        seq($._indent, repeat1($.type_definition), $._dedent),
+      ),
+    ),
+
+    type_definition: $ => seq(
+      field("name", $._symbol),
+      optional($.export_marker),
+      optional(field("generic_parameters", $.generic_parameters)),
+      optional($.pragma),
+      "=",
+      choice(
+        seq(field("type", $._type), $._newline),
+        field("type", choice($.object_type, $.enum_type, $.concept_type)),
+      ),
+    ),
+
+    _type: $ => choice(
+      $._expression,
+      $.tuple_type,
+      $.type_modifier,
+      $.proc_type,
+    ),
+
+    // object of Base, with its fields indented below
+    object_type: $ => seq(
+      optional(choice("ref", "ptr")),
+      "object",
+      optional(seq("of", field("base", $._expression))),
+      optional($.pragma),
+      choice(
+        $._newline,
+        seq($._indent, repeat1(seq($.field_declaration, $._newline)), $._dedent),
+      ),
+    ),
+
+    // enum a, b, or the fields indented below
+    enum_type: $ => seq(
+      "enum",
+      choice(
+        seq(commaSep1($.enum_field), $._newline),
+        seq(
+          $._indent,
+          repeat1(seq(commaSep1($.enum_field), optional(","), $._newline)),
+          $._dedent,
+        ),
+      ),
+    ),
+
+    enum_field: $ => seq(
+      field("name", $._symbol),
+      optional($.pragma),
+      optional(seq("=", $._expression)),
+    ),
+
+    tuple_type: $ => prec.right(seq("tuple", optional(seq(
+      "[",
+      optional(seq($.field_declaration, repeat(seq(choice(",", ";"), $.field_declaration)))),
+      "]",
+    )))),
+
+    concept_type: $ => seq(
+      "concept",
+      commaSep($.identifier),
+      choice($._newline, seq($._indent, $.block)),
+    ),
+
+    // ref Node, ptr UncheckedArray[byte], var seq[int], distinct string
+    type_modifier: $ => prec.right(PREC.unary, seq(
+      choice("ref", "ptr", "var", "distinct", "sink", "lent", "out", "static"),
+      $._type,
+    )),
+
+    proc_type: $ => prec.right(seq(
+      choice("proc", "iterator"),
+      optional($.parameters),
+      optional(seq(":", field("result", $._type))),
+      optional($.pragma),
+    )),
+
+    // Object fields: `x, y*: float`
+    field_declaration: $ => seq(
+      commaSep1(seq(field("name", $._symbol), optional($.export_marker), optional($.pragma))),
+      ":",
+      field("type", $._type),
+      optional(seq("=", field("default", $._expression))),
+    ),
+
+    // Variables
+
+    // var x = 1, or an indented list of declarations
+    variable_section: $ => seq(
+      field("kind", choice("var", "let", "const", "using")),
+      choice(
+        seq($.variable_declaration, $._newline),
+        seq($._indent, repeat1(seq($.variable_declaration, $._newline)), $._dedent),
+      ),
+    ),
+
+    // x = 1, a, b: int, (a, b) = pair, Max* = 10
+    variable_declaration: $ => seq(
+      choice(
+        commaSep1(seq(field("name", $._symbol), optional($.export_marker), optional($.pragma))),
+        seq("(", commaSep1(field("name", $.identifier)), ")"),
+      ),
+      choice(
+        seq(":", field("type", $._type), optional(seq("=", field("value", $._value)))),
+        seq("=", field("value", $._value)),
+      ),
+    ),
+
+    _value: $ => choice($._expression, $.command_call),
+
+    assignment: $ => prec.right(PREC.assignment, seq(
+      field("left", $._expression),
+      field("operator", choice("=", "+=", "-=", "*=", "/=", "&=", "@=")),
+      field("right", $._value),
+    )),
+
+    // Control flow
+
+    if_statement: $ => seq(
+      "if",
+      field("condition", $._expression),
+      ":",
+      field("consequence", $._suite),
+      repeat(field("alternative", $.elif_branch)),
+      optional(field("alternative", $.else_branch)),
+    ),
+
+    elif_branch: $ => seq("elif", field("condition", $._expression), ":", field("body", $._suite)),
+    else_branch: $ => seq("else", ":", field("body", $._suite)),
+
+    when_statement: $ => seq(
+      "when",
+      field("condition", $._expression),
+      ":",
+      field("consequence", $._suite),
+      repeat(field("alternative", $.elif_branch)),
+      optional(field("alternative", $.else_branch)),
+    ),
+
+    while_statement: $ => seq(
+      "while",
+      field("condition", $._expression),
+      ":",
+      field("body", $._suite),
+    ),
+
+    for_statement: $ => seq(
+      "for",
+      choice(
+        commaSep1(field("variable", $.identifier)),
+        seq("(", commaSep1(field("variable", $.identifier)), ")"),
+      ),
+      "in",
+      field("iterable", $._expression),
+      ":",
+      field("body", $._suite),
+    ),
+
+    // The branches may line up with `case` or be indented below it
+    case_statement: $ => seq(
+      "case",
+      field("value", $._expression),
+      optional(":"),
+      choice(
+        seq($._newline, $._case_branches),
+        seq($._indent, $._case_branches, $._dedent),
+      ),
+    ),
+
+    _case_branches: $ => seq(
+      repeat1($.of_branch),
+      repeat($.elif_branch),
+      optional($.else_branch),
+    ),
+
+    of_branch: $ => seq("of", commaSep1($._expression), ":", field("body", $._suite)),
+
+    try_statement: $ => seq(
+      "try",
+      ":",
+      field("body", $._suite),
+      repeat($.except_branch),
+      optional($.finally_branch),
+    ),
+
+    except_branch: $ => seq(
+      "except",
+      optional(seq(commaSep1($._expression), optional(seq("as", field("variable", $.identifier))))),
+      ":",
+      field("body", $._suite),
+    ),
+
+    finally_branch: $ => seq("finally", ":", field("body", $._suite)),
+
+    block_statement: $ => seq(
+      "block",
+      optional(field("label", $.identifier)),
+      ":",
+      field("body", $._suite),
+    ),
+
+    defer_statement: $ => seq("defer", ":", field("body", $._suite)),
+
+    // A template or macro taking the indented block as its last argument:
+    // `withLock l:`, `test "name":`
+    block_call: $ => seq(
+      field("function", choice($._expression, $.command_call)),
+      ":",
+      field("body", $._suite),
+    ),
+
+    return_statement: $ => prec.right(seq("return", optional($._value))),
+    yield_statement: $ => prec.right(seq("yield", optional($._value))),
+    raise_statement: $ => prec.right(seq("raise", optional($._expression))),
+    discard_statement: $ => prec.right(seq("discard", optional($._value))),
+    break_statement: $ => prec.right(seq("break", optional(field("label", $.identifier)))),
+    continue_statement: $ => "continue",
+
+    // Expressions
+
+    _expression: $ => choice(
+      $.identifier,
+      $.accent_quoted,
+      $.integer,
+      $.float,
+      $.string,
+      $.raw_string,
+      $.long_string,
+      $.generalized_string,
+      $.character,
+      $.boolean,
+      $.nil,
+      $.call,
+      $.dot_expression,
+      $.bracket_expression,
+      $.array_constructor,
+      $.set_constructor,
+      $.table_constructor,
+      $.tuple_constructor,
+      $.parenthesized_expression,
+      $.prefix_expression,
+      $.infix_expression,
+      $.if_expression,
+      $.cast_expression,
+      $.lambda,
+    ),
+
+    // echo "hi", x
+    command_call: $ => prec.right(PREC.command, seq(
+      field("function", choice($.identifier, $.dot_expression)),
+      commaSep1($._expression),
+    )),
+
+    // f(x, y = 1), Point(x: 1, y: 2)
+    call: $ => prec(PREC.postfix, seq(
+      field("function", $._expression),
+      token.immediate("("),
+      commaSep(choice(
+        $._expression,
+        seq(field("name", $.identifier), choice("=", ":"), $._expression),
+      )),
+      ")",
+    )),
+
+    dot_expression: $ => prec(PREC.postfix, seq(
+      field("left", $._expression),
+      ".",
+      field("right", $._symbol),
+    )),
+
+    // a[i], seq[int], a[^1], a[1..^2]
+    bracket_expression: $ => prec(PREC.postfix, seq(
+      $._expression,
+      token.immediate("["),
+      commaSep1($._type),
+      "]",
+    )),
+
+    // `@[1, 2]` is the `@` operator applied to an array
+    array_constructor: $ => seq("[", commaSep($._expression), "]"),
+
+    set_constructor: $ => seq("{", commaSep($._expression), "}"),
+
+    table_constructor: $ => seq(
+      "{",
+      commaSep1(seq(field("key", $._expression), ":", field("value", $._expression))),
+      "}",
+    ),
+
+    tuple_constructor: $ => seq(
+      "(",
+      choice(
+        seq($._expression, ",", commaSep($._expression)),
+        commaSep1(seq(field("name", $.identifier), ":", $._expression)),
+      ),
+      ")",
+    ),
+
+    parenthesized_expression: $ => seq("(", $._expression, ")"),
+
+    // -x, not ok, $n, &"{x}"
+    prefix_expression: $ => prec(PREC.unary, seq(
+      field("operator", choice("-", "+", "not", "$", "@", "^", "&", "addr", "!", "~")),
+      $._expression,
+    )),
+
+    infix_expression: $ => choice(
+      ...[
+        [PREC.or, choice("or", "xor")],
+        [PREC.and, "and"],
+        [PREC.comparison, choice("==", "!=", "<", "<=", ">", ">=", "in", "notin", "is", "isnot", "of", "=~")],
+        [PREC.range, choice("..", "..<", "..^")],
+        [PREC.additive, choice("+", "-", "&", "|")],
+        [PREC.multiplicative, choice("*", "/", "div", "mod", "shl", "shr", "%")],
+      ].map(([precedence, operator]) =>
+        prec.left(precedence, seq(
+          field("left", $._expression),
+          field("operator", operator),
+          field("right", $._expression),
+        )),
+      ),
+    ),
+
+    if_expression: $ => prec.right(seq(
+      "if", $._expression, ":", $._expression,
+      repeat(seq("elif", $._expression, ":", $._expression)),
+      "else", ":", $._expression,
+    )),
+
+    cast_expression: $ => seq("cast", "[", $._type, "]", "(", $._expression, ")"),
+
+    // proc (x: int): int = x * 2
+    lambda: $ => prec.right(PREC.lambda, seq(
+      choice("proc", "func"),
+      $.parameters,
+      optional(seq(":", field("result", $._type))),
+      optional($.pragma),
+      "=",
+      $._expression,
+    )),
+
+    // {.inline, raises: [IOError].}
+    pragma: $ => seq(
+      "{.",
+      commaSep(seq(
+        $.identifier,
+        optional(seq(":", $._expression)),
+      )),
+      choice(".}", "}"),
+    ),
+
+    // Literals
+
+    identifier: $ => /[\p{L}_][\p{L}\p{N}_]*/u,
+
+    boolean: $ => choice("true", "false"),
+
+    nil: $ => "nil",
+
+    integer: $ => token(seq(
+      choice(
+        /[0-9][0-9_]*/,
+        /0[xX][0-9a-fA-F_]+/,
+        /0[oO][0-7_]+/,
+        /0[bB][01_]+/,
+      ),
+      optional(/'?([iu](8|16|32|64)|u)/),
+    )),
+
+    float: $ => token(seq(
+      choice(
+        /[0-9][0-9_]*\.[0-9][0-9_]*([eE][+-]?[0-9]+)?/,
+        /[0-9][0-9_]*[eE][+-]?[0-9]+/,
+      ),
+      optional(/'?[fd](32|64|128)?/),
+    )),
+
+    string: $ => seq(
+      '"',
+      repeat(choice(token.immediate(prec(1, /[^"\\\n]+/)), $.escape_sequence)),
+      token.immediate('"'),
+    ),
+
+    raw_string: $ => token(seq(/[rR]"/, /([^"\n]|"")*/, '"')),
+
+    long_string: $ => token(seq('"""', /([^"]|"[^"]|""[^"])*/, '"""', optional(/"+/))),
+
+    // sql"select 1", re"\d+": a call with a raw string
+    generalized_string: $ => seq(
+      field("function", $.identifier),
+      choice(
+        token.immediate(seq('"', /([^"\n]|"")*/, '"')),
+        token.immediate(seq('"""', /([^"]|"[^"]|""[^"])*/, '"""')),
+      ),
+    ),
+
+    character: $ => seq("'", choice(token.immediate(/[^'\\\n]/), $.escape_sequence), "'"),
+
+    escape_sequence: $ => token.immediate(/\\([abcefnlprtv\\'"]|x[0-9a-fA-F]{2}|u[0-9a-fA-F]{4}|u\{[0-9a-fA-F]+\}|[0-9]{1,3})/),
+
+    // `#[ ]#` block comments and `##` documentation comments come from the
+    // external scanner, since block comments nest
+    comment: $ => token(seq("#", /[^\n]*/)),
+  },
+});
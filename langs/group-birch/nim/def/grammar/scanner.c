@@ -0,0 +1,204 @@
+#include "tree_sitter/array.h"
+#include "tree_sitter/parser.h"
+
+#include <stdint.h>
+#include <string.h>
+
+enum TokenType {
+  NEWLINE,
+  INDENT,
+  DEDENT,
+  BLOCK_COMMENT,
+  DOCUMENTATION_COMMENT,
+};
+
+typedef struct {
+  // Indentation of the enclosing blocks, innermost last
+  Array(uint16_t) indents;
+} Scanner;
+
+static inline void advance(TSLexer *lexer) { lexer->advance(lexer, false); }
+
+static inline void skip(TSLexer *lexer) { lexer->advance(lexer, true); }
+
+// Scans the rest of a comment whose first `#` was consumed, if it's a
+// `#[ ]#` block comment, a `##[ ]##` documentation block or a `##` line
+// documentation comment. Block comments nest.
+static bool scan_comment(TSLexer *lexer, const bool *valid_symbols) {
+  bool documentation = false;
+  if (lexer->lookahead == '#') {
+    advance(lexer);
+    documentation = true;
+  }
+
+  if (lexer->lookahead == '[') {
+    advance(lexer);
+    uint32_t depth = 1;
+    while (depth > 0) {
+      if (lexer->eof(lexer)) {
+        return false;
+      }
+      int32_t c = lexer->lookahead;
+      advance(lexer);
+      if (c == '#' && lexer->lookahead == '[') {
+        advance(lexer);
+        depth++;
+      } else if (c == ']' && lexer->lookahead == '#') {
+        advance(lexer);
+        depth--;
+      }
+    }
+    // `]##` closes a documentation block
+    if (documentation && lexer->lookahead == '#') {
+      advance(lexer);
+    }
+  } else if (documentation) {
+    while (lexer->lookahead != '\n' && lexer->lookahead != '\r' && !lexer->eof(lexer)) {
+      advance(lexer);
+    }
+  } else {
+    return false;
+  }
+
+  enum TokenType symbol = documentation ? DOCUMENTATION_COMMENT : BLOCK_COMMENT;
+  if (!valid_symbols[symbol]) {
+    return false;
+  }
+  lexer->mark_end(lexer);
+  lexer->result_symbol = symbol;
+  return true;
+}
+
+bool tree_sitter_nim_external_scanner_scan(void *payload, TSLexer *lexer, const bool *valid_symbols) {
+  Scanner *scanner = (Scanner *)payload;
+
+  bool error_recovery_mode = valid_symbols[INDENT] && valid_symbols[DEDENT];
+
+  // Indentation tokens are empty, at the end of the previous line
+  lexer->mark_end(lexer);
+
+  bool found_end_of_line = false;
+  uint16_t indent_length = 0;
+  int32_t first_comment_indent_length = -1;
+  for (;;) {
+    if (lexer->lookahead == '\n') {
+      found_end_of_line = true;
+      indent_length = 0;
+      skip(lexer);
+    } else if (lexer->lookahead == ' ') {
+      indent_length++;
+      skip(lexer);
+    } else if (lexer->lookahead == '\r' || lexer->lookahead == '\f') {
+      indent_length = 0;
+      skip(lexer);
+    } else if (lexer->lookahead == '\t') {
+      indent_length += 8;
+      skip(lexer);
+    } else if (lexer->lookahead == '#') {
+      advance(lexer);
+      if (lexer->lookahead == '#' || lexer->lookahead == '[') {
+        // A block or documentation comment. On a line of its own, end the
+        // previous statement or open a block first, so the comment lands in
+        // the block it's indented for.
+        if (found_end_of_line && scanner->indents.size > 0) {
+          uint16_t current_indent_length = *array_back(&scanner->indents);
+          if (valid_symbols[NEWLINE] && !error_recovery_mode) {
+            lexer->result_symbol = NEWLINE;
+            return true;
+          }
+          if (valid_symbols[INDENT] && indent_length > current_indent_length) {
+            array_push(&scanner->indents, indent_length);
+            lexer->result_symbol = INDENT;
+            return true;
+          }
+        }
+        return scan_comment(lexer, valid_symbols);
+      }
+
+      // A line comment after code, like `x = 1 # comment`, is left to the
+      // grammar
+      if (!found_end_of_line) {
+        return false;
+      }
+      if (first_comment_indent_length == -1) {
+        first_comment_indent_length = (int32_t)indent_length;
+      }
+      while (lexer->lookahead && lexer->lookahead != '\n') {
+        skip(lexer);
+      }
+      skip(lexer);
+      indent_length = 0;
+    } else if (lexer->eof(lexer)) {
+      indent_length = 0;
+      found_end_of_line = true;
+      break;
+    } else {
+      break;
+    }
+  }
+
+  if (found_end_of_line && scanner->indents.size > 0) {
+    uint16_t current_indent_length = *array_back(&scanner->indents);
+
+    if (valid_symbols[INDENT] && indent_length > current_indent_length) {
+      array_push(&scanner->indents, indent_length);
+      lexer->result_symbol = INDENT;
+      return true;
+    }
+
+    // Wait to dedent until we've consumed any comments indented for the
+    // current block
+    if (valid_symbols[DEDENT] && indent_length < current_indent_length &&
+        first_comment_indent_length < (int32_t)current_indent_length) {
+      array_pop(&scanner->indents);
+      lexer->result_symbol = DEDENT;
+      return true;
+    }
+
+    if (valid_symbols[NEWLINE] && !error_recovery_mode) {
+      lexer->result_symbol = NEWLINE;
+      return true;
+    }
+  }
+
+  return false;
+}
+
+unsigned tree_sitter_nim_external_scanner_serialize(void *payload, char *buffer) {
+  Scanner *scanner = (Scanner *)payload;
+
+  size_t size = 0;
+  uint32_t iter = 1;
+  for (; iter < scanner->indents.size && size + 1 < TREE_SITTER_SERIALIZATION_BUFFER_SIZE; ++iter) {
+    uint16_t indent_value = *array_get(&scanner->indents, iter);
+    buffer[size++] = (char)(indent_value & 0xFF);
+    buffer[size++] = (char)((indent_value >> 8) & 0xFF);
+  }
+
+  return size;
+}
+
+void tree_sitter_nim_external_scanner_deserialize(void *payload, const char *buffer, unsigned length) {
+  Scanner *scanner = (Scanner *)payload;
+
+  array_delete(&scanner->indents);
+  array_push(&scanner->indents, 0);
+
+  for (size_t size = 0; size + 1 < length; size += 2) {
+    uint16_t indent_value = (unsigned char)buffer[size] | ((unsigned char)buffer[size + 1] << 8);
+    array_push(&scanner->indents, indent_value);
+  }
+}
+
+void *tree_sitter_nim_external_scanner_create() {
+  Scanner *scanner = ts_calloc(1, sizeof(Scanner));
+  array_init(&scanner->indents);
+  tree_sitter_nim_external_scanner_deserialize(scanner, NULL, 0);
+  return scanner;
+}
+
+void tree_sitter_nim_external_scanner_destroy(void *payload) {
+  Scanner *scanner = (Scanner *)payload;
+  array_delete(&scanner->indents);
+  ts_free(scanner);
+}
@@ -0,0 +1,242 @@
+; Keywords
+
+[
+  "import"
+  "from"
+  "include"
+] @keyword.import
+
+(import_statement "except" @keyword.import)
+(except_branch "except" @keyword.exception)
+
+[
+  "proc"
+  "func"
+  "method"
+  "iterator"
+  "converter"
+  "template"
+  "macro"
+] @keyword.function
+
+[
+  "type"
+  "object"
+  "enum"
+  "tuple"
+  "concept"
+  "ref"
+  "ptr"
+  "var"
+  "let"
+  "const"
+  "using"
+  "distinct"
+  "sink"
+  "lent"
+  "out"
+  "static"
+  "cast"
+  "addr"
+  "discard"
+  "block"
+  "defer"
+  "as"
+] @keyword
+
+[
+  "if"
+  "elif"
+  "else"
+  "when"
+  "case"
+  "of"
+] @keyword.conditional
+
+[
+  "for"
+  "while"
+  "break"
+] @keyword.repeat
+
+(continue_statement) @keyword.repeat
+
+[
+  "return"
+  "yield"
+] @keyword.return
+
+[
+  "try"
+  "finally"
+  "raise"
+] @keyword.exception
+
+[
+  "and"
+  "or"
+  "xor"
+  "not"
+  "in"
+  "notin"
+  "is"
+  "isnot"
+  "div"
+  "mod"
+  "shl"
+  "shr"
+] @keyword.operator
+
+; Modules
+
+(module_path (identifier) @module)
+(import_statement alias: (identifier) @module)
+(include_statement alias: (identifier) @module)
+(from_import_statement alias: (identifier) @module)
+
+; Routines
+
+(routine_declaration name: (identifier) @function)
+(routine_declaration name: (accent_quoted) @operator)
+(routine_declaration
+  kind: ["template" "macro"]
+  name: (identifier) @function.macro)
+
+(parameter name: (identifier) @variable.parameter)
+(generic_parameters name: (identifier) @type)
+
+(call function: (identifier) @function.call)
+(call function: (dot_expression right: (identifier) @function.method))
+(command_call function: (identifier) @function.call)
+(command_call function: (dot_expression right: (identifier) @function.method))
+
+((identifier) @function.builtin
+  (#any-of? @function.builtin
+    "echo" "len" "high" "low" "inc" "dec" "add" "del" "new" "newSeq" "sizeof"
+    "typeof" "assert" "doAssert" "quit" "ord" "chr" "succ" "pred" "min" "max"
+    "debugEcho" "defined" "declared" "compiles" "isNil" "toOpenArray"))
+
+; Types
+
+(type_definition name: (identifier) @type.definition)
+(object_type base: (identifier) @type)
+(enum_field name: (identifier) @constant)
+(field_declaration name: (identifier) @variable.member)
+
+(field_declaration type: (identifier) @type)
+(parameter type: (identifier) @type)
+(variable_declaration type: (identifier) @type)
+(routine_declaration result: (identifier) @type)
+(proc_type result: (identifier) @type)
+(type_modifier (identifier) @type)
+(bracket_expression (identifier) @type
+  (#match? @type "^[A-Z]"))
+
+((identifier) @type.builtin
+  (#any-of? @type.builtin
+    "int" "int8" "int16" "int32" "int64" "uint" "uint8" "uint16" "uint32" "uint64"
+    "float" "float32" "float64" "bool" "char" "string" "cstring" "pointer" "byte"
+    "seq" "array" "openArray" "varargs" "set" "range" "typedesc" "untyped" "typed"
+    "auto" "void" "Natural" "Positive" "SomeInteger" "SomeFloat" "SomeNumber"
+    "Ordinal" "RootObj" "RootRef" "Exception" "CatchableError" "Defect"))
+
+((identifier) @variable.builtin
+  (#eq? @variable.builtin "result"))
+
+; Variables
+
+(variable_declaration name: (identifier) @variable)
+(for_statement variable: (identifier) @variable)
+(except_branch variable: (identifier) @variable)
+(block_statement label: (identifier) @label)
+(break_statement label: (identifier) @label)
+(dot_expression right: (identifier) @variable.member)
+(table_constructor key: (identifier) @variable.member)
+(tuple_constructor name: (identifier) @variable.member)
+(call name: (identifier) @variable.parameter)
+
+(export_marker) @operator
+
+(pragma) @attribute
+(pragma (identifier) @attribute)
+
+; Literals
+
+[
+  (string)
+  (raw_string)
+  (long_string)
+] @string
+
+(generalized_string) @string
+(generalized_string function: (identifier) @function.call)
+(character) @character
+(escape_sequence) @string.escape
+(integer) @number
+(float) @number.float
+(boolean) @constant.builtin.boolean
+(nil) @constant.builtin
+
+; Operators
+
+[
+  "="
+  "+="
+  "-="
+  "*="
+  "/="
+  "&="
+  "@="
+  "+"
+  "-"
+  "*"
+  "/"
+  "%"
+  "&"
+  "|"
+  "$"
+  "@"
+  "^"
+  "!"
+  "~"
+  "=="
+  "!="
+  "<"
+  "<="
+  ">"
+  ">="
+  "=~"
+  ".."
+  "..<"
+  "..^"
+] @operator
+
+[
+  ":"
+  ";"
+  ","
+  "."
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+[
+  "{."
+  ".}"
+] @punctuation.special
+
+; Comments
+
+[
+  (comment)
+  (block_comment)
+] @comment
+
+(documentation_comment) @comment.documentation
@@ -0,0 +1,22 @@
+; Scopes
+
+[
+  (source_file)
+  (routine_declaration)
+  (lambda)
+  (block)
+  (for_statement)
+  (except_branch)
+] @local.scope
+
+; Definitions
+
+(routine_declaration name: (identifier) @local.definition)
+(parameter name: (identifier) @local.definition)
+(variable_declaration name: (identifier) @local.definition)
+(for_statement variable: (identifier) @local.definition)
+(except_branch variable: (identifier) @local.definition)
+
+; References
+
+(identifier) @local.reference
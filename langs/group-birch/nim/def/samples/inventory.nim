@@ -0,0 +1,75 @@
+## Inventory tracking with objects, generics and iterators.
+
+import std/[tables, strutils, sequtils]
+import std/strformat as fmt
+from std/math import round
+
+type
+  Category* = enum
+    Tools, Food = "food", Toys
+
+  Item* = object of RootObj
+    name*: string
+    price: float
+    qty {.requiresInit.}: Natural
+    category*: Category
+
+  Inventory* = ref object
+    items: Table[string, Item]
+
+  InventoryError* = object of CatchableError
+
+const MaxQty* = 1_000'u32
+
+#[ Block comment
+   spanning lines ]#
+
+proc newInventory*(): Inventory =
+  Inventory(items: initTable[string, Item]())
+
+proc add*(inv: Inventory, item: Item) {.raises: [InventoryError].} =
+  if item.qty > MaxQty.int:
+    raise newException(InventoryError, fmt"too many {item.name}: {item.qty}")
+  inv.items[item.name] = item
+
+func total(item: Item): float = item.price * item.qty.float
+
+iterator byCategory*(inv: Inventory, cat: Category): Item =
+  for name, item in inv.items:
+    if item.category == cat:
+      yield item
+
+proc `$`*(item: Item): string =
+  result = item.name.alignLeft(12) & $item.qty
+  when defined(debug):
+    result.add &" ({item.category})"
+
+template withLogging(body: untyped) =
+  echo "begin"
+  body
+  echo "end"
+
+let inv = newInventory()
+var (added, skipped) = (0, 0)
+
+for line in "hammer,12.5,3\napple,0.4,40\nrobot,99,1".splitLines:
+  let parts = line.split(',')
+  try:
+    inv.add Item(name: parts[0], price: parseFloat(parts[1]),
+                 qty: parseInt(parts[2]), category: Tools)
+    inc added
+  except InventoryError as e:
+    echo "skipped: ", e.msg
+    skipped += 1
+  finally:
+    discard
+
+withLogging:
+  let values = toSeq(inv.byCategory(Tools)).mapIt(it.total)
+  echo round(values.foldl(a + b, 0.0), 2)
+  echo if added > 0: "ok" else: "empty"
+
+case skipped
+of 0: echo 'y'
+of 1..3: echo "some"
+else: echo r"C:\no\escapes"
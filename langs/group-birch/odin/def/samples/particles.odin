@@ -0,0 +1,86 @@
+package particles
+
+import "core:fmt"
+import "core:math"
+import rl "vendor:raylib"
+
+MAX_PARTICLES :: 1024
+GRAVITY :: rl.Vector2{0, 9.81}
+
+Kind :: enum u8 {
+	Spark,
+	Smoke,
+	Ember = 4,
+}
+
+Kinds :: bit_set[Kind; u8]
+
+Particle :: struct {
+	using position: [2]f32,
+	velocity:       [2]f32,
+	life:           f32 `json:"life"`,
+	kind:           Kind,
+}
+
+Emitter :: struct($N: int) {
+	particles: [N]Particle,
+	count:     int,
+}
+
+Error :: union {
+	Full_Error,
+	mem.Allocator_Error,
+}
+
+Full_Error :: distinct string
+
+/*
+ * Spawn a particle, or report that the emitter is full.
+ */
+@(require_results)
+spawn :: proc(e: ^Emitter($N), kind := Kind.Spark, pos: [2]f32 = {}) -> (p: ^Particle, err: Error) {
+	if e.count >= N {
+		return nil, Full_Error("emitter full")
+	}
+	p = &e.particles[e.count]
+	p^ = Particle{position = pos, kind = kind, life = 1.0}
+	e.count += 1
+	return
+}
+
+update :: proc(e: ^Emitter($N), dt: f32) #no_bounds_check {
+	for &p, i in e.particles[:e.count] {
+		p.velocity += GRAVITY * dt
+		p.position += p.velocity * dt
+		p.life -= dt * (0.5 if p.kind == .Smoke else 1)
+
+		#partial switch p.kind {
+		case .Ember:
+			p.life = max(p.life, 0.1)
+		case:
+			fallthrough
+		}
+	}
+
+	alive := 0
+	defer fmt.printfln("%d particles alive", alive)
+	outer: for i := 0; i < e.count; i += 1 {
+		if e.particles[i].life <= 0 do continue outer
+		e.particles[alive] = e.particles[i]
+		alive += 1
+	}
+	e.count = alive
+}
+
+main :: proc() {
+	emitter: Emitter(MAX_PARTICLES)
+	for i in 0 ..< 16 {
+		angle := f32(i) / 16 * math.TAU
+		p := spawn(&emitter, .Ember, {math.cos(angle), math.sin(angle)}) or_break
+		p.velocity = {0, -3}
+	}
+	when ODIN_DEBUG {
+		fmt.println(size_of(Particle), 'x', 0xff, 1e-3)
+	}
+	update(&emitter, 1.0 / 60)
+}
@@ -7,3 +7,58 @@
   (token_tree) @injection.content)
  (#set! injection.language "rust")
  (#set! injection.include-children))
+
+; The template strings of `asm!`, `global_asm!` and `naked_asm!`
+((macro_invocation
+  macro: [
+    (identifier) @_macro
+    (scoped_identifier name: (identifier) @_macro)
+  ]
+  (token_tree
+    [
+      (string_literal (string_content) @injection.content)
+      (raw_string_literal (string_content) @injection.content)
+    ]))
+ (#any-of? @_macro "asm" "global_asm" "naked_asm")
+ (#set! injection.language "asm"))
+
+; Raw strings annotated with the language they contain: `/* wgsl */ r#"..."#`,
+; or a `// language=wgsl` comment on the line before a `const`, `static` or
+; `let`. Any language arborium knows can be named this way.
+((block_comment) @injection.language
+  .
+  (raw_string_literal (string_content) @injection.content)
+  (#strip-comment! @injection.language))
+
+((line_comment) @injection.language
+  .
+  [
+    (const_item value: (raw_string_literal (string_content) @injection.content))
+    (static_item value: (raw_string_literal (string_content) @injection.content))
+    (let_declaration value: (raw_string_literal (string_content) @injection.content))
+  ]
+  (#match? @injection.language "^//\\s*language=")
+  (#strip-comment! @injection.language))
+
+; Shader sources in wgpu and Bevy: `ShaderSource::Wgsl(r#"..."#.into())`,
+; `ShaderSource::Wgsl(Cow::Borrowed(r#"..."#))`, `Shader::from_wgsl(r#"..."#, path)`
+((call_expression
+  function: (scoped_identifier name: (identifier) @_constructor)
+  arguments: (arguments
+    .
+    [
+      (raw_string_literal (string_content) @injection.content)
+      (call_expression
+        arguments: (arguments . (raw_string_literal (string_content) @injection.content)))
+      (call_expression
+        function: (field_expression
+          value: (raw_string_literal (string_content) @injection.content)))
+    ]))
+ (#any-of? @_constructor "Wgsl" "from_wgsl")
+ (#set! injection.language "wgsl"))
+
+((call_expression
+  function: (scoped_identifier name: (identifier) @_constructor)
+  arguments: (arguments . (raw_string_literal (string_content) @injection.content)))
+ (#eq? @_constructor "from_glsl")
+ (#set! injection.language "glsl"))
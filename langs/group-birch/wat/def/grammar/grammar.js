@@ -0,0 +1,88 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// WebAssembly text format (.wat) and script format (.wast)
+//
+// Both are S-expressions. Rather than encoding every module field, this
+// grammar parses lists and atoms and tells keywords, instructions and types
+// apart in the queries, so new proposals (GC, exceptions, SIMD) parse without
+// grammar changes.
+
+module.exports = grammar({
+  name: "wat",
+
+  extras: $ => [/\s/, $.line_comment, $.block_comment],
+
+  // Block comments nest, so they need a counter
+  externals: $ => [$.block_comment],
+
+  rules: {
+    source_file: $ => repeat($._item),
+
+    _item: $ => choice(
+      $.list,
+      $.annotation,
+      $._atom,
+    ),
+
+    // (func $add (param $a i32) ...); a leading keyword is always the head
+    list: $ => prec(1, seq(
+      "(",
+      optional(field("head", $.keyword)),
+      repeat($._item),
+      ")",
+    )),
+
+    // (@name ...): custom annotations, like `(@custom "name" ...)`
+    annotation: $ => seq(
+      "(",
+      field("name", alias(token(seq("@", /[A-Za-z0-9_.+\-*\/\\^~=<>!?@#$%&|:'`]+/)), $.annotation_name)),
+      repeat($._item),
+      ")",
+    ),
+
+    _atom: $ => choice(
+      $.keyword,
+      $.identifier,
+      $.memarg,
+      $.string,
+      $.integer,
+      $.float,
+    ),
+
+    // Bare words: module fields, instructions (`i32.add`, `local.get`) and types
+    keyword: $ => /[a-z][A-Za-z0-9_.+\-*\/\\^~=<>!?@#$%&|:'`]*/,
+
+    identifier: $ => /\$[A-Za-z0-9_.+\-*\/\\^~=<>!?@#$%&|:'`]+/,
+
+    // offset=8 align=4; takes precedence over the keyword of the same length
+    memarg: $ => token(prec(1, seq(choice("offset", "align"), "=", /(0x)?[0-9a-fA-F_]+/))),
+
+    integer: $ => token(seq(
+      optional(choice("+", "-")),
+      choice(/[0-9][0-9_]*/, /0x[0-9a-fA-F][0-9a-fA-F_]*/),
+    )),
+
+    // `inf` and `nan` take precedence over keywords
+    float: $ => token(prec(1, seq(
+      optional(choice("+", "-")),
+      choice(
+        /[0-9][0-9_]*\.([0-9][0-9_]*)?([eE][+-]?[0-9]+)?/,
+        /[0-9][0-9_]*[eE][+-]?[0-9]+/,
+        /0x[0-9a-fA-F][0-9a-fA-F_]*(\.[0-9a-fA-F_]*)?([pP][+-]?[0-9]+)?/,
+        "inf",
+        /nan(:0x[0-9a-fA-F_]+)?/,
+      ),
+    ))),
+
+    string: $ => seq(
+      '"',
+      repeat(choice(token.immediate(prec(1, /[^"\\]+/)), $.escape_sequence)),
+      token.immediate('"'),
+    ),
+
+    escape_sequence: $ => token.immediate(/\\([tnr"'\\]|[0-9a-fA-F]{2}|u\{[0-9a-fA-F]+\})/),
+
+    line_comment: $ => token(seq(";;", /[^\n]*/)),
+  },
+});
@@ -0,0 +1,73 @@
+#include "tree_sitter/parser.h"
+
+#include <stdint.h>
+
+enum TokenType {
+  BLOCK_COMMENT,
+};
+
+void *tree_sitter_wat_external_scanner_create(void) {
+  return NULL;
+}
+
+void tree_sitter_wat_external_scanner_destroy(void *payload) {
+}
+
+unsigned tree_sitter_wat_external_scanner_serialize(void *payload, char *buffer) {
+  return 0;
+}
+
+void tree_sitter_wat_external_scanner_deserialize(void *payload, const char *buffer, unsigned length) {
+}
+
+static void advance(TSLexer *lexer) {
+  lexer->advance(lexer, false);
+}
+
+static void skip(TSLexer *lexer) {
+  lexer->advance(lexer, true);
+}
+
+bool tree_sitter_wat_external_scanner_scan(
+  void *payload,
+  TSLexer *lexer,
+  const bool *valid_symbols
+) {
+  if (!valid_symbols[BLOCK_COMMENT]) {
+    return false;
+  }
+
+  while (lexer->lookahead == ' ' || lexer->lookahead == '\t' ||
+         lexer->lookahead == '\r' || lexer->lookahead == '\n') {
+    skip(lexer);
+  }
+
+  if (lexer->lookahead != '(') {
+    return false;
+  }
+  advance(lexer);
+  if (lexer->lookahead != ';') {
+    return false;
+  }
+  advance(lexer);
+
+  // `(; a (; b ;) c ;)` is one comment: count the openers still to close
+  uint32_t depth = 1;
+  while (depth > 0) {
+    int32_t c = lexer->lookahead;
+    if (c == 0 && lexer->eof(lexer)) {
+      return false;
+    }
+    advance(lexer);
+    if (c == '(' && lexer->lookahead == ';') {
+      advance(lexer);
+      depth++;
+    } else if (c == ';' && lexer->lookahead == ')') {
+      advance(lexer);
+      depth--;
+    }
+  }
+
+  lexer->result_symbol = BLOCK_COMMENT;
+  return true;
+}
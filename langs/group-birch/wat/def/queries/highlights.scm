@@ -0,0 +1,99 @@
+; Every bare word is a (keyword); the instruction, control flow and type
+; lists below override this for the words they name
+
+(keyword) @keyword
+(identifier) @variable
+(string) @string
+
+; Instructions: `i32.add`, `local.get`, `v128.load8x8_s`
+((keyword) @function.builtin
+  (#match? @function.builtin "\\."))
+
+((keyword) @function.builtin
+  (#any-of? @function.builtin
+    "unreachable" "nop" "br" "br_if" "br_table" "br_on_null" "br_on_non_null"
+    "br_on_cast" "br_on_cast_fail" "return" "call" "call_indirect" "call_ref"
+    "return_call" "return_call_indirect" "return_call_ref" "drop" "select"
+    "throw" "throw_ref" "rethrow" "delegate"))
+
+; Control flow
+((keyword) @keyword.conditional
+  (#any-of? @keyword.conditional "if" "then" "else"))
+
+((keyword) @keyword.repeat
+  (#eq? @keyword.repeat "loop"))
+
+((keyword) @keyword
+  (#any-of? @keyword "block" "end" "try" "try_table" "catch" "catch_all" "catch_ref" "catch_all_ref"))
+
+; Module structure
+((keyword) @keyword.import
+  (#any-of? @keyword.import "import" "export"))
+
+((keyword) @keyword.function
+  (#eq? @keyword.function "func"))
+
+((keyword) @keyword.modifier
+  (#any-of? @keyword.modifier "mut" "shared" "declare" "final"))
+
+; Types
+((keyword) @type.builtin
+  (#any-of? @type.builtin
+    "i32" "i64" "f32" "f64" "v128" "i8" "i16"
+    "funcref" "externref" "anyref" "eqref" "i31ref" "structref" "arrayref"
+    "nullref" "nullfuncref" "nullexternref" "exnref"
+    "any" "eq" "i31" "extern" "none" "noextern" "nofunc" "exn" "noexn"))
+
+; SIMD lane shapes used as immediates: `i32x4`, `f64x2`
+((keyword) @type.builtin
+  (#match? @type.builtin "^[if](8|16|32|64)x[0-9]+$"))
+
+; Definitions: the name right after the field keyword
+(list
+  head: (keyword) @_kind
+  .
+  (identifier) @function
+  (#any-of? @_kind "func" "import"))
+
+(list
+  head: (keyword) @_kind
+  .
+  (identifier) @variable.parameter
+  (#eq? @_kind "param"))
+
+(list
+  head: (keyword) @_kind
+  .
+  (identifier) @type.definition
+  (#any-of? @_kind "type" "rec" "sub"))
+
+(list
+  head: (keyword) @_kind
+  .
+  (identifier) @variable.member
+  (#eq? @_kind "field"))
+
+; Import and export names
+(list
+  head: (keyword) @_kind
+  (string) @string.special
+  (#any-of? @_kind "import" "export"))
+
+(annotation name: (annotation_name) @attribute)
+
+; Literals
+
+(memarg) @variable.parameter
+(escape_sequence) @string.escape
+(integer) @number
+(float) @number.float
+
+[
+  "("
+  ")"
+] @punctuation.bracket
+
+[
+  (line_comment)
+  (block_comment)
+] @comment
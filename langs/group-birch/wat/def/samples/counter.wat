@@ -0,0 +1,37 @@
+;; A counter with a memory-backed log, exported to the host.
+(module
+  (type $log_t (func (param i32 i32)))
+  (import "env" "log" (func $log (type $log_t)))
+  (import "env" "memory" (memory 1))
+
+  (global $count (mut i32) (i32.const 0))
+  (data (i32.const 16) "count: \00\u{1F600}")
+
+  (; Increment by `n`, logging
+     every tenth step ;)
+  (func $increment (export "increment") (param $n i32) (result i32)
+    (local $i i32)
+    (loop $steps
+      (global.set $count (i32.add (global.get $count) (i32.const 1)))
+      (if (i32.eqz (i32.rem_u (global.get $count) (i32.const 10)))
+        (then (call $log (i32.const 16) (i32.const 7))))
+      (local.set $i (i32.add (local.get $i) (i32.const 1)))
+      (br_if $steps (i32.lt_u (local.get $i) (local.get $n))))
+    global.get $count)
+
+  (func $average (export "average") (param $a f64) (param $b f64) (result f64)
+    local.get $a
+    local.get $b
+    f64.add
+    f64.const 0x1p-1
+    f64.mul)
+
+  (func $store (param $addr i32) (param $v i64)
+    (i64.store offset=8 align=8 (local.get $addr) (local.get $v)))
+
+  (func $lanes (param $v v128) (result i32)
+    (i32x4.extract_lane 2 (i32x4.add (local.get $v) (v128.const i32x4 1 2 3 -4))))
+
+  (@custom "producers" "rustc")
+  (start $init)
+  (func $init (drop (f32.const nan)) (drop (f32.const -inf))))
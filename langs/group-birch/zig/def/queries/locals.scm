@@ -0,0 +1,30 @@
+; Scopes
+
+[
+  (source_file)
+  (Block)
+  (ContainerDecl)
+  (IfStatement)
+  (IfExpr)
+  (ForStatement)
+  (ForExpr)
+  (WhileStatement)
+  (WhileExpr)
+  (SwitchProng)
+] @local.scope
+
+; Parameters are declared in the prototype but used in the sibling body
+(Decl (FnProto)) @local.scope
+
+; Definitions
+
+(VarDecl variable_type_function: (IDENTIFIER) @local.definition)
+(ParamDecl parameter: (IDENTIFIER) @local.definition)
+(Payload variable: (IDENTIFIER) @local.definition)
+(PtrPayload variable: (IDENTIFIER) @local.definition)
+(PtrIndexPayload variable: (IDENTIFIER) @local.definition)
+(PtrListPayload variable: (IDENTIFIER) @local.definition)
+
+; References
+
+(SuffixExpr variable_type_function: (IDENTIFIER) @local.reference)
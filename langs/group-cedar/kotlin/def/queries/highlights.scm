@@ -371,10 +371,10 @@
 	"::"
 ] @punctuation.delimiter
 
-; NOTE: `interpolated_identifier`s can be highlighted in any way
+; Template entries are code, not string contents
 (string_literal
 	"$" @punctuation.special
-	(interpolated_identifier) @none)
+	(interpolated_identifier) @variable)
 (string_literal
 	"${" @punctuation.special
 	(interpolated_expression) @none
@@ -0,0 +1,40 @@
+([
+  (line_comment)
+  (multiline_comment)
+] @injection.content
+  (#set! injection.language "comment"))
+
+; The three ways to write a regex, as in highlights.scm
+
+;    - "[abc]?".toRegex()
+(call_expression
+  (navigation_expression
+    ((string_literal) @injection.content)
+    (navigation_suffix
+      ((simple_identifier) @_function
+        (#eq? @_function "toRegex"))))
+  (#set! injection.language "regex"))
+
+;    - Regex("[abc]?")
+(call_expression
+  ((simple_identifier) @_function
+    (#eq? @_function "Regex"))
+  (call_suffix
+    (value_arguments
+      (value_argument
+        (string_literal) @injection.content)))
+  (#set! injection.language "regex"))
+
+;    - Regex.fromLiteral("[abc]?")
+(call_expression
+  (navigation_expression
+    ((simple_identifier) @_class
+      (#eq? @_class "Regex"))
+    (navigation_suffix
+      ((simple_identifier) @_function
+        (#eq? @_function "fromLiteral"))))
+  (call_suffix
+    (value_arguments
+      (value_argument
+        (string_literal) @injection.content)))
+  (#set! injection.language "regex"))
@@ -0,0 +1,38 @@
+; Scopes
+
+[
+  (source_file)
+  (class_declaration)
+  (object_declaration)
+  (function_declaration)
+  (anonymous_function)
+  (lambda_literal)
+  (statements)
+  (for_statement)
+  (catch_block)
+  (when_entry)
+] @local.scope
+
+; Definitions
+
+(function_declaration
+  . (simple_identifier) @local.definition)
+
+(parameter
+  (simple_identifier) @local.definition)
+
+(parameter_with_optional_type
+  (simple_identifier) @local.definition)
+
+(class_parameter
+  (simple_identifier) @local.definition)
+
+(variable_declaration
+  (simple_identifier) @local.definition)
+
+(catch_block
+  (simple_identifier) @local.definition)
+
+; References
+
+(simple_identifier) @local.reference
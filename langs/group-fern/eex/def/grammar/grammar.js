@@ -0,0 +1,40 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// EEx (Embedded Elixir)
+//
+// The template is split into `content`, which is injected as HTML, and the
+// `code` of each tag, which is injected as Elixir.
+
+module.exports = grammar({
+  name: "eex",
+
+  extras: _ => [],
+
+  rules: {
+    template: $ => repeat(choice(
+      $.content,
+      $.directive,
+      $.output_directive,
+      $.comment_directive,
+    )),
+
+    // `<%%` is a literal `<%`
+    content: _ => /([^<]|<[^%]|<%%)+/,
+
+    // <% code %>
+    directive: $ => seq("<%", optional($.code), "%>"),
+
+    // <%= expression %>
+    output_directive: $ => seq("<%=", optional($.code), "%>"),
+
+    // <%# comment %> and <%!-- comment --%>
+    comment_directive: $ => choice(
+      seq("<%#", optional(alias(/([^%]|%[^>])+/, $.comment)), "%>"),
+      seq("<%!--", optional(alias(/([^-]|-[^-]|--[^%]|--%[^>])+/, $.comment)), "--%>"),
+    ),
+
+    // Runs up to the closing `%>`
+    code: _ => /([^%]|%[^>])+/,
+  },
+});
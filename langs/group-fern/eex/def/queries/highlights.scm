@@ -0,0 +1,10 @@
+[
+  "<%"
+  "<%="
+  "<%#"
+  "<%!--"
+  "%>"
+  "--%>"
+] @punctuation.special
+
+(comment_directive) @comment
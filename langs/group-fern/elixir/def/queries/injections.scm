@@ -6,6 +6,14 @@
  (#set! injection.language "heex")
  (#set! injection.combined))
 
+; EEx templates, as in Phoenix.HTML's ~E and the old LiveView ~L
+((sigil
+  (sigil_name) @_sigil_name
+  (quoted_content) @injection.content)
+ (#any-of? @_sigil_name "E" "L")
+ (#set! injection.language "eex")
+ (#set! injection.combined))
+
 ; Regex
 ((sigil
   (sigil_name) @_sigil_name
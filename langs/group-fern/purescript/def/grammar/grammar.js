@@ -0,0 +1,109 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// PureScript
+//
+// PureScript is layout-sensitive like Haskell. This grammar doesn't track
+// layout: it reads a module as a flat run of tokens and only recognizes the
+// few shapes that matter for highlighting and outlines, namely the module
+// header, imports and type signatures (`name ::`).
+
+const KEYWORDS = [
+  "where", "as", "hiding", "import", "foreign", "data", "type", "newtype", "class",
+  "instance", "derive", "else", "if", "then", "case", "of", "let", "in", "do",
+  "ado", "forall", "infix", "infixl", "infixr", "role", "nominal",
+  "representational", "phantom",
+];
+
+module.exports = grammar({
+  name: "purescript",
+
+  extras: $ => [/\s/, $.comment],
+
+  word: $ => $.variable,
+
+  rules: {
+    source_file: $ => repeat($._item),
+
+    _item: $ => choice(
+      $.module_header,
+      $.import,
+      $.signature,
+      $._term,
+    ),
+
+    module_header: $ => seq(
+      "module",
+      field("name", alias($.constructor, $.module_name)),
+    ),
+
+    // `foreign import name :: Type` imports a value, not a module
+    import: $ => prec(1, seq(
+      "import",
+      field("module", alias($.constructor, $.module_name)),
+    )),
+
+    // Only the name is part of the signature; the type follows as terms.
+    signature: $ => prec(1, seq(
+      field("name", $.variable),
+      "::",
+    )),
+
+    _term: $ => choice(
+      $.variable,
+      $.qualified_variable,
+      $.constructor,
+      $.hole,
+      $.operator,
+      $.string,
+      $.char,
+      $.number,
+      ...KEYWORDS,
+      "(", ")", "[", "]", "{", "}", ",", ";", "`",
+    ),
+
+    // Tokens
+    // ======
+
+    variable: _ => /[a-z_][A-Za-z0-9_']*/,
+
+    // `Data.Maybe.fromMaybe`
+    qualified_variable: _ => /([A-Z][A-Za-z0-9_']*\.)+[a-z_][A-Za-z0-9_']*/,
+
+    // Types, data constructors and module names, possibly qualified
+    constructor: _ => /[A-Z][A-Za-z0-9_']*(\.[A-Z][A-Za-z0-9_']*)*/,
+
+    // Typed holes: `?help`
+    hole: _ => /\?[a-z_][A-Za-z0-9_']*/,
+
+    operator: _ => choice(
+      /[!#$%&*+./<=>?@\\^|~:\-]+/,
+      "::",
+    ),
+
+    string: $ => choice(
+      /"""([^"]|"[^"]|""[^"])*"""/,
+      seq(
+        '"',
+        repeat(choice(
+          token.immediate(prec(1, /[^"\\\n]+/)),
+          $.escape_sequence,
+        )),
+        token.immediate('"'),
+      ),
+    ),
+
+    // Includes string gaps: "a\
+    //   \b"
+    escape_sequence: _ => token.immediate(/\\([^\s]|x[0-9a-fA-F]+|\s*\\)/),
+
+    char: _ => /'([^'\\\n]|\\[^\n]|\\x[0-9a-fA-F]+)'/,
+
+    number: _ => /0x[0-9a-fA-F]+|[0-9][0-9_]*(\.[0-9]+)?([eE][+-]?[0-9]+)?/,
+
+    comment: _ => token(choice(
+      seq("--", /[^\n]*/),
+      seq("{-", /[^-]*-+([^}-][^-]*-+)*/, "}"),
+    )),
+  },
+});
@@ -0,0 +1,109 @@
+; Identifiers. The tree is flat, so these and the signature patterns are
+; matched from the source file, letting later patterns override earlier ones
+(source_file
+  (variable) @variable)
+
+(source_file
+  (qualified_variable) @variable)
+
+((variable) @boolean
+  (#any-of? @boolean "true" "false"))
+
+(constructor) @type
+
+(module_name) @module
+
+(hole) @label
+
+; Signatures name functions, except for record and row fields
+(source_file
+  (signature
+    name: (variable) @function))
+
+(source_file
+  [
+    "{"
+    "("
+    ","
+  ]
+  .
+  (signature
+    name: (variable) @property))
+
+; Literals and comments
+(number) @number
+
+(char) @character
+
+(string) @string
+
+(escape_sequence) @string.escape
+
+(comment) @comment
+
+((comment) @comment.documentation
+  (#match? @comment.documentation "^-- \\|"))
+
+; Keywords
+[
+  "module"
+  "where"
+  "data"
+  "type"
+  "newtype"
+  "class"
+  "instance"
+  "derive"
+  "let"
+  "in"
+  "do"
+  "ado"
+  "infix"
+  "infixl"
+  "infixr"
+  "role"
+] @keyword
+
+[
+  "import"
+  "foreign"
+  "as"
+  "hiding"
+] @keyword.import
+
+[
+  "if"
+  "then"
+  "else"
+  "case"
+  "of"
+] @keyword.conditional
+
+"forall" @keyword.repeat
+
+[
+  "nominal"
+  "representational"
+  "phantom"
+] @type.qualifier
+
+; Operators and punctuation
+(operator) @operator
+
+"::" @punctuation.delimiter
+
+"`" @punctuation.special
+
+[
+  "("
+  ")"
+  "{"
+  "}"
+  "["
+  "]"
+] @punctuation.bracket
+
+[
+  ","
+  ";"
+] @punctuation.delimiter
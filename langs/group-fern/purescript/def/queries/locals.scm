@@ -0,0 +1,6 @@
+(source_file) @local.scope
+
+(signature
+  name: (variable) @local.definition)
+
+(variable) @local.reference
@@ -54,3 +54,33 @@
   (command (_) @constant)
   (#match? @constant "^-")
 )
+
+; curl: flags, the method, header fields and URLs
+
+((command
+  name: (command_name) @_curl
+  argument: (word) @variable.parameter)
+  (#eq? @_curl "curl")
+  (#match? @variable.parameter "^--?[a-zA-Z]"))
+
+((command
+  name: (command_name) @_curl
+  argument: (word) @_flag
+  .
+  argument: (word) @keyword)
+  (#eq? @_curl "curl")
+  (#any-of? @_flag "-X" "--request"))
+
+((command
+  name: (command_name) @_curl
+  argument: (word) @_flag
+  .
+  argument: [(string) (raw_string) (word)] @string.special)
+  (#eq? @_curl "curl")
+  (#any-of? @_flag "-H" "--header"))
+
+((command
+  name: (command_name) @_curl
+  argument: [(word) (string) (raw_string)] @markup.link.url)
+  (#eq? @_curl "curl")
+  (#match? @markup.link.url "^['\"]?[a-zA-Z][a-zA-Z0-9+.-]*://"))
@@ -0,0 +1,11 @@
+; curl request bodies that look like JSON: `curl -d '{"name": "Ferris"}'`
+((command
+  name: (command_name) @_curl
+  argument: (word) @_flag
+  .
+  argument: (raw_string) @injection.content)
+  (#eq? @_curl "curl")
+  (#any-of? @_flag "-d" "--data" "--data-raw" "--data-binary" "--json")
+  (#match? @injection.content "^'\\s*[\\[{]")
+  (#offset! @injection.content 0 1 0 -1)
+  (#set! injection.language "json"))
@@ -0,0 +1,18 @@
+[
+  "<%"
+  "<%-"
+  "<%_"
+  "<%|"
+  "<%="
+  "<%=="
+  "<%|="
+  "<%|=="
+  "<%#"
+  "<%graphql"
+  "%>"
+  "-%>"
+  "_%>"
+  "=%>"
+] @punctuation.special
+
+(comment_directive) @comment
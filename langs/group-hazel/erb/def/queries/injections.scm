@@ -0,0 +1,5 @@
+((content) @injection.content
+  (#set! injection.language "html"))
+
+((code) @injection.content
+  (#set! injection.language "ruby"))
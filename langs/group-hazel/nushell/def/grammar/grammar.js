@@ -0,0 +1,216 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// Nushell
+//
+// A script is a list of pipelines separated by newlines or `;`. Each
+// pipeline element is a command and its arguments, or a value (a variable,
+// literal, list, record or subexpression) followed by whatever comes after
+// it on the line; operators are arguments like any other. Inside `[...]`
+// and `(...)` newlines are whitespace, and a line starting with `|` carries
+// on the pipeline from the line before.
+
+// `def`, `let` and friends start their own rules below
+const KEYWORDS = [
+  "export", "export-env", "module", "use", "hide", "overlay", "source",
+  "source-env", "if", "else", "match", "for", "in", "while", "loop", "break",
+  "continue", "return", "try", "catch", "do",
+];
+
+module.exports = grammar({
+  name: "nushell",
+
+  extras: $ => [/[ \t\r]/, /\n/, /\\\r?\n/, $.comment],
+
+  word: $ => $.identifier,
+
+  rules: {
+    source_file: $ => repeat($._statement),
+
+    _statement: $ => choice($.pipeline, $._terminator),
+
+    _terminator: _ => choice(";", "\n"),
+
+    pipeline: $ => prec.right(seq(
+      $._element,
+      repeat(seq($._pipe, $._element)),
+    )),
+
+    _pipe: _ => choice("|", alias(token(prec(1, /\n[ \t\r]*\|/)), "|")),
+
+    _element: $ => choice(
+      $.command,
+      prec.right(seq($._head, repeat($._argument))),
+    ),
+
+    command: $ => prec.right(seq(
+      field("name", choice($.identifier, $.external_command)),
+      repeat(field("argument", $._argument)),
+    )),
+
+    _head: $ => choice(
+      $.function_definition,
+      $.let_binding,
+      $.record_key,
+      $._value,
+      ...KEYWORDS,
+    ),
+
+    _argument: $ => choice(
+      $.identifier,
+      $.record_key,
+      $.let_binding,
+      $.flag,
+      $.operator,
+      $._value,
+      $.bare_word,
+      ...KEYWORDS,
+      ",", ":", "=",
+    ),
+
+    // Definitions
+    // ===========
+
+    // `def greet [name: string] { ... }`, `alias ll = ls -l`; `export`
+    // before one of these belongs to the definition
+    function_definition: $ => prec.right(1, seq(
+      optional("export"),
+      choice("def", "extern", "alias"),
+      repeat($.flag),
+      field("name", choice($.identifier, $.string)),
+      optional(field("parameters", $.parameter_list)),
+    )),
+
+    parameter_list: $ => seq(
+      "[",
+      repeat(choice($.parameter, $.type_annotation, ",", "=", $._value)),
+      "]",
+    ),
+
+    // `name`, `name?`, `...rest`, `--verbose (-v)`
+    parameter: $ => prec.right(choice(
+      seq(optional("..."), field("name", $.identifier), optional(token.immediate("?"))),
+      seq(field("name", $.flag), optional(seq("(", field("short", $.flag), ")"))),
+    )),
+
+    type_annotation: $ => seq(":", field("type", $.type)),
+
+    // `int`, `list<string>`, `record<name: string>`
+    type: $ => prec.right(seq(
+      $.identifier,
+      optional(seq(
+        token.immediate("<"),
+        repeat(choice($.type, ",", ":")),
+        ">",
+      )),
+    )),
+
+    // `let x = 1`, `mut total: int = 0`
+    let_binding: $ => prec.right(seq(
+      choice("let", "mut", "const"),
+      field("name", $.identifier),
+      optional($.type_annotation),
+    )),
+
+    // Values
+    // ======
+
+    _value: $ => choice(
+      $.variable,
+      $.number,
+      $.range,
+      $.string,
+      $.raw_string,
+      $.string_interpolation,
+      $.list,
+      $.block,
+      $.subexpression,
+    ),
+
+    // `$name`, `$env.PATH`, `$row.size?`
+    variable: $ => seq(
+      field("name", $.variable_name),
+      repeat(field("path", $.path_member)),
+    ),
+
+    variable_name: _ => /\$[A-Za-z_][A-Za-z0-9_-]*/,
+
+    path_member: _ => token.immediate(prec(1, /\.([A-Za-z0-9_-]+|"[^"]*")\??/)),
+
+    // Numbers, durations and file sizes: `42`, `0x1f`, `1.5`, `10kb`, `3sec`
+    number: _ => /-?(0x[0-9a-fA-F_]+|0b[01_]+|0o[0-7_]+|[0-9][0-9_]*(\.[0-9]+)?([eE][+-]?[0-9]+)?)([a-zA-Z]+)?/,
+
+    // `1..10`, `0..<5`, `1..`
+    range: _ => /-?[0-9]+\.\.[<=]?(-?[0-9]+)?/,
+
+    string: $ => choice(
+      seq('"', repeat(choice($.string_content, $.escape_sequence)), '"'),
+      /'[^']*'/,
+      /`[^`]*`/,
+    ),
+
+    string_content: _ => token.immediate(prec(1, /[^"\\]+/)),
+
+    escape_sequence: _ => token.immediate(/\\(u\{[0-9a-fA-F]+\}|.)/),
+
+    // `r#'C:\no\escapes'#`
+    raw_string: _ => /r#+'[^']*'#+/,
+
+    // `$"hello (name)"` and `$'sum: (1 + 2)'`
+    string_interpolation: $ => choice(
+      seq('$"', repeat(choice(
+        alias(token.immediate(prec(1, /[^"\\(]+/)), $.string_content),
+        $.escape_sequence,
+        $.subexpression,
+      )), '"'),
+      seq("$'", repeat(choice(
+        alias(token.immediate(prec(1, /[^'(]+/)), $.string_content),
+        $.subexpression,
+      )), "'"),
+    ),
+
+    list: $ => seq("[", repeat(choice($._argument, ";")), "]"),
+
+    // Closures and records: `{|x| $x + 1 }`, `{ name: "nu", age: 5 }`
+    block: $ => seq(
+      "{",
+      optional(field("parameters", $.closure_parameters)),
+      repeat($._statement),
+      "}",
+    ),
+
+    closure_parameters: $ => seq(
+      "|",
+      repeat(choice($.parameter, $.type_annotation, ",")),
+      "|",
+    ),
+
+    subexpression: $ => seq("(", repeat($._statement), ")"),
+
+    // `name:` in records
+    record_key: $ => prec(1, seq(
+      field("key", choice($.identifier, $.string)),
+      token.immediate(":"),
+    )),
+
+    // Tokens
+    // ======
+
+    identifier: _ => /[A-Za-z_][A-Za-z0-9_-]*/,
+
+    // `^git` runs the external command even if nu has one with that name
+    external_command: _ => /\^[^\s|;()\[\]{}]+/,
+
+    flag: _ => /--?[A-Za-z][A-Za-z0-9_-]*/,
+
+    operator: _ => choice(
+      "==", "!=", "<", "<=", ">", ">=", "=~", "!~", "+", "-", "*", "/", "//",
+      "**", "++", "..", "..<", "..=", "=>", "+=", "-=", "*=", "/=", "++=", "!",
+    ),
+
+    // Paths, URLs and other unquoted arguments: `~/src/*.rs`, `https://nushell.sh`
+    bare_word: _ => /[^\s|;()\[\]{}"'`$#,:=]+(:[^\s|;()\[\]{}"'`$#,:=]+)*/,
+
+    comment: _ => token(seq("#", /[^\n]*/)),
+  },
+});
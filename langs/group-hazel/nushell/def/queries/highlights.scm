@@ -0,0 +1,139 @@
+; Nushell highlights query
+
+(comment) @comment
+
+[
+  (string)
+  (raw_string)
+  (string_interpolation)
+] @string
+
+(escape_sequence) @string.escape
+
+[
+  (number)
+  (range)
+] @number
+
+(bare_word) @string.special
+
+; Variables
+(variable_name) @variable
+
+((variable_name) @variable.builtin
+  (#any-of? @variable.builtin "$env" "$nu" "$in" "$it"))
+
+(path_member) @property
+
+(let_binding
+  name: (identifier) @variable)
+
+(record_key
+  key: (_) @property)
+
+; Commands
+(command
+  name: (identifier) @function.call)
+
+(command
+  name: (external_command) @function.call)
+
+; `str length`, `path join`, `into int`: the subcommand is part of the name
+(command
+  name: (identifier) @_parent
+  .
+  argument: (identifier) @function.call
+  (#any-of? @_parent
+    "str" "path" "into" "math" "date" "bytes" "random" "url" "split" "format"
+    "from" "to" "keybindings" "config" "polars" "query" "http" "hash" "encode"
+    "decode" "roll" "stor" "plugin" "scope" "view" "debug"))
+
+(flag) @variable.parameter
+
+; Definitions
+(function_definition
+  name: (_) @function)
+
+(parameter
+  name: (_) @variable.parameter)
+
+(parameter
+  short: (flag) @variable.parameter)
+
+(type
+  (identifier) @type)
+
+((identifier) @constant.builtin
+  (#any-of? @constant.builtin "true" "false" "null"))
+
+((identifier) @keyword.operator
+  (#any-of? @keyword.operator
+    "and" "or" "xor" "not" "mod" "not-in" "starts-with" "ends-with" "like"
+    "not-like" "has" "not-has" "bit-and" "bit-or" "bit-xor" "bit-shl" "bit-shr"))
+
+; Keywords
+[
+  "def"
+  "extern"
+  "alias"
+  "let"
+  "mut"
+  "const"
+  "export"
+  "export-env"
+  "module"
+  "use"
+  "hide"
+  "overlay"
+  "source"
+  "source-env"
+] @keyword
+
+[
+  "if"
+  "else"
+  "match"
+] @keyword.conditional
+
+[
+  "for"
+  "in"
+  "while"
+  "loop"
+  "break"
+  "continue"
+] @keyword.repeat
+
+"return" @keyword.return
+
+[
+  "try"
+  "catch"
+] @keyword.exception
+
+"do" @keyword
+
+; Operators and punctuation
+(operator) @operator
+
+"=" @operator
+
+"|" @operator
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+[
+  ","
+  ":"
+  ";"
+] @punctuation.delimiter
+
+(closure_parameters
+  "|" @punctuation.bracket)
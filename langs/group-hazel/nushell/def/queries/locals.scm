@@ -0,0 +1,15 @@
+[
+  (block)
+  (subexpression)
+] @local.scope
+
+(function_definition
+  name: (_) @local.definition.function)
+
+(parameter
+  name: (identifier) @local.definition.parameter)
+
+(let_binding
+  name: (identifier) @local.definition.var)
+
+(variable_name) @local.reference
@@ -12,3 +12,8 @@
   (#match? @_modifiers "e")
   (#not-match? @_modifiers "e.*e")
   (#set! injection.language "perl"))
+
+; <<~SQL ... SQL; the closing delimiter is left out of the injected range
+((heredoc_content
+  (heredoc_end) @injection.language) @injection.content
+  (#downcase! @injection.language))
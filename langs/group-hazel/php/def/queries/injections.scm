@@ -1,10 +1,17 @@
 ((comment) @injection.content
   (#set! injection.language "phpdoc"))
 
+; Everything outside <?php ... ?> is HTML
+((text) @injection.content
+  (#set! injection.language "html"))
+
+; <<<SQL ... SQL
 (heredoc
   (heredoc_body) @injection.content
-  (heredoc_end) @injection.language)
+  (heredoc_end) @injection.language
+  (#downcase! @injection.language))
 
 (nowdoc
   (nowdoc_body) @injection.content
-  (heredoc_end) @injection.language)
+  (heredoc_end) @injection.language
+  (#downcase! @injection.language))
@@ -95,7 +95,7 @@
 "-not" @operator
 
 
-";" @delimiter
+";" @punctuation.delimiter
 
 (string_literal) @string
 
@@ -108,6 +108,13 @@
 (function_statement
   (function_name) @function)
 
+(command_parameter) @variable.parameter
+
+(script_parameter
+  (variable) @variable.parameter)
+
+(label) @label
+
 (invokation_expression
   (member_name) @function)
 
@@ -118,11 +125,27 @@
 
 (type_spec) @type
 
+(attribute_name
+  (type_spec) @attribute)
+
+(class_statement
+  . (simple_name) @type.definition)
+
+(enum_statement
+  . (simple_name) @type.definition)
+
+(enum_member
+  (simple_name) @constant)
+
+(key_expression
+  (simple_name) @property)
+
 (variable) @variable
 
-(comment) @comment
+((variable) @variable.builtin
+  (#match? @variable.builtin "^\\$(?i)(env:[a-z0-9_]+|_|psitem|psscriptroot|pscommandpath|args|this|input|host|error|lastexitcode)$"))
 
-(array_expression) @array
+((variable) @constant.builtin
+  (#match? @constant.builtin "^\\$(?i)(true|false|null)$"))
 
-(assignment_expression
-  value: (pipeline) @assignvalue)
\ No newline at end of file
+(comment) @comment
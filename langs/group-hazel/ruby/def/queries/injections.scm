@@ -0,0 +1,5 @@
+; <<~SQL ... SQL
+(heredoc_body
+  (heredoc_content) @injection.content
+  (heredoc_end) @injection.language
+  (#downcase! @injection.language))
@@ -0,0 +1,82 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// Augmented Backus-Naur Form (RFC 5234, with the case-sensitive strings of
+// RFC 7405)
+//
+// ABNF is line-oriented: a rule continues on lines that start with
+// whitespace. This grammar treats newlines as whitespace instead and ends a
+// rule where the next `name =` begins, which accepts every well-formed file.
+
+module.exports = grammar({
+  name: "abnf",
+
+  extras: $ => [/\s/, $.comment],
+
+  word: $ => $.rulename,
+
+  conflicts: $ => [
+    [$.concatenation],
+    // `a b` could be the end of a rule followed by the next one; the `=`
+    // after `b` decides
+    [$._elements, $.concatenation],
+  ],
+
+  rules: {
+    source_file: $ => repeat($.rule),
+
+    rule: $ => seq(
+      field("name", $.rulename),
+      field("operator", choice("=", "=/")),
+      field("definition", $._elements),
+    ),
+
+    _elements: $ => choice(
+      $._repetition,
+      $.alternation,
+      $.concatenation,
+    ),
+
+    alternation: $ => prec.left(1, seq($._elements, "/", $._elements)),
+
+    concatenation: $ => seq($._repetition, repeat1($._repetition)),
+
+    _repetition: $ => choice($._element, $.repetition),
+
+    // `*DIGIT`, `1*ALPHA`, `2*5HEXDIG`, `4OCTET`
+    repetition: $ => seq(field("repeat", $.repeat), $._element),
+
+    repeat: $ => token(choice(
+      /[0-9]*\*[0-9]*/,
+      /[0-9]+/,
+    )),
+
+    _element: $ => choice(
+      $.rulename,
+      $.group,
+      $.option,
+      $.char_val,
+      $.num_val,
+      $.prose_val,
+    ),
+
+    group: $ => seq("(", $._elements, ")"),
+    option: $ => seq("[", $._elements, "]"),
+
+    rulename: $ => /[A-Za-z][A-Za-z0-9-]*/,
+
+    // "GET", %s"GET" (case-sensitive), %i"get"
+    char_val: $ => token(seq(optional(/%[sSiI]/), '"', /[^"\n]*/, '"')),
+
+    // %x41, %x30-39, %d13.10, %b0101
+    num_val: $ => token(choice(
+      /%[bB][01]+(-[01]+|(\.[01]+)+)?/,
+      /%[dD][0-9]+(-[0-9]+|(\.[0-9]+)+)?/,
+      /%[xX][0-9a-fA-F]+(-[0-9a-fA-F]+|(\.[0-9a-fA-F]+)+)?/,
+    )),
+
+    prose_val: $ => /<[^>\n]*>/,
+
+    comment: $ => token(seq(";", /.*/)),
+  },
+});
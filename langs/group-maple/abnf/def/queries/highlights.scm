@@ -0,0 +1,23 @@
+(rulename) @variable
+(rule name: (rulename) @function)
+
+; Core rules from RFC 5234 appendix B
+((rulename) @variable.builtin
+  (#match? @variable.builtin "^(ALPHA|BIT|CHAR|CR|CRLF|CTL|DIGIT|DQUOTE|HEXDIG|HTAB|LF|LWSP|OCTET|SP|VCHAR|WSP)$"))
+
+(char_val) @string
+(num_val) @string.special
+(prose_val) @string.special
+(repeat) @number
+
+(rule operator: _ @operator)
+"/" @operator
+
+[
+  "("
+  ")"
+  "["
+  "]"
+] @punctuation.bracket
+
+(comment) @comment
@@ -3,3 +3,27 @@
   (line_comment)
 ] @injection.content
   (#set! injection.language "comment"))
+
+; `COMMAND sh -c "..."` in execute_process(), add_custom_command() and
+; add_custom_target(). Other COMMAND arguments are argv, not shell.
+(normal_command
+  (identifier) @_command
+  (argument_list
+    (argument
+      (unquoted_argument) @_shell)
+    .
+    (argument
+      (unquoted_argument) @_flag)
+    .
+    (argument
+      [
+        (quoted_argument
+          (quoted_element) @injection.content)
+        (bracket_argument
+          (bracket_argument_content) @injection.content)
+      ]))
+  (#match? @_command "^(?i)(execute_process|add_custom_command|add_custom_target)$")
+  (#match? @_shell "^(/usr)?(/bin/)?(ba|da|z)?sh$")
+  (#match? @_flag "^-[a-z]*c$")
+  (#set! injection.language "bash")
+  (#set! injection.include-children))
@@ -0,0 +1,4 @@
+; Each column gets its own color, cycling through RAINBOW_CAPTURES
+(field) @rainbow
+
+"," @punctuation.delimiter
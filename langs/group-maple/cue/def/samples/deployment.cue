@@ -0,0 +1,56 @@
+package kube
+
+import (
+	"strings"
+	"list"
+)
+
+// Every deployment gets a service unless it opts out.
+#Deployment: {
+	name:      string & =~"^[a-z][a-z0-9-]*$"
+	image:     string
+	replicas:  *1 | int & >=0 & <=10
+	port?:     int
+	expose:    bool | *true
+	labels: [string]: string
+	env: [Name=_]: {
+		name:  Name
+		value: string
+	}
+	_fullName: "\(name)-\(strings.ToLower(image))"
+	...
+}
+
+let registry = "ghcr.io/example"
+
+deployments: [ID=_]: #Deployment & {name: ID}
+
+deployments: {
+	api: {
+		image:    "\(registry)/api:1.4.2"
+		replicas: 3
+		port:     8080
+		labels: tier: "backend"
+		env: LOG_LEVEL: value: "info"
+	}
+	worker: {
+		image:  "\(registry)/worker:1.4.2"
+		expose: false
+	} @protobuf(1,name=worker)
+}
+
+services: {
+	for id, d in deployments if d.expose && d.port != _|_ {
+		"\(id)": {
+			selector: app: id
+			ports: [{port: d.port, targetPort: d.port}]
+		}
+	}
+}
+
+summary: """
+	\(len(deployments)) deployments, \(list.Sum([for d in deployments {d.replicas}])) replicas
+	"""
+raw:  #"C:\no\(interpolation)"#
+mask: 0x1F
+mem:  512Mi
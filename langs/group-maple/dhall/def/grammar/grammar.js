@@ -0,0 +1,326 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// Dhall, following the ABNF in
+// https://github.com/dhall-lang/dhall-lang/blob/master/standard/dhall.abnf
+//
+// Unicode and ASCII spellings of operators (λ and \, → and ->, ∧ and /\)
+// parse to the same nodes.
+
+const PREC = {
+  lambda: 1,
+  annotation: 2,
+  import_alt: 3,
+  or: 4,
+  plus: 5,
+  text_append: 6,
+  list_append: 7,
+  and: 8,
+  combine: 9,
+  prefer: 10,
+  combine_types: 11,
+  times: 12,
+  equal: 13,
+  not_equal: 14,
+  equivalent: 15,
+  application: 16,
+  selector: 17,
+};
+
+const commaSep1 = rule => seq(rule, repeat(seq(",", rule)));
+
+module.exports = grammar({
+  name: "dhall",
+
+  extras: $ => [/\s/, $.line_comment, $.block_comment],
+
+  word: $ => $.label,
+
+  rules: {
+    source_file: $ => $._expression,
+
+    _expression: $ => choice(
+      $.lambda,
+      $.forall,
+      $.function_type,
+      $.let_binding,
+      $.if_then_else,
+      $.merge,
+      $.to_map,
+      $.show_constructor,
+      $.assert,
+      $.annotation,
+      $.empty_list,
+      $.binary,
+      $.with,
+      $.application,
+      $._primitive,
+    ),
+
+    lambda: $ => prec.right(PREC.lambda, seq(
+      choice("\\", "λ"),
+      "(", field("label", $.label), ":", field("type", $._expression), ")",
+      $._arrow,
+      field("body", $._expression),
+    )),
+
+    forall: $ => prec.right(PREC.lambda, seq(
+      choice("forall", "∀"),
+      "(", field("label", $.label), ":", field("type", $._expression), ")",
+      $._arrow,
+      field("body", $._expression),
+    )),
+
+    // Natural → Text
+    function_type: $ => prec.right(PREC.lambda, seq($._expression, $._arrow, $._expression)),
+
+    _arrow: $ => choice("->", "→"),
+
+    let_binding: $ => prec.right(PREC.lambda, seq(
+      repeat1($.let),
+      "in",
+      field("body", $._expression),
+    )),
+
+    let: $ => seq(
+      "let",
+      field("label", $.label),
+      optional(seq(":", field("type", $._expression))),
+      "=",
+      field("value", $._expression),
+    ),
+
+    if_then_else: $ => prec.right(PREC.lambda, seq(
+      "if", field("condition", $._expression),
+      "then", field("consequence", $._expression),
+      "else", field("alternative", $._expression),
+    )),
+
+    merge: $ => prec.right(PREC.application, seq(
+      "merge",
+      $._primitive,
+      $._primitive,
+      optional(seq(":", $._expression)),
+    )),
+
+    to_map: $ => prec.right(PREC.application, seq(
+      "toMap",
+      $._primitive,
+      optional(seq(":", $._expression)),
+    )),
+
+    show_constructor: $ => prec(PREC.application, seq("showConstructor", $._primitive)),
+
+    assert: $ => prec.right(PREC.lambda, seq("assert", ":", $._expression)),
+
+    annotation: $ => prec.right(PREC.annotation, seq($._expression, ":", $._expression)),
+
+    empty_list: $ => prec.right(PREC.annotation, seq("[", "]", ":", $._expression)),
+
+    with: $ => prec.left(PREC.annotation, seq(
+      $._expression,
+      "with",
+      field("path", seq($.label, repeat(seq(".", $.label)))),
+      "=",
+      $._expression,
+    )),
+
+    binary: $ => choice(
+      ...[
+        [PREC.import_alt, "?"],
+        [PREC.or, "||"],
+        [PREC.plus, "+"],
+        [PREC.text_append, "++"],
+        [PREC.list_append, "#"],
+        [PREC.and, "&&"],
+        [PREC.combine, choice("/\\", "∧")],
+        [PREC.prefer, choice("//", "⫽")],
+        [PREC.combine_types, choice("//\\\\", "⩓")],
+        [PREC.times, "*"],
+        [PREC.equal, "=="],
+        [PREC.not_equal, "!="],
+        [PREC.equivalent, choice("===", "≡")],
+      ].map(([precedence, operator]) =>
+        prec.left(precedence, seq(
+          field("left", $._expression),
+          field("operator", operator),
+          field("right", $._expression),
+        )),
+      ),
+    ),
+
+    application: $ => prec.left(PREC.application, seq(
+      field("function", $._expression),
+      field("argument", $._primitive),
+    )),
+
+    _primitive: $ => choice(
+      $.natural,
+      $.integer,
+      $.double,
+      $.text,
+      $.builtin,
+      $.label,
+      $.variable,
+      $.record_type,
+      $.record_literal,
+      $.union_type,
+      $.list,
+      $.selector,
+      $.completion,
+      $.import,
+      seq("(", $._expression, ")"),
+    ),
+
+    selector: $ => prec.left(PREC.selector, seq(
+      $._primitive,
+      ".",
+      choice(
+        field("field", $.label),
+        seq("{", optional(commaSep1($.label)), "}"),
+        seq("(", $._expression, ")"),
+      ),
+    )),
+
+    // Record completion: `Config::{ port = 80 }` is `Config.default // { ... } : Config.Type`
+    completion: $ => prec.left(PREC.selector, seq($._primitive, "::", $._primitive)),
+
+    // Records and unions
+
+    record_type: $ => seq(
+      "{",
+      optional(","),
+      optional(commaSep1($.record_type_field)),
+      "}",
+    ),
+
+    record_type_field: $ => seq(field("label", $.label), ":", field("type", $._expression)),
+
+    record_literal: $ => seq(
+      "{",
+      optional(","),
+      choice("=", commaSep1($.record_literal_field)),
+      "}",
+    ),
+
+    // `{ a.b.c = 1 }` and the `{ a }` punning shorthand
+    record_literal_field: $ => seq(
+      field("label", seq($.label, repeat(seq(".", $.label)))),
+      optional(seq("=", field("value", $._expression))),
+    ),
+
+    union_type: $ => seq(
+      "<",
+      optional("|"),
+      optional(seq(
+        $.union_type_field,
+        repeat(seq("|", $.union_type_field)),
+      )),
+      ">",
+    ),
+
+    union_type_field: $ => seq(
+      field("label", $.label),
+      optional(seq(":", field("type", $._expression))),
+    ),
+
+    list: $ => seq("[", optional(","), commaSep1($._expression), "]"),
+
+    // Literals
+
+    natural: $ => token(choice(/[0-9]+/, /0x[0-9a-fA-F]+/)),
+
+    integer: $ => token(seq(/[+-]/, choice(/[0-9]+/, /0x[0-9a-fA-F]+/))),
+
+    double: $ => token(choice(
+      /[+-]?[0-9]+(\.[0-9]+([eE][+-]?[0-9]+)?|[eE][+-]?[0-9]+)/,
+      "NaN",
+      "Infinity",
+      "-Infinity",
+    )),
+
+    text: $ => choice(
+      seq(
+        '"',
+        repeat(choice(
+          token.immediate(prec(1, /[^"\\$]+|\$/)),
+          $.escape_sequence,
+          $.interpolation,
+        )),
+        '"',
+      ),
+      seq(
+        "''",
+        repeat(choice(
+          token.immediate(prec(1, /([^'$]|'[^']|\$[^{])+/)),
+          alias(token.immediate(choice("'''", "''${")), $.escape_sequence),
+          $.interpolation,
+        )),
+        token.immediate("''"),
+      ),
+    ),
+
+    escape_sequence: $ => token.immediate(/\\(["$\\/bfnrt]|u[0-9a-fA-F]{4}|u\{[0-9a-fA-F]+\})/),
+
+    // Outranks the lone `$` of plain text
+    interpolation: $ => seq(token.immediate(prec(2, "${")), $._expression, "}"),
+
+    builtin: $ => choice(
+      "Natural/fold", "Natural/build", "Natural/isZero", "Natural/even", "Natural/odd",
+      "Natural/toInteger", "Natural/show", "Natural/subtract",
+      "Integer/toDouble", "Integer/show", "Integer/negate", "Integer/clamp",
+      "Double/show",
+      "List/build", "List/fold", "List/length", "List/head", "List/last",
+      "List/indexed", "List/reverse",
+      "Text/show", "Text/replace",
+      "Date/show", "Time/show", "TimeZone/show",
+      "Bool", "Natural", "Integer", "Double", "Text", "List", "Optional",
+      "Date", "Time", "TimeZone",
+      "Type", "Kind", "Sort",
+      "True", "False", "None", "Some",
+    ),
+
+    // `x@1` refers to the second-innermost binding of x
+    variable: $ => seq($.label, token.immediate("@"), token.immediate(/[0-9]+/)),
+
+    label: $ => token(choice(
+      /[A-Za-z_][A-Za-z0-9_\/-]*/,
+      /`[^`]*`/,
+    )),
+
+    // Imports
+
+    import: $ => prec.right(seq(
+      choice($.local_import, $.http_import, $.env_import, $.missing),
+      optional($.hash),
+      optional(seq("as", choice("Text", "Location", "Bytes"))),
+    )),
+
+    // The first path character can't be / or \, so `//` and `/\` stay operators
+    local_import: $ => token(seq(
+      optional(choice(".", "..", "~")),
+      "/",
+      /[^\s()\[\]{}<>,#\/\\][^\s()\[\]{}<>,#]*/,
+    )),
+
+    http_import: $ => seq(
+      token(seq(/https?:\/\//, /[^\s()\[\]{}<>,]+/)),
+      optional(seq("using", $._primitive)),
+    ),
+
+    env_import: $ => token(seq("env:", choice(/[A-Za-z_][A-Za-z0-9_]*/, /"[^"]*"/))),
+
+    missing: $ => "missing",
+
+    hash: $ => token(seq("sha256:", /[0-9a-fA-F]{64}/)),
+
+    line_comment: $ => token(seq("--", /[^\n]*/)),
+
+    // Block comments nest in Dhall; one level is handled here
+    block_comment: $ => token(seq(
+      "{-",
+      repeat(choice(/[^-{]/, /-[^}]/, /\{[^-]/, seq("{-", /([^-]|-[^}])*/, "-}"))),
+      "-}",
+    )),
+  },
+});
@@ -0,0 +1,81 @@
+; Keywords
+
+[
+  "let"
+  "in"
+  "merge"
+  "toMap"
+  "showConstructor"
+  "assert"
+  "with"
+  "using"
+  "as"
+  "forall"
+  "∀"
+  "\\"
+  "λ"
+] @keyword
+
+[
+  "if"
+  "then"
+  "else"
+] @keyword.conditional
+
+(missing) @keyword.import
+
+; Builtins: types by default, then functions and constants
+
+(builtin) @type.builtin
+
+((builtin) @function.builtin
+  (#match? @function.builtin "/"))
+
+((builtin) @constant.builtin.boolean
+  (#any-of? @constant.builtin.boolean "True" "False"))
+
+((builtin) @constructor.builtin
+  (#any-of? @constructor.builtin "None" "Some"))
+
+; Bindings
+
+(let label: (label) @variable)
+(lambda label: (label) @variable.parameter)
+(forall label: (label) @variable.parameter)
+
+; Records and unions
+
+(record_type_field label: (label) @property)
+(record_literal_field label: (label) @property)
+(selector field: (label) @variable.member)
+(union_type_field label: (label) @constructor)
+
+; Literals
+
+(text) @string
+(escape_sequence) @string.escape
+(interpolation ["${" "}"] @punctuation.special)
+[(natural) (integer)] @number
+(double) @number.float
+
+; Imports
+
+[
+  (local_import)
+  (http_import)
+  (env_import)
+] @string.special.path
+(hash) @string.special
+
+[
+  (line_comment)
+  (block_comment)
+] @comment
+
+; Operators and punctuation
+
+(binary operator: _ @operator)
+["->" "→" "=" ":" "::"] @operator
+
+["(" ")" "[" "]" "{" "}" "<" ">"] @punctuation.bracket
+["," "." "|"] @punctuation.delimiter
@@ -0,0 +1,61 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// .env files as read by dotenv, docker compose and shells
+//
+// `KEY=value` lines with optional `export`, single-quoted (raw), double-quoted
+// (escapes and `${VAR}` interpolation, may span lines) and unquoted values.
+// A `#` starts a comment at the start of a line or after whitespace.
+
+module.exports = grammar({
+  name: "dotenv",
+
+  extras: $ => [/[ \t]/],
+
+  word: $ => $.variable,
+
+  rules: {
+    source_file: $ => repeat(choice($.assignment, $.comment, /\r?\n/)),
+
+    assignment: $ => seq(
+      optional("export"),
+      field("name", $.variable),
+      "=",
+      optional(field("value", choice($.value, $.string, $.raw_string))),
+      optional($.comment),
+      /\r?\n/,
+    ),
+
+    variable: $ => /[A-Za-z_][A-Za-z0-9_.\-]*/,
+
+    // Runs of non-blank characters separated by blanks, until a ` #` comment
+    value: $ => /[^\s"'#]([^\s]|[ \t]+[^\s#])*/,
+
+    string: $ => seq(
+      '"',
+      repeat(choice(
+        token.immediate(prec(1, /[^"\\$]+/)),
+        $.escape_sequence,
+        $.interpolation,
+      )),
+      token.immediate('"'),
+    ),
+
+    raw_string: $ => token(seq("'", /[^']*/, "'")),
+
+    escape_sequence: $ => token.immediate(/\\[\\"'$nrt\n]/),
+
+    // ${VAR}, ${VAR:-default}, $VAR
+    interpolation: $ => choice(
+      seq(
+        token.immediate("${"),
+        field("name", alias(/[A-Za-z_][A-Za-z0-9_]*/, $.variable)),
+        optional(seq(field("operator", alias(/:?[-+?=]/, $.operator)), optional(field("default", alias(/[^}]+/, $.value))))),
+        "}",
+      ),
+      seq(token.immediate("$"), field("name", alias(token.immediate(/[A-Za-z_][A-Za-z0-9_]*/), $.variable))),
+    ),
+
+    comment: $ => token(seq("#", /[^\n]*/)),
+  },
+});
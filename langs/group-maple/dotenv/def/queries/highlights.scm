@@ -0,0 +1,24 @@
+"export" @keyword
+
+(assignment name: (variable) @variable)
+"=" @operator
+
+; Matched from the assignment, like the secret pattern below, so that
+; one can override the other
+(assignment value: _ @string)
+(interpolation default: (value) @string)
+(escape_sequence) @string.escape
+
+(interpolation
+  ["${" "$" "}"] @punctuation.special)
+(interpolation name: (variable) @variable)
+(interpolation operator: _ @operator)
+
+; `API_TOKEN=...`, `DB_PASSWORD=...`: secrets a theme may blur
+
+(assignment
+  name: (variable) @_key
+  value: _ @string.special.secret
+  (#match? @_key "(?i)(^|[_.-])(token|secret|passw(or)?d|pwd|pass|key|apikey|credentials?|auth|dsn)($|[_.-])"))
+
+(comment) @comment
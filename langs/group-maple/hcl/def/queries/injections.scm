@@ -0,0 +1,32 @@
+; Heredocs tagged with a language: <<JSON, <<-YAML, <<SH
+; Tags that aren't a language (<<EOT, <<EOF) are left alone.
+(heredoc_template
+  (template_literal) @injection.content
+  (heredoc_identifier) @injection.language
+  (#downcase! @injection.language))
+
+; Strings decoded as JSON or YAML. `jsonencode()` and `yamlencode()` take HCL
+; values rather than strings, so the HCL grammar already covers them.
+((function_call
+  (identifier) @_fn
+  (function_arguments
+    . (expression
+      [
+        (literal_value (string_lit (template_literal) @injection.content))
+        (template_expr (quoted_template (template_literal) @injection.content))
+        (template_expr (heredoc_template (template_literal) @injection.content))
+      ])))
+  (#eq? @_fn "jsondecode")
+  (#set! injection.language "json"))
+
+((function_call
+  (identifier) @_fn
+  (function_arguments
+    . (expression
+      [
+        (literal_value (string_lit (template_literal) @injection.content))
+        (template_expr (quoted_template (template_literal) @injection.content))
+        (template_expr (heredoc_template (template_literal) @injection.content))
+      ])))
+  (#eq? @_fn "yamldecode")
+  (#set! injection.language "yaml"))
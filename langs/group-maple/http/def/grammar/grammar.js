@@ -0,0 +1,128 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// HTTP/1.1 messages (RFC 9112, formerly RFC 7230), as written in API docs
+// and REST client `.http` files
+//
+// A request or status line, header fields, then a body after the first
+// blank line. The media type of a Content-Type header is parsed, so the
+// body can be injected in the language it names. The body runs until a
+// status line, so a response can follow its request, or until a `###`
+// separator like in REST client files, where `#` and `//` lines outside
+// messages are comments.
+
+// A line that doesn't start with `###` or `HTTP/`
+const BODY_LINE = "([^#H\\n]|#[^#\\n]|##[^#\\n]|H[^T\\n]|HT[^T\\n]|HTT[^P\\n]|HTTP[^/\\n])[^\\n]*";
+
+/** @param {RegExp} re */
+const caseInsensitive = re => new RegExp(
+  re.source.replace(/[a-z]/g, c => `[${c}${c.toUpperCase()}]`),
+);
+
+module.exports = grammar({
+  name: "http",
+
+  extras: _ => [/[ \t\r]/],
+
+  rules: {
+    document: $ => repeat(choice(
+      $.request,
+      $.response,
+      $.separator,
+      $.comment,
+      $._nl,
+    )),
+
+    request: $ => message($, $.request_line),
+
+    response: $ => message($, $.status_line),
+
+    // `GET /users/1 HTTP/1.1`; the version is often left out in docs
+    request_line: $ => prec.right(seq(
+      field("method", $.method),
+      field("target", $.target),
+      optional(field("version", $.version)),
+    )),
+
+    // `HTTP/1.1 404 Not Found`
+    status_line: $ => seq(
+      field("version", $.version),
+      field("status", $.status_code),
+      optional(field("reason", $.reason)),
+    ),
+
+    method: _ => /[A-Z]+/,
+
+    target: _ => /[^\s]+/,
+
+    version: _ => /HTTP\/\d(\.\d)?/,
+
+    status_code: _ => /\d{3}/,
+
+    // Preferred over a method, so all-caps phrases like `OK` stay reasons
+    reason: _ => token(prec(1, /[^\n]+/)),
+
+    header: $ => choice(
+      seq(
+        field("name", alias($._content_type_name, $.header_name)),
+        ":",
+        optional(field("value", $.media_type)),
+        repeat(seq(";", optional(field("parameter", $.parameter)))),
+      ),
+      seq(
+        field("name", $.header_name),
+        ":",
+        optional(field("value", $.header_value)),
+      ),
+    ),
+
+    // Preferred over a method, so all-caps names like `DNT` stay headers
+    header_name: _ => token(prec(1, /[!$%&'*+.^_`|~0-9A-Za-z-]+/)),
+
+    _content_type_name: _ => token(prec(2, caseInsensitive(/content-type/))),
+
+    header_value: _ => /[^\n]+/,
+
+    // `application/problem+json`
+    media_type: $ => seq(
+      field("type", $.type),
+      "/",
+      field("subtype", $.subtype),
+      optional(seq("+", field("suffix", $.suffix))),
+    ),
+
+    type: _ => /[\w.-]+/,
+
+    subtype: _ => /[\w.-]+/,
+
+    suffix: _ => /[\w.-]+/,
+
+    // `charset=utf-8`
+    parameter: _ => /[^;\n]+/,
+
+    body: _ => new RegExp(`${BODY_LINE}(\\n(${BODY_LINE})?)*`),
+
+    separator: _ => token(prec(1, /###[^\n]*/)),
+
+    comment: _ => /(#|\/\/)[^\n]*/,
+
+    _nl: _ => "\n",
+  },
+});
+
+/**
+ * A start line, header fields, and an optional body after a blank line.
+ *
+ * @param {GrammarSymbols<string>} $
+ * @param {RuleOrLiteral} startLine
+ */
+function message($, startLine) {
+  return prec.right(seq(
+    startLine,
+    optional(seq(
+      $._nl,
+      repeat(seq(field("header", $.header), $._nl)),
+      optional(seq($._nl, optional(field("body", $.body)))),
+    )),
+  ));
+}
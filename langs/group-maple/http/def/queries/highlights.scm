@@ -0,0 +1,23 @@
+(comment) @comment
+
+(separator) @punctuation.special
+
+(method) @keyword
+
+(target) @markup.link.url
+
+(version) @constant.builtin
+
+(status_code) @number
+
+(reason) @string
+
+(header_name) @property
+
+(header_value) @string
+
+(media_type) @type
+
+(parameter) @variable.parameter
+
+[":" ";" "/" "+"] @punctuation.delimiter
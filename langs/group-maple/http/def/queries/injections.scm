@@ -0,0 +1,11 @@
+; The body is in the language named by the Content-Type subtype
+; (`application/json`, `text/html`), or by its structured syntax suffix
+; (`application/problem+json`)
+
+(_
+  header: (header value: (media_type subtype: (subtype) @injection.language !suffix))
+  body: (body) @injection.content)
+
+(_
+  header: (header value: (media_type suffix: (suffix) @injection.language))
+  body: (body) @injection.content)
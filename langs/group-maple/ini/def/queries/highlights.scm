@@ -14,3 +14,9 @@
   (setting_name) @property)
 
 ; (setting_value) @none ; grammar does not support subtypes
+
+; `password = ...` in any section
+(setting
+  (setting_name) @_key
+  (setting_value) @string.special.secret
+  (#match? @_key "(?i)(^|[_.-])(token|secret|passw(or)?d|pwd|pass|key|apikey|credentials?|auth|dsn)($|[_.-])"))
@@ -0,0 +1,247 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// LogQL, the Grafana Loki query language
+//
+// Follows https://grafana.com/docs/loki/latest/query/. Log queries are a
+// stream selector followed by a pipeline; metric queries wrap log queries in
+// range aggregations and otherwise look like PromQL.
+
+const PREC = {
+  or: 1,
+  and: 2,
+  comparison: 3,
+  additive: 4,
+  multiplicative: 5,
+  unary: 6,
+  power: 7,
+};
+
+const commaSep = rule => optional(seq(rule, repeat(seq(",", rule)), optional(",")));
+const commaSep1 = rule => seq(rule, repeat(seq(",", rule)));
+
+module.exports = grammar({
+  name: "logql",
+
+  extras: $ => [/\s/, $.comment],
+
+  word: $ => $.identifier,
+
+  rules: {
+    source_file: $ => optional(choice($.log_query, $._metric_expression)),
+
+    // Log queries
+
+    log_query: $ => prec.right(seq($.stream_selector, repeat($._pipeline_stage))),
+
+    // {app="api", env=~"prod|staging"}
+    stream_selector: $ => seq("{", commaSep($.label_matcher), "}"),
+
+    label_matcher: $ => seq(
+      field("label", $._label_name),
+      field("operator", choice("=", "!=", "=~", "!~")),
+      field("value", $.string),
+    ),
+
+    _label_name: $ => alias($.identifier, $.label_name),
+
+    _pipeline_stage: $ => choice(
+      $.line_filter,
+      $.parser,
+      $.label_filter,
+      $.line_format,
+      $.label_format,
+      $.drop_keep,
+      $.unwrap,
+      $.decolorize,
+    ),
+
+    // |= "error" or "warn", != ip("10.0.0.0/8"), |~ `(?i)timeout`
+    line_filter: $ => prec.left(seq(
+      field("operator", choice("|=", "!=", "|~", "!~", "|>", "!>")),
+      $._filter_value,
+      repeat(seq("or", $._filter_value)),
+    )),
+
+    _filter_value: $ => choice($.string, $.ip_filter),
+
+    ip_filter: $ => seq("ip", "(", $.string, ")"),
+
+    // | json, | logfmt --strict, | regexp "(?P<level>\w+)", | pattern "<ip> - <_>"
+    parser: $ => seq(
+      "|",
+      choice(
+        seq("json", optional(commaSep1($.label_extraction))),
+        seq("logfmt", repeat($.flag), optional(commaSep1($.label_extraction))),
+        seq(field("kind", choice("regexp", "pattern")), field("expression", $.string)),
+        "unpack",
+      ),
+    ),
+
+    flag: $ => /--[a-z][a-z-]*/,
+
+    // first_server="servers[0]"
+    label_extraction: $ => choice(
+      $._label_name,
+      seq($._label_name, "=", $.string),
+    ),
+
+    // | status >= 500 and duration > 2s, | level="error" or level="warn"
+    label_filter: $ => seq("|", $._label_filter_expression),
+
+    _label_filter_expression: $ => choice(
+      $.label_comparison,
+      $.label_filter_binary,
+      seq("(", $._label_filter_expression, ")"),
+    ),
+
+    label_filter_binary: $ => choice(
+      prec.left(PREC.and, seq($._label_filter_expression, optional(choice("and", ",")), $._label_filter_expression)),
+      prec.left(PREC.or, seq($._label_filter_expression, "or", $._label_filter_expression)),
+    ),
+
+    label_comparison: $ => seq(
+      field("label", $._label_name),
+      field("operator", choice("=", "==", "!=", "=~", "!~", ">", ">=", "<", "<=")),
+      field("value", choice($.string, $.number, $.duration, $.bytes, $.ip_filter)),
+    ),
+
+    // | line_format "{{.level}} {{.msg}}"
+    line_format: $ => seq("|", "line_format", field("template", $.string)),
+
+    // | label_format dst=src, msg="{{.msg | lower}}"
+    label_format: $ => seq(
+      "|",
+      "label_format",
+      commaSep1(seq($._label_name, "=", choice($._label_name, $.string))),
+    ),
+
+    // | drop __error__, level="debug"; `!=` after a name is a matcher, not
+    // the next line filter
+    drop_keep: $ => seq(
+      "|",
+      choice("drop", "keep"),
+      commaSep1($._drop_keep_label),
+    ),
+
+    _drop_keep_label: $ => prec.right(choice($._label_name, $.label_matcher)),
+
+    // | unwrap bytes(size), | unwrap latency_ms
+    unwrap: $ => seq(
+      "|",
+      "unwrap",
+      choice(
+        $._label_name,
+        seq(field("conversion", choice("bytes", "duration", "duration_seconds")), "(", $._label_name, ")"),
+      ),
+    ),
+
+    decolorize: $ => seq("|", "decolorize"),
+
+    // Metric queries
+
+    _metric_expression: $ => choice(
+      $.range_aggregation,
+      $.vector_aggregation,
+      $.binary_expression,
+      $.unary_expression,
+      $.number,
+      $.function_call,
+      seq("(", $._metric_expression, ")"),
+    ),
+
+    // count_over_time({app="api"} |= "error" [5m]), quantile_over_time(0.99, ... [1m]) by (host)
+    range_aggregation: $ => seq(
+      field("function", $.range_function),
+      "(",
+      optional(seq($.number, ",")),
+      choice($.log_query, seq("(", $.log_query, ")")),
+      $.range,
+      optional(seq("offset", $.duration)),
+      ")",
+      optional($.grouping),
+    ),
+
+    range_function: $ => choice(
+      "rate", "rate_counter", "count_over_time", "bytes_rate", "bytes_over_time",
+      "absent_over_time", "sum_over_time", "avg_over_time", "max_over_time",
+      "min_over_time", "first_over_time", "last_over_time", "stdvar_over_time",
+      "stddev_over_time", "quantile_over_time",
+    ),
+
+    range: $ => seq("[", $.duration, "]"),
+
+    // sum by (app) (rate(...)), topk(5, ...)
+    vector_aggregation: $ => seq(
+      field("operator", $.aggregation_operator),
+      choice(
+        seq($.grouping, $.arguments),
+        seq($.arguments, optional($.grouping)),
+      ),
+    ),
+
+    aggregation_operator: $ => choice(
+      "sum", "avg", "min", "max", "stddev", "stdvar", "count", "topk", "bottomk", "sort", "sort_desc",
+    ),
+
+    arguments: $ => seq("(", commaSep($._metric_expression), ")"),
+
+    grouping: $ => seq(choice("by", "without"), "(", commaSep($._label_name), ")"),
+
+    // label_replace(...), vector(0)
+    function_call: $ => seq(
+      field("name", $.identifier),
+      "(",
+      commaSep(choice($._metric_expression, $.string)),
+      ")",
+    ),
+
+    unary_expression: $ => prec(PREC.unary, seq(choice("+", "-"), $._metric_expression)),
+
+    binary_expression: $ => choice(
+      ...[
+        [PREC.or, "or", prec.left],
+        [PREC.and, choice("and", "unless"), prec.left],
+        [PREC.comparison, choice("==", "!=", ">", "<", ">=", "<="), prec.left],
+        [PREC.additive, choice("+", "-"), prec.left],
+        [PREC.multiplicative, choice("*", "/", "%"), prec.left],
+        [PREC.power, "^", prec.right],
+      ].map(([precedence, operator, assoc]) =>
+        // @ts-ignore
+        assoc(precedence, seq(
+          field("left", $._metric_expression),
+          field("operator", operator),
+          optional("bool"),
+          optional($.vector_matching),
+          field("right", $._metric_expression),
+        )),
+      ),
+    ),
+
+    // A `(` after `group_left` always opens its label list
+    vector_matching: $ => prec.right(seq(
+      choice("on", "ignoring"),
+      "(", commaSep($._label_name), ")",
+      optional(seq(choice("group_left", "group_right"), optional(seq("(", commaSep($._label_name), ")")))),
+    )),
+
+    // Literals
+
+    identifier: $ => /[a-zA-Z_][a-zA-Z0-9_]*/,
+
+    duration: $ => /([0-9]+(\.[0-9]+)?(ns|us|µs|ms|s|m|h|d|w|y))+/,
+
+    bytes: $ => /[0-9]+(\.[0-9]+)?([KMGTPE]i?)?B/,
+
+    number: $ => /[0-9]+(\.[0-9]+)?([eE][+-]?[0-9]+)?/,
+
+    string: $ => choice(
+      seq('"', repeat(choice(token.immediate(prec(1, /[^"\\\n]+/)), $.escape_sequence)), '"'),
+      token(seq("`", /[^`]*/, "`")),
+    ),
+
+    escape_sequence: $ => token.immediate(/\\([abfnrtv\\'"]|x[0-9a-fA-F]{2}|u[0-9a-fA-F]{4}|[0-7]{3})/),
+
+    comment: $ => token(seq("#", /[^\n]*/)),
+  },
+});
@@ -0,0 +1,90 @@
+; Labels
+
+(label_name) @property
+(duration) @number
+(bytes) @number
+(flag) @attribute
+
+; Pipeline
+
+[
+  "json"
+  "logfmt"
+  "regexp"
+  "pattern"
+  "unpack"
+  "line_format"
+  "label_format"
+  "drop"
+  "keep"
+  "unwrap"
+  "decolorize"
+] @function.builtin
+
+(unwrap conversion: _ @function.builtin)
+(ip_filter "ip" @function.builtin)
+
+; Metric queries
+
+(range_function) @function.builtin
+(aggregation_operator) @function.builtin
+(function_call name: (identifier) @function.call)
+
+[
+  "by"
+  "without"
+  "on"
+  "ignoring"
+  "group_left"
+  "group_right"
+  "bool"
+  "offset"
+] @keyword
+
+[
+  "and"
+  "or"
+  "unless"
+] @keyword.operator
+
+; Literals
+
+(string) @string
+(escape_sequence) @string.escape
+(number) @number
+
+; Operators
+
+(line_filter operator: _ @operator)
+
+[
+  "|"
+  "="
+  "!="
+  "=~"
+  "!~"
+  "=="
+  ">"
+  "<"
+  ">="
+  "<="
+  "+"
+  "-"
+  "*"
+  "/"
+  "%"
+  "^"
+] @operator
+
+"," @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+(comment) @comment
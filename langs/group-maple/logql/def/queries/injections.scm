@@ -0,0 +1,20 @@
+; Regex matchers and filters: app=~"api|web", |~ "(?i)timeout"
+(label_matcher
+  operator: ["=~" "!~"]
+  value: (string) @injection.content
+  (#set! injection.language "regex"))
+
+(label_comparison
+  operator: ["=~" "!~"]
+  value: (string) @injection.content
+  (#set! injection.language "regex"))
+
+(line_filter
+  operator: ["|~" "!~"]
+  (string) @injection.content
+  (#set! injection.language "regex"))
+
+(parser
+  kind: "regexp"
+  expression: (string) @injection.content
+  (#set! injection.language "regex"))
@@ -0,0 +1,119 @@
+; Comments
+; ========
+
+(comment) @comment
+
+; Directives
+; ==========
+
+[
+  "include"
+  "-include"
+  "sinclude"
+] @include
+
+[
+  "ifeq"
+  "ifneq"
+  "ifdef"
+  "ifndef"
+  "else"
+  "endif"
+] @keyword.conditional
+
+[
+  "define"
+  "endef"
+  "export"
+  "unexport"
+  "undefine"
+  "override"
+  "private"
+  "vpath"
+] @keyword
+
+; Operators and punctuation
+; =========================
+
+[
+  "="
+  ":="
+  "::="
+  "?="
+  "+="
+  "!="
+] @operator
+
+(recipe_line
+  [
+    "@"
+    "-"
+    "+"
+  ] @punctuation.special)
+
+[
+  ":"
+  "::"
+  "&:"
+  "|"
+  ";"
+  ","
+] @punctuation.delimiter
+
+[
+  "$"
+  "$$"
+] @punctuation.special
+
+[
+  "("
+  ")"
+  "{"
+  "}"
+] @punctuation.bracket
+
+; Rules
+; =====
+
+(targets (word) @function)
+
+(targets
+  (word) @function.builtin
+  (#match? @function.builtin "^\\.(PHONY|SUFFIXES|DEFAULT|PRECIOUS|INTERMEDIATE|NOTINTERMEDIATE|SECONDARY|SECONDEXPANSION|DELETE_ON_ERROR|IGNORE|LOW_RESOLUTION_TIME|SILENT|EXPORT_ALL_VARIABLES|NOTPARALLEL|ONESHELL|POSIX)$"))
+
+(prerequisites (word) @string.special.path)
+
+; Variables
+; =========
+
+(variable_assignment name: (word) @variable)
+(shell_assignment name: (word) @variable)
+(define_directive name: (word) @variable)
+(variable_reference (word) @variable)
+(automatic_variable) @variable.builtin
+
+[
+  "VPATH"
+  ".RECIPEPREFIX"
+] @variable.builtin
+
+(variable_reference
+  (word) @variable.builtin
+  (#any-of? @variable.builtin
+    "MAKE" "MAKEFLAGS" "MAKEFILE_LIST" "MAKECMDGOALS" "CURDIR" "SHELL"
+    "CC" "CXX" "CFLAGS" "CXXFLAGS" "CPPFLAGS" "LDFLAGS" "LDLIBS" "AR" "RM"))
+
+; Functions
+; =========
+
+(function_call function: _ @function.builtin)
+(shell_function function: _ @function.builtin)
+
+; Text
+; ====
+
+(variable_assignment value: (text) @string)
+(arguments (text) @string)
+(raw_text) @string
+(string) @string
+(escape) @string.escape
@@ -0,0 +1,7 @@
+; Recipe lines are shell
+((shell_text) @injection.content
+  (#set! injection.language "bash"))
+
+; $(shell ...) and VAR != ...
+((shell_command) @injection.content
+  (#set! injection.language "bash"))
@@ -0,0 +1,45 @@
+; ['sh', '-c', '...'] as the command: of custom_target() and friends, and
+; run_command('sh', '-c', '...'). Strings can be wrapped in an expression
+; node depending on how they were parsed, so accept both shapes.
+(list
+  [
+    (string) @_shell
+    (_
+      (string) @_shell)
+  ]
+  .
+  [
+    (string) @_flag
+    (_
+      (string) @_flag)
+  ]
+  .
+  [
+    (string) @injection.content
+    (_
+      (string) @injection.content)
+  ]
+  (#match? @_shell "^'(/usr)?(/bin/)?(ba|da|z)?sh'$")
+  (#match? @_flag "^'-[a-z]*c'$")
+  (#not-match? @injection.content "^'''")
+  (#offset! @injection.content 0 1 0 -1)
+  (#set! injection.language "bash")
+  (#set! injection.include-children))
+
+(normal_command
+  command: (identifier) @_command
+  (_
+    (string) @_shell)
+  .
+  (_
+    (string) @_flag)
+  .
+  (_
+    (string) @injection.content)
+  (#eq? @_command "run_command")
+  (#match? @_shell "^'(/usr)?(/bin/)?(ba|da|z)?sh'$")
+  (#match? @_flag "^'-[a-z]*c'$")
+  (#not-match? @injection.content "^'''")
+  (#offset! @injection.content 0 1 0 -1)
+  (#set! injection.language "bash")
+  (#set! injection.include-children))
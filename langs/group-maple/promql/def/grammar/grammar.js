@@ -0,0 +1,168 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// PromQL, the Prometheus query language
+//
+// Follows https://prometheus.io/docs/prometheus/latest/querying/basics/.
+
+const PREC = {
+  or: 1,
+  and: 2,
+  comparison: 3,
+  additive: 4,
+  multiplicative: 5,
+  unary: 6,
+  power: 7,
+  postfix: 8,
+};
+
+const commaSep = rule => optional(seq(rule, repeat(seq(",", rule)), optional(",")));
+
+module.exports = grammar({
+  name: "promql",
+
+  extras: $ => [/\s/, $.comment],
+
+  word: $ => $.identifier,
+
+  rules: {
+    source_file: $ => optional($._expression),
+
+    _expression: $ => choice(
+      $.number,
+      $.string,
+      $.vector_selector,
+      $.range_selector,
+      $.subquery,
+      $.offset_expression,
+      $.at_expression,
+      $.function_call,
+      $.aggregation,
+      $.unary_expression,
+      $.binary_expression,
+      $.parenthesized_expression,
+    ),
+
+    parenthesized_expression: $ => seq("(", $._expression, ")"),
+
+    // http_requests_total{job="api", code=~"5.."}
+    vector_selector: $ => choice(
+      seq(field("metric", alias($.identifier, $.metric_name)), optional($.label_selectors)),
+      $.label_selectors,
+    ),
+
+    label_selectors: $ => seq("{", commaSep(choice($.label_matcher, $.string)), "}"),
+
+    label_matcher: $ => seq(
+      field("label", choice($._label_name, $.string)),
+      field("operator", choice("=", "!=", "=~", "!~")),
+      field("value", $.string),
+    ),
+
+    _label_name: $ => alias($.identifier, $.label_name),
+
+    range_selector: $ => prec(PREC.postfix, seq($._expression, "[", $.duration, "]")),
+
+    // rate(x[5m])[30m:1m]
+    subquery: $ => prec(PREC.postfix, seq(
+      $._expression,
+      "[",
+      $.duration,
+      ":",
+      optional($.duration),
+      "]",
+    )),
+
+    offset_expression: $ => prec(PREC.postfix, seq($._expression, "offset", optional("-"), $.duration)),
+
+    // @ 1609746000, @ start()
+    at_expression: $ => prec(PREC.postfix, seq(
+      $._expression,
+      "@",
+      choice($.number, seq(choice("start", "end"), "(", ")")),
+    )),
+
+    function_call: $ => seq(
+      field("name", $.identifier),
+      "(",
+      commaSep($._expression),
+      ")",
+    ),
+
+    // sum by (job) (rate(x[5m])), topk(3, x) without (instance)
+    aggregation: $ => seq(
+      field("operator", $.aggregation_operator),
+      choice(
+        seq($.grouping, $.arguments),
+        seq($.arguments, optional($.grouping)),
+      ),
+    ),
+
+    aggregation_operator: $ => choice(
+      "sum", "min", "max", "avg", "group", "stddev", "stdvar", "count",
+      "count_values", "bottomk", "topk", "quantile", "limitk", "limit_ratio",
+    ),
+
+    arguments: $ => seq("(", commaSep($._expression), ")"),
+
+    grouping: $ => seq(choice("by", "without"), $.label_list),
+
+    label_list: $ => seq("(", commaSep($._label_name), ")"),
+
+    unary_expression: $ => prec(PREC.unary, seq(choice("+", "-"), $._expression)),
+
+    binary_expression: $ => choice(
+      ...[
+        [PREC.or, "or", prec.left],
+        [PREC.and, choice("and", "unless"), prec.left],
+        [PREC.comparison, choice("==", "!=", ">", "<", ">=", "<="), prec.left],
+        [PREC.additive, choice("+", "-"), prec.left],
+        [PREC.multiplicative, choice("*", "/", "%", "atan2"), prec.left],
+        [PREC.power, "^", prec.right],
+      ].map(([precedence, operator, assoc]) =>
+        // @ts-ignore
+        assoc(precedence, seq(
+          field("left", $._expression),
+          field("operator", operator),
+          optional("bool"),
+          optional($.vector_matching),
+          field("right", $._expression),
+        )),
+      ),
+    ),
+
+    // on (instance) group_left (version); a `(` after `group_left` always
+    // opens its label list
+    vector_matching: $ => prec.right(seq(
+      choice("on", "ignoring"),
+      $.label_list,
+      optional(seq(choice("group_left", "group_right"), optional($.label_list))),
+    )),
+
+    // Function, metric and label names; only metric names may contain `:`.
+    // Names starting with `:` are left out so `[1d:5m]` lexes as a subquery.
+    identifier: $ => /[a-zA-Z_][a-zA-Z0-9_:]*/,
+
+    // 5m, 1h30m, 500ms
+    duration: $ => /([0-9]+(ms|s|m|h|d|w|y))+/,
+
+    // `Inf` and `NaN` take precedence over metric names
+    number: $ => token(prec(1, choice(
+      /[0-9]+(\.[0-9]*)?([eE][+-]?[0-9]+)?/,
+      /\.[0-9]+([eE][+-]?[0-9]+)?/,
+      /0[xX][0-9a-fA-F]+/,
+      /[iI][nN][fF]/,
+      /[nN][aA][nN]/,
+    ))),
+
+    string: $ => choice(
+      seq('"', repeat(choice(token.immediate(prec(1, /[^"\\\n]+/)), $.escape_sequence)), '"'),
+      seq("'", repeat(choice(token.immediate(prec(1, /[^'\\\n]+/)), $.escape_sequence)), "'"),
+      token(seq("`", /[^`]*/, "`")),
+    ),
+
+    escape_sequence: $ => token.immediate(/\\([abfnrtv\\'"]|x[0-9a-fA-F]{2}|u[0-9a-fA-F]{4}|U[0-9a-fA-F]{8}|[0-7]{3})/),
+
+    comment: $ => token(seq("#", /[^\n]*/)),
+  },
+});
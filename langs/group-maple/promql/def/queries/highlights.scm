@@ -0,0 +1,74 @@
+; Selectors
+
+(metric_name) @variable
+(label_name) @property
+(label_matcher label: (string) @property)
+(duration) @number
+
+; Functions and aggregations
+
+(function_call name: (identifier) @function.builtin)
+(aggregation_operator) @function.builtin
+
+[
+  "by"
+  "without"
+  "on"
+  "ignoring"
+  "group_left"
+  "group_right"
+  "bool"
+  "offset"
+  "start"
+  "end"
+] @keyword
+
+[
+  "and"
+  "or"
+  "unless"
+  "atan2"
+] @keyword.operator
+
+; Literals
+
+(string) @string
+(escape_sequence) @string.escape
+(number) @number
+
+; Operators
+
+[
+  "="
+  "!="
+  "=~"
+  "!~"
+  "=="
+  ">"
+  "<"
+  ">="
+  "<="
+  "+"
+  "-"
+  "*"
+  "/"
+  "%"
+  "^"
+  "@"
+] @operator
+
+[
+  ","
+  ":"
+] @punctuation.delimiter
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+(comment) @comment
@@ -0,0 +1,5 @@
+; Regex matchers: job=~"api|web"
+(label_matcher
+  operator: ["=~" "!~"]
+  value: (string) @injection.content
+  (#set! injection.language "regex"))
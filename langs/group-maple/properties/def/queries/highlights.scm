@@ -0,0 +1,34 @@
+(comment) @comment
+
+(key) @property
+
+(value) @string
+
+(value (escape) @string.escape)
+
+((index) @number
+  (#match? @number "^[0-9]+$"))
+
+((substitution (key) @constant)
+  (#match? @constant "^[A-Z0-9_]+"))
+
+(substitution
+  (key) @function
+  "::" @punctuation.special
+  (secret) @embedded)
+
+(property [ "=" ":" ] @operator)
+
+[ "${" "}" ] @punctuation.special
+
+(substitution ":" @punctuation.special)
+
+[ "[" "]" ] @punctuation.bracket
+
+[ "." "\\" ] @punctuation.delimiter
+
+; `db.password`, `auth.token` and the like
+((property
+  (key) @_key
+  (value) @string.special.secret)
+  (#match? @_key "(?i)(^|[_.-])(token|secret|passw(or)?d|pwd|pass|key|apikey|credentials?|auth|dsn)($|[_.-])"))
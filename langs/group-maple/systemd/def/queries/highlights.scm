@@ -0,0 +1,31 @@
+(section_name
+  (text) @type) ; consistency with toml
+
+(comment) @comment @spell
+
+[
+  "["
+  "]"
+] @punctuation.bracket
+
+"=" @operator
+
+(setting
+  (setting_name) @property)
+
+; Booleans, as systemd reads them
+((setting_value) @constant.builtin
+  (#match? @constant.builtin "^(?i)(yes|no|true|false|on|off)\\s*$"))
+
+; Plain numbers and time spans: `5`, `30s`, `1min 30s`, `512M`
+((setting_value) @number
+  (#match? @number "^[0-9]+(\\.[0-9]+)?\\s*([a-zA-Z]+)?(\\s+[0-9]+\\s*[a-zA-Z]+)*\\s*$"))
+
+; Other units: `After=network-online.target`, `Wants=postgresql.service`
+((setting
+  (setting_name) @_name
+  (setting_value) @string.special)
+  (#any-of? @_name
+    "After" "Before" "Requires" "Wants" "BindsTo" "PartOf" "Upholds"
+    "Conflicts" "Requisite" "OnFailure" "OnSuccess" "WantedBy" "RequiredBy"
+    "UpheldBy" "Also" "Unit" "Sockets" "Service" "Slice"))
@@ -0,0 +1,6 @@
+; Commands are split like shell words, close enough to highlight as shell
+((setting
+  (setting_name) @_name
+  (setting_value) @injection.content)
+  (#match? @_name "^Exec[A-Za-z]*$")
+  (#set! injection.language "bash"))
@@ -0,0 +1,33 @@
+; Shell scripts in the `command` and `args` lists of Kubernetes containers
+; and Compose services: `["sh", "-c", "echo hi && sleep 10"]`. Only items
+; with whitespace in them are scripts; `--port` and `/bin/sh` are not.
+
+(block_mapping_pair
+  key: (flow_node) @_key
+  value: [
+    (block_node (block_sequence (block_sequence_item (flow_node (plain_scalar) @injection.content))))
+    (flow_node (flow_sequence (flow_node (plain_scalar) @injection.content)))
+  ]
+  (#any-of? @_key "command" "args")
+  (#match? @injection.content "\\s")
+  (#set! injection.language "bash"))
+
+(block_mapping_pair
+  key: (flow_node) @_key
+  value: [
+    (block_node (block_sequence (block_sequence_item (flow_node [(double_quote_scalar) (single_quote_scalar)] @injection.content))))
+    (flow_node (flow_sequence (flow_node [(double_quote_scalar) (single_quote_scalar)] @injection.content)))
+  ]
+  (#any-of? @_key "command" "args")
+  (#match? @injection.content "\\s")
+  (#offset! @injection.content 0 1 0 -1)
+  (#set! injection.language "bash"))
+
+; `- |` literal block scalars, minus the `|`
+(block_mapping_pair
+  key: (flow_node) @_key
+  value: (block_node (block_sequence (block_sequence_item (block_node (block_scalar) @injection.content))))
+  (#any-of? @_key "command" "args")
+  (#match? @injection.content "^\\|\\r?\\n")
+  (#offset! @injection.content 0 1 0 0)
+  (#set! injection.language "bash"))
@@ -16,6 +16,31 @@
 (fixed_point_number) @number
 (real_number) @number
 
+; Identifiers (listed by context rather than as a catch-all, so that
+; the module, function and task names below keep their own captures)
+(system_tf_identifier) @function.builtin
+
+(primary
+  [(simple_identifier) (escaped_identifier)] @variable)
+(variable_lvalue
+  [(simple_identifier) (escaped_identifier)] @variable)
+(net_lvalue
+  [(simple_identifier) (escaped_identifier)] @variable)
+(variable_decl_assignment
+  [(simple_identifier) (escaped_identifier)] @variable)
+(net_decl_assignment
+  [(simple_identifier) (escaped_identifier)] @variable)
+(list_of_variable_identifiers
+  [(simple_identifier) (escaped_identifier)] @variable)
+(parameter_identifier
+  [(simple_identifier) (escaped_identifier)] @variable)
+(genvar_identifier
+  [(simple_identifier) (escaped_identifier)] @variable)
+(index_variable_identifier
+  [(simple_identifier) (escaped_identifier)] @variable)
+(member_identifier
+  [(simple_identifier) (escaped_identifier)] @property)
+
 ; Module declarations
 (module_declaration
   (module_header
@@ -43,11 +68,6 @@
 (port_identifier
   (simple_identifier) @variable)
 
-; Identifiers
-(simple_identifier) @variable
-(escaped_identifier) @variable
-(system_tf_identifier) @function.builtin
-
 ; Keywords
 [
   "module"
@@ -101,6 +121,43 @@
   "xnor"
 ] @keyword
 
+; SystemVerilog
+[
+  "interface"
+  "endinterface"
+  "modport"
+  "package"
+  "endpackage"
+  "import"
+  "typedef"
+  "enum"
+  "struct"
+  "packed"
+  "class"
+  "endclass"
+  "extends"
+  "virtual"
+  "automatic"
+  "unique"
+  "priority"
+  "foreach"
+  "do"
+  "break"
+  "continue"
+  "return"
+  "signed"
+  "unsigned"
+  "int"
+  "bit"
+  "byte"
+  "shortint"
+  "longint"
+] @keyword
+
+(package_scope
+  (package_identifier
+    (simple_identifier) @module))
+
 ; Operators
 [
   "+"
@@ -0,0 +1,49 @@
+; Verilog/SystemVerilog scopes, declarations and references
+
+[
+  (module_declaration)
+  (interface_declaration)
+  (program_declaration)
+  (package_declaration)
+  (class_declaration)
+  (function_declaration)
+  (task_declaration)
+  (seq_block)
+  (par_block)
+  (generate_block)
+  (loop_generate_construct)
+  (loop_statement)
+] @local.scope
+
+; Ports: ANSI headers, old-style port declarations, task and function ports
+(ansi_port_declaration
+  (port_identifier (simple_identifier) @local.definition))
+(list_of_port_identifiers
+  (port_identifier (simple_identifier) @local.definition))
+(list_of_variable_port_identifiers
+  (port_identifier (simple_identifier) @local.definition))
+(list_of_tf_variable_identifiers
+  (port_identifier (simple_identifier) @local.definition))
+(tf_port_item1
+  (port_identifier (simple_identifier) @local.definition))
+
+; Nets, variables, parameters and genvars
+(net_decl_assignment
+  (simple_identifier) @local.definition)
+(variable_decl_assignment
+  (simple_identifier) @local.definition)
+(list_of_variable_identifiers
+  (simple_identifier) @local.definition)
+(param_assignment
+  (parameter_identifier (simple_identifier) @local.definition))
+(list_of_genvar_identifiers
+  (genvar_identifier (simple_identifier) @local.definition))
+(genvar_initialization
+  "genvar"
+  (genvar_identifier (simple_identifier) @local.definition))
+
+; `for (int i = 0; ...)`
+(for_variable_declaration
+  (simple_identifier) @local.definition)
+
+(simple_identifier) @local.reference
@@ -147,5 +147,74 @@
   "."
 ] @punctuation.delimiter
 
-; Identifiers (fallback)
-(identifier) @variable
+; Design units and types
+(entity_declaration
+  name: (identifier) @type.definition)
+
+(architecture_body
+  name: (identifier) @type.definition
+  entity: (simple_name) @type)
+
+(component_declaration
+  name: (identifier) @type.definition)
+
+(full_type_declaration
+  name: (identifier) @type.definition)
+
+(subtype_declaration
+  name: (identifier) @type.definition)
+
+(type_mark
+  (simple_name) @type)
+
+(enumeration_type_definition
+  literal: (identifier) @constant)
+
+(logical_name_list
+  library: (simple_name) @module)
+
+; Subprograms
+(function_body
+  designator: (identifier) @function)
+
+(function_declaration
+  designator: (identifier) @function)
+
+(procedure_body
+  designator: (identifier) @function)
+
+(procedure_declaration
+  designator: (identifier) @function)
+
+(function_call
+  function: (simple_name) @function.call)
+
+(ambiguous_name
+  prefix: (simple_name) @function.call)
+
+; Statement labels
+(label
+  (identifier) @label)
+
+; Objects (listed by context rather than as a catch-all, so that the
+; design unit and subprogram names above keep their own captures)
+(identifier_list
+  (identifier) @variable)
+
+(sensitivity_list
+  (simple_name) @variable)
+
+(simple_waveform_assignment
+  target: (simple_name) @variable)
+
+(simple_variable_assignment
+  target: (simple_name) @variable)
+
+(simple_concurrent_signal_assignment
+  target: (simple_name) @variable)
+
+(expression
+  (simple_name) @variable)
+
+(simple_expression
+  (simple_name) @variable)
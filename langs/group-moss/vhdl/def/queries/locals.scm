@@ -0,0 +1,49 @@
+; VHDL scopes, declarations and references.
+; Declarations are `identifier` nodes; uses of a name are `simple_name`.
+
+[
+  (entity_declaration)
+  (architecture_body)
+  (package_declaration)
+  (package_body)
+  (function_body)
+  (procedure_body)
+  (process_statement)
+  (block_statement)
+  (for_generate_statement)
+  (loop_statement)
+] @local.scope
+
+(constant_declaration
+  (identifier_list (identifier) @local.definition))
+(signal_declaration
+  (identifier_list (identifier) @local.definition))
+(variable_declaration
+  (identifier_list (identifier) @local.definition))
+(shared_variable_declaration
+  (identifier_list (identifier) @local.definition))
+(file_declaration
+  (identifier_list (identifier) @local.definition))
+
+; Generics, ports and subprogram parameters
+(constant_interface_declaration
+  (identifier_list (identifier) @local.definition))
+(signal_interface_declaration
+  (identifier_list (identifier) @local.definition))
+(variable_interface_declaration
+  (identifier_list (identifier) @local.definition))
+
+(full_type_declaration
+  name: (identifier) @local.definition)
+(subtype_declaration
+  name: (identifier) @local.definition)
+(enumeration_type_definition
+  literal: (identifier) @local.definition)
+(alias_declaration
+  designator: (identifier) @local.definition)
+
+; `for i in 0 to 7 loop`, `gen: for i in ... generate`
+(parameter_specification
+  name: (identifier) @local.definition)
+
+(simple_name) @local.reference
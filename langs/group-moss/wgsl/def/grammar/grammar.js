@@ -0,0 +1,155 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// WebGPU Shading Language
+//
+// Declarations (functions, structs, module-scope variables, aliases) are
+// parsed properly so names, parameters, fields and types can be told apart.
+// Function bodies and initializers are a flat run of tokens and bracketed
+// groups: `<` is both a comparison and the start of a template list, and
+// highlighting doesn't need to know which.
+
+module.exports = grammar({
+  name: "wgsl",
+
+  extras: $ => [/\s/, $.line_comment, $.block_comment],
+
+  word: $ => $.identifier,
+
+  rules: {
+    source_file: $ => repeat($._item),
+
+    _item: $ => choice(
+      $.function_definition,
+      $.struct_definition,
+      $.variable_declaration,
+      $.alias_declaration,
+      $.attribute,
+      $.call,
+      $.member_access,
+      $.braced,
+      $.parenthesized,
+      $.bracketed,
+      $._atom,
+    ),
+
+    // `@vertex`, `@location(0)`, `@workgroup_size(8, 8, 1)`
+    attribute: $ => prec.right(seq(
+      "@",
+      field("name", $.identifier),
+      optional(field("arguments", $.parenthesized)),
+    )),
+
+    // The body follows as a `braced` item
+    function_definition: $ => prec.right(seq(
+      "fn",
+      field("name", $.identifier),
+      field("parameters", $.parameter_list),
+      optional(seq("->", repeat($.attribute), field("return_type", $.type))),
+    )),
+
+    parameter_list: $ => seq("(", sepBy(",", $.parameter), optional(","), ")"),
+
+    parameter: $ => seq(
+      repeat($.attribute),
+      field("name", $.identifier),
+      ":",
+      field("type", $.type),
+    ),
+
+    struct_definition: $ => seq(
+      "struct",
+      field("name", $.identifier),
+      field("body", $.struct_body),
+    ),
+
+    struct_body: $ => seq("{", sepBy(",", $.struct_member), optional(","), "}"),
+
+    struct_member: $ => seq(
+      repeat($.attribute),
+      field("name", $.identifier),
+      ":",
+      field("type", $.type),
+    ),
+
+    // `var<storage, read_write> data: array<f32>`; the initializer follows
+    variable_declaration: $ => prec.right(seq(
+      choice("var", "let", "const", "override"),
+      optional($.template_list),
+      field("name", $.identifier),
+      optional(seq(":", field("type", $.type))),
+    )),
+
+    template_list: $ => seq("<", sepBy1(",", $.identifier), ">"),
+
+    alias_declaration: $ => seq(
+      "alias",
+      field("name", $.identifier),
+      "=",
+      field("type", $.type),
+    ),
+
+    // `f32`, `vec4<f32>`, `array<Light, 4>`, `texture_2d<f32>`
+    type: $ => prec.right(seq(
+      $.identifier,
+      optional(seq("<", sepBy1(",", choice($.type, $.number)), optional(","), ">")),
+    )),
+
+    call: $ => prec(1, seq(
+      field("function", $.identifier),
+      field("arguments", $.parenthesized),
+    )),
+
+    // `.xyz`, `.position`
+    member_access: $ => seq(".", field("member", $.identifier)),
+
+    braced: $ => seq("{", repeat($._item), "}"),
+
+    parenthesized: $ => seq("(", repeat($._item), ")"),
+
+    bracketed: $ => seq("[", repeat($._item), "]"),
+
+    _atom: $ => choice(
+      $.identifier,
+      $.number,
+      $.boolean,
+      "return", "if", "else", "for", "loop", "while", "break", "continue",
+      "continuing", "switch", "case", "default", "discard", "enable",
+      "requires", "diagnostic", "const_assert",
+      "=", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", ">>=", "<<=",
+      "==", "!=", "<", ">", "<=", ">=", "&&", "||", "!", "&", "|", "^",
+      "~", "+", "-", "*", "/", "%", "<<", ">>", "++", "--", "->",
+      ",", ";", ":",
+    ),
+
+    boolean: _ => choice("true", "false"),
+
+    identifier: _ => /[A-Za-z_][A-Za-z0-9_]*/,
+
+    number: _ => token(choice(
+      /0[xX][0-9a-fA-F]+[iuh]?/,
+      /[0-9]+(\.[0-9]*)?([eE][+-]?[0-9]+)?[iufh]?/,
+      /\.[0-9]+([eE][+-]?[0-9]+)?[fh]?/,
+    )),
+
+    line_comment: _ => token(seq("//", /[^\n]*/)),
+
+    block_comment: _ => token(seq("/*", /[^*]*\*+([^/*][^*]*\*+)*/, "/")),
+  },
+});
+
+/**
+ * @param {RuleOrLiteral} separator
+ * @param {RuleOrLiteral} rule
+ */
+function sepBy1(separator, rule) {
+  return seq(rule, repeat(seq(separator, rule)));
+}
+
+/**
+ * @param {RuleOrLiteral} separator
+ * @param {RuleOrLiteral} rule
+ */
+function sepBy(separator, rule) {
+  return optional(sepBy1(separator, rule));
+}
@@ -0,0 +1,174 @@
+; An (identifier) is a variable until a declaration, type or call pattern
+; further down gives it a role
+
+[
+  (line_comment)
+  (block_comment)
+] @comment
+
+(identifier) @variable
+
+(number) @number
+
+(boolean) @boolean
+
+; Declarations
+(function_definition
+  name: (identifier) @function)
+
+(parameter
+  name: (identifier) @variable.parameter)
+
+(struct_definition
+  name: (identifier) @type)
+
+(struct_member
+  name: (identifier) @variable.member)
+
+(variable_declaration
+  name: (identifier) @variable)
+
+(alias_declaration
+  name: (identifier) @type)
+
+(template_list
+  (identifier) @keyword.modifier)
+
+(type
+  (identifier) @type)
+
+(member_access
+  member: (identifier) @variable.member)
+
+(attribute
+  "@" @attribute
+  name: (identifier) @attribute)
+
+(attribute
+  name: (identifier) @_name
+  arguments: (parenthesized
+    (identifier) @variable.builtin)
+  (#eq? @_name "builtin"))
+
+(call
+  function: (identifier) @function.call)
+
+; `vec4<f32>(...)`, `array(...)` and other constructors used as expressions
+((identifier) @type.builtin
+  (#match? @type.builtin "^(bool|[fiu]32|f16|vec[234][fhiu]?|mat[234]x[234][fh]?|array|atomic|ptr|sampler|sampler_comparison|texture_(1d|2d|2d_array|3d|cube|cube_array|multisampled_2d|depth_2d|depth_2d_array|depth_cube|depth_cube_array|depth_multisampled_2d|storage_1d|storage_2d|storage_2d_array|storage_3d|external))$"))
+
+((identifier) @type.builtin
+  (#match? @type.builtin "^(rgba8unorm|rgba8snorm|rgba8uint|rgba8sint|rgba16uint|rgba16sint|rgba16float|r32uint|r32sint|r32float|rg32uint|rg32sint|rg32float|rgba32uint|rgba32sint|rgba32float|bgra8unorm)$"))
+
+(call
+  function: (identifier) @function.builtin
+  (#any-of? @function.builtin
+    "abs" "acos" "acosh" "all" "any" "arrayLength" "asin" "asinh" "atan" "atan2"
+    "atanh" "atomicAdd" "atomicAnd" "atomicCompareExchangeWeak" "atomicExchange"
+    "atomicLoad" "atomicMax" "atomicMin" "atomicOr" "atomicStore" "atomicSub"
+    "atomicXor" "bitcast" "ceil" "clamp" "cos" "cosh" "countLeadingZeros"
+    "countOneBits" "countTrailingZeros" "cross" "degrees" "determinant" "distance"
+    "dot" "dpdx" "dpdy" "exp" "exp2" "extractBits" "faceForward" "firstLeadingBit"
+    "firstTrailingBit" "floor" "fma" "fract" "frexp" "fwidth" "insertBits"
+    "inverseSqrt" "ldexp" "length" "log" "log2" "max" "min" "mix" "modf"
+    "normalize" "pack2x16float" "pack4x8snorm" "pack4x8unorm" "pow" "radians"
+    "reflect" "refract" "reverseBits" "round" "saturate" "select" "sign" "sin"
+    "sinh" "smoothstep" "sqrt" "step" "storageBarrier" "tan" "tanh" "textureDimensions"
+    "textureGather" "textureGatherCompare" "textureLoad" "textureNumLayers"
+    "textureNumLevels" "textureNumSamples" "textureSample" "textureSampleBias"
+    "textureSampleCompare" "textureSampleCompareLevel" "textureSampleGrad"
+    "textureSampleLevel" "textureStore" "transpose" "trunc" "unpack2x16float"
+    "unpack4x8snorm" "unpack4x8unorm" "workgroupBarrier" "workgroupUniformLoad"))
+
+[
+  "struct"
+  "alias"
+] @keyword.type
+
+"fn" @keyword.function
+
+[
+  "var"
+  "let"
+  "const"
+  "override"
+  "enable"
+  "requires"
+  "diagnostic"
+  "const_assert"
+] @keyword
+
+[
+  "if"
+  "else"
+  "switch"
+  "case"
+  "default"
+] @keyword.conditional
+
+[
+  "for"
+  "loop"
+  "while"
+  "break"
+  "continue"
+  "continuing"
+] @keyword.repeat
+
+[
+  "return"
+  "discard"
+] @keyword.return
+
+[
+  "="
+  "+="
+  "-="
+  "*="
+  "/="
+  "%="
+  "&="
+  "|="
+  "^="
+  ">>="
+  "<<="
+  "=="
+  "!="
+  "<"
+  ">"
+  "<="
+  ">="
+  "&&"
+  "||"
+  "!"
+  "&"
+  "|"
+  "^"
+  "~"
+  "+"
+  "-"
+  "*"
+  "/"
+  "%"
+  "<<"
+  ">>"
+  "++"
+  "--"
+  "->"
+] @operator
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+[
+  ","
+  ";"
+  ":"
+  "."
+] @punctuation.delimiter
@@ -0,0 +1,124 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// Cairo 1, the language of Starknet contracts
+//
+// Like the Move grammar, this reads statements and expressions as a flat run
+// of tokens inside nested blocks and parentheses, and recognizes the shapes
+// that matter for highlighting: item headers, `let` bindings, `name:`
+// annotations (parameters and fields), calls and `module::` path segments.
+
+const KEYWORDS = [
+  "use", "pub", "as", "const", "type", "extern", "of", "ref", "mut",
+  "nopanic", "implicits", "self", "super", "crate", "if", "else", "match",
+  "loop", "while", "for", "in", "break", "continue", "return", "true",
+  "false",
+];
+
+module.exports = grammar({
+  name: "cairo",
+
+  extras: $ => [/\s/, $.line_comment],
+
+  word: $ => $.identifier,
+
+  rules: {
+    source_file: $ => repeat($._item),
+
+    _item: $ => choice(
+      $.module_definition,
+      $.function_definition,
+      $.struct_definition,
+      $.trait_definition,
+      $.impl_definition,
+      $.let_binding,
+      $.annotated_name,
+      $.call,
+      $.scope,
+      $.block,
+      $.parenthesized,
+      $._term,
+    ),
+
+    // Structure
+    // =========
+
+    // Contract modules are often capitalized: `mod ERC20`
+    module_definition: $ => seq("mod", field("name", choice($.identifier, $.type_identifier))),
+
+    function_definition: $ => seq("fn", field("name", $.identifier)),
+
+    struct_definition: $ => seq(
+      choice("struct", "enum"),
+      field("name", $.type_identifier),
+    ),
+
+    trait_definition: $ => seq("trait", field("name", $.type_identifier)),
+
+    // `impl Erc20Impl of IErc20<ContractState>`, and `impl TDrop: Drop<T>`
+    // in generic parameters
+    impl_definition: $ => seq("impl", field("name", $.type_identifier)),
+
+    let_binding: $ => seq(
+      "let",
+      optional("mut"),
+      field("name", $.identifier),
+    ),
+
+    // Parameters, struct fields and field initializers: `amount: u256`
+    annotated_name: $ => prec(1, seq(field("name", choice($.identifier, "self")), ":")),
+
+    call: $ => prec(1, seq(
+      field("function", $.identifier),
+      field("arguments", $.parenthesized),
+    )),
+
+    // `starknet::` in `starknet::get_caller_address()`
+    scope: $ => prec(1, seq(field("module", $.identifier), "::")),
+
+    block: $ => seq("{", repeat($._item), "}"),
+
+    parenthesized: $ => seq("(", repeat($._item), ")"),
+
+    _term: $ => choice(
+      $.identifier,
+      $.type_identifier,
+      $.macro,
+      $.number,
+      $.string,
+      $.short_string,
+      $.attribute,
+      $.operator,
+      ...KEYWORDS,
+      "[", "]", ",", ";", ":", "::",
+    ),
+
+    // Tokens
+    // ======
+
+    identifier: _ => /[a-z_][A-Za-z0-9_]*/,
+
+    // Types, traits, enum variants, and constants by convention
+    type_identifier: _ => /[A-Z][A-Za-z0-9_]*/,
+
+    // `assert!`, `array!`, `println!`
+    macro: _ => /[a-z_][A-Za-z0-9_]*!/,
+
+    // `42`, `0x1f_u8`, `1000_u256`
+    number: _ => /(0x[0-9a-fA-F_]+|0o[0-7_]+|0b[01_]+|[0-9][0-9_]*)(_?([ui](8|16|32|64|128|256)|usize|felt252))?/,
+
+    // `ByteArray` literals
+    string: _ => /"([^"\\]|\\.)*"/,
+
+    // Short strings are `felt252` constants: `'ERC20'`
+    short_string: _ => /'([^'\\\n]|\\.)*'/,
+
+    // `#[starknet::contract]`, `#[derive(Drop, Serde)]`, `#[abi(embed_v0)]`
+    attribute: _ => /#\[[^\]]*\]/,
+
+    // `@` takes a snapshot and `*` desnaps, so both are plain operators here
+    operator: _ => /[+\-*\/%&|^!<>=.@?]+/,
+
+    line_comment: _ => token(seq("//", /[^\n]*/)),
+  },
+});
@@ -0,0 +1,127 @@
+; Identifiers
+(identifier) @variable
+
+(type_identifier) @type
+
+((type_identifier) @constant
+  (#match? @constant "^[A-Z][A-Z0-9_]+$"))
+
+((identifier) @type.builtin
+  (#match? @type.builtin "^(felt252|bool|usize|[ui](8|16|32|64|128|256)|bytes31)$"))
+
+(scope
+  module: (identifier) @module)
+
+(module_definition
+  name: _ @module)
+
+(function_definition
+  name: (identifier) @function)
+
+(call
+  function: (identifier) @function.call)
+
+(macro) @function.macro
+
+(annotated_name
+  name: (identifier) @variable.member)
+
+(parenthesized
+  (annotated_name
+    name: (identifier) @variable.parameter))
+
+(struct_definition
+  name: (type_identifier) @type.definition)
+
+(trait_definition
+  name: (type_identifier) @type.definition)
+
+(impl_definition
+  name: (type_identifier) @type.definition)
+
+"self" @variable.builtin
+
+(attribute) @attribute
+
+; Literals
+(number) @number
+
+(string) @string
+
+(short_string) @string.special
+
+[
+  "true"
+  "false"
+] @boolean
+
+; Comments
+(line_comment) @comment
+
+((line_comment) @comment.documentation
+  (#match? @comment.documentation "^//[/!]"))
+
+; Keywords
+[
+  "mod"
+  "struct"
+  "enum"
+  "trait"
+  "impl"
+  "of"
+  "type"
+  "const"
+  "let"
+  "mut"
+  "ref"
+  "as"
+  "extern"
+  "implicits"
+  "super"
+  "crate"
+] @keyword
+
+"fn" @keyword.function
+
+[
+  "pub"
+  "nopanic"
+] @keyword.modifier
+
+"use" @keyword.import
+
+[
+  "if"
+  "else"
+  "match"
+] @keyword.conditional
+
+[
+  "loop"
+  "while"
+  "for"
+  "in"
+  "break"
+  "continue"
+] @keyword.repeat
+
+"return" @keyword.return
+
+; Operators and punctuation
+(operator) @operator
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+[
+  ","
+  ";"
+  ":"
+  "::"
+] @punctuation.delimiter
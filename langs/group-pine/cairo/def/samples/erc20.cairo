@@ -0,0 +1,80 @@
+// A minimal ERC-20 token, Starknet style
+use starknet::ContractAddress;
+
+#[starknet::interface]
+pub trait IERC20<TContractState> {
+    fn name(self: @TContractState) -> ByteArray;
+    fn balance_of(self: @TContractState, account: ContractAddress) -> u256;
+    fn transfer(ref self: TContractState, recipient: ContractAddress, amount: u256) -> bool;
+}
+
+#[starknet::contract]
+pub mod ERC20 {
+    use starknet::storage::{Map, StorageMapReadAccess, StorageMapWriteAccess};
+    use starknet::{ContractAddress, get_caller_address};
+    use core::num::traits::Zero;
+
+    const DECIMALS: u8 = 18;
+    const SYMBOL: felt252 = 'TKN';
+
+    #[storage]
+    struct Storage {
+        name: ByteArray,
+        total_supply: u256,
+        balances: Map<ContractAddress, u256>,
+    }
+
+    #[event]
+    #[derive(Drop, starknet::Event)]
+    pub enum Event {
+        Transfer: Transfer,
+    }
+
+    #[derive(Drop, starknet::Event)]
+    pub struct Transfer {
+        #[key]
+        pub from: ContractAddress,
+        #[key]
+        pub to: ContractAddress,
+        pub value: u256,
+    }
+
+    #[constructor]
+    fn constructor(ref self: ContractState, recipient: ContractAddress) {
+        let supply: u256 = 1_000_000 * 1_000_000_000_000_000_000_u256;
+        self.name.write("Token");
+        self.total_supply.write(supply);
+        self.balances.write(recipient, supply);
+    }
+
+    #[abi(embed_v0)]
+    impl ERC20Impl of super::IERC20<ContractState> {
+        fn name(self: @ContractState) -> ByteArray {
+            self.name.read()
+        }
+
+        fn balance_of(self: @ContractState, account: ContractAddress) -> u256 {
+            self.balances.read(account)
+        }
+
+        fn transfer(ref self: ContractState, recipient: ContractAddress, amount: u256) -> bool {
+            let sender = get_caller_address();
+            assert!(!recipient.is_zero(), "transfer to zero address");
+            let balance = self.balances.read(sender);
+            assert(balance >= amount, 'insufficient balance');
+            self.balances.write(sender, balance - amount);
+            self.balances.write(recipient, self.balances.read(recipient) + amount);
+            self.emit(Transfer { from: sender, to: recipient, value: amount });
+            true
+        }
+    }
+
+    /// Sums a span of snapshots without copying the array
+    fn sum(values: Span<u256>) -> u256 {
+        let mut total = 0;
+        for value in values {
+            total += *value;
+        };
+        total
+    }
+}
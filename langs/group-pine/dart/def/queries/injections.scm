@@ -0,0 +1,17 @@
+([
+  (comment)
+  (documentation_comment)
+] @injection.content
+  (#set! injection.language "comment"))
+
+; RegExp(r'^\d+$')
+((identifier) @_class
+  (#eq? @_class "RegExp")
+  .
+  (selector
+    (argument_part
+      (arguments
+        .
+        (argument
+          (string_literal) @injection.content))))
+  (#set! injection.language "regex"))
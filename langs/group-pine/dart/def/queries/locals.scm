@@ -0,0 +1,38 @@
+; Scopes
+
+[
+  (program)
+  (class_definition)
+  (mixin_declaration)
+  (extension_declaration)
+  (function_body)
+  (function_expression)
+  (lambda_expression)
+  (block)
+  (for_statement)
+  (catch_clause)
+] @local.scope
+
+; Definitions
+
+(function_signature
+  name: (identifier) @local.definition)
+
+(formal_parameter
+  (identifier) @local.definition)
+
+(initialized_variable_definition
+  name: (identifier) @local.definition)
+
+(initialized_identifier
+  . (identifier) @local.definition)
+
+(for_loop_parts
+  name: (identifier) @local.definition)
+
+(catch_parameters
+  (identifier) @local.definition)
+
+; References
+
+(identifier) @local.reference
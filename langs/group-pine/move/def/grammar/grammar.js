@@ -0,0 +1,126 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// Move, as used by Sui and Aptos
+//
+// Blocks and parentheses are nested, but statements and expressions are
+// read as a flat run of tokens. On top of that, the grammar recognizes the
+// shapes that matter for highlighting: module, function and struct headers,
+// `let` bindings, `name:` annotations (parameters and fields), calls and
+// `module::` path segments.
+
+const KEYWORDS = [
+  "script", "address", "use", "as", "public", "entry", "native", "friend",
+  "inline", "package", "acquires", "has", "mut", "move", "copy", "return",
+  "abort", "if", "else", "while", "loop", "break", "continue", "const",
+  "spec", "schema", "invariant", "ensures", "requires", "aborts_if",
+  "pragma", "phantom", "match", "macro", "true", "false",
+];
+
+module.exports = grammar({
+  name: "move",
+
+  extras: $ => [/\s/, $.line_comment, $.block_comment],
+
+  word: $ => $.identifier,
+
+  rules: {
+    source_file: $ => repeat($._item),
+
+    _item: $ => choice(
+      $.module_definition,
+      $.function_definition,
+      $.struct_definition,
+      $.let_binding,
+      $.annotated_name,
+      $.call,
+      $.scope,
+      $.block,
+      $.parenthesized,
+      $._term,
+    ),
+
+    // Structure
+    // =========
+
+    // `module 0x1::coin`, `module std::vector`, `module coin`
+    module_definition: $ => seq("module", field("name", $.module_name)),
+
+    module_name: _ => /((0x[0-9a-fA-F]+|[a-z_][A-Za-z0-9_]*)::)?[a-z_][A-Za-z0-9_]*/,
+
+    function_definition: $ => seq("fun", field("name", $.identifier)),
+
+    struct_definition: $ => seq(
+      choice("struct", "enum"),
+      field("name", $.type_identifier),
+    ),
+
+    let_binding: $ => seq(
+      "let",
+      optional("mut"),
+      field("name", $.identifier),
+    ),
+
+    // Parameters, struct fields and field initializers: `amount: u64`
+    annotated_name: $ => prec(1, seq(field("name", $.identifier), ":")),
+
+    call: $ => prec(1, seq(
+      field("function", $.identifier),
+      field("arguments", $.parenthesized),
+    )),
+
+    // `coin::` in `coin::value(c)`
+    scope: $ => prec(1, seq(field("module", $.identifier), "::")),
+
+    block: $ => seq("{", repeat($._item), "}"),
+
+    parenthesized: $ => seq("(", repeat($._item), ")"),
+
+    _term: $ => choice(
+      $.identifier,
+      $.type_identifier,
+      $.macro,
+      $.label,
+      $.address_literal,
+      $.number,
+      $.byte_string,
+      $.hex_string,
+      $.attribute,
+      $.operator,
+      ...KEYWORDS,
+      "[", "]", ",", ";", ":", "::",
+    ),
+
+    // Tokens
+    // ======
+
+    identifier: _ => /[a-z_][A-Za-z0-9_]*/,
+
+    // Types, and constants by convention
+    type_identifier: _ => /[A-Z][A-Za-z0-9_]*/,
+
+    // `assert!`, `vector!`
+    macro: _ => /[a-z_][A-Za-z0-9_]*!/,
+
+    // `'outer` in `'outer: loop { ... }`
+    label: _ => /'[a-z_][A-Za-z0-9_]*/,
+
+    // `@0x1`, `@std`
+    address_literal: _ => /@(0x[0-9a-fA-F]+|[a-z_][A-Za-z0-9_]*)/,
+
+    number: _ => /(0x[0-9a-fA-F_]+|[0-9][0-9_]*)(u8|u16|u32|u64|u128|u256)?/,
+
+    byte_string: _ => /b"([^"\\]|\\.)*"/,
+
+    hex_string: _ => /x"[0-9a-fA-F]*"/,
+
+    // `#[test]`, `#[expected_failure(abort_code = 1)]`
+    attribute: _ => /#\[[^\]]*\]/,
+
+    operator: _ => /[+\-*\/%&|^!<>=.]+/,
+
+    line_comment: _ => token(seq("//", /[^\n]*/)),
+
+    block_comment: _ => token(seq("/*", /[^*]*\*+([^/*][^*]*\*+)*/, "/")),
+  },
+});
@@ -0,0 +1,147 @@
+; Identifiers (listed by context rather than as a catch-all, so that
+; function, module and call names below keep their own captures)
+(block
+  (identifier) @variable)
+
+(parenthesized
+  (identifier) @variable)
+
+(let_binding
+  name: (identifier) @variable)
+
+(type_identifier) @type
+
+((type_identifier) @constant
+  (#match? @constant "^[A-Z][A-Z0-9_]+$"))
+
+((identifier) @type.builtin
+  (#any-of? @type.builtin
+    "u8" "u16" "u32" "u64" "u128" "u256" "bool" "signer" "vector"))
+
+((identifier) @type.builtin
+  (#any-of? @type.builtin "drop" "store" "key"))
+
+(module_name) @module
+
+(scope
+  module: (identifier) @module)
+
+(function_definition
+  name: (identifier) @function)
+
+(call
+  function: (identifier) @function.call)
+
+(macro) @function.macro
+
+(annotated_name
+  name: (identifier) @variable.member)
+
+(parenthesized
+  (annotated_name
+    name: (identifier) @variable.parameter))
+
+(struct_definition
+  name: (type_identifier) @type.definition)
+
+(label) @label
+
+(attribute) @attribute
+
+; Literals
+(number) @number
+
+(address_literal) @constant.builtin
+
+(byte_string) @string
+
+(hex_string) @string.special
+
+[
+  "true"
+  "false"
+] @boolean
+
+; Comments
+(line_comment) @comment
+
+(block_comment) @comment
+
+((line_comment) @comment.documentation
+  (#match? @comment.documentation "^///"))
+
+; Keywords
+[
+  "module"
+  "script"
+  "address"
+  "struct"
+  "enum"
+  "has"
+  "const"
+  "let"
+  "mut"
+  "move"
+  "copy"
+  "as"
+  "phantom"
+  "spec"
+  "schema"
+  "invariant"
+  "ensures"
+  "requires"
+  "aborts_if"
+  "pragma"
+  "macro"
+] @keyword
+
+"fun" @keyword.function
+
+[
+  "public"
+  "entry"
+  "native"
+  "friend"
+  "inline"
+  "package"
+  "acquires"
+] @keyword.modifier
+
+"use" @keyword.import
+
+[
+  "if"
+  "else"
+  "match"
+] @keyword.conditional
+
+[
+  "while"
+  "loop"
+  "break"
+  "continue"
+] @keyword.repeat
+
+[
+  "return"
+  "abort"
+] @keyword.return
+
+; Operators and punctuation
+(operator) @operator
+
+[
+  "("
+  ")"
+  "["
+  "]"
+  "{"
+  "}"
+] @punctuation.bracket
+
+[
+  ","
+  ";"
+  ":"
+  "::"
+] @punctuation.delimiter
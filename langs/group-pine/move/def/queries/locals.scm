@@ -0,0 +1,13 @@
+(block) @local.scope
+
+(function_definition
+  name: (identifier) @local.definition.function)
+
+(let_binding
+  name: (identifier) @local.definition.var)
+
+(parenthesized
+  (annotated_name
+    name: (identifier) @local.definition.parameter))
+
+(identifier) @local.reference
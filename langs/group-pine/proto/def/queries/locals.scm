@@ -0,0 +1,19 @@
+; Scopes
+
+[
+  (source_file)
+  (message_body)
+  (enum_body)
+  (service)
+] @local.scope
+
+; Definitions
+
+(message_name (identifier) @local.definition)
+(enum_name (identifier) @local.definition)
+(field (identifier) @local.definition)
+(enum_field (identifier) @local.definition)
+
+; References
+
+(message_or_enum_type (identifier) @local.reference)
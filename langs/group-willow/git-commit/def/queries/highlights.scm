@@ -0,0 +1,48 @@
+(comment) @comment
+
+(generated_comment) @comment
+
+(title) @markup.heading
+
+(branch) @markup.link
+
+(change) @keyword
+
+(filepath) @string.special.path
+
+(arrow) @punctuation.delimiter
+
+(subject) @markup.heading
+
+; Past the 50-character subject guideline
+(subject
+  (overflow) @comment.warning)
+
+(prefix
+  (type) @keyword)
+
+(prefix
+  (scope) @variable.parameter)
+
+(prefix
+  [
+    "("
+    ")"
+    ":"
+  ] @punctuation.delimiter)
+
+(prefix
+  "!" @punctuation.special)
+
+(message) @spell
+
+(trailer
+  (token) @property)
+
+(trailer
+  (value) @string)
+
+(breaking_change
+  (token) @comment.warning)
+
+(scissor) @punctuation.special
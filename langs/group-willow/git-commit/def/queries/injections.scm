@@ -0,0 +1,5 @@
+((diff) @injection.content
+  (#set! injection.language "diff"))
+
+((rebase_command) @injection.content
+  (#set! injection.language "git-rebase"))
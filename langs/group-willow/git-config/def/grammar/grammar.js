@@ -0,0 +1,69 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// git config files: ~/.gitconfig, .git/config, .gitmodules
+//
+// `[section]` or `[section "subsection"]` headers followed by `name = value`
+// lines. Comments start with `#` or `;` anywhere outside a quoted string,
+// and a trailing backslash continues a value on the next line.
+
+module.exports = grammar({
+  name: "git_config",
+
+  extras: $ => [/[ \t\r]/, $.comment],
+
+  rules: {
+    config: $ => repeat(choice($.section, $._nl)),
+
+    // A variable runs to the end of its line, so `editor = vim` can't be
+    // read as two variables
+    section: $ => prec.right(seq(
+      $.section_header,
+      repeat(choice(seq($.variable, $._nl), $._nl)),
+      optional($.variable),
+    )),
+
+    section_header: $ => seq(
+      "[",
+      field("name", $.section_name),
+      optional(field("subsection", $.subsection_name)),
+      "]",
+    ),
+
+    // `core`, and `branch.main` in the deprecated `[branch.main]` form
+    section_name: _ => /[A-Za-z0-9.\-]+/,
+
+    subsection_name: _ => /"([^"\\\n]|\\.)*"/,
+
+    // A name on its own is a boolean set to true
+    variable: $ => seq(
+      field("name", $.name),
+      optional(seq("=", optional(field("value", $.value)))),
+    ),
+
+    name: _ => /[A-Za-z][A-Za-z0-9\-]*/,
+
+    value: $ => repeat1(choice(
+      $.string,
+      $.value_text,
+      $.escape_sequence,
+      $.line_continuation,
+    )),
+
+    value_text: _ => /[^\s"\\#;]+/,
+
+    string: $ => seq(
+      '"',
+      repeat(choice(alias(token.immediate(prec(1, /[^"\\\n]+/)), $.string_content), $.escape_sequence)),
+      '"',
+    ),
+
+    escape_sequence: _ => token.immediate(/\\[\\"ntb]/),
+
+    line_continuation: _ => /\\\r?\n/,
+
+    comment: _ => token(seq(/[#;]/, /[^\n]*/)),
+
+    _nl: _ => "\n",
+  },
+});
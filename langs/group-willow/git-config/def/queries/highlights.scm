@@ -0,0 +1,36 @@
+(comment) @comment @spell
+
+(section_name) @type
+
+(subsection_name) @string.special
+
+[
+  "["
+  "]"
+] @punctuation.bracket
+
+"=" @operator
+
+(name) @property
+
+(string) @string
+
+(escape_sequence) @string.escape
+
+(line_continuation) @punctuation.special
+
+((value
+  .
+  (value_text) @constant.builtin .)
+  (#match? @constant.builtin "^(?i)(true|false|yes|no|on|off)$"))
+
+((value
+  .
+  (value_text) @number .)
+  (#match? @number "^-?[0-9]+[kKmMgG]?$"))
+
+; Paths and URLs: `path = ~/.gitconfig.work`, `url = git@github.com:o/r.git`
+((variable
+  name: (name) @_name
+  value: (value) @string.special.path)
+  (#any-of? @_name "path" "url" "pushurl" "excludesFile" "hooksPath" "attributesFile" "template"))
@@ -0,0 +1,24 @@
+; `[alias]` entries starting with `!` are run by the shell
+((section
+  (section_header
+    name: (section_name) @_section)
+  (variable
+    value: (value) @injection.content))
+  (#eq? @_section "alias")
+  (#match? @injection.content "^!")
+  (#offset! @injection.content 0 1 0 0)
+  (#set! injection.language "bash")
+  (#set! injection.include-children))
+
+((section
+  (section_header
+    name: (section_name) @_section)
+  (variable
+    value: (value
+      .
+      (string) @injection.content .)))
+  (#eq? @_section "alias")
+  (#match? @injection.content "^\"!")
+  (#offset! @injection.content 0 2 0 -1)
+  (#set! injection.language "bash")
+  (#set! injection.include-children))
@@ -0,0 +1,69 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// git rebase --interactive todo lists (git-rebase-todo)
+//
+// One command per line, by full name or one-letter abbreviation. Most take
+// a commit and its subject; `exec` takes a shell command, and the commands
+// used by --rebase-merges take labels.
+
+const COMMIT_COMMANDS = [
+  "pick", "p", "reword", "r", "edit", "e", "squash", "s", "fixup", "f",
+  "drop", "d",
+];
+
+module.exports = grammar({
+  name: "git_rebase",
+
+  extras: _ => [/[ \t\r]/],
+
+  rules: {
+    document: $ => seq(
+      repeat(seq(optional($._line), $._nl)),
+      optional($._line),
+    ),
+
+    _line: $ => choice($.operation, $.comment),
+
+    operation: $ => choice(
+      seq(
+        field("command", alias(choice(...COMMIT_COMMANDS), $.command)),
+        optional(field("option", $.option)),
+        field("commit", $.commit),
+        optional(field("message", $.message)),
+      ),
+      seq(
+        field("command", alias(choice("exec", "x"), $.command)),
+        field("shell", $.shell_command),
+      ),
+      field("command", alias(choice("break", "b", "noop"), $.command)),
+      seq(
+        field("command", alias(choice("label", "l", "reset", "t", "update-ref", "u"), $.command)),
+        field("label", $.label),
+        optional(field("message", $.message)),
+      ),
+      // `merge -C abc123 feature # Merge branch 'feature'`
+      seq(
+        field("command", alias(choice("merge", "m"), $.command)),
+        optional(seq(field("option", $.option), field("commit", $.commit))),
+        field("label", $.label),
+        optional(seq("#", field("message", $.message))),
+      ),
+    ),
+
+    // `-C` keeps the original message, `-c` opens the editor on it
+    option: _ => /-[cC]/,
+
+    commit: _ => /[0-9a-fA-F]+/,
+
+    label: _ => /[^\s#]+/,
+
+    message: _ => /[^\n]+/,
+
+    shell_command: _ => /[^\n]+/,
+
+    comment: _ => /#[^\n]*/,
+
+    _nl: _ => "\n",
+  },
+});
@@ -0,0 +1,13 @@
+(comment) @comment
+
+(command) @keyword
+
+(option) @variable.parameter
+
+(commit) @constant
+
+(label) @label
+
+(message) @string
+
+"#" @punctuation.delimiter
@@ -0,0 +1,2 @@
+((shell_command) @injection.content
+  (#set! injection.language "bash"))
@@ -0,0 +1,43 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// .gitignore, and the files that copy its syntax (.dockerignore, .npmignore)
+//
+// One pattern per line. A leading `!` re-includes what an earlier pattern
+// excluded, `#` at the start of a line is a comment, and a backslash
+// escapes either of them.
+
+module.exports = grammar({
+  name: "gitignore",
+
+  extras: _ => [/[ \t\r]/],
+
+  rules: {
+    document: $ => repeat(choice($.pattern, $.comment, $._nl)),
+
+    pattern: $ => prec.right(seq(
+      optional(field("negation", alias(token(prec(1, "!")), "!"))),
+      repeat1(choice(
+        $.pattern_text,
+        $.wildcard,
+        $.bracket_expression,
+        $.escape_sequence,
+        "/",
+      )),
+    )),
+
+    pattern_text: _ => /[^*?\[\/\\\s]+/,
+
+    // `*`, `?` and `**` (any number of directories)
+    wildcard: _ => /\*\*|\*|\?/,
+
+    // `[abc]`, `[0-9]`, `[!.]`
+    bracket_expression: _ => /\[[^\]\n]*\]/,
+
+    escape_sequence: _ => /\\[^\n]/,
+
+    comment: _ => token(prec(1, seq("#", /[^\n]*/))),
+
+    _nl: _ => "\n",
+  },
+});
@@ -0,0 +1,14 @@
+(comment) @comment @spell
+
+(pattern_text) @string.special.path
+
+(wildcard) @character.special
+
+(bracket_expression) @string.regexp
+
+(escape_sequence) @string.escape
+
+"/" @punctuation.delimiter
+
+(pattern
+  negation: "!" @operator)
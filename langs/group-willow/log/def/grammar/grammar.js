@@ -0,0 +1,101 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// Generic log file grammar
+//
+// Log lines have no common syntax, so this is a flat token stream: the
+// interesting tokens (timestamps, levels, UUIDs, IPs, strings, JSON payloads)
+// are recognized wherever they appear, and everything else is a word.
+// Tokens that look alike are told apart by precedence: "ERROR" is a level,
+// "ERRORS" a word, "2024-01-02" a date rather than a number.
+
+const DATE = /\d{4}-\d{2}-\d{2}/;
+const TIME = /\d{2}:\d{2}:\d{2}([.,]\d+)?/;
+const ZONE = /(Z|[+-]\d{2}:?\d{2})/;
+const MONTH = /(Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec)/;
+
+module.exports = grammar({
+  name: "log",
+
+  extras: $ => [/[ \t\r]/],
+
+  rules: {
+    log: $ => repeat(choice($._token, "\n")),
+
+    _token: $ => choice(
+      $.timestamp,
+      $.level_error,
+      $.level_warning,
+      $.level_info,
+      $.level_debug,
+      $.uuid,
+      $.ip_address,
+      $.url,
+      $.string,
+      $.json,
+      $.number,
+      $.word,
+      $._punctuation,
+    ),
+
+    timestamp: $ => token(prec(4, choice(
+      // 2024-01-02T03:04:05.678Z, 2024-01-02 03:04:05,678
+      seq(DATE, optional(seq(/[T ]/, TIME, optional(ZONE)))),
+      // Jan  2 03:04:05 (syslog)
+      seq(MONTH, / +\d{1,2} /, TIME),
+      // 02/Jan/2024:03:04:05 +0000 (Apache/nginx access logs)
+      seq(/\d{2}\//, MONTH, /\/\d{4}:/, TIME, optional(seq(" ", ZONE))),
+      TIME,
+    ))),
+
+    level_error: $ => token(prec(3, choice(
+      "FATAL", "Fatal", "fatal",
+      "CRITICAL", "Critical", "critical", "CRIT", "crit",
+      "ERROR", "Error", "error", "ERR", "err",
+      "PANIC", "panic", "EMERG", "emerg", "ALERT", "alert",
+    ))),
+
+    level_warning: $ => token(prec(3, choice(
+      "WARNING", "Warning", "warning", "WARN", "Warn", "warn",
+    ))),
+
+    level_info: $ => token(prec(3, choice(
+      "INFO", "Info", "info", "NOTICE", "Notice", "notice",
+    ))),
+
+    level_debug: $ => token(prec(3, choice(
+      "DEBUG", "Debug", "debug", "TRACE", "Trace", "trace", "VERBOSE", "verbose",
+    ))),
+
+    uuid: $ => token(prec(3,
+      /[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}/,
+    )),
+
+    ip_address: $ => token(prec(3, choice(
+      // 192.168.0.1, 10.0.0.1:8080
+      /\d{1,3}\.\d{1,3}\.\d{1,3}\.\d{1,3}(:\d+)?/,
+      // fe80::1, 2001:db8:0:0:0:0:2:1 (at least two colons)
+      /[0-9a-fA-F]{0,4}:[0-9a-fA-F]{0,4}:[0-9a-fA-F:]*[0-9a-fA-F]/,
+    ))),
+
+    url: $ => token(prec(3, /[a-zA-Z][a-zA-Z0-9+.-]*:\/\/[^\s"'<>{}\[\]]+/)),
+
+    string: $ => token(prec(2, choice(
+      /"([^"\\\n]|\\.)*"/,
+      /'([^'\\\n]|\\.)*'/,
+    ))),
+
+    // A JSON object that ends on the same line, injected as JSON
+    json: $ => token(prec(2, /\{[^\n]*\}/)),
+
+    number: $ => token(prec(1, /-?\d+(\.\d+)?([eE][+-]?\d+)?/)),
+
+    // Anything else up to whitespace or punctuation. Apostrophes are allowed
+    // inside words so "don't" doesn't start a string.
+    word: $ => /[^\s"{}\[\]()<>,;:=|]+/,
+
+    _punctuation: $ => choice(
+      "[", "]", "(", ")", "{", "}", "<", ">", ",", ";", ":", "=", "|", "\"",
+    ),
+  },
+});
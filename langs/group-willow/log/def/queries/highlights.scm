@@ -0,0 +1,21 @@
+; Timestamps are everywhere, keep them quiet
+(timestamp) @comment
+
+; Log levels
+(level_error) @comment.error
+(level_warning) @comment.warning
+(level_info) @comment.note
+(level_debug) @comment
+
+; Identifiers
+(uuid) @constant
+(ip_address) @constant.numeric
+(url) @string.special.url
+
+; Literals
+(string) @string
+(number) @number
+
+; Punctuation
+["[" "]" "(" ")" "{" "}" "<" ">"] @punctuation.bracket
+["," ";" ":" "=" "|"] @punctuation.delimiter
@@ -0,0 +1 @@
+((json) @injection.content (#set! injection.language "json"))
@@ -50,3 +50,6 @@
 [
   (backslash_escape)
 ] @string.escape
+
+; Paragraph text, for spell checkers
+(paragraph) @spell
@@ -0,0 +1,100 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// Mermaid diagram sources
+//
+// Every diagram type has its own syntax, and most of them are line-oriented
+// with free text in between. Rather than a parser per diagram type, this is
+// a flat token stream: the diagram header, keywords, arrows, edge labels,
+// strings and comments are recognized wherever they appear, and everything
+// else is a word.
+
+module.exports = grammar({
+  name: "mermaid",
+
+  extras: $ => [/\s/],
+
+  word: $ => $.word,
+
+  rules: {
+    source_file: $ => repeat($._token),
+
+    _token: $ => choice(
+      $.directive,
+      $.comment,
+      $.diagram_type,
+      $.direction,
+      $.keyword,
+      $.arrow,
+      $.edge_label,
+      $.class_reference,
+      $.string,
+      $.number,
+      $.word,
+      $._punctuation,
+    ),
+
+    // %%{init: {"theme": "dark"}}%%
+    directive: $ => token(prec(3, /%%\{[^\n]*\}%%/)),
+
+    comment: $ => token(prec(2, /%%[^\n]*/)),
+
+    diagram_type: $ => choice(
+      "graph", "flowchart",
+      "sequenceDiagram", "classDiagram", "classDiagram-v2",
+      "stateDiagram", "stateDiagram-v2", "erDiagram",
+      "journey", "gantt", "pie", "gitGraph", "mindmap", "timeline",
+      "quadrantChart", "requirementDiagram", "zenuml", "kanban",
+      "C4Context", "C4Container", "C4Component", "C4Dynamic", "C4Deployment",
+      "sankey-beta", "xychart-beta", "block-beta", "packet-beta",
+      "architecture-beta", "radar-beta",
+    ),
+
+    direction: $ => choice("TB", "TD", "BT", "RL", "LR"),
+
+    keyword: $ => choice(
+      // flowchart
+      "subgraph", "end", "direction", "click", "call", "href",
+      "style", "classDef", "class", "linkStyle", "default",
+      // sequence
+      "participant", "actor", "as", "autonumber", "activate", "deactivate",
+      "note", "over", "left", "right", "of", "loop", "alt", "else", "opt",
+      "par", "and", "critical", "option", "break", "rect", "box", "create",
+      "destroy", "links", "link",
+      // state, class, ER
+      "state", "namespace",
+      // gantt, journey, pie, timeline
+      "title", "section", "dateFormat", "axisFormat", "tickInterval",
+      "excludes", "includes", "todayMarker", "weekday", "showData",
+      // git
+      "commit", "branch", "checkout", "merge", "cherry-pick", "id", "tag", "type",
+      // accessibility
+      "accTitle", "accDescr",
+    ),
+
+    // -->, ---, -.->, ==>, ->>, --x, <-->, ~~~, --o, -) and class/ER relations
+    arrow: $ => token(prec(1, choice(
+      /(<|x|o)?(--+|==+|-\.+-|~~~+)(>|x|o)?/,
+      /-(>>?|x|\))|--(>>|x|\))/,
+      /(<\||\*|o)?(--|\.\.)(\|>|\*|o|>)?/,
+      /[|}][|o](--|\.\.)[|o][|{]/,
+    ))),
+
+    // A -->|label| B
+    edge_label: $ => token(prec(1, /\|[^|\n]*\|/)),
+
+    // A:::highlighted
+    class_reference: $ => token(prec(1, /:::[A-Za-z_][\w-]*/)),
+
+    string: $ => token(prec(1, /"[^"\n]*"/)),
+
+    number: $ => token(prec(1, /-?\d+(\.\d+)?%?/)),
+
+    word: $ => /[\p{L}_][\p{L}\p{N}_]*/,
+
+    _punctuation: $ => choice(
+      "[", "]", "(", ")", "{", "}", "<", ">", "/", "\\",
+      ",", ";", ":", "=", "&", "#", "@", "'", "!", "?", ".", "-", "+", "*",
+    ),
+  },
+});
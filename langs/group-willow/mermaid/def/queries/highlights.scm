@@ -0,0 +1,19 @@
+(comment) @comment
+(directive) @keyword.directive
+
+; The first line names the diagram type
+(diagram_type) @keyword.directive
+(direction) @constant
+(keyword) @keyword
+
+(arrow) @operator
+(edge_label) @label
+(class_reference) @type
+
+; Literals
+(string) @string
+(number) @number
+
+; Punctuation
+["[" "]" "(" ")" "{" "}"] @punctuation.bracket
+["," ";" ":" "&"] @punctuation.delimiter
@@ -0,0 +1,279 @@
+// C port of src/scanner.cc from milisims/tree-sitter-org; the token logic is
+// unchanged, only the std::vector stacks are replaced with tree-sitter arrays.
+
+#include "tree_sitter/array.h"
+#include "tree_sitter/parser.h"
+
+#include <wctype.h>
+
+enum TokenType {
+  LISTSTART,
+  LISTEND,
+  LISTITEMEND,
+  BULLET,
+  HLSTARS,
+  SECTIONEND,
+  ENDOFFILE,
+};
+
+typedef enum {
+  NOTABULLET,
+  DASH,
+  PLUS,
+  STAR,
+  LOWERDOT,
+  UPPERDOT,
+  LOWERPAREN,
+  UPPERPAREN,
+  NUMDOT,
+  NUMPAREN,
+} Bullet;
+
+typedef struct {
+  Array(int16_t) indent_length_stack;
+  Array(int16_t) bullet_stack;
+  Array(int16_t) section_stack;
+} Scanner;
+
+static unsigned serialize(Scanner *scanner, char *buffer) {
+  size_t i = 0;
+
+  size_t indent_count = scanner->indent_length_stack.size - 1;
+  if (indent_count > UINT8_MAX) indent_count = UINT8_MAX;
+  buffer[i++] = indent_count;
+
+  for (uint32_t j = 1; j < scanner->indent_length_stack.size &&
+                       i < TREE_SITTER_SERIALIZATION_BUFFER_SIZE;
+       j++) {
+    buffer[i++] = *array_get(&scanner->indent_length_stack, j);
+  }
+
+  for (uint32_t j = 1;
+       j < scanner->bullet_stack.size && i < TREE_SITTER_SERIALIZATION_BUFFER_SIZE;
+       j++) {
+    buffer[i++] = *array_get(&scanner->bullet_stack, j);
+  }
+
+  for (uint32_t j = 1;
+       j < scanner->section_stack.size && i < TREE_SITTER_SERIALIZATION_BUFFER_SIZE;
+       j++) {
+    buffer[i++] = *array_get(&scanner->section_stack, j);
+  }
+
+  return i;
+}
+
+static void deserialize(Scanner *scanner, const char *buffer, unsigned length) {
+  array_clear(&scanner->section_stack);
+  array_push(&scanner->section_stack, 0);
+  array_clear(&scanner->indent_length_stack);
+  array_push(&scanner->indent_length_stack, -1);
+  array_clear(&scanner->bullet_stack);
+  array_push(&scanner->bullet_stack, NOTABULLET);
+
+  if (length == 0) return;
+
+  size_t i = 0;
+
+  size_t indent_count = (uint8_t)buffer[i++];
+
+  for (; i <= indent_count; i++) array_push(&scanner->indent_length_stack, buffer[i]);
+  for (; i <= 2 * indent_count; i++) array_push(&scanner->bullet_stack, buffer[i]);
+  for (; i < length; i++) array_push(&scanner->section_stack, buffer[i]);
+}
+
+static inline void advance(TSLexer *lexer) { lexer->advance(lexer, false); }
+
+static inline void skip(TSLexer *lexer) { lexer->advance(lexer, true); }
+
+static bool dedent(Scanner *scanner, TSLexer *lexer) {
+  array_pop(&scanner->indent_length_stack);
+  array_pop(&scanner->bullet_stack);
+  lexer->result_symbol = LISTEND;
+  return true;
+}
+
+static bool in_error_recovery(const bool *valid_symbols) {
+  return (valid_symbols[LISTSTART] && valid_symbols[LISTEND] &&
+          valid_symbols[LISTITEMEND] && valid_symbols[BULLET] &&
+          valid_symbols[HLSTARS] && valid_symbols[SECTIONEND] &&
+          valid_symbols[ENDOFFILE]);
+}
+
+static Bullet getbullet(TSLexer *lexer) {
+  if (lexer->lookahead == '-') {
+    advance(lexer);
+    if (iswspace(lexer->lookahead)) return DASH;
+  } else if (lexer->lookahead == '+') {
+    advance(lexer);
+    if (iswspace(lexer->lookahead)) return PLUS;
+  } else if (lexer->lookahead == '*') {
+    advance(lexer);
+    if (iswspace(lexer->lookahead)) return STAR;
+  } else if ('a' <= lexer->lookahead && lexer->lookahead <= 'z') {
+    advance(lexer);
+    if (lexer->lookahead == '.') {
+      advance(lexer);
+      if (iswspace(lexer->lookahead)) return LOWERDOT;
+    } else if (lexer->lookahead == ')') {
+      advance(lexer);
+      if (iswspace(lexer->lookahead)) return LOWERPAREN;
+    }
+  } else if ('A' <= lexer->lookahead && lexer->lookahead <= 'Z') {
+    advance(lexer);
+    if (lexer->lookahead == '.') {
+      advance(lexer);
+      if (iswspace(lexer->lookahead)) return UPPERDOT;
+    } else if (lexer->lookahead == ')') {
+      advance(lexer);
+      if (iswspace(lexer->lookahead)) return UPPERPAREN;
+    }
+  } else if ('0' <= lexer->lookahead && lexer->lookahead <= '9') {
+    do {
+      advance(lexer);
+    } while ('0' <= lexer->lookahead && lexer->lookahead <= '9');
+    if (lexer->lookahead == '.') {
+      advance(lexer);
+      if (iswspace(lexer->lookahead)) return NUMDOT;
+    } else if (lexer->lookahead == ')') {
+      advance(lexer);
+      if (iswspace(lexer->lookahead)) return NUMPAREN;
+    }
+  }
+  return NOTABULLET;
+}
+
+static bool scan(Scanner *scanner, TSLexer *lexer, const bool *valid_symbols) {
+  if (in_error_recovery(valid_symbols)) return false;
+
+  // - Section ends
+  int16_t indent_length = 0;
+  lexer->mark_end(lexer);
+  for (;;) {
+    if (lexer->lookahead == ' ') {
+      indent_length++;
+    } else if (lexer->lookahead == '\t') {
+      indent_length += 8;
+    } else if (lexer->lookahead == '\0') {
+      if (valid_symbols[LISTEND]) {
+        lexer->result_symbol = LISTEND;
+      } else if (valid_symbols[SECTIONEND]) {
+        lexer->result_symbol = SECTIONEND;
+      } else if (valid_symbols[ENDOFFILE]) {
+        lexer->result_symbol = ENDOFFILE;
+      } else {
+        return false;
+      }
+
+      return true;
+    } else {
+      break;
+    }
+    skip(lexer);
+  }
+
+  // - Listiem ends
+  // Listend -> end of a line, looking for:
+  // 1. dedent
+  // 2. same indent, not a bullet
+  // 3. two eols
+  int16_t newlines = 0;
+  if (valid_symbols[LISTEND] || valid_symbols[LISTITEMEND]) {
+    for (;;) {
+      if (lexer->lookahead == ' ') {
+        indent_length++;
+      } else if (lexer->lookahead == '\t') {
+        indent_length += 8;
+      } else if (lexer->lookahead == '\0') {
+        return dedent(scanner, lexer);
+      } else if (lexer->lookahead == '\n') {
+        if (++newlines > 1) return dedent(scanner, lexer);
+        indent_length = 0;
+      } else {
+        break;
+      }
+      skip(lexer);
+    }
+
+    if (indent_length < *array_back(&scanner->indent_length_stack)) {
+      return dedent(scanner, lexer);
+    } else if (indent_length == *array_back(&scanner->indent_length_stack)) {
+      if (getbullet(lexer) == *array_back(&scanner->bullet_stack)) {
+        lexer->result_symbol = LISTITEMEND;
+        return true;
+      }
+      return dedent(scanner, lexer);
+    }
+  }
+
+  // - Col=0 star
+  if (indent_length == 0 && lexer->lookahead == '*') {
+    lexer->mark_end(lexer);
+    int16_t stars = 1;
+    skip(lexer);
+    while (lexer->lookahead == '*') {
+      stars++;
+      skip(lexer);
+    }
+
+    if (valid_symbols[SECTIONEND] && iswspace(lexer->lookahead) && stars > 0 &&
+        stars <= *array_back(&scanner->section_stack)) {
+      array_pop(&scanner->section_stack);
+      lexer->result_symbol = SECTIONEND;
+      return true;
+    } else if (valid_symbols[HLSTARS] && iswspace(lexer->lookahead)) {
+      array_push(&scanner->section_stack, stars);
+      lexer->result_symbol = HLSTARS;
+      return true;
+    }
+    return false;
+  }
+
+  // - Liststart and bullets
+  if ((valid_symbols[LISTSTART] || valid_symbols[BULLET]) && newlines == 0) {
+    Bullet bullet = getbullet(lexer);
+
+    if (valid_symbols[BULLET] && bullet == *array_back(&scanner->bullet_stack) &&
+        indent_length == *array_back(&scanner->indent_length_stack)) {
+      lexer->mark_end(lexer);
+      lexer->result_symbol = BULLET;
+      return true;
+    } else if (valid_symbols[LISTSTART] && bullet != NOTABULLET &&
+               indent_length > *array_back(&scanner->indent_length_stack)) {
+      array_push(&scanner->indent_length_stack, indent_length);
+      array_push(&scanner->bullet_stack, bullet);
+      lexer->result_symbol = LISTSTART;
+      return true;
+    }
+  }
+
+  return false; // default
+}
+
+void *tree_sitter_org_external_scanner_create() {
+  Scanner *scanner = ts_calloc(1, sizeof(Scanner));
+  deserialize(scanner, NULL, 0);
+  return scanner;
+}
+
+bool tree_sitter_org_external_scanner_scan(void *payload, TSLexer *lexer,
+                                           const bool *valid_symbols) {
+  return scan((Scanner *)payload, lexer, valid_symbols);
+}
+
+unsigned tree_sitter_org_external_scanner_serialize(void *payload, char *buffer) {
+  return serialize((Scanner *)payload, buffer);
+}
+
+void tree_sitter_org_external_scanner_deserialize(void *payload, const char *buffer,
+                                                  unsigned length) {
+  deserialize((Scanner *)payload, buffer, length);
+}
+
+void tree_sitter_org_external_scanner_destroy(void *payload) {
+  Scanner *scanner = (Scanner *)payload;
+  array_delete(&scanner->indent_length_stack);
+  array_delete(&scanner->bullet_stack);
+  array_delete(&scanner->section_stack);
+  ts_free(scanner);
+}
@@ -0,0 +1,35 @@
+; Headlines
+(headline (stars) @punctuation.special (item) @markup.heading)
+((item . (expr) @keyword)
+  (#match? @keyword "^(TODO|NEXT|WAITING)$"))
+((item . (expr) @comment.todo)
+  (#match? @comment.todo "^(DONE|CANCELL?ED)$"))
+(tag_list (tag) @tag)
+
+; Directives, drawers and comments
+(directive name: (expr) @keyword.directive)
+(directive value: (value) @string)
+(property name: (expr) @property)
+(drawer name: (expr) @keyword.directive)
+(comment) @comment
+
+; Planning and timestamps
+(entry name: (entry_name) @keyword)
+(timestamp) @string.special
+(fndef label: (expr) @label)
+
+; Blocks
+(block name: (expr) @keyword.directive)
+(block end_name: (expr) @keyword.directive)
+(block parameter: (expr) @label)
+(dynamic_block name: (expr) @keyword.directive)
+(block contents: (contents) @markup.raw.block)
+
+; Lists and tables
+(bullet) @markup.list
+(checkbox) @markup.list.unchecked
+(checkbox status: (expr) @markup.list.checked
+  (#match? @markup.list.checked "^[xX]$"))
+(hr) @punctuation.delimiter
+(row "|" @punctuation.delimiter)
+(cell "|" @punctuation.delimiter)
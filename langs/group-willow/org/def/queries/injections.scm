@@ -0,0 +1,9 @@
+; #+begin_src python, #+begin_export html
+((block
+  name: (expr) @_name
+  .
+  parameter: (expr) @injection.language
+  contents: (contents) @injection.content)
+  (#match? @_name "^(src|SRC|export|EXPORT)$")
+  (#downcase! @injection.language)
+  (#set! injection.include-children))
@@ -0,0 +1,107 @@
+/// <reference types="tree-sitter-cli/dsl" />
+// @ts-check
+
+// PlantUML diagram sources
+//
+// Like Mermaid, PlantUML has a different syntax for each diagram type, so
+// this is a flat token stream: `@startuml` markers, preprocessor directives,
+// keywords, arrows, stereotypes, colors, strings and comments are recognized
+// wherever they appear, and everything else is a word. Apostrophes are
+// allowed inside words, so only a `'` that starts a word opens a comment.
+
+module.exports = grammar({
+  name: "plantuml",
+
+  extras: $ => [/\s/],
+
+  word: $ => $.word,
+
+  rules: {
+    source_file: $ => repeat($._token),
+
+    _token: $ => choice(
+      $.block_marker,
+      $.preprocessor,
+      $.comment,
+      $.keyword,
+      $.arrow,
+      $.stereotype,
+      $.color,
+      $.activity,
+      $.variable,
+      $.string,
+      $.number,
+      $.word,
+      $._punctuation,
+    ),
+
+    // @startuml, @enduml, @startmindmap, ...
+    block_marker: $ => token(prec(3, /@(start|end)[a-z]+/)),
+
+    // !include, !define, !theme, !procedure, ...
+    preprocessor: $ => token(prec(3, /![a-z_]+/)),
+
+    comment: $ => token(prec(2, choice(
+      /'[^\n]*/,
+      seq("/'", /[^']*'+([^/'][^']*'+)*/, "/"),
+    ))),
+
+    keyword: $ => choice(
+      // participants and elements
+      "participant", "actor", "boundary", "control", "entity", "database",
+      "collections", "queue", "class", "interface", "abstract", "enum",
+      "annotation", "struct", "object", "map", "json", "package", "namespace",
+      "component", "node", "cloud", "frame", "folder", "rectangle", "artifact",
+      "usecase", "storage", "file", "card", "agent", "person", "state",
+      // sequence
+      "as", "activate", "deactivate", "destroy", "create", "return",
+      "autonumber", "alt", "else", "opt", "loop", "par", "break", "critical",
+      "group", "ref", "box", "newpage", "hnote", "rnote",
+      // activity
+      "start", "stop", "end", "kill", "detach", "if", "then", "elseif",
+      "endif", "while", "endwhile", "repeat", "backward", "fork", "again",
+      "split", "switch", "case", "endswitch", "partition", "swimlane",
+      // notes and layout
+      "note", "over", "of", "on", "link", "left", "right", "top", "bottom",
+      "up", "down", "direction", "together", "hide", "show", "remove",
+      "skinparam", "skin", "style", "title", "header", "footer", "legend",
+      "endlegend", "caption", "scale", "extends", "implements",
+    ),
+
+    // ->, -->, <->, ..>, -[#red]->, -up->, <|--, *--, o--, #--, ->x, ->>
+    arrow: $ => {
+      const head = choice("<", "<<", "<|", "*", "o", "#", "x", "}", "{", "+");
+      const tail = choice(">", ">>", "|>", "*", "o", "#", "x", "{", "}", "+", "\\\\", "//");
+      const hint = choice(/\[[^\]\n]*\]/, "left", "right", "up", "down", "l", "r", "u", "d");
+      return token(prec(1, choice(
+        seq(optional(head), /[-.=]{2,}/, optional(tail)),
+        seq(optional(head), /[-.=]+/, hint, /[-.=]+/, optional(tail)),
+        seq(optional(head), /[-.=]/, tail),
+        seq(head, /[-.=]/),
+      )));
+    },
+
+    // <<interface>>, << (S,#FF7700) Singleton >>
+    stereotype: $ => token(prec(2, /<<[^<>\n]+>>/)),
+
+    // #Red, #FF7700, #lightblue/white
+    color: $ => token(prec(2, /#[0-9A-Za-z]+(\/[0-9A-Za-z#]+)?/)),
+
+    // :Activity label;
+    activity: $ => token(prec(1, /:[^:;\n][^;\n]*;/)),
+
+    // $name, used with the preprocessor
+    variable: $ => token(prec(1, /\$[A-Za-z_]\w*/)),
+
+    string: $ => token(prec(1, /"[^"\n]*"/)),
+
+    number: $ => token(prec(1, /-?\d+(\.\d+)?/)),
+
+    word: $ => /[\p{L}_][\p{L}\p{N}_']*/,
+
+    _punctuation: $ => choice(
+      "[", "]", "(", ")", "{", "}", "<", ">", "/", "\\",
+      ",", ";", ":", "=", "&", "@", "!", "?", ".", "-", "+", "*", "|", "%", "~",
+    ),
+  },
+});
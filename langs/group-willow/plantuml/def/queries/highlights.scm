@@ -0,0 +1,19 @@
+(comment) @comment
+
+(block_marker) @keyword.directive
+(preprocessor) @keyword.directive
+(keyword) @keyword
+
+(arrow) @operator
+(stereotype) @attribute
+(color) @constant
+(variable) @variable
+
+; Literals
+(activity) @string
+(string) @string
+(number) @number
+
+; Punctuation
+["[" "]" "(" ")" "{" "}"] @punctuation.bracket
+["," ";" ":"] @punctuation.delimiter
@@ -0,0 +1,39 @@
+; Sections
+(title) @markup.heading
+"adornment" @punctuation.special
+(transition) @punctuation.special
+
+; Directives and explicit markup
+".." @punctuation.special
+(directive name: (type) @keyword.directive)
+(directive "::" @punctuation.special)
+(body (arguments) @label)
+(substitution_definition name: (substitution) @constant)
+(target name: (name) @label)
+(target link: (link) @markup.link.url)
+(footnote name: (label) @label)
+(citation name: (label) @label)
+(comment) @comment
+
+; Literal blocks after a paragraph ending in `::`
+(literal_block) @markup.raw.block
+(doctest_block) @markup.raw.block
+
+; Fields and lists
+(field_name) @property
+(term) @markup.strong
+(classifier) @type
+(attribution) @markup.italic
+
+; Inline markup
+(strong) @markup.bold
+(emphasis) @markup.italic
+(literal) @markup.raw.inline
+(interpreted_text) @markup.italic
+(role) @function.builtin
+(reference) @markup.link
+(footnote_reference) @markup.link
+(citation_reference) @markup.link
+(inline_target) @label
+(substitution_reference) @constant
+(standalone_hyperlink) @markup.link.url
@@ -0,0 +1,9 @@
+; .. code-block:: python, .. raw:: html
+((directive
+  name: (type) @_name
+  body: (body
+    (arguments) @injection.language
+    (content) @injection.content))
+  (#match? @_name "^(code-block|code|sourcecode|raw)$")
+  (#downcase! @injection.language)
+  (#set! injection.include-children))
@@ -5,7 +5,15 @@
  * Configuration via data attributes or window.Arborium object.
  */
 
-import { loadGrammar, highlight, getConfig, setConfig, defaultConfig } from "./loader.js";
+import {
+  loadGrammar,
+  highlight,
+  getConfig,
+  setConfig,
+  defaultConfig,
+  prefetch,
+  resolveLanguage,
+} from "./loader.js";
 import { detectLanguage, extractLanguageFromClass, normalizeLanguage } from "./detect.js";
 import type { ArboriumConfig } from "./types.js";
 
@@ -47,6 +55,9 @@ function getConfigFromScript(): Partial<ArboriumConfig> {
   const version = currentScript.getAttribute("data-version");
   if (version) config.version = version;
 
+  const indexUrl = currentScript.getAttribute("data-index");
+  if (indexUrl) config.indexUrl = indexUrl;
+
   // Query parameters (for local testing)
   const pluginsUrl = params.get("pluginsUrl");
   if (pluginsUrl) config.pluginsUrl = pluginsUrl;
@@ -368,6 +379,8 @@ export async function highlightElement(
   highlightElement,
   loadGrammar,
   highlight,
+  prefetch,
+  resolveLanguage,
   detectLanguage,
   config: getMergedConfig(),
 };
@@ -413,6 +426,12 @@ function watchThemeChanges(): void {
 
 // Auto-highlight on DOMContentLoaded (unless manual mode)
 const config = getMergedConfig();
+
+// Start loading hinted languages right away, before the DOM is ready
+const prefetchHint = currentScript?.getAttribute("data-prefetch");
+if (prefetchHint) {
+  prefetch(prefetchHint.split(/[\s,]+/).filter(Boolean), config);
+}
 if (!config.manual) {
   if (document.readyState === "loading") {
     document.addEventListener("DOMContentLoaded", () => {
@@ -4,15 +4,36 @@
  * ESM entry point for programmatic usage.
  */
 
-export { loadGrammar, highlight, spansToHtml, getConfig, setConfig } from './loader.js';
+export {
+  loadGrammar,
+  highlight,
+  spansToHtml,
+  getConfig,
+  setConfig,
+  prefetch,
+  resolveLanguage,
+} from './loader.js';
 export { detectLanguage, extractLanguageFromClass, normalizeLanguage } from './detect.js';
-export { pluginVersion, availableLanguages, highlights } from './plugins-manifest.js';
+export { pluginVersion, availableLanguages, languageAliases, highlights } from './plugins-manifest.js';
 export type {
   Grammar,
   Session,
   Span,
   Injection,
   ParseResult,
+  QueryMatch,
+  QueryCapture,
+  FoldingRange,
+  DocumentSymbol,
+  CommentSyntax,
+  CommentEdit,
+  BracketPair,
+  PluginInfo,
+  ProseClass,
+  ProseRange,
   Highlight,
   ArboriumConfig,
+  PluginIndex,
+  PluginIndexEntry,
+  PluginFile,
 } from './types.js';
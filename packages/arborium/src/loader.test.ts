@@ -1,6 +1,12 @@
 import { describe, it, expect } from "vitest";
-import { spansToHtml, utf8ByteLength, utf8OffsetToUtf16 } from "./utils.js";
-import type { Span } from "./types.js";
+import {
+  findIndexEntry,
+  normalizeLanguageName,
+  spansToHtml,
+  utf8ByteLength,
+  utf8OffsetToUtf16,
+} from "./utils.js";
+import type { PluginIndex, PluginIndexEntry, Span } from "./types.js";
 
 // Helper to get UTF-8 byte offsets for a substring
 function getUtf8Offsets(source: string, substring: string): { start: number; end: number } {
@@ -171,3 +177,33 @@ describe("spansToHtml", () => {
     expect(html).toContain("<a-s>&quot;42µs&quot;</a-s>");
   });
 });
+
+describe("findIndexEntry", () => {
+  const entry = (language: string, aliases: string[]): PluginIndexEntry => ({
+    language,
+    aliases,
+    js: { path: `${language}/grammar.js`, size: 0, sha256: "" },
+    wasm: { path: `${language}/grammar_bg.wasm`, size: 0, sha256: "" },
+  });
+  const index: PluginIndex = {
+    wire_version: 1,
+    version: "0.0.0",
+    plugins: [entry("javascript", ["js", "mjs"]), entry("json", ["jsonc"])],
+  };
+
+  it("normalizes names", () => {
+    expect(normalizeLanguageName(".MJS")).toBe("mjs");
+    expect(normalizeLanguageName("Rust")).toBe("rust");
+  });
+
+  it("finds plugins by id, alias and extension", () => {
+    expect(findIndexEntry(index, "JavaScript")?.language).toBe("javascript");
+    expect(findIndexEntry(index, "js")?.language).toBe("javascript");
+    expect(findIndexEntry(index, ".mjs")?.language).toBe("javascript");
+    expect(findIndexEntry(index, "jsonc")?.wasm.path).toBe("json/grammar_bg.wasm");
+  });
+
+  it("returns undefined for unknown languages", () => {
+    expect(findIndexEntry(index, "python")).toBeUndefined();
+  });
+});
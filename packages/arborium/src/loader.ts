@@ -4,13 +4,31 @@
  * Architecture:
  * 1. Grammar registry is bundled at build time (no network request needed in production)
  *    - Can be overridden via pluginsUrl config for local development
- * 2. Load grammar wasm-bindgen modules on demand from @arborium/<lang> packages
- * 3. Parse and highlight using the grammar's tree-sitter parser
+ *    - Or replaced by a plugin index (indexUrl config) for self-hosted plugins
+ * 2. Resolve aliases and file extensions ("js", ".mjs") to language ids
+ * 3. Load grammar wasm-bindgen modules on first use (or on prefetch) from @arborium/<lang> packages
+ * 4. Parse and highlight using the grammar's tree-sitter parser
  */
 
-import type { ParseResult, ArboriumConfig, Grammar, Session } from "./types.js";
-import { availableLanguages, pluginVersion } from "./plugins-manifest.js";
-import { spansToHtml, escapeHtml } from "./utils.js";
+import type {
+  ParseResult,
+  ArboriumConfig,
+  Grammar,
+  Session,
+  PluginIndex,
+  PluginFile,
+  QueryMatch,
+  FoldingRange,
+  DocumentSymbol,
+  CommentSyntax,
+  CommentEdit,
+  BracketPair,
+  PluginInfo,
+  ProseClass,
+  ProseRange,
+} from "./types.js";
+import { availableLanguages, languageAliases, pluginVersion } from "./plugins-manifest.js";
+import { spansToHtml, escapeHtml, findIndexEntry, normalizeLanguageName } from "./utils.js";
 
 // Re-export utilities
 export { spansToHtml } from "./utils.js";
@@ -23,6 +41,7 @@ export const defaultConfig: Required<ArboriumConfig> = {
   cdn: "jsdelivr",
   version: pluginVersion, // Precise version from manifest
   pluginsUrl: "", // Empty means use bundled manifest
+  indexUrl: "", // Empty means load plugins from the CDN
   hostUrl: "", // Empty means use CDN based on version
   resolveJs: ({ baseUrl, path }) => import(/* @vite-ignore */ `${baseUrl}/${path}`),
   resolveWasm: ({ baseUrl, path }) => fetch(`${baseUrl}/${path}`),
@@ -82,8 +101,72 @@ async function ensureLocalManifest(config: Required<ArboriumConfig>): Promise<vo
   return localManifestPromise;
 }
 
+// Self-hosted plugins: an index of a directory of built plugins
+let pluginIndex: PluginIndex | null = null;
+let pluginIndexPromise: Promise<void> | null = null;
+
+/** Load the plugin index if indexUrl is configured */
+async function ensurePluginIndex(config: Required<ArboriumConfig>): Promise<void> {
+  if (!config.indexUrl) {
+    return;
+  }
+
+  if (pluginIndexPromise) {
+    return pluginIndexPromise;
+  }
+
+  pluginIndexPromise = (async () => {
+    console.debug(`[arborium] Loading plugin index from: ${config.indexUrl}`);
+    const response = await fetch(config.indexUrl);
+    if (!response.ok) {
+      throw new Error(`Failed to load plugin index: ${response.status}`);
+    }
+    pluginIndex = await response.json();
+    console.debug(
+      `[arborium] Loaded plugin index with ${pluginIndex?.plugins.length} plugins (arborium ${pluginIndex?.version})`,
+    );
+  })();
+
+  return pluginIndexPromise;
+}
+
+/** Load whichever manifests the config points at */
+async function ensureManifests(config: Required<ArboriumConfig>): Promise<void> {
+  await Promise.all([ensureLocalManifest(config), ensurePluginIndex(config)]);
+}
+
+/**
+ * Resolve a language id, alias or file extension to the id of an available
+ * plugin, or null. Manifests must be loaded first.
+ */
+function resolveLanguageName(name: string): string | null {
+  const key = normalizeLanguageName(name);
+  if (pluginIndex) {
+    return findIndexEntry(pluginIndex, key)?.language ?? null;
+  }
+  const isAvailable = (id: string) =>
+    knownLanguages.has(id) || (localManifest?.entries.some((e) => e.language === id) ?? false);
+  if (isAvailable(key)) {
+    return key;
+  }
+  const aliased = Object.prototype.hasOwnProperty.call(languageAliases, key) ? languageAliases[key] : null;
+  return aliased && isAvailable(aliased) ? aliased : null;
+}
+
+/** Resolve a path from the plugin index against the index's URL */
+function resolveIndexPath(path: string, config: Required<ArboriumConfig>): string {
+  return new URL(path, new URL(config.indexUrl, location.href)).href;
+}
+
 /** Get the CDN base URL for a grammar */
 function getGrammarBaseUrl(language: string, config: Required<ArboriumConfig>): string {
+  // If we have a plugin index, plugin paths are relative to it
+  const indexEntry = pluginIndex && findIndexEntry(pluginIndex, language);
+  if (indexEntry) {
+    const jsUrl = resolveIndexPath(indexEntry.js.path, config);
+    return jsUrl.substring(0, jsUrl.lastIndexOf("/"));
+  }
+
   // If we have a local manifest (dev mode), use the local path
   if (localManifest) {
     const entry = localManifest.entries.find((e) => e.language === language);
@@ -124,8 +207,88 @@ interface WasmBindgenPlugin {
   create_session: () => number;
   free_session: (session: number) => void;
   set_text: (session: number, text: string) => void;
+  /** Replaces UTF-16 offsets `start..end` of the session's text (absent in older plugins) */
+  splice_text?: (session: number, start: number, end: number, replacement: string) => void;
   parse: (session: number) => ParseResult;
+  /** Matches of a tree-sitter query, throwing `{ kind, message }` (absent in older plugins) */
+  query?: (session: number, source: string) => QueryMatch[];
+  /** Foldable lines (absent in older plugins) */
+  folding_ranges?: (session: number) => WireFoldingRange[];
+  /** Outline of the text, with UTF-16 offsets (absent in older plugins) */
+  symbols?: (session: number) => WireSymbol[];
+  /** Comment delimiters (absent in older plugins) */
+  comment_syntax?: () => WireCommentSyntax;
+  /** Language metadata for editors (absent in older plugins) */
+  plugin_info?: () => WirePluginInfo;
+  /** Edits toggling comments, with UTF-16 offsets (absent in older plugins) */
+  comment_ranges?: (text: string, start: number, end: number) => CommentEdit[];
+  /** Prose for spell checkers, with UTF-16 offsets (absent in older plugins) */
+  prose_ranges?: (session: number, classes: string[]) => ProseRange[];
+  /** Next larger syntax node around UTF-16 offsets (absent in older plugins) */
+  expand_selection?: (session: number, start: number, end: number) => { start: number; end: number };
   cancel: (session: number) => void;
+  /** Indented S-expression of the session's syntax tree (absent in older plugins) */
+  tree_sexp?: (session: number, ranges: boolean, anonymous: boolean) => string;
+  /** Checks the grammar and sets up the plugin, throwing `{ kind, message }` (absent in older plugins) */
+  handshake?: () => void;
+  /** Whether the plugin needs `set_queries` before use (absent in older plugins) */
+  external_queries?: () => boolean;
+  /** Passes in the queries of a plugin that doesn't embed them */
+  set_queries?: (highlights: string, injections: string, locals: string) => void;
+}
+
+/** `FoldingRange` as plugins return it */
+interface WireFoldingRange {
+  start_row: number;
+  end_row: number;
+  kind: "region" | "comment";
+}
+
+/** `DocumentSymbol` as plugins return it */
+interface WireSymbol {
+  name: string;
+  kind: string;
+  start: number;
+  end: number;
+  name_start: number;
+  name_end: number;
+  children: WireSymbol[];
+}
+
+function fromWireSymbol(symbol: WireSymbol): DocumentSymbol {
+  return {
+    name: symbol.name,
+    kind: symbol.kind,
+    start: symbol.start,
+    end: symbol.end,
+    nameStart: symbol.name_start,
+    nameEnd: symbol.name_end,
+    children: symbol.children.map(fromWireSymbol),
+  };
+}
+
+/** `CommentSyntax` as plugins return it */
+interface WireCommentSyntax {
+  line?: string;
+  block_start?: string;
+  block_end?: string;
+}
+
+function fromWireCommentSyntax(syntax: WireCommentSyntax | undefined): CommentSyntax {
+  return {
+    line: syntax?.line,
+    block:
+      syntax?.block_start && syntax.block_end ? [syntax.block_start, syntax.block_end] : undefined,
+  };
+}
+
+/** `PluginInfo` as plugins return it */
+interface WirePluginInfo {
+  language_id: string;
+  wire_version: number;
+  comments: WireCommentSyntax;
+  brackets: BracketPair[];
+  auto_close_pairs: BracketPair[];
 }
 
 /** A loaded grammar plugin */
@@ -136,8 +299,84 @@ interface GrammarPlugin {
   parse: (text: string) => ParseResult;
 }
 
-/** Load a grammar plugin */
-async function loadGrammarPlugin(language: string, config: Required<ArboriumConfig>): Promise<GrammarPlugin | null> {
+/**
+ * Compile a plugin's WASM while it downloads.
+ *
+ * Plugins are large, so they should be served compressed: the server picks
+ * the encoding from the browser's Accept-Encoding (`cargo xtask package`
+ * writes `.wasm.br` and `.wasm.gz` for that) and the browser decodes it, so
+ * the bytes compiled here are plain WASM either way.
+ */
+async function compileWasm(response: Response): Promise<WebAssembly.Module> {
+  if (!response.ok) {
+    throw new Error(`Failed to load ${response.url}: ${response.status}`);
+  }
+  // compileStreaming rejects responses without the WASM MIME type
+  if (response.headers.get("Content-Type")?.split(";")[0].trim() === "application/wasm") {
+    return WebAssembly.compileStreaming(response);
+  }
+  console.debug(`[arborium] ${response.url} is not served as application/wasm, compiling after download`);
+  return WebAssembly.compile(await response.arrayBuffer());
+}
+
+/** Fetch and compile a plugin's WASM from the plugin index, checking it against the index's hash */
+async function fetchIndexedWasm(index: PluginIndex, language: string, config: Required<ArboriumConfig>): Promise<WebAssembly.Module> {
+  const entry = findIndexEntry(index, language);
+  if (!entry) {
+    throw new Error(`'${language}' is not in the plugin index`);
+  }
+  const response = await fetch(resolveIndexPath(entry.wasm.path, config));
+  if (!response.ok) {
+    throw new Error(`Failed to load ${entry.wasm.path}: ${response.status}`);
+  }
+  // Hash a copy of the body while the original compiles
+  const [module] = await Promise.all([
+    compileWasm(response.clone()),
+    response.arrayBuffer().then((bytes) => checkSha256(bytes, entry.wasm)),
+  ]);
+  return module;
+}
+
+/** Throw if a file fetched through the plugin index doesn't have the hash the index lists */
+async function checkSha256(bytes: ArrayBuffer, file: PluginFile): Promise<void> {
+  const digest = new Uint8Array(await crypto.subtle.digest("SHA-256", bytes));
+  const hex = Array.from(digest, (b) => b.toString(16).padStart(2, "0")).join("");
+  if (hex !== file.sha256) {
+    throw new Error(`${file.path} does not match the plugin index (sha256 ${hex}, expected ${file.sha256})`);
+  }
+}
+
+/** Queries of a plugin that doesn't embed them (its queries.json) */
+interface PluginQueries {
+  highlights: string;
+  injections: string;
+  locals: string;
+}
+
+/** Fetch a plugin's queries.json, checking it against the plugin index if it's listed there */
+async function fetchQueries(url: string, file?: PluginFile): Promise<PluginQueries> {
+  const response = await fetch(url);
+  if (!response.ok) {
+    throw new Error(`Failed to load ${url}: ${response.status}`);
+  }
+  const bytes = await response.arrayBuffer();
+  if (file) {
+    await checkSha256(bytes, file);
+  }
+  return JSON.parse(new TextDecoder().decode(bytes));
+}
+
+/** Load a grammar plugin by language id, alias or file extension */
+async function loadGrammarPlugin(name: string, config: Required<ArboriumConfig>): Promise<GrammarPlugin | null> {
+  // Load local manifest or plugin index if configured
+  await ensureManifests(config);
+
+  const language = resolveLanguageName(name);
+  if (!language) {
+    console.debug(`[arborium] Grammar '${name}' not available`);
+    return null;
+  }
+
   // Check cache first
   const cached = grammarCache.get(language);
   if (cached) {
@@ -164,37 +403,55 @@ async function loadGrammarPlugin(language: string, config: Required<ArboriumConf
   }
 }
 
-/** Internal grammar loading - called only once per language */
+/** Internal grammar loading - called only once per (resolved, available) language */
 async function loadGrammarPluginInner(language: string, config: Required<ArboriumConfig>): Promise<GrammarPlugin | null> {
-  // Load local manifest if in dev mode
-  await ensureLocalManifest(config);
-
-  // Check if language is known
-  if (
-    !knownLanguages.has(language) &&
-    !localManifest?.entries.some((e) => e.language === language)
-  ) {
-    console.debug(`[arborium] Grammar '${language}' not available`);
-    return null;
-  }
-
   try {
     const baseUrl = getGrammarBaseUrl(language, config);
     const detail = config.resolveJs === defaultConfig.resolveJs ? ` from ${baseUrl}/grammar.js` : "";
     console.debug(`[arborium] Loading grammar '${language}'${detail}`);
 
     const module = (await config.resolveJs({ language, baseUrl, path: "grammar.js" })) as WasmBindgenPlugin;
-    const wasm = await config.resolveWasm({ language, baseUrl, path: "grammar_bg.wasm" });
+
+    // Plugins that don't embed their queries have them in queries.json. The index
+    // says which ones those are, so their queries download alongside the WASM.
+    const queriesFile = (pluginIndex && findIndexEntry(pluginIndex, language))?.queries;
+    const queriesUrl = queriesFile ? resolveIndexPath(queriesFile.path, config) : `${baseUrl}/queries.json`;
+    const queriesPromise = queriesFile ? fetchQueries(queriesUrl, queriesFile) : null;
+    queriesPromise?.catch(() => {}); // awaited below
+
+    let wasm =
+      pluginIndex && config.resolveWasm === defaultConfig.resolveWasm
+        ? await fetchIndexedWasm(pluginIndex, language, config)
+        : await config.resolveWasm({ language, baseUrl, path: "grammar_bg.wasm" });
+    if (wasm instanceof Response) {
+      wasm = await compileWasm(wasm);
+    }
 
     // Initialize the WASM module
     await module.default({ module_or_path: wasm });
 
+    if (module.external_queries?.()) {
+      const queries = await (queriesPromise ?? fetchQueries(queriesUrl));
+      module.set_queries!(queries.highlights, queries.injections, queries.locals);
+    }
+
     // Verify it loaded correctly
     const loadedId = module.language_id();
     if (loadedId !== language) {
       console.warn(`[arborium] Language ID mismatch: expected '${language}', got '${loadedId}'`);
     }
 
+    // Older plugins don't have a handshake, and only fail on first parse
+    if (module.handshake) {
+      try {
+        module.handshake();
+      } catch (e) {
+        const message = (e as { message?: string })?.message ?? String(e);
+        console.error(`[arborium] Grammar '${language}' can't be used: ${message}`);
+        return null;
+      }
+    }
+
     // Get injection languages
     const injectionLanguages = module.injection_languages();
 
@@ -240,7 +497,7 @@ function setupHostInterface(config: Required<ArboriumConfig>): void {
   (window as any).arboriumHost = {
     /** Check if a language is available (sync) */
     isLanguageAvailable(language: string): boolean {
-      return knownLanguages.has(language) || grammarCache.has(language);
+      return resolveLanguageName(language) !== null || grammarCache.has(language);
     },
 
     /** Load a grammar and return a handle (async) */
@@ -328,6 +585,8 @@ export async function highlight(
   configOverrides?: ArboriumConfig,
 ): Promise<string> {
   const config = getConfig(configOverrides);
+  // The host asks for languages synchronously, so the manifests must be ready
+  await ensureManifests(config);
   // Try to use the Rust host (handles injections properly)
   const host = await loadHost(config);
   if (host) {
@@ -369,8 +628,21 @@ export async function loadGrammar(
     parse: (source: string) => plugin.parse(source),
     createSession: (): Session => {
       const handle = module.create_session();
+      // Kept for plugins without splice_text, which need the whole text
+      let current = "";
       return {
-        setText: (text: string) => module.set_text(handle, text),
+        setText: (text: string) => {
+          current = text;
+          module.set_text(handle, text);
+        },
+        splice: (start: number, end: number, replacement: string) => {
+          current = current.slice(0, start) + replacement + current.slice(end);
+          if (module.splice_text) {
+            module.splice_text(handle, start, end, replacement);
+          } else {
+            module.set_text(handle, current);
+          }
+        },
         parse: () => {
           try {
             const result = module.parse(handle);
@@ -383,10 +655,38 @@ export async function loadGrammar(
             return { spans: [], injections: [] };
           }
         },
+        query: (source: string) => {
+          if (!module.query) {
+            throw new Error(`The ${plugin.languageId} plugin doesn't support queries`);
+          }
+          return module.query(handle, source);
+        },
+        foldingRanges: (): FoldingRange[] =>
+          (module.folding_ranges?.(handle) ?? []).map((range) => ({
+            startRow: range.start_row,
+            endRow: range.end_row,
+            kind: range.kind,
+          })),
+        symbols: (): DocumentSymbol[] => (module.symbols?.(handle) ?? []).map(fromWireSymbol),
+        expandSelection: (start: number, end: number) =>
+          module.expand_selection?.(handle, start, end) ?? { start, end },
+        proseRanges: (classes: ProseClass[] = []) => module.prose_ranges?.(handle, classes) ?? [],
         cancel: () => module.cancel(handle),
         free: () => module.free_session(handle),
       };
     },
+    commentSyntax: (): CommentSyntax => fromWireCommentSyntax(module.comment_syntax?.()),
+    toggleComments: (text: string, start: number, end: number) =>
+      module.comment_ranges?.(text, start, end) ?? [],
+    info: (): PluginInfo => {
+      const info = module.plugin_info?.();
+      return {
+        languageId: plugin.languageId,
+        commentSyntax: fromWireCommentSyntax(info?.comments ?? module.comment_syntax?.()),
+        brackets: info?.brackets ?? [],
+        autoClosePairs: info?.auto_close_pairs ?? [],
+      };
+    },
     dispose: () => {
       // No-op for now, plugins are cached
     },
@@ -406,20 +706,43 @@ export function setConfig(newConfig: Partial<ArboriumConfig>): void {
   globalConfig = { ...globalConfig, ...newConfig };
 }
 
-/** Check if a language is available */
+/**
+ * Resolve a language id, alias or file extension ("javascript", "js", ".mjs")
+ * to the id of an available plugin, or null if there is none.
+ */
+export async function resolveLanguage(name: string, configOverrides?: ArboriumConfig): Promise<string | null> {
+  const config = getConfig(configOverrides);
+  await ensureManifests(config);
+  return resolveLanguageName(name);
+}
+
+/** Check if a language is available, by id, alias or file extension */
 export async function isLanguageAvailable(language: string, configOverrides?: ArboriumConfig): Promise<boolean> {
+  return (await resolveLanguage(language, configOverrides)) !== null;
+}
+
+/**
+ * Start loading the plugins for languages that are likely to be needed soon
+ * (and the host), so highlighting them later doesn't wait on the network.
+ *
+ * Names are resolved like everywhere else; unknown languages and failed
+ * loads are ignored, and are reported again when the language is used.
+ */
+export async function prefetch(languages: Iterable<string>, configOverrides?: ArboriumConfig): Promise<void> {
   const config = getConfig(configOverrides);
-  await ensureLocalManifest(config);
-  return (
-    knownLanguages.has(language) ||
-    (localManifest?.entries.some((e) => e.language === language) ?? false)
-  );
+  await Promise.all([
+    loadHost(config),
+    ...Array.from(languages, (language) => loadGrammarPlugin(language, config).catch(() => null)),
+  ]);
 }
 
 /** Get list of available languages */
 export async function getAvailableLanguages(configOverrides?: ArboriumConfig): Promise<string[]> {
   const config = getConfig(configOverrides);
-  await ensureLocalManifest(config);
+  await ensureManifests(config);
+  if (pluginIndex) {
+    return pluginIndex.plugins.map((e) => e.language);
+  }
   // In dev mode, use local manifest if available
   if (localManifest) {
     return localManifest.entries.map((e) => e.language);
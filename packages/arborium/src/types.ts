@@ -26,6 +26,98 @@ export interface Injection {
   includeChildren: boolean;
 }
 
+/** A node captured by a query */
+export interface QueryCapture {
+  /** The capture name, without the `@` */
+  name: string;
+  /** The node's kind, like `function_item` */
+  kind: string;
+  /** UTF-16 code unit index where the node starts (inclusive) */
+  start: number;
+  /** UTF-16 code unit index where the node ends (exclusive) */
+  end: number;
+}
+
+/** A match of a tree-sitter query */
+export interface QueryMatch {
+  /** Index of the matching pattern in the query */
+  pattern: number;
+  captures: QueryCapture[];
+}
+
+/** Lines that can be folded, 0-based and inclusive */
+export interface FoldingRange {
+  /** The line the fold starts on, which stays visible when folded */
+  startRow: number;
+  /** The last line hidden by the fold */
+  endRow: number;
+  kind: "region" | "comment";
+}
+
+/** A definition in the document outline */
+export interface DocumentSymbol {
+  name: string;
+  /** Like `function` or `class`, named after LSP's symbol kinds */
+  kind: string;
+  /** UTF-16 code unit index where the definition starts (inclusive) */
+  start: number;
+  /** UTF-16 code unit index where the definition ends (exclusive) */
+  end: number;
+  /** UTF-16 code unit index where the name starts (inclusive) */
+  nameStart: number;
+  /** UTF-16 code unit index where the name ends (exclusive) */
+  nameEnd: number;
+  /** Definitions nested in this one, like a class's methods */
+  children: DocumentSymbol[];
+}
+
+/** A language's comment delimiters */
+export interface CommentSyntax {
+  /** Starts a comment running to the end of the line, like `//` */
+  line?: string;
+  /** Start and end of a block comment, like `["/*", "*/"]` */
+  block?: [string, string];
+}
+
+/** A change that toggles comments: replace `start..end` with `replacement` */
+export interface CommentEdit {
+  /** UTF-16 code unit index where the replaced text starts (inclusive) */
+  start: number;
+  /** UTF-16 code unit index where the replaced text ends (exclusive) */
+  end: number;
+  replacement: string;
+}
+
+/** A kind of prose, as highlighting classifies it */
+export type ProseClass = "comment" | "string" | "markup";
+
+/** A run of prose in a session's text: a comment, string literal or markup text */
+export interface ProseRange {
+  /** UTF-16 code unit index where the text starts (inclusive) */
+  start: number;
+  /** UTF-16 code unit index where the text ends (exclusive) */
+  end: number;
+  class: ProseClass;
+}
+
+/** An opening delimiter and the closing one that goes with it, like `(` and `)` */
+export interface BracketPair {
+  open: string;
+  close: string;
+}
+
+/** What editors need to know about a language, from its `arborium.yaml` */
+export interface PluginInfo {
+  /** The language identifier */
+  languageId: string;
+  /** Comment delimiters */
+  commentSyntax: CommentSyntax;
+  /** Brackets to match and highlight in pairs */
+  brackets: BracketPair[];
+  /** Pairs to close automatically when the opening one is typed: the brackets, then others like quotes */
+  autoClosePairs: BracketPair[];
+}
+
 /** Result of parsing source code */
 export interface ParseResult {
   spans: Span[];
@@ -40,7 +132,9 @@ export interface ParseResult {
  * editors where text changes frequently.
  *
  * Usage pattern:
- *   - Call `setText(newText)` whenever the text changes.
+ *   - Call `setText(newText)` whenever the text changes, or `splice(start,
+ *     end, replacement)` with just the change, which only sends the change to
+ *     the plugin and lets it reparse incrementally.
  *   - Then call `parse()` to parse the current text and get results.
  *
  * Example:
@@ -49,7 +143,7 @@ export interface ParseResult {
  * session.setText("let x = 1;");
  * let result = session.parse();
  * // ... user edits text ...
- * session.setText("let x = 42;");
+ * session.splice(8, 9, "42"); // "let x = 42;"
  * result = session.parse();
  * session.free();
  * ```
@@ -57,8 +151,33 @@ export interface ParseResult {
 export interface Session {
   /** Set the text to parse */
   setText(text: string): void;
+  /**
+   * Replace the text between `start` and `end`, JavaScript string offsets
+   * into the current text, with `replacement`
+   */
+  splice(start: number, end: number, replacement: string): void;
   /** Parse the current text and return spans/injections */
   parse(): ParseResult;
+  /**
+   * Run a tree-sitter query over the current text's syntax tree, for
+   * structural search. Throws if the query doesn't compile.
+   */
+  query(source: string): QueryMatch[];
+  /** Lines of the current text that can be folded, ordered by start line */
+  foldingRanges(): FoldingRange[];
+  /** Definitions in the current text, for an outline */
+  symbols(): DocumentSymbol[];
+  /**
+   * The next larger syntax node around the selection `start..end`, for an
+   * "expand selection" command. Calling it again on the result keeps growing
+   * the selection, up to the whole text.
+   */
+  expandSelection(start: number, end: number): { start: number; end: number };
+  /**
+   * Ranges of the current text holding prose of the given classes (all of
+   * them by default), for spell checkers. Empty for older plugins.
+   */
+  proseRanges(classes?: ProseClass[]): ProseRange[];
   /** Cancel any in-progress parsing */
   cancel(): void;
   /**
@@ -80,6 +199,15 @@ export interface Grammar {
   parse(source: string): ParseResult;
   /** Create a session for incremental parsing */
   createSession(): Session;
+  /** The language's comment delimiters (none for older plugins) */
+  commentSyntax(): CommentSyntax;
+  /**
+   * Edits that comment out the lines `start..end` of `text` touches, or
+   * uncomment them if they all are already. Apply them from last to first.
+   */
+  toggleComments(text: string, start: number, end: number): CommentEdit[];
+  /** Comment delimiters and bracket pairs (no pairs for older plugins) */
+  info(): PluginInfo;
   /** Dispose of resources */
   dispose(): void;
 }
@@ -94,6 +222,40 @@ export interface Highlight {
   parentTag?: string;
 }
 
+/** A file of a plugin in a `PluginIndex` */
+export interface PluginFile {
+  /** Path relative to the index, with `/` separators */
+  path: string;
+  /** Size in bytes */
+  size: number;
+  /** SHA-256 of the file, in lowercase hex */
+  sha256: string;
+}
+
+/** A plugin in a `PluginIndex` */
+export interface PluginIndexEntry {
+  /** The language id, e.g. "javascript" */
+  language: string;
+  /** Other names of the language, file extensions included (e.g. "js", "mjs") */
+  aliases: string[];
+  /** The plugin's JavaScript glue */
+  js: PluginFile;
+  /** The plugin's WebAssembly module */
+  wasm: PluginFile;
+  /** The plugin's queries, for plugins that don't embed them in their WASM */
+  queries?: PluginFile;
+}
+
+/** The plugins in a directory of built plugins (its plugins.json) */
+export interface PluginIndex {
+  /** Wire protocol version the plugins were built with */
+  wire_version: number;
+  /** arborium version the plugins were built from */
+  version: string;
+  /** The plugins, sorted by language */
+  plugins: PluginIndexEntry[];
+}
+
 type MaybePromise<T> = T | Promise<T>;
 
 interface ResolveArgs {
@@ -119,6 +281,11 @@ export interface ArboriumConfig {
   version?: string;
   /** URL to plugins.json manifest - overrides bundled manifest (for local testing) */
   pluginsUrl?: string;
+  /**
+   * URL to a `PluginIndex` (the plugins.json written by `cargo xtask build --output`).
+   * Plugins are then loaded from next to it, and their WASM is checked against its hashes.
+   */
+  indexUrl?: string;
   /** Base URL for the Rust host module (for local testing) */
   hostUrl?: string;
   /** Custom grammar resolution for JS */
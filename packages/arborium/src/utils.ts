@@ -1,4 +1,4 @@
-import type { Span } from "./types.js";
+import type { PluginIndex, PluginIndexEntry, Span } from "./types.js";
 
 // Shared TextEncoder instance
 const encoder = new TextEncoder();
@@ -150,6 +150,28 @@ function getTagForCapture(capture: string): string | null {
   return null;
 }
 
+/**
+ * Normalize a language id, alias or file extension for lookup:
+ * lowercase, without a leading `.`.
+ */
+export function normalizeLanguageName(name: string): string {
+  const lower = name.toLowerCase();
+  return lower.startsWith(".") ? lower.slice(1) : lower;
+}
+
+/**
+ * Find the plugin for a language id, alias or file extension in a plugin index.
+ *
+ * Language ids take precedence over aliases.
+ */
+export function findIndexEntry(index: PluginIndex, name: string): PluginIndexEntry | undefined {
+  const key = normalizeLanguageName(name);
+  return (
+    index.plugins.find((e) => e.language.toLowerCase() === key) ??
+    index.plugins.find((e) => e.aliases.some((a) => a.toLowerCase() === key))
+  );
+}
+
 /** Escape HTML special characters */
 export function escapeHtml(text: string): string {
   return text
@@ -0,0 +1,248 @@
+#!/usr/bin/env node
+// End-to-end tests for grammar plugins
+// Loads each built plugin in a headless browser, runs the handshake, parses the
+// grammar's sample and compares the spans and injections with the native
+// highlighter's (`arborium --parse`) for the same file
+
+import { chromium } from 'playwright';
+import { createServer } from 'http';
+import { existsSync, readdirSync, readFileSync } from 'fs';
+import { join, dirname, extname, resolve, sep } from 'path';
+import { fileURLToPath } from 'url';
+import { execFileSync } from 'child_process';
+
+const __dirname = dirname(fileURLToPath(import.meta.url));
+const REPO_ROOT = resolve(__dirname, '../..');
+
+// Config
+const PLUGINS_DIR = resolve(REPO_ROOT, process.env.PLUGINS_DIR || 'dist/plugins');
+const ARBORIUM_BIN = resolve(REPO_ROOT, process.env.ARBORIUM_BIN || 'crates/arborium-cli/target/release/arborium');
+// Note: Keep parallel pages low to avoid Chromium WASM OOM crashes
+const PARALLEL_PAGES = parseInt(process.env.PARALLEL_PAGES || '2', 10);
+// How many differing spans to print per language
+const MAX_REPORTED = 10;
+
+const CONTENT_TYPES = {
+    '.html': 'text/html; charset=utf-8',
+    '.js': 'application/javascript; charset=utf-8',
+    '.json': 'application/json; charset=utf-8',
+    '.wasm': 'application/wasm',
+};
+
+// Serve the plugin directory, so plugins load the way web hosts load them
+function servePlugins(dir) {
+    const server = createServer((req, res) => {
+        const path = decodeURIComponent(new URL(req.url, 'http://localhost').pathname);
+        if (path === '/') {
+            res.writeHead(200, { 'Content-Type': CONTENT_TYPES['.html'] });
+            res.end('<!doctype html><title>arborium plugin tests</title>');
+            return;
+        }
+        const file = resolve(dir, `.${path}`);
+        if (!file.startsWith(dir + sep) || !existsSync(file)) {
+            res.writeHead(404);
+            res.end('Not Found');
+            return;
+        }
+        res.writeHead(200, { 'Content-Type': CONTENT_TYPES[extname(file)] || 'application/octet-stream' });
+        res.end(readFileSync(file));
+    });
+    return new Promise(resolve => {
+        server.listen(0, '127.0.0.1', () => resolve(server));
+    });
+}
+
+// The sample the native highlighter and the plugin both parse:
+// def/sample.<ext>, or the first file in def/samples/
+function findSample(language) {
+    const langsDir = join(REPO_ROOT, 'langs');
+    for (const group of readdirSync(langsDir).filter(g => g.startsWith('group-'))) {
+        const defDir = join(langsDir, group, language, 'def');
+        if (!existsSync(defDir)) continue;
+
+        const sample = readdirSync(defDir).find(f => f.startsWith('sample.'));
+        if (sample) return join(defDir, sample);
+
+        const samplesDir = join(defDir, 'samples');
+        if (existsSync(samplesDir)) {
+            const samples = readdirSync(samplesDir).sort();
+            if (samples.length > 0) return join(samplesDir, samples[0]);
+        }
+        return null;
+    }
+    return null;
+}
+
+function nativeParse(language, samplePath) {
+    const output = execFileSync(ARBORIUM_BIN, ['--parse', '--no-query-cache', '--lang', language, samplePath], {
+        encoding: 'utf-8',
+        maxBuffer: 256 * 1024 * 1024,
+    });
+    return JSON.parse(output);
+}
+
+// Runs in the browser: load the plugin like the loader does and parse `source`
+async function pluginParse({ language, source }) {
+    const module = await import(`/${language}/grammar.js`);
+    await module.default({ module_or_path: fetch(`/${language}/grammar_bg.wasm`) });
+
+    if (module.language_id() !== language) {
+        throw new Error(`plugin reports language '${module.language_id()}'`);
+    }
+    if (module.external_queries?.()) {
+        const queries = await (await fetch(`/${language}/queries.json`)).json();
+        module.set_queries(queries.highlights, queries.injections, queries.locals);
+    }
+    if (!module.handshake) {
+        throw new Error('plugin has no handshake (built by an older xtask?)');
+    }
+    module.handshake();
+
+    const session = module.create_session();
+    try {
+        module.set_text(session, source);
+        return module.parse(session);
+    } finally {
+        module.free_session(session);
+    }
+}
+
+// Spans and injections as sorted strings, since their order isn't part of the contract
+function normalize(result) {
+    const spans = result.spans.map(s => `${s.start}..${s.end} @${s.capture}`).sort();
+    const injections = result.injections
+        .map(i => `${i.start}..${i.end} ${i.language}${i.include_children ? ' (children)' : ''}`)
+        .sort();
+    return { spans, injections };
+}
+
+// Entries only in `a`, counting duplicates
+function difference(a, b) {
+    const counts = new Map();
+    for (const x of b) counts.set(x, (counts.get(x) || 0) + 1);
+    return a.filter(x => {
+        const n = counts.get(x) || 0;
+        if (n === 0) return true;
+        counts.set(x, n - 1);
+        return false;
+    });
+}
+
+function compare(native, plugin) {
+    const n = normalize(native);
+    const p = normalize(plugin);
+    const diffs = [];
+    for (const kind of ['spans', 'injections']) {
+        for (const x of difference(n[kind], p[kind])) diffs.push(`  native only: ${x}`);
+        for (const x of difference(p[kind], n[kind])) diffs.push(`  plugin only: ${x}`);
+    }
+    return diffs;
+}
+
+async function processLanguage(context, baseUrl, language) {
+    const samplePath = findSample(language);
+    if (!samplePath) {
+        return { lang: language, status: 'skip', message: 'no sample' };
+    }
+
+    const page = await context.newPage();
+    try {
+        await page.goto(baseUrl);
+        const source = readFileSync(samplePath, 'utf-8');
+        const native = nativeParse(language, samplePath);
+        const plugin = await page.evaluate(pluginParse, { language, source });
+        const diffs = compare(native, plugin);
+        return diffs.length === 0
+            ? { lang: language, status: 'pass', spans: native.spans.length }
+            : { lang: language, status: 'fail', diffs };
+    } catch (e) {
+        return { lang: language, status: 'error', message: e.message };
+    } finally {
+        await page.close();
+    }
+}
+
+async function main() {
+    const specificLangs = process.argv.slice(2).filter(a => !a.startsWith('--'));
+
+    const indexPath = join(PLUGINS_DIR, 'plugins.json');
+    if (!existsSync(indexPath)) {
+        console.error(`No plugin index at ${indexPath}`);
+        console.error('Build the plugins first: cargo xtask build -o dist/plugins');
+        process.exit(1);
+    }
+    if (!existsSync(ARBORIUM_BIN)) {
+        console.error(`No arborium binary at ${ARBORIUM_BIN}`);
+        console.error('Build it first: cargo build --release --manifest-path crates/arborium-cli/Cargo.toml');
+        process.exit(1);
+    }
+
+    let languages = JSON.parse(readFileSync(indexPath, 'utf-8')).plugins.map(p => p.language);
+    if (specificLangs.length > 0) {
+        const unknown = specificLangs.filter(l => !languages.includes(l));
+        if (unknown.length > 0) {
+            console.warn(`Warning: No plugins for: ${unknown.join(', ')}`);
+        }
+        languages = languages.filter(l => specificLangs.includes(l));
+    }
+
+    const server = await servePlugins(PLUGINS_DIR);
+    const baseUrl = `http://127.0.0.1:${server.address().port}/`;
+
+    console.log('Launching browser...');
+    const browser = await chromium.launch({ headless: true });
+    const contexts = await Promise.all(
+        Array(PARALLEL_PAGES).fill(null).map(() => browser.newContext())
+    );
+
+    console.log(`Testing ${languages.length} plugins from ${PLUGINS_DIR}...`);
+    const results = { passed: [], failed: [], skipped: [], errors: [] };
+
+    for (let i = 0; i < languages.length; i += PARALLEL_PAGES) {
+        const batch = languages.slice(i, i + PARALLEL_PAGES);
+        const batchResults = await Promise.all(
+            batch.map((lang, j) => processLanguage(contexts[j], baseUrl, lang))
+        );
+
+        for (const { lang, status, spans, diffs, message } of batchResults) {
+            switch (status) {
+                case 'pass':
+                    process.stdout.write(`  ${lang}... PASS (${spans} spans)\n`);
+                    results.passed.push(lang);
+                    break;
+                case 'fail':
+                    process.stdout.write(`  ${lang}... FAIL (${diffs.length} differences)\n`);
+                    for (const diff of diffs.slice(0, MAX_REPORTED)) console.log(`  ${diff}`);
+                    if (diffs.length > MAX_REPORTED) console.log(`    ... and ${diffs.length - MAX_REPORTED} more`);
+                    results.failed.push(lang);
+                    break;
+                case 'skip':
+                    process.stdout.write(`  ${lang}... SKIP (${message})\n`);
+                    results.skipped.push(lang);
+                    break;
+                case 'error':
+                    process.stdout.write(`  ${lang}... ERROR: ${message}\n`);
+                    results.errors.push({ lang, message });
+                    break;
+            }
+        }
+    }
+
+    await browser.close();
+    server.close();
+
+    console.log('\n=== Summary ===');
+    console.log(`Passed: ${results.passed.length}`);
+    if (results.skipped.length > 0) console.log(`Skipped: ${results.skipped.length} (${results.skipped.join(', ')})`);
+    if (results.failed.length > 0) console.log(`Failed: ${results.failed.length} (${results.failed.join(', ')})`);
+    if (results.errors.length > 0) console.log(`Errors: ${results.errors.length} (${results.errors.map(e => e.lang).join(', ')})`);
+
+    if (results.failed.length > 0 || results.errors.length > 0) {
+        process.exit(1);
+    }
+}
+
+main().catch(e => {
+    console.error(e);
+    process.exit(1);
+});
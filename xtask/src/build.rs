@@ -508,6 +508,34 @@ pub struct PluginManifest {
     pub entries: Vec<PluginManifestEntry>,
 }
 
+/// `plugins.json` in a plugin output directory.
+///
+/// Mirrors `arborium_wire::PluginIndex`, which xtask can't depend on since
+/// its Cargo.toml is generated; keep the two in sync.
+#[derive(Debug, Clone, facet::Facet)]
+pub struct PluginIndex {
+    pub wire_version: u32,
+    pub version: String,
+    pub plugins: Vec<PluginIndexEntry>,
+}
+
+#[derive(Debug, Clone, facet::Facet)]
+pub struct PluginIndexEntry {
+    pub language: String,
+    pub aliases: Vec<String>,
+    pub js: PluginFile,
+    pub wasm: PluginFile,
+    #[facet(default, skip_serializing_if = Option::is_none)]
+    pub queries: Option<PluginFile>,
+}
+
+#[derive(Debug, Clone, facet::Facet)]
+pub struct PluginFile {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
 pub fn build_plugins(repo_root: &Utf8Path, options: &BuildOptions) -> Result<()> {
     let crates_dir = repo_root.join("crates");
     let version = version_store::read_version(repo_root)?;
@@ -644,6 +672,18 @@ pub fn build_plugins(repo_root: &Utf8Path, options: &BuildOptions) -> Result<()>
         manifest_path.cyan()
     );
 
+    // Compress and index the output directory, so web hosts serving it can
+    // discover the plugins and send them compressed
+    if let Some(output_dir) = &options.output_dir {
+        let output_dir = if output_dir.is_absolute() {
+            output_dir.clone()
+        } else {
+            repo_root.join(output_dir)
+        };
+        package_plugins(&output_dir)?;
+        write_plugin_index(repo_root, &output_dir)?;
+    }
+
     // Write TypeScript manifest to packages/arborium/src/plugins-manifest.ts (bundled)
     // This is a simplified manifest - just a list of language names plus version
     let mut sorted_grammars = grammars.clone();
@@ -656,6 +696,7 @@ pub fn build_plugins(repo_root: &Utf8Path, options: &BuildOptions) -> Result<()>
     let ts_template = PluginsManifestTsTemplate {
         version: &version,
         languages: &sorted_grammars,
+        aliases: &language_aliases(&registry, &sorted_grammars),
         highlights: &highlights.defs,
     };
     let ts_content = ts_template
@@ -829,7 +870,7 @@ fn build_single_plugin(
 ) -> Result<(u64, u64, u64)> {
     printer.print_line(grammar, "Building...", false);
 
-    let (crate_state, _) = locate_grammar(registry, grammar).ok_or_else(|| {
+    let (crate_state, grammar_config) = locate_grammar(registry, grammar).ok_or_else(|| {
         report(format!(
             "grammar `{}` not found in registry (generate components must be enabled)",
             grammar
@@ -994,6 +1035,18 @@ fn build_single_plugin(
 
     std::fs::copy(&src_js, &dest_js)?;
 
+    // Plugins with split queries load them from queries.json at runtime
+    let dest_queries = plugin_output.join("queries.json");
+    if grammar_config.split_queries() {
+        let queries = plugin_queries(registry, crate_state)?;
+        fs_err::write(
+            &dest_queries,
+            facet_json::to_string_pretty(&queries).expect("queries serialization failed"),
+        )?;
+    } else if dest_queries.exists() {
+        fs_err::remove_file(&dest_queries)?;
+    }
+
     // Check WASM browser compatibility on final WASM file
     println!("  {} Checking WASM browser compatibility...", "●".yellow());
     check_wasm_browser_compatibility(&dest_wasm)?;
@@ -1021,6 +1074,65 @@ fn build_single_plugin(
     Ok((size_bytes, size_gzip, size_brotli))
 }
 
+/// `queries.json` of a plugin built with `split_queries`: the queries its
+/// grammar crate would otherwise embed.
+#[derive(Debug, Clone, facet::Facet)]
+pub(crate) struct PluginQueries {
+    pub(crate) highlights: String,
+    pub(crate) injections: String,
+    pub(crate) locals: String,
+}
+
+fn plugin_queries(
+    registry: &CrateRegistry,
+    crate_state: &crate::types::CrateState,
+) -> Result<PluginQueries> {
+    Ok(PluginQueries {
+        highlights: highlights_query(registry, crate_state)?,
+        injections: read_query(crate_state, "injections.scm")?,
+        locals: read_query(crate_state, "locals.scm")?,
+    })
+}
+
+/// A grammar crate's `HIGHLIGHTS_QUERY`: the highlights of the crates it
+/// prepends, each followed by a newline, then its own.
+fn highlights_query(
+    registry: &CrateRegistry,
+    crate_state: &crate::types::CrateState,
+) -> Result<String> {
+    let prepends = crate_state
+        .config
+        .as_ref()
+        .and_then(|config| config.grammars.first())
+        .and_then(|grammar| grammar.queries.as_ref())
+        .and_then(|queries| queries.highlights.as_ref())
+        .and_then(|highlights| highlights.prepend.as_ref());
+
+    let mut query = String::new();
+    for prepend in prepends.into_iter().flatten() {
+        let prepended = registry.crates.get(&prepend.crate_name).ok_or_else(|| {
+            report(format!(
+                "{} prepends highlights from unknown crate `{}`",
+                crate_state.name, prepend.crate_name
+            ))
+        })?;
+        query.push_str(&highlights_query(registry, prepended)?);
+        query.push('\n');
+    }
+    query.push_str(&read_query(crate_state, "highlights.scm")?);
+    Ok(query)
+}
+
+/// A query from a grammar's `def/queries/`, empty if it has none.
+fn read_query(crate_state: &crate::types::CrateState, file: &str) -> Result<String> {
+    let path = crate_state.def_path.join("queries").join(file);
+    if path.exists() {
+        Ok(fs_err::read_to_string(&path)?)
+    } else {
+        Ok(String::new())
+    }
+}
+
 /// Count lines of C code in parser.c (and scanner.c if present)
 pub fn count_c_lines(crate_path: &Utf8Path) -> u64 {
     let mut total = 0;
@@ -1045,10 +1157,6 @@ pub fn count_c_lines(crate_path: &Utf8Path) -> u64 {
 }
 
 pub fn calculate_wasm_sizes(wasm_path: &Utf8Path) -> Result<(u64, u64, u64)> {
-    use flate2::Compression;
-    use flate2::write::GzEncoder;
-    use std::io::Write;
-
     // Uncompressed size
     let metadata = fs_err::metadata(wasm_path)?;
     let size_bytes = metadata.len();
@@ -1056,10 +1164,22 @@ pub fn calculate_wasm_sizes(wasm_path: &Utf8Path) -> Result<(u64, u64, u64)> {
     // Read file into memory
     let wasm_data = fs_err::read(wasm_path)?;
 
+    let (gzip, brotli) = compress(&wasm_data)?;
+
+    Ok((size_bytes, gzip.len() as u64, brotli.len() as u64))
+}
+
+/// Compress `data` the way plugins are served: gzip and brotli, both at
+/// their best compression.
+fn compress(data: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
     // Gzip compression
     let mut gz_encoder = GzEncoder::new(Vec::new(), Compression::best());
-    gz_encoder.write_all(&wasm_data)?;
-    let size_gzip = gz_encoder.finish()?.len() as u64;
+    gz_encoder.write_all(data)?;
+    let gzip_output = gz_encoder.finish()?;
 
     // Brotli compression (quality 11 = max)
     let mut brotli_output = Vec::new();
@@ -1069,11 +1189,10 @@ pub fn calculate_wasm_sizes(wasm_path: &Utf8Path) -> Result<(u64, u64, u64)> {
         11,   // quality (max)
         22,   // lg_window_size
     );
-    brotli_encoder.write_all(&wasm_data)?;
+    brotli_encoder.write_all(data)?;
     drop(brotli_encoder);
-    let size_brotli = brotli_output.len() as u64;
 
-    Ok((size_bytes, size_gzip, size_brotli))
+    Ok((gzip_output, brotli_output))
 }
 
 pub fn locate_grammar<'a>(
@@ -1097,9 +1216,32 @@ pub fn locate_grammar<'a>(
 struct PluginsManifestTsTemplate<'a> {
     version: &'a str,
     languages: &'a [String],
+    /// `(alias, language)`, sorted by alias.
+    aliases: &'a [(String, String)],
     highlights: &'a [NamedHighlight],
 }
 
+/// The aliases of `languages`, which include their file extensions, as
+/// lowercase `(alias, language)` pairs sorted by alias. An alias claimed by
+/// several languages goes to the one that sorts first.
+fn language_aliases(registry: &CrateRegistry, languages: &[String]) -> Vec<(String, String)> {
+    let mut aliases: Vec<(String, String)> = languages
+        .iter()
+        .filter_map(|language| locate_grammar(registry, language))
+        .flat_map(|(_, grammar)| {
+            grammar
+                .aliases
+                .iter()
+                .flatten()
+                .map(move |alias| (alias.to_lowercase(), grammar.id().to_string()))
+        })
+        .filter(|(alias, _)| !languages.contains(alias))
+        .collect();
+    aliases.sort();
+    aliases.dedup_by(|a, b| a.0 == b.0);
+    aliases
+}
+
 /// Generate the plugins-manifest.ts file for the npm package.
 /// This uses ALL grammars with generate_component enabled, not just locally built ones.
 /// The manifest is simplified: just a list of language names plus the version.
@@ -1141,6 +1283,7 @@ pub fn generate_plugins_manifest(repo_root: &Utf8Path, crates_dir: &Utf8Path) ->
     let ts_template = PluginsManifestTsTemplate {
         version: &version,
         languages: &languages,
+        aliases: &language_aliases(&registry, &languages),
         highlights: &highlights.defs,
     };
     let ts_content = ts_template
@@ -1159,6 +1302,151 @@ pub fn generate_plugins_manifest(repo_root: &Utf8Path, crates_dir: &Utf8Path) ->
     Ok(())
 }
 
+/// Write `plugins.json` for a directory of built plugins, one per
+/// `<language>/` subdirectory, as laid out by `cargo xtask build --output`.
+pub fn write_plugin_index(repo_root: &Utf8Path, output_dir: &Utf8Path) -> Result<()> {
+    let crates_dir = repo_root.join("crates");
+    let registry = CrateRegistry::load(&crates_dir)
+        .map_err(|e| report(format!("failed to load crate registry: {}", e)))?;
+
+    let languages = plugin_dirs(output_dir)?;
+
+    let mut plugins = Vec::with_capacity(languages.len());
+    for language in languages {
+        let (_, grammar) = locate_grammar(&registry, &language).ok_or_else(|| {
+            report(format!("{}/{} is not a known grammar", output_dir, language))
+        })?;
+        let file = |name: &str| -> Result<PluginFile> {
+            let bytes = fs_err::read(output_dir.join(&language).join(name))?;
+            Ok(PluginFile {
+                path: format!("{}/{}", language, name),
+                size: bytes.len() as u64,
+                sha256: sha256_hex(&bytes),
+            })
+        };
+        let has_queries = output_dir.join(&language).join("queries.json").is_file();
+        plugins.push(PluginIndexEntry {
+            aliases: grammar.aliases.clone().unwrap_or_default(),
+            js: file("grammar.js")?,
+            wasm: file("grammar_bg.wasm")?,
+            queries: has_queries.then(|| file("queries.json")).transpose()?,
+            language,
+        });
+    }
+
+    let index = PluginIndex {
+        wire_version: read_wire_version(repo_root)?,
+        version: version_store::read_version(repo_root)?,
+        plugins,
+    };
+    let index_path = output_dir.join("plugins.json");
+    fs_err::write(
+        &index_path,
+        facet_json::to_string_pretty(&index).expect("plugin index serialization failed"),
+    )?;
+    println!(
+        "{} Wrote plugin index {} ({} plugins)",
+        "✓".green(),
+        index_path.cyan(),
+        index.plugins.len()
+    );
+    Ok(())
+}
+
+/// The `<language>` subdirectories of a plugin output directory that hold a
+/// built plugin, sorted.
+fn plugin_dirs(output_dir: &Utf8Path) -> Result<Vec<String>> {
+    let mut languages: Vec<String> = fs_err::read_dir(output_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("grammar_bg.wasm").is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    languages.sort();
+    Ok(languages)
+}
+
+/// Write brotli and gzip copies of each plugin's WASM in a plugin output
+/// directory (`<language>/grammar_bg.wasm.br` and `.wasm.gz`), for servers
+/// that serve precompressed files by `Accept-Encoding`, and print a size
+/// report, largest plugins first.
+pub fn package_plugins(output_dir: &Utf8Path) -> Result<()> {
+    let languages = plugin_dirs(output_dir)?;
+    println!(
+        "{} Compressing {} plugin(s) in {}",
+        "●".cyan(),
+        languages.len(),
+        output_dir.cyan()
+    );
+
+    // Brotli at quality 11 takes seconds per megabyte, so compress in parallel
+    let mut sizes = languages
+        .par_iter()
+        .map(|language| {
+            let wasm_path = output_dir.join(language).join("grammar_bg.wasm");
+            let package = || -> Result<(u64, u64, u64)> {
+                let wasm = fs_err::read(&wasm_path)?;
+                let (gzip, brotli) = compress(&wasm)?;
+                fs_err::write(format!("{}.gz", wasm_path), &gzip)?;
+                fs_err::write(format!("{}.br", wasm_path), &brotli)?;
+                Ok((wasm.len() as u64, gzip.len() as u64, brotli.len() as u64))
+            };
+            package()
+                .map(|(raw, gzip, brotli)| (language.as_str(), raw, gzip, brotli))
+                .map_err(|e| format!("{}: {}", wasm_path, e))
+        })
+        .collect::<std::result::Result<Vec<_>, String>>()
+        .map_err(report)?;
+
+    sizes.sort_by_key(|&(_, _, _, brotli)| std::cmp::Reverse(brotli));
+    let format_size = crate::deploy_website::format_size;
+    println!();
+    println!(
+        "  {:<24} {:>10} {:>10} {:>10}",
+        "plugin", "wasm", "gzip", "brotli"
+    );
+    for (language, raw, gzip, brotli) in &sizes {
+        println!(
+            "  {:<24} {:>10} {:>10} {:>10}",
+            language,
+            format_size(*raw),
+            format_size(*gzip),
+            format_size(*brotli)
+        );
+    }
+    let (raw, gzip, brotli) = sizes.iter().fold((0, 0, 0), |total, size| {
+        (total.0 + size.1, total.1 + size.2, total.2 + size.3)
+    });
+    println!(
+        "  {:<24} {:>10} {:>10} {:>10}",
+        "total",
+        format_size(raw),
+        format_size(gzip),
+        format_size(brotli)
+    );
+    println!();
+
+    Ok(())
+}
+
+/// The `WIRE_VERSION` declared in arborium-wire.
+fn read_wire_version(repo_root: &Utf8Path) -> Result<u32> {
+    let path = repo_root.join("crates/arborium-wire/src/lib.rs");
+    let source = fs_err::read_to_string(&path)?;
+    source
+        .lines()
+        .find_map(|line| line.strip_prefix("pub const WIRE_VERSION: u32 = "))
+        .and_then(|rest| rest.trim_end_matches(';').trim().parse().ok())
+        .ok_or_else(|| report(format!("no WIRE_VERSION found in {}", path)))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
 fn build_manifest(
     repo_root: &Utf8Path,
     registry: &CrateRegistry,
@@ -514,6 +514,9 @@ echo "Version: $VERSION (release: $IS_RELEASE)""#,
                         ),
                         Step::run("Build arborium-rustdoc", "cargo build --manifest-path crates/arborium-rustdoc/Cargo.toml --verbose"),
                         Step::run("Test arborium-rustdoc", "cargo test --manifest-path crates/arborium-rustdoc/Cargo.toml --verbose"),
+                        Step::run("Test arborium-http", "cargo test --manifest-path crates/arborium-http/Cargo.toml --verbose"),
+                        Step::run("Test arborium-test-support", "cargo test --manifest-path crates/arborium-test-support/Cargo.toml --verbose"),
+                        Step::run("Test arborium-plugin-runtime", "cargo test --manifest-path crates/arborium-plugin-runtime/Cargo.toml --features integration-tests --verbose"),
                         // TypeScript tests for the npm package
                         Step::run("Enable pnpm via corepack", "corepack enable pnpm"),
                         Step::run("Install npm dependencies", "cd packages/arborium && pnpm install --frozen-lockfile"),
@@ -558,10 +561,34 @@ echo "Version: $VERSION (release: $IS_RELEASE)""#,
                     .chain([
                         Step::run("Run Clippy", "cargo clippy --manifest-path crates/arborium/Cargo.toml --all-targets -- -D warnings"),
                         Step::run("Run Clippy on arborium-rustdoc", "cargo clippy --manifest-path crates/arborium-rustdoc/Cargo.toml --all-targets -- -D warnings"),
+                        Step::run("Run Clippy on arborium-http", "cargo clippy --manifest-path crates/arborium-http/Cargo.toml --all-targets -- -D warnings"),
+                        Step::run("Run Clippy on arborium-test-support", "cargo clippy --manifest-path crates/arborium-test-support/Cargo.toml --all-targets -- -D warnings"),
                     ])
             ),
     );
 
+    // Parse throughput against perf-baseline.json
+    // Only warns: shared runners are too noisy to fail a PR on
+    jobs.insert(
+        "perf".into(),
+        Job::new(runners::UBUNTU_32)
+            .name("Performance")
+            .container(CONTAINER)
+            .needs(["generate"])
+            .steps(
+                [checkout()]
+                    .into_iter()
+                    .chain(download_generate_output())
+                    .chain([
+                        rust_cache(),
+                        Step::run(
+                            "Compare parse throughput",
+                            "./xtask/target/release/xtask perf --warn-only",
+                        ),
+                    ]),
+            ),
+    );
+
     // Documentation
     // Note: no root workspace, so we target crates/arborium directly
     jobs.insert(
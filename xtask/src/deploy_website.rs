@@ -298,7 +298,7 @@ fn list_site_contents(site_dir: &Utf8Path) -> Result<()> {
     Ok(())
 }
 
-fn format_size(bytes: u64) -> String {
+pub(crate) fn format_size(bytes: u64) -> String {
     if bytes < 1024 {
         format!("{} B", bytes)
     } else if bytes < 1024 * 1024 {
@@ -0,0 +1,449 @@
+//! Differential testing of grammar plugins against the native highlighter.
+//!
+//! `cargo xtask difftest <lang>...` parses each grammar's samples twice: with
+//! the native grammar, through `arborium --parse`, and with the grammar's
+//! built plugin, run in wasmtime through the plugin's raw `arborium_*`
+//! exports. Spans and injections have to match exactly, in UTF-8 byte
+//! offsets and in the UTF-16 offsets plugins give JavaScript hosts, so
+//! offset-conversion and query-loading bugs on either side show up as
+//! differences.
+
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use facet::Facet;
+use owo_colors::OwoColorize;
+use rootcause::Report;
+use wasmtime::{Engine, Instance, Linker, Memory, Module, Store};
+
+use crate::build::{PluginQueries, locate_grammar};
+use crate::types::CrateRegistry;
+
+type Result<T> = std::result::Result<T, Report>;
+
+fn report(msg: impl Into<String>) -> Report {
+    std::io::Error::other(msg.into()).into()
+}
+
+/// How many differences to print per sample.
+const MAX_REPORTED: usize = 10;
+
+/// Options for [`run`].
+pub struct DifftestOptions {
+    /// Grammars to test; all grammars with a built plugin if empty.
+    pub grammars: Vec<String>,
+    /// Where the plugins were built to with `build --output`; the standard
+    /// layout (`langs/group-*/<lang>/npm/`) if `None`.
+    pub plugins_dir: Option<Utf8PathBuf>,
+}
+
+/// `arborium --parse` output, and what a plugin's `arborium_parse_json`
+/// returns.
+#[derive(Debug, Facet)]
+struct ParseOutput {
+    #[facet(default)]
+    spans: Vec<SpanOutput>,
+    #[facet(default)]
+    injections: Vec<InjectionOutput>,
+    #[facet(default)]
+    error: Option<ErrorOutput>,
+}
+
+#[derive(Debug, Facet)]
+struct SpanOutput {
+    start: u32,
+    end: u32,
+    capture: String,
+}
+
+#[derive(Debug, Facet)]
+struct InjectionOutput {
+    start: u32,
+    end: u32,
+    language: String,
+    include_children: bool,
+}
+
+#[derive(Debug, Facet)]
+struct ErrorOutput {
+    kind: String,
+    message: String,
+}
+
+/// Runs the difftest, failing if any sample parses differently.
+pub fn run(repo_root: &Utf8Path, crates_dir: &Utf8Path, options: &DifftestOptions) -> Result<()> {
+    let registry = CrateRegistry::load(crates_dir)?;
+
+    let grammars: Vec<String> = if options.grammars.is_empty() {
+        registry
+            .configured_crates()
+            .flat_map(|(_, _, config)| config.grammars.iter().map(|g| g.id().to_string()))
+            .filter(|id| plugin_dir(repo_root, &registry, options, id).is_some_and(|d| d.exists()))
+            .collect()
+    } else {
+        options.grammars.clone()
+    };
+    if grammars.is_empty() {
+        return Err(report(
+            "no built plugins found; build them with `cargo xtask build` first",
+        ));
+    }
+
+    let arborium = build_cli(repo_root)?;
+    let engine = Engine::default();
+
+    let mut failed = Vec::new();
+    for grammar in &grammars {
+        let (crate_state, grammar_config) = locate_grammar(&registry, grammar)
+            .ok_or_else(|| report(format!("unknown grammar `{grammar}`")))?;
+        let plugin_dir = plugin_dir(repo_root, &registry, options, grammar)
+            .ok_or_else(|| report(format!("no plugin directory for `{grammar}`")))?;
+        if !plugin_dir.join("grammar_bg.wasm").exists() {
+            return Err(report(format!(
+                "no plugin for `{grammar}` in {plugin_dir}; build it with `cargo xtask build {grammar}`"
+            )));
+        }
+
+        println!("{} {}", "→".blue(), grammar.bold());
+        let mut plugin = Plugin::load(&engine, &plugin_dir)?;
+
+        let samples = grammar_config.samples.as_deref().unwrap_or_default();
+        if samples.is_empty() {
+            println!("  {} no samples", "-".dimmed());
+        }
+        for sample in samples {
+            let path = crate_state.def_path.join(&sample.path);
+            let Ok(text) = fs_err::read_to_string(&path) else {
+                println!(
+                    "  {} {} (not UTF-8 or unreadable)",
+                    "-".dimmed(),
+                    sample.path
+                );
+                continue;
+            };
+
+            let native = native_parse(&arborium, grammar, &path)?;
+            let mut diffs = diff("UTF-8", &text, &native, &plugin.parse(&text, false)?);
+            let native_utf16 = to_utf16(&text, &native);
+            diffs.extend(diff(
+                "UTF-16",
+                &text,
+                &native_utf16,
+                &plugin.parse(&text, true)?,
+            ));
+
+            if diffs.is_empty() {
+                println!(
+                    "  {} {} ({} spans, {} injections)",
+                    "✓".green(),
+                    sample.path,
+                    native.spans.len(),
+                    native.injections.len()
+                );
+            } else {
+                println!(
+                    "  {} {} ({} differences)",
+                    "✗".red(),
+                    sample.path,
+                    diffs.len()
+                );
+                for line in diffs.iter().take(MAX_REPORTED) {
+                    println!("      {line}");
+                }
+                if diffs.len() > MAX_REPORTED {
+                    println!("      ... and {} more", diffs.len() - MAX_REPORTED);
+                }
+                failed.push(format!("{grammar}: {}", sample.path));
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        println!("{} native and plugin parses match", "✓".green());
+        Ok(())
+    } else {
+        Err(report(format!(
+            "{} samples parse differently:\n  {}",
+            failed.len(),
+            failed.join("\n  ")
+        )))
+    }
+}
+
+/// Where a grammar's plugin was built to.
+fn plugin_dir(
+    repo_root: &Utf8Path,
+    registry: &CrateRegistry,
+    options: &DifftestOptions,
+    grammar: &str,
+) -> Option<Utf8PathBuf> {
+    match &options.plugins_dir {
+        Some(dir) if dir.is_absolute() => Some(dir.join(grammar)),
+        Some(dir) => Some(repo_root.join(dir).join(grammar)),
+        None => {
+            let (crate_state, _) = locate_grammar(registry, grammar)?;
+            Some(crate_state.crate_path.parent()?.join("npm"))
+        }
+    }
+}
+
+/// Builds the arborium CLI with all languages and returns its path.
+fn build_cli(repo_root: &Utf8Path) -> Result<Utf8PathBuf> {
+    println!("{} Building the arborium CLI", "→".blue());
+    // There is no workspace; the CLI crate builds on its own
+    let cli_dir = repo_root.join("crates/arborium-cli");
+    let status = Command::new("cargo")
+        .args(["build", "--release", "--manifest-path"])
+        .arg(cli_dir.join("Cargo.toml"))
+        .status()?;
+    if !status.success() {
+        return Err(report("failed to build the arborium CLI"));
+    }
+
+    let target_dir = std::env::var("CARGO_TARGET_DIR")
+        .map(|dir| repo_root.join(dir))
+        .unwrap_or_else(|_| cli_dir.join("target"));
+    Ok(target_dir
+        .join("release")
+        .join(format!("arborium{}", std::env::consts::EXE_SUFFIX)))
+}
+
+fn native_parse(arborium: &Utf8Path, grammar: &str, path: &Utf8Path) -> Result<ParseOutput> {
+    let output = Command::new(arborium)
+        .args([
+            "--parse",
+            "--no-query-cache",
+            "--lang",
+            grammar,
+            path.as_str(),
+        ])
+        .output()?;
+    if !output.status.success() {
+        return Err(report(format!(
+            "arborium --parse failed on {path}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let json = String::from_utf8_lossy(&output.stdout);
+    facet_json::from_str(json.trim())
+        .map_err(|e| report(format!("invalid arborium --parse output for {path}: {e}")))
+}
+
+/// A plugin instantiated in wasmtime.
+///
+/// Plugins import wasm-bindgen's JavaScript glue, which the raw exports
+/// never call; those imports trap.
+struct Plugin {
+    store: Store<()>,
+    instance: Instance,
+    memory: Memory,
+}
+
+impl Plugin {
+    fn load(engine: &Engine, dir: &Utf8Path) -> Result<Self> {
+        let wasm = dir.join("grammar_bg.wasm");
+        let module = Module::from_file(engine, &wasm)
+            .map_err(|e| report(format!("failed to compile {wasm}: {e:#}")))?;
+        let mut linker = Linker::new(engine);
+        linker
+            .define_unknown_imports_as_traps(&module)
+            .map_err(|e| report(format!("{e:#}")))?;
+        let mut store = Store::new(engine, ());
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .map_err(|e| report(format!("failed to instantiate {wasm}: {e:#}")))?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| report(format!("{wasm} exports no memory")))?;
+        if instance
+            .get_func(&mut store, "arborium_parse_json")
+            .is_none()
+        {
+            return Err(report(format!(
+                "{wasm} has no arborium_parse_json export; rebuild it with `cargo xtask build`"
+            )));
+        }
+
+        let mut plugin = Self {
+            store,
+            instance,
+            memory,
+        };
+
+        // Plugins built with `split_queries` take their queries from the host
+        let queries = dir.join("queries.json");
+        if queries.exists() {
+            let queries: PluginQueries = facet_json::from_str(&fs_err::read_to_string(&queries)?)
+                .map_err(|e| report(format!("invalid {queries}: {e}")))?;
+            let highlights = plugin.write(queries.highlights.as_bytes())?;
+            let injections = plugin.write(queries.injections.as_bytes())?;
+            let locals = plugin.write(queries.locals.as_bytes())?;
+            plugin
+                .instance
+                .get_typed_func::<(u32, u32, u32, u32, u32, u32), ()>(
+                    &mut plugin.store,
+                    "arborium_set_queries",
+                )
+                .and_then(|f| {
+                    f.call(
+                        &mut plugin.store,
+                        (
+                            highlights.0,
+                            highlights.1,
+                            injections.0,
+                            injections.1,
+                            locals.0,
+                            locals.1,
+                        ),
+                    )
+                })
+                .map_err(|e| report(format!("failed to set queries: {e:#}")))?;
+        }
+
+        Ok(plugin)
+    }
+
+    /// Copies `bytes` into memory from `arborium_alloc`, returning the
+    /// pointer and length.
+    fn write(&mut self, bytes: &[u8]) -> Result<(u32, u32)> {
+        let len = bytes.len() as u32;
+        let ptr = self
+            .instance
+            .get_typed_func::<u32, u32>(&mut self.store, "arborium_alloc")
+            .and_then(|alloc| alloc.call(&mut self.store, len))
+            .map_err(|e| report(format!("arborium_alloc failed: {e:#}")))?;
+        self.memory
+            .write(&mut self.store, ptr as usize, bytes)
+            .map_err(|e| report(format!("{e}")))?;
+        Ok((ptr, len))
+    }
+
+    fn parse(&mut self, text: &str, utf16: bool) -> Result<ParseOutput> {
+        let (ptr, len) = self.write(text.as_bytes())?;
+        let packed = self
+            .instance
+            .get_typed_func::<(u32, u32, u32), u64>(&mut self.store, "arborium_parse_json")
+            .and_then(|parse| parse.call(&mut self.store, (ptr, len, utf16 as u32)))
+            .map_err(|e| report(format!("arborium_parse_json failed: {e:#}")))?;
+
+        let (json_ptr, json_len) = ((packed >> 32) as u32, packed as u32);
+        let mut json = vec![0; json_len as usize];
+        self.memory
+            .read(&self.store, json_ptr as usize, &mut json)
+            .map_err(|e| report(format!("{e}")))?;
+        self.instance
+            .get_typed_func::<(u32, u32), ()>(&mut self.store, "arborium_free")
+            .and_then(|free| free.call(&mut self.store, (json_ptr, json_len)))
+            .map_err(|e| report(format!("arborium_free failed: {e:#}")))?;
+
+        let output: ParseOutput = facet_json::from_str(&String::from_utf8_lossy(&json))
+            .map_err(|e| report(format!("invalid plugin output: {e}")))?;
+        match output.error {
+            Some(error) => Err(report(format!(
+                "plugin error {}: {}",
+                error.kind, error.message
+            ))),
+            None => Ok(output),
+        }
+    }
+}
+
+/// The native result with UTF-16 offsets, converted the way plugins convert
+/// theirs: offsets inside a character round down to its start.
+fn to_utf16(text: &str, output: &ParseOutput) -> ParseOutput {
+    let mut units = vec![0u32; text.len() + 1];
+    let mut unit = 0;
+    for (offset, c) in text.char_indices() {
+        units[offset..offset + c.len_utf8()].fill(unit);
+        unit += c.len_utf16() as u32;
+    }
+    units[text.len()] = unit;
+    let convert = |offset: u32| units[(offset as usize).min(text.len())];
+
+    ParseOutput {
+        spans: output
+            .spans
+            .iter()
+            .map(|s| SpanOutput {
+                start: convert(s.start),
+                end: convert(s.end),
+                capture: s.capture.clone(),
+            })
+            .collect(),
+        injections: output
+            .injections
+            .iter()
+            .map(|i| InjectionOutput {
+                start: convert(i.start),
+                end: convert(i.end),
+                language: i.language.clone(),
+                include_children: i.include_children,
+            })
+            .collect(),
+        error: None,
+    }
+}
+
+/// The spans and injections only one side has, as lines to print.
+///
+/// Order isn't part of the contract, so both sides are compared as
+/// multisets. Lines point at UTF-8 results' text; UTF-16 ones only show
+/// offsets.
+fn diff(unit: &str, text: &str, native: &ParseOutput, plugin: &ParseOutput) -> Vec<String> {
+    fn entries(output: &ParseOutput) -> BTreeMap<(u32, u32, String), usize> {
+        let mut entries = BTreeMap::new();
+        for span in &output.spans {
+            *entries
+                .entry((span.start, span.end, format!("@{}", span.capture)))
+                .or_default() += 1;
+        }
+        for injection in &output.injections {
+            let children = if injection.include_children {
+                " (children)"
+            } else {
+                ""
+            };
+            *entries
+                .entry((
+                    injection.start,
+                    injection.end,
+                    format!("injection {}{children}", injection.language),
+                ))
+                .or_default() += 1;
+        }
+        entries
+    }
+
+    let snippet = |start: u32, end: u32| -> String {
+        if unit != "UTF-8" {
+            return String::new();
+        }
+        match text.get(start as usize..end as usize) {
+            Some(s) if s.len() <= 40 => format!(" {s:?}"),
+            Some(s) => format!(" {:?}…", s.chars().take(40).collect::<String>()),
+            None => " (not on a character boundary)".to_string(),
+        }
+    };
+
+    let native = entries(native);
+    let plugin = entries(plugin);
+    let mut lines = Vec::new();
+    for (side, ours, theirs) in [("native", &native, &plugin), ("plugin", &plugin, &native)] {
+        for ((start, end, what), count) in ours {
+            let missing = count.saturating_sub(
+                theirs
+                    .get(&(*start, *end, what.clone()))
+                    .copied()
+                    .unwrap_or(0),
+            );
+            for _ in 0..missing {
+                lines.push(format!(
+                    "{unit} {side} only: {start}..{end} {what}{}",
+                    snippet(*start, *end)
+                ));
+            }
+        }
+    }
+    lines
+}
@@ -8,7 +8,7 @@
 use crate::cache::GrammarCache;
 use crate::plan::{Operation, Plan, PlanMode, PlanSet};
 use crate::tool::Tool;
-use crate::types::{CrateRegistry, CrateState};
+use crate::types::{CrateRegistry, CrateState, PairConfig};
 use crate::util::find_repo_root;
 use crate::version_store;
 use camino::{Utf8Path, Utf8PathBuf};
@@ -78,17 +78,30 @@ struct BuildRsTemplate<'a> {
     generated_disclaimer: &'a str,
     has_scanner: bool,
     c_symbol: &'a str,
+    /// Whether `HIGHLIGHTS_QUERY` includes other crates' highlights
+    inherits_highlights: bool,
 }
 
 #[derive(TemplateSimple)]
 #[template(path = "lib.stpl.rs")]
 struct LibRsTemplate<'a> {
     generated_disclaimer: &'a str,
-    grammar_id: &'a str,
+    /// Rust string literals, quotes included
+    grammar_id_literal: &'a str,
+    grammar_name_literal: &'a str,
     c_symbol: &'a str,
-    highlights_exists: bool,
-    injections_exists: bool,
-    locals_exists: bool,
+    /// `"highlights.scm"` etc., or `none` for a missing query
+    highlights: &'a str,
+    injections: &'a str,
+    locals: &'a str,
+    textobjects: &'a str,
+    /// `"//"` etc., or `none`
+    line_comment: &'a str,
+    /// `("/*", "*/")` etc., or `none`
+    block_comment: &'a str,
+    /// `("(", ")"), ("[", "]")` etc.
+    brackets: &'a str,
+    auto_close: &'a str,
     tests_cursed: bool,
     /// Crate names to prepend highlights from, in order
     /// e.g. ["arborium_c"] for C++ inheriting from C
@@ -135,6 +148,8 @@ struct PluginCargoTomlTemplate<'a> {
 struct PluginLibRsTemplate<'a> {
     grammar_id: &'a str,
     grammar_crate_name_snake: &'a str,
+    /// Whether the host passes the queries in with `set_queries`
+    split_queries: bool,
 }
 
 #[derive(TemplateSimple)]
@@ -143,6 +158,8 @@ struct PluginPackageJsonTemplate<'a> {
     grammar_id: &'a str,
     grammar_name: &'a str,
     version: &'a str,
+    /// Whether the package ships `queries.json`
+    split_queries: bool,
 }
 
 #[derive(TemplateSimple)]
@@ -670,9 +687,7 @@ fn generate_cargo_toml(
 
     let grammar_name = grammar.map(|g| g.name.as_ref()).unwrap_or(grammar_id);
 
-    let tag = grammar
-        .map(|g| g.tag.as_str())
-        .unwrap_or("programming");
+    let tag = grammar.map(|g| g.tag.as_str()).unwrap_or("programming");
 
     // Use license from arborium.yaml, fallback to MIT if empty
     let license: &str = {
@@ -703,7 +718,11 @@ fn generate_cargo_toml(
 }
 
 /// Generate build.rs content for a grammar crate.
-fn generate_build_rs(crate_name: &str, config: &crate::types::CrateConfig) -> String {
+fn generate_build_rs(
+    crate_name: &str,
+    config: &crate::types::CrateConfig,
+    inherits_highlights: bool,
+) -> String {
     let grammar = config.grammars.first();
     let has_scanner = grammar.map(|g| g.has_scanner()).unwrap_or(false);
 
@@ -721,6 +740,7 @@ fn generate_build_rs(crate_name: &str, config: &crate::types::CrateConfig) -> St
         generated_disclaimer: &generated_disclaimer("build.stpl.rs"),
         has_scanner,
         c_symbol: &c_symbol,
+        inherits_highlights,
     };
     template
         .render_once()
@@ -741,23 +761,54 @@ fn generate_lib_rs(
         .map(|g| g.id.as_ref())
         .unwrap_or_else(|| crate_name.strip_prefix("arborium-").unwrap_or(crate_name));
 
+    let grammar_name = grammar.map(|g| g.name.as_ref()).unwrap_or(grammar_id);
+
     let c_symbol = grammar
         .and_then(|g| g.c_symbol.as_ref())
         .map(|s| s.to_string())
         .unwrap_or_else(|| grammar_id.replace('-', "_"));
 
-    // Check if queries exist (in def/queries/)
-    let highlights_exists = def_path.join("queries/highlights.scm").exists();
-    let injections_exists = def_path.join("queries/injections.scm").exists();
-    let locals_exists = def_path.join("queries/locals.scm").exists();
+    // Queries that exist (in def/queries/) are passed by file name, the others as `none`
+    let query = |file: &str| {
+        if def_path.join("queries").join(file).exists() {
+            format!("{file:?}")
+        } else {
+            "none".to_string()
+        }
+    };
+
+    let comments = grammar.and_then(|g| g.comments.as_ref());
+    let line_comment = comments
+        .and_then(|c| c.line.as_deref())
+        .map_or_else(|| "none".to_string(), |token| format!("{token:?}"));
+    let block_comment = comments
+        .and_then(|c| c.block())
+        .map_or_else(|| "none".to_string(), |block| format!("{block:?}"));
+
+    let pairs = |pairs: Option<&Vec<PairConfig>>| {
+        pairs
+            .into_iter()
+            .flatten()
+            .map(|pair| format!("({:?}, {:?})", pair.open, pair.close))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let brackets = pairs(grammar.and_then(|g| g.brackets.as_ref()));
+    let auto_close = pairs(grammar.and_then(|g| g.auto_close.as_ref()));
 
     let template = LibRsTemplate {
         generated_disclaimer: &generated_disclaimer("lib.stpl.rs"),
-        grammar_id,
+        grammar_id_literal: &format!("{grammar_id:?}"),
+        grammar_name_literal: &format!("{grammar_name:?}"),
         c_symbol: &c_symbol,
-        highlights_exists,
-        injections_exists,
-        locals_exists,
+        highlights: &query("highlights.scm"),
+        injections: &query("injections.scm"),
+        locals: &query("locals.scm"),
+        textobjects: &query("textobjects.scm"),
+        line_comment: &line_comment,
+        block_comment: &block_comment,
+        brackets: &brackets,
+        auto_close: &auto_close,
         tests_cursed,
         highlights_prepend,
     };
@@ -811,9 +862,7 @@ fn generate_readme(crate_name: &str, config: &crate::types::CrateConfig) -> Stri
         .map(|i| i.as_str())
         .unwrap_or("");
 
-    let year = grammar
-        .and_then(|g| g.year)
-        .unwrap_or(0);
+    let year = grammar.and_then(|g| g.year).unwrap_or(0);
 
     let language_link = grammar
         .and_then(|g| g.link.as_ref())
@@ -863,12 +912,17 @@ fn generate_plugin_cargo_toml(grammar_id: &str, grammar_crate_name: &str) -> Str
 }
 
 /// Generate plugin src/lib.rs content.
-fn generate_plugin_lib_rs(grammar_id: &str, grammar_crate_name: &str) -> String {
+fn generate_plugin_lib_rs(
+    grammar_id: &str,
+    grammar_crate_name: &str,
+    split_queries: bool,
+) -> String {
     let grammar_crate_name_snake = grammar_crate_name.replace('-', "_");
 
     let template = PluginLibRsTemplate {
         grammar_id,
         grammar_crate_name_snake: &grammar_crate_name_snake,
+        split_queries,
     };
     template
         .render_once()
@@ -876,11 +930,17 @@ fn generate_plugin_lib_rs(grammar_id: &str, grammar_crate_name: &str) -> String
 }
 
 /// Generate plugin package.json content.
-fn generate_plugin_package_json(grammar_id: &str, grammar_name: &str, version: &str) -> String {
+fn generate_plugin_package_json(
+    grammar_id: &str,
+    grammar_name: &str,
+    version: &str,
+    split_queries: bool,
+) -> String {
     let template = PluginPackageJsonTemplate {
         grammar_id,
         grammar_name,
         version,
+        split_queries,
     };
     template
         .render_once()
@@ -1605,7 +1665,11 @@ fn plan_crate_files_only(
 
     // Generate build.rs
     let build_rs_path = crate_path.join("build.rs");
-    let new_build_rs = generate_build_rs(&crate_state.name, config);
+    let new_build_rs = generate_build_rs(
+        &crate_state.name,
+        config,
+        !highlight_prepends.lib_prepends.is_empty(),
+    );
 
     if build_rs_path.exists() {
         let old_content = fs::read_to_string(&build_rs_path)?;
@@ -1738,15 +1802,20 @@ fn plan_crate_files_only(
         plan_copy_grammar_sources(&mut plan, &def_lang_common, &crate_common_dir, mode)?;
     }
 
-    // Copy query files (highlights.scm, injections.scm, locals.scm) into crate/queries/
-    // so that include_str! paths work in the published package.
+    // Copy query files (highlights.scm, injections.scm, locals.scm, textobjects.scm)
+    // into crate/queries/ so that include_str! paths work in the published package.
     let def_queries_dir = def_path.join("queries");
     let crate_queries_dir = crate_path.join("queries");
 
     if def_queries_dir.exists() {
         let mut queries_found = false;
 
-        for query_name in &["highlights.scm", "injections.scm", "locals.scm"] {
+        for query_name in &[
+            "highlights.scm",
+            "injections.scm",
+            "locals.scm",
+            "textobjects.scm",
+        ] {
             let src_query = def_queries_dir.join(query_name);
             if src_query.exists() {
                 if !queries_found {
@@ -1895,7 +1964,7 @@ fn plan_plugin_crate_files(
 
     // Generate npm/src/lib.rs
     let lib_rs_path = npm_path.join("src/lib.rs");
-    let new_lib_rs = generate_plugin_lib_rs(grammar_id, crate_name);
+    let new_lib_rs = generate_plugin_lib_rs(grammar_id, crate_name, grammar.split_queries());
 
     if lib_rs_path.exists() {
         let old_content = fs::read_to_string(&lib_rs_path)?;
@@ -1930,22 +1999,19 @@ fn plan_plugin_crate_files(
         .as_ref()
         .map(|d| d.as_str())
         .unwrap_or("");
-    let language_link = grammar
-        .link
-        .as_ref()
-        .map(|l| l.as_str())
-        .unwrap_or("");
-    let inventor = grammar
-        .inventor
-        .as_ref()
-        .map(|i| i.as_str())
-        .unwrap_or("");
+    let language_link = grammar.link.as_ref().map(|l| l.as_str()).unwrap_or("");
+    let inventor = grammar.inventor.as_ref().map(|i| i.as_str()).unwrap_or("");
     let year = grammar.year.unwrap_or(0);
 
     // Generate npm/package.json
     let package_json_path = npm_path.join("package.json");
     let new_package_json =
-        generate_plugin_package_json(grammar_id, grammar_name, workspace_version);
+        generate_plugin_package_json(
+            grammar_id,
+            grammar_name,
+            workspace_version,
+            grammar.split_queries(),
+        );
 
     if package_json_path.exists() {
         let old_content = fs::read_to_string(&package_json_path)?;
@@ -2055,6 +2121,7 @@ include = [
     "build.rs",
     "examples/**/*.rs",
     "tests/**/*.rs",
+    "benches/**/*.rs",
     "arborium-header.html",
 ]
 
@@ -2076,6 +2143,35 @@ all-languages = [
     }
     content.push_str("]\n\n");
 
+    // Parallel batch highlighting
+    content.push_str("# Parallel `Highlighter::par_highlight_many`\n");
+    content.push_str("rayon = [\"dep:rayon\"]\n\n");
+
+    // Async highlighting
+    content.push_str("# `Highlighter::highlight_to_html_async` on tokio's blocking pool\n");
+    content.push_str("tokio = [\"dep:tokio\"]\n\n");
+
+    // Jupyter notebook rendering
+    content.push_str("# `arborium::notebook` for .ipynb files\n");
+    content.push_str("notebook = [\"dep:serde_json\"]\n\n");
+
+    // arborium.toml support
+    content.push_str("# `arborium::config_file` for arborium.toml\n");
+    content.push_str("config-file = [\"dep:serde\", \"dep:toml\", \"arborium-theme/toml\"]\n\n");
+
+    // Serializable tokens
+    content.push_str("# Serialize for `arborium::Token`\n");
+    content.push_str("serde = [\"dep:serde\", \"arborium-highlight/serde\"]\n\n");
+
+    // Queries compiled at build time
+    content.push_str("# Compile the enabled grammars' queries at build time, so that the first\n");
+    content.push_str("# highlight in each language doesn't have to\n");
+    content.push_str("precompiled-configs = [\n");
+    for (name, _, _) in &grammar_crates {
+        content.push_str(&format!("    \"{}?/precompiled\",\n", name));
+    }
+    content.push_str("]\n\n");
+
     // Individual language features
     content.push_str("# Individual language features\n");
     for (name, grammar_id, _) in &grammar_crates {
@@ -2089,6 +2185,12 @@ all-languages = [
 arborium-tree-sitter = {{ version = "{version}", path = "../arborium-tree-sitter" }}
 arborium-theme = {{ version = "{version}", path = "../arborium-theme" }}
 arborium-highlight = {{ version = "{version}", path = "../arborium-highlight", features = ["tree-sitter"] }}
+rayon = {{ version = "1", optional = true }}
+serde = {{ version = "1", features = ["derive"], optional = true }}
+serde_json = {{ version = "1", optional = true }}
+thiserror = "2"
+tokio = {{ version = "1", features = ["rt"], optional = true }}
+toml = {{ version = "0.8", optional = true }}
 
 # Optional grammar dependencies
 "#
@@ -2109,8 +2211,18 @@ arborium-highlight = {{ version = "{version}", path = "../arborium-highlight", f
     content.push_str(
         r#"
 [dev-dependencies]
+arborium-test-support = { path = "../arborium-test-support" }
 indoc = "2"
 
+[[bench]]
+name = "parser_pool"
+harness = false
+required-features = ["lang-rust"]
+
+[[bench]]
+name = "corpus"
+harness = false
+
 # WASM allocator (automatically enabled on wasm targets)
 [target.'cfg(target_family = "wasm")'.dependencies]
 dlmalloc = "0.2"
@@ -2186,8 +2298,9 @@ dlmalloc = "0.2"
         if let Some(ref alias_list) = grammar.aliases {
             for alias in alias_list {
                 aliases.push((alias.clone(), grammar_id.clone()));
-                // Aliases also serve as file extensions
-                extensions.push((alias.clone(), grammar_id.clone()));
+                // Aliases also serve as file extensions, which are matched
+                // lowercased (so `R` is a name, but `.R` files match `r`)
+                extensions.push((alias.to_lowercase(), grammar_id.clone()));
             }
         }
     }
@@ -2195,6 +2308,7 @@ dlmalloc = "0.2"
     // Sort for deterministic output
     aliases.sort();
     extensions.sort();
+    extensions.dedup();
     languages.sort();
 
     // =========================================================================
@@ -2349,10 +2463,14 @@ fn plan_shared_crates(prepared: &PreparedStructures, mode: PlanMode) -> Result<P
 
     // Shared crates with Cargo.toml.in templates
     let shared_crates = [
+        "arborium-captures",
+        "arborium-grammar",
         "arborium-theme",
         "arborium-highlight",
+        "arborium-syntax",
         "arborium-sysroot",
         "arborium-test-harness",
+        "arborium-test-support",
         "arborium-tree-sitter",
         "arborium-host",
         "arborium-plugin-runtime",
@@ -2360,6 +2478,7 @@ fn plan_shared_crates(prepared: &PreparedStructures, mode: PlanMode) -> Result<P
         "arborium-query",
         "arborium-rustdoc",
         "arborium-mdbook",
+        "arborium-http",
     ];
 
     for crate_name in shared_crates {
@@ -2470,6 +2589,99 @@ use arborium_highlight::{Span, spans_to_html, HtmlFormat};
 // After getting spans from a grammar...
 let html = spans_to_html(source, spans, &HtmlFormat::CustomElements);
 ```
+"#
+        }
+        "arborium-captures" => {
+            r#"# arborium-captures
+
+The capture vocabulary shared by all of arborium: highlighter, themes, the
+WASM plugin wire protocol, and external hosts.
+
+## Contents
+
+- `CAPTURE_NAMES`: every capture name arborium recognizes in highlight queries
+- `ThemeSlot` and `capture_to_slot`: how those names (including nvim-treesitter and helix aliases) collapse onto theme slots
+- `HIGHLIGHTS`: the highlight categories themes define, with their HTML tags and parents
+- `highlights.toml`: the same categories, read by xtask for CSS generation
+
+The crate is `no_std` and has no dependencies. `arborium-theme` re-exports it
+as `arborium_theme::highlights`.
+
+## Usage
+
+```rust
+use arborium_captures::{capture_to_slot, tag_for_capture, ThemeSlot};
+
+assert_eq!(capture_to_slot("keyword.function"), ThemeSlot::Keyword);
+assert_eq!(tag_for_capture("include"), Some("k"));
+```
+"#
+        }
+        "arborium-syntax" => {
+            r#"# arborium-syntax
+
+Syntax tree utilities shared by arborium's highlighter and its WASM grammar
+plugins, so plugins answer editors the way the highlighter does without
+pulling in themes and renderers.
+
+## Contents
+
+- `toggle_comments`: comment out lines, or uncomment them
+- `prose_ranges`: comments, strings and markup text, for spell checkers
+
+With the `tree-sitter` feature:
+
+- `tree_sexp`: dump a syntax tree like `tree-sitter parse` does
+- `injection_range` and `annotated_language`: where injections go and in which language
+- `folding_ranges` and `symbols`: code folding and outlines
+- `expand_selection`: grow a selection to the enclosing syntax node
+
+`arborium-highlight` re-exports all of it.
+"#
+        }
+        "arborium-grammar" => {
+            r#"# arborium-grammar
+
+The `declare_grammar!` macro behind arborium's grammar crates.
+
+## Purpose
+
+Every `arborium-<lang>` crate exposes the same items: `language()`, the
+highlights, injections, locals and textobjects queries, `GRAMMAR_ID` and `GRAMMAR_NAME`,
+`LINE_COMMENT` and `BLOCK_COMMENT`, `BRACKETS` and `AUTO_CLOSE_PAIRS`,
+and tests hooked up to `arborium-test-harness`. Their `src/lib.rs` is one
+invocation of this macro, generated by `cargo xtask gen`:
+
+```rust,ignore
+arborium_grammar::declare_grammar! {
+    id: "cpp",
+    name: "C++",
+    c_symbol: tree_sitter_cpp,
+    highlights: "highlights.scm",
+    injections: "injections.scm",
+    locals: none,
+    textobjects: none,
+    line_comment: "//",
+    block_comment: ("/*", "*/"),
+    brackets: [("(", ")"), ("[", "]"), ("{", "}")],
+    auto_close: [("\"", "\""), ("'", "'")],
+    highlights_prepend: [arborium_c],
+    tests: true,
+}
+```
+
+Queries are read from the crate's `queries/` directory at compile time.
+With the `build` feature, `validate::validate_queries` checks them against
+the grammar's `node-types.json` from `build.rs`, failing the build with
+`file:line:column` diagnostics for syntax errors and unknown node types or
+fields.
+
+With the `precompile` feature, enabled by grammar crates' `precompiled`
+feature, `precompile::precompile_queries` also compiles the queries from
+`build.rs`, against a host build of the grammar, and the macro embeds them
+as `PRECOMPILED_HIGHLIGHTS` and `PRECOMPILED_INJECTIONS`.
+
+This is an internal crate used by arborium's grammar crates.
 "#
         }
         "arborium-sysroot" => {
@@ -2524,6 +2736,42 @@ The harness validates that:
 - The grammar compiles correctly
 - Highlight queries are syntactically valid
 - Injection queries parse without errors
+"#
+        }
+        "arborium-test-support" => {
+            r#"# arborium-test-support
+
+Helpers for testing arborium grammars and highlight queries.
+
+## Purpose
+
+`arborium-test-harness` checks that a grammar's queries compile. This crate
+checks what they highlight: it records the highlights over a piece of source
+code, injections included, as nested start/text/end events, and asserts on
+them.
+
+## Usage
+
+```rust,ignore
+use arborium::Highlighter;
+use arborium_test_support::{assert_has_highlights, assert_text_highlighted, record_events};
+
+#[test]
+fn script_is_highlighted() {
+    let mut highlighter = Highlighter::new();
+    let events = record_events(&mut highlighter, "html", "<script>let x = 1;</script>");
+    assert_has_highlights(&events, &["tag", "keyword"], "html with script");
+    assert_text_highlighted(&events, "let", "keyword", "script injection");
+}
+```
+
+Highlight names match the captures they are a prefix of, so `"keyword"`
+matches `@keyword.return`. `format_events` renders events one line per piece
+of text, for snapshot tests and readable failures, and `spans_to_events`
+records spans from elsewhere, such as a plugin.
+
+Grammars outside arborium, like a plugin under development, are tested the
+same way once registered with `GrammarStore::register_language`.
 "#
         }
         "arborium-tree-sitter" => {
@@ -2663,6 +2911,44 @@ cargo doc
 arborium-rustdoc ./target/doc ./target/doc-highlighted
 ```
 
+Or both in one step: `cargo arborium-doc` runs `cargo doc` with whatever
+options you pass it, finds the doc directory through `cargo metadata`
+(honouring `--target-dir` and `--target`) and highlights it in place.
+
+```bash
+cargo arborium-doc --no-deps --all-features
+```
+
+A crate can keep its options in its own manifest, where
+`cargo arborium-doc` finds them:
+
+```toml
+[package.metadata.arborium]
+fallback = "generic"
+languages = { exclude = ["text"], aliases = { cfg = "toml" } }
+
+[package.metadata.arborium.rustdoc]
+guess-languages = true
+highlight-rust = true
+themes = { light = "github-light", dark = "github-dark", ayu = "tokyo-night" }
+```
+
+Outside the `rustdoc` table, the keys are those of `arborium.toml` below.
+
+## Configuration
+
+An `arborium.toml` in the working directory can alias and filter languages
+and set the fallback, comment tags and injection depth. Flags win; the
+theme and output settings are ignored, since rustdoc's own themes are used.
+
+```toml
+fallback = "generic"
+
+[languages]
+exclude = ["text"]
+aliases = { cfg = "toml" }
+```
+
 ## How It Works
 
 1. **CSS Generation**: Appends theme CSS rules to rustdoc's stylesheet
@@ -2705,6 +2991,49 @@ command = "arborium-mdbook"
 - Supports all languages available in arborium
 - Uses arborium's custom HTML elements for styling
 - Compatible with mdBook's standard themes
+"#
+        }
+        "arborium-http" => {
+            r#"# arborium-http
+
+[axum](https://docs.rs/axum) and [tower](https://docs.rs/tower) building
+blocks for serving arborium-highlighted HTML.
+
+## Purpose
+
+Pastebins, doc sites and code review tools all need the same layer between
+an HTTP request and a highlighter: pick the language, highlight without
+blocking the executor, and send the output with caching headers.
+
+## Usage
+
+```rust
+use std::sync::Arc;
+
+use arborium::Highlighter;
+use arborium_http::{highlight_files, router};
+use axum::Router;
+use tower_http::services::ServeDir;
+
+let highlighter = Arc::new(Highlighter::new());
+let files = Router::new()
+    .fallback_service(ServeDir::new("pastes"))
+    .layer(axum::middleware::from_fn_with_state(highlighter.clone(), highlight_files));
+let app = router(highlighter).nest("/files", files);
+```
+
+- `POST /highlight?lang=rust` highlights the request body
+- `GET /files/notes.md` highlights a file, its language picked by extension
+  or by `?lang=`
+
+## Features
+
+- `Highlight` extractor for `?lang=` plus body
+- ETags derived from the content hash, with `304 Not Modified` for matching
+  `If-None-Match` requests before any highlighting
+- Highlighting on tokio's blocking pool, cancelled when the client goes away
+
+See `examples/pastebin.rs` for a complete server.
 "#
         }
         // Fallback for any crates not explicitly listed
@@ -2948,7 +3277,10 @@ name = "arborium"
 path = "src/main.rs"
 
 [features]
-default = ["all-languages"]
+default = ["all-languages", "precompiled"]
+
+# Compile queries at build time, for a faster start
+precompiled = ["arborium/precompiled-configs"]
 
 # All languages
 all-languages = [
@@ -2978,7 +3310,7 @@ all-languages = [
     content.push_str(&format!(
         r#"
 [dependencies]
-arborium = {{ version = "{version}", path = "../arborium" }}
+arborium = {{ version = "{version}", path = "../arborium", features = ["config-file"] }}
 facet = "0.33.0"
 facet-args = "0.33.0"
 "#
@@ -1,6 +1,6 @@
 //! Highlight parsing - loads highlights.toml for theme generation.
 //!
-//! This module parses highlights.toml from crates/arborium-theme/
+//! This module parses highlights.toml from crates/arborium-captures/
 //! and provides types for CSS generation with fallback resolution.
 
 use camino::Utf8Path;
@@ -61,7 +61,7 @@ impl Highlights {
 
 /// Parse highlights.toml and return all definitions.
 pub fn parse_highlights(crates_dir: &Utf8Path) -> Result<Highlights, String> {
-    let toml_path = crates_dir.join("arborium-theme/highlights.toml");
+    let toml_path = crates_dir.join("arborium-captures/highlights.toml");
     let content = fs::read_to_string(&toml_path)
         .map_err(|e| format!("Failed to read {}: {}", toml_path, e))?;
 
@@ -234,6 +234,24 @@ fn lint_crate(
             )));
         }
 
+        // Block comments need both delimiters
+        if let Some(comments) = &grammar.comments
+            && comments.block_start.is_some() != comments.block_end.is_some()
+        {
+            diagnostics.push(LintDiagnostic::Error(format!(
+                "grammar '{gid}': comments need both block_start and block_end, or neither",
+            )));
+        }
+
+        // Pairs need both delimiters
+        let pairs = grammar.brackets.iter().chain(&grammar.auto_close).flatten();
+        for pair in pairs.filter(|pair| pair.open.is_empty() || pair.close.is_empty()) {
+            diagnostics.push(LintDiagnostic::Error(format!(
+                "grammar '{gid}': pair {:?} {:?} has an empty delimiter",
+                pair.open, pair.close,
+            )));
+        }
+
         // Check highlights.scm exists
         if !state.files.queries.highlights.is_present() {
             diagnostics.push(LintDiagnostic::Warning(format!(
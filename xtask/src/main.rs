@@ -6,13 +6,17 @@
 //! - `lint` - Validate all grammars
 //! - `gen \[name\]` - Regenerate crate files from arborium.yaml and build the static demo
 //! - `serve` - Build and serve the WASM demo locally
+//! - `difftest \[lang\]` - Check that plugins parse like the native highlighter
+//! - `perf \[lang\]` - Compare parse throughput with `perf-baseline.json`
 
 mod cache;
 mod ci;
 mod deploy_website;
+mod difftest;
 mod generate;
 mod highlight_gen;
 mod lint_new;
+mod perf;
 mod theme_gen;
 
 mod build;
@@ -127,6 +131,21 @@ enum Command {
         no_fail_fast: bool,
     },
 
+    /// Write plugins.json for a directory of plugins built with `build --output`
+    PluginIndex {
+        /// The directory holding one subdirectory per plugin
+        #[facet(args::positional)]
+        dir: String,
+    },
+
+    /// Compress the plugins in a directory built with `build --output`,
+    /// report their sizes and write plugins.json
+    Package {
+        /// The directory holding one subdirectory per plugin
+        #[facet(args::positional)]
+        dir: String,
+    },
+
     /// Run grammar tests for a specific language crate
     GrammarTest {
         /// Grammar ID (e.g., "kdl")
@@ -138,6 +157,40 @@ enum Command {
         no_capture: bool,
     },
 
+    /// Check that built plugins parse each grammar's samples exactly like
+    /// the native highlighter
+    Difftest {
+        /// Grammars to test (all with a built plugin if omitted)
+        #[facet(args::positional, default)]
+        grammars: Vec<String>,
+
+        /// Directory the plugins were built to with `build --output`
+        /// (default: the standard layout)
+        #[facet(args::named, default)]
+        plugins: Option<String>,
+    },
+
+    /// Measure parse throughput over the grammars' samples and compare it
+    /// with perf-baseline.json
+    Perf {
+        /// Grammars to measure (all if omitted)
+        #[facet(args::positional, default)]
+        grammars: Vec<String>,
+
+        /// Record the measurements in perf-baseline.json instead of comparing
+        #[facet(args::named, default)]
+        update: bool,
+
+        /// How much slower than the baseline a language may get, in percent
+        /// (default: 20)
+        #[facet(args::named, default)]
+        threshold: Option<u32>,
+
+        /// Report regressions without failing
+        #[facet(args::named, default)]
+        warn_only: bool,
+    },
+
     /// Clean plugin build artifacts (standard layout)
     Clean,
 
@@ -363,6 +416,27 @@ fn main() {
                 std::process::exit(1);
             }
         }
+        Command::PluginIndex { dir } => {
+            let repo_root = util::find_repo_root().expect("Could not find repo root");
+            let repo_root = camino::Utf8PathBuf::from_path_buf(repo_root).expect("non-UTF8 path");
+
+            if let Err(e) = build::write_plugin_index(&repo_root, camino::Utf8Path::new(&dir)) {
+                eprintln!("{:?}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Package { dir } => {
+            let repo_root = util::find_repo_root().expect("Could not find repo root");
+            let repo_root = camino::Utf8PathBuf::from_path_buf(repo_root).expect("non-UTF8 path");
+            let dir = camino::Utf8Path::new(&dir);
+
+            if let Err(e) = build::package_plugins(dir)
+                .and_then(|()| build::write_plugin_index(&repo_root, dir))
+            {
+                eprintln!("{:?}", e);
+                std::process::exit(1);
+            }
+        }
         Command::GrammarTest {
             grammar,
             no_capture,
@@ -411,6 +485,36 @@ fn main() {
                 std::process::exit(status.code().unwrap_or(1));
             }
         }
+        Command::Difftest { grammars, plugins } => {
+            let repo_root = util::find_repo_root().expect("Could not find repo root");
+            let repo_root = camino::Utf8PathBuf::from_path_buf(repo_root).expect("non-UTF8 path");
+
+            let options = difftest::DifftestOptions {
+                grammars,
+                plugins_dir: plugins.map(camino::Utf8PathBuf::from),
+            };
+            if let Err(e) = difftest::run(&repo_root, &crates_dir, &options) {
+                eprintln!("{:?}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Perf {
+            grammars,
+            update,
+            threshold,
+            warn_only,
+        } => {
+            let options = perf::PerfOptions {
+                grammars,
+                update,
+                threshold: threshold.unwrap_or(20),
+                warn_only,
+            };
+            if let Err(e) = perf::run(&repo_root, &crates_dir, &options) {
+                eprintln!("{:?}", e);
+                std::process::exit(1);
+            }
+        }
         Command::Clean => {
             let repo_root = util::find_repo_root().expect("Could not find repo root");
             let repo_root = camino::Utf8PathBuf::from_path_buf(repo_root).expect("non-UTF8 path");
@@ -0,0 +1,258 @@
+//! Parse throughput tracking against a committed baseline.
+//!
+//! `cargo xtask perf` runs the `corpus` bench of the arborium crate over
+//! every grammar's samples and compares each language's throughput with
+//! `perf-baseline.json`. Grammar bumps change scanner performance without
+//! anyone noticing; this makes a language that got slower fail (or, with
+//! `--warn-only`, warn) before it ships. `--update` records the current
+//! numbers as the new baseline, to commit along with the change that
+//! explains them.
+//!
+//! Absolute numbers depend on the machine, so when enough languages are
+//! measured, each one is compared after dividing out the median change
+//! across all of them: a slower machine makes every language slower, a
+//! regression makes one language slower than the rest.
+
+use std::collections::BTreeMap;
+use std::process::{Command, Stdio};
+
+use camino::Utf8Path;
+use facet::Facet;
+use owo_colors::OwoColorize;
+use rootcause::Report;
+
+use crate::types::CrateRegistry;
+
+type Result<T> = std::result::Result<T, Report>;
+
+fn report(msg: impl Into<String>) -> Report {
+    std::io::Error::other(msg.into()).into()
+}
+
+/// The baseline file, relative to the repository root.
+pub const BASELINE_FILE: &str = "perf-baseline.json";
+
+/// Languages needed to estimate the machine's speed from the median change;
+/// with fewer, throughputs are compared as they are.
+const MIN_LANGUAGES_TO_NORMALIZE: usize = 5;
+
+/// Options for [`run`].
+pub struct PerfOptions {
+    /// Grammars to measure; all of them if empty.
+    pub grammars: Vec<String>,
+    /// Write the measurements to the baseline instead of comparing.
+    pub update: bool,
+    /// How much slower than the baseline a language may get, in percent.
+    pub threshold: u32,
+    /// Report regressions without failing.
+    pub warn_only: bool,
+}
+
+/// `perf-baseline.json`.
+#[derive(Debug, Default, Facet)]
+struct PerfBaseline {
+    /// Parse throughput by language.
+    languages: BTreeMap<String, LanguagePerf>,
+}
+
+#[derive(Debug, Clone, Facet)]
+struct LanguagePerf {
+    /// The size of the language's samples, in bytes. Throughput is only
+    /// comparable over the same samples.
+    bytes: u64,
+    /// Parse throughput, in MB/s.
+    mb_per_sec: f64,
+}
+
+pub fn run(repo_root: &Utf8Path, crates_dir: &Utf8Path, options: &PerfOptions) -> Result<()> {
+    let registry = CrateRegistry::load(crates_dir)?;
+    if let Some(unknown) = options
+        .grammars
+        .iter()
+        .find(|g| registry.find_grammar(g).is_none())
+    {
+        return Err(report(format!("unknown grammar `{unknown}`")));
+    }
+
+    // The corpus: every sample of the selected grammars
+    let mut corpus = String::new();
+    let mut features = Vec::new();
+    for (_, state, config) in registry.configured_crates() {
+        for grammar in &config.grammars {
+            let id = grammar.id();
+            if id.ends_with("_inline")
+                || (!options.grammars.is_empty() && !options.grammars.iter().any(|g| g == id))
+            {
+                continue;
+            }
+            let samples = grammar.samples.as_deref().unwrap_or_default();
+            for sample in samples {
+                corpus.push_str(&format!("{id}\t{}\n", state.def_path.join(&sample.path)));
+            }
+            if !samples.is_empty() {
+                features.push(format!("lang-{id}"));
+            }
+        }
+    }
+    if features.is_empty() {
+        return Err(report("no samples to measure"));
+    }
+
+    let corpus_path = repo_root.join("target").join("perf-corpus.tsv");
+    fs_err::create_dir_all(corpus_path.parent().expect("corpus path has a parent"))?;
+    fs_err::write(&corpus_path, corpus)?;
+
+    println!(
+        "{} Measuring parse throughput for {} languages",
+        "→".blue(),
+        features.len()
+    );
+    let features = if options.grammars.is_empty() {
+        "all-languages".to_string()
+    } else {
+        features.join(",")
+    };
+    // There is no workspace; the arborium crate builds on its own
+    let output = Command::new("cargo")
+        .args(["bench", "--bench", "corpus", "--manifest-path"])
+        .arg(repo_root.join("crates/arborium/Cargo.toml"))
+        .args(["--features", &features])
+        .env("ARBORIUM_BENCH_CORPUS", &corpus_path)
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(report("the corpus bench failed"));
+    }
+
+    let mut measured = BTreeMap::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [language, bytes, nanos] = fields.as_slice() else {
+            continue;
+        };
+        let (Ok(bytes), Ok(nanos)) = (bytes.parse::<u64>(), nanos.parse::<u64>()) else {
+            continue;
+        };
+        let mb_per_sec = bytes as f64 / nanos.max(1) as f64 * 1e3;
+        measured.insert(
+            language.to_string(),
+            LanguagePerf {
+                bytes,
+                // Two decimals keep the baseline's diffs readable
+                mb_per_sec: (mb_per_sec * 100.0).round() / 100.0,
+            },
+        );
+    }
+
+    let baseline_path = repo_root.join(BASELINE_FILE);
+    let mut baseline: PerfBaseline = if baseline_path.exists() {
+        facet_json::from_str(&fs_err::read_to_string(&baseline_path)?)
+            .map_err(|e| report(format!("invalid {baseline_path}: {e}")))?
+    } else {
+        PerfBaseline::default()
+    };
+
+    if options.update {
+        let count = measured.len();
+        baseline.languages.extend(measured);
+        let json = facet_json::to_string_pretty(&baseline).expect("baseline serializes");
+        fs_err::write(&baseline_path, json + "\n")?;
+        println!(
+            "{} Recorded {count} languages in {BASELINE_FILE}; commit it",
+            "✓".green()
+        );
+        return Ok(());
+    }
+
+    compare(&baseline, &measured, options)
+}
+
+/// Print measurements next to the baseline and fail on regressions.
+fn compare(
+    baseline: &PerfBaseline,
+    measured: &BTreeMap<String, LanguagePerf>,
+    options: &PerfOptions,
+) -> Result<()> {
+    // Changes relative to the baseline, for languages measured over the same samples
+    let ratios: BTreeMap<&str, f64> = measured
+        .iter()
+        .filter_map(|(language, current)| {
+            let base = baseline.languages.get(language)?;
+            (base.bytes == current.bytes && base.mb_per_sec > 0.0)
+                .then(|| (language.as_str(), current.mb_per_sec / base.mb_per_sec))
+        })
+        .collect();
+
+    let machine = if ratios.len() >= MIN_LANGUAGES_TO_NORMALIZE {
+        let mut sorted: Vec<f64> = ratios.values().copied().collect();
+        sorted.sort_by(f64::total_cmp);
+        sorted[sorted.len() / 2]
+    } else {
+        1.0
+    };
+    if machine != 1.0 {
+        println!(
+            "  This machine is {:.0}% {} than the baseline's; comparing relative to that",
+            (machine - 1.0).abs() * 100.0,
+            if machine > 1.0 { "faster" } else { "slower" }
+        );
+    }
+
+    let limit = 1.0 - options.threshold as f64 / 100.0;
+    let mut regressions = Vec::new();
+    for (language, current) in measured {
+        let Some(base) = baseline.languages.get(language) else {
+            println!(
+                "  {} {language}: {:.2} MB/s (not in the baseline)",
+                "?".yellow(),
+                current.mb_per_sec
+            );
+            continue;
+        };
+        let Some(ratio) = ratios.get(language.as_str()) else {
+            println!(
+                "  {} {language}: {:.2} MB/s (samples changed since the baseline)",
+                "?".yellow(),
+                current.mb_per_sec
+            );
+            continue;
+        };
+
+        let change = ratio / machine;
+        let line = format!(
+            "{language}: {:.2} → {:.2} MB/s ({:+.1}%)",
+            base.mb_per_sec,
+            current.mb_per_sec,
+            (change - 1.0) * 100.0
+        );
+        if change < limit {
+            println!("  {} {line}", "✗".red());
+            regressions.push(line);
+        } else {
+            println!("  {} {line}", "✓".green());
+        }
+    }
+
+    if regressions.is_empty() {
+        println!(
+            "{} No language is more than {}% slower than the baseline",
+            "✓".green(),
+            options.threshold
+        );
+        return Ok(());
+    }
+
+    let message = format!(
+        "{} languages are more than {}% slower than the baseline:\n  {}\n\
+         If that's expected, record it with `cargo xtask perf --update`.",
+        regressions.len(),
+        options.threshold,
+        regressions.join("\n  ")
+    );
+    if options.warn_only {
+        println!("{} {message}", "⚠".yellow());
+        Ok(())
+    } else {
+        Err(report(message))
+    }
+}
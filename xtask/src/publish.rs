@@ -32,15 +32,20 @@ type Result<T> = std::result::Result<T, Report>;
 /// ORDER MATTERS - sorted by dependency order (no deps first).
 const PRE_CRATES: &[&str] = &[
     // No arborium dependencies
+    "crates/arborium-captures",
     "crates/arborium-docsrs-demo",
+    "crates/arborium-grammar",
     "crates/arborium-sysroot",
-    "crates/arborium-theme",
     "crates/arborium-wire",
+    // Depends on captures
+    "crates/arborium-theme",
     // Depends on sysroot
     "crates/arborium-tree-sitter",
-    // Depends on theme, tree-sitter
+    // Depends on tree-sitter
+    "crates/arborium-syntax",
+    // Depends on syntax, theme, tree-sitter
     "crates/arborium-highlight",
-    // Depends on highlight, theme, tree-sitter
+    // Depends on highlight, captures, tree-sitter
     "crates/arborium-test-harness",
 ];
 
@@ -50,7 +55,8 @@ const PRE_CRATES: &[&str] = &[
 const POST_CRATES: &[&str] = &[
     // Main arborium crate first
     "crates/arborium",
-    // Depends on arborium
+    // Depend on arborium
+    "crates/arborium-test-support",
     "crates/arborium-cli",
 ];
 
@@ -969,10 +975,13 @@ fn extract_arborium_deps(crate_dir: &Utf8Path) -> Result<Vec<String>> {
         for (name, _) in deps_table {
             // Only include arborium-* grammar crates, not infrastructure crates
             if name.starts_with("arborium-")
+                && name != "arborium-captures"
+                && name != "arborium-grammar"
                 && name != "arborium-sysroot"
                 && name != "arborium-test-harness"
                 && name != "arborium-tree-sitter"
                 && name != "arborium-highlight"
+                && name != "arborium-syntax"
                 && name != "arborium-theme"
                 && name != "tree-sitter-language"
             {
@@ -1256,6 +1265,7 @@ include = [
     "build.rs",
     "examples/**/*.rs",
     "tests/**/*.rs",
+    "benches/**/*.rs",
     "arborium-header.html",
 ]
 
@@ -1276,6 +1286,14 @@ all-languages = [
     }
     content.push_str("]\n\n");
 
+    // Parallel batch highlighting
+    content.push_str("# Parallel `Highlighter::par_highlight_many`\n");
+    content.push_str("rayon = [\"dep:rayon\"]\n\n");
+
+    // Jupyter notebook rendering
+    content.push_str("# `arborium::notebook` for .ipynb files\n");
+    content.push_str("notebook = [\"dep:serde_json\"]\n\n");
+
     // Individual language features
     content.push_str("# Individual language features\n");
     for (name, grammar_id, _, _) in &grammar_crates {
@@ -1290,6 +1308,8 @@ all-languages = [
 arborium-tree-sitter = {{ version = "{workspace_version}", path = "../arborium-tree-sitter" }}
 arborium-theme = {{ version = "{workspace_version}", path = "../arborium-theme" }}
 arborium-highlight = {{ version = "{workspace_version}", path = "../arborium-highlight", features = ["tree-sitter"] }}
+rayon = {{ version = "1", optional = true }}
+serde_json = {{ version = "1", optional = true }}
 
 # Optional grammar dependencies
 "#
@@ -1309,6 +1329,11 @@ arborium-highlight = {{ version = "{workspace_version}", path = "../arborium-hig
 [dev-dependencies]
 indoc = "2"
 
+[[bench]]
+name = "parser_pool"
+harness = false
+required-features = ["lang-rust"]
+
 # WASM allocator (automatically enabled on wasm targets)
 [target.'cfg(target_family = "wasm")'.dependencies]
 dlmalloc = "0.2"
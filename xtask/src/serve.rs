@@ -555,10 +555,14 @@ fn generate_shared_crate_manifests(repo_root: &Path) -> Result<(), String> {
     let version = crate::version_store::read_version(Utf8Path::new(repo_root.to_str().unwrap()))
         .map_err(|e| e.to_string())?;
     let shared_crates = [
+        "arborium-captures",
+        "arborium-grammar",
         "arborium-theme",
         "arborium-highlight",
+        "arborium-syntax",
         "arborium-sysroot",
         "arborium-test-harness",
+        "arborium-test-support",
         "arborium-tree-sitter",
         "arborium-host",
         "arborium-plugin-runtime",
@@ -566,6 +570,7 @@ fn generate_shared_crate_manifests(repo_root: &Path) -> Result<(), String> {
         "arborium-query",
         "arborium-rustdoc",
         "arborium-mdbook",
+        "arborium-http",
     ];
     for crate_name in shared_crates {
         let crate_dir = repo_root.join("crates").join(crate_name);
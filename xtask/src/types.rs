@@ -183,6 +183,11 @@ pub struct GrammarConfig {
     #[facet(default)]
     pub has_scanner: Option<bool>,
 
+    /// Ship the plugin's queries in a `queries.json` next to its WASM, which
+    /// hosts fetch alongside it, instead of embedding them in the module.
+    #[facet(default)]
+    pub split_queries: Option<bool>,
+
     /// Path to the grammar within the repo (for multi-grammar repos).
     #[facet(default)]
     pub grammar_path: Option<String>,
@@ -204,6 +209,21 @@ pub struct GrammarConfig {
     #[facet(default)]
     pub injections: Option<Vec<String>>,
 
+    // =========================================================================
+    // Editor Metadata
+    // =========================================================================
+    /// Comment delimiters, for toggling comments.
+    #[facet(default)]
+    pub comments: Option<CommentsConfig>,
+
+    /// Brackets editors match and highlight in pairs (e.g., "(" and ")").
+    #[facet(default)]
+    pub brackets: Option<Vec<PairConfig>>,
+
+    /// Pairs editors close automatically besides the brackets (e.g., quotes).
+    #[facet(default)]
+    pub auto_close: Option<Vec<PairConfig>>,
+
     // =========================================================================
     // Language Metadata (for demos and documentation)
     // =========================================================================
@@ -251,6 +271,11 @@ impl GrammarConfig {
         self.has_scanner.unwrap_or(false)
     }
 
+    /// Whether the plugin's queries are shipped separately from its WASM.
+    pub fn split_queries(&self) -> bool {
+        self.split_queries.unwrap_or(false)
+    }
+
     /// Whether tests are cursed (skip test generation).
     pub fn tests_cursed(&self) -> bool {
         self.tests_cursed.unwrap_or(false)
@@ -263,6 +288,39 @@ impl GrammarConfig {
     }
 }
 
+/// A language's comment delimiters.
+#[derive(Debug, Clone, Facet)]
+pub struct CommentsConfig {
+    /// Starts a comment running to the end of the line (e.g., "//").
+    #[facet(default)]
+    pub line: Option<String>,
+
+    /// Starts a block comment (e.g., "/*").
+    #[facet(default)]
+    pub block_start: Option<String>,
+
+    /// Ends a block comment (e.g., "*/").
+    #[facet(default)]
+    pub block_end: Option<String>,
+}
+
+impl CommentsConfig {
+    /// The block comment delimiters, if both are set.
+    pub fn block(&self) -> Option<(&str, &str)> {
+        Some((self.block_start.as_deref()?, self.block_end.as_deref()?))
+    }
+}
+
+/// An opening delimiter and the closing one that goes with it.
+#[derive(Debug, Clone, Facet)]
+pub struct PairConfig {
+    /// The opening delimiter (e.g., "(").
+    pub open: String,
+
+    /// The closing delimiter (e.g., ")").
+    pub close: String,
+}
+
 /// Cross-grammar dependency for tree-sitter generation.
 #[derive(Debug, Clone, Facet)]
 pub struct Dependency {
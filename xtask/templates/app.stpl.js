@@ -169,6 +169,37 @@ function setupArboriumHost() {
             }
         },
 
+        // Dump the syntax tree of the last parsed text (sync)
+        treeSexp(handle, ranges = true, anonymous = false) {
+            const entry = handleToPlugin.get(handle);
+            if (!entry || !entry.plugin.tree_sexp) return null;
+
+            const { plugin, session } = entry;
+            try {
+                return plugin.tree_sexp(session, ranges, anonymous);
+            } catch (e) {
+                console.error(`Tree dump error for handle ${handle}:`, e);
+                return null;
+            }
+        },
+
+        // Replace the queries of a grammar handle's plugin, for editing
+        // highlights.scm live. Sessions keep their trees, so the next parse
+        // re-highlights with the new queries. Returns an error message, or
+        // null if the queries compiled.
+        updateQueries(handle, highlights, injections = '', locals = '') {
+            const entry = handleToPlugin.get(handle);
+            if (!entry) return `Unknown grammar handle ${handle}`;
+            if (!entry.plugin.update_queries) return `'${entry.language}' plugin can't update queries`;
+
+            try {
+                entry.plugin.update_queries(highlights, injections, locals);
+                return null;
+            } catch (e) {
+                return e?.message ?? String(e);
+            }
+        },
+
         // Free a grammar handle and release its session memory
         freeGrammar(handle) {
             const entry = handleToPlugin.get(handle);
@@ -17,11 +17,31 @@ fn main() {
     let src_dir = manifest_dir.join("grammar/src");
     let grammar_dir = manifest_dir.join("grammar");
 
+    // Fail the build on queries that don't match the grammar, rather than
+    // shipping highlights that silently never apply.
+    arborium_grammar::validate::validate_queries(&manifest_dir);
+
     println!("cargo:rerun-if-changed={}", src_dir.join("parser.c").display());
 <% if has_scanner { %>
     println!("cargo:rerun-if-changed={}", grammar_dir.join("scanner.c").display());
 <% } %>
 
+    // With the `precompiled` feature, also compile the queries now rather
+    // than on first use; this builds the grammar once more, for the host.
+    #[cfg(feature = "precompiled")]
+    arborium_grammar::precompile::precompile_queries(
+        &manifest_dir,
+        "tree_sitter_<%= c_symbol %>",
+        &[
+            src_dir.join("parser.c"),
+<% if has_scanner { %>
+            grammar_dir.join("scanner.c"),
+<% } %>
+        ],
+        &[src_dir.clone(), grammar_dir.clone(), src_dir.join("tree_sitter")],
+        <%= inherits_highlights %>,
+    );
+
     let mut build = cc::Build::new();
 
     build
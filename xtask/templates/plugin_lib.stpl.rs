@@ -1,32 +1,91 @@
 //! <%= grammar_id %> grammar plugin for arborium.
 
 use wasm_bindgen::prelude::*;
-use arborium_plugin_runtime::{HighlightConfig, PluginRuntime};
-use arborium_wire::ParseResult as WireParseResult;
+use arborium_plugin_runtime::{
+    CommentSyntax, HighlightConfig, PluginRuntime, ProseClass, SexpOptions, check_language,
+};
+use arborium_wire::{ParseError, ParseResult as WireParseResult};
 use std::cell::RefCell;
 
 thread_local! {
     static RUNTIME: RefCell<Option<PluginRuntime>> = const { RefCell::new(None) };
+<% if split_queries { %>    /// Highlights, injections and locals, as passed to `set_queries`.
+    static QUERIES: RefCell<Option<[String; 3]>> = const { RefCell::new(None) };
+<% } %>}
+
+<% if split_queries { %>fn new_runtime() -> Result<PluginRuntime, ParseError> {
+    check_language(<%= grammar_crate_name_snake %>::language(), "<%= grammar_id %>")?;
+    let config = QUERIES.with(|queries| {
+        let queries = queries.borrow();
+        let [highlights, injections, locals] = queries.as_ref().ok_or_else(|| {
+            ParseError::new("<%= grammar_id %> queries not loaded: call set_queries first")
+        })?;
+        HighlightConfig::new(
+            <%= grammar_crate_name_snake %>::language(),
+            highlights,
+            injections,
+            locals,
+        )
+        .map_err(|e| ParseError::new(format!("<%= grammar_id %> query error: {e}")))
+    })?;
+    Ok(PluginRuntime::new(config))
+}
+
+/// Sets the plugin's queries, which it doesn't embed to keep its WASM
+/// small. Hosts fetch them from the `queries.json` next to the plugin and
+/// call this before [`handshake`].
+#[wasm_bindgen]
+pub fn set_queries(highlights: String, injections: String, locals: String) {
+    QUERIES.with(|queries| *queries.borrow_mut() = Some([highlights, injections, locals]));
+}
+<% } else { %>fn new_runtime() -> Result<PluginRuntime, ParseError> {
+    check_language(<%= grammar_crate_name_snake %>::language(), "<%= grammar_id %>")?;
+    // Use &* to handle both &str constants and LazyLock<String> statics
+    let config = HighlightConfig::new(
+        <%= grammar_crate_name_snake %>::language(),
+        &*<%= grammar_crate_name_snake %>::HIGHLIGHTS_QUERY,
+        <%= grammar_crate_name_snake %>::INJECTIONS_QUERY,
+        <%= grammar_crate_name_snake %>::LOCALS_QUERY,
+    )
+    .map_err(|e| ParseError::new(format!("<%= grammar_id %> query error: {e}")))?;
+    Ok(PluginRuntime::new(config))
+}
+<% } %>
+/// Whether the host has to pass the queries in with `set_queries` before
+/// using the plugin.
+#[wasm_bindgen]
+pub fn external_queries() -> bool {
+    <%= split_queries %>
 }
 
 fn get_or_init_runtime() -> &'static RefCell<Option<PluginRuntime>> {
     RUNTIME.with(|r| {
         let mut runtime = r.borrow_mut();
         if runtime.is_none() {
-            // Use &* to handle both &str constants and LazyLock<String> statics
-            let config = HighlightConfig::new(
-                <%= grammar_crate_name_snake %>::language(),
-                &*<%= grammar_crate_name_snake %>::HIGHLIGHTS_QUERY,
-                <%= grammar_crate_name_snake %>::INJECTIONS_QUERY,
-                <%= grammar_crate_name_snake %>::LOCALS_QUERY,
-            )
-            .expect("failed to create highlight config");
-            *runtime = Some(PluginRuntime::new(config));
+            *runtime = Some(new_runtime().expect("failed to create highlight config"));
         }
         unsafe { &*(r as *const _) }
     })
 }
 
+/// Checks that the grammar loads and its queries compile, and sets up the
+/// plugin.
+///
+/// Hosts call this right after instantiating the plugin. It throws a
+/// `{ kind, message }` object, with kind `IncompatibleGrammar` for a
+/// grammar generated for a tree-sitter ABI this plugin's runtime can't
+/// load; without it, such errors only surface as a panic on first use.
+#[wasm_bindgen]
+pub fn handshake() -> Result<(), JsValue> {
+    RUNTIME.with(|r| {
+        let mut runtime = r.borrow_mut();
+        if runtime.is_none() {
+            *runtime = Some(new_runtime().map_err(|e| parse_error_to_js(&e))?);
+        }
+        Ok(())
+    })
+}
+
 /// Returns the language ID for this grammar plugin.
 #[wasm_bindgen]
 pub fn language_id() -> String {
@@ -40,6 +99,65 @@ pub fn injection_languages() -> Vec<String> {
     vec![]
 }
 
+/// The language's comment delimiters, from its `arborium.yaml`.
+const COMMENT_SYNTAX: CommentSyntax = CommentSyntax {
+    line: <%= grammar_crate_name_snake %>::LINE_COMMENT,
+    block: <%= grammar_crate_name_snake %>::BLOCK_COMMENT,
+};
+
+fn wire_comment_syntax() -> arborium_wire::CommentSyntax {
+    arborium_wire::CommentSyntax {
+        line: COMMENT_SYNTAX.line.map(String::from),
+        block_start: COMMENT_SYNTAX.block.map(|(start, _)| start.to_string()),
+        block_end: COMMENT_SYNTAX.block.map(|(_, end)| end.to_string()),
+    }
+}
+
+/// Returns the language's comment delimiters, as a `{ line, block_start,
+/// block_end }` object without the delimiters the language doesn't have.
+#[wasm_bindgen]
+pub fn comment_syntax() -> Result<JsValue, JsValue> {
+    serde_wasm_bindgen::to_value(&wire_comment_syntax())
+        .map_err(|e| JsValue::from_str(&format!("serialization error: {}", e)))
+}
+
+/// Returns what editors need to know about the language, from its
+/// `arborium.yaml`, as a `{ language_id, wire_version, comments, brackets,
+/// auto_close_pairs }` object, with pairs as `{ open, close }`.
+#[wasm_bindgen]
+pub fn plugin_info() -> Result<JsValue, JsValue> {
+    let pairs = |pairs: &[(&str, &str)]| -> Vec<arborium_wire::Pair> {
+        pairs
+            .iter()
+            .map(|&(open, close)| arborium_wire::Pair {
+                open: open.to_string(),
+                close: close.to_string(),
+            })
+            .collect()
+    };
+    let info = arborium_wire::PluginInfo {
+        language_id: language_id(),
+        wire_version: arborium_wire::WIRE_VERSION,
+        comments: wire_comment_syntax(),
+        brackets: pairs(<%= grammar_crate_name_snake %>::BRACKETS),
+        auto_close_pairs: pairs(<%= grammar_crate_name_snake %>::AUTO_CLOSE_PAIRS),
+    };
+    serde_wasm_bindgen::to_value(&info)
+        .map_err(|e| JsValue::from_str(&format!("serialization error: {}", e)))
+}
+
+/// Returns the edits that toggle comments on the lines `start..end` touches
+/// in `text`, as `{ start, end, replacement }` objects to apply from last to
+/// first.
+///
+/// Like [`query`]'s, offsets are UTF-16 code units.
+#[wasm_bindgen]
+pub fn comment_ranges(text: &str, start: u32, end: u32) -> Result<JsValue, JsValue> {
+    let edits = arborium_plugin_runtime::comment_ranges(COMMENT_SYNTAX, text, start, end);
+    serde_wasm_bindgen::to_value(&edits)
+        .map_err(|e| JsValue::from_str(&format!("serialization error: {}", e)))
+}
+
 /// Creates a new parser session and returns its ID.
 #[wasm_bindgen]
 pub fn create_session() -> u32 {
@@ -70,6 +188,22 @@ pub fn set_text(session: u32, text: &str) {
         .set_text(session, text);
 }
 
+/// Replaces the text between UTF-16 offsets `start` and `end` with
+/// `replacement`, and reparses incrementally.
+///
+/// Editors call this on each change instead of [`set_text`], so only the
+/// change crosses the JavaScript boundary. Offsets are the ones of
+/// JavaScript strings, into the session's current text.
+#[wasm_bindgen]
+pub fn splice_text(session: u32, start: u32, end: u32, replacement: &str) -> Result<(), JsValue> {
+    get_or_init_runtime()
+        .borrow_mut()
+        .as_mut()
+        .expect("runtime not initialized")
+        .splice_text(session, start, end, replacement)
+        .map_err(|e| parse_error_to_js(&e))
+}
+
 /// Parses the text in a session and returns the result as a JS value.
 ///
 /// The result is a JavaScript object representation of ParseResult containing spans and injections.
@@ -84,10 +218,124 @@ pub fn parse(session: u32) -> Result<JsValue, JsValue> {
     match result {
         Ok(r) => serde_wasm_bindgen::to_value(&r)
             .map_err(|e| JsValue::from_str(&format!("serialization error: {}", e))),
-        Err(e) => Err(JsValue::from_str(&format!("parse error: {}", e.message))),
+        Err(e) => Err(parse_error_to_js(&e)),
     }
 }
 
+/// Replaces the plugin's highlights, injections and locals queries.
+///
+/// Live sessions keep their text and syntax tree; their next [`parse`] runs
+/// the new queries. The playground uses this to edit queries against the
+/// compiled grammar. Throws a `{ kind, message }` object if the queries don't
+/// compile, in which case the old ones stay in use.
+#[wasm_bindgen]
+pub fn update_queries(highlights: &str, injections: &str, locals: &str) -> Result<(), JsValue> {
+    get_or_init_runtime()
+        .borrow_mut()
+        .as_mut()
+        .expect("runtime not initialized")
+        .update_queries(highlights, injections, locals)
+        .map_err(|e| {
+            parse_error_to_js(&ParseError::new(format!("<%= grammar_id %> query error: {e}")))
+        })
+}
+
+/// Runs a tree-sitter query over the session's syntax tree and returns its
+/// matches, as `{ pattern, captures: [{ name, kind, start, end }] }` objects.
+///
+/// For structural search and lint rules, without a second tree-sitter build
+/// in the browser. Unlike [`parse`]'s, offsets are UTF-16 code units, ready
+/// for JavaScript's `slice()`. Throws a `{ kind, message }` object if the
+/// query doesn't compile.
+#[wasm_bindgen]
+pub fn query(session: u32, query_source: &str) -> Result<JsValue, JsValue> {
+    let matches = get_or_init_runtime()
+        .borrow_mut()
+        .as_mut()
+        .expect("runtime not initialized")
+        .query_utf16(session, query_source)
+        .map_err(|e| parse_error_to_js(&e))?;
+    serde_wasm_bindgen::to_value(&matches)
+        .map_err(|e| JsValue::from_str(&format!("serialization error: {}", e)))
+}
+
+/// Returns the lines of the session's text that can be folded, as
+/// `{ start_row, end_row, kind }` objects with 0-based, inclusive rows and
+/// kind `region` or `comment`.
+#[wasm_bindgen]
+pub fn folding_ranges(session: u32) -> Result<JsValue, JsValue> {
+    let ranges = get_or_init_runtime()
+        .borrow()
+        .as_ref()
+        .expect("runtime not initialized")
+        .folding_ranges(session)
+        .map_err(|e| parse_error_to_js(&e))?;
+    serde_wasm_bindgen::to_value(&ranges)
+        .map_err(|e| JsValue::from_str(&format!("serialization error: {}", e)))
+}
+
+/// Returns the definitions in the session's text for an outline, as nested
+/// `{ name, kind, start, end, name_start, name_end, children }` objects.
+///
+/// Like [`query`]'s, offsets are UTF-16 code units.
+#[wasm_bindgen]
+pub fn symbols(session: u32) -> Result<JsValue, JsValue> {
+    let symbols = get_or_init_runtime()
+        .borrow()
+        .as_ref()
+        .expect("runtime not initialized")
+        .symbols_utf16(session)
+        .map_err(|e| parse_error_to_js(&e))?;
+    serde_wasm_bindgen::to_value(&symbols)
+        .map_err(|e| JsValue::from_str(&format!("serialization error: {}", e)))
+}
+
+/// Returns the ranges of the session's text holding prose, for spell
+/// checkers, as `{ start, end, class }` objects. `classes` picks which of
+/// `comment`, `string` and `markup` to look for; empty means all of them.
+///
+/// Like [`query`]'s, offsets are UTF-16 code units.
+#[wasm_bindgen]
+pub fn prose_ranges(session: u32, classes: Vec<String>) -> Result<JsValue, JsValue> {
+    let classes = classes
+        .iter()
+        .map(|name| {
+            ProseClass::from_name(name).ok_or_else(|| {
+                parse_error_to_js(&ParseError::new(format!("unknown prose class: {name}")))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let classes = if classes.is_empty() {
+        ProseClass::ALL
+    } else {
+        &classes
+    };
+    let ranges = get_or_init_runtime()
+        .borrow_mut()
+        .as_mut()
+        .expect("runtime not initialized")
+        .prose_ranges_utf16(session, classes)
+        .map_err(|e| parse_error_to_js(&e))?;
+    serde_wasm_bindgen::to_value(&ranges)
+        .map_err(|e| JsValue::from_str(&format!("serialization error: {}", e)))
+}
+
+/// Grows the selection `start..end` to the next larger syntax node, returning
+/// it as a `{ start, end }` object.
+///
+/// Like [`query`]'s, offsets are UTF-16 code units.
+#[wasm_bindgen]
+pub fn expand_selection(session: u32, start: u32, end: u32) -> Result<JsValue, JsValue> {
+    let range = get_or_init_runtime()
+        .borrow()
+        .as_ref()
+        .expect("runtime not initialized")
+        .expand_selection_utf16(session, start, end)
+        .map_err(|e| parse_error_to_js(&e))?;
+    serde_wasm_bindgen::to_value(&range)
+        .map_err(|e| JsValue::from_str(&format!("serialization error: {}", e)))
+}
+
 /// Cancels an ongoing parse operation.
 #[wasm_bindgen]
 pub fn cancel(session: u32) {
@@ -97,3 +345,116 @@ pub fn cancel(session: u32) {
         .expect("runtime not initialized")
         .cancel(session);
 }
+
+/// Returns the session's syntax tree as an indented S-expression.
+///
+/// `ranges` appends `[row, column] - [row, column]` to each node and
+/// `anonymous` includes keywords and punctuation. Used by the playground's
+/// tree view.
+#[wasm_bindgen]
+pub fn tree_sexp(session: u32, ranges: bool, anonymous: bool) -> Result<String, JsValue> {
+    get_or_init_runtime()
+        .borrow()
+        .as_ref()
+        .expect("runtime not initialized")
+        .tree_sexp(session, SexpOptions { ranges, anonymous })
+        .map_err(|e| parse_error_to_js(&e))
+}
+
+// Exports for hosts without JavaScript, such as `cargo xtask difftest`,
+// which runs plugins in wasmtime. They only pass pointers and numbers, so
+// they work without wasm-bindgen's JavaScript glue.
+
+/// Allocates `len` bytes for the host to write text into.
+#[unsafe(no_mangle)]
+pub extern "C" fn arborium_alloc(len: usize) -> *mut u8 {
+    Box::into_raw(vec![0u8; len].into_boxed_slice()).cast()
+}
+
+/// Frees `len` bytes at `ptr`, returned by [`arborium_alloc`] or
+/// [`arborium_parse_json`].
+///
+/// # Safety
+///
+/// `ptr` and `len` must come from one of those functions, and be freed once.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn arborium_free(ptr: *mut u8, len: usize) {
+    drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)) });
+}
+<% if split_queries { %>
+/// [`set_queries`] for hosts without JavaScript: each query is a pointer
+/// and length of UTF-8 text allocated with [`arborium_alloc`], which the
+/// plugin frees.
+///
+/// # Safety
+///
+/// The pointers and lengths must come from [`arborium_alloc`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn arborium_set_queries(
+    highlights: *mut u8,
+    highlights_len: usize,
+    injections: *mut u8,
+    injections_len: usize,
+    locals: *mut u8,
+    locals_len: usize,
+) {
+    let take = |ptr, len| {
+        let bytes = unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)) };
+        String::from_utf8_lossy(&bytes).into_owned()
+    };
+    set_queries(
+        take(highlights, highlights_len),
+        take(injections, injections_len),
+        take(locals, locals_len),
+    );
+}
+<% } %>
+/// Parses `len` bytes of UTF-8 text at `text`, allocated with
+/// [`arborium_alloc`], which the plugin frees.
+///
+/// Returns the JSON of the [`WireParseResult`], with UTF-16 offsets if
+/// `utf16` isn't 0, or `{"error":{ kind, message }}`; the pointer in the high
+/// 32 bits and the length in the low ones. The host frees it with
+/// [`arborium_free`].
+///
+/// # Safety
+///
+/// `text` and `len` must come from [`arborium_alloc`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn arborium_parse_json(text: *mut u8, len: usize, utf16: u32) -> u64 {
+    let bytes = unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(text, len)) };
+    let json = match parse_once(&bytes, utf16 != 0) {
+        Ok(result) => result.to_json(),
+        Err(e) => format!("{{\"error\":{}}}", e.to_json()),
+    };
+    let json = Box::into_raw(json.into_bytes().into_boxed_slice());
+    ((json.cast::<u8>() as u64) << 32) | json.len() as u64
+}
+
+/// Parses `text` in a session of its own.
+fn parse_once(text: &[u8], utf16: bool) -> Result<WireParseResult, ParseError> {
+    let text = std::str::from_utf8(text)
+        .map_err(|e| ParseError::new(format!("text is not UTF-8: {e}")))?;
+    RUNTIME.with(|r| {
+        let mut runtime = r.borrow_mut();
+        if runtime.is_none() {
+            *runtime = Some(new_runtime()?);
+        }
+        let runtime = runtime.as_mut().expect("runtime initialized above");
+        let session = runtime.create_session();
+        runtime.set_text(session, text);
+        let result = if utf16 {
+            runtime.parse_utf16(session)
+        } else {
+            runtime.parse(session)
+        };
+        runtime.free_session(session);
+        result
+    })
+}
+
+/// Throw parse errors as `{ kind, message }` objects, so JavaScript hosts can
+/// tell an invalid session from a session without text.
+fn parse_error_to_js(e: &ParseError) -> JsValue {
+    serde_wasm_bindgen::to_value(e).unwrap_or_else(|_| JsValue::from_str(&e.message))
+}
@@ -10,6 +10,11 @@ export const availableLanguages: string[] = [
 <% for lang in languages { %>  "<%= lang %>",
 <% } %>];
 
+/** Other names of the available languages, file extensions included */
+export const languageAliases: Record<string, string> = {
+<% for (alias, lang) in aliases { %>  "<%= alias %>": "<%= lang %>",
+<% } %>};
+
 /** All possible highlights and their short tags */
 export const highlights: Highlight[] = [
 <% for hl in highlights { %>  {
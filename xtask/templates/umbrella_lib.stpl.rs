@@ -83,14 +83,80 @@
 //!
 //! For building custom grammar providers or working with raw spans, see the
 //! [`advanced`] module.
+//!
+//! # API Stability
+//!
+//! The following follow semver: breaking changes only come with a new major
+//! version (or minor version before 1.0):
+//!
+//! - the items at the crate root: [`Highlighter`], [`AnsiHighlighter`],
+//!   [`Config`], [`RenderOptions`], [`Span`], [`Error`] and friends
+//! - the [`prelude`] and [`theme`] modules
+//! - the `lang_*` grammar modules and their `language()` and `*_QUERY` items
+//!
+//! [`Error`] is `#[non_exhaustive]`, so new variants are not breaking
+//! changes. [`Config`] and [`RenderOptions`] gain new fields in minor
+//! releases; build them with `..Default::default()`.
+//!
+//! The [`advanced`] module is public and documented, but tracks
+//! `arborium-highlight` closely and may change in minor releases.
+//! Anything `#[doc(hidden)]`, including the `__private` module and the
+//! `tree_sitter` re-export, is an implementation detail with no stability
+//! guarantees at all.
 
 // Internal modules
+mod annotate;
+mod api_spec;
+mod diagnostics;
+mod diff;
 mod error;
+mod guess;
 mod highlighter;
+mod injection_cache;
+mod kubernetes;
+mod mapping_keys;
+mod word_diff;
+#[cfg(debug_assertions)]
+pub(crate) mod query_dir;
 pub(crate) mod store;
 
 // Public modules
 pub mod advanced;
+#[cfg(feature = "config-file")]
+pub mod config_file;
+#[cfg(feature = "notebook")]
+pub mod notebook;
+
+/// The stable API in one import.
+///
+/// ```rust
+/// use arborium::prelude::*;
+///
+/// let mut hl = Highlighter::with_config(Config {
+///     fallback: Fallback::Plain,
+///     ..Default::default()
+/// });
+/// let html = hl.highlight("plaintext", "hello").unwrap();
+/// assert_eq!(html, "hello");
+/// ```
+pub mod prelude {
+    pub use crate::theme::Theme;
+    pub use crate::{
+        AnsiHighlighter, Config, Error, Fallback, Highlighter, HtmlFormat, LineEndings, Links,
+        RenderOptions, Span, detect_language, detect_language_from_content, guess_language,
+    };
+}
+
+/// Internals for arborium's own crates, tests and generated code.
+///
+/// Not part of the public API: anything in here can change or disappear in
+/// any release.
+#[doc(hidden)]
+pub mod __private {
+    pub use arborium_highlight as highlight;
+    pub use arborium_theme as theme;
+    pub use arborium_tree_sitter as tree_sitter;
+}
 
 /// Theme system for ANSI output.
 ///
@@ -100,12 +166,24 @@ pub mod theme {
 }
 
 // Primary API exports
+pub use annotate::{HeatShading, LineAnnotation};
+pub use api_spec::api_spec_keywords;
+pub use diagnostics::Diagnostic;
 pub use error::Error;
-pub use highlighter::{AnsiHighlighter, Highlighter};
+pub use guess::guess_language;
+pub use highlighter::{AnsiHighlighter, Explanation, Highlighter};
+pub use injection_cache::InjectionCacheStats;
+pub use kubernetes::kubernetes_keywords;
 pub use store::GrammarStore;
 
 // Configuration types (re-exported from arborium-highlight)
-pub use arborium_highlight::HtmlFormat;
+pub use arborium_highlight::{HtmlFormat, LineEndings, RenderOptions};
+pub use arborium_highlight::{MATCH_CAPTURE, Span};
+pub use arborium_highlight::{FrontMatter, split_front_matter};
+pub use arborium_highlight::Motion;
+pub use arborium_highlight::{CommentEdit, CommentSyntax, Pairs};
+pub use arborium_highlight::{ProseClass, ProseRange};
+pub use arborium_highlight::tree_sitter::{SexpOptions, Token};
 
 /// Configuration for highlighting.
 ///
@@ -123,6 +201,68 @@ pub struct Config {
     ///
     /// See [`HtmlFormat`] for options.
     pub html_format: HtmlFormat,
+
+    /// Maximum bytes of text in a single HTML element.
+    ///
+    /// When set, longer runs (say, a 500KB string in minified JSON) are split
+    /// into several consecutive elements of the same kind, which browsers and
+    /// HTML parsers handle much better. `None` (the default) never splits.
+    pub max_chunk_len: Option<usize>,
+
+    /// What to do with languages arborium has no grammar for.
+    ///
+    /// See [`Fallback`] for options.
+    pub fallback: Fallback,
+
+    /// What to do with injected code in languages arborium has no grammar
+    /// for, like a Markdown code block tagged `cobol`.
+    ///
+    /// See [`UnknownInjections`] for options, and
+    /// [`Highlighter::set_unknown_injection_handler`] to load grammars on
+    /// demand.
+    pub unknown_injections: UnknownInjections,
+
+    /// Highlight tags like `TODO:`, `FIXME(alice):` and `SAFETY:` in comments.
+    ///
+    /// Off by default. The tags get `comment.todo`, `comment.error`,
+    /// `comment.warning` or `comment.note` spans nested in the comment.
+    pub comment_tags: bool,
+
+    /// Find URLs and file paths in comments and strings.
+    ///
+    /// See [`Links`] for options.
+    pub links: Links,
+
+    /// Strip a leading UTF-8 byte order mark before highlighting.
+    ///
+    /// On by default. Span offsets still refer to the original text.
+    pub strip_bom: bool,
+
+    /// How to treat `\r\n` and lone `\r` line endings.
+    ///
+    /// See [`LineEndings`] for options.
+    pub line_endings: LineEndings,
+
+    /// Tab expansion and visible whitespace in HTML and ANSI output.
+    ///
+    /// See [`RenderOptions`] for options. Spans from
+    /// [`Highlighter::highlight_spans`] are not affected.
+    pub render_options: RenderOptions,
+
+    /// How lines with a [`LineAnnotation::heat`] weight are shaded by
+    /// [`Highlighter::highlight_annotated`].
+    ///
+    /// See [`HeatShading`] for options.
+    pub heat_shading: HeatShading,
+
+    /// How many distinct injected snippets a highlighter remembers the spans
+    /// of.
+    ///
+    /// Snippets repeated across documents, like the same code block on every
+    /// page of a site, are then only parsed once per highlighter. `0`
+    /// disables the cache; the default is 256. See
+    /// [`Highlighter::injection_cache_stats`].
+    pub injection_cache_size: usize,
 }
 
 impl Default for Config {
@@ -130,20 +270,105 @@ impl Default for Config {
         Self {
             max_injection_depth: 3,
             html_format: HtmlFormat::default(),
+            max_chunk_len: None,
+            fallback: Fallback::default(),
+            unknown_injections: UnknownInjections::default(),
+            comment_tags: false,
+            links: Links::default(),
+            strip_bom: true,
+            line_endings: LineEndings::default(),
+            render_options: RenderOptions::default(),
+            heat_shading: HeatShading::default(),
+            injection_cache_size: 256,
         }
     }
 }
 
+/// How to highlight code in a language without a grammar.
+///
+/// The `plaintext` and `generic` languages are always available and behave
+/// like [`Fallback::Plain`] and [`Fallback::Generic`] respectively.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "config-file",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum Fallback {
+    /// Return [`Error::UnsupportedLanguage`].
+    #[default]
+    Error,
+    /// Render the code as escaped text without any highlighting.
+    Plain,
+    /// Highlight numbers, strings and comments with a generic tokenizer.
+    Generic,
+}
+
+/// How to highlight injected code in a language without a grammar.
+///
+/// Unlike [`Fallback`], this never fails the highlight: the host language
+/// is still highlighted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "config-file",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum UnknownInjections {
+    /// Leave the code as the host language highlights it, which is usually
+    /// not at all.
+    #[default]
+    Skip,
+    /// Give the whole snippet an `embedded` span, so themes can set it apart.
+    Embedded,
+    /// Highlight numbers, strings and comments with a generic tokenizer.
+    Generic,
+    /// Like [`UnknownInjections::Skip`], and record a
+    /// [`Diagnostic::UnknownInjection`], see [`Highlighter::take_diagnostics`].
+    Warn,
+}
+
+/// What to do with URLs and file paths in comments and strings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Links {
+    /// Leave them as part of the comment or string.
+    #[default]
+    Off,
+    /// Give them `markup.link.url` (URLs) or `markup.link` (paths) spans.
+    Highlight,
+    /// Like [`Links::Highlight`], and wrap URLs in `<a href>` in HTML output.
+    ///
+    /// Paths are highlighted but not linked, since there's nothing to
+    /// resolve them against.
+    Anchors,
+}
+
+/// How [`Highlighter::highlight_diff`] lays out the two versions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiffLayout {
+    /// One column like `diff -u` output: unchanged lines once, changed lines
+    /// as the removed lines followed by the added ones.
+    #[default]
+    Unified,
+    /// A `<table>` with the old version in the left column and the new one
+    /// in the right. A changed line shares its row with the line replacing
+    /// it. Style the cells with `white-space: pre`.
+    SideBySide,
+}
+
 impl From<Config> for arborium_highlight::HighlightConfig {
     fn from(config: Config) -> Self {
         arborium_highlight::HighlightConfig {
             max_injection_depth: config.max_injection_depth,
             html_format: config.html_format,
+            max_chunk_len: config.max_chunk_len,
         }
     }
 }
 
-// Tree-sitter re-export for advanced users
+// Tree-sitter re-export, kept so existing code builds. Not covered by the
+// stability guarantees: it moves whenever arborium updates tree-sitter.
+#[doc(hidden)]
 pub use arborium_tree_sitter as tree_sitter;
 
 // WASM allocator (automatically enabled on WASM targets)
@@ -191,6 +416,59 @@ pub fn detect_language(path: &str) -> Option<&'static str> {
     })
 }
 
+/// Detect the language from file contents, for input without a file name.
+///
+/// Recognizes shebang lines (`#!/usr/bin/env python`) and diffs, including
+/// `git show`, `git log -p` and `git format-patch` output.
+///
+/// # Example
+///
+/// ```rust
+/// use arborium::detect_language_from_content;
+///
+/// assert_eq!(detect_language_from_content("#!/bin/bash\necho hi"), Some("bash"));
+/// assert_eq!(detect_language_from_content("#!/usr/bin/env nu\nls"), Some("nushell"));
+/// assert_eq!(detect_language_from_content("diff --git a/x b/x\n"), Some("diff"));
+/// assert_eq!(detect_language_from_content("hello"), None);
+/// ```
+pub fn detect_language_from_content(content: &str) -> Option<&'static str> {
+    let first_line = content.lines().next()?;
+
+    if let Some(shebang) = first_line.strip_prefix("#!") {
+        let shebang = shebang.trim();
+
+        // Common interpreters
+        return if shebang.contains("python") {
+            Some("python")
+        } else if shebang.contains("node") {
+            Some("javascript")
+        } else if shebang.contains("ruby") {
+            Some("ruby")
+        } else if shebang.contains("perl") {
+            Some("perl")
+        } else if shebang.contains("bash") || shebang.contains("/sh") {
+            Some("bash")
+        } else if shebang.contains("zsh") {
+            Some("zsh")
+        } else if shebang.contains("fish") {
+            Some("fish")
+        } else if shebang.contains("php") {
+            Some("php")
+        } else if shebang.split([' ', '/']).any(|word| word == "nu") {
+            Some("nushell")
+        } else {
+            None
+        };
+    }
+
+    let is_diff = first_line.starts_with("diff ")
+        || first_line.starts_with("commit ")
+        || first_line.starts_with("From ")
+        || (first_line.starts_with("--- ")
+            && content.lines().nth(1).is_some_and(|l| l.starts_with("+++ ")));
+    is_diff.then_some("diff")
+}
+
 // =============================================================================
 // Language grammar re-exports based on enabled features.
 // Each module provides:
@@ -1,7 +1,8 @@
 //! Thread-safe grammar store for caching compiled grammars.
 //!
 //! The `GrammarStore` holds compiled grammars that can be shared across threads.
-//! Each grammar is compiled once and cached for reuse.
+//! Each grammar is compiled once and cached for reuse, and idle parse contexts
+//! are pooled so highlighters sharing the store can reuse them.
 //!
 //! # Generated Code
 //!
@@ -10,10 +11,17 @@
 
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 
+use arborium_highlight::{CommentSyntax, Pairs};
 #[allow(unused_imports)]
-use arborium_highlight::tree_sitter::{CompiledGrammar, GrammarConfig};
+use arborium_highlight::tree_sitter::{
+    CompiledGrammar, GrammarConfig, GrammarError, ParserPool, QueryCache,
+};
+use arborium_tree_sitter::Language;
+
+use crate::error::Error;
 
 /// Thread-safe cache of compiled grammars.
 ///
@@ -35,8 +43,18 @@ use arborium_highlight::tree_sitter::{CompiledGrammar, GrammarConfig};
 ///     // Use grammar...
 /// });
 /// ```
+///
+/// A grammar that fails to compile, say because a bad upstream sync broke
+/// its queries, doesn't take the others down with it: its language behaves
+/// as unsupported, and the error is kept for [`init_errors`](Self::init_errors).
 pub struct GrammarStore {
     grammars: RwLock<HashMap<String, Arc<CompiledGrammar>>>,
+    init_errors: RwLock<HashMap<String, Arc<Error>>>,
+    parser_pool: ParserPool,
+    query_cache: Option<QueryCache>,
+    /// Bumped whenever a grammar is replaced, so highlighters know to drop
+    /// what they cached from the old one.
+    generation: AtomicU64,
 }
 
 impl Default for GrammarStore {
@@ -48,14 +66,46 @@ impl Default for GrammarStore {
 impl GrammarStore {
     /// Create a new empty grammar store.
     pub fn new() -> Self {
+        Self::with_parser_pool(ParserPool::default())
+    }
+
+    /// Create a new empty grammar store with a custom parser pool.
+    ///
+    /// Highlighters using this store take their parse contexts from the pool
+    /// and return them when dropped. Use `ParserPool::new(0)` to disable reuse.
+    pub fn with_parser_pool(parser_pool: ParserPool) -> Self {
         Self {
             grammars: RwLock::new(HashMap::new()),
+            init_errors: RwLock::new(HashMap::new()),
+            parser_pool,
+            query_cache: None,
+            generation: AtomicU64::new(0),
         }
     }
 
+    /// Load compiled queries from `cache`, and store them there when
+    /// compiling a grammar for the first time.
+    ///
+    /// This cuts the cost of the first highlight in each language on later
+    /// runs, which is most of a CLI's runtime.
+    ///
+    /// ```rust,ignore
+    /// let store = GrammarStore::new().with_query_cache(QueryCache::user());
+    /// ```
+    pub fn with_query_cache(mut self, cache: Option<QueryCache>) -> Self {
+        self.query_cache = cache;
+        self
+    }
+
+    /// Get the pool of idle parse contexts shared by this store's highlighters.
+    pub fn parser_pool(&self) -> &ParserPool {
+        &self.parser_pool
+    }
+
     /// Get a grammar by language name, compiling and caching it if needed.
     ///
-    /// Returns `None` if the language is not supported.
+    /// Returns `None` if the language is not supported, or if its grammar
+    /// failed to compile; see [`init_errors`](Self::init_errors).
     pub fn get(&self, language: &str) -> Option<Arc<CompiledGrammar>> {
         let normalized = Self::normalize_language(language);
 
@@ -66,10 +116,20 @@ impl GrammarStore {
                 return Some(grammar.clone());
             }
         }
+        if self.init_errors.read().unwrap().contains_key(&*normalized) {
+            return None;
+        }
 
         // Slow path: compile and cache
-        let grammar = Self::compile_grammar(&normalized)?;
-        let grammar = Arc::new(grammar);
+        let grammar = match Self::compile_grammar(&normalized, self.query_cache.as_ref())? {
+            Ok(grammar) => Arc::new(grammar),
+            Err(e) => {
+                let error = Arc::new(Error::grammar(&normalized, e));
+                let mut init_errors = self.init_errors.write().unwrap();
+                init_errors.insert(normalized.into_owned(), error);
+                return None;
+            }
+        };
 
         {
             let mut grammars = self.grammars.write().unwrap();
@@ -83,8 +143,103 @@ impl GrammarStore {
         Some(grammar)
     }
 
+    /// Replace a language's queries, recompiling and caching the grammar.
+    ///
+    /// The tree-sitter language still comes from the compiled-in grammar crate;
+    /// only the queries change. This lets query authors iterate on
+    /// `highlights.scm` without rebuilding the grammar crate. Note that
+    /// inherited queries (e.g. JavaScript's for TypeScript) are not prepended,
+    /// and that the compiled-in textobjects query is kept.
+    pub fn set_queries(
+        &self,
+        language: &str,
+        highlights_query: &str,
+        injections_query: &str,
+        locals_query: &str,
+    ) -> Result<(), Error> {
+        let normalized = Self::normalize_language(language);
+        let ts_language =
+            Self::language_for(&normalized).ok_or_else(|| Error::UnsupportedLanguage {
+                language: language.to_string(),
+            })?;
+
+        let config = GrammarConfig {
+            language: ts_language,
+            highlights_query,
+            injections_query,
+            locals_query,
+            textobjects_query: Self::textobjects_for(&normalized),
+        };
+        let grammar = CompiledGrammar::new(config).map_err(|e| Error::grammar(&normalized, e))?;
+        self.insert(normalized.into_owned(), grammar);
+        Ok(())
+    }
+
+    /// Register a grammar that isn't compiled in, under the name `language`.
+    ///
+    /// This is how grammars loaded at runtime, e.g. from a shared library
+    /// built by the tree-sitter CLI, get highlighted. A registered grammar
+    /// replaces the compiled-in one of the same name.
+    ///
+    /// Fails with [`Error::IncompatibleGrammar`] if the grammar was generated
+    /// for a tree-sitter ABI this build can't load, and with
+    /// [`Error::QueryError`] if its queries don't compile.
+    ///
+    /// ```rust,ignore
+    /// let language = unsafe { LanguageFn::from_raw(tree_sitter_foo) };
+    /// store.register_language("foo", GrammarConfig {
+    ///     language: language.into(),
+    ///     highlights_query: FOO_HIGHLIGHTS,
+    ///     injections_query: "",
+    ///     locals_query: "",
+    ///     textobjects_query: "",
+    /// })?;
+    /// ```
+    pub fn register_language(&self, language: &str, config: GrammarConfig<'_>) -> Result<(), Error> {
+        let normalized = Self::normalize_language(language);
+        let grammar = CompiledGrammar::with_query_cache(config, self.query_cache.as_ref())
+            .map_err(|e| Error::grammar(&normalized, e))?;
+        self.insert(normalized.into_owned(), grammar);
+        Ok(())
+    }
+
+    /// The grammars that failed to compile so far, sorted by language.
+    ///
+    /// Grammars are compiled on first use, so this only covers languages
+    /// that were asked for. A successful [`set_queries`](Self::set_queries)
+    /// or [`register_language`](Self::register_language) clears the
+    /// language's error.
+    pub fn init_errors(&self) -> Vec<(String, Arc<Error>)> {
+        let init_errors = self.init_errors.read().unwrap();
+        let mut errors: Vec<_> = init_errors
+            .iter()
+            .map(|(language, error)| (language.clone(), error.clone()))
+            .collect();
+        errors.sort_by(|a, b| a.0.cmp(&b.0));
+        errors
+    }
+
+    /// Cache `grammar` for `language`, replacing any grammar or error it had.
+    fn insert(&self, language: String, grammar: CompiledGrammar) {
+        self.init_errors.write().unwrap().remove(&language);
+        let mut grammars = self.grammars.write().unwrap();
+        grammars.insert(language, Arc::new(grammar));
+        self.generation.fetch_add(1, Ordering::Release);
+    }
+
+    /// Changes whenever [`set_queries`](Self::set_queries) or
+    /// [`register_language`](Self::register_language) replace a grammar.
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Whether a grammar for `language` (or one of its aliases) is enabled.
+    pub fn supports(language: &str) -> bool {
+        Self::language_for(&Self::normalize_language(language)).is_some()
+    }
+
     /// Normalize a language name to its canonical form.
-    fn normalize_language(language: &str) -> Cow<'_, str> {
+    pub(crate) fn normalize_language(language: &str) -> Cow<'_, str> {
         match language {
             // Aliases (generated from arborium.kdl)
 <% for (alias, canonical) in aliases { %>
@@ -95,9 +250,106 @@ impl GrammarStore {
         }
     }
 
-    /// Compile a grammar for a language.
+    /// Get the tree-sitter language for a canonical language name.
+    #[allow(unused_variables)]
+    fn language_for(language: &str) -> Option<Language> {
+        macro_rules! try_lang {
+            ($feature:literal, $module:ident, $primary:literal) => {
+                #[cfg(feature = $feature)]
+                if language == $primary {
+                    return Some(crate::$module::language().into());
+                }
+            };
+        }
+
+        // All languages (generated from arborium.kdl)
+<% for (feature, module, grammar_id) in languages { %>
+        try_lang!("<%= feature %>", <%= module %>, "<%= grammar_id %>");
+<% } %>
+
+        None
+    }
+
+    /// Get the compiled-in textobjects query for a canonical language name,
+    /// empty if it has none.
+    #[allow(unused_variables)]
+    fn textobjects_for(language: &str) -> &'static str {
+        macro_rules! try_lang {
+            ($feature:literal, $module:ident, $primary:literal) => {
+                #[cfg(feature = $feature)]
+                if language == $primary {
+                    return crate::$module::TEXTOBJECTS_QUERY;
+                }
+            };
+        }
+
+        // All languages (generated from arborium.kdl)
+<% for (feature, module, grammar_id) in languages { %>
+        try_lang!("<%= feature %>", <%= module %>, "<%= grammar_id %>");
+<% } %>
+
+        ""
+    }
+
+    /// The comment delimiters of a compiled-in language, from its
+    /// `arborium.yaml`, or `None` if it isn't enabled.
+    ///
+    /// Languages without comments, like JSON, have a [`CommentSyntax`] with
+    /// neither delimiter.
+    #[allow(unused_variables)]
+    pub fn comment_syntax(language: &str) -> Option<CommentSyntax> {
+        let language = Self::normalize_language(language);
+        macro_rules! try_lang {
+            ($feature:literal, $module:ident, $primary:literal) => {
+                #[cfg(feature = $feature)]
+                if language == $primary {
+                    return Some(CommentSyntax {
+                        line: crate::$module::LINE_COMMENT,
+                        block: crate::$module::BLOCK_COMMENT,
+                    });
+                }
+            };
+        }
+
+        // All languages (generated from arborium.kdl)
+<% for (feature, module, grammar_id) in languages { %>
+        try_lang!("<%= feature %>", <%= module %>, "<%= grammar_id %>");
+<% } %>
+
+        None
+    }
+
+    /// The bracket and auto-close pairs of a compiled-in language, from its
+    /// `arborium.yaml`, or `None` if it isn't enabled.
+    #[allow(unused_variables)]
+    pub fn pairs(language: &str) -> Option<Pairs> {
+        let language = Self::normalize_language(language);
+        macro_rules! try_lang {
+            ($feature:literal, $module:ident, $primary:literal) => {
+                #[cfg(feature = $feature)]
+                if language == $primary {
+                    return Some(Pairs {
+                        brackets: crate::$module::BRACKETS,
+                        auto_close: crate::$module::AUTO_CLOSE_PAIRS,
+                    });
+                }
+            };
+        }
+
+        // All languages (generated from arborium.kdl)
+<% for (feature, module, grammar_id) in languages { %>
+        try_lang!("<%= feature %>", <%= module %>, "<%= grammar_id %>");
+<% } %>
+
+        None
+    }
+
+    /// Compile a grammar for a language, or `None` if it isn't enabled.
     #[allow(unused_variables)]
-    fn compile_grammar(language: &str) -> Option<CompiledGrammar> {
+    fn compile_grammar(
+        language: &str,
+        cache: Option<&QueryCache>,
+    ) -> Option<Result<CompiledGrammar, GrammarError>> {
         macro_rules! try_lang {
             ($feature:literal, $module:ident, $primary:literal) => {
                 #[cfg(feature = $feature)]
@@ -107,8 +359,14 @@ impl GrammarStore {
                         highlights_query: &crate::$module::HIGHLIGHTS_QUERY,
                         injections_query: crate::$module::INJECTIONS_QUERY,
                         locals_query: crate::$module::LOCALS_QUERY,
+                        textobjects_query: crate::$module::TEXTOBJECTS_QUERY,
                     };
-                    return CompiledGrammar::new(config).ok();
+                    return Some(CompiledGrammar::with_precompiled(
+                        config,
+                        crate::$module::PRECOMPILED_HIGHLIGHTS,
+                        crate::$module::PRECOMPILED_INJECTIONS,
+                        cache,
+                    ));
                 }
             };
         }